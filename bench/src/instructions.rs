@@ -0,0 +1,286 @@
+//! Instruction builders for the program under test. Uses the crate's generated
+//! `accounts` and `instruction` types, so a changed account list fails to compile here
+//! instead of failing at runtime.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{self, get_associated_token_address};
+use anchor_spl::token;
+use universal_nft::constants::{AUDIT_ENTRY_SEED, AUDIT_LOG_SEED, BLOCKLIST_SEED, BUNDLE_TRANSFER_SEED, CHAIN_CONFIG_SEED, COLLECTION_LEDGER_SEED, CPI_ALLOWLIST_SEED, CROSS_CHAIN_CONFIG_SEED, CROSS_CHAIN_RECEIPT_SEED, CROSS_CHAIN_TRANSFER_SEED, FEE_RESERVE_SEED, FREEZE_AUTHORITY_SEED, INBOUND_MINT_SEED, INBOUND_NONCE_SEED, MINT_GUARD_SEED, MINT_LIMITS_SEED, NFT_METADATA_SEED, OPT_OUT_REGISTRY_SEED, OWNER_TRANSFER_INDEX_SEED, PENDING_TRANSFERS_SEED, PROGRAM_STATE_SEED, STAKE_RECORD_SEED, WALLET_MINT_COUNTER_SEED};
+use universal_nft_messages::chain_id;
+
+pub const SOLANA_CHAIN_ID: u64 = universal_nft::config::SELF_CHAIN_ID;
+pub const ETHEREUM_CHAIN_ID: u64 = chain_id::ETHEREUM;
+
+pub fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &universal_nft::ID).0
+}
+
+pub fn program_state() -> Pubkey {
+    pda(&[PROGRAM_STATE_SEED])
+}
+
+pub fn cross_chain_config() -> Pubkey {
+    pda(&[CROSS_CHAIN_CONFIG_SEED])
+}
+
+pub fn blocklist() -> Pubkey {
+    pda(&[BLOCKLIST_SEED])
+}
+
+pub fn opt_out_registry() -> Pubkey {
+    pda(&[OPT_OUT_REGISTRY_SEED])
+}
+
+pub fn audit_log() -> Pubkey {
+    pda(&[AUDIT_LOG_SEED])
+}
+
+pub fn cpi_allowlist() -> Pubkey {
+    pda(&[CPI_ALLOWLIST_SEED])
+}
+
+pub fn audit_entry(index: u64) -> Pubkey {
+    pda(&[AUDIT_ENTRY_SEED, &index.to_le_bytes()])
+}
+
+pub fn chain_config(chain_id: u64) -> Pubkey {
+    pda(&[CHAIN_CONFIG_SEED, &chain_id.to_le_bytes()])
+}
+
+pub fn nft_metadata(mint: &Pubkey) -> Pubkey {
+    pda(&[NFT_METADATA_SEED, mint.as_ref()])
+}
+
+pub fn collection_ledger(origin_chain_id: u64, origin_contract: &[u8]) -> Pubkey {
+    pda(&[COLLECTION_LEDGER_SEED, &origin_chain_id.to_le_bytes(), origin_contract])
+}
+
+pub fn inbound_mint(origin_chain_id: u64, origin_contract: &[u8], origin_token_id: &[u8; 32]) -> Pubkey {
+    pda(&[INBOUND_MINT_SEED, &origin_chain_id.to_le_bytes(), origin_contract, origin_token_id])
+}
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: universal_nft::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+pub fn initialize(authority: Pubkey, gateway: Pubkey, tss: Pubkey) -> Instruction {
+    build(
+        universal_nft::accounts::Initialize {
+            program_state: program_state(),
+            cross_chain_config: cross_chain_config(),
+            blocklist: blocklist(),
+            opt_out_registry: opt_out_registry(),
+            audit_log: audit_log(),
+            cpi_allowlist: cpi_allowlist(),
+            authority,
+            system_program: system_program::ID,
+        },
+        universal_nft::instruction::Initialize {
+            gateway_address: gateway,
+            tss_address: tss,
+            chain_id: SOLANA_CHAIN_ID,
+        },
+    )
+}
+
+/// `audit_index` is the audit log's current entry count
+pub fn register_chain(authority: Pubkey, chain_id: u64, audit_index: u64) -> Instruction {
+    build(
+        universal_nft::accounts::RegisterChain {
+            program_state: program_state(),
+            chain_config: chain_config(chain_id),
+            cpi_allowlist: cpi_allowlist(),
+            audit_log: audit_log(),
+            audit_entry: audit_entry(audit_index),
+            authority,
+            system_program: system_program::ID,
+            instructions_sysvar: sysvar::instructions::ID,
+        },
+        universal_nft::instruction::RegisterChain { chain_id },
+    )
+}
+
+pub fn mint_nft(authority: Pubkey, mint: Pubkey, metadata_uri: String) -> Instruction {
+    build(
+        universal_nft::accounts::MintNft {
+            program_state: program_state(),
+            blocklist: blocklist(),
+            collection_config: None,
+            freeze_authority: pda(&[FREEZE_AUTHORITY_SEED]),
+            mint_guard: pda(&[MINT_GUARD_SEED]),
+            fee_treasury: None,
+            mint_limits: pda(&[MINT_LIMITS_SEED]),
+            wallet_mint_counter: pda(&[WALLET_MINT_COUNTER_SEED, authority.as_ref()]),
+            mint,
+            token_account: get_associated_token_address(&authority, &mint),
+            nft_metadata: nft_metadata(&mint),
+            authority,
+            payer: authority,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        universal_nft::instruction::MintNft {
+            metadata_uri,
+            name: "Bench NFT".to_string(),
+            symbol: "BENCH".to_string(),
+            cross_chain_enabled: true,
+        },
+    )
+}
+
+pub fn verify_ownership(owner: Pubkey, mint: Pubkey) -> Instruction {
+    build(
+        universal_nft::accounts::VerifyOwnership {
+            program_state: program_state(),
+            nft_metadata: nft_metadata(&mint),
+            token_account: get_associated_token_address(&owner, &mint),
+            owner,
+            token_program: token::ID,
+        },
+        universal_nft::instruction::VerifyOwnership { token_mint: mint },
+    )
+}
+
+pub fn cross_chain_transfer(owner: Pubkey, mint: Pubkey, recipient_address: Vec<u8>, nonce: u64) -> Instruction {
+    build(
+        universal_nft::accounts::InitiateCrossChainTransfer {
+            program_state: program_state(),
+            cross_chain_config: cross_chain_config(),
+            blocklist: blocklist(),
+            opt_out_registry: opt_out_registry(),
+            destination_chain_config: chain_config(ETHEREUM_CHAIN_ID),
+            nft_metadata: nft_metadata(&mint),
+            collection_config: None,
+            collection_ledger: collection_ledger(SOLANA_CHAIN_ID, &[]),
+            transfer_record: pda(&[CROSS_CHAIN_TRANSFER_SEED, mint.as_ref(), &nonce.to_le_bytes()]),
+            owner_transfer_index: pda(&[OWNER_TRANSFER_INDEX_SEED, owner.as_ref()]),
+            pending_transfers: pda(&[PENDING_TRANSFERS_SEED]),
+            mint,
+            token_account: get_associated_token_address(&owner, &mint),
+            owner,
+            credential: None,
+            gate_token_account: None,
+            stake_record: pda(&[STAKE_RECORD_SEED, mint.as_ref()]),
+            localized_metadata: None,
+            fee_treasury: None,
+            payer_fee_account: None,
+            treasury_fee_account: None,
+            fee_reserve: pda(&[FEE_RESERVE_SEED]),
+            payer: owner,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        universal_nft::instruction::CrossChainTransfer {
+            destination_chain_id: ETHEREUM_CHAIN_ID,
+            recipient_address,
+            nonce,
+        },
+    )
+}
+
+pub fn cross_chain_transfer_batch(owner: Pubkey, mints: &[Pubkey], nonce: u64) -> Instruction {
+    let mut instruction = build(
+        universal_nft::accounts::InitiateBundleTransfer {
+            program_state: program_state(),
+            cross_chain_config: cross_chain_config(),
+            blocklist: blocklist(),
+            opt_out_registry: opt_out_registry(),
+            destination_chain_config: chain_config(ETHEREUM_CHAIN_ID),
+            collection_ledger: collection_ledger(SOLANA_CHAIN_ID, &[]),
+            collection_config: None,
+            bundle_record: pda(&[BUNDLE_TRANSFER_SEED, owner.as_ref(), &nonce.to_le_bytes()]),
+            owner,
+            owner_transfer_index: pda(&[OWNER_TRANSFER_INDEX_SEED, owner.as_ref()]),
+            pending_transfers: pda(&[PENDING_TRANSFERS_SEED]),
+            fee_reserve: pda(&[FEE_RESERVE_SEED]),
+            payer: owner,
+            credential: None,
+            gate_token_account: None,
+            fee_treasury: None,
+            payer_fee_account: None,
+            treasury_fee_account: None,
+            token_program: token::ID,
+            system_program: system_program::ID,
+            instructions_sysvar: sysvar::instructions::ID,
+        },
+        universal_nft::instruction::CrossChainTransferBatch {
+            destination_chain_id: ETHEREUM_CHAIN_ID,
+            recipient_address: vec![0x11; 20],
+            nonce,
+            item_count: mints.len() as u8,
+        },
+    );
+    for mint in mints {
+        instruction.accounts.push(AccountMeta::new_readonly(*mint, false));
+        instruction.accounts.push(AccountMeta::new_readonly(get_associated_token_address(&owner, mint), false));
+        instruction.accounts.push(AccountMeta::new(nft_metadata(mint), false));
+        instruction.accounts.push(AccountMeta::new(pda(&[STAKE_RECORD_SEED, mint.as_ref()]), false));
+    }
+    instruction
+}
+
+/// Inbound NFT from Ethereum; `token_id` keeps each delivery's mint distinct
+pub fn receive_cross_chain(
+    authority: Pubkey,
+    recipient: Pubkey,
+    token_id: u64,
+    metadata_uri: String,
+    tss_signature: Vec<u8>,
+) -> Instruction {
+    let origin_contract = vec![0x22; 20];
+    let mut origin_token_id = [0u8; 32];
+    origin_token_id[24..].copy_from_slice(&token_id.to_be_bytes());
+    let origin_tx_hash = [token_id as u8; 32];
+    let nonce = token_id;
+    let mint = inbound_mint(ETHEREUM_CHAIN_ID, &origin_contract, &origin_token_id);
+
+    build(
+        universal_nft::accounts::ReceiveCrossChain {
+            program_state: program_state(),
+            cross_chain_config: cross_chain_config(),
+            blocklist: blocklist(),
+            cpi_allowlist: cpi_allowlist(),
+            origin_chain_config: chain_config(ETHEREUM_CHAIN_ID),
+            receipt: pda(&[CROSS_CHAIN_RECEIPT_SEED, &origin_tx_hash, &nonce.to_le_bytes()]),
+            inbound_nonce: pda(&[INBOUND_NONCE_SEED, &ETHEREUM_CHAIN_ID.to_le_bytes()]),
+            mint,
+            freeze_authority: pda(&[FREEZE_AUTHORITY_SEED]),
+            token_account: get_associated_token_address(&recipient, &mint),
+            nft_metadata: nft_metadata(&mint),
+            collection_ledger: collection_ledger(ETHEREUM_CHAIN_ID, &origin_contract),
+            foreign_collection: None,
+            posted_vaa: None,
+            recipient,
+            recipient_deposit: None,
+            relayer_registration: None,
+            authority,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            instructions_sysvar: sysvar::instructions::ID,
+        },
+        universal_nft::instruction::ReceiveCrossChain {
+            origin_chain_id: ETHEREUM_CHAIN_ID,
+            origin_tx_hash,
+            origin_contract,
+            origin_token_id,
+            metadata_uri,
+            name: "Bench Inbound".to_string(),
+            symbol: "BENCH".to_string(),
+            original_owner: vec![0x33; 20],
+            intended_recipient: recipient,
+            tss_signature,
+            nonce,
+        },
+    )
+}
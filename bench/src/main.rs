@@ -0,0 +1,234 @@
+//! Compute unit benchmark for the Universal NFT program.
+//!
+//! Runs each instruction against the compiled SBF program inside
+//! `solana-program-test` across a range of payload sizes and prints the units
+//! consumed next to the 1.4M CU transaction ceiling. Build the program first:
+//!
+//! ```text
+//! anchor build
+//! cd bench && BPF_OUT_DIR=../target/deploy cargo run --release
+//! ```
+
+mod instructions;
+
+use instructions::ETHEREUM_CHAIN_ID;
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+
+/// Maximum compute units a single transaction may request
+const CU_CEILING: u64 = 1_400_000;
+
+struct Measurement {
+    instruction: &'static str,
+    payload: String,
+    units: u64,
+}
+
+struct Bench {
+    context: ProgramTestContext,
+    results: Vec<Measurement>,
+}
+
+impl Bench {
+    async fn start() -> Self {
+        let mut program_test = ProgramTest::new("universal_nft", universal_nft::ID, None);
+        // Native processors are not metered, so always load the compiled program
+        program_test.prefer_bpf(true);
+        Self {
+            context: program_test.start_with_context().await,
+            results: Vec::new(),
+        }
+    }
+
+    fn payer(&self) -> Pubkey {
+        self.context.payer.pubkey()
+    }
+
+    /// Simulates the instruction to read its CU usage, then executes it so
+    /// later measurements see the resulting state.
+    async fn run(&mut self, instruction: Instruction, signers: &[&Keypair]) -> u64 {
+        let blockhash = self
+            .context
+            .get_new_latest_blockhash()
+            .await
+            .expect("fetch blockhash");
+        let transaction = {
+            let mut all_signers = vec![&self.context.payer];
+            all_signers.extend_from_slice(signers);
+            Transaction::new_signed_with_payer(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(CU_CEILING as u32),
+                    instruction,
+                ],
+                Some(&self.context.payer.pubkey()),
+                &all_signers,
+                blockhash,
+            )
+        };
+
+        let simulation = self
+            .context
+            .banks_client
+            .simulate_transaction(transaction.clone())
+            .await
+            .expect("simulate transaction");
+        if let Some(Err(err)) = simulation.result {
+            let logs = simulation
+                .simulation_details
+                .map(|details| details.logs.join("\n"))
+                .unwrap_or_default();
+            panic!("simulation failed: {err}\n{logs}");
+        }
+        let units = simulation
+            .simulation_details
+            .map(|details| details.units_consumed)
+            .unwrap_or_default();
+
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .expect("process transaction");
+        units
+    }
+
+    async fn measure(&mut self, name: &'static str, payload: String, instruction: Instruction, signers: &[&Keypair]) {
+        let units = self.run(instruction, signers).await;
+        self.results.push(Measurement { instruction: name, payload, units });
+    }
+
+    async fn mint(&mut self, uri_len: usize) -> (Pubkey, u64) {
+        let mint = Keypair::new();
+        let instruction = instructions::mint_nft(self.payer(), mint.pubkey(), uri(uri_len));
+        let units = self.run(instruction, &[&mint]).await;
+        (mint.pubkey(), units)
+    }
+
+    fn report(&self) {
+        println!();
+        println!("{:<28} {:<24} {:>10} {:>9}", "instruction", "payload", "units", "ceiling");
+        println!("{}", "-".repeat(74));
+        for result in &self.results {
+            let share = result.units as f64 * 100.0 / CU_CEILING as f64;
+            println!(
+                "{:<28} {:<24} {:>10} {:>8.2}%",
+                result.instruction, result.payload, result.units, share
+            );
+        }
+
+        let worst = self.results.iter().max_by_key(|result| result.units);
+        if let Some(worst) = worst {
+            println!();
+            println!(
+                "Heaviest: {} ({}) at {} CU, {} CU headroom",
+                worst.instruction,
+                worst.payload,
+                worst.units,
+                CU_CEILING.saturating_sub(worst.units)
+            );
+        }
+    }
+}
+
+fn uri(len: usize) -> String {
+    let prefix = "https://example.com/";
+    let mut uri = prefix.to_string();
+    uri.extend(std::iter::repeat_n('a', len.saturating_sub(prefix.len())));
+    uri.truncate(len);
+    uri
+}
+
+#[tokio::main]
+async fn main() {
+    let mut bench = Bench::start().await;
+    let authority = bench.payer();
+
+    bench
+        .measure(
+            "initialize",
+            "-".to_string(),
+            instructions::initialize(authority, Pubkey::new_unique(), Pubkey::new_unique()),
+            &[],
+        )
+        .await;
+    bench
+        .measure(
+            "register_chain",
+            "-".to_string(),
+            instructions::register_chain(authority, ETHEREUM_CHAIN_ID, 0),
+            &[],
+        )
+        .await;
+
+    for uri_len in [20, 100, 200] {
+        let (_, units) = bench.mint(uri_len).await;
+        bench.results.push(Measurement {
+            instruction: "mint_nft",
+            payload: format!("uri {uri_len}B"),
+            units,
+        });
+    }
+
+    let (mint, _) = bench.mint(100).await;
+    bench
+        .measure(
+            "verify_ownership",
+            "-".to_string(),
+            instructions::verify_ownership(authority, mint),
+            &[],
+        )
+        .await;
+
+    for (nonce, recipient_len) in (1u64..).zip([20, 32, 64]) {
+        let (mint, _) = bench.mint(100).await;
+        bench
+            .measure(
+                "cross_chain_transfer",
+                format!("recipient {recipient_len}B"),
+                instructions::cross_chain_transfer(authority, mint, vec![0x11; recipient_len], nonce),
+                &[],
+            )
+            .await;
+    }
+
+    let mut token_id = 0u64;
+    for (uri_len, signature_len) in [(20, 64), (100, 64), (200, 64), (200, 128)] {
+        token_id += 1;
+        let recipient = Pubkey::new_unique();
+        bench
+            .measure(
+                "receive_cross_chain",
+                format!("uri {uri_len}B, sig {signature_len}B"),
+                instructions::receive_cross_chain(
+                    authority,
+                    recipient,
+                    token_id,
+                    uri(uri_len),
+                    vec![0x44; signature_len],
+                ),
+                &[],
+            )
+            .await;
+    }
+
+    for (nonce, batch_size) in (100u64..).zip([1usize, 5, 10]) {
+        let mut mints = Vec::with_capacity(batch_size);
+        for _ in 0..batch_size {
+            mints.push(bench.mint(100).await.0);
+        }
+        bench
+            .measure(
+                "cross_chain_transfer_batch",
+                format!("{batch_size} items"),
+                instructions::cross_chain_transfer_batch(authority, &mints, nonce),
+                &[],
+            )
+            .await;
+    }
+
+    bench.report();
+}
@@ -0,0 +1,278 @@
+import { Wallet } from '@coral-xyz/anchor';
+import { Connection, Keypair, PublicKey, TransactionInstruction } from '@solana/web3.js';
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { EnsAttestation, resolveRecipient } from './src/recipient';
+import { UniversalNftClient } from './src/client';
+import { ReplayedEvent, eventsToCsv } from './src/event-replay';
+import {
+  DEFAULT_LEDGER_PATH,
+  LedgerWallet,
+  SigningWallet,
+  proposeSquadsTransaction,
+  squadsVaultAddress,
+} from './src/signers';
+
+const USAGE = `Usage:
+  ts-node client/cli.ts resolve <recipient> <destination-chain-id> [--trust <attester>]...
+  ts-node client/cli.ts export-state <out.json> [program-id]
+  ts-node client/cli.ts replay-events <out.json|out.csv> [--until <signature>] [--program <id>]
+  ts-node client/cli.ts pause|resume [signer options]
+  ts-node client/cli.ts rotate-tss <new-tss> [signer options]
+  ts-node client/cli.ts withdraw-fees <amount> <recipient> [--mint <mint>] [signer options]
+
+<recipient> is a hex or base58 address, a .sol name, or a path to a JSON
+ENS attestation. Pass --trust once for each ENS attester to accept.
+export-state writes every account of the program to <out.json>.
+replay-events decodes every event since the program's first transaction, or
+since --until, oldest first. Full history needs an archival RPC node.
+
+Admin commands sign with the program authority. Signer options:
+  --keypair <path>      file wallet (default ~/.config/solana/id.json)
+  --ledger              sign on a Ledger instead of a file wallet
+  --ledger-path <path>  derivation path (default ${DEFAULT_LEDGER_PATH})
+  --squads <multisig>   the authority is this Squads multisig's vault; propose
+                        and approve as the --keypair or --ledger member
+  --program <id>        program ID (default the client's)
+RPC endpoint: SOLANA_RPC_URL (default devnet).`;
+
+function connect(): Connection {
+  return new Connection(
+    process.env.SOLANA_RPC_URL || 'https://api.devnet.solana.com',
+    'confirmed'
+  );
+}
+
+async function resolve(args: string[]) {
+  const [recipientArg, chainArg, ...rest] = args;
+  const destinationChainId = Number(chainArg);
+  if (!recipientArg || !Number.isInteger(destinationChainId)) {
+    throw new Error(USAGE);
+  }
+
+  const trustedEnsSigners: string[] = [];
+  for (let i = 0; i < rest.length; i += 2) {
+    if (rest[i] !== '--trust' || !rest[i + 1]) {
+      throw new Error(USAGE);
+    }
+    trustedEnsSigners.push(rest[i + 1]);
+  }
+
+  const recipient: string | EnsAttestation = recipientArg.endsWith('.json')
+    ? JSON.parse(fs.readFileSync(recipientArg, 'utf8'))
+    : recipientArg;
+
+  const address = await resolveRecipient(recipient, destinationChainId, connect(), trustedEnsSigners);
+  console.log(`0x${address.toString('hex')}`);
+}
+
+async function exportState(args: string[]) {
+  const [outPath, programId] = args;
+  if (!outPath) {
+    throw new Error(USAGE);
+  }
+
+  // Reads only, so any wallet will do
+  const wallet = new Wallet(Keypair.generate());
+  const client = programId
+    ? new UniversalNftClient(connect(), wallet, programId)
+    : new UniversalNftClient(connect(), wallet);
+  const snapshot = await client.exportState();
+  fs.writeFileSync(outPath, JSON.stringify(snapshot, null, 2));
+  console.log(`Exported ${snapshot.accounts.length} accounts at slot ${snapshot.slot} to ${outPath}`);
+}
+
+async function replayEventsCommand(args: string[]) {
+  const [outPath, ...rest] = args;
+  let until: string | undefined;
+  let programId: string | undefined;
+  for (let i = 0; i < rest.length; i += 2) {
+    if (!rest[i + 1]) {
+      throw new Error(USAGE);
+    }
+    if (rest[i] === '--until') {
+      until = rest[i + 1];
+    } else if (rest[i] === '--program') {
+      programId = rest[i + 1];
+    } else {
+      throw new Error(USAGE);
+    }
+  }
+  if (!outPath || !/\.(json|csv)$/.test(outPath)) {
+    throw new Error(USAGE);
+  }
+
+  // Reads only, so any wallet will do
+  const wallet = new Wallet(Keypair.generate());
+  const client = programId
+    ? new UniversalNftClient(connect(), wallet, programId)
+    : new UniversalNftClient(connect(), wallet);
+
+  const events: ReplayedEvent[] = [];
+  for await (const event of client.replayEvents({
+    until,
+    onProgress: (done, total) => process.stderr.write(`\r${done}/${total} transactions`),
+  })) {
+    events.push(event);
+  }
+  process.stderr.write('\n');
+
+  fs.writeFileSync(outPath, outPath.endsWith('.csv') ? eventsToCsv(events) : JSON.stringify(events, null, 2));
+  const last = events[events.length - 1];
+  console.log(`Wrote ${events.length} events to ${outPath}${last ? `; resume with --until ${last.signature}` : ''}`);
+}
+
+interface SignerOptions {
+  keypair?: string;
+  ledger: boolean;
+  ledgerPath: string;
+  squads?: PublicKey;
+  program?: string;
+}
+
+/** Split signer options from positional arguments and `--mint` */
+function parseAdminArgs(args: string[]): { positional: string[]; mint?: PublicKey; signer: SignerOptions } {
+  const positional: string[] = [];
+  const signer: SignerOptions = { ledger: false, ledgerPath: DEFAULT_LEDGER_PATH };
+  let mint: PublicKey | undefined;
+  for (let i = 0; i < args.length; i++) {
+    const value = () => {
+      if (!args[i + 1]) {
+        throw new Error(USAGE);
+      }
+      return args[++i];
+    };
+    switch (args[i]) {
+      case '--keypair': signer.keypair = value(); break;
+      case '--ledger': signer.ledger = true; break;
+      case '--ledger-path': signer.ledgerPath = value(); break;
+      case '--squads': signer.squads = new PublicKey(value()); break;
+      case '--program': signer.program = value(); break;
+      case '--mint': mint = new PublicKey(value()); break;
+      default: positional.push(args[i]);
+    }
+  }
+  if (signer.ledger && signer.keypair) {
+    throw new Error('Pass either --ledger or --keypair, not both');
+  }
+  return { positional, mint, signer };
+}
+
+async function loadSigner(options: SignerOptions): Promise<SigningWallet> {
+  if (options.ledger) {
+    const ledger = await LedgerWallet.connect(options.ledgerPath);
+    console.log(`Signing on Ledger as ${ledger.publicKey.toBase58()}`);
+    return ledger;
+  }
+  const keypairPath = options.keypair ?? path.join(os.homedir(), '.config', 'solana', 'id.json');
+  const secret = JSON.parse(fs.readFileSync(keypairPath, 'utf8'));
+  return new Wallet(Keypair.fromSecretKey(Uint8Array.from(secret)));
+}
+
+/**
+ * Run an admin action: send it directly, or with --squads propose it to the
+ * multisig, whose vault is the program authority
+ */
+async function runAdmin(
+  options: SignerOptions,
+  description: string,
+  build: (client: UniversalNftClient, authority: PublicKey) => Promise<TransactionInstruction>,
+  send: (client: UniversalNftClient) => Promise<string>
+) {
+  const signer = await loadSigner(options);
+  const connection = connect();
+  const client = options.program
+    ? new UniversalNftClient(connection, signer, options.program)
+    : new UniversalNftClient(connection, signer);
+
+  if (!options.squads) {
+    console.log(`${description}: ${await send(client)}`);
+    return;
+  }
+
+  const vault = squadsVaultAddress(options.squads);
+  const instruction = await build(client, vault);
+  const proposal = await proposeSquadsTransaction(connection, options.squads, signer, [instruction], description);
+  console.log(`Proposed "${description}" as Squads transaction ${proposal.transactionIndex}: ${proposal.signature}`);
+  console.log('Execute it before any other admin action, or it will target a stale audit entry.');
+}
+
+async function setPaused(args: string[], paused: boolean) {
+  const { positional, signer } = parseAdminArgs(args);
+  if (positional.length !== 0) {
+    throw new Error(USAGE);
+  }
+  await runAdmin(
+    signer,
+    paused ? 'Pause cross-chain transfers' : 'Resume cross-chain transfers',
+    (client, authority) => client.setPausedInstruction(paused, authority),
+    (client) => client.setPaused(paused)
+  );
+}
+
+async function rotateTss(args: string[]) {
+  const { positional, signer } = parseAdminArgs(args);
+  if (positional.length !== 1) {
+    throw new Error(USAGE);
+  }
+  const newTss = new PublicKey(positional[0]);
+  await runAdmin(
+    signer,
+    `Rotate TSS to ${newTss.toBase58()}`,
+    (client, authority) => client.rotateTssInstruction(newTss, authority),
+    (client) => client.rotateTss(newTss)
+  );
+}
+
+async function withdrawFees(args: string[]) {
+  const { positional, mint, signer } = parseAdminArgs(args);
+  const [amount, recipientArg] = positional;
+  if (positional.length !== 2 || !/^\d+$/.test(amount)) {
+    throw new Error(USAGE);
+  }
+  const recipient = new PublicKey(recipientArg);
+  await runAdmin(
+    signer,
+    `Withdraw ${amount} ${mint ? `of ${mint.toBase58()}` : 'lamports'} in fees to ${recipient.toBase58()}`,
+    (client, authority) => client.withdrawFeesInstruction(mint ?? null, amount, recipient, authority),
+    (client) => client.withdrawFees(mint ?? null, amount, recipient)
+  );
+}
+
+async function main() {
+  const [command, ...args] = process.argv.slice(2);
+  switch (command) {
+    case 'resolve':
+      await resolve(args);
+      break;
+    case 'export-state':
+      await exportState(args);
+      break;
+    case 'replay-events':
+      await replayEventsCommand(args);
+      break;
+    case 'pause':
+      await setPaused(args, true);
+      break;
+    case 'resume':
+      await setPaused(args, false);
+      break;
+    case 'rotate-tss':
+      await rotateTss(args);
+      break;
+    case 'withdraw-fees':
+      await withdrawFees(args);
+      break;
+    default:
+      console.log(USAGE);
+      process.exit(command ? 1 : 0);
+  }
+}
+
+if (require.main === module) {
+  main().catch((error) => {
+    console.error(`❌ ${error.message}`);
+    process.exit(1);
+  });
+}
@@ -5,25 +5,256 @@ import {
   Keypair, 
   SystemProgram,
   Transaction,
-  SYSVAR_RENT_PUBKEY 
+  TransactionInstruction,
+  ComputeBudgetProgram,
+  Ed25519Program,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  AccountMeta,
 } from '@solana/web3.js';
 import { 
   TOKEN_PROGRAM_ID, 
   ASSOCIATED_TOKEN_PROGRAM_ID,
   getAssociatedTokenAddress,
+  createAssociatedTokenAccountIdempotentInstruction,
 } from '@solana/spl-token';
 import { Program } from '@coral-xyz/anchor';
 import { UniversalNft, IDL } from '../../target/types/universal_nft';
-import { NftMetadata, CrossChainTransfer, CrossChainReceipt } from './types';
+import { createHash } from 'crypto';
+import {
+  NftMetadata,
+  NftAppData,
+  TrustedPeer,
+  SnapshotHolder,
+  CrossChainTransfer,
+  CrossChainReceipt,
+  ReceiptLookup,
+  CollectionLedger,
+  CollectionRoyalty,
+  RoyaltyShare,
+  CollectionActionArgs,
+  COLLECTION_ACTIONS,
+  CreatorSet,
+  SymbolReservation,
+  CollectionVerification,
+  DutchAuction,
+  Offer,
+  FractionVault,
+  StakeRecord,
+  Redemption,
+  TicketCheckIn,
+  LocalizedUri,
+  MetadataVariant,
+  DynamicMetadata,
+  MintGuard,
+  MintLimits,
+  WalletMintCount,
+  PendingTransfers,
+  ProgramVersion,
+  AuditEntry,
+  AuditAction,
+  AUDIT_ACTIONS,
+  TransferStatus,
+  TransferPreview,
+  BridgeAdapterKind,
+  CredentialRequirement,
+  TokenGate,
+  BridgeFee,
+  RelayerRequirement,
+  SweepTarget,
+  ValidationLimits,
+  NFT_METADATA_OFFSETS,
+  TRANSFER_RECORD_OFFSETS,
+} from './types';
+import { EnsAttestation, resolveRecipient } from './recipient';
+import { TransferPermit, transferPermitMessage } from './permit';
+import { SigningWallet } from './signers';
+import { TransferBuilder } from './transfer-builder';
+import { ReplayOptions, ReplayedEvent, replayEvents } from './event-replay';
+import {
+  EXPORT_PAGE_SIZE,
+  StateExport,
+  ExportedAccountRecord,
+  accountDiscriminator,
+  sha256Hex,
+  toJsonValue,
+} from './state-export';
+import {
+  COMPUTE_UNITS,
+  JITO_BLOCK_ENGINE_URL,
+  MAX_BUNDLE_TRANSACTIONS,
+  computeBudgetInstructions,
+  estimatePriorityFee,
+  getJitoTipAccounts,
+  jitoTipInstruction,
+  recommendedComputeUnits,
+  sendJitoBundle,
+} from './priority-fees';
+
+/** Compute unit limit the program requires on `receive_cross_chain` transactions */
+export const RECEIVE_COMPUTE_UNITS = COMPUTE_UNITS.receive_cross_chain;
+
+/** Compute unit limit the program requires on a bundle transfer of `items` NFTs */
+export function bundleComputeUnits(items: number): number {
+  return recommendedComputeUnits('cross_chain_transfer', items);
+}
+
+/** NFTs per `record_snapshot` transaction; two accounts each keeps it under the account limit without a lookup table */
+export const SNAPSHOT_CHUNK_SIZE = 10;
+
+/**
+ * Receipts key on a fixed 32-byte origin tx hash. Longer identifiers
+ * (e.g. 64-byte Solana signatures) are reduced with sha256, matching the program.
+ */
+export function normalizeOriginTxHash(identifier: Buffer): Buffer {
+  if (identifier.length === 32) {
+    return identifier;
+  }
+  return createHash('sha256').update(identifier).digest();
+}
+
+/**
+ * The hash `redeem_nft` records for a claim code; the code itself stays with
+ * the redeemer until they collect
+ */
+export function hashClaimCode(claimCode: string): Buffer {
+  return createHash('sha256').update(claimCode, 'utf8').digest();
+}
+
+/**
+ * The id of an oracle feed named like "SOL/USD": the sha256 of its name
+ */
+export function oracleFeedId(name: string): Buffer {
+  return createHash('sha256').update(name, 'utf8').digest();
+}
+
+/**
+ * Leading zero bits of the proof-of-work hash `mint_nft` checks for an open
+ * mint of `mint` by `authority`
+ */
+export function mintPowBits(programId: PublicKey, authority: PublicKey, mint: PublicKey): number {
+  const digest = createHash('sha256')
+    .update(Buffer.from("UNIVERSAL_NFT_MINT_POW"))
+    .update(programId.toBuffer())
+    .update(authority.toBuffer())
+    .update(mint.toBuffer())
+    .digest();
+  let bits = 0;
+  for (const byte of digest) {
+    if (byte !== 0) {
+      return bits + Math.clz32(byte) - 24;
+    }
+    bits += 8;
+  }
+  return bits;
+}
+
+/**
+ * Generate mint keypairs until one meets `difficulty` bits of proof of work.
+ * Each extra bit doubles the expected work.
+ */
+export function grindMintKeypair(programId: PublicKey, authority: PublicKey, difficulty: number): Keypair {
+  for (;;) {
+    const mint = Keypair.generate();
+    if (mintPowBits(programId, authority, mint.publicKey) >= difficulty) {
+      return mint;
+    }
+  }
+}
+
+/**
+ * The Borsh-encoded arguments of a collection action, as the program hashes
+ * them to match creator approvals
+ */
+export function encodeCollectionActionArgs(args: CollectionActionArgs): Buffer {
+  const u32 = (value: number) => {
+    const buffer = Buffer.alloc(4);
+    buffer.writeUInt32LE(value);
+    return buffer;
+  };
+  switch (args.action) {
+    case 'setBridging':
+      return Buffer.from([args.enabled ? 1 : 0]);
+    case 'setVelocityLimit':
+      return u32(args.dailyLimit);
+    case 'setRoyalty': {
+      const basisPoints = Buffer.alloc(2);
+      basisPoints.writeUInt16LE(args.basisPoints);
+      return Buffer.concat([
+        basisPoints,
+        u32(args.creators.length),
+        ...args.creators.map((creator) => Buffer.concat([creator.address.toBuffer(), Buffer.from([creator.share])])),
+      ]);
+    }
+    case 'setCreatorSet':
+      return Buffer.concat([
+        u32(args.creators.length),
+        ...args.creators.map((creator) => creator.toBuffer()),
+        Buffer.from([args.threshold]),
+      ]);
+  }
+}
+
+/**
+ * The hash creators approve a collection action by
+ */
+export function collectionActionArgsHash(args: CollectionActionArgs): Buffer {
+  return createHash('sha256').update(encodeCollectionActionArgs(args)).digest();
+}
+
+/** Lookalike characters a symbol's skeleton folds together, after upper-casing */
+const SYMBOL_CONFUSABLES: Record<string, string> = {
+  '0': 'O', 'О': 'O', 'Ο': 'O',
+  '1': 'I', 'L': 'I', '|': 'I', 'І': 'I', 'Ι': 'I', 'Ӏ': 'I',
+  '5': 'S', 'Ѕ': 'S',
+  'А': 'A', 'Α': 'A',
+  'В': 'B', 'Β': 'B',
+  'С': 'C', 'Ϲ': 'C',
+  'Е': 'E', 'Ε': 'E',
+  'Н': 'H', 'Η': 'H',
+  'Ј': 'J',
+  'К': 'K', 'Κ': 'K',
+  'М': 'M', 'Μ': 'M',
+  'Ν': 'N',
+  'Р': 'P', 'Ρ': 'P',
+  'Т': 'T', 'Τ': 'T',
+  'Х': 'X', 'Χ': 'X',
+  'У': 'Y', 'Υ': 'Y',
+  'Ζ': 'Z',
+};
+
+/**
+ * A symbol trimmed, upper-cased and with lookalike characters folded
+ * together, as the program compares reservations
+ */
+export function symbolSkeleton(symbol: string): string {
+  return [...symbol.trim()]
+    .map((c) => {
+      const code = c.codePointAt(0)!;
+      return code >= 0xff01 && code <= 0xff5e ? String.fromCodePoint(code - 0xfee0) : c;
+    })
+    .flatMap((c) => [...c.toUpperCase()])
+    .map((c) => SYMBOL_CONFUSABLES[c] ?? c)
+    .join('');
+}
+
+/**
+ * Seed of a symbol's reservation: the sha256 of its skeleton
+ */
+export function symbolReservationSeed(symbol: string): Buffer {
+  return createHash('sha256').update(symbolSkeleton(symbol), 'utf8').digest();
+}
 
 export class UniversalNftClient {
   public program: Program<UniversalNft>;
   public provider: anchor.AnchorProvider;
   public programId: PublicKey;
+  /** EVM addresses whose ENS attestations `resolveRecipient` accepts */
+  public trustedEnsSigners: string[] = [];
 
   constructor(
     connection: Connection,
-    wallet: anchor.Wallet,
+    wallet: SigningWallet,
     programId: string = "UnivNFT111111111111111111111111111111111111"
   ) {
     this.provider = new anchor.AnchorProvider(connection, wallet, {});
@@ -51,6 +282,16 @@ export class UniversalNftClient {
       this.programId
     );
 
+    const [blocklistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blocklist")],
+      this.programId
+    );
+
+    const [optOutRegistryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("opt_out_registry")],
+      this.programId
+    );
+
     const tx = await this.program.methods
       .initialize(
         new PublicKey(gatewayAddress),
@@ -60,6 +301,10 @@ export class UniversalNftClient {
       .accounts({
         programState: programStatePda,
         crossChainConfig: crossChainConfigPda,
+        blocklist: blocklistPda,
+        optOutRegistry: optOutRegistryPda,
+        auditLog: this.findAuditLogAddress(),
+        cpiAllowlist: this.findCpiAllowlistAddress(),
         authority: this.provider.wallet.publicKey,
         systemProgram: SystemProgram.programId,
       })
@@ -69,7 +314,8 @@ export class UniversalNftClient {
   }
 
   /**
-   * Mint a new NFT
+   * Mint a new NFT. When the mint guard asks for proof of work, generate
+   * `mintKeypair` with `mintKeypairFor`.
    */
   async mintNft(
     mintKeypair: Keypair,
@@ -103,10 +349,23 @@ export class UniversalNftClient {
       new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s")
     );
 
+    const [blocklistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blocklist")],
+      this.programId
+    );
+
+    const mintGuard = await this.getMintGuard();
+
     const tx = await this.program.methods
       .mintNft(metadataUri, name, symbol, crossChainEnabled)
       .accounts({
         programState: programStatePda,
+        blocklist: blocklistPda,
+        freezeAuthority: this.findFreezeAuthorityAddress(),
+        mintGuard: this.findMintGuardAddress(),
+        feeTreasury: mintGuard && mintGuard.feeLamports > 0 ? this.findFeeTreasuryAddress() : null,
+        mintLimits: this.findMintLimitsAddress(),
+        walletMintCounter: this.findWalletMintCounterAddress(recipient),
         mint: mintKeypair.publicKey,
         tokenAccount: tokenAccount,
         nftMetadata: nftMetadataPda,
@@ -125,6 +384,13 @@ export class UniversalNftClient {
     return tx;
   }
 
+  /**
+   * Start building an unsigned transfer of `mint`, with every account resolved
+   */
+  transfer(mint: PublicKey): TransferBuilder {
+    return new TransferBuilder(this, mint);
+  }
+
   /**
    * Initiate a cross-chain transfer
    */
@@ -132,8 +398,10 @@ export class UniversalNftClient {
     mint: PublicKey,
     owner: Keypair,
     destinationChainId: number,
-    recipientAddress: string,
-    gatewayAddress: string
+    recipientAddress: string | EnsAttestation, // Address, .sol name, or signed ENS resolution
+    gatewayAddress: string,
+    credential?: PublicKey,
+    payer?: Keypair // Funds rent and fees instead of the owner, e.g. a sponsor
   ): Promise<string> {
     const [programStatePda] = PublicKey.findProgramAddressSync(
       [Buffer.from("program_state")],
@@ -151,26 +419,35 @@ export class UniversalNftClient {
     );
 
     const nonce = Date.now();
-    const [transferRecordPda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("cross_chain_transfer"),
-        mint.toBytes(),
-        Buffer.from(nonce.toString().padStart(8, '0'))
-      ],
+    const transferRecordPda = this.findTransferRecordAddress(mint, nonce);
+
+    const tokenAccount = await getAssociatedTokenAddress(mint, owner.publicKey);
+
+    const recipientBytes = Array.from(await this.resolveRecipient(recipientAddress, destinationChainId));
+
+    const [blocklistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blocklist")],
       this.programId
     );
 
-    const tokenAccount = await getAssociatedTokenAddress(mint, owner.publicKey);
+    const [destinationChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("chain_config"), new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8)],
+      this.programId
+    );
 
-    // Convert recipient address to bytes based on chain
-    let recipientBytes: number[];
-    if (destinationChainId === 1 || destinationChainId === 56 || destinationChainId === 137 || destinationChainId === 1001) {
-      // EVM chains - expect hex address
-      const cleanAddress = recipientAddress.startsWith('0x') ? recipientAddress.slice(2) : recipientAddress;
-      recipientBytes = Array.from(Buffer.from(cleanAddress, 'hex'));
-    } else {
-      throw new Error(`Unsupported destination chain: ${destinationChainId}`);
-    }
+    const [optOutRegistryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("opt_out_registry")],
+      this.programId
+    );
+
+    const nftMetadata = await this.program.account.nftMetadata.fetch(nftMetadataPda);
+    const collectionLedgerPda = this.findCollectionLedgerAddress(
+      nftMetadata.originChainId.toNumber(),
+      Buffer.from(nftMetadata.originContract)
+    );
+    const collectionConfigPda = nftMetadata.collection.equals(PublicKey.default)
+      ? null
+      : this.findCollectionConfigAddress(nftMetadata.collection);
 
     const tx = await this.program.methods
       .crossChainTransfer(
@@ -181,35 +458,218 @@ export class UniversalNftClient {
       .accounts({
         programState: programStatePda,
         crossChainConfig: crossChainConfigPda,
+        blocklist: blocklistPda,
+        optOutRegistry: optOutRegistryPda,
+        destinationChainConfig: destinationChainConfigPda,
         nftMetadata: nftMetadataPda,
+        collectionConfig: collectionConfigPda,
+        collectionLedger: collectionLedgerPda,
         transferRecord: transferRecordPda,
         mint: mint,
         tokenAccount: tokenAccount,
         owner: owner.publicKey,
+        ownerTransferIndex: this.findOwnerTransferIndexAddress(owner.publicKey),
+        pendingTransfers: this.findPendingTransfersAddress(),
+        credential: credential ?? null,
+        gateTokenAccount: await this.findGateTokenAccount(owner.publicKey, destinationChainId),
+        stakeRecord: this.findStakeRecordAddress(mint),
+        localizedMetadata: await this.findLocalizedMetadataToCarry(mint),
+        ...(await this.findFeeAccounts((payer ?? owner).publicKey, destinationChainId)),
+        feeReserve: this.findFeeReserveAddress(),
+        payer: (payer ?? owner).publicKey,
         gatewayProgram: new PublicKey(gatewayAddress),
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
-      .signers([owner])
+      .signers(payer ? [owner, payer] : [owner])
       .rpc();
 
     return tx;
   }
 
+  /**
+   * Bytes the owner signs to let a sponsor submit `permit`. Nothing is sent.
+   */
+  async buildTransferPermit(permit: TransferPermit): Promise<Buffer> {
+    const config = await this.program.account.crossChainConfig.fetch(
+      PublicKey.findProgramAddressSync([Buffer.from("cross_chain_config")], this.programId)[0]
+    );
+    return transferPermitMessage(this.programId, config.chainId.toNumber(), permit);
+  }
+
+  /**
+   * Submit a transfer the owner authorized off-chain by signing
+   * `buildTransferPermit(permit)`. The sponsor signs the transaction and pays
+   * the fee and rent; the owner does not sign it at all.
+   */
+  async crossChainTransferWithPermit(
+    permit: TransferPermit,
+    signature: Uint8Array,
+    sponsor: Keypair,
+    credential?: PublicKey
+  ): Promise<string> {
+    const { mint, owner, destinationChainId, recipientAddress, nonce, deadline } = permit;
+    const nftMetadataPda = PublicKey.findProgramAddressSync(
+      [Buffer.from("nft_metadata"), mint.toBytes()],
+      this.programId
+    )[0];
+    const nftMetadata = await this.program.account.nftMetadata.fetch(nftMetadataPda);
+
+    return await this.program.methods
+      .crossChainTransferWithPermit(
+        new anchor.BN(destinationChainId),
+        recipientAddress,
+        new anchor.BN(nonce),
+        new anchor.BN(deadline)
+      )
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        crossChainConfig: PublicKey.findProgramAddressSync([Buffer.from("cross_chain_config")], this.programId)[0],
+        blocklist: PublicKey.findProgramAddressSync([Buffer.from("blocklist")], this.programId)[0],
+        optOutRegistry: PublicKey.findProgramAddressSync([Buffer.from("opt_out_registry")], this.programId)[0],
+        destinationChainConfig: PublicKey.findProgramAddressSync(
+          [Buffer.from("chain_config"), new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8)],
+          this.programId
+        )[0],
+        nftMetadata: nftMetadataPda,
+        collectionConfig: nftMetadata.collection.equals(PublicKey.default)
+          ? null
+          : this.findCollectionConfigAddress(nftMetadata.collection),
+        collectionLedger: this.findCollectionLedgerAddress(
+          nftMetadata.originChainId.toNumber(),
+          Buffer.from(nftMetadata.originContract)
+        ),
+        transferRecord: this.findTransferRecordAddress(mint, nonce),
+        ownerTransferIndex: this.findOwnerTransferIndexAddress(owner),
+        pendingTransfers: this.findPendingTransfersAddress(),
+        mint,
+        tokenAccount: await getAssociatedTokenAddress(mint, owner),
+        owner,
+        credential: credential ?? null,
+        gateTokenAccount: await this.findGateTokenAccount(owner, destinationChainId),
+        stakeRecord: this.findStakeRecordAddress(mint),
+        ...(await this.findFeeAccounts(sponsor.publicKey, destinationChainId)),
+        feeReserve: this.findFeeReserveAddress(),
+        payer: sponsor.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      // The program reads the owner's signature from the instruction right before its own
+      .preInstructions([
+        Ed25519Program.createInstructionWithPublicKey({
+          publicKey: owner.toBytes(),
+          message: await this.buildTransferPermit(permit),
+          signature,
+        }),
+      ])
+      .signers([sponsor])
+      .rpc();
+  }
+
+  /**
+   * Resolve a recipient for `destinationChainId` to the bytes a transfer sends:
+   * a hex or base58 address, a `.sol` name, or an ENS attestation signed by one
+   * of `trustedEnsSigners`. Throws if the result does not fit the destination.
+   */
+  async resolveRecipient(recipient: string | EnsAttestation, destinationChainId: number): Promise<Buffer> {
+    return resolveRecipient(recipient, destinationChainId, this.provider.connection, this.trustedEnsSigners);
+  }
+
+  /**
+   * Preview a cross-chain transfer without signing or changing any state. Returns
+   * the message hash the real transfer will emit and the rent it will cost.
+   * Throws the same program error the transfer would fail with.
+   */
+  async previewTransfer(
+    mint: PublicKey,
+    owner: PublicKey,
+    destinationChainId: number,
+    recipientAddress: Buffer,
+    nonce: number,
+    credential?: PublicKey
+  ): Promise<TransferPreview> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    const [crossChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cross_chain_config")],
+      this.programId
+    );
+    const [blocklistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blocklist")],
+      this.programId
+    );
+    const [optOutRegistryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("opt_out_registry")],
+      this.programId
+    );
+    const [destinationChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("chain_config"), new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8)],
+      this.programId
+    );
+    const [nftMetadataPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nft_metadata"), mint.toBytes()],
+      this.programId
+    );
+
+    const nftMetadata = await this.program.account.nftMetadata.fetch(nftMetadataPda);
+
+    const preview = await this.program.methods
+      .previewTransfer(new anchor.BN(destinationChainId), recipientAddress, new anchor.BN(nonce))
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: crossChainConfigPda,
+        blocklist: blocklistPda,
+        optOutRegistry: optOutRegistryPda,
+        destinationChainConfig: destinationChainConfigPda,
+        nftMetadata: nftMetadataPda,
+        collectionConfig: nftMetadata.collection.equals(PublicKey.default)
+          ? null
+          : this.findCollectionConfigAddress(nftMetadata.collection),
+        collectionLedger: this.findCollectionLedgerAddress(
+          nftMetadata.originChainId.toNumber(),
+          Buffer.from(nftMetadata.originContract)
+        ),
+        transferRecord: this.findTransferRecordAddress(mint, nonce),
+        mint,
+        tokenAccount: await getAssociatedTokenAddress(mint, owner),
+        owner,
+        ownerTransferIndex: this.findOwnerTransferIndexAddress(owner),
+        pendingTransfers: this.findPendingTransfersAddress(),
+        credential: credential ?? null,
+        gateTokenAccount: await this.findGateTokenAccount(owner, destinationChainId),
+        localizedMetadata: await this.findLocalizedMetadataToCarry(mint),
+      })
+      .view();
+
+    return {
+      messageHash: Buffer.from(preview.messageHash).toString('hex'),
+      rentLamports: preview.rentLamports.toString(),
+      transferRecord: preview.transferRecord.toString(),
+      feeAmount: preview.feeAmount.toString(),
+      feeMint: preview.feeMint ? preview.feeMint.toString() : null,
+      payloadBytes: preview.payloadBytes,
+    };
+  }
+
   /**
    * Receive a cross-chain NFT
    */
   async receiveCrossChainNft(
-    mintKeypair: Keypair,
     recipient: PublicKey,
     originChainId: number,
     originTxHash: string,
+    originContract: string,
+    originTokenId: anchor.BN,
     metadataUri: string,
     name: string,
     symbol: string,
     originalOwner: string,
     tssSignature: string,
-    tssAddress: string
+    tssAddress: string,
+    postedVaa?: PublicKey // Required when the origin chain uses the Wormhole adapter
   ): Promise<string> {
     const [programStatePda] = PublicKey.findProgramAddressSync(
       [Buffer.from("program_state")],
@@ -221,24 +681,28 @@ export class UniversalNftClient {
       this.programId
     );
 
+    const originContractBytes = Buffer.from(originContract.startsWith('0x') ? originContract.slice(2) : originContract, 'hex');
+    const originTokenIdBytes = originTokenId.toArrayLike(Buffer, "be", 32);
+    const mint = this.findInboundMintAddress(originChainId, originContractBytes, originTokenIdBytes);
+
     const tokenAccount = await getAssociatedTokenAddress(
-      mintKeypair.publicKey,
+      mint,
       recipient
     );
 
     const [nftMetadataPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("nft_metadata"), mintKeypair.publicKey.toBytes()],
+      [Buffer.from("nft_metadata"), mint.toBytes()],
       this.programId
     );
 
     const nonce = Date.now();
-    const originTxHashBytes = Buffer.from(originTxHash.startsWith('0x') ? originTxHash.slice(2) : originTxHash, 'hex');
+    const originTxHashBytes = normalizeOriginTxHash(Buffer.from(originTxHash.startsWith('0x') ? originTxHash.slice(2) : originTxHash, 'hex'));
     
     const [receiptPda] = PublicKey.findProgramAddressSync(
       [
         Buffer.from("cross_chain_receipt"),
         originTxHashBytes,
-        Buffer.from(nonce.toString().padStart(8, '0'))
+        new anchor.BN(nonce).toArrayLike(Buffer, "le", 8)
       ],
       this.programId
     );
@@ -247,7 +711,7 @@ export class UniversalNftClient {
       [
         Buffer.from("metadata"),
         new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s").toBytes(),
-        mintKeypair.publicKey.toBytes(),
+        mint.toBytes(),
       ],
       new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s")
     );
@@ -256,163 +720,4329 @@ export class UniversalNftClient {
     const originalOwnerBytes = Array.from(Buffer.from(originalOwner.startsWith('0x') ? originalOwner.slice(2) : originalOwner, 'hex'));
     const tssSignatureBytes = Array.from(Buffer.from(tssSignature.startsWith('0x') ? tssSignature.slice(2) : tssSignature, 'hex'));
 
+    const [blocklistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blocklist")],
+      this.programId
+    );
+
+    const [originChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("chain_config"), new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8)],
+      this.programId
+    );
+
+    const collectionLedgerPda = this.findCollectionLedgerAddress(originChainId, originContractBytes);
+    const [foreignCollectionPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("foreign_collection"),
+        new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8),
+        originContractBytes,
+      ],
+      this.programId
+    );
+    // Only registered collections have the account; the program requires it once it exists
+    const foreignCollectionInfo = await this.provider.connection.getAccountInfo(foreignCollectionPda);
+    // A recipient who has funded a rent deposit pays for their own accounts
+    const recipientDeposit = this.findRentDepositAddress(recipient);
+    const recipientDepositInfo = await this.provider.connection.getAccountInfo(recipientDeposit);
+
     const tx = await this.program.methods
       .receiveCrossChain(
         new anchor.BN(originChainId),
         Array.from(originTxHashBytes),
+        originContractBytes,
+        Array.from(originTokenIdBytes),
         metadataUri,
         name,
         symbol,
         originalOwnerBytes,
+        recipient,
         tssSignatureBytes,
         new anchor.BN(nonce)
       )
       .accounts({
         programState: programStatePda,
         crossChainConfig: crossChainConfigPda,
-        mint: mintKeypair.publicKey,
+        blocklist: blocklistPda,
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        originChainConfig: originChainConfigPda,
+        mint: mint,
+        freezeAuthority: this.findFreezeAuthorityAddress(),
         tokenAccount: tokenAccount,
         nftMetadata: nftMetadataPda,
         receipt: receiptPda,
+        inboundNonce: this.findInboundNonceAddress(originChainId),
+        collectionLedger: collectionLedgerPda,
+        foreignCollection: foreignCollectionInfo ? foreignCollectionPda : null,
+        postedVaa: postedVaa ?? null,
+        relayerRegistration: await this.findOwnRelayerRegistration(),
         metadataAccount: metadataAccount,
         payer: this.provider.wallet.publicKey,
         recipient: recipient,
+        recipientDeposit: recipientDepositInfo ? recipientDeposit : null,
         tssAuthority: new PublicKey(tssAddress),
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
         rent: SYSVAR_RENT_PUBKEY,
         metadataProgram: new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"),
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
       })
-      .signers([mintKeypair])
+      // The program rejects receives that do not request this much compute up front
+      .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: RECEIVE_COMPUTE_UNITS })])
       .rpc();
 
     return tx;
   }
 
   /**
-   * Verify NFT ownership
+   * Escrow an existing Metaplex NFT and receive a cross-chain capable stand-in
    */
-  async verifyOwnership(mint: PublicKey, owner: Keypair): Promise<string> {
+  async wrapExistingNft(originalMint: PublicKey, owner: Keypair, payer?: Keypair): Promise<string> {
     const [programStatePda] = PublicKey.findProgramAddressSync(
       [Buffer.from("program_state")],
       this.programId
     );
 
+    const [blocklistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blocklist")],
+      this.programId
+    );
+
+    const metadataProgram = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+    const [originalMetadata] = PublicKey.findProgramAddressSync(
+      [Buffer.from("metadata"), metadataProgram.toBytes(), originalMint.toBytes()],
+      metadataProgram
+    );
+
+    const [wrapEscrow] = PublicKey.findProgramAddressSync(
+      [Buffer.from("wrap_escrow"), originalMint.toBytes()],
+      this.programId
+    );
+
+    const mint = this.findWrappedMintAddress(originalMint);
+
     const [nftMetadataPda] = PublicKey.findProgramAddressSync(
       [Buffer.from("nft_metadata"), mint.toBytes()],
       this.programId
     );
 
-    const tokenAccount = await getAssociatedTokenAddress(mint, owner.publicKey);
-
     const tx = await this.program.methods
-      .verifyOwnership(mint)
+      .wrapExistingNft()
       .accounts({
         programState: programStatePda,
+        blocklist: blocklistPda,
+        originalMint: originalMint,
+        originalMetadata: originalMetadata,
+        ownerOriginalAccount: await getAssociatedTokenAddress(originalMint, owner.publicKey),
+        wrapEscrow: wrapEscrow,
+        mint: mint,
+        tokenAccount: await getAssociatedTokenAddress(mint, owner.publicKey),
         nftMetadata: nftMetadataPda,
-        tokenAccount: tokenAccount,
         owner: owner.publicKey,
+        payer: (payer ?? owner).publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
       })
-      .signers([owner])
+      .signers(payer ? [owner, payer] : [owner])
       .rpc();
 
     return tx;
   }
 
   /**
-   * Get NFT metadata
+   * Derive the universal stand-in mint for a wrapped Metaplex NFT
    */
-  async getNftMetadata(mint: PublicKey): Promise<NftMetadata | null> {
-    try {
-      const [nftMetadataPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("nft_metadata"), mint.toBytes()],
-        this.programId
-      );
+  findWrappedMintAddress(originalMint: PublicKey): PublicKey {
+    const [mint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("wrapped_mint"), originalMint.toBytes()],
+      this.programId
+    );
+    return mint;
+  }
 
-      const metadata = await this.program.account.nftMetadata.fetch(nftMetadataPda);
-      
-      return {
-        mint: metadata.mint.toString(),
-        originalOwner: metadata.originalOwner.toString(),
-        currentOwner: metadata.currentOwner.toString(),
-        metadataUri: metadata.metadataUri,
-        name: metadata.name,
-        symbol: metadata.symbol,
-        crossChainEnabled: metadata.crossChainEnabled,
-        isLocked: metadata.isLocked,
-        originChainId: metadata.originChainId.toString(),
-        creationTimestamp: metadata.creationTimestamp.toString(),
-      };
-    } catch (error) {
-      console.error("Error fetching NFT metadata:", error);
-      return null;
-    }
+  /**
+   * The owner's associated token account for the token gate that applies to
+   * `destinationChainId`, or null when bridging there is not gated
+   */
+  async findGateTokenAccount(owner: PublicKey, destinationChainId: number): Promise<PublicKey | null> {
+    const [chainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("chain_config"), new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8)],
+      this.programId
+    );
+    const [crossChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cross_chain_config")],
+      this.programId
+    );
+
+    const chainConfig = await this.program.account.chainConfig.fetch(chainConfigPda);
+    const gate = chainConfig.tokenGate
+      ?? (await this.program.account.crossChainConfig.fetch(crossChainConfigPda)).tokenGate;
+    return gate ? getAssociatedTokenAddress(gate.mint, owner, true) : null;
   }
 
   /**
-   * Get cross-chain transfer record
+   * Derive the collection-wide settings account for a collection mint
    */
-  async getCrossChainTransfer(mint: PublicKey, nonce: number): Promise<CrossChainTransfer | null> {
-    try {
-      const [transferRecordPda] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("cross_chain_transfer"),
-          mint.toBytes(),
-          Buffer.from(nonce.toString().padStart(8, '0'))
-        ],
-        this.programId
-      );
+  findCollectionConfigAddress(collectionMint: PublicKey): PublicKey {
+    const [config] = PublicKey.findProgramAddressSync(
+      [Buffer.from("collection_config"), collectionMint.toBytes()],
+      this.programId
+    );
+    return config;
+  }
 
-      const transfer = await this.program.account.crossChainTransfer.fetch(transferRecordPda);
-      
-      return {
-        mint: transfer.mint.toString(),
-        originalOwner: transfer.originalOwner.toString(),
-        destinationChainId: transfer.destinationChainId.toString(),
-        recipientAddress: Buffer.from(transfer.recipientAddress).toString('hex'),
-        nonce: transfer.nonce.toString(),
-        timestamp: transfer.timestamp.toString(),
-        status: transfer.status,
-      };
-    } catch (error) {
-      console.error("Error fetching cross-chain transfer:", error);
+  /**
+   * Derive the royalty settings account for a collection mint
+   */
+  findCollectionRoyaltyAddress(collectionMint: PublicKey): PublicKey {
+    const [royalty] = PublicKey.findProgramAddressSync(
+      [Buffer.from("collection_royalty"), collectionMint.toBytes()],
+      this.programId
+    );
+    return royalty;
+  }
+
+  /**
+   * Set the royalty sales of a collection's NFTs pay, split between up to 5
+   * creators whose shares add up to 100. The signer must be the collection's
+   * creator or the program authority, or a member of its active creator set
+   * once enough creators approved. Pass 0 and no creators to waive it.
+   */
+  async setCollectionRoyalty(collectionMint: PublicKey, basisPoints: number, creators: RoyaltyShare[]): Promise<string> {
+    return await this.program.methods
+      .setCollectionRoyalty(basisPoints, creators)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        collectionConfig: this.findCollectionConfigAddress(collectionMint),
+        collectionRoyalty: this.findCollectionRoyaltyAddress(collectionMint),
+        creatorSet: this.findCreatorSetAddress(collectionMint),
+        creatorApproval: await this.creatorApprovalFor(collectionMint, { action: 'setRoyalty', basisPoints, creators }),
+        signer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  /**
+   * Enable or disable bridging for a whole collection, with the same signer
+   * rules as `setCollectionRoyalty`
+   */
+  async setCollectionBridging(collectionMint: PublicKey, enabled: boolean): Promise<string> {
+    return await this.program.methods
+      .setCollectionBridging(enabled)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        collectionConfig: this.findCollectionConfigAddress(collectionMint),
+        creatorSet: this.findCreatorSetAddress(collectionMint),
+        creatorApproval: await this.creatorApprovalFor(collectionMint, { action: 'setBridging', enabled }),
+        signer: this.provider.wallet.publicKey,
+      })
+      .rpc();
+  }
+
+  /**
+   * Derive the creator set account for a collection mint
+   */
+  findCreatorSetAddress(collectionMint: PublicKey): PublicKey {
+    const [creatorSet] = PublicKey.findProgramAddressSync(
+      [Buffer.from("creator_set"), collectionMint.toBytes()],
+      this.programId
+    );
+    return creatorSet;
+  }
+
+  /**
+   * Derive the account gathering creator approvals for one collection action
+   * with exactly these arguments
+   */
+  findCreatorApprovalAddress(collectionMint: PublicKey, args: CollectionActionArgs): PublicKey {
+    const [approval] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("creator_approval"),
+        collectionMint.toBytes(),
+        Buffer.from([COLLECTION_ACTIONS.indexOf(args.action)]),
+        collectionActionArgsHash(args),
+      ],
+      this.programId
+    );
+    return approval;
+  }
+
+  async getCreatorSet(collectionMint: PublicKey): Promise<CreatorSet | null> {
+    const creatorSet = await this.program.account.creatorSet.fetchNullable(this.findCreatorSetAddress(collectionMint));
+    if (!creatorSet) {
       return null;
     }
+    return {
+      collectionMint: creatorSet.collectionMint.toString(),
+      creators: creatorSet.creators.map((creator: PublicKey) => creator.toString()),
+      threshold: creatorSet.threshold,
+      version: creatorSet.version,
+      updatedAt: creatorSet.updatedAt.toNumber(),
+    };
   }
 
   /**
-   * Get cross-chain receipt
+   * The approval account an action needs, or null while the collection has no
+   * active creator set
    */
-  async getCrossChainReceipt(originTxHash: string, nonce: number): Promise<CrossChainReceipt | null> {
-    try {
-      const originTxHashBytes = Buffer.from(originTxHash.startsWith('0x') ? originTxHash.slice(2) : originTxHash, 'hex');
-      
-      const [receiptPda] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("cross_chain_receipt"),
-          originTxHashBytes,
-          Buffer.from(nonce.toString().padStart(8, '0'))
-        ],
-        this.programId
-      );
+  private async creatorApprovalFor(collectionMint: PublicKey, args: CollectionActionArgs): Promise<PublicKey | null> {
+    const creatorSet = await this.getCreatorSet(collectionMint);
+    return creatorSet && creatorSet.threshold > 0 ? this.findCreatorApprovalAddress(collectionMint, args) : null;
+  }
 
-      const receipt = await this.program.account.crossChainReceipt.fetch(receiptPda);
-      
-      return {
-        originChainId: receipt.originChainId.toString(),
-        originTxHash: Buffer.from(receipt.originTxHash).toString('hex'),
-        mint: receipt.mint.toString(),
-        recipient: receipt.recipient.toString(),
-        originalOwner: Buffer.from(receipt.originalOwner).toString('hex'),
-        nonce: receipt.nonce.toString(),
-        timestamp: receipt.timestamp.toString(),
-        tssSignature: Buffer.from(receipt.tssSignature).toString('hex'),
-      };
-    } catch (error) {
-      console.error("Error fetching cross-chain receipt:", error);
-      return null;
+  /**
+   * Make `creators` jointly manage a collection, `threshold` of them approving
+   * each change with `approveCollectionAction`. Pass no creators and a
+   * threshold of 0 to hand control back to the collection's creator.
+   */
+  async setCreatorSet(collectionMint: PublicKey, creators: PublicKey[], threshold: number): Promise<string> {
+    return await this.program.methods
+      .setCreatorSet(creators, threshold)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        collectionConfig: this.findCollectionConfigAddress(collectionMint),
+        creatorSet: this.findCreatorSetAddress(collectionMint),
+        creatorApproval: await this.creatorApprovalFor(collectionMint, { action: 'setCreatorSet', creators, threshold }),
+        signer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  /**
+   * Approve a collection action with exactly these arguments as one of the
+   * collection's creators
+   */
+  async approveCollectionAction(collectionMint: PublicKey, args: CollectionActionArgs, creator: Keypair): Promise<string> {
+    return await this.program.methods
+      .approveCollectionAction({ [args.action]: {} } as any, [...collectionActionArgsHash(args)])
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        creatorSet: this.findCreatorSetAddress(collectionMint),
+        creatorApproval: this.findCreatorApprovalAddress(collectionMint, args),
+        creator: creator.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+  }
+
+  /**
+   * Derive the symbol registry switch
+   */
+  findSymbolRegistryAddress(): PublicKey {
+    const [symbolRegistry] = PublicKey.findProgramAddressSync(
+      [Buffer.from("symbol_registry")],
+      this.programId
+    );
+    return symbolRegistry;
+  }
+
+  /**
+   * Derive the reservation of a collection symbol; "bayc", "BAYC" and
+   * lookalikes such as "BAYС" (Cyrillic С) share one
+   */
+  findSymbolReservationAddress(symbol: string): PublicKey {
+    const [reservation] = PublicKey.findProgramAddressSync(
+      [Buffer.from("symbol_reservation"), symbolReservationSeed(symbol)],
+      this.programId
+    );
+    return reservation;
+  }
+
+  async getSymbolReservation(symbol: string): Promise<SymbolReservation | null> {
+    const reservation = await this.program.account.symbolReservation.fetchNullable(
+      this.findSymbolReservationAddress(symbol)
+    );
+    if (!reservation) {
+      return null;
+    }
+    return {
+      symbol: reservation.symbol,
+      creator: reservation.creator.toString(),
+      collectionMint: reservation.collectionMint.toString(),
+      reservedAt: reservation.reservedAt.toNumber(),
+    };
+  }
+
+  /**
+   * Reserve the symbol in a collection mint's metadata for the collection's
+   * creator. Fails with `SymbolReserved` when another creator holds it; the
+   * signer must be the collection's creator or the program authority.
+   */
+  async reserveCollectionSymbol(collectionMint: PublicKey): Promise<string> {
+    const [collectionMetadata] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nft_metadata"), collectionMint.toBytes()],
+      this.programId
+    );
+    const { symbol } = await this.program.account.nftMetadata.fetch(collectionMetadata);
+
+    return await this.program.methods
+      .reserveCollectionSymbol()
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        collectionConfig: this.findCollectionConfigAddress(collectionMint),
+        collectionMetadata,
+        symbolReservation: this.findSymbolReservationAddress(symbol),
+        signer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  /**
+   * Hand a symbol's reservation to another collection, or release it by
+   * passing the default public key as `creator` (admin only)
+   */
+  async reassignCollectionSymbol(symbol: string, creator: PublicKey, collectionMint: PublicKey): Promise<string> {
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    return await this.program.methods
+      .reassignCollectionSymbol(symbol, creator, collectionMint)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        symbolReservation: this.findSymbolReservationAddress(symbol),
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Require collections to reserve their symbol when registered (admin only)
+   */
+  async setSymbolRegistry(enabled: boolean): Promise<string> {
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    return await this.program.methods
+      .setSymbolRegistry(enabled)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        symbolRegistry: this.findSymbolRegistryAddress(),
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Derive the registration of a collection from another chain
+   */
+  findForeignCollectionAddress(originChainId: number, originContract: Buffer): PublicKey {
+    const [foreignCollection] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("foreign_collection"),
+        new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8),
+        originContract,
+      ],
+      this.programId
+    );
+    return foreignCollection;
+  }
+
+  /**
+   * Grant or revoke a collection's verified badge (admin only). Pass `foreign`
+   * to badge a collection registered from another chain.
+   */
+  async setCollectionVerified(
+    collectionMint: PublicKey,
+    verified: boolean,
+    foreign?: { originChainId: number; originContract: Buffer }
+  ): Promise<string> {
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    return await this.program.methods
+      .setCollectionVerified(collectionMint, verified)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        collectionConfig: foreign ? null : this.findCollectionConfigAddress(collectionMint),
+        foreignCollection: foreign ? this.findForeignCollectionAddress(foreign.originChainId, foreign.originContract) : null,
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * A collection's verified badge, read through `get_collection_verification`
+   * in one simulated call. Pass `foreign` for a collection registered from
+   * another chain.
+   */
+  async getCollectionVerification(
+    collectionMint: PublicKey,
+    foreign?: { originChainId: number; originContract: Buffer }
+  ): Promise<CollectionVerification> {
+    const result = await this.program.methods
+      .getCollectionVerification(collectionMint)
+      .accounts({
+        collectionConfig: foreign ? null : this.findCollectionConfigAddress(collectionMint),
+        foreignCollection: foreign ? this.findForeignCollectionAddress(foreign.originChainId, foreign.originContract) : null,
+      })
+      .view();
+    return {
+      collectionMint: result.collectionMint.toString(),
+      registered: result.registered,
+      verified: result.badge.verified,
+      updatedBy: result.badge.updatedBy.toString(),
+      updatedAt: result.badge.updatedAt.toNumber(),
+    };
+  }
+
+  findOperatorConfigAddress(): PublicKey {
+    const [operatorConfig] = PublicKey.findProgramAddressSync([Buffer.from("operator_config")], this.programId);
+    return operatorConfig;
+  }
+
+  /**
+   * The key the program authority delegated operational settings to, or null
+   * when none is set
+   */
+  async getOperator(): Promise<string | null> {
+    const operatorConfig = await this.program.account.operatorConfig.fetchNullable(this.findOperatorConfigAddress());
+    if (!operatorConfig || operatorConfig.operator.equals(PublicKey.default)) {
+      return null;
+    }
+    return operatorConfig.operator.toString();
+  }
+
+  /**
+   * Let `operator` set chain fees, execution bounties, payload caps, the mint
+   * guard and limits and staking rewards, keeping the authority key cold.
+   * `PublicKey.default` revokes the operator (authority only).
+   */
+  async setOperator(operator: PublicKey): Promise<string> {
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    return await this.program.methods
+      .setOperator(operator)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        operatorConfig: this.findOperatorConfigAddress(),
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Derive the reserve ledger for an origin collection. Solana-native NFTs use the
+   * Solana chain id with an empty contract.
+   */
+  findCollectionLedgerAddress(originChainId: number, originContract: Buffer): PublicKey {
+    const [ledger] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("collection_ledger"),
+        new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8),
+        originContract,
+      ],
+      this.programId
+    );
+    return ledger;
+  }
+
+  /**
+   * Derive the index of an owner's most recent outbound transfers
+   */
+  findOwnerTransferIndexAddress(owner: PublicKey): PublicKey {
+    const [index] = PublicKey.findProgramAddressSync(
+      [Buffer.from("owner_transfer_index"), owner.toBytes()],
+      this.programId
+    );
+    return index;
+  }
+
+  findPendingTransfersAddress(): PublicKey {
+    const [registry] = PublicKey.findProgramAddressSync([Buffer.from("pending_transfers")], this.programId);
+    return registry;
+  }
+
+  /**
+   * Derive the app data account of an NFT
+   */
+  findAppDataAddress(mint: PublicKey): PublicKey {
+    const [appData] = PublicKey.findProgramAddressSync(
+      [Buffer.from("app_data"), mint.toBytes()],
+      this.programId
+    );
+    return appData;
+  }
+
+  /**
+   * Derive the index of children equipped on a parent NFT
+   */
+  findChildIndexAddress(parentMint: PublicKey): PublicKey {
+    const [childIndex] = PublicKey.findProgramAddressSync(
+      [Buffer.from("child_index"), parentMint.toBytes()],
+      this.programId
+    );
+    return childIndex;
+  }
+
+  /**
+   * Derive the escrow that holds a child NFT while it is equipped
+   */
+  findChildEscrowAddress(childMint: PublicKey): PublicKey {
+    const [escrow] = PublicKey.findProgramAddressSync(
+      [Buffer.from("child_escrow"), childMint.toBytes()],
+      this.programId
+    );
+    return escrow;
+  }
+
+  /**
+   * Derive the Dutch auction listing an NFT
+   */
+  findDutchAuctionAddress(mint: PublicKey): PublicKey {
+    const [auction] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dutch_auction"), mint.toBytes()],
+      this.programId
+    );
+    return auction;
+  }
+
+  /**
+   * Derive the escrow that holds a Dutch-auctioned NFT until it sells
+   */
+  findAuctionEscrowAddress(mint: PublicKey): PublicKey {
+    const [escrow] = PublicKey.findProgramAddressSync(
+      [Buffer.from("auction_escrow"), mint.toBytes()],
+      this.programId
+    );
+    return escrow;
+  }
+
+  /**
+   * Derive a buyer's offer for an NFT
+   */
+  findOfferAddress(mint: PublicKey, buyer: PublicKey): PublicKey {
+    const [offer] = PublicKey.findProgramAddressSync(
+      [Buffer.from("offer"), mint.toBytes(), buyer.toBytes()],
+      this.programId
+    );
+    return offer;
+  }
+
+  /**
+   * Derive the delegate listing of an NFT
+   */
+  findDelegateListingAddress(mint: PublicKey): PublicKey {
+    const [listing] = PublicKey.findProgramAddressSync(
+      [Buffer.from("delegate_listing"), mint.toBytes()],
+      this.programId
+    );
+    return listing;
+  }
+
+  /**
+   * Derive the freeze authority of the NFTs the program mints
+   */
+  findFreezeAuthorityAddress(): PublicKey {
+    const [freezeAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("freeze_authority")],
+      this.programId
+    );
+    return freezeAuthority;
+  }
+
+  /**
+   * Derive the vault of a fractionalized NFT
+   */
+  findFractionVaultAddress(mint: PublicKey): PublicKey {
+    const [vault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fraction_vault"), mint.toBytes()],
+      this.programId
+    );
+    return vault;
+  }
+
+  /**
+   * Derive the SPL mint of a fractionalized NFT's fractions
+   */
+  findFractionMintAddress(mint: PublicKey): PublicKey {
+    const [fractionMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fraction_mint"), mint.toBytes()],
+      this.programId
+    );
+    return fractionMint;
+  }
+
+  /**
+   * Derive the escrow that holds a fractionalized NFT
+   */
+  findFractionEscrowAddress(mint: PublicKey): PublicKey {
+    const [escrow] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fraction_escrow"), mint.toBytes()],
+      this.programId
+    );
+    return escrow;
+  }
+
+  /**
+   * Derive the staking reward config
+   */
+  findRewardConfigAddress(): PublicKey {
+    const [rewardConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_config")],
+      this.programId
+    );
+    return rewardConfig;
+  }
+
+  /**
+   * Derive the SPL mint staking rewards are paid in
+   */
+  findRewardMintAddress(): PublicKey {
+    const [rewardMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_mint")],
+      this.programId
+    );
+    return rewardMint;
+  }
+
+  /**
+   * Derive the staking reward multiplier of a collection
+   */
+  findRewardMultiplierAddress(collectionMint: PublicKey): PublicKey {
+    const [multiplier] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_multiplier"), collectionMint.toBytes()],
+      this.programId
+    );
+    return multiplier;
+  }
+
+  /**
+   * Derive the stake record of an NFT
+   */
+  findStakeRecordAddress(mint: PublicKey): PublicKey {
+    const [stakeRecord] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stake_record"), mint.toBytes()],
+      this.programId
+    );
+    return stakeRecord;
+  }
+
+  /**
+   * Derive the PDA holding an NFT's localized metadata URIs
+   */
+  findLocalizedMetadataAddress(mint: PublicKey): PublicKey {
+    const [localizedMetadata] = PublicKey.findProgramAddressSync(
+      [Buffer.from("localized_metadata"), mint.toBytes()],
+      this.programId
+    );
+    return localizedMetadata;
+  }
+
+  /**
+   * The localized metadata a transfer of `mint` carries, or null if it has none
+   */
+  async findLocalizedMetadataToCarry(mint: PublicKey): Promise<PublicKey | null> {
+    const localizedMetadata = this.findLocalizedMetadataAddress(mint);
+    const account = await this.program.account.localizedMetadata.fetchNullable(localizedMetadata);
+    return account && account.entries.length > 0 ? localizedMetadata : null;
+  }
+
+  /**
+   * Derive the redemption record left when `mint` is burned by `redeemNft`
+   */
+  findRedemptionAddress(mint: PublicKey): PublicKey {
+    const [redemption] = PublicKey.findProgramAddressSync(
+      [Buffer.from("redemption"), mint.toBytes()],
+      this.programId
+    );
+    return redemption;
+  }
+
+  /**
+   * Derive the ticketing settings of a collection
+   */
+  findTicketConfigAddress(collectionMint: PublicKey): PublicKey {
+    const [ticketConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("ticket_config"), collectionMint.toBytes()],
+      this.programId
+    );
+    return ticketConfig;
+  }
+
+  /**
+   * Derive the record `checkIn` leaves once a ticket is used
+   */
+  findTicketCheckInAddress(mint: PublicKey): PublicKey {
+    const [ticketCheckIn] = PublicKey.findProgramAddressSync(
+      [Buffer.from("ticket_check_in"), mint.toBytes()],
+      this.programId
+    );
+    return ticketCheckIn;
+  }
+
+  /**
+   * Derive the mint guard PDA, which exists once `setMintGuard` has run
+   */
+  findMintGuardAddress(): PublicKey {
+    const [mintGuard] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint_guard")],
+      this.programId
+    );
+    return mintGuard;
+  }
+
+  /**
+   * Derive the mint limits PDA, which exists once `setMintLimits` has run
+   */
+  findMintLimitsAddress(): PublicKey {
+    const [mintLimits] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint_limits")],
+      this.programId
+    );
+    return mintLimits;
+  }
+
+  /**
+   * Derive the PDA counting `wallet`'s mints
+   */
+  findWalletMintCounterAddress(wallet: PublicKey): PublicKey {
+    const [walletMintCounter] = PublicKey.findProgramAddressSync(
+      [Buffer.from("wallet_mint_counter"), wallet.toBytes()],
+      this.programId
+    );
+    return walletMintCounter;
+  }
+
+  /**
+   * Derive the oracle feed PDA for `feedId`, see `oracleFeedId`
+   */
+  findOracleFeedAddress(feedId: Buffer): PublicKey {
+    const [oracleFeed] = PublicKey.findProgramAddressSync(
+      [Buffer.from("oracle_feed"), feedId],
+      this.programId
+    );
+    return oracleFeed;
+  }
+
+  /**
+   * Derive the PDA holding the metadata variants an oracle feed selects for `mint`
+   */
+  findDynamicMetadataAddress(mint: PublicKey): PublicKey {
+    const [dynamicMetadata] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dynamic_metadata"), mint.toBytes()],
+      this.programId
+    );
+    return dynamicMetadata;
+  }
+
+  /**
+   * Derive the PDA of `appProgram` that must sign its app data writes for `mint`
+   */
+  findAppDataAuthority(appProgram: PublicKey, mint: PublicKey): PublicKey {
+    const [authority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("universal_nft_app_data"), mint.toBytes()],
+      appProgram
+    );
+    return authority;
+  }
+
+  /**
+   * Derive the tracker of the last inbound nonce accepted from an origin chain
+   */
+  findInboundNonceAddress(originChainId: number): PublicKey {
+    const [inboundNonce] = PublicKey.findProgramAddressSync(
+      [Buffer.from("inbound_nonce"), new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8)],
+      this.programId
+    );
+    return inboundNonce;
+  }
+
+  /**
+   * Derive the Solana mint that a foreign NFT always maps to. The token id is
+   * encoded as a 32-byte big-endian integer, matching EVM uint256 token ids.
+   */
+  findInboundMintAddress(
+    originChainId: number,
+    originContract: Buffer,
+    originTokenId: Buffer
+  ): PublicKey {
+    const [mint] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("inbound_mint"),
+        new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8),
+        originContract,
+        originTokenId,
+      ],
+      this.programId
+    );
+    return mint;
+  }
+
+  /**
+   * Derive the registry entry of a game contract trusted to mint achievements
+   */
+  findTrustedPeerAddress(peerChainId: number, peerContract: Buffer): PublicKey {
+    const [trustedPeer] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("trusted_peer"),
+        new anchor.BN(peerChainId).toArrayLike(Buffer, "le", 8),
+        peerContract,
+      ],
+      this.programId
+    );
+    return trustedPeer;
+  }
+
+  /**
+   * Derive the mint of an achievement; each award ID mints once
+   */
+  findAchievementMintAddress(peerChainId: number, peerContract: Buffer, achievementId: Buffer): PublicKey {
+    const [mint] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("achievement_mint"),
+        new anchor.BN(peerChainId).toArrayLike(Buffer, "le", 8),
+        peerContract,
+        achievementId,
+      ],
+      this.programId
+    );
+    return mint;
+  }
+
+  /**
+   * Derive one chunk of a collection's holder snapshot
+   */
+  findSnapshotAddress(collectionMint: PublicKey, snapshotId: number, chunkIndex: number): PublicKey {
+    const [snapshot] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("holder_snapshot"),
+        collectionMint.toBytes(),
+        new anchor.BN(snapshotId).toArrayLike(Buffer, "le", 8),
+        new anchor.BN(chunkIndex).toArrayLike(Buffer, "le", 2),
+      ],
+      this.programId
+    );
+    return snapshot;
+  }
+
+  /**
+   * Derive the record of an outbound transfer
+   */
+  findTransferRecordAddress(mint: PublicKey, nonce: number): PublicKey {
+    const [record] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("cross_chain_transfer"),
+        mint.toBytes(),
+        new anchor.BN(nonce).toArrayLike(Buffer, "le", 8),
+      ],
+      this.programId
+    );
+    return record;
+  }
+
+  /**
+   * Submit the TSS attestation that an outbound transfer landed on its destination
+   * chain. Longer transaction identifiers are reduced to 32 bytes like origin tx hashes.
+   * `gasSpent` is the part of the transfer's gas budget delivery consumed.
+   */
+  async acknowledgeDelivery(
+    mint: PublicKey,
+    nonce: number,
+    destinationTxHash: Buffer,
+    gasSpent: number | string,
+    tssSignature: Buffer
+  ): Promise<string> {
+    const [crossChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cross_chain_config")],
+      this.programId
+    );
+
+    return await this.program.methods
+      .acknowledgeDelivery(
+        mint,
+        new anchor.BN(nonce),
+        Array.from(normalizeOriginTxHash(destinationTxHash)),
+        new anchor.BN(gasSpent),
+        tssSignature
+      )
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        crossChainConfig: crossChainConfigPda,
+        transferRecord: this.findTransferRecordAddress(mint, nonce),
+        pendingTransfers: this.findPendingTransfersAddress(),
+        feeReserve: this.findFeeReserveAddress(),
+        relayerRegistration: await this.findOwnRelayerRegistration(),
+        relayer: this.provider.wallet.publicKey,
+      })
+      .rpc();
+  }
+
+  /**
+   * Submit the TSS attestation that an outbound transfer failed, unlocking the NFT
+   * and returning any escrowed value to its owner
+   */
+  async acknowledgeFailure(
+    mint: PublicKey,
+    nonce: number,
+    tssSignature: Buffer
+  ): Promise<string> {
+    const [crossChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cross_chain_config")],
+      this.programId
+    );
+    const [nftMetadataPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nft_metadata"), mint.toBytes()],
+      this.programId
+    );
+
+    const transferRecordPda = this.findTransferRecordAddress(mint, nonce);
+    const transferRecord = await this.program.account.crossChainTransfer.fetch(transferRecordPda);
+    const nftMetadata = await this.program.account.nftMetadata.fetch(nftMetadataPda);
+
+    // Value escrow accounts are only needed when the transfer carried fungible value
+    const hasValue = !transferRecord.valueAmount.isZero();
+    const [valueEscrowPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("value_escrow"), transferRecordPda.toBytes()],
+      this.programId
+    );
+    const ownerValueAccount = hasValue
+      ? await getAssociatedTokenAddress(transferRecord.valueMint, transferRecord.originalOwner)
+      : null;
+
+    return await this.program.methods
+      .acknowledgeFailure(mint, new anchor.BN(nonce), tssSignature)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        crossChainConfig: crossChainConfigPda,
+        transferRecord: transferRecordPda,
+        nftMetadata: nftMetadataPda,
+        collectionLedger: this.findCollectionLedgerAddress(
+          nftMetadata.originChainId.toNumber(),
+          Buffer.from(nftMetadata.originContract)
+        ),
+        valueEscrow: hasValue ? valueEscrowPda : null,
+        ownerValueAccount,
+        originalOwner: transferRecord.originalOwner,
+        stakeRecord: this.findStakeRecordAddress(mint),
+        pendingTransfers: this.findPendingTransfersAddress(),
+        relayerRegistration: await this.findOwnRelayerRegistration(),
+        relayer: this.provider.wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+  }
+
+  /**
+   * Derive the program state, which also holds the event sequence counter
+   */
+  findProgramStateAddress(): PublicKey {
+    const [programState] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    return programState;
+  }
+
+  /**
+   * Derive the admin audit log head
+   */
+  findAuditLogAddress(): PublicKey {
+    const [auditLog] = PublicKey.findProgramAddressSync(
+      [Buffer.from("audit_log")],
+      this.programId
+    );
+    return auditLog;
+  }
+
+  /**
+   * Derive the audit entry at `index`. Admin instructions take the entry at the
+   * log's current `entryCount`.
+   */
+  findAuditEntryAddress(index: number): PublicKey {
+    const [entry] = PublicKey.findProgramAddressSync(
+      [Buffer.from("audit_entry"), new anchor.BN(index).toArrayLike(Buffer, "le", 8)],
+      this.programId
+    );
+    return entry;
+  }
+
+  /**
+   * Fetch every admin audit entry in order
+   */
+  async getAuditEntries(): Promise<AuditEntry[]> {
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+    const count = auditLog.entryCount.toNumber();
+    const addresses = Array.from({ length: count }, (_, index) => this.findAuditEntryAddress(index));
+    const entries = await this.program.account.auditEntry.fetchMultiple(addresses);
+
+    return entries.map((entry: any, index: number) => {
+      if (!entry) {
+        throw new Error(`Audit entry ${index} is missing`);
+      }
+      return {
+        index: entry.index.toNumber(),
+        action: Object.keys(entry.action)[0] as AuditAction,
+        actor: entry.actor.toString(),
+        detailsHash: Buffer.from(entry.detailsHash).toString('hex'),
+        prevHash: Buffer.from(entry.prevHash).toString('hex'),
+        entryHash: Buffer.from(entry.entryHash).toString('hex'),
+        timestamp: entry.timestamp.toString(),
+      };
+    });
+  }
+
+  /**
+   * Recompute the audit hash chain and check it ends at the on-chain head.
+   * Returns false if any entry was altered, reordered or dropped.
+   */
+  async verifyAuditLog(): Promise<boolean> {
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+    const entries = await this.getAuditEntries();
+
+    let prevHash = Buffer.alloc(32);
+    for (const entry of entries) {
+      if (entry.prevHash !== prevHash.toString('hex')) {
+        return false;
+      }
+      const entryHash = createHash('sha256')
+        .update(prevHash)
+        .update(new anchor.BN(entry.index).toArrayLike(Buffer, "le", 8))
+        .update(Buffer.from([AUDIT_ACTIONS.indexOf(entry.action)]))
+        .update(new PublicKey(entry.actor).toBuffer())
+        .update(Buffer.from(entry.detailsHash, 'hex'))
+        .update(new anchor.BN(entry.timestamp).toTwos(64).toArrayLike(Buffer, "le", 8))
+        .digest();
+      if (entryHash.toString('hex') !== entry.entryHash) {
+        return false;
+      }
+      prevHash = entryHash;
+    }
+
+    return prevHash.equals(Buffer.from(auditLog.headHash));
+  }
+
+  /**
+   * Choose the transport that carries messages to and from a registered chain
+   */
+  async setChainAdapter(chainId: number, adapter: BridgeAdapterKind): Promise<string> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    const [chainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("chain_config"), new anchor.BN(chainId).toArrayLike(Buffer, "le", 8)],
+      this.programId
+    );
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    const tx = await this.program.methods
+      .setChainAdapter(new anchor.BN(chainId), adapter)
+      .accounts({
+        programState: programStatePda,
+        chainConfig: chainConfigPda,
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Pause cross-chain automatically once `maxTransfers` outbound transfers land
+   * within `windowSlots` slots. Pass 0 for `maxTransfers` to disable.
+   */
+  async setCircuitBreaker(maxTransfers: number, windowSlots: number): Promise<string> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    const [crossChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cross_chain_config")],
+      this.programId
+    );
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    const tx = await this.program.methods
+      .setCircuitBreaker(new anchor.BN(maxTransfers), new anchor.BN(windowSlots))
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: crossChainConfigPda,
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Rotate the TSS address that inbound messages must be signed by
+   */
+  async rotateTss(newTss: PublicKey): Promise<string> {
+    return await this.provider.sendAndConfirm(new Transaction().add(await this.rotateTssInstruction(newTss)));
+  }
+
+  /**
+   * Pause or resume all cross-chain transfers
+   */
+  async setPaused(paused: boolean): Promise<string> {
+    return await this.provider.sendAndConfirm(new Transaction().add(await this.setPausedInstruction(paused)));
+  }
+
+  /**
+   * Build `rotate_tss` for `authority` to sign, e.g. as a multisig vault
+   * proposal. The audit entry is the next one at build time.
+   */
+  async rotateTssInstruction(
+    newTss: PublicKey,
+    authority: PublicKey = this.provider.wallet.publicKey
+  ): Promise<TransactionInstruction> {
+    return await this.program.methods
+      .rotateTss(newTss)
+      .accounts(await this.updateConfigAccounts(authority))
+      .instruction();
+  }
+
+  /**
+   * Build `set_paused` for `authority` to sign. The audit entry is the next
+   * one at build time.
+   */
+  async setPausedInstruction(
+    paused: boolean,
+    authority: PublicKey = this.provider.wallet.publicKey
+  ): Promise<TransactionInstruction> {
+    return await this.program.methods
+      .setPaused(paused)
+      .accounts(await this.updateConfigAccounts(authority))
+      .instruction();
+  }
+
+  private async updateConfigAccounts(authority: PublicKey) {
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+    return {
+      programState: this.findProgramStateAddress(),
+      crossChainConfig: PublicKey.findProgramAddressSync([Buffer.from("cross_chain_config")], this.programId)[0],
+      cpiAllowlist: this.findCpiAllowlistAddress(),
+      auditLog: this.findAuditLogAddress(),
+      auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+      authority,
+      systemProgram: SystemProgram.programId,
+      instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+    };
+  }
+
+  /**
+   * Require owners to present a credential account when bridging out, or pass
+   * null to lift the requirement
+   */
+  async setCredentialRequirement(requirement: CredentialRequirement | null): Promise<string> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    const [crossChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cross_chain_config")],
+      this.programId
+    );
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    const tx = await this.program.methods
+      .setCredentialRequirement(requirement)
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: crossChainConfigPda,
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Require owners to hold at least `minAmount` of `mint` to bridge out. Pass a
+   * chain ID to gate only that chain, overriding the global gate; pass null as
+   * the gate to remove it.
+   */
+  async setTokenGate(gate: TokenGate | null, chainId?: number): Promise<string> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+    const accounts = {
+      programState: programStatePda,
+      cpiAllowlist: this.findCpiAllowlistAddress(),
+      auditLog: this.findAuditLogAddress(),
+      auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+      authority: this.provider.wallet.publicKey,
+      systemProgram: SystemProgram.programId,
+      instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+    };
+    const args = gate ? { mint: gate.mint, minAmount: new anchor.BN(gate.minAmount) } : null;
+
+    if (chainId === undefined) {
+      const [crossChainConfigPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("cross_chain_config")],
+        this.programId
+      );
+      return this.program.methods
+        .setTokenGate(args)
+        .accounts({ ...accounts, crossChainConfig: crossChainConfigPda })
+        .rpc();
+    }
+
+    const [chainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("chain_config"), new anchor.BN(chainId).toArrayLike(Buffer, "le", 8)],
+      this.programId
+    );
+    return this.program.methods
+      .setChainTokenGate(new anchor.BN(chainId), args)
+      .accounts({ ...accounts, chainConfig: chainConfigPda })
+      .rpc();
+  }
+
+  /**
+   * Require relayers to bond `requirement.minBond` of `requirement.bondMint`
+   * (lamports when null) before calling receive and completion instructions;
+   * null lets anyone relay
+   */
+  async setRelayerRequirement(requirement: RelayerRequirement | null): Promise<string> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    const [crossChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cross_chain_config")],
+      this.programId
+    );
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+    const args = requirement
+      ? {
+          bondMint: requirement.bondMint,
+          minBond: new anchor.BN(requirement.minBond),
+          withdrawCooldownSeconds: requirement.withdrawCooldownSeconds,
+        }
+      : null;
+
+    return this.program.methods
+      .setRelayerRequirement(args)
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: crossChainConfigPda,
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Tune the length limits mint and receive validation enforce. Each must be
+   * non-zero and at most its value in `VALIDATION_LIMITS_CEILING`.
+   */
+  async setValidationLimits(limits: ValidationLimits): Promise<string> {
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    return this.program.methods
+      .setValidationLimits(limits)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        crossChainConfig: PublicKey.findProgramAddressSync(
+          [Buffer.from("cross_chain_config")],
+          this.programId
+        )[0],
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Trust a game contract on another chain to mint up to `mintQuota`
+   * achievement NFTs in total; 0 revokes it
+   */
+  async setTrustedPeer(peerChainId: number, peerContract: Buffer, mintQuota: number): Promise<string> {
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    return this.program.methods
+      .setTrustedPeer(new anchor.BN(peerChainId), peerContract, new anchor.BN(mintQuota))
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        trustedPeer: this.findTrustedPeerAddress(peerChainId, peerContract),
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Mint an achievement a trusted peer awarded to `recipient`, with the TSS
+   * signature over the `AchievementMint` message. The relayer wallet pays.
+   */
+  async mintAchievement(
+    peerChainId: number,
+    peerContract: Buffer,
+    achievementId: Buffer,
+    recipient: PublicKey,
+    metadataUri: string,
+    name: string,
+    symbol: string,
+    tssSignature: Buffer
+  ): Promise<string> {
+    const mint = this.findAchievementMintAddress(peerChainId, peerContract, achievementId);
+
+    return this.program.methods
+      .mintAchievement(
+        new anchor.BN(peerChainId),
+        peerContract,
+        Array.from(achievementId),
+        metadataUri,
+        name,
+        symbol,
+        tssSignature
+      )
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        crossChainConfig: PublicKey.findProgramAddressSync(
+          [Buffer.from("cross_chain_config")],
+          this.programId
+        )[0],
+        blocklist: PublicKey.findProgramAddressSync([Buffer.from("blocklist")], this.programId)[0],
+        trustedPeer: this.findTrustedPeerAddress(peerChainId, peerContract),
+        mint,
+        tokenAccount: await getAssociatedTokenAddress(mint, recipient),
+        nftMetadata: PublicKey.findProgramAddressSync(
+          [Buffer.from("nft_metadata"), mint.toBytes()],
+          this.programId
+        )[0],
+        recipient,
+        authority: this.provider.wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  /**
+   * Snapshot who holds every NFT of a collection that is on Solana and not
+   * locked, in chunks of `SNAPSHOT_CHUNK_SIZE`. The wallet must be the
+   * collection's creator or the program authority, and pays for the chunks.
+   */
+  async takeSnapshot(collectionMint: PublicKey, snapshotId: number): Promise<string[]> {
+    const nfts = await this.program.account.nftMetadata.all([
+      { memcmp: { offset: NFT_METADATA_OFFSETS.collection, bytes: collectionMint.toBase58() } },
+      { memcmp: { offset: NFT_METADATA_OFFSETS.isLocked, bytes: anchor.utils.bytes.bs58.encode([0]) } },
+    ]);
+
+    const pairs = [];
+    for (const { publicKey, account } of nfts) {
+      const largest = await this.provider.connection.getTokenLargestAccounts(account.mint);
+      const holder = largest.value.find((tokenAccount) => tokenAccount.amount === '1');
+      if (holder) {
+        pairs.push([
+          { pubkey: holder.address, isWritable: false, isSigner: false },
+          { pubkey: publicKey, isWritable: false, isSigner: false },
+        ]);
+      }
+    }
+
+    const signatures = [];
+    for (let start = 0; start < pairs.length; start += SNAPSHOT_CHUNK_SIZE) {
+      const chunkIndex = start / SNAPSHOT_CHUNK_SIZE;
+      signatures.push(
+        await this.program.methods
+          .recordSnapshot(new anchor.BN(snapshotId), chunkIndex)
+          .accounts({
+            programState: this.findProgramStateAddress(),
+            collectionConfig: PublicKey.findProgramAddressSync(
+              [Buffer.from("collection_config"), collectionMint.toBytes()],
+              this.programId
+            )[0],
+            snapshot: this.findSnapshotAddress(collectionMint, snapshotId, chunkIndex),
+            authority: this.provider.wallet.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(pairs.slice(start, start + SNAPSHOT_CHUNK_SIZE).flat())
+          .rpc()
+      );
+    }
+    return signatures;
+  }
+
+  /**
+   * Read back every chunk of a holder snapshot, in chunk order
+   */
+  async getSnapshot(collectionMint: PublicKey, snapshotId: number): Promise<SnapshotHolder[]> {
+    const holders: SnapshotHolder[] = [];
+    for (let chunkIndex = 0; ; chunkIndex++) {
+      let chunk;
+      try {
+        chunk = await this.program.account.holderSnapshot.fetch(
+          this.findSnapshotAddress(collectionMint, snapshotId, chunkIndex)
+        );
+      } catch (error) {
+        return holders;
+      }
+      holders.push(
+        ...chunk.holders.map((entry: any) => ({ mint: entry.mint.toString(), owner: entry.owner.toString() }))
+      );
+    }
+  }
+
+  /**
+   * Fetch a trusted peer's quota and mint count, or null if it was never trusted
+   */
+  async getTrustedPeer(peerChainId: number, peerContract: Buffer): Promise<TrustedPeer | null> {
+    let peer;
+    try {
+      peer = await this.program.account.trustedPeer.fetch(this.findTrustedPeerAddress(peerChainId, peerContract));
+    } catch (error) {
+      return null;
+    }
+    return {
+      chainId: peer.chainId.toString(),
+      contract: '0x' + Buffer.from(peer.contract).toString('hex'),
+      mintQuota: peer.mintQuota.toString(),
+      minted: peer.minted.toString(),
+      registeredAt: peer.registeredAt.toString(),
+    };
+  }
+
+
+  /**
+   * Derive the relayer registration for `owner`
+   */
+  findRelayerAddress(owner: PublicKey): PublicKey {
+    const [relayer] = PublicKey.findProgramAddressSync(
+      [Buffer.from("relayer"), owner.toBytes()],
+      this.programId
+    );
+    return relayer;
+  }
+
+  /**
+   * The wallet's relayer registration, or null if it has not registered
+   */
+  private async findOwnRelayerRegistration(): Promise<PublicKey | null> {
+    const relayer = this.findRelayerAddress(this.provider.wallet.publicKey);
+    return (await this.provider.connection.getAccountInfo(relayer)) ? relayer : null;
+  }
+
+  /**
+   * Register the wallet as a relayer, or add to its bond, in the configured bond mint
+   */
+  async registerRelayer(amount: number | string): Promise<string> {
+    const [crossChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cross_chain_config")],
+      this.programId
+    );
+    const config = await this.program.account.crossChainConfig.fetch(crossChainConfigPda);
+    const owner = this.provider.wallet.publicKey;
+    const relayer = this.findRelayerAddress(owner);
+    const bondMint: PublicKey | null = config.relayerRequirement?.bondMint ?? null;
+
+    const method = this.program.methods
+      .registerRelayer(new anchor.BN(amount))
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        crossChainConfig: crossChainConfigPda,
+        relayer,
+        ownerBondAccount: bondMint ? await getAssociatedTokenAddress(bondMint, owner) : null,
+        relayerBondAccount: bondMint ? await getAssociatedTokenAddress(bondMint, relayer, true) : null,
+        owner,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      });
+    if (!bondMint) {
+      return method.rpc();
+    }
+    // SPL bonds are held by the relayer account's associated token account
+    return method
+      .preInstructions([
+        createAssociatedTokenAccountIdempotentInstruction(
+          owner,
+          await getAssociatedTokenAddress(bondMint, relayer, true),
+          relayer,
+          bondMint
+        ),
+      ])
+      .rpc();
+  }
+
+  /**
+   * Withdraw the wallet's whole relayer bond once its cooldown has passed
+   */
+  async withdrawBond(): Promise<string> {
+    const [crossChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cross_chain_config")],
+      this.programId
+    );
+    const owner = this.provider.wallet.publicKey;
+    const relayer = this.findRelayerAddress(owner);
+    const registration = await this.program.account.relayer.fetch(relayer);
+    const bondMint: PublicKey | null = registration.bondMint;
+
+    return this.program.methods
+      .withdrawBond()
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        crossChainConfig: crossChainConfigPda,
+        relayer,
+        relayerBondAccount: bondMint ? await getAssociatedTokenAddress(bondMint, relayer, true) : null,
+        ownerBondAccount: bondMint ? await getAssociatedTokenAddress(bondMint, owner) : null,
+        owner,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+  }
+
+  /**
+   * Derive the rent deposit that pays for inbound accounts created for `owner`
+   */
+  findRentDepositAddress(owner: PublicKey): PublicKey {
+    const [rentDeposit] = PublicKey.findProgramAddressSync(
+      [Buffer.from("rent_deposit"), owner.toBytes()],
+      this.programId
+    );
+    return rentDeposit;
+  }
+
+  /**
+   * Add `amount` lamports to the wallet's rent deposit, opening it if needed
+   */
+  async depositRent(amount: number | string): Promise<string> {
+    const owner = this.provider.wallet.publicKey;
+    return await this.program.methods
+      .depositRent(new anchor.BN(amount))
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        rentDeposit: this.findRentDepositAddress(owner),
+        owner,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  /**
+   * Take `amount` unspent lamports back out of the wallet's rent deposit
+   */
+  async withdrawRentDeposit(amount: number | string): Promise<string> {
+    const owner = this.provider.wallet.publicKey;
+    return await this.program.methods
+      .withdrawRentDeposit(new anchor.BN(amount))
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        rentDeposit: this.findRentDepositAddress(owner),
+        owner,
+      })
+      .rpc();
+  }
+
+  /**
+   * Slash up to `amount` of a relayer's bond into the fee treasury. `evidenceHash`
+   * identifies the misbehavior in the audit log.
+   */
+  async slashRelayer(relayerOwner: PublicKey, amount: number | string, evidenceHash: Buffer): Promise<string> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    const relayer = this.findRelayerAddress(relayerOwner);
+    const registration = await this.program.account.relayer.fetch(relayer);
+    const bondMint: PublicKey | null = registration.bondMint;
+    const feeTreasury = this.findFeeTreasuryAddress();
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    return this.program.methods
+      .slashRelayer(relayerOwner, new anchor.BN(amount), Array.from(evidenceHash))
+      .accounts({
+        programState: programStatePda,
+        relayer,
+        feeTreasury,
+        relayerBondAccount: bondMint ? await getAssociatedTokenAddress(bondMint, relayer, true) : null,
+        treasuryBondAccount: bondMint ? await getAssociatedTokenAddress(bondMint, feeTreasury, true) : null,
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Charge a fee for each NFT bridged to `chainId`: lamports when `fee.mint` is
+   * null, otherwise base units of that SPL mint
+   */
+  async setChainFee(chainId: number, fee: BridgeFee): Promise<string> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    const [chainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("chain_config"), new anchor.BN(chainId).toArrayLike(Buffer, "le", 8)],
+      this.programId
+    );
+    const feeTreasury = this.findFeeTreasuryAddress();
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    const tx = await this.program.methods
+      .setChainFee(new anchor.BN(chainId), {
+        mint: fee.mint,
+        flatFee: new anchor.BN(fee.flatFee),
+        gasBudget: new anchor.BN(fee.gasBudget),
+      })
+      .accounts({
+        programState: programStatePda,
+        chainConfig: chainConfigPda,
+        feeTreasury,
+        feeMint: fee.mint,
+        treasuryFeeAccount: fee.mint ? await getAssociatedTokenAddress(fee.mint, feeTreasury, true) : null,
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Move collected fees out of the fee treasury to `recipient`: lamports when
+   * `mint` is null, otherwise tokens of `mint` to the recipient's associated
+   * token account
+   */
+  async withdrawFees(mint: PublicKey | null, amount: number | string, recipient: PublicKey): Promise<string> {
+    const instruction = await this.withdrawFeesInstruction(mint, amount, recipient);
+    return await this.provider.sendAndConfirm(new Transaction().add(instruction));
+  }
+
+  /**
+   * Build `withdraw_fees` for `authority` to sign, e.g. as a multisig vault
+   * proposal. The audit entry is the next one at build time.
+   */
+  async withdrawFeesInstruction(
+    mint: PublicKey | null,
+    amount: number | string,
+    recipient: PublicKey,
+    authority: PublicKey = this.provider.wallet.publicKey
+  ): Promise<TransactionInstruction> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    const feeTreasury = this.findFeeTreasuryAddress();
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    return await this.program.methods
+      .withdrawFees(mint, new anchor.BN(amount))
+      .accounts({
+        programState: programStatePda,
+        feeTreasury,
+        feeReserve: this.findFeeReserveAddress(),
+        treasuryFeeAccount: mint ? await getAssociatedTokenAddress(mint, feeTreasury, true) : null,
+        recipient,
+        recipientFeeAccount: mint ? await getAssociatedTokenAddress(mint, recipient, true) : null,
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .instruction();
+  }
+
+  /**
+   * Send the lamports `target` holds above rent exemption to `recipient`
+   */
+  async sweepSurplusLamports(target: SweepTarget, recipient: PublicKey): Promise<string> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    const seed = Object.keys(target)[0].replace(/[A-Z]/g, (c) => `_${c.toLowerCase()}`);
+    const [targetAccount] = PublicKey.findProgramAddressSync([Buffer.from(seed)], this.programId);
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    return await this.program.methods
+      .sweepSurplusLamports(target)
+      .accounts({
+        programState: programStatePda,
+        targetAccount,
+        recipient,
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Derive the staged unlock of a native NFT returning to Solana
+   */
+  findPendingReturnAddress(mint: PublicKey): PublicKey {
+    const [pendingReturn] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pending_return"), mint.toBytes()],
+      this.programId
+    );
+    return pendingReturn;
+  }
+
+  /**
+   * Submit the TSS attestation that a native NFT came back from `originChainId`.
+   * The NFT unlocks through `executeNativeReturn` once the origin chain's
+   * finality delay has passed. `stakedSeconds` is the staking time the
+   * attestation credits back, echoed from the outbound transfer's checkpoint.
+   */
+  async stageNativeReturn(
+    mint: PublicKey,
+    originChainId: number,
+    originTxHash: Buffer,
+    nonce: number,
+    recipient: PublicKey,
+    tssSignature: Buffer,
+    stakedSeconds: number = 0
+  ): Promise<string> {
+    const originTxHashBytes = normalizeOriginTxHash(originTxHash);
+    const [crossChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cross_chain_config")],
+      this.programId
+    );
+    const [originChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("chain_config"), new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8)],
+      this.programId
+    );
+    const [receiptPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("cross_chain_receipt"),
+        originTxHashBytes,
+        new anchor.BN(nonce).toArrayLike(Buffer, "le", 8)
+      ],
+      this.programId
+    );
+    const [nftMetadataPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nft_metadata"), mint.toBytes()],
+      this.programId
+    );
+    const [blocklistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blocklist")],
+      this.programId
+    );
+
+    return await this.program.methods
+      .stageNativeReturn(
+        mint,
+        new anchor.BN(originChainId),
+        Array.from(originTxHashBytes),
+        new anchor.BN(nonce),
+        recipient,
+        new anchor.BN(stakedSeconds),
+        tssSignature
+      )
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        crossChainConfig: crossChainConfigPda,
+        blocklist: blocklistPda,
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        originChainConfig: originChainConfigPda,
+        receipt: receiptPda,
+        inboundNonce: this.findInboundNonceAddress(originChainId),
+        nftMetadata: nftMetadataPda,
+        pendingReturn: this.findPendingReturnAddress(mint),
+        relayerRegistration: await this.findOwnRelayerRegistration(),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Unlock a staged native return once its finality delay has passed
+   */
+  async executeNativeReturn(mint: PublicKey): Promise<string> {
+    const [crossChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cross_chain_config")],
+      this.programId
+    );
+    const [nftMetadataPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nft_metadata"), mint.toBytes()],
+      this.programId
+    );
+    const pendingReturn = this.findPendingReturnAddress(mint);
+    const staged = await this.program.account.pendingReturn.fetch(pendingReturn);
+    const nftMetadata = await this.program.account.nftMetadata.fetch(nftMetadataPda);
+
+    return await this.program.methods
+      .executeNativeReturn(mint)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        crossChainConfig: crossChainConfigPda,
+        pendingReturn,
+        nftMetadata: nftMetadataPda,
+        stakeRecord: this.findStakeRecordAddress(mint),
+        collectionLedger: this.findCollectionLedgerAddress(
+          nftMetadata.originChainId.toNumber(),
+          Buffer.from(nftMetadata.originContract)
+        ),
+        payer: staged.payer,
+      })
+      .rpc();
+  }
+
+  /**
+   * Drop a staged native return before it executes; the NFT stays locked
+   */
+  async cancelNativeReturn(mint: PublicKey): Promise<string> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    const pendingReturn = this.findPendingReturnAddress(mint);
+    const staged = await this.program.account.pendingReturn.fetch(pendingReturn);
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    return await this.program.methods
+      .cancelNativeReturn(mint)
+      .accounts({
+        programState: programStatePda,
+        pendingReturn,
+        payer: staged.payer,
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Hold native NFTs returning from `chainId` for `delaySeconds` before they unlock
+   */
+  async setChainFinalityDelay(chainId: number, delaySeconds: number): Promise<string> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    const [chainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("chain_config"), new anchor.BN(chainId).toArrayLike(Buffer, "le", 8)],
+      this.programId
+    );
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    return await this.program.methods
+      .setChainFinalityDelay(new anchor.BN(chainId), delaySeconds)
+      .accounts({
+        programState: programStatePda,
+        chainConfig: chainConfigPda,
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Derive the staged optimistic receive for an origin transaction and nonce
+   */
+  findOptimisticReceiveAddress(originTxHash: string, nonce: number): PublicKey {
+    const originTxHashBytes = normalizeOriginTxHash(Buffer.from(originTxHash.startsWith('0x') ? originTxHash.slice(2) : originTxHash, 'hex'));
+    const [optimisticReceive] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("optimistic_receive"),
+        originTxHashBytes,
+        new anchor.BN(nonce).toArrayLike(Buffer, "le", 8)
+      ],
+      this.programId
+    );
+    return optimisticReceive;
+  }
+
+  /**
+   * Stage an inbound NFT without a TSS signature (program authority or a
+   * bonded watcher). It can be claimed once the origin chain's challenge
+   * window has closed.
+   */
+  async stageOptimisticReceive(
+    recipient: PublicKey,
+    originChainId: number,
+    originTxHash: string,
+    originContract: string,
+    originTokenId: anchor.BN,
+    metadataUri: string,
+    name: string,
+    symbol: string,
+    originalOwner: string,
+    nonce: number
+  ): Promise<string> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    const [crossChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cross_chain_config")],
+      this.programId
+    );
+    const [blocklistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blocklist")],
+      this.programId
+    );
+    const [originChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("chain_config"), new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8)],
+      this.programId
+    );
+    const originTxHashBytes = normalizeOriginTxHash(Buffer.from(originTxHash.startsWith('0x') ? originTxHash.slice(2) : originTxHash, 'hex'));
+    const originContractBytes = Buffer.from(originContract.startsWith('0x') ? originContract.slice(2) : originContract, 'hex');
+    const originalOwnerBytes = Buffer.from(originalOwner.startsWith('0x') ? originalOwner.slice(2) : originalOwner, 'hex');
+    const watcherPda = this.findWatcherAddress(this.provider.wallet.publicKey);
+    const watcherInfo = await this.provider.connection.getAccountInfo(watcherPda);
+
+    return await this.program.methods
+      .stageOptimisticReceive(
+        new anchor.BN(originChainId),
+        Array.from(originTxHashBytes),
+        originContractBytes,
+        Array.from(originTokenId.toArrayLike(Buffer, "be", 32)),
+        metadataUri,
+        name,
+        symbol,
+        originalOwnerBytes,
+        recipient,
+        new anchor.BN(nonce)
+      )
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: crossChainConfigPda,
+        blocklist: blocklistPda,
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        originChainConfig: originChainConfigPda,
+        optimisticReceive: this.findOptimisticReceiveAddress(originTxHash, nonce),
+        inboundNonce: this.findInboundNonceAddress(originChainId),
+        submitterWatcher: watcherInfo ? watcherPda : null,
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Mint a staged optimistic receive to its recipient after the challenge window
+   */
+  async claimOptimisticReceive(originTxHash: string, nonce: number): Promise<string> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    const [crossChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cross_chain_config")],
+      this.programId
+    );
+    const [blocklistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blocklist")],
+      this.programId
+    );
+    const optimisticReceive = this.findOptimisticReceiveAddress(originTxHash, nonce);
+    const staged = await this.program.account.optimisticReceive.fetch(optimisticReceive);
+    const originChainId = staged.originChainId.toNumber();
+    const originContractBytes = Buffer.from(staged.originContract);
+    const mint = this.findInboundMintAddress(originChainId, originContractBytes, Buffer.from(staged.originTokenId));
+    const [nftMetadataPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nft_metadata"), mint.toBytes()],
+      this.programId
+    );
+    const [receiptPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("cross_chain_receipt"),
+        Buffer.from(staged.originTxHash),
+        new anchor.BN(nonce).toArrayLike(Buffer, "le", 8)
+      ],
+      this.programId
+    );
+    const [foreignCollectionPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("foreign_collection"),
+        new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8),
+        originContractBytes,
+      ],
+      this.programId
+    );
+    const foreignCollectionInfo = await this.provider.connection.getAccountInfo(foreignCollectionPda);
+
+    return await this.program.methods
+      .claimOptimisticReceive(staged.originTxHash, new anchor.BN(nonce))
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: crossChainConfigPda,
+        blocklist: blocklistPda,
+        optimisticReceive,
+        receipt: receiptPda,
+        mint,
+        freezeAuthority: this.findFreezeAuthorityAddress(),
+        tokenAccount: await getAssociatedTokenAddress(mint, staged.recipient),
+        nftMetadata: nftMetadataPda,
+        collectionLedger: this.findCollectionLedgerAddress(originChainId, originContractBytes),
+        foreignCollection: foreignCollectionInfo ? foreignCollectionPda : null,
+        recipient: staged.recipient,
+        authority: this.provider.wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Cancel a pending optimistic receive with a TSS-signed fraud proof
+   */
+  async cancelOptimisticReceive(originTxHash: string, nonce: number, tssSignature: Buffer): Promise<string> {
+    const [crossChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cross_chain_config")],
+      this.programId
+    );
+    const optimisticReceive = this.findOptimisticReceiveAddress(originTxHash, nonce);
+    const staged = await this.program.account.optimisticReceive.fetch(optimisticReceive);
+
+    return await this.program.methods
+      .cancelOptimisticReceive(staged.originTxHash, new anchor.BN(nonce), tssSignature)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        crossChainConfig: crossChainConfigPda,
+        optimisticReceive,
+        challenger: this.provider.wallet.publicKey,
+      })
+      .rpc();
+  }
+
+  /**
+   * Derive the staged inbound message for an origin transaction and nonce
+   */
+  findStagedInboundAddress(originTxHash: string, nonce: number): PublicKey {
+    const originTxHashBytes = normalizeOriginTxHash(Buffer.from(originTxHash.startsWith('0x') ? originTxHash.slice(2) : originTxHash, 'hex'));
+    const [stagedInbound] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("staged_inbound"),
+        originTxHashBytes,
+        new anchor.BN(nonce).toArrayLike(Buffer, "le", 8)
+      ],
+      this.programId
+    );
+    return stagedInbound;
+  }
+
+  /**
+   * Verify an inbound message and stage it so anyone can execute it, for
+   * delivery when the primary relayer is offline
+   */
+  async stageInboundMessage(
+    recipient: PublicKey,
+    originChainId: number,
+    originTxHash: string,
+    originContract: string,
+    originTokenId: anchor.BN,
+    metadataUri: string,
+    name: string,
+    symbol: string,
+    originalOwner: string,
+    tssSignature: Buffer,
+    nonce: number,
+    postedVaa?: PublicKey // Required when the origin chain uses the Wormhole adapter
+  ): Promise<string> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    const [crossChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cross_chain_config")],
+      this.programId
+    );
+    const [blocklistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blocklist")],
+      this.programId
+    );
+    const [originChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("chain_config"), new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8)],
+      this.programId
+    );
+    const originTxHashBytes = normalizeOriginTxHash(Buffer.from(originTxHash.startsWith('0x') ? originTxHash.slice(2) : originTxHash, 'hex'));
+    const originContractBytes = Buffer.from(originContract.startsWith('0x') ? originContract.slice(2) : originContract, 'hex');
+    const originalOwnerBytes = Buffer.from(originalOwner.startsWith('0x') ? originalOwner.slice(2) : originalOwner, 'hex');
+
+    return await this.program.methods
+      .stageInboundMessage(
+        new anchor.BN(originChainId),
+        Array.from(originTxHashBytes),
+        originContractBytes,
+        Array.from(originTokenId.toArrayLike(Buffer, "be", 32)),
+        metadataUri,
+        name,
+        symbol,
+        originalOwnerBytes,
+        recipient,
+        tssSignature,
+        new anchor.BN(nonce)
+      )
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: crossChainConfigPda,
+        blocklist: blocklistPda,
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        originChainConfig: originChainConfigPda,
+        stagedInbound: this.findStagedInboundAddress(originTxHash, nonce),
+        inboundNonce: this.findInboundNonceAddress(originChainId),
+        postedVaa: postedVaa ?? null,
+        stager: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Mint a staged inbound message to its recipient, earning the origin chain's
+   * execution bounty. Before the message opens, only bonded relayers may execute.
+   */
+  async executeInboundMessage(originTxHash: string, nonce: number): Promise<string> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    const [crossChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cross_chain_config")],
+      this.programId
+    );
+    const [blocklistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blocklist")],
+      this.programId
+    );
+    const stagedInbound = this.findStagedInboundAddress(originTxHash, nonce);
+    const staged = await this.program.account.stagedInbound.fetch(stagedInbound);
+    const originChainId = staged.originChainId.toNumber();
+    const originContractBytes = Buffer.from(staged.originContract);
+    const mint = this.findInboundMintAddress(originChainId, originContractBytes, Buffer.from(staged.originTokenId));
+    const [originChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("chain_config"), new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8)],
+      this.programId
+    );
+    const [nftMetadataPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nft_metadata"), mint.toBytes()],
+      this.programId
+    );
+    const [receiptPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("cross_chain_receipt"),
+        Buffer.from(staged.originTxHash),
+        new anchor.BN(nonce).toArrayLike(Buffer, "le", 8)
+      ],
+      this.programId
+    );
+    const [foreignCollectionPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("foreign_collection"),
+        new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8),
+        originContractBytes,
+      ],
+      this.programId
+    );
+    const foreignCollectionInfo = await this.provider.connection.getAccountInfo(foreignCollectionPda);
+
+    return await this.program.methods
+      .executeInboundMessage(staged.originTxHash, new anchor.BN(nonce))
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: crossChainConfigPda,
+        blocklist: blocklistPda,
+        originChainConfig: originChainConfigPda,
+        stagedInbound,
+        stager: staged.stager,
+        receipt: receiptPda,
+        mint,
+        freezeAuthority: this.findFreezeAuthorityAddress(),
+        tokenAccount: await getAssociatedTokenAddress(mint, staged.recipient),
+        nftMetadata: nftMetadataPda,
+        collectionLedger: this.findCollectionLedgerAddress(originChainId, originContractBytes),
+        foreignCollection: foreignCollectionInfo ? foreignCollectionPda : null,
+        recipient: staged.recipient,
+        feeTreasury: this.findFeeTreasuryAddress(),
+        feeReserve: this.findFeeReserveAddress(),
+        relayerRegistration: await this.findOwnRelayerRegistration(),
+        authority: this.provider.wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Pay `bounty` lamports from the fee treasury for each staged inbound message
+   * from `chainId` that gets executed
+   */
+  async setChainExecutionBounty(chainId: number, bounty: number | string): Promise<string> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    const [chainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("chain_config"), new anchor.BN(chainId).toArrayLike(Buffer, "le", 8)],
+      this.programId
+    );
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    return await this.program.methods
+      .setChainExecutionBounty(new anchor.BN(chainId), new anchor.BN(bounty))
+      .accounts({
+        programState: programStatePda,
+        chainConfig: chainConfigPda,
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Cap outbound payloads to `chainId` at `maxPayloadBytes`; 0 removes the cap
+   */
+  async setChainMaxPayload(chainId: number, maxPayloadBytes: number): Promise<string> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    const [chainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("chain_config"), new anchor.BN(chainId).toArrayLike(Buffer, "le", 8)],
+      this.programId
+    );
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    return await this.program.methods
+      .setChainMaxPayload(new anchor.BN(chainId), maxPayloadBytes)
+      .accounts({
+        programState: programStatePda,
+        chainConfig: chainConfigPda,
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Derive the watcher account for `owner`
+   */
+  findWatcherAddress(owner: PublicKey): PublicKey {
+    const [watcher] = PublicKey.findProgramAddressSync(
+      [Buffer.from("watcher"), owner.toBytes()],
+      this.programId
+    );
+    return watcher;
+  }
+
+  /**
+   * Derive the challenge account for a staged optimistic receive
+   */
+  findChallengeAddress(optimisticReceive: PublicKey): PublicKey {
+    const [challenge] = PublicKey.findProgramAddressSync(
+      [Buffer.from("challenge"), optimisticReceive.toBytes()],
+      this.programId
+    );
+    return challenge;
+  }
+
+  /**
+   * Register the wallet as a watcher, or top up its bond, with `bond` lamports
+   */
+  async registerWatcher(bond: anchor.BN): Promise<string> {
+    return await this.program.methods
+      .registerWatcher(bond)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        watcher: this.findWatcherAddress(this.provider.wallet.publicKey),
+        owner: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  /**
+   * Challenge a pending optimistic receive, attaching up to 256 bytes of evidence
+   */
+  async submitChallenge(originTxHash: string, nonce: number, evidence: Buffer): Promise<string> {
+    const optimisticReceive = this.findOptimisticReceiveAddress(originTxHash, nonce);
+    const staged = await this.program.account.optimisticReceive.fetch(optimisticReceive);
+
+    return await this.program.methods
+      .submitChallenge(staged.originTxHash, new anchor.BN(nonce), evidence)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        optimisticReceive,
+        challengerWatcher: this.findWatcherAddress(this.provider.wallet.publicKey),
+        challenge: this.findChallengeAddress(optimisticReceive),
+        challenger: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  /**
+   * Uphold a challenge, slashing the submitter's bond to the challenger, or dismiss it
+   */
+  async resolveChallenge(originTxHash: string, nonce: number, upheld: boolean): Promise<string> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    const optimisticReceive = this.findOptimisticReceiveAddress(originTxHash, nonce);
+    const staged = await this.program.account.optimisticReceive.fetch(optimisticReceive);
+    const challengePda = this.findChallengeAddress(optimisticReceive);
+    const challenge = await this.program.account.challenge.fetch(challengePda);
+    const submitterWatcher = this.findWatcherAddress(staged.submitter);
+    const submitterWatcherInfo = await this.provider.connection.getAccountInfo(submitterWatcher);
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    return await this.program.methods
+      .resolveChallenge(staged.originTxHash, new anchor.BN(nonce), upheld)
+      .accounts({
+        programState: programStatePda,
+        optimisticReceive,
+        challenge: challengePda,
+        challengerWatcher: this.findWatcherAddress(challenge.challenger),
+        challenger: challenge.challenger,
+        submitterWatcher: submitterWatcherInfo ? submitterWatcher : null,
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Set the challenge window for optimistic receives from `chainId`; 0 disables them
+   */
+  async setChainOptimisticWindow(chainId: number, windowSeconds: number): Promise<string> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    const [chainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("chain_config"), new anchor.BN(chainId).toArrayLike(Buffer, "le", 8)],
+      this.programId
+    );
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    return await this.program.methods
+      .setChainOptimisticWindow(new anchor.BN(chainId), windowSeconds)
+      .accounts({
+        programState: programStatePda,
+        chainConfig: chainConfigPda,
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Refund a finished transfer's fee to whoever paid it: the whole fee after a
+   * revert or failure, the unspent gas budget after delivery
+   */
+  async claimFeeRefund(mint: PublicKey, nonce: number): Promise<string> {
+    const transferRecord = this.findTransferRecordAddress(mint, nonce);
+    const transfer = await this.program.account.crossChainTransfer.fetch(transferRecord);
+    const feeTreasury = this.findFeeTreasuryAddress();
+    const feeMint: PublicKey | null = transfer.feeMint;
+
+    return await this.program.methods
+      .claimFeeRefund(mint, new anchor.BN(nonce))
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        transferRecord,
+        feeTreasury,
+        feeReserve: this.findFeeReserveAddress(),
+        feePayer: transfer.feePayer,
+        treasuryFeeAccount: feeMint ? await getAssociatedTokenAddress(feeMint, feeTreasury, true) : null,
+        payerFeeAccount: feeMint ? await getAssociatedTokenAddress(feeMint, transfer.feePayer, true) : null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  /**
+   * Derive the record of an outbound bundle
+   */
+  findBundleRecordAddress(owner: PublicKey, nonce: number): PublicKey {
+    const [record] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bundle_transfer"),
+        owner.toBytes(),
+        new anchor.BN(nonce).toArrayLike(Buffer, "le", 8),
+      ],
+      this.programId
+    );
+    return record;
+  }
+
+  /**
+   * Submit the TSS attestation that an outbound bundle landed on its destination
+   * chain. `gasSpent` is the part of the bundle's gas budget delivery consumed.
+   */
+  async acknowledgeBundleDelivery(
+    owner: PublicKey,
+    nonce: number,
+    destinationTxHash: Buffer,
+    gasSpent: number | string,
+    tssSignature: Buffer
+  ): Promise<string> {
+    const [crossChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cross_chain_config")],
+      this.programId
+    );
+
+    return await this.program.methods
+      .acknowledgeBundleDelivery(
+        owner,
+        new anchor.BN(nonce),
+        Array.from(normalizeOriginTxHash(destinationTxHash)),
+        new anchor.BN(gasSpent),
+        tssSignature
+      )
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        crossChainConfig: crossChainConfigPda,
+        bundleRecord: this.findBundleRecordAddress(owner, nonce),
+        pendingTransfers: this.findPendingTransfersAddress(),
+        feeReserve: this.findFeeReserveAddress(),
+        relayerRegistration: await this.findOwnRelayerRegistration(),
+        relayer: this.provider.wallet.publicKey,
+      })
+      .rpc();
+  }
+
+  /**
+   * Submit the TSS attestation that an outbound bundle failed, unlocking every NFT in it
+   */
+  async acknowledgeBundleFailure(
+    owner: PublicKey,
+    nonce: number,
+    tssSignature: Buffer
+  ): Promise<string> {
+    const [crossChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cross_chain_config")],
+      this.programId
+    );
+    const bundleRecord = this.findBundleRecordAddress(owner, nonce);
+    const { collectionLedger, remainingAccounts } = await this.bundleUnlockAccounts(bundleRecord);
+
+    return await this.program.methods
+      .acknowledgeBundleFailure(owner, new anchor.BN(nonce), tssSignature)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        crossChainConfig: crossChainConfigPda,
+        bundleRecord,
+        collectionLedger,
+        pendingTransfers: this.findPendingTransfersAddress(),
+        relayerRegistration: await this.findOwnRelayerRegistration(),
+        relayer: this.provider.wallet.publicKey,
+      })
+      .remainingAccounts(remainingAccounts)
+      .rpc();
+  }
+
+  /**
+   * Unlock every NFT of a bundle the destination chain will never deliver (admin only)
+   */
+  async revertBundleTransfer(owner: PublicKey, nonce: number): Promise<string> {
+    const bundleRecord = this.findBundleRecordAddress(owner, nonce);
+    const { collectionLedger, remainingAccounts } = await this.bundleUnlockAccounts(bundleRecord);
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    return await this.program.methods
+      .revertBundleTransfer(owner, new anchor.BN(nonce))
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        bundleRecord,
+        collectionLedger,
+        pendingTransfers: this.findPendingTransfersAddress(),
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .remainingAccounts(remainingAccounts)
+      .rpc();
+  }
+
+  /**
+   * Refund a finished bundle's fee to whoever paid it, as `claimFeeRefund` does for a
+   * single transfer
+   */
+  async claimBundleFeeRefund(owner: PublicKey, nonce: number): Promise<string> {
+    const bundleRecord = this.findBundleRecordAddress(owner, nonce);
+    const bundle = await this.program.account.bundleTransfer.fetch(bundleRecord);
+    const feeTreasury = this.findFeeTreasuryAddress();
+    const feeMint: PublicKey | null = bundle.feeMint;
+
+    return await this.program.methods
+      .claimBundleFeeRefund(owner, new anchor.BN(nonce))
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        bundleRecord,
+        feeTreasury,
+        feeReserve: this.findFeeReserveAddress(),
+        feePayer: bundle.feePayer,
+        treasuryFeeAccount: feeMint ? await getAssociatedTokenAddress(feeMint, feeTreasury, true) : null,
+        payerFeeAccount: feeMint ? await getAssociatedTokenAddress(feeMint, bundle.feePayer, true) : null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  /**
+   * The collection ledger and `(nft_metadata, stake_record)` pairs that unlocking a bundle needs
+   */
+  private async bundleUnlockAccounts(bundleRecord: PublicKey): Promise<{
+    collectionLedger: PublicKey;
+    remainingAccounts: AccountMeta[];
+  }> {
+    const bundle = await this.program.account.bundleTransfer.fetch(bundleRecord);
+    const mints: PublicKey[] = bundle.mints;
+    const metadataAddresses = mints.map((mint) => {
+      const [nftMetadataPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("nft_metadata"), mint.toBytes()],
+        this.programId
+      );
+      return nftMetadataPda;
+    });
+    // Every NFT in a bundle comes from the same collection
+    const nftMetadata = await this.program.account.nftMetadata.fetch(metadataAddresses[0]);
+
+    return {
+      collectionLedger: this.findCollectionLedgerAddress(
+        nftMetadata.originChainId.toNumber(),
+        Buffer.from(nftMetadata.originContract)
+      ),
+      remainingAccounts: mints.flatMap((mint, index) => [
+        { pubkey: metadataAddresses[index], isSigner: false, isWritable: true },
+        { pubkey: this.findStakeRecordAddress(mint), isSigner: false, isWritable: true },
+      ]),
+    };
+  }
+
+  /**
+   * Repair an NFT whose lock disagrees with its latest transfer record or swap order.
+   * Omitting `transferNonce` asserts that the NFT has no pending transfer.
+   */
+  async reconcileNft(
+    mint: PublicKey,
+    transferNonce?: number,
+    valueEscrow?: PublicKey // Required when the pending transfer carries value
+  ): Promise<string> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    const [nftMetadataPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nft_metadata"), mint.toBytes()],
+      this.programId
+    );
+    const nonce = new anchor.BN(transferNonce ?? 0);
+    const [transferRecordPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cross_chain_transfer"), mint.toBytes(), nonce.toArrayLike(Buffer, "le", 8)],
+      this.programId
+    );
+    const [swapOrderPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("swap_order"), mint.toBytes()],
+      this.programId
+    );
+    const swapOrderInfo = await this.provider.connection.getAccountInfo(swapOrderPda);
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    const tx = await this.program.methods
+      .reconcileNft(mint, nonce)
+      .accounts({
+        programState: programStatePda,
+        nftMetadata: nftMetadataPda,
+        transferRecord: transferNonce === undefined ? null : transferRecordPda,
+        valueEscrow: valueEscrow ?? null,
+        swapOrder: swapOrderInfo ? swapOrderPda : null,
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Derive the PDA registered with the LayerZero endpoint as this program's OApp
+   */
+  findLzOappAddress(): PublicKey {
+    const [lzOappPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lz_oapp")],
+      this.programId
+    );
+    return lzOappPda;
+  }
+
+  /**
+   * Derive the CPI allowlist account
+   */
+  findCpiAllowlistAddress(): PublicKey {
+    const [cpiAllowlist] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cpi_allowlist")],
+      this.programId
+    );
+    return cpiAllowlist;
+  }
+
+  /**
+   * Derive the PDA that collects bridge fees. SPL fees are held by its
+   * associated token account for the fee mint.
+   */
+  findFeeTreasuryAddress(): PublicKey {
+    const [feeTreasury] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_treasury")],
+      this.programId
+    );
+    return feeTreasury;
+  }
+
+  /**
+   * Derive the account tracking refundable fees, which `withdrawFees` can't take
+   */
+  findFeeReserveAddress(): PublicKey {
+    const [feeReserve] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_reserve")],
+      this.programId
+    );
+    return feeReserve;
+  }
+
+  /**
+   * Fee accounts a transfer to `destinationChainId` needs, paid by `payer`
+   */
+  async findFeeAccounts(payer: PublicKey, destinationChainId: number): Promise<{
+    feeTreasury: PublicKey | null;
+    payerFeeAccount: PublicKey | null;
+    treasuryFeeAccount: PublicKey | null;
+  }> {
+    const [chainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("chain_config"), new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8)],
+      this.programId
+    );
+    const { fee } = await this.program.account.chainConfig.fetch(chainConfigPda);
+    const feeTreasury = this.findFeeTreasuryAddress();
+
+    if (fee.flatFee.isZero() && fee.gasBudget.isZero()) {
+      return { feeTreasury: null, payerFeeAccount: null, treasuryFeeAccount: null };
+    }
+    if (!fee.mint) {
+      return { feeTreasury, payerFeeAccount: null, treasuryFeeAccount: null };
+    }
+    return {
+      feeTreasury: null,
+      payerFeeAccount: await getAssociatedTokenAddress(fee.mint, payer, true),
+      treasuryFeeAccount: await getAssociatedTokenAddress(fee.mint, feeTreasury, true),
+    };
+  }
+
+  /**
+   * Replace the set of programs allowed to CPI into receive and admin
+   * instructions. Direct calls are unaffected; `enforced: false` turns the
+   * guard off.
+   */
+  async setCpiAllowlist(enforced: boolean, programs: PublicKey[]): Promise<string> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    const tx = await this.program.methods
+      .setCpiAllowlist(enforced, programs)
+      .accounts({
+        programState: programStatePda,
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Point an NFT at a new metadata URI of up to 500 bytes. The metadata account
+   * grows to fit, and `owner` pays the rent for the added space.
+   */
+  async expandMetadata(mint: PublicKey, owner: Keypair, metadataUri: string): Promise<string> {
+    return await this.program.methods
+      .expandMetadata(metadataUri)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        nftMetadata: PublicKey.findProgramAddressSync(
+          [Buffer.from("nft_metadata"), mint.toBytes()],
+          this.programId
+        )[0],
+        mint,
+        tokenAccount: await getAssociatedTokenAddress(mint, owner.publicKey),
+        owner: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  /**
+   * Set a second metadata URI for when the primary stops resolving, or clear it
+   * with null. Outbound transfers carry both URIs.
+   */
+  async setFallbackUri(mint: PublicKey, owner: Keypair, fallbackUri: string | null): Promise<string> {
+    return await this.program.methods
+      .setFallbackUri(fallbackUri)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        nftMetadata: PublicKey.findProgramAddressSync(
+          [Buffer.from("nft_metadata"), mint.toBytes()],
+          this.programId
+        )[0],
+        mint,
+        tokenAccount: await getAssociatedTokenAddress(mint, owner.publicKey),
+        owner: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  /**
+   * Set the metadata URIs the NFT offers for other locales, replacing any
+   * earlier ones; an empty list clears them. Up to 4, keyed by BCP 47 tags
+   * such as "pt-BR". `cross_chain_transfer` carries them to the destination.
+   */
+  async setLocalizedUris(mint: PublicKey, owner: Keypair, entries: LocalizedUri[]): Promise<string> {
+    return await this.program.methods
+      .setLocalizedUris(entries)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        nftMetadata: PublicKey.findProgramAddressSync(
+          [Buffer.from("nft_metadata"), mint.toBytes()],
+          this.programId
+        )[0],
+        localizedMetadata: this.findLocalizedMetadataAddress(mint),
+        mint,
+        tokenAccount: await getAssociatedTokenAddress(mint, owner.publicKey),
+        owner: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  /**
+   * Get an NFT's localized metadata URIs; empty when it has none
+   */
+  async getLocalizedUris(mint: PublicKey): Promise<LocalizedUri[]> {
+    const localizedMetadata = await this.program.account.localizedMetadata.fetchNullable(
+      this.findLocalizedMetadataAddress(mint)
+    );
+    return localizedMetadata
+      ? localizedMetadata.entries.map((entry) => ({ locale: entry.locale, uri: entry.uri }))
+      : [];
+  }
+
+  /**
+   * Let `appProgram` write the NFT's app data by CPI, or revoke with null.
+   * `owner` pays for the app data account the first time.
+   */
+  async setAppDataProgram(mint: PublicKey, owner: Keypair, appProgram: PublicKey | null): Promise<string> {
+    return await this.program.methods
+      .setAppDataProgram(appProgram)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        nftMetadata: PublicKey.findProgramAddressSync(
+          [Buffer.from("nft_metadata"), mint.toBytes()],
+          this.programId
+        )[0],
+        appData: this.findAppDataAddress(mint),
+        mint,
+        tokenAccount: await getAssociatedTokenAddress(mint, owner.publicKey),
+        owner: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  /**
+   * Equip `childMint` on `parentMint`; `owner` must hold both. The child sits
+   * in escrow, and the parent can't be bridged, until it is unequipped.
+   */
+  async equipChild(parentMint: PublicKey, childMint: PublicKey, owner: Keypair): Promise<string> {
+    return await this.program.methods
+      .equipChild()
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        parentMetadata: PublicKey.findProgramAddressSync(
+          [Buffer.from("nft_metadata"), parentMint.toBytes()],
+          this.programId
+        )[0],
+        parentMint,
+        parentTokenAccount: await getAssociatedTokenAddress(parentMint, owner.publicKey),
+        childIndex: this.findChildIndexAddress(parentMint),
+        childMetadata: PublicKey.findProgramAddressSync(
+          [Buffer.from("nft_metadata"), childMint.toBytes()],
+          this.programId
+        )[0],
+        childMint,
+        ownerChildAccount: await getAssociatedTokenAddress(childMint, owner.publicKey),
+        childEscrow: this.findChildEscrowAddress(childMint),
+        owner: owner.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  /**
+   * Return an equipped child to `owner`, the parent's current holder
+   */
+  async unequipChild(parentMint: PublicKey, childMint: PublicKey, owner: Keypair): Promise<string> {
+    return await this.program.methods
+      .unequipChild()
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        parentMetadata: PublicKey.findProgramAddressSync(
+          [Buffer.from("nft_metadata"), parentMint.toBytes()],
+          this.programId
+        )[0],
+        parentMint,
+        parentTokenAccount: await getAssociatedTokenAddress(parentMint, owner.publicKey),
+        childIndex: this.findChildIndexAddress(parentMint),
+        childMint,
+        childEscrow: this.findChildEscrowAddress(childMint),
+        ownerChildAccount: await getAssociatedTokenAddress(childMint, owner.publicKey),
+        owner: owner.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  /**
+   * List `mint` in a Dutch auction. The price starts at `startPrice` lamports
+   * at `startTime` (now if omitted) and falls by `decayPerSecond` each second
+   * until it reaches `floorPrice`. The NFT sits in escrow until it sells or
+   * the seller cancels.
+   */
+  async createDutchAuction(
+    mint: PublicKey,
+    seller: Keypair,
+    terms: { startPrice: anchor.BN; floorPrice: anchor.BN; decayPerSecond: anchor.BN; startTime?: number }
+  ): Promise<string> {
+    return await this.program.methods
+      .createDutchAuction(terms.startPrice, terms.floorPrice, terms.decayPerSecond, new anchor.BN(terms.startTime ?? 0))
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        nftMetadata: PublicKey.findProgramAddressSync(
+          [Buffer.from("nft_metadata"), mint.toBytes()],
+          this.programId
+        )[0],
+        dutchAuction: this.findDutchAuctionAddress(mint),
+        mint,
+        sellerTokenAccount: await getAssociatedTokenAddress(mint, seller.publicKey),
+        auctionEscrow: this.findAuctionEscrowAddress(mint),
+        seller: seller.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([seller])
+      .rpc();
+  }
+
+  /**
+   * Buy a Dutch-auctioned NFT at its current price, failing if that is above
+   * `maxPrice` lamports. Pays the collection's royalty creators and the seller.
+   */
+  async buyDutchAuction(mint: PublicKey, buyer: Keypair, maxPrice: anchor.BN): Promise<string> {
+    const auction = await this.program.account.dutchAuction.fetch(this.findDutchAuctionAddress(mint));
+    const [nftMetadataPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nft_metadata"), mint.toBytes()],
+      this.programId
+    );
+    const nftMetadata = await this.program.account.nftMetadata.fetch(nftMetadataPda);
+    const { collectionRoyalty, creatorAccounts } = await this.royaltyAccounts(nftMetadata.collection);
+
+    return await this.program.methods
+      .buyDutchAuction(maxPrice)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        nftMetadata: nftMetadataPda,
+        collectionRoyalty,
+        dutchAuction: this.findDutchAuctionAddress(mint),
+        mint,
+        auctionEscrow: this.findAuctionEscrowAddress(mint),
+        buyerTokenAccount: await getAssociatedTokenAddress(mint, buyer.publicKey),
+        seller: auction.seller,
+        buyer: buyer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(creatorAccounts)
+      .signers([buyer])
+      .rpc();
+  }
+
+  /**
+   * Take an unsold NFT out of its Dutch auction, back to the seller
+   */
+  async cancelDutchAuction(mint: PublicKey, seller: Keypair): Promise<string> {
+    return await this.program.methods
+      .cancelDutchAuction()
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        dutchAuction: this.findDutchAuctionAddress(mint),
+        mint,
+        auctionEscrow: this.findAuctionEscrowAddress(mint),
+        sellerTokenAccount: await getAssociatedTokenAddress(mint, seller.publicKey),
+        seller: seller.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([seller])
+      .rpc();
+  }
+
+  /**
+   * Offer `amount` lamports for `mint`, listed or not, until `expiresAt`
+   * (unix seconds). The lamports are escrowed until the holder accepts, or
+   * the buyer cancels or the offer expires.
+   */
+  async makeOffer(mint: PublicKey, buyer: Keypair, amount: anchor.BN, expiresAt: number): Promise<string> {
+    return await this.program.methods
+      .makeOffer(amount, new anchor.BN(expiresAt))
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        nftMetadata: PublicKey.findProgramAddressSync(
+          [Buffer.from("nft_metadata"), mint.toBytes()],
+          this.programId
+        )[0],
+        mint,
+        offer: this.findOfferAddress(mint, buyer.publicKey),
+        buyer: buyer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([buyer])
+      .rpc();
+  }
+
+  /**
+   * Sell `mint` to `buyer`'s offer as its holder `owner`, failing if the offer
+   * is below `minAmount` lamports. Pays the collection's royalty creators.
+   */
+  async acceptOffer(mint: PublicKey, buyer: PublicKey, owner: Keypair, minAmount: anchor.BN): Promise<string> {
+    const [nftMetadataPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nft_metadata"), mint.toBytes()],
+      this.programId
+    );
+    const nftMetadata = await this.program.account.nftMetadata.fetch(nftMetadataPda);
+    const { collectionRoyalty, creatorAccounts } = await this.royaltyAccounts(nftMetadata.collection);
+
+    return await this.program.methods
+      .acceptOffer(minAmount)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        nftMetadata: nftMetadataPda,
+        collectionRoyalty,
+        offer: this.findOfferAddress(mint, buyer),
+        mint,
+        ownerTokenAccount: await getAssociatedTokenAddress(mint, owner.publicKey),
+        buyerTokenAccount: await getAssociatedTokenAddress(mint, buyer),
+        buyer,
+        owner: owner.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(creatorAccounts)
+      .signers([owner])
+      .rpc();
+  }
+
+  /**
+   * Refund `buyer`'s offer for `mint`. Before it expires only the buyer may
+   * sign; after, any signer (the client's wallet by default) may.
+   */
+  async cancelOffer(mint: PublicKey, buyer: PublicKey, signer?: Keypair): Promise<string> {
+    return await this.program.methods
+      .cancelOffer()
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        offer: this.findOfferAddress(mint, buyer),
+        buyer,
+        signer: signer?.publicKey ?? this.provider.wallet.publicKey,
+      })
+      .signers(signer ? [signer] : [])
+      .rpc();
+  }
+
+  /**
+   * Get the vault of a fractionalized NFT; null if it isn't fractionalized
+   */
+  async getFractionVault(mint: PublicKey): Promise<FractionVault | null> {
+    const vault = await this.program.account.fractionVault.fetchNullable(this.findFractionVaultAddress(mint));
+    if (!vault) {
+      return null;
+    }
+    return {
+      mint: vault.mint.toString(),
+      depositor: vault.depositor.toString(),
+      fractionMint: vault.fractionMint.toString(),
+      totalSupply: vault.totalSupply.toString(),
+      createdAt: vault.createdAt.toString(),
+    };
+  }
+
+  /**
+   * Get a ticket's check-in, or null if it hasn't been used
+   */
+  async getTicketCheckIn(mint: PublicKey): Promise<TicketCheckIn | null> {
+    const checkIn = await this.program.account.ticketCheckIn.fetchNullable(this.findTicketCheckInAddress(mint));
+    if (!checkIn) {
+      return null;
+    }
+    return {
+      mint: checkIn.mint.toString(),
+      collection: checkIn.collection.toString(),
+      holder: checkIn.holder.toString(),
+      validator: checkIn.validator.equals(PublicKey.default) ? null : checkIn.validator.toString(),
+      checkedInAt: checkIn.checkedInAt.toNumber(),
+    };
+  }
+
+  /**
+   * Get the fee and proof of work open mints need, or null if there is no guard
+   */
+  async getMintGuard(): Promise<MintGuard | null> {
+    const mintGuard = await this.program.account.mintGuard.fetchNullable(this.findMintGuardAddress());
+    if (!mintGuard) {
+      return null;
+    }
+    return {
+      feeLamports: mintGuard.feeLamports.toNumber(),
+      powDifficulty: mintGuard.powDifficulty,
+      updatedAt: mintGuard.updatedAt.toNumber(),
+    };
+  }
+
+  /**
+   * Get the per-wallet mint caps, or null if none were set
+   */
+  async getMintLimits(): Promise<MintLimits | null> {
+    const mintLimits = await this.program.account.mintLimits.fetchNullable(this.findMintLimitsAddress());
+    if (!mintLimits) {
+      return null;
+    }
+    return {
+      maxPerWallet: mintLimits.maxPerWallet,
+      phase: mintLimits.phase,
+      phaseMaxPerWallet: mintLimits.phaseMaxPerWallet,
+      updatedAt: mintLimits.updatedAt.toNumber(),
+    };
+  }
+
+  /**
+   * How many NFTs `wallet` has minted, overall and in the current phase
+   */
+  async getWalletMintCount(wallet: PublicKey): Promise<WalletMintCount> {
+    const [counter, limits] = await Promise.all([
+      this.program.account.walletMintCounter.fetchNullable(this.findWalletMintCounterAddress(wallet)),
+      this.getMintLimits(),
+    ]);
+    const phase = limits ? limits.phase : 0;
+    if (!counter) {
+      return { totalMinted: 0, phase, phaseMinted: 0 };
+    }
+    return {
+      totalMinted: counter.totalMinted,
+      phase,
+      phaseMinted: counter.phase === phase ? counter.phaseMinted : 0,
+    };
+  }
+
+  /**
+   * A mint keypair `authority` can mint with under the current mint guard,
+   * ground to its proof-of-work difficulty when it has one
+   */
+  async mintKeypairFor(authority: PublicKey): Promise<Keypair> {
+    const mintGuard = await this.getMintGuard();
+    return grindMintKeypair(this.programId, authority, mintGuard ? mintGuard.powDifficulty : 0);
+  }
+
+  /**
+   * Get the metadata variants an oracle feed selects for `mint`, or null if
+   * its metadata is static
+   */
+  async getDynamicMetadata(mint: PublicKey): Promise<DynamicMetadata | null> {
+    const dynamicMetadata = await this.program.account.dynamicMetadata.fetchNullable(
+      this.findDynamicMetadataAddress(mint)
+    );
+    if (!dynamicMetadata) {
+      return null;
+    }
+    return {
+      feed: dynamicMetadata.feed.toString(),
+      activeVariant: dynamicMetadata.activeVariant,
+      lastValue: dynamicMetadata.lastValue.toNumber(),
+      updatedAt: dynamicMetadata.updatedAt.toNumber(),
+      variants: dynamicMetadata.variants.map((variant) => ({
+        minValue: variant.minValue.toNumber(),
+        uri: variant.uri,
+      })),
+    };
+  }
+
+  /**
+   * Get the redemption of a burned NFT, or null if it wasn't redeemed
+   */
+  async getRedemption(mint: PublicKey): Promise<Redemption | null> {
+    const redemption = await this.program.account.redemption.fetchNullable(this.findRedemptionAddress(mint));
+    if (!redemption) {
+      return null;
+    }
+    return {
+      mint: redemption.mint.toString(),
+      redeemer: redemption.redeemer.toString(),
+      collection: redemption.collection.toString(),
+      originChainId: redemption.originChainId.toNumber(),
+      claimCodeHash: Buffer.from(redemption.claimCodeHash).toString('hex'),
+      redeemedAt: redemption.redeemedAt.toNumber(),
+      metadataUri: redemption.metadataUri,
+      name: redemption.name,
+      details: redemption.details,
+    };
+  }
+
+  /**
+   * Whether `claimCode` is the one a redemption was made with, as a
+   * fulfillment service checks before handing over the goods
+   */
+  async verifyClaimCode(mint: PublicKey, claimCode: string): Promise<boolean> {
+    const redemption = await this.getRedemption(mint);
+    return redemption !== null && redemption.claimCodeHash === hashClaimCode(claimCode).toString('hex');
+  }
+
+  /**
+   * Get an NFT's stake and the rewards it could claim now, or null if it
+   * isn't staked
+   */
+  async getStakeRecord(mint: PublicKey): Promise<StakeRecord | null> {
+    const stake = await this.program.account.stakeRecord.fetchNullable(this.findStakeRecordAddress(mint));
+    if (!stake) {
+      return null;
+    }
+    const multiplier = stake.collection.equals(PublicKey.default)
+      ? null
+      : await this.program.account.rewardMultiplier.fetchNullable(this.findRewardMultiplierAddress(stake.collection));
+    const multiplierBps = multiplier ? multiplier.multiplierBps : 10_000;
+    const config = await this.program.account.rewardConfig.fetchNullable(this.findRewardConfigAddress());
+    let pendingRewards = BigInt(0);
+    if (config && stake.bridgedAt.isZero()) {
+      const now = Math.floor(Date.now() / 1000);
+      const epochs = BigInt(Math.max(0, Math.floor((now - stake.lastClaimedAt.toNumber()) / config.epochSeconds)));
+      pendingRewards = (epochs * BigInt(config.emissionPerEpoch.toString()) * BigInt(multiplierBps)) / BigInt(10_000);
+    }
+    return {
+      owner: stake.owner.toString(),
+      mint: stake.mint.toString(),
+      collection: stake.collection.toString(),
+      stakedAt: stake.stakedAt.toString(),
+      lastClaimedAt: stake.lastClaimedAt.toString(),
+      totalClaimed: stake.totalClaimed.toString(),
+      bridgedAt: stake.bridgedAt.toString(),
+      multiplierBps,
+      pendingRewards: pendingRewards.toString(),
+    };
+  }
+
+  /**
+   * Get the open offers for an NFT, highest first
+   */
+  async getOffers(mint: PublicKey): Promise<Offer[]> {
+    const offers = await this.program.account.offer.all([
+      { memcmp: { offset: 8 + 32, bytes: mint.toBase58() } }, // After the discriminator and buyer
+    ]);
+    const now = Math.floor(Date.now() / 1000);
+    return offers
+      .map(({ account }) => ({
+        buyer: account.buyer.toString(),
+        mint: account.mint.toString(),
+        amount: account.amount.toString(),
+        expiresAt: account.expiresAt.toString(),
+        expired: now >= account.expiresAt.toNumber(),
+      }))
+      .sort((a, b) => (BigInt(b.amount) > BigInt(a.amount) ? 1 : BigInt(b.amount) < BigInt(a.amount) ? -1 : 0));
+  }
+
+  /**
+   * List `mint` for `price` lamports without escrow. The NFT stays in the
+   * seller's associated token account, frozen, with the listing as delegate.
+   * Only NFTs minted by the program can be listed this way.
+   */
+  async listWithDelegate(mint: PublicKey, seller: Keypair, price: anchor.BN): Promise<string> {
+    return await this.program.methods
+      .listWithDelegate(price)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        nftMetadata: PublicKey.findProgramAddressSync(
+          [Buffer.from("nft_metadata"), mint.toBytes()],
+          this.programId
+        )[0],
+        delegateListing: this.findDelegateListingAddress(mint),
+        mint,
+        freezeAuthority: this.findFreezeAuthorityAddress(),
+        tokenAccount: await getAssociatedTokenAddress(mint, seller.publicKey),
+        seller: seller.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([seller])
+      .rpc();
+  }
+
+  /**
+   * Buy a delegate-listed NFT, failing if its price is above `maxPrice`
+   * lamports. Pays the collection's royalty creators and the seller.
+   */
+  async buyDelegateListing(mint: PublicKey, buyer: Keypair, maxPrice: anchor.BN): Promise<string> {
+    const listing = await this.program.account.delegateListing.fetch(this.findDelegateListingAddress(mint));
+    const [nftMetadataPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nft_metadata"), mint.toBytes()],
+      this.programId
+    );
+    const nftMetadata = await this.program.account.nftMetadata.fetch(nftMetadataPda);
+    const { collectionRoyalty, creatorAccounts } = await this.royaltyAccounts(nftMetadata.collection);
+
+    return await this.program.methods
+      .buyDelegateListing(maxPrice)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        nftMetadata: nftMetadataPda,
+        collectionRoyalty,
+        delegateListing: this.findDelegateListingAddress(mint),
+        mint,
+        freezeAuthority: this.findFreezeAuthorityAddress(),
+        tokenAccount: listing.tokenAccount,
+        buyerTokenAccount: await getAssociatedTokenAddress(mint, buyer.publicKey),
+        seller: listing.seller,
+        buyer: buyer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(creatorAccounts)
+      .signers([buyer])
+      .rpc();
+  }
+
+  /**
+   * Cancel a delegate listing, thawing the seller's token account
+   */
+  async cancelDelegateListing(mint: PublicKey, seller: Keypair): Promise<string> {
+    const listing = await this.program.account.delegateListing.fetch(this.findDelegateListingAddress(mint));
+    return await this.program.methods
+      .cancelDelegateListing()
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        delegateListing: this.findDelegateListingAddress(mint),
+        mint,
+        freezeAuthority: this.findFreezeAuthorityAddress(),
+        tokenAccount: listing.tokenAccount,
+        seller: seller.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([seller])
+      .rpc();
+  }
+
+  /**
+   * Lock `mint` in a vault and mint `totalSupply` fractions to `owner`. The
+   * NFT can't be bridged until someone redeems it with every fraction.
+   */
+  async fractionalizeNft(mint: PublicKey, owner: Keypair, totalSupply: anchor.BN): Promise<string> {
+    const fractionMint = this.findFractionMintAddress(mint);
+    return await this.program.methods
+      .fractionalizeNft(totalSupply)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        nftMetadata: PublicKey.findProgramAddressSync(
+          [Buffer.from("nft_metadata"), mint.toBytes()],
+          this.programId
+        )[0],
+        fractionVault: this.findFractionVaultAddress(mint),
+        mint,
+        ownerTokenAccount: await getAssociatedTokenAddress(mint, owner.publicKey),
+        vaultEscrow: this.findFractionEscrowAddress(mint),
+        fractionMint,
+        ownerFractionAccount: await getAssociatedTokenAddress(fractionMint, owner.publicKey),
+        owner: owner.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  /**
+   * Burn every fraction of `mint`'s vault, held in `redeemer`'s associated
+   * token account, and take the NFT out
+   */
+  async redeemFractions(mint: PublicKey, redeemer: Keypair): Promise<string> {
+    const vault = await this.program.account.fractionVault.fetch(this.findFractionVaultAddress(mint));
+    return await this.program.methods
+      .redeemFractions()
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        nftMetadata: PublicKey.findProgramAddressSync(
+          [Buffer.from("nft_metadata"), mint.toBytes()],
+          this.programId
+        )[0],
+        fractionVault: this.findFractionVaultAddress(mint),
+        mint,
+        vaultEscrow: this.findFractionEscrowAddress(mint),
+        fractionMint: vault.fractionMint,
+        redeemerFractionAccount: await getAssociatedTokenAddress(vault.fractionMint, redeemer.publicKey),
+        redeemerTokenAccount: await getAssociatedTokenAddress(mint, redeemer.publicKey),
+        depositor: vault.depositor,
+        redeemer: redeemer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([redeemer])
+      .rpc();
+  }
+
+  /**
+   * Set the staking reward emission to `emissionPerEpoch` base units per NFT
+   * every `epochSeconds`, creating the reward mint on first use (admin only)
+   */
+  async setRewardConfig(emissionPerEpoch: anchor.BN, epochSeconds: number): Promise<string> {
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    return await this.program.methods
+      .setRewardConfig(emissionPerEpoch, epochSeconds)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        rewardConfig: this.findRewardConfigAddress(),
+        rewardMint: this.findRewardMintAddress(),
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Boost staking rewards for `collectionMint`'s NFTs to `multiplierBps`
+   * (10000 is 1x, up to 10x) (admin only)
+   */
+  async setRewardMultiplier(collectionMint: PublicKey, multiplierBps: number): Promise<string> {
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    return await this.program.methods
+      .setRewardMultiplier(collectionMint, multiplierBps)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        rewardMultiplier: this.findRewardMultiplierAddress(collectionMint),
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Stake `mint` in `owner`'s wallet. The NFT doesn't move; rewards are paid
+   * while `owner` still holds it.
+   */
+  async stakeNft(mint: PublicKey, owner: Keypair): Promise<string> {
+    return await this.program.methods
+      .stakeNft()
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        nftMetadata: PublicKey.findProgramAddressSync(
+          [Buffer.from("nft_metadata"), mint.toBytes()],
+          this.programId
+        )[0],
+        stakeRecord: this.findStakeRecordAddress(mint),
+        mint,
+        ownerTokenAccount: await getAssociatedTokenAddress(mint, owner.publicKey),
+        owner: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  /**
+   * Mint the rewards `mint` has accrued to `owner`'s reward token account,
+   * at its collection's multiplier if it has one
+   */
+  async claimRewards(mint: PublicKey, owner: Keypair): Promise<string> {
+    const stake = await this.program.account.stakeRecord.fetch(this.findStakeRecordAddress(mint));
+    let rewardMultiplier: PublicKey | null = null;
+    if (!stake.collection.equals(PublicKey.default)) {
+      const multiplier = this.findRewardMultiplierAddress(stake.collection);
+      if (await this.provider.connection.getAccountInfo(multiplier)) {
+        rewardMultiplier = multiplier;
+      }
+    }
+    const rewardMint = this.findRewardMintAddress();
+
+    return await this.program.methods
+      .claimRewards()
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        rewardConfig: this.findRewardConfigAddress(),
+        rewardMultiplier,
+        nftMetadata: PublicKey.findProgramAddressSync(
+          [Buffer.from("nft_metadata"), mint.toBytes()],
+          this.programId
+        )[0],
+        stakeRecord: this.findStakeRecordAddress(mint),
+        mint,
+        ownerTokenAccount: await getAssociatedTokenAddress(mint, owner.publicKey),
+        rewardMint,
+        ownerRewardAccount: await getAssociatedTokenAddress(rewardMint, owner.publicKey),
+        owner: owner.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  /**
+   * Close `mint`'s stake. Rewards not yet claimed are forfeited, so call
+   * `claimRewards` first.
+   */
+  async unstakeNft(mint: PublicKey, owner: Keypair): Promise<string> {
+    return await this.program.methods
+      .unstakeNft()
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        stakeRecord: this.findStakeRecordAddress(mint),
+        owner: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  /**
+   * Burn `redeemer`'s NFT to redeem what it stands for. Only the hash of
+   * `claimCode` goes on chain; `details` (at most 200 bytes) is passed on to
+   * fulfillment, for example a variant or a delivery reference.
+   */
+  async redeemNft(mint: PublicKey, redeemer: Keypair, claimCode: string, details: string = ""): Promise<string> {
+    const nftMetadataPda = PublicKey.findProgramAddressSync(
+      [Buffer.from("nft_metadata"), mint.toBytes()],
+      this.programId
+    )[0];
+    const nftMetadata = await this.program.account.nftMetadata.fetch(nftMetadataPda);
+    const isNative = nftMetadata.originContract.length === 0;
+
+    return await this.program.methods
+      .redeemNft(Array.from(hashClaimCode(claimCode)), details)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        nftMetadata: nftMetadataPda,
+        collectionLedger: isNative
+          ? null
+          : this.findCollectionLedgerAddress(
+              nftMetadata.originChainId.toNumber(),
+              Buffer.from(nftMetadata.originContract)
+            ),
+        redemption: this.findRedemptionAddress(mint),
+        mint,
+        tokenAccount: await getAssociatedTokenAddress(mint, redeemer.publicKey),
+        redeemer: redeemer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([redeemer])
+      .rpc();
+  }
+
+  /**
+   * Make `collectionMint`'s NFTs single-use tickets. Signed by the client's
+   * wallet, which must be the collection's creator or the program authority.
+   */
+  async setTicketConfig(
+    collectionMint: PublicKey,
+    lockAfterUse: boolean,
+    validators: PublicKey[] = []
+  ): Promise<string> {
+    const collectionConfig = PublicKey.findProgramAddressSync(
+      [Buffer.from("collection_config"), collectionMint.toBytes()],
+      this.programId
+    )[0];
+    const hasConfig = (await this.provider.connection.getAccountInfo(collectionConfig)) !== null;
+
+    return await this.program.methods
+      .setTicketConfig(collectionMint, lockAfterUse, validators)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        collectionConfig: hasConfig ? collectionConfig : null,
+        ticketConfig: this.findTicketConfigAddress(collectionMint),
+        signer: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  /**
+   * Check in `holder`'s ticket, co-signed by `validator` when the collection
+   * lists validators
+   */
+  async checkIn(mint: PublicKey, holder: Keypair, validator: Keypair | null = null): Promise<string> {
+    const nftMetadataPda = PublicKey.findProgramAddressSync(
+      [Buffer.from("nft_metadata"), mint.toBytes()],
+      this.programId
+    )[0];
+    const nftMetadata = await this.program.account.nftMetadata.fetch(nftMetadataPda);
+
+    return await this.program.methods
+      .checkIn()
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        ticketConfig: this.findTicketConfigAddress(nftMetadata.collection),
+        nftMetadata: nftMetadataPda,
+        ticketCheckIn: this.findTicketCheckInAddress(mint),
+        mint,
+        holderTokenAccount: await getAssociatedTokenAddress(mint, holder.publicKey),
+        freezeAuthority: this.findFreezeAuthorityAddress(),
+        holder: holder.publicKey,
+        validator: validator ? validator.publicKey : null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers(validator ? [holder, validator] : [holder])
+      .rpc();
+  }
+
+  /**
+   * Charge open mints `feeLamports` and require `powDifficulty` bits of proof
+   * of work on the mint address; 0 and 0 turn the guard off (authority or
+   * operator)
+   */
+  async setMintGuard(feeLamports: number, powDifficulty: number): Promise<string> {
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    return await this.program.methods
+      .setMintGuard(new anchor.BN(feeLamports), powDifficulty)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        mintGuard: this.findMintGuardAddress(),
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Cap open mints at `maxPerWallet` per wallet and `phaseMaxPerWallet` in
+   * `phase`; 0 leaves a cap off. A new `phase` starts the phase counts over
+   * (authority or operator).
+   */
+  async setMintLimits(maxPerWallet: number, phase: number, phaseMaxPerWallet: number): Promise<string> {
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    return await this.program.methods
+      .setMintLimits(maxPerWallet, phase, phaseMaxPerWallet)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        mintLimits: this.findMintLimitsAddress(),
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Create or update the oracle feed `feedId`, naming the key that pushes its
+   * values and how long a value stays usable (admin only)
+   */
+  async configureOracleFeed(feedId: Buffer, updater: PublicKey, maxStalenessSeconds: number): Promise<string> {
+    const auditLog = await this.program.account.auditLog.fetch(this.findAuditLogAddress());
+
+    return await this.program.methods
+      .configureOracleFeed(Array.from(feedId), updater, maxStalenessSeconds)
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        oracleFeed: this.findOracleFeedAddress(feedId),
+        cpiAllowlist: this.findCpiAllowlistAddress(),
+        auditLog: this.findAuditLogAddress(),
+        auditEntry: this.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+        authority: this.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  }
+
+  /**
+   * Publish the latest value of the oracle feed `feedId` (feed updater only)
+   */
+  async pushOracleValue(feedId: Buffer, value: number, updater: Keypair): Promise<string> {
+    return await this.program.methods
+      .pushOracleValue(new anchor.BN(value))
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        oracleFeed: this.findOracleFeedAddress(feedId),
+        updater: updater.publicKey,
+      })
+      .signers([updater])
+      .rpc();
+  }
+
+  /**
+   * Make the NFT's metadata follow the oracle feed `feedId`: each variant is
+   * selected once the value reaches its `minValue`, the first while it is below
+   * them all. Up to 4, with increasing `minValue`.
+   */
+  async setMetadataVariants(
+    mint: PublicKey,
+    owner: Keypair,
+    feedId: Buffer,
+    variants: MetadataVariant[]
+  ): Promise<string> {
+    return await this.program.methods
+      .setMetadataVariants(variants.map((variant) => ({ minValue: new anchor.BN(variant.minValue), uri: variant.uri })))
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        nftMetadata: PublicKey.findProgramAddressSync(
+          [Buffer.from("nft_metadata"), mint.toBytes()],
+          this.programId
+        )[0],
+        oracleFeed: this.findOracleFeedAddress(feedId),
+        dynamicMetadata: this.findDynamicMetadataAddress(mint),
+        mint,
+        tokenAccount: await getAssociatedTokenAddress(mint, owner.publicKey),
+        owner: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  /**
+   * Apply the variant the feed's latest value selects to the NFT's metadata.
+   * Anyone may call it, including while the NFT is bridged away; a change
+   * emits a metadata sync message for its copy on the other chain.
+   */
+  async updateMetadataFromOracle(mint: PublicKey): Promise<string> {
+    const dynamicMetadata = this.findDynamicMetadataAddress(mint);
+    const { feed } = await this.program.account.dynamicMetadata.fetch(dynamicMetadata);
+
+    return await this.program.methods
+      .updateMetadataFromOracle()
+      .accounts({
+        programState: this.findProgramStateAddress(),
+        crossChainConfig: PublicKey.findProgramAddressSync([Buffer.from("cross_chain_config")], this.programId)[0],
+        nftMetadata: PublicKey.findProgramAddressSync(
+          [Buffer.from("nft_metadata"), mint.toBytes()],
+          this.programId
+        )[0],
+        dynamicMetadata,
+        oracleFeed: feed,
+        mint,
+      })
+      .rpc();
+  }
+
+  /**
+   * The royalty PDA and creator accounts a sale of an NFT in `collection`
+   * passes; none for NFTs outside a collection
+   */
+  private async royaltyAccounts(collection: PublicKey): Promise<{
+    collectionRoyalty: PublicKey | null;
+    creatorAccounts: { pubkey: PublicKey; isWritable: boolean; isSigner: boolean }[];
+  }> {
+    if (collection.equals(PublicKey.default)) {
+      return { collectionRoyalty: null, creatorAccounts: [] };
+    }
+    const collectionRoyalty = this.findCollectionRoyaltyAddress(collection);
+    const royalty = await this.program.account.collectionRoyalty.fetchNullable(collectionRoyalty);
+    return {
+      collectionRoyalty,
+      creatorAccounts: (royalty?.creators ?? []).map((creator) => ({
+        pubkey: creator.address,
+        isWritable: true,
+        isSigner: false,
+      })),
+    };
+  }
+
+  /**
+   * Verify NFT ownership
+   */
+  async verifyOwnership(mint: PublicKey, owner: Keypair): Promise<string> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+
+    const [nftMetadataPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nft_metadata"), mint.toBytes()],
+      this.programId
+    );
+
+    const tokenAccount = await getAssociatedTokenAddress(mint, owner.publicKey);
+
+    const tx = await this.program.methods
+      .verifyOwnership(mint)
+      .accounts({
+        programState: programStatePda,
+        nftMetadata: nftMetadataPda,
+        tokenAccount: tokenAccount,
+        owner: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Get NFT metadata
+   */
+  async getNftMetadata(mint: PublicKey): Promise<NftMetadata | null> {
+    try {
+      const [nftMetadataPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("nft_metadata"), mint.toBytes()],
+        this.programId
+      );
+
+      const metadata = await this.program.account.nftMetadata.fetch(nftMetadataPda);
+      return this.toNftMetadata(metadata);
+    } catch (error) {
+      console.error("Error fetching NFT metadata:", error);
+      return null;
+    }
+  }
+
+  /**
+   * Get cross-chain transfer record
+   */
+  async getCrossChainTransfer(mint: PublicKey, nonce: number): Promise<CrossChainTransfer | null> {
+    try {
+      const transferRecordPda = this.findTransferRecordAddress(mint, nonce);
+      const transfer = await this.program.account.crossChainTransfer.fetch(transferRecordPda);
+      return this.toCrossChainTransfer(transfer);
+    } catch (error) {
+      console.error("Error fetching cross-chain transfer:", error);
+      return null;
+    }
+  }
+
+  /**
+   * Every NFT currently held by `owner`, found with an RPC filter on
+   * `current_owner` rather than a scan of all metadata accounts
+   */
+  async getNftsByOwner(owner: PublicKey): Promise<NftMetadata[]> {
+    const accounts = await this.program.account.nftMetadata.all([
+      { memcmp: { offset: NFT_METADATA_OFFSETS.currentOwner, bytes: owner.toBase58() } },
+    ]);
+    return accounts.map(({ account }) => this.toNftMetadata(account));
+  }
+
+  /**
+   * Transfer records in `status`, optionally only those started by `owner`
+   */
+  async getTransfersByStatus(status: TransferStatus, owner?: PublicKey): Promise<CrossChainTransfer[]> {
+    const filters = [
+      { memcmp: { offset: TRANSFER_RECORD_OFFSETS.status, bytes: anchor.utils.bytes.bs58.encode([status]) } },
+    ];
+    if (owner) {
+      filters.push({ memcmp: { offset: TRANSFER_RECORD_OFFSETS.originalOwner, bytes: owner.toBase58() } });
+    }
+    const accounts = await this.program.account.crossChainTransfer.all(filters);
+    return accounts.map(({ account }) => this.toCrossChainTransfer(account));
+  }
+
+  /**
+   * The owner's most recent outbound transfers, newest first, read from their
+   * on-chain index. Entries of a batch transfer are keyed by the default mint and
+   * are skipped here; use `getTransfersByStatus` to reach those and anything older.
+   */
+  async getTransferHistory(owner: PublicKey): Promise<CrossChainTransfer[]> {
+    let index;
+    try {
+      index = await this.program.account.ownerTransferIndex.fetch(this.findOwnerTransferIndexAddress(owner));
+    } catch (error) {
+      return [];
+    }
+
+    const addresses = index.entries
+      .filter((entry: any) => !entry.mint.equals(PublicKey.default))
+      .reverse()
+      .map((entry: any) => this.findTransferRecordAddress(entry.mint, entry.nonce.toNumber()));
+    const records = await this.program.account.crossChainTransfer.fetchMultiple(addresses);
+    return records
+      .filter((record) => record !== null)
+      .map((record) => this.toCrossChainTransfer(record));
+  }
+
+  /**
+   * Fetch the app data of an NFT, or null if no app was ever approved for it
+   */
+  async getAppData(mint: PublicKey): Promise<NftAppData | null> {
+    let appData;
+    try {
+      appData = await this.program.account.nftAppData.fetch(this.findAppDataAddress(mint));
+    } catch (error) {
+      return null;
+    }
+    return {
+      mint: appData.mint.toString(),
+      appProgram: appData.appProgram.equals(PublicKey.default) ? null : appData.appProgram.toString(),
+      updatedAt: appData.updatedAt.toString(),
+      data: Buffer.from(appData.data),
+    };
+  }
+
+  /**
+   * Fetch the mints equipped on a parent NFT, in equip order
+   */
+  async getChildren(parentMint: PublicKey): Promise<PublicKey[]> {
+    try {
+      const childIndex = await this.program.account.childIndex.fetch(this.findChildIndexAddress(parentMint));
+      return childIndex.children;
+    } catch (error) {
+      return [];
+    }
+  }
+
+  private toNftMetadata(metadata: any): NftMetadata {
+    return {
+      mint: metadata.mint.toString(),
+      originalOwner: metadata.originalOwner.toString(),
+      currentOwner: metadata.currentOwner.toString(),
+      metadataUri: metadata.metadataUri,
+      fallbackUri: metadata.fallbackUri ?? null,
+      metadataMultihash: metadata.metadataMultihash?.length
+        ? Buffer.from(metadata.metadataMultihash).toString('hex')
+        : null,
+      name: metadata.name,
+      symbol: metadata.symbol,
+      crossChainEnabled: metadata.crossChainEnabled,
+      isLocked: metadata.isLocked,
+      originChainId: metadata.originChainId.toString(),
+      collection: metadata.collection.equals(PublicKey.default) ? null : metadata.collection.toString(),
+      creationTimestamp: metadata.creationTimestamp.toString(),
+      childCount: metadata.childCount ?? 0,
+    };
+  }
+
+  private toCrossChainTransfer(transfer: any): CrossChainTransfer {
+    return {
+      mint: transfer.mint.toString(),
+      originalOwner: transfer.originalOwner.toString(),
+      destinationChainId: transfer.destinationChainId.toString(),
+      recipientAddress: Buffer.from(transfer.recipientAddress).toString('hex'),
+      nonce: transfer.nonce.toString(),
+      timestamp: transfer.timestamp.toString(),
+      status: transfer.status,
+      destinationTxHash: transfer.status === TransferStatus.Completed
+        ? Buffer.from(transfer.destinationTxHash).toString('hex')
+        : null,
+      completedAt: transfer.completedAt.toString(),
+      feePayer: transfer.feePayer.toString(),
+      feeMint: transfer.feeMint ? transfer.feeMint.toString() : null,
+      feePaid: transfer.feePaid.toString(),
+      gasBudget: transfer.gasBudget.toString(),
+      gasSpent: transfer.gasSpent.toString(),
+      feeRefunded: transfer.feeRefunded,
+      stakedSeconds: transfer.stakedSeconds.toString(),
+    };
+  }
+
+  /**
+   * Get cross-chain receipt
+   */
+  async getCrossChainReceipt(originTxHash: string, nonce: number): Promise<CrossChainReceipt | null> {
+    try {
+      const receipt = await this.program.account.crossChainReceipt.fetch(this.findReceiptAddress(originTxHash, nonce));
+      return this.toCrossChainReceipt(receipt);
+    } catch (error) {
+      console.error("Error fetching cross-chain receipt:", error);
+      return null;
+    }
+  }
+
+  /**
+   * Check whether an inbound message has arrived, in one simulated call.
+   * `originTxHash` is the origin chain's transaction hash in hex; the program
+   * derives the receipt address from it and reports what the receipt holds.
+   */
+  async findReceipt(originTxHash: string, nonce: number): Promise<ReceiptLookup> {
+    const lookup = await this.program.methods
+      .findReceipt(this.parseOriginTxHash(originTxHash), new anchor.BN(nonce))
+      .accounts({ receipt: this.findReceiptAddress(originTxHash, nonce) })
+      .view();
+
+    return {
+      address: lookup.address.toString(),
+      processed: lookup.processed,
+      receipt: lookup.receipt ? this.toCrossChainReceipt(lookup.receipt) : null,
+    };
+  }
+
+  findReceiptAddress(originTxHash: string, nonce: number): PublicKey {
+    const [receiptPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("cross_chain_receipt"),
+        normalizeOriginTxHash(this.parseOriginTxHash(originTxHash)),
+        new anchor.BN(nonce).toArrayLike(Buffer, "le", 8)
+      ],
+      this.programId
+    );
+    return receiptPda;
+  }
+
+  private parseOriginTxHash(originTxHash: string): Buffer {
+    return Buffer.from(originTxHash.startsWith('0x') ? originTxHash.slice(2) : originTxHash, 'hex');
+  }
+
+  private toCrossChainReceipt(receipt: any): CrossChainReceipt {
+    return {
+      version: receipt.version,
+      originChainId: receipt.originChainId.toString(),
+      originTxHash: Buffer.from(receipt.originTxHash).toString('hex'),
+      mint: receipt.mint.toString(),
+      recipient: receipt.recipient.toString(),
+      originalOwner: Buffer.from(receipt.originalOwner).toString('hex'),
+      nonce: receipt.nonce.toString(),
+      timestamp: receipt.timestamp.toString(),
+      tssSignatureHash: Buffer.from(receipt.tssSignatureHash).toString('hex'),
+    };
+  }
+
+  /**
+   * Get the supply ledger for an origin collection
+   */
+  async getCollectionLedger(originChainId: number, originContract: Buffer): Promise<CollectionLedger | null> {
+    try {
+      const ledgerPda = this.findCollectionLedgerAddress(originChainId, originContract);
+      const ledger = await this.program.account.collectionLedger.fetch(ledgerPda);
+
+      return {
+        originChainId: ledger.originChainId.toString(),
+        originContract: Buffer.from(ledger.originContract).toString('hex'),
+        nativeLocked: ledger.nativeLocked.toString(),
+        wrappedSupply: ledger.wrappedSupply.toString(),
+        totalOutbound: ledger.totalOutbound.toString(),
+        totalInbound: ledger.totalInbound.toString(),
+        lastUpdated: ledger.lastUpdated.toString(),
+      };
+    } catch (error) {
+      console.error("Error fetching collection ledger:", error);
+      return null;
+    }
+  }
+
+  /**
+   * Get a collection's royalty settings; null if it never set any
+   */
+  async getCollectionRoyalty(collectionMint: PublicKey): Promise<CollectionRoyalty | null> {
+    const royalty = await this.program.account.collectionRoyalty.fetchNullable(
+      this.findCollectionRoyaltyAddress(collectionMint)
+    );
+    if (!royalty) {
+      return null;
+    }
+    return {
+      collectionMint: royalty.collectionMint.toString(),
+      basisPoints: royalty.basisPoints,
+      creators: royalty.creators.map((creator) => ({ address: creator.address.toString(), share: creator.share })),
+      updatedBy: royalty.updatedBy.toString(),
+      updatedAt: royalty.updatedAt.toString(),
+    };
+  }
+
+  /**
+   * Get an NFT's Dutch auction and its price now; null if it isn't listed
+   */
+  async getDutchAuction(mint: PublicKey): Promise<DutchAuction | null> {
+    const auction = await this.program.account.dutchAuction.fetchNullable(this.findDutchAuctionAddress(mint));
+    if (!auction) {
+      return null;
+    }
+    const elapsed = Math.max(0, Math.floor(Date.now() / 1000) - auction.startTime.toNumber());
+    const decayed = auction.startPrice.sub(auction.decayPerSecond.mul(new anchor.BN(elapsed)));
+    return {
+      seller: auction.seller.toString(),
+      mint: auction.mint.toString(),
+      startPrice: auction.startPrice.toString(),
+      floorPrice: auction.floorPrice.toString(),
+      decayPerSecond: auction.decayPerSecond.toString(),
+      startTime: auction.startTime.toString(),
+      currentPrice: anchor.BN.max(decayed, auction.floorPrice).toString(),
+    };
+  }
+
+  /**
+   * Get the outbound transfer backlog: counts and the latest status changes.
+   * Null until the first transfer creates the registry.
+   */
+  async getPendingTransfers(): Promise<PendingTransfers | null> {
+    const registry = await this.program.account.pendingTransfers.fetchNullable(this.findPendingTransfersAddress());
+    if (!registry) {
+      return null;
+    }
+
+    return {
+      pending: registry.pending.toString(),
+      totalCreated: registry.totalCreated.toString(),
+      totalCompleted: registry.totalCompleted.toString(),
+      totalReverted: registry.totalReverted.toString(),
+      lastUpdated: registry.lastUpdated.toString(),
+      recent: registry.recent.map((change: any) => ({
+        mint: change.mint.toString(),
+        nonce: change.nonce.toString(),
+        status: change.status,
+        timestamp: change.timestamp.toString(),
+      })),
+    };
+  }
+
+  /**
+   * Export every account the program owns as JSON, for audits and for
+   * migrating to a new deployment. Each page of accounts is checked against
+   * the type and data hash `export_state` reports for it, so the export
+   * fails rather than record data the program doesn't see.
+   */
+  async exportState(): Promise<StateExport> {
+    const connection = this.provider.connection;
+    const slot = await connection.getSlot();
+    const programAccounts = await connection.getProgramAccounts(this.programId, { minContextSlot: slot });
+    const accounts = [...programAccounts].sort((a, b) => a.pubkey.toBase58().localeCompare(b.pubkey.toBase58()));
+
+    const typeNames = new Map(
+      (this.program.idl.accounts ?? []).map((account) => [accountDiscriminator(account.name).toString('hex'), account.name])
+    );
+
+    const records: ExportedAccountRecord[] = [];
+    for (let start = 0; start < accounts.length; start += EXPORT_PAGE_SIZE) {
+      const page = accounts.slice(start, start + EXPORT_PAGE_SIZE);
+      const attested = await this.program.methods
+        .exportState(start / EXPORT_PAGE_SIZE)
+        .accounts({ programState: this.findProgramStateAddress() })
+        .remainingAccounts(page.map(({ pubkey }) => ({ pubkey, isWritable: false, isSigner: false })))
+        .view();
+
+      page.forEach(({ pubkey, account }, index) => {
+        const dataHash = sha256Hex(account.data);
+        const entry = attested.accounts[index];
+        if (!entry.address.equals(pubkey) || Buffer.from(entry.dataHash).toString('hex') !== dataHash) {
+          throw new Error(`Account ${pubkey.toBase58()} changed during the export; retry`);
+        }
+
+        const type = typeNames.get(Buffer.from(entry.discriminator).toString('hex')) ?? null;
+        let decoded: unknown = null;
+        if (type) {
+          try {
+            decoded = toJsonValue(this.program.coder.accounts.decode(type, account.data));
+          } catch (error) {
+            decoded = null; // Older layout; the raw data is still exported
+          }
+        }
+        records.push({
+          address: pubkey.toBase58(),
+          type,
+          lamports: account.lamports,
+          dataHash,
+          data: account.data.toString('base64'),
+          decoded,
+        });
+      });
+    }
+
+    return {
+      programId: this.programId.toBase58(),
+      slot,
+      exportedAt: new Date().toISOString(),
+      accounts: records,
+    };
+  }
+
+  /**
+   * Decode every event the program has emitted, oldest first, by walking its
+   * transaction history. Pass `until` to resume after the last signature seen.
+   */
+  replayEvents(options: ReplayOptions = {}): AsyncGenerator<ReplayedEvent> {
+    return replayEvents(this.program, options);
+  }
+
+  /**
+   * Get the deployed program's version and build commit
+   */
+  async getProgramVersion(): Promise<ProgramVersion> {
+    const [programStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program_state")],
+      this.programId
+    );
+
+    const result = await this.program.methods
+      .getProgramVersion()
+      .accounts({ programState: programStatePda })
+      .view();
+    return {
+      version: result.version,
+      gitHash: result.gitHash,
+    };
+  }
+
+  /**
+   * ComputeBudget instructions for `operation`: the recommended unit limit
+   * and a priority fee estimated from recent blocks that wrote `writableAccounts`
+   */
+  async priorityFeeInstructions(
+    operation: string,
+    options: { items?: number; writableAccounts?: PublicKey[]; percentile?: number; maxMicroLamports?: number } = {}
+  ): Promise<TransactionInstruction[]> {
+    const microLamports = await estimatePriorityFee(this.provider.connection, options);
+    return computeBudgetInstructions(recommendedComputeUnits(operation, options.items), microLamports);
+  }
+
+  /**
+   * Sign and submit `transactions` as one Jito bundle, tipping `tipLamports`
+   * from the wallet in the last one. Meant for relayers that need a batch of
+   * receives to land together. Returns the bundle ID.
+   */
+  async submitJitoBundle(
+    transactions: Transaction[],
+    tipLamports: number,
+    blockEngineUrl: string = JITO_BLOCK_ENGINE_URL
+  ): Promise<string> {
+    if (transactions.length === 0 || transactions.length > MAX_BUNDLE_TRANSACTIONS) {
+      throw new Error(`A bundle holds 1 to ${MAX_BUNDLE_TRANSACTIONS} transactions, got ${transactions.length}`);
+    }
+    const payer = this.provider.wallet.publicKey;
+    const tipAccounts = await getJitoTipAccounts(blockEngineUrl);
+    const tipAccount = tipAccounts[Math.floor(Math.random() * tipAccounts.length)];
+    transactions[transactions.length - 1].add(jitoTipInstruction(payer, tipAccount, tipLamports));
+
+    const { blockhash } = await this.provider.connection.getLatestBlockhash();
+    for (const tx of transactions) {
+      tx.feePayer = payer;
+      tx.recentBlockhash = blockhash;
     }
+    const signed = await this.provider.wallet.signAllTransactions(transactions);
+    return sendJitoBundle(signed, blockEngineUrl);
   }
 
   /**
@@ -0,0 +1,110 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Connection, PublicKey } from '@solana/web3.js';
+import { toJsonValue } from './state-export';
+
+/** Signatures per `getSignaturesForAddress` page; the RPC maximum */
+export const SIGNATURE_PAGE_SIZE = 1000;
+
+/** Transactions fetched per `getTransactions` call */
+export const DEFAULT_REPLAY_BATCH_SIZE = 100;
+
+/** One event the program emitted, with where it was emitted */
+export interface ReplayedEvent {
+  signature: string;
+  slot: number;
+  blockTime: number | null; // Unix seconds, null if the node doesn't know it
+  name: string; // Event type from the IDL, e.g. "CrossChainTransferInitiatedEvent"
+  sequence: string | null; // The event's `sequence` field, the program-wide emit order
+  data: unknown; // Fields as plain JSON: keys and numbers as strings, bytes as hex
+}
+
+export interface ReplayOptions {
+  until?: string; // Stop at this signature, exclusive; defaults to the program's first transaction
+  before?: string; // Start below this signature, exclusive; defaults to the latest
+  batchSize?: number;
+  onProgress?: (done: number, total: number) => void; // Transactions fetched so far
+}
+
+/**
+ * Every signature that touched `programId` in the range, oldest first.
+ * Reaching genesis needs an RPC node that keeps full history.
+ */
+export async function programSignatures(
+  connection: Pick<Connection, 'getSignaturesForAddress'>,
+  programId: PublicKey,
+  options: Pick<ReplayOptions, 'until' | 'before'> = {}
+): Promise<string[]> {
+  const signatures: string[] = [];
+  let before = options.before;
+  for (;;) {
+    const page = await connection.getSignaturesForAddress(programId, {
+      before,
+      until: options.until,
+      limit: SIGNATURE_PAGE_SIZE,
+    }, 'confirmed');
+    // Failed transactions roll back, so the events they logged never happened
+    signatures.push(...page.filter((entry) => entry.err === null).map((entry) => entry.signature));
+    if (page.length < SIGNATURE_PAGE_SIZE) {
+      break;
+    }
+    before = page[page.length - 1].signature;
+  }
+  return signatures.reverse();
+}
+
+/**
+ * Decode every event the program emitted in the range, oldest first. Events
+ * inside one transaction come out in emit order. Events whose layout the IDL
+ * doesn't know, e.g. from a newer program version, are skipped.
+ */
+export async function* replayEvents(
+  program: anchor.Program<any>,
+  options: ReplayOptions = {}
+): AsyncGenerator<ReplayedEvent> {
+  const connection = program.provider.connection;
+  const parser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+  const signatures = await programSignatures(connection, program.programId, options);
+  const batchSize = options.batchSize ?? DEFAULT_REPLAY_BATCH_SIZE;
+
+  for (let start = 0; start < signatures.length; start += batchSize) {
+    const batch = signatures.slice(start, start + batchSize);
+    const transactions = await connection.getTransactions(batch, {
+      commitment: 'confirmed',
+      maxSupportedTransactionVersion: 0,
+    });
+
+    for (const [index, tx] of transactions.entries()) {
+      if (!tx?.meta?.logMessages) {
+        continue;
+      }
+      for (const event of parser.parseLogs(tx.meta.logMessages)) {
+        const sequence = (event.data as { sequence?: anchor.BN }).sequence;
+        yield {
+          signature: batch[index],
+          slot: tx.slot,
+          blockTime: tx.blockTime ?? null,
+          name: event.name,
+          sequence: sequence ? sequence.toString() : null,
+          data: toJsonValue(event.data),
+        };
+      }
+    }
+    options.onProgress?.(Math.min(start + batchSize, signatures.length), signatures.length);
+  }
+}
+
+const csvField = (value: string): string =>
+  /[",\n]/.test(value) ? `"${value.replace(/"/g, '""')}"` : value;
+
+/** Events as CSV, one row each, with the decoded fields as a JSON column */
+export function eventsToCsv(events: ReplayedEvent[]): string {
+  const rows = events.map((event) => [
+    event.signature,
+    String(event.slot),
+    event.blockTime === null ? '' : String(event.blockTime),
+    event.name,
+    event.sequence ?? '',
+    JSON.stringify(event.data),
+  ].map(csvField).join(','));
+  return ['signature,slot,block_time,name,sequence,data', ...rows].join('\n') + '\n';
+}
@@ -0,0 +1,65 @@
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { createPrivateKey, sign } from 'crypto';
+
+/** Layout constants shared with `universal-nft-messages` */
+const SIGNING_DOMAIN_TAG = Buffer.from('UNIVERSAL_NFT');
+const SIGNING_DOMAIN_VERSION = 1;
+const PAYLOAD_VERSION = 6;
+const TRANSFER_PERMIT_MESSAGE_TYPE = 9;
+
+/** PKCS#8 header that wraps a raw 32-byte ed25519 seed for node's crypto */
+const ED25519_PKCS8_PREFIX = Buffer.from('302e020100300506032b657004220420', 'hex');
+
+/**
+ * An owner's consent to one cross-chain transfer, submitted by a sponsor
+ * through `cross_chain_transfer_with_permit`
+ */
+export interface TransferPermit {
+  mint: PublicKey;
+  owner: PublicKey;
+  destinationChainId: number;
+  recipientAddress: Buffer;
+  nonce: number;
+  deadline: number; // Unix seconds; the permit is void afterwards
+}
+
+/**
+ * Bytes the owner signs for `permit`: the signing domain of `programId` on
+ * `chainId` (the Solana chain ID in `CrossChainConfig`), then the payload.
+ * Wallets can sign these with `signMessage`.
+ */
+export function transferPermitMessage(programId: PublicKey, chainId: number, permit: TransferPermit): Buffer {
+  const u64 = (value: number) => {
+    const bytes = Buffer.alloc(8);
+    bytes.writeBigUInt64LE(BigInt(value));
+    return bytes;
+  };
+  const length = Buffer.alloc(4);
+  length.writeUInt32LE(permit.recipientAddress.length);
+  const deadline = Buffer.alloc(8);
+  deadline.writeBigInt64LE(BigInt(permit.deadline));
+
+  return Buffer.concat([
+    SIGNING_DOMAIN_TAG,
+    programId.toBuffer(),
+    u64(chainId),
+    Buffer.from([TRANSFER_PERMIT_MESSAGE_TYPE, SIGNING_DOMAIN_VERSION, PAYLOAD_VERSION]),
+    permit.mint.toBuffer(),
+    permit.owner.toBuffer(),
+    u64(permit.destinationChainId),
+    length,
+    permit.recipientAddress,
+    u64(permit.nonce),
+    deadline,
+  ]);
+}
+
+/** Sign a permit message with a local keypair, as a wallet's `signMessage` would */
+export function signTransferPermit(owner: Keypair, message: Buffer): Buffer {
+  const key = createPrivateKey({
+    key: Buffer.concat([ED25519_PKCS8_PREFIX, owner.secretKey.subarray(0, 32)]),
+    format: 'der',
+    type: 'pkcs8',
+  });
+  return sign(null, message, key);
+}
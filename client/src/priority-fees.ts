@@ -0,0 +1,121 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  Connection,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+  VersionedTransaction,
+  ComputeBudgetProgram,
+} from '@solana/web3.js';
+import axios from 'axios';
+
+/** Compute unit limits per operation, matching the program's `calculate_compute_budget` */
+export const COMPUTE_UNITS: Record<string, number> = {
+  mint: 200_000,
+  cross_chain_transfer: 300_000,
+  receive_cross_chain: 400_000,
+  verify_ownership: 50_000,
+};
+
+/** Limit for operations missing from `COMPUTE_UNITS` */
+export const DEFAULT_COMPUTE_UNITS = 100_000;
+
+/** Additional compute per NFT in a bundle transfer, matching `BUNDLE_ITEM_COMPUTE_UNITS` */
+export const BUNDLE_ITEM_COMPUTE_UNITS = 25_000;
+
+/** Percentile of recent priority fees `estimatePriorityFee` targets by default */
+export const DEFAULT_FEE_PERCENTILE = 75;
+
+/** Jito mainnet block engine; pass a regional endpoint to cut latency */
+export const JITO_BLOCK_ENGINE_URL = 'https://mainnet.block-engine.jito.wtf/api/v1';
+
+/** Most transactions a Jito bundle may hold */
+export const MAX_BUNDLE_TRANSACTIONS = 5;
+
+/** Compute unit limit to request for `operation`; `items` counts NFTs in a bundle transfer */
+export function recommendedComputeUnits(operation: string, items: number = 0): number {
+  return (COMPUTE_UNITS[operation] ?? DEFAULT_COMPUTE_UNITS) + BUNDLE_ITEM_COMPUTE_UNITS * items;
+}
+
+export interface PriorityFeeOptions {
+  writableAccounts?: PublicKey[]; // Accounts the transaction writes; fees are sampled where they contend
+  percentile?: number; // 0-100, defaults to DEFAULT_FEE_PERCENTILE
+  maxMicroLamports?: number; // Cap on the returned price
+}
+
+/**
+ * Priority fee in micro-lamports per compute unit, taken as a percentile of
+ * the fees paid in recently confirmed blocks. Returns 0 when the cluster
+ * reports no recent fees, e.g. on localnet.
+ */
+export async function estimatePriorityFee(
+  connection: Pick<Connection, 'getRecentPrioritizationFees'>,
+  options: PriorityFeeOptions = {}
+): Promise<number> {
+  const percentile = options.percentile ?? DEFAULT_FEE_PERCENTILE;
+  if (percentile < 0 || percentile > 100) {
+    throw new Error(`Percentile must be between 0 and 100, got ${percentile}`);
+  }
+
+  const recent = await connection.getRecentPrioritizationFees({
+    lockedWritableAccounts: options.writableAccounts,
+  });
+  if (recent.length === 0) {
+    return 0;
+  }
+
+  const fees = recent.map((entry) => entry.prioritizationFee).sort((a, b) => a - b);
+  const index = Math.min(fees.length - 1, Math.ceil((percentile / 100) * fees.length) - 1);
+  const fee = fees[Math.max(0, index)];
+  return options.maxMicroLamports === undefined ? fee : Math.min(fee, options.maxMicroLamports);
+}
+
+/** ComputeBudget instructions to prepend; the price is omitted when it is 0 */
+export function computeBudgetInstructions(units: number, microLamports: number = 0): TransactionInstruction[] {
+  const instructions = [ComputeBudgetProgram.setComputeUnitLimit({ units })];
+  if (microLamports > 0) {
+    instructions.push(ComputeBudgetProgram.setComputeUnitPrice({ microLamports }));
+  }
+  return instructions;
+}
+
+const blockEngineRequest = async <T>(blockEngineUrl: string, method: string, params: unknown[]): Promise<T> => {
+  const response = await axios.post(`${blockEngineUrl}/bundles`, {
+    jsonrpc: '2.0',
+    id: 1,
+    method,
+    params,
+  });
+  if (response.data.error) {
+    throw new Error(`Block engine ${method} failed: ${response.data.error.message}`);
+  }
+  return response.data.result as T;
+};
+
+/** Tip accounts the block engine currently accepts; they rotate, so fetch rather than hard-code */
+export async function getJitoTipAccounts(blockEngineUrl: string = JITO_BLOCK_ENGINE_URL): Promise<PublicKey[]> {
+  const accounts = await blockEngineRequest<string[]>(blockEngineUrl, 'getTipAccounts', []);
+  return accounts.map((account) => new PublicKey(account));
+}
+
+/** Transfer of `lamports` from `payer` to a Jito tip account */
+export function jitoTipInstruction(payer: PublicKey, tipAccount: PublicKey, lamports: number): TransactionInstruction {
+  return SystemProgram.transfer({ fromPubkey: payer, toPubkey: tipAccount, lamports });
+}
+
+/**
+ * Submit signed transactions as one Jito bundle: they land in order, in the
+ * same block, or not at all. One of them must tip a Jito tip account.
+ * Returns the bundle ID.
+ */
+export async function sendJitoBundle(
+  transactions: (Transaction | VersionedTransaction)[],
+  blockEngineUrl: string = JITO_BLOCK_ENGINE_URL
+): Promise<string> {
+  if (transactions.length === 0 || transactions.length > MAX_BUNDLE_TRANSACTIONS) {
+    throw new Error(`A bundle holds 1 to ${MAX_BUNDLE_TRANSACTIONS} transactions, got ${transactions.length}`);
+  }
+  const encoded = transactions.map((tx) => anchor.utils.bytes.bs58.encode(Buffer.from(tx.serialize())));
+  return blockEngineRequest<string>(blockEngineUrl, 'sendBundle', [encoded]);
+}
@@ -0,0 +1,154 @@
+import { Connection, PublicKey } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { getAddress, verifyMessage } from 'ethers';
+import { SUPPORTED_CHAINS } from './types';
+
+/** Solana Name Service program that owns `.sol` name registries */
+export const SNS_PROGRAM_ID = new PublicKey('namesLPneVptA9Z5rqUDD9tMTWEJwofgaYwp8cawRkX');
+
+/** Parent registry of every `.sol` domain */
+export const SOL_TLD_AUTHORITY = new PublicKey('58PwtjSDuFHuUkYjH9BYnnQKHfwo9reZhC2zMJv9JPkx');
+
+const SNS_HASH_PREFIX = 'SPL Name Service';
+
+/** Registry owner offset: after `parent_name` */
+const SNS_OWNER_OFFSET = 32;
+
+/** Bounds the program enforces on `recipient_address` */
+const MAX_RECIPIENT_ADDRESS_LENGTH = 64;
+
+/**
+ * An ENS name resolved off-chain, signed by an attester the caller trusts. The
+ * signature is an EIP-191 personal signature over `ensAttestationMessage`.
+ */
+export interface EnsAttestation {
+  name: string; // e.g. "vitalik.eth"
+  address: string; // 0x-prefixed address the name resolved to
+  chainId: number; // Destination chain the resolution is for
+  expiresAt: number; // Unix seconds
+  signature: string;
+}
+
+/**
+ * Derive the registry account of a `.sol` domain. Subdomains are not supported.
+ */
+export function findSolNameAddress(name: string): PublicKey {
+  const label = name.toLowerCase().replace(/\.sol$/, '');
+  if (label.length === 0 || label.includes('.')) {
+    throw new Error(`Invalid .sol name: ${name}`);
+  }
+
+  const hashedName = createHash('sha256').update(SNS_HASH_PREFIX + label).digest();
+  const [registry] = PublicKey.findProgramAddressSync(
+    [hashedName, Buffer.alloc(32), SOL_TLD_AUTHORITY.toBuffer()],
+    SNS_PROGRAM_ID
+  );
+  return registry;
+}
+
+/**
+ * Resolve a `.sol` domain to the wallet that owns it
+ */
+export async function resolveSolName(connection: Connection, name: string): Promise<PublicKey> {
+  const registry = await connection.getAccountInfo(findSolNameAddress(name));
+  if (!registry || !registry.owner.equals(SNS_PROGRAM_ID)) {
+    throw new Error(`Unregistered .sol name: ${name}`);
+  }
+  return new PublicKey(registry.data.subarray(SNS_OWNER_OFFSET, SNS_OWNER_OFFSET + 32));
+}
+
+/** Text an ENS attester signs */
+export function ensAttestationMessage(attestation: Omit<EnsAttestation, 'signature'>): string {
+  return [
+    'Universal NFT ENS resolution',
+    `name: ${attestation.name.toLowerCase()}`,
+    `address: ${getAddress(attestation.address)}`,
+    `chain: ${attestation.chainId}`,
+    `expires: ${attestation.expiresAt}`,
+  ].join('\n');
+}
+
+/**
+ * Check an ENS attestation for `destinationChainId` and return the 20-byte
+ * address it vouches for. `trustedSigners` are EVM addresses of attesters.
+ */
+export function verifyEnsAttestation(
+  attestation: EnsAttestation,
+  destinationChainId: number,
+  trustedSigners: string[],
+  now: number = Math.floor(Date.now() / 1000)
+): Buffer {
+  if (!attestation.name.toLowerCase().endsWith('.eth')) {
+    throw new Error(`Not an ENS name: ${attestation.name}`);
+  }
+  if (attestation.chainId !== destinationChainId) {
+    throw new Error(
+      `ENS attestation is for chain ${attestation.chainId}, not ${destinationChainId}`
+    );
+  }
+  if (attestation.expiresAt <= now) {
+    throw new Error(`ENS attestation for ${attestation.name} has expired`);
+  }
+
+  const signer = verifyMessage(ensAttestationMessage(attestation), attestation.signature);
+  if (!trustedSigners.some((trusted) => getAddress(trusted) === signer)) {
+    throw new Error(`ENS attestation signed by untrusted attester ${signer}`);
+  }
+  return Buffer.from(getAddress(attestation.address).slice(2), 'hex');
+}
+
+/**
+ * Reject an address the destination chain could not deliver to, before the
+ * transfer is submitted and the NFT locked
+ */
+export function validateRecipientAddress(destinationChainId: number, address: Buffer): void {
+  if (address.length === 0 || address.length > MAX_RECIPIENT_ADDRESS_LENGTH) {
+    throw new Error(`Recipient address must be 1 to ${MAX_RECIPIENT_ADDRESS_LENGTH} bytes`);
+  }
+  const chain = SUPPORTED_CHAINS.find((supported) => supported.id === destinationChainId);
+  if (chain && address.length !== chain.addressLength) {
+    throw new Error(
+      `${chain.name} addresses are ${chain.addressLength} bytes, got ${address.length}`
+    );
+  }
+  if (address.every((byte) => byte === 0)) {
+    throw new Error('Recipient address is the zero address');
+  }
+}
+
+/**
+ * Turn what a user typed into recipient bytes for `destinationChainId`: a
+ * `.sol` name, a signed ENS attestation, a hex address (0x-prefixed unless
+ * it is a 20-byte EVM address), or a base58 Solana address. The result is validated against the destination.
+ */
+export async function resolveRecipient(
+  recipient: string | EnsAttestation,
+  destinationChainId: number,
+  connection: Connection,
+  trustedEnsSigners: string[] = []
+): Promise<Buffer> {
+  let address: Buffer;
+  if (typeof recipient !== 'string') {
+    address = verifyEnsAttestation(recipient, destinationChainId, trustedEnsSigners);
+  } else if (recipient.toLowerCase().endsWith('.sol')) {
+    address = (await resolveSolName(connection, recipient)).toBuffer();
+  } else if (recipient.toLowerCase().endsWith('.eth')) {
+    throw new Error(`ENS names need an attestation from a trusted resolver: ${recipient}`);
+  } else if (/^(0x)?([0-9a-fA-F]{2})+$/.test(recipient) && (recipient.startsWith('0x') || recipient.length === 40)) {
+    const hex = recipient.replace(/^0x/, '');
+    // Mixed case carries an EIP-55 checksum, which catches typos
+    if (hex.length === 40 && /[a-f]/.test(hex) && /[A-F]/.test(hex)) {
+      getAddress('0x' + hex);
+    }
+    address = Buffer.from(hex, 'hex');
+  } else {
+    try {
+      address = new PublicKey(recipient).toBuffer();
+    } catch (error) {
+      throw new Error(`Unrecognized recipient: ${recipient}`);
+    }
+  }
+
+  validateRecipientAddress(destinationChainId, address);
+  return address;
+}
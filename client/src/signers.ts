@@ -0,0 +1,122 @@
+import {
+  Connection,
+  PublicKey,
+  Transaction,
+  TransactionInstruction,
+  TransactionMessage,
+  VersionedTransaction,
+} from '@solana/web3.js';
+import * as multisig from '@sqds/multisig';
+
+/** Anything that can sign for a public key: a file keypair, a Ledger, a browser wallet */
+export interface SigningWallet {
+  publicKey: PublicKey;
+  signTransaction<T extends Transaction | VersionedTransaction>(tx: T): Promise<T>;
+  signAllTransactions<T extends Transaction | VersionedTransaction>(txs: T[]): Promise<T[]>;
+}
+
+/** Solana's BIP44 path for the first Ledger account */
+export const DEFAULT_LEDGER_PATH = "44'/501'/0'";
+
+/** The device calls `LedgerWallet` makes; matches `@ledgerhq/hw-app-solana` */
+export interface LedgerSolanaApp {
+  getAddress(path: string): Promise<{ address: Buffer }>;
+  signTransaction(path: string, message: Buffer): Promise<{ signature: Buffer }>;
+}
+
+/**
+ * A wallet whose key never leaves a Ledger. Each signature is confirmed on
+ * the device, so `signAllTransactions` prompts once per transaction.
+ */
+export class LedgerWallet implements SigningWallet {
+  constructor(
+    private readonly app: LedgerSolanaApp,
+    private readonly path: string,
+    public readonly publicKey: PublicKey
+  ) {}
+
+  /** Open the first Ledger on USB with the Solana app running */
+  static async connect(path: string = DEFAULT_LEDGER_PATH): Promise<LedgerWallet> {
+    // Loaded on demand so file-wallet users don't need the native HID module
+    const { default: Solana } = await import('@ledgerhq/hw-app-solana');
+    const { default: TransportNodeHid } = await import('@ledgerhq/hw-transport-node-hid');
+    const app = new Solana(await TransportNodeHid.create());
+    return LedgerWallet.fromApp(app, path);
+  }
+
+  static async fromApp(app: LedgerSolanaApp, path: string = DEFAULT_LEDGER_PATH): Promise<LedgerWallet> {
+    const { address } = await app.getAddress(path);
+    return new LedgerWallet(app, path, new PublicKey(address));
+  }
+
+  async signTransaction<T extends Transaction | VersionedTransaction>(tx: T): Promise<T> {
+    const message = tx instanceof VersionedTransaction
+      ? Buffer.from(tx.message.serialize())
+      : tx.serializeMessage();
+    const { signature } = await this.app.signTransaction(this.path, message);
+    tx.addSignature(this.publicKey, signature);
+    return tx;
+  }
+
+  async signAllTransactions<T extends Transaction | VersionedTransaction>(txs: T[]): Promise<T[]> {
+    for (const tx of txs) {
+      await this.signTransaction(tx);
+    }
+    return txs;
+  }
+}
+
+/** Vault of a Squads multisig; set it as the program authority to put admin actions behind the multisig */
+export function squadsVaultAddress(multisigPda: PublicKey, vaultIndex: number = 0): PublicKey {
+  const [vault] = multisig.getVaultPda({ multisigPda, index: vaultIndex });
+  return vault;
+}
+
+export interface SquadsProposal {
+  transactionIndex: bigint;
+  signature: string;
+}
+
+/**
+ * Propose `instructions` for a Squads multisig vault to execute, with
+ * `member`'s approval attached. The other members approve and execute it in
+ * Squads. Instructions must sign with the vault as authority.
+ */
+export async function proposeSquadsTransaction(
+  connection: Connection,
+  multisigPda: PublicKey,
+  member: SigningWallet,
+  instructions: TransactionInstruction[],
+  memo?: string,
+  vaultIndex: number = 0
+): Promise<SquadsProposal> {
+  const account = await multisig.accounts.Multisig.fromAccountAddress(connection, multisigPda);
+  const transactionIndex = BigInt(account.transactionIndex.toString()) + BigInt(1);
+  const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
+
+  const transactionMessage = new TransactionMessage({
+    payerKey: squadsVaultAddress(multisigPda, vaultIndex),
+    recentBlockhash: blockhash,
+    instructions,
+  });
+  const tx = new Transaction().add(
+    multisig.instructions.vaultTransactionCreate({
+      multisigPda,
+      transactionIndex,
+      creator: member.publicKey,
+      vaultIndex,
+      ephemeralSigners: 0,
+      transactionMessage,
+      memo,
+    }),
+    multisig.instructions.proposalCreate({ multisigPda, transactionIndex, creator: member.publicKey }),
+    multisig.instructions.proposalApprove({ multisigPda, transactionIndex, member: member.publicKey })
+  );
+  tx.feePayer = member.publicKey;
+  tx.recentBlockhash = blockhash;
+
+  const signed = await member.signTransaction(tx);
+  const signature = await connection.sendRawTransaction(signed.serialize());
+  await connection.confirmTransaction({ signature, blockhash, lastValidBlockHeight }, 'confirmed');
+  return { transactionIndex, signature };
+}
@@ -0,0 +1,61 @@
+import * as anchor from '@coral-xyz/anchor';
+import { PublicKey } from '@solana/web3.js';
+import { createHash } from 'crypto';
+
+/** Accounts per `export_state` call, matching the program's `MAX_EXPORT_PAGE` */
+export const EXPORT_PAGE_SIZE = 12;
+
+/** One program account in a state export */
+export interface ExportedAccountRecord {
+  address: string;
+  type: string | null; // Account type from the IDL, or null if the discriminator is unknown
+  lamports: number;
+  dataHash: string; // Hex sha256 of `data`, as attested by `export_state`
+  data: string; // Base64 of the raw account data, discriminator included
+  decoded: unknown; // Fields decoded with the IDL, or null
+}
+
+/**
+ * Every account the program owned at `slot`, in address order. `data` can
+ * be replayed into a redeployment; PDA addresses must be rederived under the
+ * new program ID.
+ */
+export interface StateExport {
+  programId: string;
+  slot: number;
+  exportedAt: string; // ISO timestamp
+  accounts: ExportedAccountRecord[];
+}
+
+/** Anchor's 8-byte discriminator for an account type */
+export function accountDiscriminator(typeName: string): Buffer {
+  return createHash('sha256').update(`account:${typeName}`).digest().subarray(0, 8);
+}
+
+export function sha256Hex(data: Buffer): string {
+  return createHash('sha256').update(data).digest('hex');
+}
+
+/** Convert decoded account fields to plain JSON: keys and numbers as strings, bytes as hex */
+export function toJsonValue(value: unknown): unknown {
+  if (value instanceof PublicKey) {
+    return value.toBase58();
+  }
+  if (anchor.BN.isBN(value)) {
+    return (value as anchor.BN).toString();
+  }
+  if (Buffer.isBuffer(value) || value instanceof Uint8Array) {
+    return Buffer.from(value).toString('hex');
+  }
+  if (Array.isArray(value)) {
+    const isBytes = value.length > 0
+      && value.every((item) => Number.isInteger(item) && item >= 0 && item <= 255);
+    return isBytes
+      ? Buffer.from(value).toString('hex')
+      : value.map(toJsonValue);
+  }
+  if (value !== null && typeof value === 'object') {
+    return Object.fromEntries(Object.entries(value).map(([key, field]) => [key, toJsonValue(field)]));
+  }
+  return value;
+}
@@ -0,0 +1,192 @@
+import * as anchor from '@coral-xyz/anchor';
+import { PublicKey, SystemProgram, Transaction, TransactionInstruction } from '@solana/web3.js';
+import {
+  TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddress,
+  createAssociatedTokenAccountIdempotentInstruction,
+} from '@solana/spl-token';
+import type { UniversalNftClient } from './client';
+import { EnsAttestation } from './recipient';
+import { PriorityFeeOptions } from './priority-fees';
+
+/** A transfer ready for the owner (and payer, if different) to sign */
+export interface BuiltTransfer {
+  transaction: Transaction; // Fee payer and recent blockhash set, unsigned
+  lastValidBlockHeight: number;
+  nonce: number;
+  transferRecord: PublicKey; // Where the program records the transfer
+}
+
+/**
+ * Assembles a `cross_chain_transfer` from just the mint, destination and
+ * recipient: it derives every PDA, reads the NFT's collection and the
+ * chain's fee and gate settings, and creates the treasury's fee token
+ * account if the chain charges in a token it has never received.
+ *
+ * ```typescript
+ * const { transaction } = await client.transfer(mint)
+ *   .toChain(ChainId.Ethereum)
+ *   .recipient('0x742d35Cc6634C0532925a3b844Bc454e4438f44e')
+ *   .build();
+ * await wallet.signTransaction(transaction);
+ * ```
+ */
+export class TransferBuilder {
+  private destinationChainId?: number;
+  private recipientAddress?: string | EnsAttestation;
+  private ownerKey?: PublicKey;
+  private payerKey?: PublicKey;
+  private tokenAccountKey?: PublicKey;
+  private nonceValue?: number;
+  private credentialKey?: PublicKey;
+  private priorityFee?: PriorityFeeOptions;
+
+  constructor(private readonly client: UniversalNftClient, private readonly mint: PublicKey) {}
+
+  toChain(chainId: number): this {
+    this.destinationChainId = chainId;
+    return this;
+  }
+
+  /** An address, `.sol` name or signed ENS resolution, as `resolveRecipient` accepts */
+  recipient(address: string | EnsAttestation): this {
+    this.recipientAddress = address;
+    return this;
+  }
+
+  /** Holder of the NFT; defaults to the client's wallet */
+  owner(owner: PublicKey): this {
+    this.ownerKey = owner;
+    return this;
+  }
+
+  /** Pays rent and fees instead of the owner, e.g. a sponsor */
+  payer(payer: PublicKey): this {
+    this.payerKey = payer;
+    return this;
+  }
+
+  /** Token account holding the NFT, if it isn't the owner's associated one */
+  tokenAccount(tokenAccount: PublicKey): this {
+    this.tokenAccountKey = tokenAccount;
+    return this;
+  }
+
+  /** Defaults to the current time in ms, or the next unused nonce if that is higher */
+  nonce(nonce: number): this {
+    this.nonceValue = nonce;
+    return this;
+  }
+
+  credential(credential: PublicKey): this {
+    this.credentialKey = credential;
+    return this;
+  }
+
+  /** Prepend compute budget instructions with an estimated priority fee */
+  withPriorityFee(options: PriorityFeeOptions = {}): this {
+    this.priorityFee = options;
+    return this;
+  }
+
+  async build(): Promise<BuiltTransfer> {
+    const { client, mint } = this;
+    const destinationChainId = this.destinationChainId;
+    if (destinationChainId === undefined || this.recipientAddress === undefined) {
+      throw new Error('A transfer needs a destination chain and a recipient');
+    }
+    const owner = this.ownerKey ?? client.provider.wallet.publicKey;
+    const payer = this.payerKey ?? owner;
+    const program = client.program;
+
+    const crossChainConfigPda = PublicKey.findProgramAddressSync(
+      [Buffer.from("cross_chain_config")],
+      client.programId
+    )[0];
+    const destinationChainConfigPda = PublicKey.findProgramAddressSync(
+      [Buffer.from("chain_config"), new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8)],
+      client.programId
+    )[0];
+    const nftMetadataPda = PublicKey.findProgramAddressSync(
+      [Buffer.from("nft_metadata"), mint.toBytes()],
+      client.programId
+    )[0];
+
+    const [crossChainConfig, chainConfig, nftMetadata] = await Promise.all([
+      program.account.crossChainConfig.fetch(crossChainConfigPda),
+      program.account.chainConfig.fetchNullable(destinationChainConfigPda),
+      program.account.nftMetadata.fetchNullable(nftMetadataPda),
+    ]);
+    if (!chainConfig) {
+      throw new Error(`Chain ${destinationChainId} is not registered`);
+    }
+    if (!nftMetadata) {
+      throw new Error(`${mint.toBase58()} is not a Universal NFT`);
+    }
+
+    const nonce = this.nonceValue
+      ?? Math.max(Date.now(), crossChainConfig.nonceCounter.toNumber() + 1);
+    const recipientBytes = Array.from(await client.resolveRecipient(this.recipientAddress, destinationChainId));
+    const feeAccounts = await client.findFeeAccounts(payer, destinationChainId);
+    const transferRecord = client.findTransferRecordAddress(mint, nonce);
+
+    const preInstructions: TransactionInstruction[] = this.priorityFee
+      ? await client.priorityFeeInstructions('cross_chain_transfer', {
+        ...this.priorityFee,
+        writableAccounts: this.priorityFee.writableAccounts ?? [mint, nftMetadataPda],
+      })
+      : [];
+    if (feeAccounts.treasuryFeeAccount && chainConfig.fee.mint) {
+      const treasuryFeeAccount = await client.provider.connection.getAccountInfo(feeAccounts.treasuryFeeAccount);
+      if (!treasuryFeeAccount) {
+        preInstructions.push(createAssociatedTokenAccountIdempotentInstruction(
+          payer,
+          feeAccounts.treasuryFeeAccount,
+          client.findFeeTreasuryAddress(),
+          chainConfig.fee.mint
+        ));
+      }
+    }
+
+    const instruction = await program.methods
+      .crossChainTransfer(new anchor.BN(destinationChainId), recipientBytes, new anchor.BN(nonce))
+      .accounts({
+        programState: client.findProgramStateAddress(),
+        crossChainConfig: crossChainConfigPda,
+        blocklist: PublicKey.findProgramAddressSync([Buffer.from("blocklist")], client.programId)[0],
+        optOutRegistry: PublicKey.findProgramAddressSync([Buffer.from("opt_out_registry")], client.programId)[0],
+        destinationChainConfig: destinationChainConfigPda,
+        nftMetadata: nftMetadataPda,
+        collectionConfig: nftMetadata.collection.equals(PublicKey.default)
+          ? null
+          : client.findCollectionConfigAddress(nftMetadata.collection),
+        collectionLedger: client.findCollectionLedgerAddress(
+          nftMetadata.originChainId.toNumber(),
+          Buffer.from(nftMetadata.originContract)
+        ),
+        transferRecord,
+        mint,
+        tokenAccount: this.tokenAccountKey ?? await getAssociatedTokenAddress(mint, owner, true),
+        owner,
+        ownerTransferIndex: client.findOwnerTransferIndexAddress(owner),
+        pendingTransfers: client.findPendingTransfersAddress(),
+        credential: this.credentialKey ?? null,
+        gateTokenAccount: await client.findGateTokenAccount(owner, destinationChainId),
+        stakeRecord: client.findStakeRecordAddress(mint),
+        localizedMetadata: await client.findLocalizedMetadataToCarry(mint),
+        ...feeAccounts,
+        feeReserve: client.findFeeReserveAddress(),
+        payer,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+
+    const { blockhash, lastValidBlockHeight } = await client.provider.connection.getLatestBlockhash();
+    const transaction = new Transaction().add(...preInstructions, instruction);
+    transaction.feePayer = payer;
+    transaction.recentBlockhash = blockhash;
+
+    return { transaction, lastValidBlockHeight, nonce, transferRecord };
+  }
+}
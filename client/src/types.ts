@@ -1,14 +1,40 @@
+import { PublicKey } from '@solana/web3.js';
+
 export interface NftMetadata {
   mint: string;
   originalOwner: string;
   currentOwner: string;
   metadataUri: string;
+  fallbackUri: string | null; // Second host for the metadata JSON
+  metadataMultihash: string | null; // Hex multihash decoded from an ipfs:// CID
   name: string;
   symbol: string;
   crossChainEnabled: boolean;
   isLocked: boolean;
   originChainId: string;
+  collection: string | null; // Collection mint, if the NFT belongs to one
   creationTimestamp: string;
+  childCount: number; // Equipped children; the NFT can't leave Solana while nonzero
+}
+
+export interface NftAppData {
+  mint: string;
+  appProgram: string | null; // Program the holder approved to write `data`
+  updatedAt: string;
+  data: Buffer; // 256 bytes, laid out by the app
+}
+
+export interface TrustedPeer {
+  chainId: string;
+  contract: string; // Hex address of the game contract
+  mintQuota: string; // Most achievements it may ever mint; 0 revokes it
+  minted: string;
+  registeredAt: string;
+}
+
+export interface SnapshotHolder {
+  mint: string;
+  owner: string; // Wallet holding the NFT when the snapshot was taken
 }
 
 export interface CrossChainTransfer {
@@ -19,9 +45,59 @@ export interface CrossChainTransfer {
   nonce: string;
   timestamp: string;
   status: number; // 0: Pending, 1: Completed, 2: Failed
+  destinationTxHash: string | null; // Set once delivery is acknowledged
+  completedAt: string;
+  feePayer: string;
+  feeMint: string | null; // Null when the fee was paid in lamports
+  feePaid: string; // Flat fee plus gas budget
+  gasBudget: string;
+  gasSpent: string; // Attested on delivery
+  feeRefunded: boolean;
+  stakedSeconds: string; // Checkpointed from the NFT's stake, credited back on failure
+}
+
+/**
+ * Byte offsets of fixed-size `NftMetadata` fields, including the 8-byte
+ * account discriminator, for `getProgramAccounts` memcmp filters
+ */
+export const NFT_METADATA_OFFSETS = {
+  mint: 8,
+  currentOwner: 40,
+  originalOwner: 72,
+  collection: 104,
+  wrappedMint: 136,
+  originChainId: 168,
+  originTokenId: 176,
+  creationTimestamp: 208,
+  crossChainEnabled: 216,
+  isLocked: 217,
+} as const;
+
+/** Byte offsets of fixed-size `CrossChainTransfer` fields, as above */
+export const TRANSFER_RECORD_OFFSETS = {
+  mint: 8,
+  originalOwner: 40,
+  status: 72,
+  destinationChainId: 73,
+  nonce: 81,
+  timestamp: 89,
+  completedAt: 97,
+  valueMint: 105,
+  feePayer: 145,
+  feeRefunded: 201,
+} as const;
+
+export interface TransferPreview {
+  messageHash: string; // Matches messageHash in the transfer's CrossChainTransferEvent
+  rentLamports: string; // Rent for accounts the transfer creates
+  transferRecord: string;
+  feeAmount: string; // Bridge fee, in lamports or base units of feeMint
+  feeMint: string | null;
+  payloadBytes: number; // Checked against the destination chain's maxPayloadBytes
 }
 
 export interface CrossChainReceipt {
+  version: number;
   originChainId: string;
   originTxHash: string;
   mint: string;
@@ -29,7 +105,14 @@ export interface CrossChainReceipt {
   originalOwner: string;
   nonce: string;
   timestamp: string;
-  tssSignature: string;
+  tssSignatureHash: string; // sha256 of the TSS signature; the full signature is in the receive event
+}
+
+/** Result of `findReceipt`: whether an inbound message has arrived */
+export interface ReceiptLookup {
+  address: string;
+  processed: boolean; // The message arrived and its NFT was delivered
+  receipt: CrossChainReceipt | null; // Null until the message is received or staged
 }
 
 export interface ProgramState {
@@ -37,6 +120,41 @@ export interface ProgramState {
   isInitialized: boolean;
   totalNftsMinted: string;
   crossChainTransfers: string;
+  isSunset: boolean; // Withdraw-only mode
+  eventSequence: string; // Sequence of the last emitted event
+  validationLimits: ValidationLimits;
+}
+
+/** Input length limits, tunable per deployment up to `VALIDATION_LIMITS_CEILING` */
+export interface ValidationLimits {
+  maxUriLen: number; // Bytes
+  maxNameLen: number; // Bytes, and characters once decoded
+  maxSymbolLen: number;
+  maxSignatureLen: number; // Bytes of a TSS signature
+}
+
+/** Limits a fresh deployment starts with */
+export const DEFAULT_VALIDATION_LIMITS: ValidationLimits = {
+  maxUriLen: 200,
+  maxNameLen: 32,
+  maxSymbolLen: 10,
+  maxSignatureLen: 128,
+};
+
+/** Largest limits the program accepts; bounded by account capacity */
+export const VALIDATION_LIMITS_CEILING: ValidationLimits = {
+  maxUriLen: 200,
+  maxNameLen: 32,
+  maxSymbolLen: 10,
+  maxSignatureLen: 512,
+};
+
+/** Layout version the program stamps on every event as `schemaVersion` */
+export const EVENT_SCHEMA_VERSION = 2;
+
+export interface ProgramVersion {
+  version: string; // Semantic version of the deployed build
+  gitHash: string; // Commit the build came from, or "unknown"
 }
 
 export interface CrossChainConfig {
@@ -45,8 +163,73 @@ export interface CrossChainConfig {
   chainId: string;
   isPaused: boolean;
   nonceCounter: string;
+  breakerMaxTransfers: string; // 0 when the circuit breaker is disabled
+  breakerWindowSlots: string;
+  breakerWindowStart: string;
+  breakerWindowCount: string;
+  credentialRequirement: CredentialRequirement | null; // null lets any owner bridge out
+  tokenGate: TokenGate | null; // Applies to chains without their own gate
+  relayerRequirement: RelayerRequirement | null; // null lets anyone relay
+}
+
+/** Fee charged for each NFT bridged to a chain */
+export interface BridgeFee {
+  mint: PublicKey | null; // null charges lamports
+  flatFee: string;
+  gasBudget: string; // Destination gas, collected up front with the flat fee
 }
 
+/** Minimum balance of an SPL token an owner must hold to bridge out */
+export interface TokenGate {
+  mint: PublicKey;
+  minAmount: string; // Base units
+}
+
+/** Bond relayers must hold to call receive and completion instructions */
+export interface RelayerRequirement {
+  bondMint: PublicKey | null; // null for a bond in lamports
+  minBond: string;
+  withdrawCooldownSeconds: number; // Measured from the relayer's last relayed message
+}
+
+/**
+ * Credential owners must present to bridge out: an account owned by `issuer`
+ * holding `credentialTag` at `tagOffset` and the owner at `subjectOffset`
+ */
+export interface CredentialRequirement {
+  issuer: PublicKey;
+  credentialTag: PublicKey;
+  tagOffset: number;
+  subjectOffset: number;
+}
+
+/** Program PDA whose surplus lamports `sweep_surplus_lamports` can recover */
+export type SweepTarget =
+  | { programState: {} }
+  | { crossChainConfig: {} }
+  | { blocklist: {} }
+  | { optOutRegistry: {} }
+  | { cpiAllowlist: {} }
+  | { auditLog: {} };
+
+/** Transport serving a registered chain, as stored in its chain config */
+export type BridgeAdapterKind =
+  | { zetaChain: {} }
+  | {
+      wormhole: {
+        coreBridge: PublicKey;
+        emitterChain: number; // Wormhole chain ID
+        emitterAddress: number[]; // 32 bytes
+      };
+    }
+  | {
+      layerZero: {
+        endpoint: PublicKey;
+        remoteEid: number; // LayerZero endpoint ID
+        peer: number[]; // 32 bytes
+      };
+    };
+
 export interface ChainInfo {
   id: number;
   name: string;
@@ -54,6 +237,28 @@ export interface ChainInfo {
   isSupported: boolean;
 }
 
+/** ZetaChain's chain IDs for Solana clusters; a deployment is initialized with its cluster's */
+export const SOLANA_CHAIN_IDS = {
+  mainnet: 900,
+  devnet: 901,
+  localnet: 902,
+} as const;
+
+/** Solana's chain ID before the program adopted ZetaChain's; older native NFTs carry it as their origin */
+export const LEGACY_SOLANA_CHAIN_ID = 7565164;
+
+export function isSolanaChainId(chainId: number): boolean {
+  return chainId === LEGACY_SOLANA_CHAIN_ID || Object.values(SOLANA_CHAIN_IDS).some((id) => id === chainId);
+}
+
+/** ZetaChain chain IDs of the supported destinations */
+export enum ChainId {
+  Ethereum = 1,
+  BSC = 56,
+  Polygon = 137,
+  ZetaChain = 1001,
+}
+
 export const SUPPORTED_CHAINS: ChainInfo[] = [
   { id: 1, name: "Ethereum", addressLength: 20, isSupported: true },
   { id: 56, name: "BSC", addressLength: 20, isSupported: true },
@@ -66,3 +271,267 @@ export enum TransferStatus {
   Completed = 1,
   Failed = 2,
 }
+
+/** Program error ranges, matching the grouping in the program's error.rs */
+export enum ErrorCategory {
+  Config = 'config',
+  Mint = 'mint',
+  Transfer = 'transfer',
+  Receive = 'receive',
+  Security = 'security',
+  Unknown = 'unknown',
+}
+
+/** Offset Anchor adds to every custom program error */
+export const ANCHOR_ERROR_OFFSET = 6000;
+
+/**
+ * Buckets a program error code by its numeric range. Accepts either the code
+ * reported on chain (e.g. 6301) or the range-relative code (e.g. 301).
+ */
+export function errorCodeToCategory(code: number): ErrorCategory {
+  const relative = code >= ANCHOR_ERROR_OFFSET ? code - ANCHOR_ERROR_OFFSET : code;
+  switch (Math.floor(relative / 100)) {
+    case 1:
+      return ErrorCategory.Config;
+    case 2:
+      return ErrorCategory.Mint;
+    case 3:
+      return ErrorCategory.Transfer;
+    case 4:
+      return ErrorCategory.Receive;
+    case 5:
+      return ErrorCategory.Security;
+    default:
+      return ErrorCategory.Unknown;
+  }
+}
+
+/** Outbound transfer backlog, from the program's `PendingTransfers` registry */
+export interface PendingTransfers {
+  pending: string; // Created and neither completed nor reverted
+  totalCreated: string;
+  totalCompleted: string;
+  totalReverted: string;
+  lastUpdated: string;
+  recent: {
+    mint: string;
+    nonce: string;
+    status: TransferStatus; // The status the transfer moved to
+    timestamp: string;
+  }[]; // Oldest first
+}
+
+export interface CollectionLedger {
+  originChainId: string;
+  originContract: string;
+  nativeLocked: string; // Solana-native NFTs locked while bridged out
+  wrappedSupply: string; // Foreign NFTs currently held on Solana
+  totalOutbound: string;
+  totalInbound: string;
+  lastUpdated: string;
+}
+
+/** One creator's cut of a collection royalty */
+export interface RoyaltyShare {
+  address: PublicKey;
+  share: number; // Percent of the royalty; a collection's shares add up to 100
+}
+
+export interface CollectionRoyalty {
+  collectionMint: string;
+  basisPoints: number; // Of the sale price; 0 waives royalties
+  creators: { address: string; share: number }[];
+  updatedBy: string;
+  updatedAt: string;
+}
+
+/** Collection settings a creator set approves, in the order of the program's `CollectionAction` enum */
+export const COLLECTION_ACTIONS = [
+  'setBridging',
+  'setVelocityLimit',
+  'setRoyalty',
+  'setCreatorSet',
+] as const;
+
+export type CollectionAction = typeof COLLECTION_ACTIONS[number];
+
+/** A collection action with the arguments creators approve it for */
+export type CollectionActionArgs =
+  | { action: 'setBridging'; enabled: boolean }
+  | { action: 'setVelocityLimit'; dailyLimit: number }
+  | { action: 'setRoyalty'; basisPoints: number; creators: RoyaltyShare[] }
+  | { action: 'setCreatorSet'; creators: PublicKey[]; threshold: number };
+
+export interface CreatorSet {
+  collectionMint: string;
+  creators: string[];
+  threshold: number; // 0 when inactive
+  version: number;
+  updatedAt: number;
+}
+
+export interface SymbolReservation {
+  symbol: string; // As first registered
+  creator: string; // Collection creator, or the collection mint of a foreign collection
+  collectionMint: string; // Collection that first registered it
+  reservedAt: number;
+}
+
+/** A collection's trust badge, as returned by `get_collection_verification` */
+export interface CollectionVerification {
+  collectionMint: string;
+  registered: boolean; // False when the collection has neither a config nor a foreign registration
+  verified: boolean;
+  updatedBy: string; // Default key until the badge is first set
+  updatedAt: number;
+}
+
+export interface DutchAuction {
+  seller: string;
+  mint: string;
+  startPrice: string; // Lamports
+  floorPrice: string;
+  decayPerSecond: string;
+  startTime: string;
+  currentPrice: string; // At the local clock; the program charges the price at its own
+}
+
+export interface Offer {
+  buyer: string;
+  mint: string;
+  amount: string; // Lamports
+  expiresAt: string;
+  expired: boolean; // By the local clock; anyone may refund an expired offer
+}
+
+export interface FractionVault {
+  mint: string;
+  depositor: string;
+  fractionMint: string;
+  totalSupply: string; // Redeeming the NFT burns all of them
+  createdAt: string;
+}
+
+export interface StakeRecord {
+  owner: string;
+  mint: string;
+  collection: string;
+  stakedAt: string;
+  lastClaimedAt: string;
+  totalClaimed: string;
+  bridgedAt: string; // When the NFT was bridged out with the stake; "0" while on Solana
+  multiplierBps: number; // The collection's reward multiplier; 10000 is 1x
+  pendingRewards: string; // Claimable now, in reward base units
+}
+
+export interface LocalizedUri {
+  locale: string; // BCP 47 tag, e.g. "pt-BR"
+  uri: string;
+}
+
+export interface Redemption {
+  mint: string; // Burned by the redemption
+  redeemer: string;
+  collection: string;
+  originChainId: number;
+  claimCodeHash: string; // Hex sha256 of the redeemer's claim code
+  redeemedAt: number;
+  metadataUri: string;
+  name: string;
+  details: string;
+}
+
+export interface TicketCheckIn {
+  mint: string;
+  collection: string;
+  holder: string; // Who presented the ticket
+  validator: string | null; // Null when the holder checked in alone
+  checkedInAt: number;
+}
+
+export interface MetadataVariant {
+  minValue: number; // Selected once the oracle value reaches this
+  uri: string;
+}
+
+export interface DynamicMetadata {
+  feed: string; // The oracle feed account
+  activeVariant: number;
+  lastValue: number; // Feed value that selected activeVariant
+  updatedAt: number; // 0 until the first update from the oracle
+  variants: MetadataVariant[];
+}
+
+export interface MintGuard {
+  feeLamports: number; // Charged to the payer of each open mint
+  powDifficulty: number; // Leading zero bits of proof of work on the mint address
+  updatedAt: number;
+}
+
+export interface MintLimits {
+  maxPerWallet: number; // 0 is unlimited
+  phase: number;
+  phaseMaxPerWallet: number; // 0 is unlimited
+  updatedAt: number;
+}
+
+export interface WalletMintCount {
+  totalMinted: number;
+  phase: number; // Phase phaseMinted counts for
+  phaseMinted: number;
+}
+
+/** Admin actions in the order of the program's `AuditAction` enum */
+export const AUDIT_ACTIONS = [
+  'updateGateway',
+  'rotateTss',
+  'setPaused',
+  'setSunset',
+  'setChainPaused',
+  'registerChain',
+  'updateBlocklist',
+  'setCpiAllowlist',
+  'setChainAdapter',
+  'reconcileNft',
+  'setCircuitBreaker',
+  'setCredentialRequirement',
+  'setTokenGate',
+  'setChainTokenGate',
+  'setChainFee',
+  'withdrawFees',
+  'setChainFinalityDelay',
+  'cancelNativeReturn',
+  'setChainOptimisticWindow',
+  'resolveChallenge',
+  'setRelayerRequirement',
+  'slashRelayer',
+  'setChainExecutionBounty',
+  'sweepSurplusLamports',
+  'setValidationLimits',
+  'setTrustedPeer',
+  'setChainMaxPayload',
+  'setRewardConfig',
+  'setRewardMultiplier',
+  'configureOracleFeed',
+  'setMintGuard',
+  'setMintLimits',
+  'setSymbolRegistry',
+  'setCollectionVerified',
+  'setOperator',
+  'reassignCollectionSymbol',
+  'revertCrossChainTransfer',
+  'revertBundleTransfer',
+] as const;
+
+export type AuditAction = typeof AUDIT_ACTIONS[number];
+
+export interface AuditEntry {
+  index: number;
+  action: AuditAction;
+  actor: string;
+  detailsHash: string; // sha256 of the Borsh-encoded action arguments
+  prevHash: string;
+  entryHash: string; // Chains prevHash with this entry's fields
+  timestamp: string;
+}
@@ -0,0 +1,338 @@
+//! CPI interface for programs that bridge NFTs through Universal NFT.
+//!
+//! A game or marketplace that holds NFTs in a PDA can send them cross-chain by
+//! invoking `cross_chain_transfer` with the PDA as `owner`, signing with its
+//! seeds. A separate `payer` funds the accounts the transfer creates, so the
+//! owner PDA may hold data.
+//!
+//! ```ignore
+//! use universal_nft_interface::{pda, CrossChainTransferCpi};
+//!
+//! CrossChainTransferCpi {
+//!     universal_nft_program: ctx.accounts.universal_nft_program.to_account_info(),
+//!     program_state: ctx.accounts.program_state.to_account_info(),
+//!     // ... remaining accounts, see the field docs ...
+//! }
+//! .invoke_signed(destination_chain_id, recipient_address, nonce, &[&[b"vault", &[vault_bump]]])?;
+//! ```
+//!
+//! An application the holder approved with `set_app_data_program` writes the
+//! NFT's app data with [`WriteAppDataCpi`], signing as
+//! [`pda::app_data_authority`].
+
+use anchor_lang::prelude::*;
+
+pub use universal_nft::cpi::accounts::{InitiateCrossChainTransfer, WriteAppData};
+pub use universal_nft::state::NftAppData;
+pub use universal_nft::ID;
+/// PDA seeds and limits, as listed in the program's IDL
+pub use universal_nft::constants;
+
+/// Addresses of the Universal NFT accounts a transfer touches
+pub mod pda {
+    use anchor_lang::prelude::Pubkey;
+    use super::constants::*;
+
+    fn find(seeds: &[&[u8]]) -> Pubkey {
+        Pubkey::find_program_address(seeds, &universal_nft::ID).0
+    }
+
+    pub fn program_state() -> Pubkey {
+        find(&[PROGRAM_STATE_SEED])
+    }
+
+    pub fn cross_chain_config() -> Pubkey {
+        find(&[CROSS_CHAIN_CONFIG_SEED])
+    }
+
+    pub fn blocklist() -> Pubkey {
+        find(&[BLOCKLIST_SEED])
+    }
+
+    pub fn opt_out_registry() -> Pubkey {
+        find(&[OPT_OUT_REGISTRY_SEED])
+    }
+
+    /// Hot key the program authority delegated fees and limits to
+    pub fn operator_config() -> Pubkey {
+        find(&[OPERATOR_CONFIG_SEED])
+    }
+
+    pub fn chain_config(chain_id: u64) -> Pubkey {
+        find(&[CHAIN_CONFIG_SEED, &chain_id.to_le_bytes()])
+    }
+
+    pub fn nft_metadata(mint: &Pubkey) -> Pubkey {
+        find(&[NFT_METADATA_SEED, mint.as_ref()])
+    }
+
+    pub fn collection_config(collection_mint: &Pubkey) -> Pubkey {
+        find(&[COLLECTION_CONFIG_SEED, collection_mint.as_ref()])
+    }
+
+    /// Royalty paid to a collection's creators on sales
+    pub fn collection_royalty(collection_mint: &Pubkey) -> Pubkey {
+        find(&[COLLECTION_ROYALTY_SEED, collection_mint.as_ref()])
+    }
+
+    /// Creators who jointly manage a collection's settings
+    pub fn creator_set(collection_mint: &Pubkey) -> Pubkey {
+        find(&[CREATOR_SET_SEED, collection_mint.as_ref()])
+    }
+
+    /// Approvals gathered for one `CollectionAction`, passed as its `u8`
+    /// index, with the arguments hashing to `args_hash`
+    pub fn creator_approval(collection_mint: &Pubkey, action: u8, args_hash: &[u8; 32]) -> Pubkey {
+        find(&[CREATOR_APPROVAL_SEED, collection_mint.as_ref(), &[action], args_hash])
+    }
+
+    /// Reservation of a collection symbol, keyed by `SymbolReservation::seed`
+    pub fn symbol_reservation(symbol: &str) -> Pubkey {
+        find(&[SYMBOL_RESERVATION_SEED, &universal_nft::state::SymbolReservation::seed(symbol)])
+    }
+
+    pub fn dutch_auction(mint: &Pubkey) -> Pubkey {
+        find(&[DUTCH_AUCTION_SEED, mint.as_ref()])
+    }
+
+    /// Holds a Dutch-auctioned NFT until it sells
+    pub fn auction_escrow(mint: &Pubkey) -> Pubkey {
+        find(&[AUCTION_ESCROW_SEED, mint.as_ref()])
+    }
+
+    pub fn offer(mint: &Pubkey, buyer: &Pubkey) -> Pubkey {
+        find(&[OFFER_SEED, mint.as_ref(), buyer.as_ref()])
+    }
+
+    pub fn delegate_listing(mint: &Pubkey) -> Pubkey {
+        find(&[DELEGATE_LISTING_SEED, mint.as_ref()])
+    }
+
+    /// Freeze authority of the NFTs the program mints
+    pub fn freeze_authority() -> Pubkey {
+        find(&[FREEZE_AUTHORITY_SEED])
+    }
+
+    pub fn fraction_vault(mint: &Pubkey) -> Pubkey {
+        find(&[FRACTION_VAULT_SEED, mint.as_ref()])
+    }
+
+    /// SPL mint of a fractionalized NFT's fractions
+    pub fn fraction_mint(mint: &Pubkey) -> Pubkey {
+        find(&[FRACTION_MINT_SEED, mint.as_ref()])
+    }
+
+    pub fn reward_config() -> Pubkey {
+        find(&[REWARD_CONFIG_SEED])
+    }
+
+    /// SPL mint staking rewards are paid in; the reward config is its authority
+    pub fn reward_mint() -> Pubkey {
+        find(&[REWARD_MINT_SEED])
+    }
+
+    pub fn reward_multiplier(collection_mint: &Pubkey) -> Pubkey {
+        find(&[REWARD_MULTIPLIER_SEED, collection_mint.as_ref()])
+    }
+
+    pub fn stake_record(mint: &Pubkey) -> Pubkey {
+        find(&[STAKE_RECORD_SEED, mint.as_ref()])
+    }
+
+    pub fn localized_metadata(mint: &Pubkey) -> Pubkey {
+        find(&[LOCALIZED_METADATA_SEED, mint.as_ref()])
+    }
+
+    pub fn redemption(mint: &Pubkey) -> Pubkey {
+        find(&[REDEMPTION_SEED, mint.as_ref()])
+    }
+
+    pub fn ticket_config(collection_mint: &Pubkey) -> Pubkey {
+        find(&[TICKET_CONFIG_SEED, collection_mint.as_ref()])
+    }
+
+    pub fn ticket_check_in(mint: &Pubkey) -> Pubkey {
+        find(&[TICKET_CHECK_IN_SEED, mint.as_ref()])
+    }
+
+    pub fn mint_guard() -> Pubkey {
+        find(&[MINT_GUARD_SEED])
+    }
+
+    pub fn mint_limits() -> Pubkey {
+        find(&[MINT_LIMITS_SEED])
+    }
+
+    pub fn wallet_mint_counter(wallet: &Pubkey) -> Pubkey {
+        find(&[WALLET_MINT_COUNTER_SEED, wallet.as_ref()])
+    }
+
+    pub fn oracle_feed(feed_id: &[u8; 32]) -> Pubkey {
+        find(&[ORACLE_FEED_SEED, feed_id])
+    }
+
+    pub fn dynamic_metadata(mint: &Pubkey) -> Pubkey {
+        find(&[DYNAMIC_METADATA_SEED, mint.as_ref()])
+    }
+
+    /// Solana-native NFTs use the Solana chain id with an empty contract
+    pub fn collection_ledger(origin_chain_id: u64, origin_contract: &[u8]) -> Pubkey {
+        find(&[COLLECTION_LEDGER_SEED, &origin_chain_id.to_le_bytes(), origin_contract])
+    }
+
+    pub fn transfer_record(mint: &Pubkey, nonce: u64) -> Pubkey {
+        find(&[CROSS_CHAIN_TRANSFER_SEED, mint.as_ref(), &nonce.to_le_bytes()])
+    }
+
+    pub fn owner_transfer_index(owner: &Pubkey) -> Pubkey {
+        find(&[OWNER_TRANSFER_INDEX_SEED, owner.as_ref()])
+    }
+
+    pub fn pending_transfers() -> Pubkey {
+        find(&[PENDING_TRANSFERS_SEED])
+    }
+
+    /// Collects bridge fees; SPL fees go to its associated token account for the fee mint
+    pub fn fee_treasury() -> Pubkey {
+        find(&[FEE_TREASURY_SEED])
+    }
+
+    /// Refundable fees the fee treasury must keep
+    pub fn fee_reserve() -> Pubkey {
+        find(&[FEE_RESERVE_SEED])
+    }
+
+    pub fn app_data(mint: &Pubkey) -> Pubkey {
+        find(&[APP_DATA_SEED, mint.as_ref()])
+    }
+
+    /// PDA of `app_program` that signs its app data writes for `mint`, with its bump
+    pub fn app_data_authority(app_program: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[APP_DATA_AUTHORITY_SEED, mint.as_ref()], app_program)
+    }
+}
+
+/// Accounts for a `cross_chain_transfer` CPI. Addresses are validated by the
+/// Universal NFT program; use [`pda`] to derive them.
+pub struct CrossChainTransferCpi<'info> {
+    pub universal_nft_program: AccountInfo<'info>,
+    pub program_state: AccountInfo<'info>,
+    /// Writable; records the accepted nonce
+    pub cross_chain_config: AccountInfo<'info>,
+    pub blocklist: AccountInfo<'info>,
+    pub opt_out_registry: AccountInfo<'info>,
+    /// `pda::chain_config(destination_chain_id)`
+    pub destination_chain_config: AccountInfo<'info>,
+    /// Writable; `pda::nft_metadata(mint)`
+    pub nft_metadata: AccountInfo<'info>,
+    /// Writable; required when the NFT belongs to a collection
+    pub collection_config: Option<AccountInfo<'info>>,
+    /// Writable; keyed by the NFT's origin chain and contract
+    pub collection_ledger: AccountInfo<'info>,
+    /// Writable; `pda::transfer_record(mint, nonce)`
+    pub transfer_record: AccountInfo<'info>,
+    /// Writable; `pda::owner_transfer_index(owner)`
+    pub owner_transfer_index: AccountInfo<'info>,
+    /// Writable; `pda::pending_transfers()`
+    pub pending_transfers: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    /// Token account holding the NFT, owned by `owner`
+    pub token_account: AccountInfo<'info>,
+    /// Holder of the NFT; typically the calling program's PDA
+    pub owner: AccountInfo<'info>,
+    /// Required when the deployment sets a credential requirement
+    pub credential: Option<AccountInfo<'info>>,
+    /// Owner's account of the gate mint, when a token gate applies
+    pub gate_token_account: Option<AccountInfo<'info>>,
+    /// Writable; `pda::stake_record(mint)`, whether or not the NFT is staked
+    pub stake_record: AccountInfo<'info>,
+    /// `pda::localized_metadata(mint)`, when the NFT has localized URIs to carry
+    pub localized_metadata: Option<AccountInfo<'info>>,
+    /// Writable; `pda::fee_treasury()`, when the destination charges a lamport fee
+    pub fee_treasury: Option<AccountInfo<'info>>,
+    /// Writable; the payer's account of the fee mint, for SPL fees
+    pub payer_fee_account: Option<AccountInfo<'info>>,
+    /// Writable; the fee treasury's associated token account, for SPL fees
+    pub treasury_fee_account: Option<AccountInfo<'info>>,
+    /// Writable; `pda::fee_reserve()`
+    pub fee_reserve: AccountInfo<'info>,
+    /// Writable signer funding the transfer record and, on first use, the transfer index and backlog registry
+    pub payer: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> CrossChainTransferCpi<'info> {
+    /// Invoke `cross_chain_transfer`, signing for a PDA `owner` (and `payer`,
+    /// if it is also a PDA) with `signer_seeds`
+    pub fn invoke_signed(
+        self,
+        destination_chain_id: u64,
+        recipient_address: Vec<u8>,
+        nonce: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let accounts = InitiateCrossChainTransfer {
+            program_state: self.program_state,
+            cross_chain_config: self.cross_chain_config,
+            blocklist: self.blocklist,
+            opt_out_registry: self.opt_out_registry,
+            destination_chain_config: self.destination_chain_config,
+            nft_metadata: self.nft_metadata,
+            collection_config: self.collection_config,
+            collection_ledger: self.collection_ledger,
+            transfer_record: self.transfer_record,
+            owner_transfer_index: self.owner_transfer_index,
+            pending_transfers: self.pending_transfers,
+            mint: self.mint,
+            token_account: self.token_account,
+            owner: self.owner,
+            credential: self.credential,
+            gate_token_account: self.gate_token_account,
+            stake_record: self.stake_record,
+            localized_metadata: self.localized_metadata,
+            fee_treasury: self.fee_treasury,
+            payer_fee_account: self.payer_fee_account,
+            treasury_fee_account: self.treasury_fee_account,
+            fee_reserve: self.fee_reserve,
+            payer: self.payer,
+            token_program: self.token_program,
+            system_program: self.system_program,
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.universal_nft_program, accounts, signer_seeds);
+        universal_nft::cpi::cross_chain_transfer(cpi_ctx, destination_chain_id, recipient_address, nonce)
+    }
+
+    /// Invoke `cross_chain_transfer` when `owner` and `payer` signed the outer transaction
+    pub fn invoke(self, destination_chain_id: u64, recipient_address: Vec<u8>, nonce: u64) -> Result<()> {
+        self.invoke_signed(destination_chain_id, recipient_address, nonce, &[])
+    }
+}
+
+/// Accounts for a `write_app_data` CPI from the approved application program
+pub struct WriteAppDataCpi<'info> {
+    pub universal_nft_program: AccountInfo<'info>,
+    /// Writable
+    pub program_state: AccountInfo<'info>,
+    /// `pda::nft_metadata(mint)`
+    pub nft_metadata: AccountInfo<'info>,
+    /// Writable; `pda::app_data(mint)`
+    pub app_data: AccountInfo<'info>,
+    /// `pda::app_data_authority(calling program, mint)`
+    pub app_authority: AccountInfo<'info>,
+}
+
+impl<'info> WriteAppDataCpi<'info> {
+    /// Invoke `write_app_data`, signing as the app authority PDA, whose seeds
+    /// are `[NftAppData::AUTHORITY_SEED, mint, bump]`
+    pub fn invoke_signed(self, offset: u16, bytes: Vec<u8>, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let accounts = WriteAppData {
+            program_state: self.program_state,
+            nft_metadata: self.nft_metadata,
+            app_data: self.app_data,
+            app_authority: self.app_authority,
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.universal_nft_program, accounts, signer_seeds);
+        universal_nft::cpi::write_app_data(cpi_ctx, offset, bytes)
+    }
+}
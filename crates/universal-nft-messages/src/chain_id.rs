@@ -0,0 +1,38 @@
+//! Chain IDs used in cross-chain messages, as published by ZetaChain
+
+/// Solana clusters. A deployment is initialized with its own cluster's ID,
+/// which is the chain ID it signs messages under.
+pub const SOLANA_MAINNET: u64 = 900;
+pub const SOLANA_DEVNET: u64 = 901;
+pub const SOLANA_LOCALNET: u64 = 902;
+
+/// The ID this program used for Solana before adopting ZetaChain's. NFTs
+/// minted under it still carry it as their origin chain.
+pub const LEGACY_SOLANA: u64 = 7565164;
+
+pub const ETHEREUM: u64 = 1;
+pub const BNB_SMART_CHAIN: u64 = 56;
+pub const POLYGON: u64 = 137;
+pub const ZETACHAIN: u64 = 1001;
+
+/// Every chain ID above with its name
+pub const CHAINS: &[(u64, &str)] = &[
+    (SOLANA_MAINNET, "Solana"),
+    (SOLANA_DEVNET, "Solana Devnet"),
+    (SOLANA_LOCALNET, "Solana Localnet"),
+    (LEGACY_SOLANA, "Solana (legacy ID)"),
+    (ETHEREUM, "Ethereum"),
+    (BNB_SMART_CHAIN, "BNB Smart Chain"),
+    (POLYGON, "Polygon"),
+    (ZETACHAIN, "ZetaChain"),
+];
+
+/// Whether `id` names a Solana cluster, the legacy ID included
+pub const fn is_solana(id: u64) -> bool {
+    matches!(id, SOLANA_MAINNET | SOLANA_DEVNET | SOLANA_LOCALNET | LEGACY_SOLANA)
+}
+
+/// The name of a known chain
+pub fn name(id: u64) -> Option<&'static str> {
+    CHAINS.iter().find(|(chain, _)| *chain == id).map(|(_, name)| *name)
+}
@@ -0,0 +1,310 @@
+//! Decoding messages back into payloads.
+//!
+//! Bodies start with a payload version byte. Decoders accept every version in
+//! [`SUPPORTED_PAYLOAD_VERSIONS`], so messages signed before an upgrade still
+//! decode after it, and reject anything else with
+//! [`DecodeError::UnsupportedPayloadVersion`] instead of misreading it.
+//!
+//! ```
+//! use universal_nft_messages::{chain_id, FailureAck, SigningDomain};
+//!
+//! let domain = SigningDomain { program_id: [7; 32], chain_id: chain_id::SOLANA_MAINNET };
+//! let ack = FailureAck {
+//!     mint: [1; 32],
+//!     nonce: 42,
+//!     destination_chain_id: chain_id::ETHEREUM,
+//!     recipient_address: &[0xab; 20],
+//! };
+//! let message = domain.message(&ack);
+//! assert_eq!(domain.decode::<FailureAck>(&message), Ok(ack));
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{
+    LocalizedUris, OutboundBundle, OutboundBundleItem, OutboundTransfer, OutboundTransferWithValue,
+    SignedMessageType, SignedPayload, SigningDomain, PAYLOAD_VERSION, SIGNING_DOMAIN_TAG,
+    SIGNING_DOMAIN_VERSION,
+};
+
+/// Payload versions the decoders understand, oldest first
+pub const SUPPORTED_PAYLOAD_VERSIONS: &[u8] = &[1, 2, 3, 4, 5, PAYLOAD_VERSION];
+
+/// Why a message could not be decoded
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The message ended before a field was complete
+    Truncated,
+    /// Bytes were left over after the last field
+    TrailingBytes,
+    /// A string field is not valid UTF-8
+    InvalidUtf8,
+    /// The message does not start with `SIGNING_DOMAIN_TAG`
+    TagMismatch,
+    /// The message is for another program or chain
+    DomainMismatch,
+    UnknownMessageType(u8),
+    MessageTypeMismatch {
+        expected: SignedMessageType,
+        found: SignedMessageType,
+    },
+    UnsupportedDomainVersion(u8),
+    UnsupportedPayloadVersion(u8),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "message is truncated"),
+            Self::TrailingBytes => write!(f, "message has trailing bytes"),
+            Self::InvalidUtf8 => write!(f, "string field is not valid UTF-8"),
+            Self::TagMismatch => write!(f, "message does not start with the Universal NFT tag"),
+            Self::DomainMismatch => write!(f, "message is for another program or chain"),
+            Self::UnknownMessageType(found) => write!(f, "unknown message type {}", found),
+            Self::MessageTypeMismatch { expected, found } => {
+                write!(f, "expected a {:?} message, found {:?}", expected, found)
+            }
+            Self::UnsupportedDomainVersion(found) => {
+                write!(f, "unsupported signing domain version {}", found)
+            }
+            Self::UnsupportedPayloadVersion(found) => {
+                write!(f, "unsupported payload version {}", found)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+/// Cursor over a message body; reads the encodings `encode_body` writes
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        if self.bytes.len() < len {
+            return Err(DecodeError::Truncated);
+        }
+        let (head, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    pub fn array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    pub fn u8(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    pub fn u16(&mut self) -> Result<u16, DecodeError> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    pub fn u64(&mut self) -> Result<u64, DecodeError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    pub fn i64(&mut self) -> Result<i64, DecodeError> {
+        Ok(i64::from_le_bytes(self.array()?))
+    }
+
+    /// Length-prefixed byte string
+    pub fn bytes(&mut self) -> Result<&'a [u8], DecodeError> {
+        let len = u32::from_le_bytes(self.array()?);
+        let len = usize::try_from(len).map_err(|_| DecodeError::Truncated)?;
+        self.take(len)
+    }
+
+    /// Length-prefixed UTF-8 string
+    pub fn str(&mut self) -> Result<&'a str, DecodeError> {
+        core::str::from_utf8(self.bytes()?).map_err(|_| DecodeError::InvalidUtf8)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Fail unless every byte was read
+    pub fn finish(self) -> Result<(), DecodeError> {
+        if self.bytes.is_empty() {
+            Ok(())
+        } else {
+            Err(DecodeError::TrailingBytes)
+        }
+    }
+}
+
+/// A payload that can be read back from its body
+pub trait DecodePayload<'a>: SignedPayload + Sized {
+    /// Read a body of payload version `version`, one of `SUPPORTED_PAYLOAD_VERSIONS`
+    fn decode_body(version: u8, reader: &mut Reader<'a>) -> Result<Self, DecodeError>;
+}
+
+/// Domain prefix of a signed message
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MessageHeader {
+    pub domain: SigningDomain,
+    pub message_type: SignedMessageType,
+}
+
+impl MessageHeader {
+    /// Split `message` into its prefix and body, checking the tag and domain version
+    pub fn parse(message: &[u8]) -> Result<(Self, &[u8]), DecodeError> {
+        let mut reader = Reader::new(message);
+        read_tag(&mut reader)?;
+        let program_id = reader.array()?;
+        let chain_id = reader.u64()?;
+        let message_type = reader.u8()?;
+        let message_type =
+            SignedMessageType::try_from(message_type).map_err(DecodeError::UnknownMessageType)?;
+        let domain_version = reader.u8()?;
+        if domain_version != SIGNING_DOMAIN_VERSION {
+            return Err(DecodeError::UnsupportedDomainVersion(domain_version));
+        }
+
+        let header = Self {
+            domain: SigningDomain { program_id, chain_id },
+            message_type,
+        };
+        Ok((header, reader.bytes))
+    }
+}
+
+impl SigningDomain {
+    /// Decode a message built by [`SigningDomain::message`] for this domain
+    pub fn decode<'a, P: DecodePayload<'a>>(&self, message: &'a [u8]) -> Result<P, DecodeError> {
+        let (header, body) = MessageHeader::parse(message)?;
+        if header.domain != *self {
+            return Err(DecodeError::DomainMismatch);
+        }
+        if header.message_type != P::MESSAGE_TYPE {
+            return Err(DecodeError::MessageTypeMismatch {
+                expected: P::MESSAGE_TYPE,
+                found: header.message_type,
+            });
+        }
+
+        let mut reader = Reader::new(body);
+        let version = read_payload_version(&mut reader)?;
+        let payload = P::decode_body(version, &mut reader)?;
+        reader.finish()?;
+        Ok(payload)
+    }
+
+    /// Decode an `OutboundTransfer` message built by
+    /// [`SigningDomain::outbound_message`] for this domain
+    pub fn decode_outbound<'a>(&self, message: &'a [u8]) -> Result<OutboundTransfer<'a>, DecodeError> {
+        let (_, transfer, reader) = self.read_outbound(message)?;
+        reader.finish()?;
+        Ok(transfer)
+    }
+
+    /// Decode an `OutboundTransferWithValue` message built by
+    /// [`SigningDomain::outbound_message`] for this domain; messages before
+    /// payload version 6 carry no value and fail with `UnsupportedPayloadVersion`
+    pub fn decode_outbound_with_value<'a>(
+        &self,
+        message: &'a [u8],
+    ) -> Result<OutboundTransferWithValue<'a>, DecodeError> {
+        let (version, transfer, mut reader) = self.read_outbound(message)?;
+        if version < 6 {
+            return Err(DecodeError::UnsupportedPayloadVersion(version));
+        }
+        let outbound = OutboundTransferWithValue {
+            transfer,
+            value_mint: reader.array()?,
+            value_amount: reader.u64()?,
+        };
+        reader.finish()?;
+        Ok(outbound)
+    }
+
+    /// Decode an `OutboundBundle` message built by
+    /// [`SigningDomain::outbound_message`] for this domain; bundles were first
+    /// sent in payload version 6
+    pub fn decode_outbound_bundle<'a>(&self, message: &'a [u8]) -> Result<OutboundBundle<'a>, DecodeError> {
+        let (version, mut reader) = self.read_outbound_prefix(message)?;
+        if version < 6 {
+            return Err(DecodeError::UnsupportedPayloadVersion(version));
+        }
+        let owner = reader.array()?;
+        let destination_chain_id = reader.u64()?;
+        let recipient_address = reader.bytes()?;
+        let nonce = reader.u64()?;
+        let count = u32::from_le_bytes(reader.array()?);
+        let mut items = Vec::new();
+        for _ in 0..count {
+            items.push(OutboundBundleItem {
+                mint: reader.array()?,
+                metadata_uri: reader.str()?,
+                fallback_uri: reader.str()?,
+                staked_seconds: reader.u64()?,
+            });
+        }
+        reader.finish()?;
+        Ok(OutboundBundle { owner, destination_chain_id, recipient_address, nonce, items })
+    }
+
+    /// Read an outbound message up to the end of its transfer fields
+    fn read_outbound<'a>(
+        &self,
+        message: &'a [u8],
+    ) -> Result<(u8, OutboundTransfer<'a>, Reader<'a>), DecodeError> {
+        let (version, mut reader) = self.read_outbound_prefix(message)?;
+        let transfer = OutboundTransfer {
+            mint: reader.array()?,
+            owner: reader.array()?,
+            destination_chain_id: reader.u64()?,
+            recipient_address: reader.bytes()?,
+            nonce: reader.u64()?,
+            metadata_uri: if version >= 3 { reader.str()? } else { "" },
+            fallback_uri: if version >= 3 { reader.str()? } else { "" },
+            staked_seconds: if version >= 4 { reader.u64()? } else { 0 },
+            localized_uris: if version >= 5 {
+                LocalizedUris::from_encoded(reader.bytes()?)?
+            } else {
+                LocalizedUris::default()
+            },
+        };
+        Ok((version, transfer, reader))
+    }
+
+    /// Check an outbound message's tag and domain and read its payload version
+    fn read_outbound_prefix<'a>(&self, message: &'a [u8]) -> Result<(u8, Reader<'a>), DecodeError> {
+        let mut reader = Reader::new(message);
+        read_tag(&mut reader)?;
+        let program_id = reader.array()?;
+        let chain_id = reader.u64()?;
+        if (SigningDomain { program_id, chain_id }) != *self {
+            return Err(DecodeError::DomainMismatch);
+        }
+        Ok((read_payload_version(&mut reader)?, reader))
+    }
+}
+
+fn read_tag(reader: &mut Reader) -> Result<(), DecodeError> {
+    let tag = reader.take(SIGNING_DOMAIN_TAG.len()).map_err(|_| DecodeError::TagMismatch)?;
+    if tag != SIGNING_DOMAIN_TAG {
+        return Err(DecodeError::TagMismatch);
+    }
+    Ok(())
+}
+
+fn read_payload_version(reader: &mut Reader) -> Result<u8, DecodeError> {
+    let version = reader.u8()?;
+    if !SUPPORTED_PAYLOAD_VERSIONS.contains(&version) {
+        return Err(DecodeError::UnsupportedPayloadVersion(version));
+    }
+    Ok(version)
+}
@@ -0,0 +1,132 @@
+//! Cross-chain message formats for Universal NFT.
+//!
+//! The on-chain program and off-chain relayers and indexers build the bytes the
+//! TSS signs from these types, so both sides agree on the layout by
+//! construction. The crate has no dependencies and builds without `std`
+//! (disable default features); it only needs `alloc`.
+//!
+//! Every signed message is a [`SigningDomain`] prefix followed by a payload
+//! version byte and the payload body, which [`decode`] reads back:
+//!
+//! ```
+//! use universal_nft_messages::{chain_id, DeliveryAck, SigningDomain};
+//!
+//! let domain = SigningDomain { program_id: [7; 32], chain_id: chain_id::SOLANA_MAINNET };
+//! let message = domain.message(&DeliveryAck {
+//!     mint: [1; 32],
+//!     nonce: 42,
+//!     destination_chain_id: chain_id::ETHEREUM,
+//!     recipient_address: &[0xab; 20],
+//!     destination_tx_hash: [2; 32],
+//!     gas_spent: 0,
+//! });
+//! assert_eq!(message[..13], *b"UNIVERSAL_NFT");
+//! ```
+//!
+//! With the `protobuf` feature, [`proto`] mirrors the payloads and program
+//! events as prost messages described by `proto/universal_nft.proto`, for
+//! relayers written in other languages.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod chain_id;
+pub mod decode;
+pub mod payloads;
+#[cfg(feature = "protobuf")]
+pub mod proto;
+
+pub use decode::{DecodeError, DecodePayload, MessageHeader, SUPPORTED_PAYLOAD_VERSIONS};
+pub use payloads::*;
+
+use alloc::vec::Vec;
+
+/// Version of the domain prefix below; bump when the prefix layout changes
+pub const SIGNING_DOMAIN_VERSION: u8 = 1;
+/// Version byte that starts every payload body; bump when a body layout
+/// changes, keeping the old version in `SUPPORTED_PAYLOAD_VERSIONS` until
+/// messages in flight under it have drained
+pub const PAYLOAD_VERSION: u8 = 6;
+pub const SIGNING_DOMAIN_TAG: &[u8] = b"UNIVERSAL_NFT";
+
+/// Purpose of a signed message, so a signature for one purpose can't be reused for another
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum SignedMessageType {
+    ReceiveNft = 1,
+    SwapPayment = 2,
+    ReserveAttestation = 3,
+    ForeignCollection = 4,
+    DeliveryAck = 5,
+    FailureAck = 6,
+    NativeReturn = 7,
+    FraudProof = 8,
+    TransferPermit = 9,
+    AchievementMint = 10,
+    MetadataSync = 11,
+    BundleDeliveryAck = 12,
+    BundleFailureAck = 13,
+}
+
+impl TryFrom<u8> for SignedMessageType {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::ReceiveNft),
+            2 => Ok(Self::SwapPayment),
+            3 => Ok(Self::ReserveAttestation),
+            4 => Ok(Self::ForeignCollection),
+            5 => Ok(Self::DeliveryAck),
+            6 => Ok(Self::FailureAck),
+            7 => Ok(Self::NativeReturn),
+            8 => Ok(Self::FraudProof),
+            9 => Ok(Self::TransferPermit),
+            10 => Ok(Self::AchievementMint),
+            11 => Ok(Self::MetadataSync),
+            12 => Ok(Self::BundleDeliveryAck),
+            13 => Ok(Self::BundleFailureAck),
+            other => Err(other),
+        }
+    }
+}
+
+/// A payload the TSS signs, or for `TransferPermit` the NFT owner; a
+/// `MetadataSync` is emitted by the program for relayers to deliver. Bodies are their fields in declaration order, in
+/// borsh encoding: integers little-endian, fixed-size arrays as is, and byte
+/// and string fields prefixed with their length as a u32.
+pub trait SignedPayload {
+    const MESSAGE_TYPE: SignedMessageType;
+
+    /// Append the payload body to `out`
+    fn encode_body(&self, out: &mut Vec<u8>);
+}
+
+/// Identifies the deployment a signed message is meant for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SigningDomain {
+    pub program_id: [u8; 32],
+    pub chain_id: u64,
+}
+
+impl SigningDomain {
+    /// Domain prefix: tag || program id || chain id || message type || version
+    pub fn prefix(&self, message_type: SignedMessageType) -> Vec<u8> {
+        let mut prefix = Vec::with_capacity(SIGNING_DOMAIN_TAG.len() + 32 + 8 + 2);
+        prefix.extend_from_slice(SIGNING_DOMAIN_TAG);
+        prefix.extend_from_slice(&self.program_id);
+        prefix.extend_from_slice(&self.chain_id.to_le_bytes());
+        prefix.push(message_type as u8);
+        prefix.push(SIGNING_DOMAIN_VERSION);
+        prefix
+    }
+
+    /// Full message the TSS signs for `payload`: prefix || payload version || body
+    pub fn message<P: SignedPayload>(&self, payload: &P) -> Vec<u8> {
+        let mut message = self.prefix(P::MESSAGE_TYPE);
+        message.push(PAYLOAD_VERSION);
+        payload.encode_body(&mut message);
+        message
+    }
+}
@@ -0,0 +1,751 @@
+//! Payload bodies. Addresses on Solana are raw 32-byte keys; addresses on
+//! other chains are their raw bytes (20 for EVM).
+
+/// Append a variable-length field: its length as a u32 LE, then the bytes
+fn put_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    out.extend_from_slice(bytes);
+}
+
+use alloc::vec::Vec;
+
+use crate::decode::{DecodeError, DecodePayload, Reader};
+use crate::{SignedMessageType, SignedPayload, SigningDomain, PAYLOAD_VERSION, SIGNING_DOMAIN_TAG};
+
+/// An NFT arriving from another chain, attested for `receive_cross_chain`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReceiveNft<'a> {
+    pub origin_chain_id: u64,
+    pub origin_tx_hash: [u8; 32],
+    pub origin_contract: &'a [u8],
+    pub origin_token_id: [u8; 32],
+    pub metadata_uri: &'a str,
+    pub name: &'a str,
+    pub symbol: &'a str,
+    pub original_owner: &'a [u8],
+    pub intended_recipient: [u8; 32],
+    pub nonce: u64,
+}
+
+impl SignedPayload for ReceiveNft<'_> {
+    const MESSAGE_TYPE: SignedMessageType = SignedMessageType::ReceiveNft;
+
+    fn encode_body(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.origin_chain_id.to_le_bytes());
+        out.extend_from_slice(&self.origin_tx_hash);
+        put_bytes(out, self.origin_contract);
+        out.extend_from_slice(&self.origin_token_id);
+        put_bytes(out, self.metadata_uri.as_bytes());
+        put_bytes(out, self.name.as_bytes());
+        put_bytes(out, self.symbol.as_bytes());
+        put_bytes(out, self.original_owner);
+        out.extend_from_slice(&self.intended_recipient);
+        out.extend_from_slice(&self.nonce.to_le_bytes());
+    }
+}
+
+impl<'a> DecodePayload<'a> for ReceiveNft<'a> {
+    fn decode_body(_version: u8, reader: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            origin_chain_id: reader.u64()?,
+            origin_tx_hash: reader.array()?,
+            origin_contract: reader.bytes()?,
+            origin_token_id: reader.array()?,
+            metadata_uri: reader.str()?,
+            name: reader.str()?,
+            symbol: reader.str()?,
+            original_owner: reader.bytes()?,
+            intended_recipient: reader.array()?,
+            nonce: reader.u64()?,
+        })
+    }
+}
+
+/// Payment for a cross-chain swap, made by the buyer on the destination chain
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapPayment<'a> {
+    pub mint: [u8; 32],
+    pub destination_chain_id: u64,
+    pub payment_token: &'a [u8],
+    pub payment_amount: u64,
+    pub buyer_address: &'a [u8],
+    pub payment_tx_hash: &'a [u8],
+}
+
+impl SignedPayload for SwapPayment<'_> {
+    const MESSAGE_TYPE: SignedMessageType = SignedMessageType::SwapPayment;
+
+    fn encode_body(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.mint);
+        out.extend_from_slice(&self.destination_chain_id.to_le_bytes());
+        put_bytes(out, self.payment_token);
+        out.extend_from_slice(&self.payment_amount.to_le_bytes());
+        put_bytes(out, self.buyer_address);
+        put_bytes(out, self.payment_tx_hash);
+    }
+}
+
+impl<'a> DecodePayload<'a> for SwapPayment<'a> {
+    fn decode_body(_version: u8, reader: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            mint: reader.array()?,
+            destination_chain_id: reader.u64()?,
+            payment_token: reader.bytes()?,
+            payment_amount: reader.u64()?,
+            buyer_address: reader.bytes()?,
+            payment_tx_hash: reader.bytes()?,
+        })
+    }
+}
+
+/// Number of a collection's NFTs held on a remote chain at `attested_at`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReserveAttestation<'a> {
+    pub origin_chain_id: u64,
+    pub origin_contract: &'a [u8],
+    pub attested_remote_count: u64,
+    pub attested_at: i64,
+}
+
+impl SignedPayload for ReserveAttestation<'_> {
+    const MESSAGE_TYPE: SignedMessageType = SignedMessageType::ReserveAttestation;
+
+    fn encode_body(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.origin_chain_id.to_le_bytes());
+        put_bytes(out, self.origin_contract);
+        out.extend_from_slice(&self.attested_remote_count.to_le_bytes());
+        out.extend_from_slice(&self.attested_at.to_le_bytes());
+    }
+}
+
+impl<'a> DecodePayload<'a> for ReserveAttestation<'a> {
+    fn decode_body(_version: u8, reader: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            origin_chain_id: reader.u64()?,
+            origin_contract: reader.bytes()?,
+            attested_remote_count: reader.u64()?,
+            attested_at: reader.i64()?,
+        })
+    }
+}
+
+/// Details of a collection native to another chain
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ForeignCollection<'a> {
+    pub origin_chain_id: u64,
+    pub origin_contract: &'a [u8],
+    pub name: &'a str,
+    pub symbol: &'a str,
+    pub royalty_basis_points: u16,
+    pub royalty_recipient: &'a [u8],
+    pub collection_mint: [u8; 32],
+}
+
+impl SignedPayload for ForeignCollection<'_> {
+    const MESSAGE_TYPE: SignedMessageType = SignedMessageType::ForeignCollection;
+
+    fn encode_body(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.origin_chain_id.to_le_bytes());
+        put_bytes(out, self.origin_contract);
+        put_bytes(out, self.name.as_bytes());
+        put_bytes(out, self.symbol.as_bytes());
+        out.extend_from_slice(&self.royalty_basis_points.to_le_bytes());
+        put_bytes(out, self.royalty_recipient);
+        out.extend_from_slice(&self.collection_mint);
+    }
+}
+
+impl<'a> DecodePayload<'a> for ForeignCollection<'a> {
+    fn decode_body(_version: u8, reader: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            origin_chain_id: reader.u64()?,
+            origin_contract: reader.bytes()?,
+            name: reader.str()?,
+            symbol: reader.str()?,
+            royalty_basis_points: reader.u16()?,
+            royalty_recipient: reader.bytes()?,
+            collection_mint: reader.array()?,
+        })
+    }
+}
+
+/// An outbound transfer landed in `destination_tx_hash`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DeliveryAck<'a> {
+    pub mint: [u8; 32],
+    pub nonce: u64,
+    pub destination_chain_id: u64,
+    pub recipient_address: &'a [u8],
+    pub destination_tx_hash: [u8; 32],
+    /// Part of the transfer's gas budget delivery consumed, in units of its
+    /// fee. Added in payload version 2; version 1 messages decode it as 0.
+    pub gas_spent: u64,
+}
+
+impl SignedPayload for DeliveryAck<'_> {
+    const MESSAGE_TYPE: SignedMessageType = SignedMessageType::DeliveryAck;
+
+    fn encode_body(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.mint);
+        out.extend_from_slice(&self.nonce.to_le_bytes());
+        out.extend_from_slice(&self.destination_chain_id.to_le_bytes());
+        put_bytes(out, self.recipient_address);
+        out.extend_from_slice(&self.destination_tx_hash);
+        out.extend_from_slice(&self.gas_spent.to_le_bytes());
+    }
+}
+
+impl<'a> DecodePayload<'a> for DeliveryAck<'a> {
+    fn decode_body(version: u8, reader: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            mint: reader.array()?,
+            nonce: reader.u64()?,
+            destination_chain_id: reader.u64()?,
+            recipient_address: reader.bytes()?,
+            destination_tx_hash: reader.array()?,
+            gas_spent: if version >= 2 { reader.u64()? } else { 0 },
+        })
+    }
+}
+
+/// An outbound transfer that will never be delivered
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FailureAck<'a> {
+    pub mint: [u8; 32],
+    pub nonce: u64,
+    pub destination_chain_id: u64,
+    pub recipient_address: &'a [u8],
+}
+
+impl SignedPayload for FailureAck<'_> {
+    const MESSAGE_TYPE: SignedMessageType = SignedMessageType::FailureAck;
+
+    fn encode_body(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.mint);
+        out.extend_from_slice(&self.nonce.to_le_bytes());
+        out.extend_from_slice(&self.destination_chain_id.to_le_bytes());
+        put_bytes(out, self.recipient_address);
+    }
+}
+
+impl<'a> DecodePayload<'a> for FailureAck<'a> {
+    fn decode_body(_version: u8, reader: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            mint: reader.array()?,
+            nonce: reader.u64()?,
+            destination_chain_id: reader.u64()?,
+            recipient_address: reader.bytes()?,
+        })
+    }
+}
+
+/// An outbound bundle landed in `destination_tx_hash`. A bundle is named by
+/// its owner and nonce rather than a mint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BundleDeliveryAck<'a> {
+    pub owner: [u8; 32],
+    pub nonce: u64,
+    pub destination_chain_id: u64,
+    pub recipient_address: &'a [u8],
+    pub destination_tx_hash: [u8; 32],
+    /// Part of the bundle's gas budget delivery consumed, in units of its fee
+    pub gas_spent: u64,
+}
+
+impl SignedPayload for BundleDeliveryAck<'_> {
+    const MESSAGE_TYPE: SignedMessageType = SignedMessageType::BundleDeliveryAck;
+
+    fn encode_body(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.owner);
+        out.extend_from_slice(&self.nonce.to_le_bytes());
+        out.extend_from_slice(&self.destination_chain_id.to_le_bytes());
+        put_bytes(out, self.recipient_address);
+        out.extend_from_slice(&self.destination_tx_hash);
+        out.extend_from_slice(&self.gas_spent.to_le_bytes());
+    }
+}
+
+impl<'a> DecodePayload<'a> for BundleDeliveryAck<'a> {
+    fn decode_body(_version: u8, reader: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            owner: reader.array()?,
+            nonce: reader.u64()?,
+            destination_chain_id: reader.u64()?,
+            recipient_address: reader.bytes()?,
+            destination_tx_hash: reader.array()?,
+            gas_spent: reader.u64()?,
+        })
+    }
+}
+
+/// An outbound bundle that will never be delivered
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BundleFailureAck<'a> {
+    pub owner: [u8; 32],
+    pub nonce: u64,
+    pub destination_chain_id: u64,
+    pub recipient_address: &'a [u8],
+}
+
+impl SignedPayload for BundleFailureAck<'_> {
+    const MESSAGE_TYPE: SignedMessageType = SignedMessageType::BundleFailureAck;
+
+    fn encode_body(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.owner);
+        out.extend_from_slice(&self.nonce.to_le_bytes());
+        out.extend_from_slice(&self.destination_chain_id.to_le_bytes());
+        put_bytes(out, self.recipient_address);
+    }
+}
+
+impl<'a> DecodePayload<'a> for BundleFailureAck<'a> {
+    fn decode_body(_version: u8, reader: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            owner: reader.array()?,
+            nonce: reader.u64()?,
+            destination_chain_id: reader.u64()?,
+            recipient_address: reader.bytes()?,
+        })
+    }
+}
+
+/// A Solana-native NFT came back from `origin_chain_id` to `recipient`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NativeReturn {
+    pub mint: [u8; 32],
+    pub origin_chain_id: u64,
+    pub origin_tx_hash: [u8; 32],
+    pub recipient: [u8; 32],
+    pub nonce: u64,
+    /// Unclaimed staking time the NFT brings back, in seconds, credited to
+    /// its stake. Added in payload version 4; earlier messages decode it as 0.
+    pub staked_seconds: u64,
+}
+
+impl SignedPayload for NativeReturn {
+    const MESSAGE_TYPE: SignedMessageType = SignedMessageType::NativeReturn;
+
+    fn encode_body(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.mint);
+        out.extend_from_slice(&self.origin_chain_id.to_le_bytes());
+        out.extend_from_slice(&self.origin_tx_hash);
+        out.extend_from_slice(&self.recipient);
+        out.extend_from_slice(&self.nonce.to_le_bytes());
+        out.extend_from_slice(&self.staked_seconds.to_le_bytes());
+    }
+}
+
+impl<'a> DecodePayload<'a> for NativeReturn {
+    fn decode_body(version: u8, reader: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            mint: reader.array()?,
+            origin_chain_id: reader.u64()?,
+            origin_tx_hash: reader.array()?,
+            recipient: reader.array()?,
+            nonce: reader.u64()?,
+            staked_seconds: if version >= 4 { reader.u64()? } else { 0 },
+        })
+    }
+}
+
+/// A receive staged optimistically without a TSS signature is fraudulent.
+/// `message_hash` is the sha256 of the staged `ReceiveNft` message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FraudProof {
+    pub origin_chain_id: u64,
+    pub origin_tx_hash: [u8; 32],
+    pub nonce: u64,
+    pub message_hash: [u8; 32],
+}
+
+impl SignedPayload for FraudProof {
+    const MESSAGE_TYPE: SignedMessageType = SignedMessageType::FraudProof;
+
+    fn encode_body(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.origin_chain_id.to_le_bytes());
+        out.extend_from_slice(&self.origin_tx_hash);
+        out.extend_from_slice(&self.nonce.to_le_bytes());
+        out.extend_from_slice(&self.message_hash);
+    }
+}
+
+impl<'a> DecodePayload<'a> for FraudProof {
+    fn decode_body(_version: u8, reader: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            origin_chain_id: reader.u64()?,
+            origin_tx_hash: reader.array()?,
+            nonce: reader.u64()?,
+            message_hash: reader.array()?,
+        })
+    }
+}
+
+/// An NFT owner's consent to `cross_chain_transfer_with_permit`, signed with
+/// the owner's ed25519 key so any sponsor can submit the transfer and pay its
+/// fees. The permit is void once `deadline` (unix seconds) has passed or
+/// `nonce` has been used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransferPermit<'a> {
+    pub mint: [u8; 32],
+    pub owner: [u8; 32],
+    pub destination_chain_id: u64,
+    pub recipient_address: &'a [u8],
+    pub nonce: u64,
+    pub deadline: i64,
+}
+
+impl SignedPayload for TransferPermit<'_> {
+    const MESSAGE_TYPE: SignedMessageType = SignedMessageType::TransferPermit;
+
+    fn encode_body(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.mint);
+        out.extend_from_slice(&self.owner);
+        out.extend_from_slice(&self.destination_chain_id.to_le_bytes());
+        put_bytes(out, self.recipient_address);
+        out.extend_from_slice(&self.nonce.to_le_bytes());
+        out.extend_from_slice(&self.deadline.to_le_bytes());
+    }
+}
+
+impl<'a> DecodePayload<'a> for TransferPermit<'a> {
+    fn decode_body(_version: u8, reader: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            mint: reader.array()?,
+            owner: reader.array()?,
+            destination_chain_id: reader.u64()?,
+            recipient_address: reader.bytes()?,
+            nonce: reader.u64()?,
+            deadline: reader.i64()?,
+        })
+    }
+}
+
+/// An achievement NFT that a trusted game contract on another chain awards to
+/// a Solana wallet, attested for `mint_achievement`. `achievement_id` is
+/// unique per award, so each can be minted only once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AchievementMint<'a> {
+    pub peer_chain_id: u64,
+    pub peer_contract: &'a [u8],
+    pub achievement_id: [u8; 32],
+    pub recipient: [u8; 32],
+    pub metadata_uri: &'a str,
+    pub name: &'a str,
+    pub symbol: &'a str,
+}
+
+impl SignedPayload for AchievementMint<'_> {
+    const MESSAGE_TYPE: SignedMessageType = SignedMessageType::AchievementMint;
+
+    fn encode_body(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.peer_chain_id.to_le_bytes());
+        put_bytes(out, self.peer_contract);
+        out.extend_from_slice(&self.achievement_id);
+        out.extend_from_slice(&self.recipient);
+        put_bytes(out, self.metadata_uri.as_bytes());
+        put_bytes(out, self.name.as_bytes());
+        put_bytes(out, self.symbol.as_bytes());
+    }
+}
+
+impl<'a> DecodePayload<'a> for AchievementMint<'a> {
+    fn decode_body(_version: u8, reader: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            peer_chain_id: reader.u64()?,
+            peer_contract: reader.bytes()?,
+            achievement_id: reader.array()?,
+            recipient: reader.array()?,
+            metadata_uri: reader.str()?,
+            name: reader.str()?,
+            symbol: reader.str()?,
+        })
+    }
+}
+
+/// An NFT's oracle-driven metadata changed on Solana, for relayers to apply
+/// to its copy on whichever chain holds it. `variant_index` is the variant
+/// `oracle_value` selected, and `metadata_uri` its URI.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MetadataSync<'a> {
+    pub mint: [u8; 32],
+    pub origin_chain_id: u64,
+    pub origin_token_id: [u8; 32],
+    pub variant_index: u8,
+    pub oracle_value: i64,
+    pub oracle_updated_at: i64,
+    pub metadata_uri: &'a str,
+}
+
+impl SignedPayload for MetadataSync<'_> {
+    const MESSAGE_TYPE: SignedMessageType = SignedMessageType::MetadataSync;
+
+    fn encode_body(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.mint);
+        out.extend_from_slice(&self.origin_chain_id.to_le_bytes());
+        out.extend_from_slice(&self.origin_token_id);
+        out.push(self.variant_index);
+        out.extend_from_slice(&self.oracle_value.to_le_bytes());
+        out.extend_from_slice(&self.oracle_updated_at.to_le_bytes());
+        put_bytes(out, self.metadata_uri.as_bytes());
+    }
+}
+
+impl<'a> DecodePayload<'a> for MetadataSync<'a> {
+    fn decode_body(_version: u8, reader: &mut Reader<'a>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            mint: reader.array()?,
+            origin_chain_id: reader.u64()?,
+            origin_token_id: reader.array()?,
+            variant_index: reader.u8()?,
+            oracle_value: reader.i64()?,
+            oracle_updated_at: reader.i64()?,
+            metadata_uri: reader.str()?,
+        })
+    }
+}
+
+/// Locale to metadata URI pairs an NFT carries, such as `("ja-JP", "ipfs://…")`.
+/// Kept in their encoded form, each locale and URI length-prefixed, so an
+/// `OutboundTransfer` can borrow them like its other fields.
+///
+/// ```
+/// use universal_nft_messages::LocalizedUris;
+///
+/// let encoded = LocalizedUris::encode([("ja-JP", "https://example.com/ja.json")]);
+/// let localized = LocalizedUris::from_encoded(&encoded).unwrap();
+/// assert_eq!(localized.iter().collect::<Vec<_>>(), [("ja-JP", "https://example.com/ja.json")]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LocalizedUris<'a> {
+    encoded: &'a [u8],
+}
+
+impl<'a> LocalizedUris<'a> {
+    /// Encode pairs for `from_encoded`
+    pub fn encode<'s>(pairs: impl IntoIterator<Item = (&'s str, &'s str)>) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        for (locale, uri) in pairs {
+            put_bytes(&mut encoded, locale.as_bytes());
+            put_bytes(&mut encoded, uri.as_bytes());
+        }
+        encoded
+    }
+
+    /// Wrap bytes built by `encode`, checking every pair is complete
+    pub fn from_encoded(encoded: &'a [u8]) -> Result<Self, DecodeError> {
+        let localized = Self { encoded };
+        let mut reader = Reader::new(encoded);
+        while !reader.is_empty() {
+            reader.str()?;
+            reader.str()?;
+        }
+        Ok(localized)
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.encoded
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.encoded.is_empty()
+    }
+
+    /// The pairs in the order they were encoded
+    pub fn iter(&self) -> impl Iterator<Item = (&'a str, &'a str)> {
+        let mut reader = Reader::new(self.encoded);
+        core::iter::from_fn(move || {
+            if reader.is_empty() {
+                return None;
+            }
+            // Checked by `from_encoded`
+            Some((reader.str().ok()?, reader.str().ok()?))
+        })
+    }
+}
+
+/// An NFT leaving Solana. Not signed; its sha256 is the `message_hash` the
+/// program emits with the transfer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutboundTransfer<'a> {
+    pub mint: [u8; 32],
+    pub owner: [u8; 32],
+    pub destination_chain_id: u64,
+    pub recipient_address: &'a [u8],
+    pub nonce: u64,
+    /// Added in payload version 3, with `fallback_uri`; version 2 messages
+    /// decode both as empty
+    pub metadata_uri: &'a str,
+    /// Empty when the NFT has no fallback
+    pub fallback_uri: &'a str,
+    /// Unclaimed staking time the NFT carries, in seconds; 0 when it wasn't
+    /// staked. The destination echoes it back in the `NativeReturn` that
+    /// brings the NFT home. Added in payload version 4; earlier transfers
+    /// decode it as 0.
+    pub staked_seconds: u64,
+    /// Localized metadata URIs; empty when the NFT has none. Added in payload
+    /// version 5; earlier transfers decode it as empty.
+    pub localized_uris: LocalizedUris<'a>,
+}
+
+/// Fields of an outbound message, hashed after the source chain and payload
+/// version by [`SigningDomain::outbound_message`]
+pub trait OutboundPayload {
+    /// Size of the fields as encoded, without the signing domain prefix
+    fn encoded_len(&self) -> usize;
+
+    /// Append the fields to `out`
+    fn encode_fields(&self, out: &mut Vec<u8>);
+}
+
+impl OutboundPayload for OutboundTransfer<'_> {
+    fn encoded_len(&self) -> usize {
+        32 + 32 + 8 + 4 + self.recipient_address.len() + 8 + 4 + self.metadata_uri.len() + 4
+            + self.fallback_uri.len() + 8 + 4 + self.localized_uris.as_bytes().len()
+    }
+
+    /// mint || owner || destination chain id || recipient (length-prefixed) ||
+    /// nonce || metadata URI (length-prefixed) || fallback URI (length-prefixed) ||
+    /// staked seconds || localized URIs (length-prefixed)
+    fn encode_fields(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.mint);
+        out.extend_from_slice(&self.owner);
+        out.extend_from_slice(&self.destination_chain_id.to_le_bytes());
+        put_bytes(out, self.recipient_address);
+        out.extend_from_slice(&self.nonce.to_le_bytes());
+        put_bytes(out, self.metadata_uri.as_bytes());
+        put_bytes(out, self.fallback_uri.as_bytes());
+        out.extend_from_slice(&self.staked_seconds.to_le_bytes());
+        put_bytes(out, self.localized_uris.as_bytes());
+    }
+}
+
+/// An NFT leaving Solana with fungible value attached, from
+/// `cross_chain_transfer_with_value`. Not signed; its sha256 is the
+/// `message_hash` in `CrossChainValueTransferEvent`. Added in payload
+/// version 6; earlier value transfers were sent as a plain `OutboundTransfer`.
+///
+/// ```
+/// use universal_nft_messages::{chain_id, LocalizedUris, OutboundTransfer, OutboundTransferWithValue, SigningDomain};
+///
+/// let domain = SigningDomain { program_id: [7; 32], chain_id: chain_id::SOLANA_MAINNET };
+/// let outbound = OutboundTransferWithValue {
+///     transfer: OutboundTransfer {
+///         mint: [1; 32],
+///         owner: [2; 32],
+///         destination_chain_id: chain_id::ETHEREUM,
+///         recipient_address: &[0xab; 20],
+///         nonce: 42,
+///         metadata_uri: "ipfs://nft.json",
+///         fallback_uri: "",
+///         staked_seconds: 0,
+///         localized_uris: LocalizedUris::default(),
+///     },
+///     value_mint: [3; 32],
+///     value_amount: 1_000_000,
+/// };
+/// let message = domain.outbound_message(&outbound);
+/// assert_eq!(domain.decode_outbound_with_value(&message), Ok(outbound));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutboundTransferWithValue<'a> {
+    pub transfer: OutboundTransfer<'a>,
+    /// SPL mint of the value escrowed with the NFT
+    pub value_mint: [u8; 32],
+    pub value_amount: u64,
+}
+
+impl OutboundPayload for OutboundTransferWithValue<'_> {
+    fn encoded_len(&self) -> usize {
+        self.transfer.encoded_len() + 32 + 8
+    }
+
+    /// The transfer's fields || value mint || value amount
+    fn encode_fields(&self, out: &mut Vec<u8>) {
+        self.transfer.encode_fields(out);
+        out.extend_from_slice(&self.value_mint);
+        out.extend_from_slice(&self.value_amount.to_le_bytes());
+    }
+}
+
+/// One NFT of an [`OutboundBundle`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutboundBundleItem<'a> {
+    pub mint: [u8; 32],
+    pub metadata_uri: &'a str,
+    /// Empty when the NFT has no fallback
+    pub fallback_uri: &'a str,
+    /// Unclaimed staking time the NFT carries, in seconds
+    pub staked_seconds: u64,
+}
+
+impl OutboundBundleItem<'_> {
+    fn encoded_len(&self) -> usize {
+        32 + 4 + self.metadata_uri.len() + 4 + self.fallback_uri.len() + 8
+    }
+}
+
+/// NFTs leaving Solana together from `cross_chain_transfer_batch`. Not
+/// signed; its sha256 is the `message_hash` in `BundleTransferEvent`. Added
+/// in payload version 6; earlier bundles were only emitted as events.
+///
+/// ```
+/// use universal_nft_messages::{chain_id, OutboundBundle, OutboundBundleItem, SigningDomain};
+///
+/// let domain = SigningDomain { program_id: [7; 32], chain_id: chain_id::SOLANA_MAINNET };
+/// let item = |mint| OutboundBundleItem { mint, metadata_uri: "ipfs://nft.json", fallback_uri: "", staked_seconds: 0 };
+/// let bundle = OutboundBundle {
+///     owner: [2; 32],
+///     destination_chain_id: chain_id::ETHEREUM,
+///     recipient_address: &[0xab; 20],
+///     nonce: 42,
+///     items: vec![item([1; 32]), item([3; 32])],
+/// };
+/// let message = domain.outbound_message(&bundle);
+/// assert_eq!(domain.decode_outbound_bundle(&message), Ok(bundle));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OutboundBundle<'a> {
+    pub owner: [u8; 32],
+    pub destination_chain_id: u64,
+    pub recipient_address: &'a [u8],
+    pub nonce: u64,
+    pub items: Vec<OutboundBundleItem<'a>>,
+}
+
+impl OutboundPayload for OutboundBundle<'_> {
+    fn encoded_len(&self) -> usize {
+        32 + 8 + 4 + self.recipient_address.len() + 8 + 4
+            + self.items.iter().map(OutboundBundleItem::encoded_len).sum::<usize>()
+    }
+
+    /// owner || destination chain id || recipient (length-prefixed) || nonce ||
+    /// item count (u32), then per item: mint || metadata URI (length-prefixed) ||
+    /// fallback URI (length-prefixed) || staked seconds
+    fn encode_fields(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.owner);
+        out.extend_from_slice(&self.destination_chain_id.to_le_bytes());
+        put_bytes(out, self.recipient_address);
+        out.extend_from_slice(&self.nonce.to_le_bytes());
+        out.extend_from_slice(&(self.items.len() as u32).to_le_bytes());
+        for item in &self.items {
+            out.extend_from_slice(&item.mint);
+            put_bytes(out, item.metadata_uri.as_bytes());
+            put_bytes(out, item.fallback_uri.as_bytes());
+            out.extend_from_slice(&item.staked_seconds.to_le_bytes());
+        }
+    }
+}
+
+impl SigningDomain {
+    /// Bytes hashed into an outbound `message_hash`:
+    /// tag || program id || source chain id || payload version || fields
+    pub fn outbound_message<T: OutboundPayload>(&self, outbound: &T) -> Vec<u8> {
+        let mut message = Vec::with_capacity(SIGNING_DOMAIN_TAG.len() + 32 + 8 + 1 + outbound.encoded_len());
+        message.extend_from_slice(SIGNING_DOMAIN_TAG);
+        message.extend_from_slice(&self.program_id);
+        message.extend_from_slice(&self.chain_id.to_le_bytes());
+        message.push(PAYLOAD_VERSION);
+        outbound.encode_fields(&mut message);
+        message
+    }
+}
@@ -0,0 +1,803 @@
+//! Protobuf mirror of the message types, defined by `proto/universal_nft.proto`.
+//!
+//! The signed bytes are always the layout in [`crate::payloads`]; protobuf is
+//! only a transport for relayers written in other languages. Convert a
+//! received protobuf message back with `to_payload` and sign or verify the
+//! result of [`SigningDomain::message`].
+//!
+//! ```
+//! use prost::Message;
+//! use universal_nft_messages::{proto, FailureAck, SigningDomain};
+//!
+//! let domain = SigningDomain { program_id: [7; 32], chain_id: 900 };
+//! let payload = FailureAck { mint: [1; 32], nonce: 9, destination_chain_id: 1, recipient_address: &[0xab; 20] };
+//! let envelope = proto::SignedEnvelope {
+//!     domain: Some((&domain).into()),
+//!     signature: vec![0; 65],
+//!     payload: Some(proto::signed_envelope::Payload::FailureAck((&payload).into())),
+//! };
+//!
+//! let decoded = proto::SignedEnvelope::decode(envelope.encode_to_vec().as_slice()).unwrap();
+//! assert_eq!(decoded.signed_message().unwrap(), domain.message(&payload));
+//! ```
+//!
+//! The structs are written out by hand rather than generated, so building the
+//! crate does not need `protoc`. Keep them in step with the `.proto` file.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::SigningDomain as DomainPayload;
+
+/// A protobuf field that cannot be mapped back onto the signed layout
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProtoError {
+    /// A fixed-size byte field had the wrong length
+    InvalidLength {
+        field: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    /// A required message or oneof field was not set
+    MissingField(&'static str),
+    /// `royalty_basis_points` does not fit in a u16
+    RoyaltyOutOfRange(u32),
+    /// `variant_index` does not fit in a u8
+    VariantIndexOutOfRange(u32),
+}
+
+impl fmt::Display for ProtoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength { field, expected, actual } => {
+                write!(f, "{} must be {} bytes, got {}", field, expected, actual)
+            }
+            Self::MissingField(field) => write!(f, "{} is not set", field),
+            Self::RoyaltyOutOfRange(value) => {
+                write!(f, "royalty_basis_points {} does not fit in a u16", value)
+            }
+            Self::VariantIndexOutOfRange(value) => {
+                write!(f, "variant_index {} does not fit in a u8", value)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ProtoError {}
+
+fn fixed(field: &'static str, bytes: &[u8]) -> Result<[u8; 32], ProtoError> {
+    bytes.try_into().map_err(|_| ProtoError::InvalidLength {
+        field,
+        expected: 32,
+        actual: bytes.len(),
+    })
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SigningDomain {
+    #[prost(bytes = "vec", tag = "1")]
+    pub program_id: Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub chain_id: u64,
+}
+
+impl From<&DomainPayload> for SigningDomain {
+    fn from(domain: &DomainPayload) -> Self {
+        Self {
+            program_id: domain.program_id.to_vec(),
+            chain_id: domain.chain_id,
+        }
+    }
+}
+
+impl SigningDomain {
+    pub fn to_domain(&self) -> Result<DomainPayload, ProtoError> {
+        Ok(DomainPayload {
+            program_id: fixed("program_id", &self.program_id)?,
+            chain_id: self.chain_id,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ReceiveNft {
+    #[prost(uint64, tag = "1")]
+    pub origin_chain_id: u64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub origin_tx_hash: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub origin_contract: Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub origin_token_id: Vec<u8>,
+    #[prost(string, tag = "5")]
+    pub metadata_uri: String,
+    #[prost(string, tag = "6")]
+    pub name: String,
+    #[prost(string, tag = "7")]
+    pub symbol: String,
+    #[prost(bytes = "vec", tag = "8")]
+    pub original_owner: Vec<u8>,
+    #[prost(bytes = "vec", tag = "9")]
+    pub intended_recipient: Vec<u8>,
+    #[prost(uint64, tag = "10")]
+    pub nonce: u64,
+}
+
+impl From<&crate::ReceiveNft<'_>> for ReceiveNft {
+    fn from(payload: &crate::ReceiveNft<'_>) -> Self {
+        Self {
+            origin_chain_id: payload.origin_chain_id,
+            origin_tx_hash: payload.origin_tx_hash.to_vec(),
+            origin_contract: payload.origin_contract.to_vec(),
+            origin_token_id: payload.origin_token_id.to_vec(),
+            metadata_uri: payload.metadata_uri.into(),
+            name: payload.name.into(),
+            symbol: payload.symbol.into(),
+            original_owner: payload.original_owner.to_vec(),
+            intended_recipient: payload.intended_recipient.to_vec(),
+            nonce: payload.nonce,
+        }
+    }
+}
+
+impl ReceiveNft {
+    pub fn to_payload(&self) -> Result<crate::ReceiveNft<'_>, ProtoError> {
+        Ok(crate::ReceiveNft {
+            origin_chain_id: self.origin_chain_id,
+            origin_tx_hash: fixed("origin_tx_hash", &self.origin_tx_hash)?,
+            origin_contract: &self.origin_contract,
+            origin_token_id: fixed("origin_token_id", &self.origin_token_id)?,
+            metadata_uri: &self.metadata_uri,
+            name: &self.name,
+            symbol: &self.symbol,
+            original_owner: &self.original_owner,
+            intended_recipient: fixed("intended_recipient", &self.intended_recipient)?,
+            nonce: self.nonce,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SwapPayment {
+    #[prost(bytes = "vec", tag = "1")]
+    pub mint: Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub destination_chain_id: u64,
+    #[prost(bytes = "vec", tag = "3")]
+    pub payment_token: Vec<u8>,
+    #[prost(uint64, tag = "4")]
+    pub payment_amount: u64,
+    #[prost(bytes = "vec", tag = "5")]
+    pub buyer_address: Vec<u8>,
+    #[prost(bytes = "vec", tag = "6")]
+    pub payment_tx_hash: Vec<u8>,
+}
+
+impl From<&crate::SwapPayment<'_>> for SwapPayment {
+    fn from(payload: &crate::SwapPayment<'_>) -> Self {
+        Self {
+            mint: payload.mint.to_vec(),
+            destination_chain_id: payload.destination_chain_id,
+            payment_token: payload.payment_token.to_vec(),
+            payment_amount: payload.payment_amount,
+            buyer_address: payload.buyer_address.to_vec(),
+            payment_tx_hash: payload.payment_tx_hash.to_vec(),
+        }
+    }
+}
+
+impl SwapPayment {
+    pub fn to_payload(&self) -> Result<crate::SwapPayment<'_>, ProtoError> {
+        Ok(crate::SwapPayment {
+            mint: fixed("mint", &self.mint)?,
+            destination_chain_id: self.destination_chain_id,
+            payment_token: &self.payment_token,
+            payment_amount: self.payment_amount,
+            buyer_address: &self.buyer_address,
+            payment_tx_hash: &self.payment_tx_hash,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ReserveAttestation {
+    #[prost(uint64, tag = "1")]
+    pub origin_chain_id: u64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub origin_contract: Vec<u8>,
+    #[prost(uint64, tag = "3")]
+    pub attested_remote_count: u64,
+    #[prost(int64, tag = "4")]
+    pub attested_at: i64,
+}
+
+impl From<&crate::ReserveAttestation<'_>> for ReserveAttestation {
+    fn from(payload: &crate::ReserveAttestation<'_>) -> Self {
+        Self {
+            origin_chain_id: payload.origin_chain_id,
+            origin_contract: payload.origin_contract.to_vec(),
+            attested_remote_count: payload.attested_remote_count,
+            attested_at: payload.attested_at,
+        }
+    }
+}
+
+impl ReserveAttestation {
+    pub fn to_payload(&self) -> Result<crate::ReserveAttestation<'_>, ProtoError> {
+        Ok(crate::ReserveAttestation {
+            origin_chain_id: self.origin_chain_id,
+            origin_contract: &self.origin_contract,
+            attested_remote_count: self.attested_remote_count,
+            attested_at: self.attested_at,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ForeignCollection {
+    #[prost(uint64, tag = "1")]
+    pub origin_chain_id: u64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub origin_contract: Vec<u8>,
+    #[prost(string, tag = "3")]
+    pub name: String,
+    #[prost(string, tag = "4")]
+    pub symbol: String,
+    #[prost(uint32, tag = "5")]
+    pub royalty_basis_points: u32,
+    #[prost(bytes = "vec", tag = "6")]
+    pub royalty_recipient: Vec<u8>,
+    #[prost(bytes = "vec", tag = "7")]
+    pub collection_mint: Vec<u8>,
+}
+
+impl From<&crate::ForeignCollection<'_>> for ForeignCollection {
+    fn from(payload: &crate::ForeignCollection<'_>) -> Self {
+        Self {
+            origin_chain_id: payload.origin_chain_id,
+            origin_contract: payload.origin_contract.to_vec(),
+            name: payload.name.into(),
+            symbol: payload.symbol.into(),
+            royalty_basis_points: payload.royalty_basis_points.into(),
+            royalty_recipient: payload.royalty_recipient.to_vec(),
+            collection_mint: payload.collection_mint.to_vec(),
+        }
+    }
+}
+
+impl ForeignCollection {
+    pub fn to_payload(&self) -> Result<crate::ForeignCollection<'_>, ProtoError> {
+        Ok(crate::ForeignCollection {
+            origin_chain_id: self.origin_chain_id,
+            origin_contract: &self.origin_contract,
+            name: &self.name,
+            symbol: &self.symbol,
+            royalty_basis_points: self
+                .royalty_basis_points
+                .try_into()
+                .map_err(|_| ProtoError::RoyaltyOutOfRange(self.royalty_basis_points))?,
+            royalty_recipient: &self.royalty_recipient,
+            collection_mint: fixed("collection_mint", &self.collection_mint)?,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct DeliveryAck {
+    #[prost(bytes = "vec", tag = "1")]
+    pub mint: Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub nonce: u64,
+    #[prost(uint64, tag = "3")]
+    pub destination_chain_id: u64,
+    #[prost(bytes = "vec", tag = "4")]
+    pub recipient_address: Vec<u8>,
+    #[prost(bytes = "vec", tag = "5")]
+    pub destination_tx_hash: Vec<u8>,
+    #[prost(uint64, tag = "6")]
+    pub gas_spent: u64,
+}
+
+impl From<&crate::DeliveryAck<'_>> for DeliveryAck {
+    fn from(payload: &crate::DeliveryAck<'_>) -> Self {
+        Self {
+            mint: payload.mint.to_vec(),
+            nonce: payload.nonce,
+            destination_chain_id: payload.destination_chain_id,
+            recipient_address: payload.recipient_address.to_vec(),
+            destination_tx_hash: payload.destination_tx_hash.to_vec(),
+            gas_spent: payload.gas_spent,
+        }
+    }
+}
+
+impl DeliveryAck {
+    pub fn to_payload(&self) -> Result<crate::DeliveryAck<'_>, ProtoError> {
+        Ok(crate::DeliveryAck {
+            mint: fixed("mint", &self.mint)?,
+            nonce: self.nonce,
+            destination_chain_id: self.destination_chain_id,
+            recipient_address: &self.recipient_address,
+            destination_tx_hash: fixed("destination_tx_hash", &self.destination_tx_hash)?,
+            gas_spent: self.gas_spent,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct FailureAck {
+    #[prost(bytes = "vec", tag = "1")]
+    pub mint: Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub nonce: u64,
+    #[prost(uint64, tag = "3")]
+    pub destination_chain_id: u64,
+    #[prost(bytes = "vec", tag = "4")]
+    pub recipient_address: Vec<u8>,
+}
+
+impl From<&crate::FailureAck<'_>> for FailureAck {
+    fn from(payload: &crate::FailureAck<'_>) -> Self {
+        Self {
+            mint: payload.mint.to_vec(),
+            nonce: payload.nonce,
+            destination_chain_id: payload.destination_chain_id,
+            recipient_address: payload.recipient_address.to_vec(),
+        }
+    }
+}
+
+impl FailureAck {
+    pub fn to_payload(&self) -> Result<crate::FailureAck<'_>, ProtoError> {
+        Ok(crate::FailureAck {
+            mint: fixed("mint", &self.mint)?,
+            nonce: self.nonce,
+            destination_chain_id: self.destination_chain_id,
+            recipient_address: &self.recipient_address,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct BundleDeliveryAck {
+    #[prost(bytes = "vec", tag = "1")]
+    pub owner: Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub nonce: u64,
+    #[prost(uint64, tag = "3")]
+    pub destination_chain_id: u64,
+    #[prost(bytes = "vec", tag = "4")]
+    pub recipient_address: Vec<u8>,
+    #[prost(bytes = "vec", tag = "5")]
+    pub destination_tx_hash: Vec<u8>,
+    #[prost(uint64, tag = "6")]
+    pub gas_spent: u64,
+}
+
+impl From<&crate::BundleDeliveryAck<'_>> for BundleDeliveryAck {
+    fn from(payload: &crate::BundleDeliveryAck<'_>) -> Self {
+        Self {
+            owner: payload.owner.to_vec(),
+            nonce: payload.nonce,
+            destination_chain_id: payload.destination_chain_id,
+            recipient_address: payload.recipient_address.to_vec(),
+            destination_tx_hash: payload.destination_tx_hash.to_vec(),
+            gas_spent: payload.gas_spent,
+        }
+    }
+}
+
+impl BundleDeliveryAck {
+    pub fn to_payload(&self) -> Result<crate::BundleDeliveryAck<'_>, ProtoError> {
+        Ok(crate::BundleDeliveryAck {
+            owner: fixed("owner", &self.owner)?,
+            nonce: self.nonce,
+            destination_chain_id: self.destination_chain_id,
+            recipient_address: &self.recipient_address,
+            destination_tx_hash: fixed("destination_tx_hash", &self.destination_tx_hash)?,
+            gas_spent: self.gas_spent,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct BundleFailureAck {
+    #[prost(bytes = "vec", tag = "1")]
+    pub owner: Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub nonce: u64,
+    #[prost(uint64, tag = "3")]
+    pub destination_chain_id: u64,
+    #[prost(bytes = "vec", tag = "4")]
+    pub recipient_address: Vec<u8>,
+}
+
+impl From<&crate::BundleFailureAck<'_>> for BundleFailureAck {
+    fn from(payload: &crate::BundleFailureAck<'_>) -> Self {
+        Self {
+            owner: payload.owner.to_vec(),
+            nonce: payload.nonce,
+            destination_chain_id: payload.destination_chain_id,
+            recipient_address: payload.recipient_address.to_vec(),
+        }
+    }
+}
+
+impl BundleFailureAck {
+    pub fn to_payload(&self) -> Result<crate::BundleFailureAck<'_>, ProtoError> {
+        Ok(crate::BundleFailureAck {
+            owner: fixed("owner", &self.owner)?,
+            nonce: self.nonce,
+            destination_chain_id: self.destination_chain_id,
+            recipient_address: &self.recipient_address,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct NativeReturn {
+    #[prost(bytes = "vec", tag = "1")]
+    pub mint: Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub origin_chain_id: u64,
+    #[prost(bytes = "vec", tag = "3")]
+    pub origin_tx_hash: Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub recipient: Vec<u8>,
+    #[prost(uint64, tag = "5")]
+    pub nonce: u64,
+    #[prost(uint64, tag = "6")]
+    pub staked_seconds: u64,
+}
+
+impl From<&crate::NativeReturn> for NativeReturn {
+    fn from(payload: &crate::NativeReturn) -> Self {
+        Self {
+            mint: payload.mint.to_vec(),
+            origin_chain_id: payload.origin_chain_id,
+            origin_tx_hash: payload.origin_tx_hash.to_vec(),
+            recipient: payload.recipient.to_vec(),
+            nonce: payload.nonce,
+            staked_seconds: payload.staked_seconds,
+        }
+    }
+}
+
+impl NativeReturn {
+    pub fn to_payload(&self) -> Result<crate::NativeReturn, ProtoError> {
+        Ok(crate::NativeReturn {
+            mint: fixed("mint", &self.mint)?,
+            origin_chain_id: self.origin_chain_id,
+            origin_tx_hash: fixed("origin_tx_hash", &self.origin_tx_hash)?,
+            recipient: fixed("recipient", &self.recipient)?,
+            nonce: self.nonce,
+            staked_seconds: self.staked_seconds,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct FraudProof {
+    #[prost(uint64, tag = "1")]
+    pub origin_chain_id: u64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub origin_tx_hash: Vec<u8>,
+    #[prost(uint64, tag = "3")]
+    pub nonce: u64,
+    #[prost(bytes = "vec", tag = "4")]
+    pub message_hash: Vec<u8>,
+}
+
+impl From<&crate::FraudProof> for FraudProof {
+    fn from(payload: &crate::FraudProof) -> Self {
+        Self {
+            origin_chain_id: payload.origin_chain_id,
+            origin_tx_hash: payload.origin_tx_hash.to_vec(),
+            nonce: payload.nonce,
+            message_hash: payload.message_hash.to_vec(),
+        }
+    }
+}
+
+impl FraudProof {
+    pub fn to_payload(&self) -> Result<crate::FraudProof, ProtoError> {
+        Ok(crate::FraudProof {
+            origin_chain_id: self.origin_chain_id,
+            origin_tx_hash: fixed("origin_tx_hash", &self.origin_tx_hash)?,
+            nonce: self.nonce,
+            message_hash: fixed("message_hash", &self.message_hash)?,
+        })
+    }
+}
+
+/// Signed by the NFT owner's ed25519 key, not the TSS
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TransferPermit {
+    #[prost(bytes = "vec", tag = "1")]
+    pub mint: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub owner: Vec<u8>,
+    #[prost(uint64, tag = "3")]
+    pub destination_chain_id: u64,
+    #[prost(bytes = "vec", tag = "4")]
+    pub recipient_address: Vec<u8>,
+    #[prost(uint64, tag = "5")]
+    pub nonce: u64,
+    #[prost(int64, tag = "6")]
+    pub deadline: i64,
+}
+
+impl From<&crate::TransferPermit<'_>> for TransferPermit {
+    fn from(payload: &crate::TransferPermit) -> Self {
+        Self {
+            mint: payload.mint.to_vec(),
+            owner: payload.owner.to_vec(),
+            destination_chain_id: payload.destination_chain_id,
+            recipient_address: payload.recipient_address.to_vec(),
+            nonce: payload.nonce,
+            deadline: payload.deadline,
+        }
+    }
+}
+
+impl TransferPermit {
+    pub fn to_payload(&self) -> Result<crate::TransferPermit<'_>, ProtoError> {
+        Ok(crate::TransferPermit {
+            mint: fixed("mint", &self.mint)?,
+            owner: fixed("owner", &self.owner)?,
+            destination_chain_id: self.destination_chain_id,
+            recipient_address: &self.recipient_address,
+            nonce: self.nonce,
+            deadline: self.deadline,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AchievementMint {
+    #[prost(uint64, tag = "1")]
+    pub peer_chain_id: u64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub peer_contract: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub achievement_id: Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub recipient: Vec<u8>,
+    #[prost(string, tag = "5")]
+    pub metadata_uri: String,
+    #[prost(string, tag = "6")]
+    pub name: String,
+    #[prost(string, tag = "7")]
+    pub symbol: String,
+}
+
+impl From<&crate::AchievementMint<'_>> for AchievementMint {
+    fn from(payload: &crate::AchievementMint) -> Self {
+        Self {
+            peer_chain_id: payload.peer_chain_id,
+            peer_contract: payload.peer_contract.to_vec(),
+            achievement_id: payload.achievement_id.to_vec(),
+            recipient: payload.recipient.to_vec(),
+            metadata_uri: payload.metadata_uri.into(),
+            name: payload.name.into(),
+            symbol: payload.symbol.into(),
+        }
+    }
+}
+
+impl AchievementMint {
+    pub fn to_payload(&self) -> Result<crate::AchievementMint<'_>, ProtoError> {
+        Ok(crate::AchievementMint {
+            peer_chain_id: self.peer_chain_id,
+            peer_contract: &self.peer_contract,
+            achievement_id: fixed("achievement_id", &self.achievement_id)?,
+            recipient: fixed("recipient", &self.recipient)?,
+            metadata_uri: &self.metadata_uri,
+            name: &self.name,
+            symbol: &self.symbol,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MetadataSync {
+    #[prost(bytes = "vec", tag = "1")]
+    pub mint: Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub origin_chain_id: u64,
+    #[prost(bytes = "vec", tag = "3")]
+    pub origin_token_id: Vec<u8>,
+    #[prost(uint32, tag = "4")]
+    pub variant_index: u32,
+    #[prost(int64, tag = "5")]
+    pub oracle_value: i64,
+    #[prost(int64, tag = "6")]
+    pub oracle_updated_at: i64,
+    #[prost(string, tag = "7")]
+    pub metadata_uri: String,
+}
+
+impl From<&crate::MetadataSync<'_>> for MetadataSync {
+    fn from(payload: &crate::MetadataSync) -> Self {
+        Self {
+            mint: payload.mint.to_vec(),
+            origin_chain_id: payload.origin_chain_id,
+            origin_token_id: payload.origin_token_id.to_vec(),
+            variant_index: payload.variant_index.into(),
+            oracle_value: payload.oracle_value,
+            oracle_updated_at: payload.oracle_updated_at,
+            metadata_uri: payload.metadata_uri.into(),
+        }
+    }
+}
+
+impl MetadataSync {
+    pub fn to_payload(&self) -> Result<crate::MetadataSync<'_>, ProtoError> {
+        Ok(crate::MetadataSync {
+            mint: fixed("mint", &self.mint)?,
+            origin_chain_id: self.origin_chain_id,
+            origin_token_id: fixed("origin_token_id", &self.origin_token_id)?,
+            variant_index: self
+                .variant_index
+                .try_into()
+                .map_err(|_| ProtoError::VariantIndexOutOfRange(self.variant_index))?,
+            oracle_value: self.oracle_value,
+            oracle_updated_at: self.oracle_updated_at,
+            metadata_uri: &self.metadata_uri,
+        })
+    }
+}
+
+/// A signed message together with its signature
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SignedEnvelope {
+    #[prost(message, optional, tag = "1")]
+    pub domain: Option<SigningDomain>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub signature: Vec<u8>,
+    #[prost(oneof = "signed_envelope::Payload", tags = "3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13")]
+    pub payload: Option<signed_envelope::Payload>,
+}
+
+pub mod signed_envelope {
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub enum Payload {
+        #[prost(message, tag = "3")]
+        ReceiveNft(super::ReceiveNft),
+        #[prost(message, tag = "4")]
+        SwapPayment(super::SwapPayment),
+        #[prost(message, tag = "5")]
+        ReserveAttestation(super::ReserveAttestation),
+        #[prost(message, tag = "6")]
+        ForeignCollection(super::ForeignCollection),
+        #[prost(message, tag = "7")]
+        DeliveryAck(super::DeliveryAck),
+        #[prost(message, tag = "8")]
+        FailureAck(super::FailureAck),
+        #[prost(message, tag = "9")]
+        NativeReturn(super::NativeReturn),
+        #[prost(message, tag = "10")]
+        FraudProof(super::FraudProof),
+        #[prost(message, tag = "11")]
+        TransferPermit(super::TransferPermit),
+        #[prost(message, tag = "12")]
+        AchievementMint(super::AchievementMint),
+        #[prost(message, tag = "13")]
+        MetadataSync(super::MetadataSync),
+        #[prost(message, tag = "14")]
+        BundleDeliveryAck(super::BundleDeliveryAck),
+        #[prost(message, tag = "15")]
+        BundleFailureAck(super::BundleFailureAck),
+    }
+}
+
+impl SignedEnvelope {
+    /// Rebuild the exact bytes the signature covers
+    pub fn signed_message(&self) -> Result<Vec<u8>, ProtoError> {
+        use signed_envelope::Payload;
+
+        let domain = self
+            .domain
+            .as_ref()
+            .ok_or(ProtoError::MissingField("domain"))?
+            .to_domain()?;
+        Ok(match self.payload.as_ref().ok_or(ProtoError::MissingField("payload"))? {
+            Payload::ReceiveNft(payload) => domain.message(&payload.to_payload()?),
+            Payload::SwapPayment(payload) => domain.message(&payload.to_payload()?),
+            Payload::ReserveAttestation(payload) => domain.message(&payload.to_payload()?),
+            Payload::ForeignCollection(payload) => domain.message(&payload.to_payload()?),
+            Payload::DeliveryAck(payload) => domain.message(&payload.to_payload()?),
+            Payload::FailureAck(payload) => domain.message(&payload.to_payload()?),
+            Payload::NativeReturn(payload) => domain.message(&payload.to_payload()?),
+            Payload::FraudProof(payload) => domain.message(&payload.to_payload()?),
+            Payload::TransferPermit(payload) => domain.message(&payload.to_payload()?),
+            Payload::AchievementMint(payload) => domain.message(&payload.to_payload()?),
+            Payload::MetadataSync(payload) => domain.message(&payload.to_payload()?),
+            Payload::BundleDeliveryAck(payload) => domain.message(&payload.to_payload()?),
+            Payload::BundleFailureAck(payload) => domain.message(&payload.to_payload()?),
+        })
+    }
+}
+
+/// Mirrors the program's `CrossChainTransferEvent`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CrossChainTransferEvent {
+    #[prost(bytes = "vec", tag = "1")]
+    pub mint: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub owner: Vec<u8>,
+    #[prost(uint64, tag = "3")]
+    pub destination_chain_id: u64,
+    #[prost(bytes = "vec", tag = "4")]
+    pub recipient_address: Vec<u8>,
+    #[prost(uint64, tag = "5")]
+    pub nonce: u64,
+    #[prost(bytes = "vec", tag = "6")]
+    pub message_hash: Vec<u8>,
+    #[prost(int64, tag = "7")]
+    pub timestamp: i64,
+    #[prost(string, tag = "8")]
+    pub metadata_uri: String,
+    /// Empty when the NFT has no fallback
+    #[prost(string, tag = "9")]
+    pub fallback_uri: String,
+}
+
+/// Mirrors the program's `CrossChainReceiveEvent`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CrossChainReceiveEvent {
+    #[prost(bytes = "vec", tag = "1")]
+    pub mint: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub recipient: Vec<u8>,
+    #[prost(uint64, tag = "3")]
+    pub origin_chain_id: u64,
+    #[prost(uint64, tag = "4")]
+    pub nonce: u64,
+    #[prost(bytes = "vec", tag = "5")]
+    pub tss_signature: Vec<u8>,
+    #[prost(int64, tag = "6")]
+    pub timestamp: i64,
+}
+
+/// Mirrors the program's `DeliveryAcknowledgedEvent`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct DeliveryAcknowledgedEvent {
+    #[prost(bytes = "vec", tag = "1")]
+    pub mint: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub owner: Vec<u8>,
+    #[prost(uint64, tag = "3")]
+    pub nonce: u64,
+    #[prost(uint64, tag = "4")]
+    pub destination_chain_id: u64,
+    #[prost(bytes = "vec", tag = "5")]
+    pub recipient_address: Vec<u8>,
+    #[prost(bytes = "vec", tag = "6")]
+    pub destination_tx_hash: Vec<u8>,
+    #[prost(int64, tag = "7")]
+    pub timestamp: i64,
+    #[prost(uint64, tag = "8")]
+    pub gas_spent: u64,
+}
+
+/// Mirrors the program's `FailureAcknowledgedEvent`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct FailureAcknowledgedEvent {
+    #[prost(bytes = "vec", tag = "1")]
+    pub mint: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub owner: Vec<u8>,
+    #[prost(uint64, tag = "3")]
+    pub nonce: u64,
+    #[prost(uint64, tag = "4")]
+    pub destination_chain_id: u64,
+    #[prost(bytes = "vec", tag = "5")]
+    pub value_mint: Vec<u8>,
+    #[prost(uint64, tag = "6")]
+    pub value_amount: u64,
+    #[prost(int64, tag = "7")]
+    pub timestamp: i64,
+}
@@ -0,0 +1,220 @@
+use anchor_lang::prelude::*;
+use universal_nft::program::UniversalNft;
+use universal_nft_interface::{CrossChainTransferCpi, NftAppData, WriteAppDataCpi};
+
+declare_id!("CPiCa11er1111111111111111111111111111111111");
+
+/// Example integrator: keeps NFTs in a program-owned vault PDA and bridges them
+/// through Universal NFT by CPI, signing as the vault.
+#[program]
+pub mod cpi_caller {
+    use super::*;
+
+    /// Create the vault PDA that holds NFTs for this program
+    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.authority = ctx.accounts.authority.key();
+        vault.bump = ctx.bumps.vault;
+        Ok(())
+    }
+
+    /// Bridge an NFT held by the vault to another chain
+    pub fn bridge_from_vault(
+        ctx: Context<BridgeFromVault>,
+        destination_chain_id: u64,
+        recipient_address: Vec<u8>,
+        nonce: u64,
+    ) -> Result<()> {
+        let bump = ctx.accounts.vault.bump;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"vault", &[bump]]];
+
+        CrossChainTransferCpi {
+            universal_nft_program: ctx.accounts.universal_nft_program.to_account_info(),
+            program_state: ctx.accounts.program_state.to_account_info(),
+            cross_chain_config: ctx.accounts.cross_chain_config.to_account_info(),
+            blocklist: ctx.accounts.blocklist.to_account_info(),
+            opt_out_registry: ctx.accounts.opt_out_registry.to_account_info(),
+            destination_chain_config: ctx.accounts.destination_chain_config.to_account_info(),
+            nft_metadata: ctx.accounts.nft_metadata.to_account_info(),
+            collection_config: ctx
+                .accounts
+                .collection_config
+                .as_ref()
+                .map(|config| config.to_account_info()),
+            collection_ledger: ctx.accounts.collection_ledger.to_account_info(),
+            transfer_record: ctx.accounts.transfer_record.to_account_info(),
+            owner_transfer_index: ctx.accounts.owner_transfer_index.to_account_info(),
+            pending_transfers: ctx.accounts.pending_transfers.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            token_account: ctx.accounts.token_account.to_account_info(),
+            owner: ctx.accounts.vault.to_account_info(),
+            credential: ctx
+                .accounts
+                .credential
+                .as_ref()
+                .map(|credential| credential.to_account_info()),
+            gate_token_account: ctx
+                .accounts
+                .gate_token_account
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            stake_record: ctx.accounts.stake_record.to_account_info(),
+            localized_metadata: None,
+            fee_treasury: ctx
+                .accounts
+                .fee_treasury
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            payer_fee_account: ctx
+                .accounts
+                .payer_fee_account
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            treasury_fee_account: ctx
+                .accounts
+                .treasury_fee_account
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            fee_reserve: ctx.accounts.fee_reserve.to_account_info(),
+            payer: ctx.accounts.authority.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        }
+        .invoke_signed(destination_chain_id, recipient_address, nonce, signer_seeds)
+    }
+
+    /// Record state for an NFT whose holder approved this program for app data
+    pub fn write_app_data(ctx: Context<WriteNftAppData>, offset: u16, bytes: Vec<u8>) -> Result<()> {
+        let mint = ctx.accounts.mint.key();
+        let signer_seeds: &[&[&[u8]]] =
+            &[&[NftAppData::AUTHORITY_SEED, mint.as_ref(), &[ctx.bumps.app_authority]]];
+
+        WriteAppDataCpi {
+            universal_nft_program: ctx.accounts.universal_nft_program.to_account_info(),
+            program_state: ctx.accounts.program_state.to_account_info(),
+            nft_metadata: ctx.accounts.nft_metadata.to_account_info(),
+            app_data: ctx.accounts.app_data.to_account_info(),
+            app_authority: ctx.accounts.app_authority.to_account_info(),
+        }
+        .invoke_signed(offset, bytes, signer_seeds)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub authority: Pubkey,
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+pub struct InitializeVault<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Universal NFT accounts are passed through unchecked; that program validates them
+#[derive(Accounts)]
+pub struct BridgeFromVault<'info> {
+    #[account(
+        seeds = [b"vault"],
+        bump = vault.bump,
+        has_one = authority
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Pays for the transfer record, since the vault holds data and can't fund accounts
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Validated by Universal NFT
+    #[account(mut)]
+    pub program_state: UncheckedAccount<'info>,
+    /// CHECK: Validated by Universal NFT
+    #[account(mut)]
+    pub cross_chain_config: UncheckedAccount<'info>,
+    /// CHECK: Validated by Universal NFT
+    pub blocklist: UncheckedAccount<'info>,
+    /// CHECK: Validated by Universal NFT
+    pub opt_out_registry: UncheckedAccount<'info>,
+    /// CHECK: Validated by Universal NFT
+    pub destination_chain_config: UncheckedAccount<'info>,
+    /// CHECK: Validated by Universal NFT
+    #[account(mut)]
+    pub nft_metadata: UncheckedAccount<'info>,
+    /// CHECK: Validated by Universal NFT
+    #[account(mut)]
+    pub collection_config: Option<UncheckedAccount<'info>>,
+    /// CHECK: Validated by Universal NFT
+    #[account(mut)]
+    pub collection_ledger: UncheckedAccount<'info>,
+    /// CHECK: Validated by Universal NFT
+    #[account(mut)]
+    pub transfer_record: UncheckedAccount<'info>,
+    /// CHECK: Validated by Universal NFT
+    #[account(mut)]
+    pub owner_transfer_index: UncheckedAccount<'info>,
+    /// CHECK: Validated by Universal NFT
+    #[account(mut)]
+    pub pending_transfers: UncheckedAccount<'info>,
+    /// CHECK: Validated by Universal NFT
+    pub mint: UncheckedAccount<'info>,
+    /// CHECK: Validated by Universal NFT
+    pub token_account: UncheckedAccount<'info>,
+    /// CHECK: Validated by Universal NFT; a credential naming the vault
+    pub credential: Option<UncheckedAccount<'info>>,
+    /// CHECK: Validated by Universal NFT; the vault's account of the gate mint
+    pub gate_token_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: Validated by Universal NFT; the NFT's stake PDA
+    #[account(mut)]
+    pub stake_record: UncheckedAccount<'info>,
+    /// CHECK: Validated by Universal NFT
+    #[account(mut)]
+    pub fee_treasury: Option<UncheckedAccount<'info>>,
+    /// CHECK: Validated by Universal NFT; the authority's account of the fee mint
+    #[account(mut)]
+    pub payer_fee_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: Validated by Universal NFT
+    #[account(mut)]
+    pub treasury_fee_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: Validated by Universal NFT
+    #[account(mut)]
+    pub fee_reserve: UncheckedAccount<'info>,
+
+    pub universal_nft_program: Program<'info, UniversalNft>,
+    /// CHECK: Validated by Universal NFT
+    pub token_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WriteNftAppData<'info> {
+    /// CHECK: Signs the app data write; Universal NFT checks the holder approved it
+    #[account(seeds = [NftAppData::AUTHORITY_SEED, mint.key().as_ref()], bump)]
+    pub app_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Seeds the app authority; Universal NFT matches it to the app data
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by Universal NFT
+    #[account(mut)]
+    pub program_state: UncheckedAccount<'info>,
+    /// CHECK: Validated by Universal NFT
+    pub nft_metadata: UncheckedAccount<'info>,
+    /// CHECK: Validated by Universal NFT
+    #[account(mut)]
+    pub app_data: UncheckedAccount<'info>,
+
+    pub universal_nft_program: Program<'info, UniversalNft>,
+}
@@ -0,0 +1,53 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Program ID of local builds, matching `Anchor.toml`
+const LOCALNET_PROGRAM_ID: &str = "UnivNFT111111111111111111111111111111111111";
+
+/// Embeds the git commit the program is built from, so deployed binaries can be
+/// traced back to source. `UNIVERSAL_NFT_GIT_HASH` overrides it for builds
+/// without a git checkout (e.g. verifiable builds).
+///
+/// Also writes the program ID: `UNIVERSAL_NFT_PROGRAM_ID`, which `devnet` and
+/// `mainnet` builds must set, or the localnet placeholder.
+fn main() {
+    println!("cargo:rerun-if-env-changed=UNIVERSAL_NFT_GIT_HASH");
+    println!("cargo:rerun-if-changed=../../.git/HEAD");
+    println!("cargo:rerun-if-changed=../../.git/refs");
+
+    let git_hash = std::env::var("UNIVERSAL_NFT_GIT_HASH")
+        .ok()
+        .filter(|hash| !hash.is_empty())
+        .or_else(|| {
+            Command::new("git")
+                .args(["rev-parse", "--short=12", "HEAD"])
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .and_then(|output| String::from_utf8(output.stdout).ok())
+                .map(|hash| hash.trim().to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+
+    println!("cargo:rustc-env=UNIVERSAL_NFT_GIT_HASH={}", git_hash);
+
+    println!("cargo:rerun-if-env-changed=UNIVERSAL_NFT_PROGRAM_ID");
+    let is_cluster_build =
+        env::var_os("CARGO_FEATURE_DEVNET").is_some() || env::var_os("CARGO_FEATURE_MAINNET").is_some();
+    let program_id = match env::var("UNIVERSAL_NFT_PROGRAM_ID").ok().filter(|id| !id.is_empty()) {
+        Some(id) => id,
+        None if is_cluster_build => {
+            panic!("UNIVERSAL_NFT_PROGRAM_ID must be set for devnet and mainnet builds")
+        }
+        None => LOCALNET_PROGRAM_ID.to_string(),
+    };
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    fs::write(
+        out_dir.join("program_id.rs"),
+        format!("declare_id!(\"{}\");\n", program_id),
+    )
+    .expect("failed to write program_id.rs");
+}
@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::{hash, hashv};
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use universal_nft_messages::{OutboundPayload, SignedPayload};
+use crate::state::{BridgeAdapterKind, CrossChainConfig};
+use crate::error::UniversalNftError;
+use crate::utils::security::signing_domain;
+use super::BridgeAdapter;
+
+/// Seed of the PDA registered with the endpoint as this program's OApp; it
+/// signs `send` and `clear`
+pub const LZ_OAPP_SEED: &[u8] = b"lz_oapp";
+
+/// Delivery details the LayerZero executor passes to `lz_receive`. The
+/// message itself is rebuilt from the NFT fields, as for the other adapters.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LzReceiveParams {
+    pub src_eid: u32,
+    pub sender: [u8; 32],
+    pub nonce: u64,
+    pub guid: [u8; 32],
+}
+
+/// Arguments of the endpoint's `clear` instruction
+#[derive(AnchorSerialize)]
+struct ClearParams<'m> {
+    receiver: Pubkey,
+    src_eid: u32,
+    sender: [u8; 32],
+    nonce: u64,
+    guid: [u8; 32],
+    message: &'m [u8],
+}
+
+/// Arguments of the endpoint's `send` instruction
+#[derive(AnchorSerialize)]
+struct SendParams<'m> {
+    dst_eid: u32,
+    receiver: [u8; 32],
+    message: &'m [u8],
+    options: Vec<u8>,
+    native_fee: u64,
+    lz_token_fee: u64,
+}
+
+/// LayerZero transport through the Solana endpoint program. Inbound, the
+/// message is cleared from the endpoint, which fails unless the DVNs verified
+/// exactly that message from the configured peer. Outbound, the transfer
+/// message is sent to the peer on `remote_eid`. Either way the endpoint's
+/// accounts come in the instruction's remaining accounts, ordered as the
+/// endpoint expects, with the OApp PDA among them.
+pub struct LayerZeroAdapter<'a, 'info> {
+    config: &'a CrossChainConfig,
+    endpoint: Pubkey,
+    remote_eid: u32,
+    peer: [u8; 32],
+    endpoint_accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'a, 'info> LayerZeroAdapter<'a, 'info> {
+    pub fn new(
+        config: &'a CrossChainConfig,
+        endpoint: Pubkey,
+        remote_eid: u32,
+        peer: [u8; 32],
+        endpoint_accounts: &'a [AccountInfo<'info>],
+    ) -> Self {
+        Self {
+            config,
+            endpoint,
+            remote_eid,
+            peer,
+            endpoint_accounts,
+        }
+    }
+
+    /// Invoke `instruction` on the endpoint, signed by the OApp PDA
+    fn invoke_endpoint<T: AnchorSerialize>(&self, instruction: &str, args: &T) -> Result<()> {
+        let (oapp, bump) = Pubkey::find_program_address(&[LZ_OAPP_SEED], &crate::ID);
+
+        let mut data = hashv(&[b"global:", instruction.as_bytes()]).to_bytes()[..8].to_vec();
+        args.serialize(&mut data)?;
+
+        let accounts = self
+            .endpoint_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer || account.key() == oapp,
+                is_writable: account.is_writable,
+            })
+            .collect();
+
+        invoke_signed(
+            &Instruction {
+                program_id: self.endpoint,
+                accounts,
+                data,
+            },
+            self.endpoint_accounts,
+            &[&[LZ_OAPP_SEED, &[bump]]],
+        )?;
+        Ok(())
+    }
+}
+
+impl BridgeAdapter for LayerZeroAdapter<'_, '_> {
+    fn kind(&self) -> BridgeAdapterKind {
+        BridgeAdapterKind::LayerZero {
+            endpoint: self.endpoint,
+            remote_eid: self.remote_eid,
+            peer: self.peer,
+        }
+    }
+
+    fn gateway(&self) -> Pubkey {
+        self.endpoint
+    }
+
+    /// The LayerZero message is the same domain-prefixed message the TSS would sign
+    fn inbound_message<P: SignedPayload>(&self, payload: &P) -> Vec<u8> {
+        signing_domain(self.config.chain_id).message(payload)
+    }
+
+    /// The attestation is the borsh-encoded `LzReceiveParams` of the delivery
+    fn verify_inbound(&self, message: &[u8], attestation: &[u8]) -> Result<()> {
+        let delivery = LzReceiveParams::try_from_slice(attestation)
+            .map_err(|_| UniversalNftError::InvalidLzDelivery)?;
+        require!(
+            delivery.src_eid == self.remote_eid && delivery.sender == self.peer,
+            UniversalNftError::PeerMismatch
+        );
+
+        self.invoke_endpoint(
+            "clear",
+            &ClearParams {
+                receiver: crate::ID,
+                src_eid: delivery.src_eid,
+                sender: delivery.sender,
+                nonce: delivery.nonce,
+                guid: delivery.guid,
+                message,
+            },
+        )
+    }
+
+    fn can_send(&self) -> bool {
+        true
+    }
+
+    fn outbound_message_hash<T: OutboundPayload>(&self, transfer: &T) -> [u8; 32] {
+        hash(&signing_domain(self.config.chain_id).outbound_message(transfer)).to_bytes()
+    }
+
+    fn dispatch_outbound<T: OutboundPayload>(&self, transfer: &T, _message_hash: &[u8; 32]) -> Result<()> {
+        let message = signing_domain(self.config.chain_id).outbound_message(transfer);
+        self.invoke_endpoint(
+            "send",
+            &SendParams {
+                dst_eid: self.remote_eid,
+                receiver: self.peer,
+                message: &message,
+                options: Vec::new(),
+                // A ceiling only: the endpoint charges its quoted fee to the payer
+                native_fee: u64::MAX,
+                lz_token_fee: 0,
+            },
+        )
+    }
+}
@@ -0,0 +1,148 @@
+//! Transports that carry NFTs between Solana and other chains.
+//!
+//! Each registered chain names the adapter that serves it in
+//! `ChainConfig::adapter`. Transfer and receive instructions resolve it with
+//! [`Adapter::for_chain`] and go through [`BridgeAdapter`] for everything
+//! transport-specific: how inbound messages are laid out and attested, and
+//! how outbound transfers are handed off. A new transport adds a
+//! `BridgeAdapterKind` variant and an implementation here; the NFT logic in
+//! the instructions stays as it is.
+
+pub mod zetachain;
+pub mod wormhole;
+pub mod layerzero;
+
+pub use zetachain::ZetaChainAdapter;
+pub use wormhole::WormholeAdapter;
+pub use layerzero::{LayerZeroAdapter, LzReceiveParams};
+
+use anchor_lang::prelude::*;
+use universal_nft_messages::{OutboundPayload, SignedPayload};
+use crate::state::{BridgeAdapterKind, ChainConfig, CrossChainConfig};
+
+pub trait BridgeAdapter {
+    fn kind(&self) -> BridgeAdapterKind;
+
+    /// Gateway or endpoint program the transport delivers through
+    fn gateway(&self) -> Pubkey;
+
+    /// Bytes the transport attests for an inbound `payload`
+    fn inbound_message<P: SignedPayload>(&self, payload: &P) -> Vec<u8>;
+
+    /// Check that `attestation` covers `message`
+    fn verify_inbound(&self, message: &[u8], attestation: &[u8]) -> Result<()>;
+
+    /// Whether outbound transfers can be sent over this transport
+    fn can_send(&self) -> bool;
+
+    /// Identifier of an outbound transfer, emitted with it so relayers and
+    /// previews can refer to it
+    fn outbound_message_hash<T: OutboundPayload>(&self, transfer: &T) -> [u8; 32];
+
+    /// Hand an outbound transfer to the transport
+    fn dispatch_outbound<T: OutboundPayload>(&self, transfer: &T, message_hash: &[u8; 32]) -> Result<()>;
+}
+
+/// Accounts an adapter reads beyond the instruction's own
+#[derive(Clone, Copy, Default)]
+pub struct AdapterAccounts<'a, 'info> {
+    /// Posted VAA, read by the Wormhole adapter on the inbound path
+    pub posted_vaa: Option<&'a AccountInfo<'info>>,
+    /// Endpoint accounts for the LayerZero adapter, from the remaining accounts
+    pub endpoint_accounts: &'a [AccountInfo<'info>],
+}
+
+/// The adapter configured for a chain
+pub enum Adapter<'a, 'info> {
+    ZetaChain(ZetaChainAdapter<'a>),
+    Wormhole(WormholeAdapter<'a, 'info>),
+    LayerZero(LayerZeroAdapter<'a, 'info>),
+}
+
+impl<'a, 'info> Adapter<'a, 'info> {
+    pub fn for_chain(
+        chain_config: &ChainConfig,
+        cross_chain_config: &'a CrossChainConfig,
+        accounts: AdapterAccounts<'a, 'info>,
+    ) -> Self {
+        match chain_config.adapter {
+            BridgeAdapterKind::ZetaChain => Self::ZetaChain(ZetaChainAdapter::new(cross_chain_config)),
+            BridgeAdapterKind::Wormhole { core_bridge, emitter_chain, emitter_address } => {
+                Self::Wormhole(WormholeAdapter::new(
+                    cross_chain_config,
+                    core_bridge,
+                    emitter_chain,
+                    emitter_address,
+                    accounts.posted_vaa,
+                ))
+            }
+            BridgeAdapterKind::LayerZero { endpoint, remote_eid, peer } => {
+                Self::LayerZero(LayerZeroAdapter::new(
+                    cross_chain_config,
+                    endpoint,
+                    remote_eid,
+                    peer,
+                    accounts.endpoint_accounts,
+                ))
+            }
+        }
+    }
+}
+
+impl BridgeAdapter for Adapter<'_, '_> {
+    fn kind(&self) -> BridgeAdapterKind {
+        match self {
+            Self::ZetaChain(adapter) => adapter.kind(),
+            Self::Wormhole(adapter) => adapter.kind(),
+            Self::LayerZero(adapter) => adapter.kind(),
+        }
+    }
+
+    fn gateway(&self) -> Pubkey {
+        match self {
+            Self::ZetaChain(adapter) => adapter.gateway(),
+            Self::Wormhole(adapter) => adapter.gateway(),
+            Self::LayerZero(adapter) => adapter.gateway(),
+        }
+    }
+
+    fn inbound_message<P: SignedPayload>(&self, payload: &P) -> Vec<u8> {
+        match self {
+            Self::ZetaChain(adapter) => adapter.inbound_message(payload),
+            Self::Wormhole(adapter) => adapter.inbound_message(payload),
+            Self::LayerZero(adapter) => adapter.inbound_message(payload),
+        }
+    }
+
+    fn verify_inbound(&self, message: &[u8], attestation: &[u8]) -> Result<()> {
+        match self {
+            Self::ZetaChain(adapter) => adapter.verify_inbound(message, attestation),
+            Self::Wormhole(adapter) => adapter.verify_inbound(message, attestation),
+            Self::LayerZero(adapter) => adapter.verify_inbound(message, attestation),
+        }
+    }
+
+    fn can_send(&self) -> bool {
+        match self {
+            Self::ZetaChain(adapter) => adapter.can_send(),
+            Self::Wormhole(adapter) => adapter.can_send(),
+            Self::LayerZero(adapter) => adapter.can_send(),
+        }
+    }
+
+    fn outbound_message_hash<T: OutboundPayload>(&self, transfer: &T) -> [u8; 32] {
+        match self {
+            Self::ZetaChain(adapter) => adapter.outbound_message_hash(transfer),
+            Self::Wormhole(adapter) => adapter.outbound_message_hash(transfer),
+            Self::LayerZero(adapter) => adapter.outbound_message_hash(transfer),
+        }
+    }
+
+    fn dispatch_outbound<T: OutboundPayload>(&self, transfer: &T, message_hash: &[u8; 32]) -> Result<()> {
+        match self {
+            Self::ZetaChain(adapter) => adapter.dispatch_outbound(transfer, message_hash),
+            Self::Wormhole(adapter) => adapter.dispatch_outbound(transfer, message_hash),
+            Self::LayerZero(adapter) => adapter.dispatch_outbound(transfer, message_hash),
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use universal_nft_messages::{OutboundPayload, SignedPayload};
+use crate::state::{BridgeAdapterKind, CrossChainConfig};
+use crate::error::UniversalNftError;
+use crate::utils::security::signing_domain;
+use super::BridgeAdapter;
+
+/// Account discriminator of a VAA the core bridge has verified and posted
+const POSTED_VAA_MAGIC: &[u8; 3] = b"vaa";
+
+/// Body of a core bridge `PostedVAA` account, after the magic
+#[derive(AnchorDeserialize)]
+struct PostedVaaData {
+    _vaa_version: u8,
+    _consistency_level: u8,
+    _vaa_time: u32,
+    _vaa_signature_account: Pubkey,
+    _submission_time: u32,
+    _nonce: u32,
+    _sequence: u64,
+    emitter_chain: u16,
+    emitter_address: [u8; 32],
+    payload: Vec<u8>,
+}
+
+/// Wormhole transport, inbound only. The guardian signatures are checked by
+/// the core bridge when the VAA is posted; here we only accept a posted VAA
+/// owned by the configured core bridge, from the emitter registered for the
+/// origin chain, whose payload is exactly the expected message.
+pub struct WormholeAdapter<'a, 'info> {
+    config: &'a CrossChainConfig,
+    core_bridge: Pubkey,
+    emitter_chain: u16,
+    emitter_address: [u8; 32],
+    posted_vaa: Option<&'a AccountInfo<'info>>,
+}
+
+impl<'a, 'info> WormholeAdapter<'a, 'info> {
+    pub fn new(
+        config: &'a CrossChainConfig,
+        core_bridge: Pubkey,
+        emitter_chain: u16,
+        emitter_address: [u8; 32],
+        posted_vaa: Option<&'a AccountInfo<'info>>,
+    ) -> Self {
+        Self {
+            config,
+            core_bridge,
+            emitter_chain,
+            emitter_address,
+            posted_vaa,
+        }
+    }
+}
+
+impl BridgeAdapter for WormholeAdapter<'_, '_> {
+    fn kind(&self) -> BridgeAdapterKind {
+        BridgeAdapterKind::Wormhole {
+            core_bridge: self.core_bridge,
+            emitter_chain: self.emitter_chain,
+            emitter_address: self.emitter_address,
+        }
+    }
+
+    fn gateway(&self) -> Pubkey {
+        self.core_bridge
+    }
+
+    /// The VAA payload carries the same domain-prefixed message the TSS would sign
+    fn inbound_message<P: SignedPayload>(&self, payload: &P) -> Vec<u8> {
+        signing_domain(self.config.chain_id).message(payload)
+    }
+
+    /// The attestation is the posted VAA account; the instruction argument is unused
+    fn verify_inbound(&self, message: &[u8], _attestation: &[u8]) -> Result<()> {
+        let posted_vaa = self.posted_vaa.ok_or(UniversalNftError::InvalidVaa)?;
+        require_keys_eq!(*posted_vaa.owner, self.core_bridge, UniversalNftError::InvalidVaa);
+
+        let data = posted_vaa.try_borrow_data()?;
+        require!(data.starts_with(POSTED_VAA_MAGIC), UniversalNftError::InvalidVaa);
+        let vaa = PostedVaaData::deserialize(&mut &data[POSTED_VAA_MAGIC.len()..])
+            .map_err(|_| UniversalNftError::InvalidVaa)?;
+
+        require!(
+            vaa.emitter_chain == self.emitter_chain && vaa.emitter_address == self.emitter_address,
+            UniversalNftError::EmitterMismatch
+        );
+        require!(vaa.payload == message, UniversalNftError::InvalidVaa);
+        Ok(())
+    }
+
+    fn can_send(&self) -> bool {
+        false
+    }
+
+    fn outbound_message_hash<T: OutboundPayload>(&self, transfer: &T) -> [u8; 32] {
+        hash(&signing_domain(self.config.chain_id).outbound_message(transfer)).to_bytes()
+    }
+
+    fn dispatch_outbound<T: OutboundPayload>(&self, _transfer: &T, _message_hash: &[u8; 32]) -> Result<()> {
+        err!(UniversalNftError::AdapterCannotSend)
+    }
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use universal_nft_messages::{OutboundPayload, SignedPayload};
+use crate::state::{BridgeAdapterKind, CrossChainConfig};
+use crate::error::UniversalNftError;
+use crate::utils::security::{signing_domain, verify_tss_signature};
+use super::BridgeAdapter;
+
+/// ZetaChain gateway transport. Inbound messages are signed by the ZetaChain
+/// TSS; outbound transfers are picked up by the gateway from the transfer
+/// event. Configured by `CrossChainConfig`.
+pub struct ZetaChainAdapter<'a> {
+    config: &'a CrossChainConfig,
+}
+
+impl<'a> ZetaChainAdapter<'a> {
+    pub fn new(config: &'a CrossChainConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl BridgeAdapter for ZetaChainAdapter<'_> {
+    fn kind(&self) -> BridgeAdapterKind {
+        BridgeAdapterKind::ZetaChain
+    }
+
+    fn gateway(&self) -> Pubkey {
+        self.config.gateway_address
+    }
+
+    fn inbound_message<P: SignedPayload>(&self, payload: &P) -> Vec<u8> {
+        signing_domain(self.config.chain_id).message(payload)
+    }
+
+    fn verify_inbound(&self, message: &[u8], attestation: &[u8]) -> Result<()> {
+        let is_valid = verify_tss_signature(message, attestation, &self.config.tss_address)?;
+        require!(is_valid, UniversalNftError::InvalidTssSignature);
+        Ok(())
+    }
+
+    fn can_send(&self) -> bool {
+        true
+    }
+
+    fn outbound_message_hash<T: OutboundPayload>(&self, transfer: &T) -> [u8; 32] {
+        hash(&signing_domain(self.config.chain_id).outbound_message(transfer)).to_bytes()
+    }
+
+    fn dispatch_outbound<T: OutboundPayload>(&self, _transfer: &T, _message_hash: &[u8; 32]) -> Result<()> {
+        // The gateway watches for the transfer event; there is nothing to invoke
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+//! Per-deployment settings fixed at build time. The `devnet` and `mainnet`
+//! features select the cluster; without either, the build is for localnet.
+
+use anchor_lang::prelude::*;
+#[cfg(any(feature = "devnet", feature = "mainnet"))]
+use anchor_lang::solana_program::pubkey;
+use universal_nft_messages::chain_id;
+
+#[cfg(all(feature = "devnet", feature = "mainnet"))]
+compile_error!("the `devnet` and `mainnet` features are mutually exclusive");
+
+/// The chain ID this deployment identifies as. Native NFTs record it as their
+/// origin, and `initialize` only accepts it for `CrossChainConfig::chain_id`.
+#[cfg(feature = "mainnet")]
+pub const SELF_CHAIN_ID: u64 = chain_id::SOLANA_MAINNET;
+#[cfg(all(feature = "devnet", not(feature = "mainnet")))]
+pub const SELF_CHAIN_ID: u64 = chain_id::SOLANA_DEVNET;
+#[cfg(not(any(feature = "devnet", feature = "mainnet")))]
+pub const SELF_CHAIN_ID: u64 = chain_id::SOLANA_LOCALNET;
+
+/// ZetaChain's gateway program, which `initialize` requires on public
+/// clusters. Localnet accepts any gateway, so tests can stand in their own.
+#[cfg(any(feature = "devnet", feature = "mainnet"))]
+pub const GATEWAY_PROGRAM_ID: Option<Pubkey> =
+    Some(pubkey!("ZETAjseVjuFsxdRxo6MmTCvqFwb3ZHUx56Co3vCmGis"));
+#[cfg(not(any(feature = "devnet", feature = "mainnet")))]
+pub const GATEWAY_PROGRAM_ID: Option<Pubkey> = None;
+
+/// Demo builds accept any non-empty TSS signature. Mainnet builds are not
+/// demo builds, so they reject TSS-signed messages until real verification
+/// replaces `verify_tss_signature`.
+pub const DEMO_MODE: bool = !cfg!(feature = "mainnet");
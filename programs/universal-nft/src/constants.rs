@@ -0,0 +1,211 @@
+//! PDA seeds and account limits, exported as IDL constants so clients derive
+//! addresses and size inputs from the IDL rather than hard-coding them.
+
+use anchor_lang::prelude::*;
+use crate::state::{
+    NftMetadata, Blocklist, CpiAllowlist, OptOutRegistry, ChildIndex, HolderSnapshot,
+    OwnerTransferIndex, PendingTransfers, BundleTransfer, ForeignCollection, NftAppData,
+    Watcher, Challenge, StagedInbound, ProgramState, CrossChainTransfer, CrossChainReceipt, CollectionRoyalty,
+};
+use crate::utils::validation::{MAX_NAME_CHARS, MAX_SYMBOL_CHARS};
+
+// Seeds. Each PDA's remaining seeds (mint, chain ID, nonce, ...) are listed
+// with the account in the IDL.
+
+#[constant]
+pub const ACHIEVEMENT_MINT_SEED: &[u8] = b"achievement_mint";
+#[constant]
+pub const APP_DATA_SEED: &[u8] = b"app_data";
+#[constant]
+pub const AUCTION_ESCROW_SEED: &[u8] = b"auction_escrow";
+#[constant]
+pub const AUDIT_ENTRY_SEED: &[u8] = b"audit_entry";
+#[constant]
+pub const AUDIT_LOG_SEED: &[u8] = b"audit_log";
+#[constant]
+pub const BLOCKLIST_SEED: &[u8] = b"blocklist";
+#[constant]
+pub const BUNDLE_TRANSFER_SEED: &[u8] = b"bundle_transfer";
+#[constant]
+pub const CHAIN_CONFIG_SEED: &[u8] = b"chain_config";
+#[constant]
+pub const CHALLENGE_SEED: &[u8] = b"challenge";
+#[constant]
+pub const CHILD_ESCROW_SEED: &[u8] = b"child_escrow";
+#[constant]
+pub const CHILD_INDEX_SEED: &[u8] = b"child_index";
+#[constant]
+pub const COLLECTION_CONFIG_SEED: &[u8] = b"collection_config";
+#[constant]
+pub const COLLECTION_LEDGER_SEED: &[u8] = b"collection_ledger";
+#[constant]
+pub const COLLECTION_ROYALTY_SEED: &[u8] = b"collection_royalty";
+#[constant]
+pub const CPI_ALLOWLIST_SEED: &[u8] = b"cpi_allowlist";
+#[constant]
+pub const CREATOR_APPROVAL_SEED: &[u8] = b"creator_approval";
+#[constant]
+pub const CREATOR_SET_SEED: &[u8] = b"creator_set";
+#[constant]
+pub const CROSS_CHAIN_CONFIG_SEED: &[u8] = b"cross_chain_config";
+#[constant]
+pub const CROSS_CHAIN_RECEIPT_SEED: &[u8] = b"cross_chain_receipt";
+#[constant]
+pub const CROSS_CHAIN_TRANSFER_SEED: &[u8] = b"cross_chain_transfer";
+#[constant]
+pub const DELEGATE_LISTING_SEED: &[u8] = b"delegate_listing";
+#[constant]
+pub const DUTCH_AUCTION_SEED: &[u8] = b"dutch_auction";
+#[constant]
+pub const DYNAMIC_METADATA_SEED: &[u8] = b"dynamic_metadata";
+#[constant]
+pub const FEE_RESERVE_SEED: &[u8] = b"fee_reserve";
+/// Lamport fees are held by the PDA itself, SPL fees by its associated token account for the fee mint
+#[constant]
+pub const FEE_TREASURY_SEED: &[u8] = b"fee_treasury";
+#[constant]
+pub const FOREIGN_COLLECTION_SEED: &[u8] = b"foreign_collection";
+#[constant]
+pub const FRACTION_ESCROW_SEED: &[u8] = b"fraction_escrow";
+#[constant]
+pub const FRACTION_MINT_SEED: &[u8] = b"fraction_mint";
+#[constant]
+pub const FRACTION_VAULT_SEED: &[u8] = b"fraction_vault";
+#[constant]
+pub const FREEZE_AUTHORITY_SEED: &[u8] = b"freeze_authority";
+#[constant]
+pub const HOLDER_SNAPSHOT_SEED: &[u8] = b"holder_snapshot";
+#[constant]
+pub const INBOUND_MINT_SEED: &[u8] = b"inbound_mint";
+#[constant]
+pub const INBOUND_NONCE_SEED: &[u8] = b"inbound_nonce";
+#[constant]
+pub const LOCALIZED_METADATA_SEED: &[u8] = b"localized_metadata";
+#[constant]
+pub const MINT_GUARD_SEED: &[u8] = b"mint_guard";
+#[constant]
+pub const MINT_LIMITS_SEED: &[u8] = b"mint_limits";
+#[constant]
+pub const NFT_METADATA_SEED: &[u8] = b"nft_metadata";
+#[constant]
+pub const OFFER_SEED: &[u8] = b"offer";
+#[constant]
+pub const OPERATOR_CONFIG_SEED: &[u8] = b"operator_config";
+#[constant]
+pub const OPT_OUT_REGISTRY_SEED: &[u8] = b"opt_out_registry";
+#[constant]
+pub const OPTIMISTIC_RECEIVE_SEED: &[u8] = b"optimistic_receive";
+#[constant]
+pub const ORACLE_FEED_SEED: &[u8] = b"oracle_feed";
+#[constant]
+pub const OWNER_TRANSFER_INDEX_SEED: &[u8] = b"owner_transfer_index";
+#[constant]
+pub const PENDING_RETURN_SEED: &[u8] = b"pending_return";
+#[constant]
+pub const PENDING_TRANSFERS_SEED: &[u8] = b"pending_transfers";
+#[constant]
+pub const PROGRAM_STATE_SEED: &[u8] = b"program_state";
+#[constant]
+pub const REDEMPTION_SEED: &[u8] = b"redemption";
+#[constant]
+pub const RELAYER_SEED: &[u8] = b"relayer";
+#[constant]
+pub const RELEASED_MINT_SEED: &[u8] = b"released_mint";
+#[constant]
+pub const RENT_DEPOSIT_SEED: &[u8] = b"rent_deposit";
+#[constant]
+pub const REWARD_CONFIG_SEED: &[u8] = b"reward_config";
+#[constant]
+pub const REWARD_MINT_SEED: &[u8] = b"reward_mint";
+#[constant]
+pub const REWARD_MULTIPLIER_SEED: &[u8] = b"reward_multiplier";
+#[constant]
+pub const STAGED_INBOUND_SEED: &[u8] = b"staged_inbound";
+#[constant]
+pub const STAKE_RECORD_SEED: &[u8] = b"stake_record";
+#[constant]
+pub const SWAP_ORDER_SEED: &[u8] = b"swap_order";
+#[constant]
+pub const SYMBOL_REGISTRY_SEED: &[u8] = b"symbol_registry";
+#[constant]
+pub const SYMBOL_RESERVATION_SEED: &[u8] = b"symbol_reservation";
+#[constant]
+pub const TICKET_CHECK_IN_SEED: &[u8] = b"ticket_check_in";
+#[constant]
+pub const TICKET_CONFIG_SEED: &[u8] = b"ticket_config";
+#[constant]
+pub const TRUSTED_PEER_SEED: &[u8] = b"trusted_peer";
+#[constant]
+pub const VALUE_ESCROW_SEED: &[u8] = b"value_escrow";
+#[constant]
+pub const WALLET_MINT_COUNTER_SEED: &[u8] = b"wallet_mint_counter";
+#[constant]
+pub const WATCHER_SEED: &[u8] = b"watcher";
+#[constant]
+pub const WRAP_ESCROW_SEED: &[u8] = b"wrap_escrow";
+#[constant]
+pub const WRAPPED_MINT_SEED: &[u8] = b"wrapped_mint";
+
+/// Seed a calling program signs with to write an NFT's app data
+#[constant]
+pub const APP_DATA_AUTHORITY_SEED: &[u8] = NftAppData::AUTHORITY_SEED;
+
+// Limits, mirroring the associated constants the program checks against
+
+#[constant]
+pub const EVENT_SCHEMA_VERSION: u8 = ProgramState::EVENT_SCHEMA_VERSION;
+#[constant]
+pub const MAX_NAME_LEN: u8 = MAX_NAME_CHARS as u8;
+#[constant]
+pub const MAX_SYMBOL_LEN: u8 = MAX_SYMBOL_CHARS as u8;
+/// URI capacity of a newly created metadata account
+#[constant]
+pub const DEFAULT_URI_LEN: u16 = NftMetadata::DEFAULT_URI_LEN as u16;
+#[constant]
+pub const MAX_EXPANDED_URI_LEN: u16 = NftMetadata::MAX_EXPANDED_URI_LEN as u16;
+#[constant]
+pub const MAX_BUNDLE_ITEMS: u8 = BundleTransfer::MAX_ITEMS as u8;
+#[constant]
+pub const MAX_CHILDREN: u8 = ChildIndex::MAX_CHILDREN as u8;
+#[constant]
+pub const MAX_BLOCKED_ACCOUNTS: u16 = Blocklist::MAX_BLOCKED_ACCOUNTS as u16;
+#[constant]
+pub const MAX_BLOCKED_FOREIGN_ADDRESSES: u16 = Blocklist::MAX_BLOCKED_FOREIGN_ADDRESSES as u16;
+#[constant]
+pub const MAX_FOREIGN_ADDRESS_LEN: u16 = Blocklist::MAX_FOREIGN_ADDRESS_LEN as u16;
+#[constant]
+pub const MAX_OPT_OUT_ENTRIES: u16 = OptOutRegistry::MAX_ENTRIES as u16;
+#[constant]
+pub const MAX_CPI_ALLOWLIST_PROGRAMS: u16 = CpiAllowlist::MAX_PROGRAMS as u16;
+#[constant]
+pub const MAX_SNAPSHOT_ENTRIES: u16 = HolderSnapshot::MAX_ENTRIES as u16;
+#[constant]
+pub const MAX_OWNER_TRANSFER_INDEX_ENTRIES: u16 = OwnerTransferIndex::MAX_ENTRIES as u16;
+#[constant]
+pub const MAX_RECENT_PENDING_TRANSFERS: u16 = PendingTransfers::MAX_RECENT as u16;
+#[constant]
+pub const MAX_EXPORT_PAGE: u16 = crate::instructions::export_state::MAX_EXPORT_PAGE as u16;
+#[constant]
+pub const APP_DATA_LEN: u16 = NftAppData::DATA_LEN as u16;
+#[constant]
+pub const MAX_EVIDENCE_LEN: u16 = Challenge::MAX_EVIDENCE_LEN as u16;
+#[constant]
+pub const MAX_ROYALTY_BASIS_POINTS: u16 = ForeignCollection::MAX_ROYALTY_BASIS_POINTS;
+#[constant]
+pub const MAX_ROYALTY_CREATORS: u16 = CollectionRoyalty::MAX_CREATORS as u16;
+/// Lamports a watcher must bond to challenge optimistic receives
+#[constant]
+pub const WATCHER_MIN_BOND: u64 = Watcher::MIN_BOND;
+#[constant]
+pub const RELAYER_PRIORITY_SECONDS: i64 = StagedInbound::RELAYER_PRIORITY_SECONDS;
+#[constant]
+pub const MAX_ATTESTATION_AGE: i64 = crate::instructions::verify_reserves::MAX_ATTESTATION_AGE;
+
+// Account sizes, discriminator included, for rent estimates
+
+#[constant]
+pub const NFT_METADATA_SPACE: u32 = (8 + NftMetadata::INIT_SPACE) as u32;
+#[constant]
+pub const CROSS_CHAIN_TRANSFER_SPACE: u32 = (8 + CrossChainTransfer::INIT_SPACE) as u32;
+#[constant]
+pub const CROSS_CHAIN_RECEIPT_SPACE: u32 = (8 + CrossChainReceipt::INIT_SPACE) as u32;
@@ -1,27 +1,190 @@
 use anchor_lang::prelude::*;
 
+/// Error codes are grouped into stable ranges so monitoring can bucket
+/// failures by number. Anchor adds 6000, so `1xx` surfaces as `61xx`:
+///
+/// - `1xx` configuration and program state
+/// - `2xx` minting and NFT metadata
+/// - `3xx` outbound transfers and swaps
+/// - `4xx` inbound receives
+/// - `5xx` security checks
+///
+/// New variants go at the end of their range; existing codes never move.
 #[error_code]
 pub enum UniversalNftError {
+    // 1xx: configuration and program state
     #[msg("Program not initialized")]
-    ProgramNotInitialized,
+    ProgramNotInitialized = 100,
 
     #[msg("Cross-chain functionality is paused")]
     CrossChainPaused,
 
-    #[msg("Cross-chain transfers not enabled for this NFT")]
-    CrossChainNotEnabled,
+    #[msg("Invalid gateway address")]
+    InvalidGateway,
 
-    #[msg("NFT is locked for cross-chain transfer")]
-    NftLocked,
+    #[msg("Invalid TSS authority")]
+    InvalidTssAuthority,
+
+    #[msg("Unsupported destination chain")]
+    UnsupportedChain,
+
+    #[msg("Cross-chain functionality is paused for this chain")]
+    ChainPaused,
+
+    #[msg("Compute budget exceeded")]
+    ComputeBudgetExceeded,
+
+    #[msg("Transaction must include a SetComputeUnitLimit covering this instruction")]
+    InsufficientComputeBudget,
+
+    #[msg("Blocklist is full")]
+    BlocklistFull,
+
+    #[msg("Address is not on the blocklist")]
+    BlocklistEntryNotFound,
+
+    #[msg("Collection config is missing or does not match the NFT")]
+    InvalidCollectionConfig,
+
+    #[msg("Opt-out registry is full")]
+    OptOutRegistryFull,
+
+    #[msg("Invalid royalty configuration")]
+    InvalidRoyalty,
+
+    #[msg("Program is in sunset mode; only withdrawals and settlements are allowed")]
+    ProgramSunset,
+
+    #[msg("CPI allowlist is full")]
+    CpiAllowlistFull,
+
+    #[msg("Circuit breaker needs a non-zero window when enabled")]
+    InvalidCircuitBreaker,
+
+    #[msg("Credential requirement must name an issuer program and a credential")]
+    InvalidCredentialRequirement,
+
+    #[msg("Token gate must name a mint and a non-zero minimum")]
+    InvalidTokenGate,
+
+    #[msg("Fee mint account does not match the configured fee")]
+    InvalidFeeConfig,
+
+    #[msg("Relayer requirement needs a non-zero minimum bond")]
+    InvalidRelayerRequirement,
+
+    #[msg("Relayer bonding is not enabled")]
+    RelayerModeDisabled,
+
+    #[msg("Account is not one of the program's sweepable PDAs")]
+    InvalidSweepTarget,
+
+    #[msg("Account holds no lamports above its rent-exempt minimum")]
+    NoSurplusLamports,
+
+    #[msg("Validation limits must be non-zero and within what accounts can store")]
+    InvalidValidationLimits,
+
+    #[msg("Export page must hold between 1 and 12 accounts owned by this program")]
+    InvalidExportPage,
+
+    #[msg("Reward emission, epoch length or multiplier is out of range")]
+    InvalidRewardConfig,
+
+    #[msg("Claim code hash must be set and redemption details at most 200 bytes")]
+    InvalidRedemption,
+
+    #[msg("Ticket validators must be distinct, non-default and at most 8")]
+    InvalidTicketConfig,
+
+    #[msg("Oracle feed needs an updater and a staleness bound between 1 second and 1 day")]
+    InvalidOracleConfig,
+
+    #[msg("Mint fee is capped at 0.1 SOL and proof-of-work difficulty at 28 bits")]
+    InvalidMintGuard,
+
+    #[msg("Per-phase mint limit cannot exceed the overall per-wallet limit")]
+    InvalidMintLimits,
+
+    #[msg("Creator set needs at most 5 distinct creators and a threshold between 1 and their number")]
+    InvalidCreatorSet,
+
+    #[msg("Pass the collection's config or its foreign collection registration")]
+    CollectionNotRegistered,
+
+    #[msg("The operator must be a different key from the program authority")]
+    InvalidOperator,
+
+    // 2xx: minting and NFT metadata
+    #[msg("Invalid mint account")]
+    InvalidMint = 200,
+
+    #[msg("Invalid metadata URI")]
+    InvalidMetadataUri,
+
+    #[msg("Name or symbol contains disallowed characters or is too long")]
+    InvalidDisplayText,
+
+    #[msg("Invalid NFT metadata account")]
+    InvalidNftMetadata,
 
     #[msg("Insufficient token balance")]
     InsufficientTokens,
 
-    #[msg("Invalid gateway address")]
-    InvalidGateway,
+    #[msg("Metaplex metadata account is invalid")]
+    InvalidExternalMetadata,
 
-    #[msg("Invalid TSS authority")]
-    InvalidTssAuthority,
+    #[msg("NFT is not a wrapped Metaplex NFT")]
+    NotWrapped,
+
+    #[msg("NFT did not originate on another chain")]
+    NotForeignNft,
+
+    #[msg("ipfs:// URI does not hold a valid CID")]
+    InvalidIpfsCid,
+
+    #[msg("Write does not fit in the NFT's app data")]
+    AppDataOutOfBounds,
+
+    #[msg("Parent already has the most children it can hold")]
+    TooManyChildren,
+
+    #[msg("NFT is not a child of this parent")]
+    ChildNotAttached,
+
+    #[msg("An NFT can't be equipped on itself")]
+    InvalidChild,
+
+    #[msg("Snapshot chunk must hold between 1 and 32 distinct NFTs")]
+    InvalidSnapshotSize,
+
+    #[msg("NFT is not in the snapshot's collection")]
+    NotInCollection,
+
+    #[msg("NFT mint's supply is not fixed at one")]
+    InflatableMint,
+
+    #[msg("NFT mint can be frozen by an account other than its master edition")]
+    UnexpectedFreezeAuthority,
+
+    #[msg("Localized URIs need distinct locales of up to 16 letters, digits or '-', at most 4 of them")]
+    InvalidLocalizedUris,
+
+    #[msg("Metadata variants need 1 to 4 entries with strictly increasing minimum values")]
+    InvalidMetadataVariants,
+
+    #[msg("Mint address does not meet the proof-of-work difficulty")]
+    InsufficientProofOfWork,
+
+    #[msg("Wallet has reached its mint limit")]
+    MintLimitReached,
+
+    // 3xx: outbound transfers and swaps
+    #[msg("Cross-chain transfers not enabled for this NFT")]
+    CrossChainNotEnabled = 300,
+
+    #[msg("NFT is locked for cross-chain transfer")]
+    NftLocked,
 
     #[msg("Invalid nonce - must be greater than current nonce")]
     InvalidNonce,
@@ -29,24 +192,272 @@ pub enum UniversalNftError {
     #[msg("Invalid recipient address format")]
     InvalidRecipientAddress,
 
-    #[msg("Unsupported destination chain")]
-    UnsupportedChain,
+    #[msg("Bundle must contain between 1 and 10 NFTs")]
+    InvalidBundleSize,
 
-    #[msg("Invalid mint account")]
-    InvalidMint,
+    #[msg("Transfer is not pending")]
+    TransferNotPending,
+
+    #[msg("Value escrow accounts are required for this transfer")]
+    MissingValueEscrow,
+
+    #[msg("Swap order is not open")]
+    SwapNotOpen,
+
+    #[msg("Swap deadline has passed")]
+    SwapExpired,
+
+    #[msg("Swap deadline has not passed yet")]
+    SwapNotExpired,
+
+    #[msg("Invalid swap terms")]
+    InvalidSwapTerms,
+
+    #[msg("NFT does not belong to the ledger's collection")]
+    CollectionMismatch,
+
+    #[msg("Cross-chain transfers are disabled for this collection")]
+    CollectionBridgingDisabled,
+
+    #[msg("The creator or collection has opted out of cross-chain wrapping")]
+    WrappingOptedOut,
 
+    #[msg("The destination chain's bridge adapter cannot send transfers")]
+    AdapterCannotSend,
+
+    #[msg("Value escrow holds less than the pending transfer's value")]
+    EscrowUnderfunded,
+
+    #[msg("Collection has reached its daily bridging limit")]
+    CollectionVelocityExceeded,
+
+    #[msg("Fee accounts for the destination chain's fee are missing")]
+    MissingFeeAccount,
+
+    #[msg("Fee account does not match the fee mint or the fee treasury")]
+    InvalidFeeAccount,
+
+    #[msg("Fee treasury holds less than the requested amount")]
+    InsufficientFeeBalance,
+
+    #[msg("Transfer has no fee left to refund")]
+    NoFeeRefund,
+
+    #[msg("Transfer permit deadline has passed")]
+    PermitExpired,
+
+    #[msg("Unequip the NFT's children before it leaves")]
+    HasAttachedChildren,
+
+    #[msg("Outbound payload is larger than the destination chain accepts")]
+    PayloadTooLarge,
+
+    #[msg("Invalid auction terms")]
+    InvalidAuctionTerms,
+
+    #[msg("The auction has not started")]
+    AuctionNotStarted,
+
+    #[msg("The auction's current price is above the buyer's maximum")]
+    AuctionPriceTooHigh,
+
+    #[msg("Invalid offer terms")]
+    InvalidOfferTerms,
+
+    #[msg("The offer has expired")]
+    OfferExpired,
+
+    #[msg("Only the buyer can cancel an offer before it expires")]
+    OfferNotExpired,
+
+    #[msg("Only NFTs the program can freeze can be listed without escrow")]
+    MintNotFreezable,
+
+    #[msg("Listing price must be greater than zero")]
+    InvalidListingPrice,
+
+    #[msg("The listing's price is above the buyer's maximum")]
+    ListingPriceTooHigh,
+
+    #[msg("Fraction supply must be greater than zero")]
+    InvalidFractionSupply,
+
+    #[msg("Redeeming a fractionalized NFT takes every one of its fractions")]
+    IncompleteFractions,
+
+    #[msg("NFT is already staked by its holder")]
+    NftAlreadyStaked,
+
+    #[msg("No full reward epoch has passed since the last claim")]
+    NoRewardsAccrued,
+
+    #[msg("Staked NFT was bridged out; its stake resumes when it comes back")]
+    StakeBridged,
+
+    #[msg("Unwrap a wrapped NFT before redeeming it")]
+    WrappedNotRedeemable,
+
+    #[msg("Redeeming a foreign NFT needs its collection ledger")]
+    MissingCollectionLedger,
+
+    #[msg("Ticket has already been checked in")]
+    TicketAlreadyUsed,
+
+    #[msg("Check-in must be co-signed by one of the collection's validators")]
+    ValidatorRequired,
+
+    #[msg("Stake record account is not the NFT's stake PDA")]
+    InvalidStakeRecord,
+
+    #[msg("Refundable fees are already reserved in the maximum number of fee mints")]
+    FeeReserveFull,
+
+    // 4xx: inbound receives
+    #[msg("Cross-chain message has already been processed")]
+    AlreadyProcessed = 400,
+
+    #[msg("Invalid origin contract address")]
+    InvalidOriginContract,
+
+    #[msg("Account is not a legacy receipt")]
+    InvalidReceiptLayout,
+
+    #[msg("Recipient account does not match the signed message")]
+    RecipientMismatch,
+
+    #[msg("Inbound NFT does not match its registered foreign collection")]
+    ForeignCollectionMismatch,
+
+    #[msg("Wormhole VAA is missing, not posted by the core bridge, or does not carry this message")]
+    InvalidVaa,
+
+    #[msg("VAA emitter is not the one registered for the origin chain")]
+    EmitterMismatch,
+
+    #[msg("LayerZero delivery parameters could not be decoded")]
+    InvalidLzDelivery,
+
+    #[msg("LayerZero sender is not the peer registered for the origin chain")]
+    PeerMismatch,
+
+    #[msg("The origin chain is not served by this bridge adapter")]
+    AdapterMismatch,
+
+    #[msg("The origin chain is not in the chain registry")]
+    OriginChainNotRegistered,
+
+    #[msg("Inbound messages cannot originate on this chain")]
+    OriginIsLocalChain,
+
+    #[msg("Inbound nonce is not higher than the last one accepted from the origin chain")]
+    InvalidInboundNonce,
+
+    #[msg("Only Solana-native NFTs can return through a staged unlock")]
+    NotNativeNft,
+
+    #[msg("Returning NFT is not locked for an outbound transfer")]
+    NativeNftNotLocked,
+
+    #[msg("The origin chain's finality delay has not elapsed")]
+    FinalityDelayNotElapsed,
+
+    #[msg("Optimistic receives are not enabled for the origin chain")]
+    OptimisticModeDisabled,
+
+    #[msg("Optimistic receive is not pending")]
+    OptimisticReceiveNotPending,
+
+    #[msg("The challenge window has not closed")]
+    ChallengeWindowOpen,
+
+    #[msg("Watcher bond is below the minimum")]
+    WatcherBondTooLow,
+
+    #[msg("The challenge window has closed")]
+    ChallengeWindowClosed,
+
+    #[msg("Challenge evidence is empty or too long")]
+    InvalidChallengeEvidence,
+
+    #[msg("Optimistic receive is not under challenge")]
+    OptimisticReceiveNotChallenged,
+
+    #[msg("The submitter's watcher account is required to slash its bond")]
+    SubmitterWatcherRequired,
+
+    #[msg("Recipient's rent deposit cannot cover the accounts this receive creates")]
+    RentDepositInsufficient,
+
+    #[msg("Trusted peer has used up its achievement mint quota")]
+    AchievementQuotaExceeded,
+
+    #[msg("Account is not the receipt PDA for these origin identifiers")]
+    InvalidReceiptAddress,
+
+    #[msg("Recipient must be a wallet or a program-derived address, not a token, mint or program account")]
+    InvalidRecipientAccount,
+
+    #[msg("Recipient token account has a delegate or close authority set")]
+    RecipientTokenAccountEncumbered,
+
+    #[msg("Inbound NFT already exists on Solana and is not locked from a transfer out")]
+    InboundNftNotReturnable,
+
+    // 5xx: security checks
     #[msg("TSS signature verification failed")]
-    InvalidTssSignature,
+    InvalidTssSignature = 500,
+
+    #[msg("Unauthorized access")]
+    Unauthorized,
+
+    #[msg("Address is blocklisted")]
+    AddressBlocked,
+
+    #[msg("Reserve attestation is too old or in the future")]
+    StaleAttestation,
 
     #[msg("Arithmetic overflow")]
     ArithmeticOverflow,
 
-    #[msg("Unauthorized access")]
-    Unauthorized,
+    #[msg("Calling program is not on the CPI allowlist")]
+    CallerNotAllowed,
 
-    #[msg("Invalid metadata URI")]
-    InvalidMetadataUri,
+    #[msg("A credential account is required to bridge out")]
+    MissingCredential,
 
-    #[msg("Compute budget exceeded")]
-    ComputeBudgetExceeded,
+    #[msg("Credential account does not match the requirement or the owner")]
+    InvalidCredential,
+
+    #[msg("Owner does not hold enough of the gate token")]
+    TokenGateNotMet,
+
+    #[msg("Signer is not a relayer with the required bond")]
+    RelayerNotBonded,
+
+    #[msg("Relayer bond is still in its withdrawal cooldown")]
+    RelayerCooldownActive,
+
+    #[msg("Relayer bond accounts are missing or do not match the bond mint")]
+    InvalidRelayerBond,
+
+    #[msg("Transaction lacks the owner's ed25519 signature over the permit")]
+    InvalidOwnerSignature,
+
+    #[msg("Signer is not the authority of the app the holder approved")]
+    AppNotApproved,
+
+    #[msg("TSS signature verification is not available in this build")]
+    TssVerifierUnavailable,
+
+    #[msg("Oracle feed has no value recent enough to select metadata")]
+    StaleOracleValue,
+
+    #[msg("Collection action needs approval from the creator set's threshold of creators")]
+    CreatorApprovalRequired,
+
+    #[msg("Symbol is reserved by another collection's creator")]
+    SymbolReserved,
+
+    #[msg("Symbol registry is enabled; pass the symbol's reservation")]
+    SymbolReservationRequired,
 }
@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+use crate::state::{ProgramState, CrossChainConfig, CrossChainTransfer, FeeReserve, Relayer, PendingTransfers};
+use crate::error::UniversalNftError;
+use crate::constants::{CROSS_CHAIN_CONFIG_SEED, CROSS_CHAIN_TRANSFER_SEED, FEE_RESERVE_SEED, PENDING_TRANSFERS_SEED, PROGRAM_STATE_SEED, RELAYER_SEED};
+use crate::utils::relayers::require_bonded_relayer;
+use crate::utils::security::{verify_tss_signature, signed_message};
+use universal_nft_messages::DeliveryAck;
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey, nonce: u64)]
+pub struct AcknowledgeDelivery<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
+        bump = cross_chain_config.bump
+    )]
+    pub cross_chain_config: Account<'info, CrossChainConfig>,
+
+    #[account(
+        mut,
+        seeds = [CROSS_CHAIN_TRANSFER_SEED, mint.as_ref(), nonce.to_le_bytes().as_ref()],
+        bump = transfer_record.bump,
+        constraint = transfer_record.status == 0 @ UniversalNftError::TransferNotPending
+    )]
+    pub transfer_record: Account<'info, CrossChainTransfer>,
+
+    /// Required while a relayer requirement is set
+    #[account(
+        mut,
+        seeds = [RELAYER_SEED, relayer.key().as_ref()],
+        bump = relayer_registration.bump
+    )]
+    pub relayer_registration: Option<Account<'info, Relayer>>,
+
+    /// CHECK: `PendingTransfers` registry, updated if it exists
+    #[account(mut, seeds = [PENDING_TRANSFERS_SEED], bump)]
+    pub pending_transfers: UncheckedAccount<'info>,
+
+    /// CHECK: `FeeReserve`, updated if it exists
+    #[account(mut, seeds = [FEE_RESERVE_SEED], bump)]
+    pub fee_reserve: UncheckedAccount<'info>,
+
+    pub relayer: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<AcknowledgeDelivery>,
+    mint: Pubkey,
+    nonce: u64,
+    destination_tx_hash: [u8; 32],
+    gas_spent: u64,
+    tss_signature: Vec<u8>,
+) -> Result<()> {
+    require!(
+        !tss_signature.is_empty()
+            && ctx.accounts.program_state.validation_limits.fits_signature(&tss_signature),
+        UniversalNftError::InvalidTssSignature
+    );
+    require_bonded_relayer(&ctx.accounts.cross_chain_config, ctx.accounts.relayer_registration.as_mut())?;
+
+    let transfer_record = &mut ctx.accounts.transfer_record;
+
+    // The TSS attests that this transfer landed in the given destination transaction
+    let message = signed_message(
+        ctx.accounts.cross_chain_config.chain_id,
+        &DeliveryAck {
+            mint: mint.to_bytes(),
+            nonce,
+            destination_chain_id: transfer_record.destination_chain_id,
+            recipient_address: &transfer_record.recipient_address,
+            destination_tx_hash,
+            gas_spent,
+        },
+    );
+
+    let is_valid = verify_tss_signature(
+        &message,
+        &tss_signature,
+        &ctx.accounts.cross_chain_config.tss_address,
+    )?;
+    require!(is_valid, UniversalNftError::InvalidTssSignature);
+
+    let now = Clock::get()?.unix_timestamp;
+    transfer_record.status = 1; // Completed
+    transfer_record.destination_tx_hash = destination_tx_hash;
+    transfer_record.completed_at = now;
+    // Whatever the budget didn't cover becomes claimable through `claim_fee_refund`
+    transfer_record.gas_spent = gas_spent.min(transfer_record.gas_budget);
+    // The rest of the fee is earned, so withdrawals may take it
+    FeeReserve::release_if_exists(
+        &ctx.accounts.fee_reserve,
+        transfer_record.fee_mint,
+        transfer_record.fee_paid.saturating_sub(transfer_record.refundable_fee()),
+    )?;
+    PendingTransfers::record_settled_if_exists(&ctx.accounts.pending_transfers, mint, nonce, 1, now)?;
+
+    emit!(DeliveryAcknowledgedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint,
+        owner: transfer_record.original_owner,
+        nonce,
+        destination_chain_id: transfer_record.destination_chain_id,
+        recipient_address: transfer_record.recipient_address.clone(),
+        destination_tx_hash,
+        timestamp: now,
+        gas_spent: transfer_record.gas_spent,
+    });
+
+    msg!("Delivery acknowledged for mint: {}, nonce: {}", mint, nonce);
+
+    Ok(())
+}
+
+#[event]
+pub struct DeliveryAcknowledgedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub nonce: u64,
+    pub destination_chain_id: u64,
+    pub recipient_address: Vec<u8>,
+    pub destination_tx_hash: [u8; 32],
+    pub timestamp: i64,
+    pub gas_spent: u64,
+}
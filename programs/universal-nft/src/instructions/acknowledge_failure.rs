@@ -0,0 +1,184 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::{ProgramState, CrossChainConfig, NftMetadata, CrossChainTransfer, CollectionLedger, Relayer, PendingTransfers};
+use crate::error::UniversalNftError;
+use crate::constants::{COLLECTION_LEDGER_SEED, CROSS_CHAIN_CONFIG_SEED, CROSS_CHAIN_TRANSFER_SEED, NFT_METADATA_SEED, PENDING_TRANSFERS_SEED, PROGRAM_STATE_SEED, RELAYER_SEED, STAKE_RECORD_SEED, VALUE_ESCROW_SEED};
+use crate::utils::relayers::require_bonded_relayer;
+use crate::utils::security::{verify_tss_signature, signed_message};
+use crate::utils::staking::resume_stake;
+use universal_nft_messages::FailureAck;
+use super::revert_cross_chain_transfer::return_escrowed_value;
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey, nonce: u64)]
+pub struct AcknowledgeFailure<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    // Unlocking moves assets, so a pause also stops attested failures
+    #[account(
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
+        bump = cross_chain_config.bump,
+        constraint = !cross_chain_config.is_paused @ UniversalNftError::CrossChainPaused
+    )]
+    pub cross_chain_config: Account<'info, CrossChainConfig>,
+
+    #[account(
+        mut,
+        seeds = [CROSS_CHAIN_TRANSFER_SEED, mint.as_ref(), nonce.to_le_bytes().as_ref()],
+        bump = transfer_record.bump,
+        constraint = transfer_record.status == 0 @ UniversalNftError::TransferNotPending
+    )]
+    pub transfer_record: Account<'info, CrossChainTransfer>,
+
+    #[account(
+        mut,
+        seeds = [NFT_METADATA_SEED, mint.as_ref()],
+        bump = nft_metadata.bump
+    )]
+    pub nft_metadata: Account<'info, NftMetadata>,
+
+    #[account(
+        mut,
+        seeds = [
+            COLLECTION_LEDGER_SEED,
+            nft_metadata.origin_chain_id.to_le_bytes().as_ref(),
+            nft_metadata.origin_contract.as_slice()
+        ],
+        bump = collection_ledger.bump
+    )]
+    pub collection_ledger: Account<'info, CollectionLedger>,
+
+    /// Required when the transfer carried fungible value
+    #[account(
+        mut,
+        seeds = [VALUE_ESCROW_SEED, transfer_record.key().as_ref()],
+        bump
+    )]
+    pub value_escrow: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = owner_value_account.owner == transfer_record.original_owner @ UniversalNftError::Unauthorized,
+        constraint = owner_value_account.mint == transfer_record.value_mint @ UniversalNftError::InvalidMint
+    )]
+    pub owner_value_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Receives the escrow account rent; must be the original owner
+    #[account(mut, address = transfer_record.original_owner @ UniversalNftError::Unauthorized)]
+    pub original_owner: UncheckedAccount<'info>,
+
+    /// Required while a relayer requirement is set
+    #[account(
+        mut,
+        seeds = [RELAYER_SEED, relayer.key().as_ref()],
+        bump = relayer_registration.bump
+    )]
+    pub relayer_registration: Option<Account<'info, Relayer>>,
+
+    /// CHECK: The NFT's stake PDA, resumed by the handler if the transfer
+    /// checkpointed it
+    #[account(mut, seeds = [STAKE_RECORD_SEED, mint.as_ref()], bump)]
+    pub stake_record: UncheckedAccount<'info>,
+
+    /// CHECK: `PendingTransfers` registry, updated if it exists
+    #[account(mut, seeds = [PENDING_TRANSFERS_SEED], bump)]
+    pub pending_transfers: UncheckedAccount<'info>,
+
+    pub relayer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(
+    ctx: Context<AcknowledgeFailure>,
+    mint: Pubkey,
+    nonce: u64,
+    tss_signature: Vec<u8>,
+) -> Result<()> {
+    require!(
+        !tss_signature.is_empty()
+            && ctx.accounts.program_state.validation_limits.fits_signature(&tss_signature),
+        UniversalNftError::InvalidTssSignature
+    );
+    require_bonded_relayer(&ctx.accounts.cross_chain_config, ctx.accounts.relayer_registration.as_mut())?;
+
+    let transfer_record = &ctx.accounts.transfer_record;
+
+    // The TSS attests that this transfer will never be delivered
+    let message = signed_message(
+        ctx.accounts.cross_chain_config.chain_id,
+        &FailureAck {
+            mint: mint.to_bytes(),
+            nonce,
+            destination_chain_id: transfer_record.destination_chain_id,
+            recipient_address: &transfer_record.recipient_address,
+        },
+    );
+
+    let is_valid = verify_tss_signature(
+        &message,
+        &tss_signature,
+        &ctx.accounts.cross_chain_config.tss_address,
+    )?;
+    require!(is_valid, UniversalNftError::InvalidTssSignature);
+
+    return_escrowed_value(
+        transfer_record,
+        ctx.accounts.value_escrow.as_ref(),
+        ctx.accounts.owner_value_account.as_ref(),
+        &ctx.accounts.original_owner,
+        &ctx.accounts.token_program,
+        ctx.bumps.value_escrow,
+    )?;
+
+    // Unlock the NFT and mark the transfer as failed
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.nft_metadata.is_locked = false;
+    ctx.accounts.collection_ledger.record_outbound_reverted()?;
+    resume_stake(
+        &ctx.accounts.stake_record,
+        &mut ctx.accounts.program_state,
+        mint,
+        ctx.accounts.transfer_record.original_owner,
+        ctx.accounts.transfer_record.staked_seconds,
+        now,
+    )?;
+    let transfer_record = &mut ctx.accounts.transfer_record;
+    transfer_record.status = 2; // Failed
+    transfer_record.completed_at = now;
+    PendingTransfers::record_settled_if_exists(&ctx.accounts.pending_transfers, mint, nonce, 2, now)?;
+
+    emit!(FailureAcknowledgedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint,
+        owner: transfer_record.original_owner,
+        nonce,
+        destination_chain_id: transfer_record.destination_chain_id,
+        value_mint: transfer_record.value_mint,
+        value_amount: transfer_record.value_amount,
+        timestamp: now,
+    });
+
+    msg!("Failure acknowledged for mint: {}, nonce: {}", mint, nonce);
+
+    Ok(())
+}
+
+#[event]
+pub struct FailureAcknowledgedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub nonce: u64,
+    pub destination_chain_id: u64,
+    pub value_mint: Pubkey,
+    pub value_amount: u64,
+    pub timestamp: i64,
+}
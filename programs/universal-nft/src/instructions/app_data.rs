@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::{ProgramState, NftMetadata, NftAppData};
+use crate::error::UniversalNftError;
+use crate::constants::{APP_DATA_SEED, NFT_METADATA_SEED, PROGRAM_STATE_SEED};
+
+#[derive(Accounts)]
+pub struct SetAppDataProgram<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump
+    )]
+    pub nft_metadata: Account<'info, NftMetadata>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + NftAppData::INIT_SPACE,
+        seeds = [APP_DATA_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub app_data: Account<'info, NftAppData>,
+
+    /// CHECK: Mint account validated by token account constraint
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = token_account.mint == mint.key(),
+        constraint = token_account.owner == owner.key(),
+        constraint = token_account.amount >= 1 @ UniversalNftError::InsufficientTokens
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    /// Holder of the NFT; pays for the app data account on first use
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WriteAppData<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Writes wait while the NFT is locked for a transfer
+    #[account(
+        seeds = [NFT_METADATA_SEED, app_data.mint.as_ref()],
+        bump = nft_metadata.bump,
+        constraint = !nft_metadata.is_locked @ UniversalNftError::NftLocked
+    )]
+    pub nft_metadata: Account<'info, NftMetadata>,
+
+    #[account(
+        mut,
+        seeds = [APP_DATA_SEED, app_data.mint.as_ref()],
+        bump = app_data.bump,
+        constraint = app_data.app_authority == app_authority.key() @ UniversalNftError::AppNotApproved
+    )]
+    pub app_data: Account<'info, NftAppData>,
+
+    /// The approved app program's PDA, signing through CPI
+    pub app_authority: Signer<'info>,
+}
+
+/// Approve `app_program` to write the NFT's app data, or revoke with `None`.
+/// The data itself is kept, so a newly approved app sees what the last one wrote.
+pub fn set_program_handler(ctx: Context<SetAppDataProgram>, app_program: Option<Pubkey>) -> Result<()> {
+    let mint = ctx.accounts.mint.key();
+    let app_data = &mut ctx.accounts.app_data;
+    app_data.mint = mint;
+    app_data.bump = ctx.bumps.app_data;
+    app_data.app_program = app_program.unwrap_or_default();
+    app_data.app_authority = app_program
+        .map(|program| NftAppData::app_authority(&program, &mint))
+        .unwrap_or_default();
+
+    emit!(AppDataProgramSetEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint,
+        owner: ctx.accounts.owner.key(),
+        app_program,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("App data program for {}: {:?}", mint, app_program);
+
+    Ok(())
+}
+
+/// Overwrite `bytes.len()` bytes of app data starting at `offset`
+pub fn write_handler(ctx: Context<WriteAppData>, offset: u16, bytes: Vec<u8>) -> Result<()> {
+    let start = offset as usize;
+    let end = start
+        .checked_add(bytes.len())
+        .filter(|&end| end <= NftAppData::DATA_LEN)
+        .ok_or(UniversalNftError::AppDataOutOfBounds)?;
+
+    let app_data = &mut ctx.accounts.app_data;
+    app_data.data[start..end].copy_from_slice(&bytes);
+    app_data.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(AppDataWrittenEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint: app_data.mint,
+        app_program: app_data.app_program,
+        offset,
+        len: bytes.len() as u16,
+        timestamp: app_data.updated_at,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct AppDataProgramSetEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub app_program: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AppDataWrittenEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub app_program: Pubkey,
+    pub offset: u16,
+    pub len: u16,
+    pub timestamp: i64,
+}
@@ -0,0 +1,515 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::{ProgramState, CrossChainConfig, NftMetadata, BundleTransfer, CollectionLedger, FeeReserve, Relayer, PendingTransfers, AuditLog, AuditEntry, AuditAction, CpiAllowlist};
+use crate::error::UniversalNftError;
+use crate::constants::{AUDIT_ENTRY_SEED, AUDIT_LOG_SEED, BUNDLE_TRANSFER_SEED, COLLECTION_LEDGER_SEED, CPI_ALLOWLIST_SEED, CROSS_CHAIN_CONFIG_SEED, FEE_RESERVE_SEED, FEE_TREASURY_SEED, NFT_METADATA_SEED, PENDING_TRANSFERS_SEED, PROGRAM_STATE_SEED, RELAYER_SEED, STAKE_RECORD_SEED};
+use crate::utils::fees::{refund_bridge_fee, RefundAccounts};
+use crate::utils::relayers::require_bonded_relayer;
+use crate::utils::security::{verify_tss_signature, signed_message, require_allowed_caller};
+use crate::utils::staking::resume_stake;
+use universal_nft_messages::{BundleDeliveryAck, BundleFailureAck};
+
+#[derive(Accounts)]
+#[instruction(owner: Pubkey, nonce: u64)]
+pub struct AcknowledgeBundleDelivery<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
+        bump = cross_chain_config.bump
+    )]
+    pub cross_chain_config: Account<'info, CrossChainConfig>,
+
+    #[account(
+        mut,
+        seeds = [BUNDLE_TRANSFER_SEED, owner.as_ref(), nonce.to_le_bytes().as_ref()],
+        bump = bundle_record.bump,
+        constraint = bundle_record.status == 0 @ UniversalNftError::TransferNotPending
+    )]
+    pub bundle_record: Account<'info, BundleTransfer>,
+
+    /// Required while a relayer requirement is set
+    #[account(
+        mut,
+        seeds = [RELAYER_SEED, relayer.key().as_ref()],
+        bump = relayer_registration.bump
+    )]
+    pub relayer_registration: Option<Account<'info, Relayer>>,
+
+    /// CHECK: `PendingTransfers` registry, updated if it exists
+    #[account(mut, seeds = [PENDING_TRANSFERS_SEED], bump)]
+    pub pending_transfers: UncheckedAccount<'info>,
+
+    /// CHECK: `FeeReserve`, updated if it exists
+    #[account(mut, seeds = [FEE_RESERVE_SEED], bump)]
+    pub fee_reserve: UncheckedAccount<'info>,
+
+    pub relayer: Signer<'info>,
+}
+
+/// Remaining accounts are `(nft_metadata, stake_record)` pairs, both
+/// writable, one per NFT in the order of `bundle_record.mints`
+#[derive(Accounts)]
+#[instruction(owner: Pubkey, nonce: u64)]
+pub struct AcknowledgeBundleFailure<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    // Unlocking moves assets, so a pause also stops attested failures
+    #[account(
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
+        bump = cross_chain_config.bump,
+        constraint = !cross_chain_config.is_paused @ UniversalNftError::CrossChainPaused
+    )]
+    pub cross_chain_config: Account<'info, CrossChainConfig>,
+
+    #[account(
+        mut,
+        seeds = [BUNDLE_TRANSFER_SEED, owner.as_ref(), nonce.to_le_bytes().as_ref()],
+        bump = bundle_record.bump,
+        constraint = bundle_record.status == 0 @ UniversalNftError::TransferNotPending
+    )]
+    pub bundle_record: Account<'info, BundleTransfer>,
+
+    /// The ledger every NFT in the bundle was counted out of
+    #[account(
+        mut,
+        seeds = [
+            COLLECTION_LEDGER_SEED,
+            collection_ledger.origin_chain_id.to_le_bytes().as_ref(),
+            collection_ledger.origin_contract.as_slice()
+        ],
+        bump = collection_ledger.bump
+    )]
+    pub collection_ledger: Account<'info, CollectionLedger>,
+
+    /// Required while a relayer requirement is set
+    #[account(
+        mut,
+        seeds = [RELAYER_SEED, relayer.key().as_ref()],
+        bump = relayer_registration.bump
+    )]
+    pub relayer_registration: Option<Account<'info, Relayer>>,
+
+    /// CHECK: `PendingTransfers` registry, updated if it exists
+    #[account(mut, seeds = [PENDING_TRANSFERS_SEED], bump)]
+    pub pending_transfers: UncheckedAccount<'info>,
+
+    pub relayer: Signer<'info>,
+}
+
+/// Remaining accounts as for `AcknowledgeBundleFailure`
+#[derive(Accounts)]
+#[instruction(owner: Pubkey, nonce: u64)]
+pub struct RevertBundleTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = program_state.authority == authority.key() @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [BUNDLE_TRANSFER_SEED, owner.as_ref(), nonce.to_le_bytes().as_ref()],
+        bump = bundle_record.bump,
+        constraint = bundle_record.status == 0 @ UniversalNftError::TransferNotPending
+    )]
+    pub bundle_record: Account<'info, BundleTransfer>,
+
+    /// The ledger every NFT in the bundle was counted out of
+    #[account(
+        mut,
+        seeds = [
+            COLLECTION_LEDGER_SEED,
+            collection_ledger.origin_chain_id.to_le_bytes().as_ref(),
+            collection_ledger.origin_contract.as_slice()
+        ],
+        bump = collection_ledger.bump
+    )]
+    pub collection_ledger: Account<'info, CollectionLedger>,
+
+    /// CHECK: `PendingTransfers` registry, updated if it exists
+    #[account(mut, seeds = [PENDING_TRANSFERS_SEED], bump)]
+    pub pending_transfers: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(owner: Pubkey, nonce: u64)]
+pub struct ClaimBundleFeeRefund<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [BUNDLE_TRANSFER_SEED, owner.as_ref(), nonce.to_le_bytes().as_ref()],
+        bump = bundle_record.bump
+    )]
+    pub bundle_record: Account<'info, BundleTransfer>,
+
+    /// CHECK: Fee treasury PDA; holds no data
+    #[account(
+        mut,
+        seeds = [FEE_TREASURY_SEED],
+        bump
+    )]
+    pub fee_treasury: UncheckedAccount<'info>,
+
+    /// CHECK: `FeeReserve`, updated if it exists
+    #[account(mut, seeds = [FEE_RESERVE_SEED], bump)]
+    pub fee_reserve: UncheckedAccount<'info>,
+
+    /// CHECK: Receives lamport refunds; must be the account that paid the fee
+    #[account(mut, address = bundle_record.fee_payer @ UniversalNftError::Unauthorized)]
+    pub fee_payer: UncheckedAccount<'info>,
+
+    /// Required for SPL fees
+    #[account(mut)]
+    pub treasury_fee_account: Option<Account<'info, TokenAccount>>,
+
+    /// Fee payer's account of the fee mint; required for SPL fees
+    #[account(
+        mut,
+        constraint = payer_fee_account.owner == bundle_record.fee_payer @ UniversalNftError::Unauthorized
+    )]
+    pub payer_fee_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Settle a bundle the TSS attests landed in `destination_tx_hash`
+pub fn acknowledge_delivery_handler(
+    ctx: Context<AcknowledgeBundleDelivery>,
+    owner: Pubkey,
+    nonce: u64,
+    destination_tx_hash: [u8; 32],
+    gas_spent: u64,
+    tss_signature: Vec<u8>,
+) -> Result<()> {
+    require!(
+        !tss_signature.is_empty()
+            && ctx.accounts.program_state.validation_limits.fits_signature(&tss_signature),
+        UniversalNftError::InvalidTssSignature
+    );
+    require_bonded_relayer(&ctx.accounts.cross_chain_config, ctx.accounts.relayer_registration.as_mut())?;
+
+    let bundle_record = &mut ctx.accounts.bundle_record;
+    let message = signed_message(
+        ctx.accounts.cross_chain_config.chain_id,
+        &BundleDeliveryAck {
+            owner: owner.to_bytes(),
+            nonce,
+            destination_chain_id: bundle_record.destination_chain_id,
+            recipient_address: &bundle_record.recipient_address,
+            destination_tx_hash,
+            gas_spent,
+        },
+    );
+    let is_valid = verify_tss_signature(
+        &message,
+        &tss_signature,
+        &ctx.accounts.cross_chain_config.tss_address,
+    )?;
+    require!(is_valid, UniversalNftError::InvalidTssSignature);
+
+    let now = Clock::get()?.unix_timestamp;
+    bundle_record.status = 1; // Completed
+    bundle_record.destination_tx_hash = destination_tx_hash;
+    bundle_record.completed_at = now;
+    // Whatever the budget didn't cover becomes claimable through `claim_bundle_fee_refund`
+    bundle_record.gas_spent = gas_spent.min(bundle_record.gas_budget);
+    FeeReserve::release_if_exists(
+        &ctx.accounts.fee_reserve,
+        bundle_record.fee_mint,
+        bundle_record.fee_paid.saturating_sub(bundle_record.refundable_fee()),
+    )?;
+    PendingTransfers::record_settled_if_exists(&ctx.accounts.pending_transfers, Pubkey::default(), nonce, 1, now)?;
+
+    emit!(BundleDeliveryAcknowledgedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        owner,
+        nonce,
+        mints: bundle_record.mints.clone(),
+        destination_chain_id: bundle_record.destination_chain_id,
+        destination_tx_hash,
+        gas_spent: bundle_record.gas_spent,
+        timestamp: now,
+    });
+
+    msg!("Bundle delivery acknowledged for owner: {}, nonce: {}", owner, nonce);
+
+    Ok(())
+}
+
+/// Unlock every NFT of a bundle the TSS attests will not be delivered
+pub fn acknowledge_failure_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AcknowledgeBundleFailure<'info>>,
+    owner: Pubkey,
+    nonce: u64,
+    tss_signature: Vec<u8>,
+) -> Result<()> {
+    require!(
+        !tss_signature.is_empty()
+            && ctx.accounts.program_state.validation_limits.fits_signature(&tss_signature),
+        UniversalNftError::InvalidTssSignature
+    );
+    require_bonded_relayer(&ctx.accounts.cross_chain_config, ctx.accounts.relayer_registration.as_mut())?;
+
+    let bundle_record = &ctx.accounts.bundle_record;
+    let message = signed_message(
+        ctx.accounts.cross_chain_config.chain_id,
+        &BundleFailureAck {
+            owner: owner.to_bytes(),
+            nonce,
+            destination_chain_id: bundle_record.destination_chain_id,
+            recipient_address: &bundle_record.recipient_address,
+        },
+    );
+    let is_valid = verify_tss_signature(
+        &message,
+        &tss_signature,
+        &ctx.accounts.cross_chain_config.tss_address,
+    )?;
+    require!(is_valid, UniversalNftError::InvalidTssSignature);
+
+    let now = Clock::get()?.unix_timestamp;
+    unlock_bundle(
+        &mut ctx.accounts.bundle_record,
+        ctx.remaining_accounts,
+        &mut ctx.accounts.collection_ledger,
+        &mut ctx.accounts.program_state,
+        now,
+    )?;
+    PendingTransfers::record_settled_if_exists(&ctx.accounts.pending_transfers, Pubkey::default(), nonce, 2, now)?;
+
+    emit!(BundleFailedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        owner,
+        nonce,
+        mints: ctx.accounts.bundle_record.mints.clone(),
+        destination_chain_id: ctx.accounts.bundle_record.destination_chain_id,
+        reverted_by_authority: false,
+        timestamp: now,
+    });
+
+    msg!("Bundle failure acknowledged for owner: {}, nonce: {}", owner, nonce);
+
+    Ok(())
+}
+
+/// Admin counterpart of `acknowledge_failure_handler`, for a bundle the
+/// destination chain will never deliver
+pub fn revert_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RevertBundleTransfer<'info>>,
+    owner: Pubkey,
+    nonce: u64,
+) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    unlock_bundle(
+        &mut ctx.accounts.bundle_record,
+        ctx.remaining_accounts,
+        &mut ctx.accounts.collection_ledger,
+        &mut ctx.accounts.program_state,
+        now,
+    )?;
+    PendingTransfers::record_settled_if_exists(&ctx.accounts.pending_transfers, Pubkey::default(), nonce, 2, now)?;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::RevertBundleTransfer,
+        ctx.accounts.authority.key(),
+        &(owner, nonce).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(BundleFailedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        owner,
+        nonce,
+        mints: ctx.accounts.bundle_record.mints.clone(),
+        destination_chain_id: ctx.accounts.bundle_record.destination_chain_id,
+        reverted_by_authority: true,
+        timestamp: now,
+    });
+
+    msg!("Bundle transfer reverted for owner: {}, nonce: {}", owner, nonce);
+
+    Ok(())
+}
+
+/// Return the fee a finished bundle no longer needs to its payer, as
+/// `claim_fee_refund` does for a single transfer
+pub fn claim_fee_refund_handler(ctx: Context<ClaimBundleFeeRefund>, owner: Pubkey, nonce: u64) -> Result<()> {
+    let amount = ctx.accounts.bundle_record.refundable_fee();
+    require!(amount > 0, UniversalNftError::NoFeeRefund);
+
+    let fee_mint = ctx.accounts.bundle_record.fee_mint;
+    refund_bridge_fee(
+        fee_mint,
+        amount,
+        RefundAccounts {
+            fee_treasury: &ctx.accounts.fee_treasury.to_account_info(),
+            fee_treasury_bump: ctx.bumps.fee_treasury,
+            fee_payer: &ctx.accounts.fee_payer.to_account_info(),
+            treasury_fee_account: ctx.accounts.treasury_fee_account.as_ref(),
+            payer_fee_account: ctx.accounts.payer_fee_account.as_ref(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+        },
+    )?;
+
+    ctx.accounts.bundle_record.fee_refunded = true;
+    FeeReserve::release_if_exists(&ctx.accounts.fee_reserve, fee_mint, amount)?;
+
+    emit!(BundleFeeRefundedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        owner,
+        nonce,
+        fee_payer: ctx.accounts.fee_payer.key(),
+        fee_mint,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Refunded {} fees ({:?}) for bundle of owner: {}, nonce: {}", amount, fee_mint, owner, nonce);
+
+    Ok(())
+}
+
+/// Unlock every NFT of a bundle that won't arrive, give back their stakes
+/// and mark the bundle failed, which makes its whole fee refundable
+fn unlock_bundle<'info>(
+    bundle_record: &mut Account<'info, BundleTransfer>,
+    remaining: &'info [AccountInfo<'info>],
+    collection_ledger: &mut CollectionLedger,
+    program_state: &mut ProgramState,
+    now: i64,
+) -> Result<()> {
+    require!(
+        remaining.len() == bundle_record.mints.len() * BundleTransfer::UNLOCK_ACCOUNTS,
+        UniversalNftError::InvalidBundleSize
+    );
+
+    for (index, accounts) in remaining.chunks_exact(BundleTransfer::UNLOCK_ACCOUNTS).enumerate() {
+        let mint = bundle_record.mints[index];
+        let (expected_metadata, _) =
+            Pubkey::find_program_address(&[NFT_METADATA_SEED, mint.as_ref()], &crate::ID);
+        require_keys_eq!(accounts[0].key(), expected_metadata, UniversalNftError::InvalidNftMetadata);
+        let (expected_stake_record, _) =
+            Pubkey::find_program_address(&[STAKE_RECORD_SEED, mint.as_ref()], &crate::ID);
+        require_keys_eq!(accounts[1].key(), expected_stake_record, UniversalNftError::InvalidStakeRecord);
+
+        let mut nft_metadata = Account::<NftMetadata>::try_from(&accounts[0])?;
+        require!(
+            nft_metadata.origin_chain_id == collection_ledger.origin_chain_id
+                && nft_metadata.origin_contract == collection_ledger.origin_contract,
+            UniversalNftError::CollectionMismatch
+        );
+        nft_metadata.is_locked = false;
+        nft_metadata.exit(&crate::ID)?;
+        collection_ledger.record_outbound_reverted()?;
+        resume_stake(
+            &accounts[1],
+            program_state,
+            mint,
+            bundle_record.original_owner,
+            bundle_record.staked_seconds.get(index).copied().unwrap_or_default(),
+            now,
+        )?;
+    }
+
+    bundle_record.status = 2; // Failed
+    bundle_record.completed_at = now;
+    Ok(())
+}
+
+#[event]
+pub struct BundleDeliveryAcknowledgedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub owner: Pubkey,
+    pub nonce: u64,
+    pub mints: Vec<Pubkey>,
+    pub destination_chain_id: u64,
+    pub destination_tx_hash: [u8; 32],
+    pub gas_spent: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BundleFailedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub owner: Pubkey,
+    pub nonce: u64,
+    pub mints: Vec<Pubkey>,
+    pub destination_chain_id: u64,
+    pub reverted_by_authority: bool, // False when the TSS attested the failure
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BundleFeeRefundedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub owner: Pubkey,
+    pub nonce: u64,
+    pub fee_payer: Pubkey,
+    pub fee_mint: Option<Pubkey>,
+    pub amount: u64,
+    pub timestamp: i64,
+}
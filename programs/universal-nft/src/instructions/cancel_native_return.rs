@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use crate::state::{ProgramState, PendingReturn, AuditLog, AuditEntry, AuditAction, CpiAllowlist};
+use crate::error::UniversalNftError;
+use crate::constants::{AUDIT_ENTRY_SEED, AUDIT_LOG_SEED, CPI_ALLOWLIST_SEED, PENDING_RETURN_SEED, PROGRAM_STATE_SEED};
+use crate::utils::security::require_allowed_caller;
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct CancelNativeReturn<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = program_state.authority == authority.key() @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [PENDING_RETURN_SEED, mint.as_ref()],
+        bump = pending_return.bump
+    )]
+    pub pending_return: Account<'info, PendingReturn>,
+
+    /// CHECK: Receives the staged return's rent; must be the account that staged it
+    #[account(mut, address = pending_return.payer @ UniversalNftError::Unauthorized)]
+    pub payer: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Drop a staged native return before it executes, such as one whose origin
+/// transaction was reorged away. The NFT stays locked, and the attestation's
+/// receipt stays processed so it cannot be staged again.
+pub fn handler(ctx: Context<CancelNativeReturn>, mint: Pubkey) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    let origin_chain_id = ctx.accounts.pending_return.origin_chain_id;
+    let nonce = ctx.accounts.pending_return.nonce;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::CancelNativeReturn,
+        ctx.accounts.authority.key(),
+        &(mint, origin_chain_id, nonce).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(NativeReturnCancelledEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint,
+        origin_chain_id,
+        nonce,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Native return cancelled for mint: {}", mint);
+
+    Ok(())
+}
+
+#[event]
+pub struct NativeReturnCancelledEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub origin_chain_id: u64,
+    pub nonce: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use crate::state::{ProgramState, CrossChainConfig, OptimisticReceive};
+use crate::error::UniversalNftError;
+use crate::constants::{CROSS_CHAIN_CONFIG_SEED, OPTIMISTIC_RECEIVE_SEED, PROGRAM_STATE_SEED};
+use crate::utils::security::{verify_tss_signature, signed_message};
+use universal_nft_messages::FraudProof;
+
+#[derive(Accounts)]
+#[instruction(origin_tx_hash: [u8; 32], nonce: u64)]
+pub struct CancelOptimisticReceive<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
+        bump = cross_chain_config.bump
+    )]
+    pub cross_chain_config: Account<'info, CrossChainConfig>,
+
+    #[account(
+        mut,
+        seeds = [OPTIMISTIC_RECEIVE_SEED, origin_tx_hash.as_ref(), nonce.to_le_bytes().as_ref()],
+        bump = optimistic_receive.bump,
+        constraint = optimistic_receive.status == 0 @ UniversalNftError::OptimisticReceiveNotPending
+    )]
+    pub optimistic_receive: Box<Account<'info, OptimisticReceive>>,
+
+    pub challenger: Signer<'info>,
+}
+
+/// Cancel a pending optimistic receive with a TSS-signed fraud proof naming
+/// its message hash. Anyone holding the proof may submit it.
+pub fn handler(
+    ctx: Context<CancelOptimisticReceive>,
+    origin_tx_hash: [u8; 32],
+    nonce: u64,
+    tss_signature: Vec<u8>,
+) -> Result<()> {
+    require!(
+        !tss_signature.is_empty()
+            && ctx.accounts.program_state.validation_limits.fits_signature(&tss_signature),
+        UniversalNftError::InvalidTssSignature
+    );
+
+    let optimistic_receive = &mut ctx.accounts.optimistic_receive;
+    let message = signed_message(
+        ctx.accounts.cross_chain_config.chain_id,
+        &FraudProof {
+            origin_chain_id: optimistic_receive.origin_chain_id,
+            origin_tx_hash,
+            nonce,
+            message_hash: optimistic_receive.message_hash,
+        },
+    );
+    let is_valid = verify_tss_signature(
+        &message,
+        &tss_signature,
+        &ctx.accounts.cross_chain_config.tss_address,
+    )?;
+    require!(is_valid, UniversalNftError::InvalidTssSignature);
+
+    optimistic_receive.status = 2; // Cancelled
+
+    emit!(OptimisticReceiveCancelledEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        origin_chain_id: optimistic_receive.origin_chain_id,
+        origin_tx_hash,
+        nonce,
+        submitter: optimistic_receive.submitter,
+        challenger: ctx.accounts.challenger.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Optimistic receive cancelled by fraud proof, nonce {}", nonce);
+
+    Ok(())
+}
+
+#[event]
+pub struct OptimisticReceiveCancelledEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub origin_chain_id: u64,
+    pub origin_tx_hash: [u8; 32],
+    pub nonce: u64,
+    pub submitter: Pubkey,
+    pub challenger: Pubkey,
+    pub timestamp: i64,
+}
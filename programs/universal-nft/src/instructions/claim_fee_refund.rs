@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::{ProgramState, CrossChainTransfer, FeeReserve};
+use crate::error::UniversalNftError;
+use crate::constants::{CROSS_CHAIN_TRANSFER_SEED, FEE_RESERVE_SEED, FEE_TREASURY_SEED, PROGRAM_STATE_SEED};
+use crate::utils::fees::{refund_bridge_fee, RefundAccounts};
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey, nonce: u64)]
+pub struct ClaimFeeRefund<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [CROSS_CHAIN_TRANSFER_SEED, mint.as_ref(), nonce.to_le_bytes().as_ref()],
+        bump = transfer_record.bump
+    )]
+    pub transfer_record: Account<'info, CrossChainTransfer>,
+
+    /// CHECK: Fee treasury PDA; holds no data
+    #[account(
+        mut,
+        seeds = [FEE_TREASURY_SEED],
+        bump
+    )]
+    pub fee_treasury: UncheckedAccount<'info>,
+
+    /// CHECK: `FeeReserve`, updated if it exists
+    #[account(mut, seeds = [FEE_RESERVE_SEED], bump)]
+    pub fee_reserve: UncheckedAccount<'info>,
+
+    /// CHECK: Receives lamport refunds; must be the account that paid the fee
+    #[account(mut, address = transfer_record.fee_payer @ UniversalNftError::Unauthorized)]
+    pub fee_payer: UncheckedAccount<'info>,
+
+    /// Required for SPL fees
+    #[account(mut)]
+    pub treasury_fee_account: Option<Account<'info, TokenAccount>>,
+
+    /// Fee payer's account of the fee mint; required for SPL fees
+    #[account(
+        mut,
+        constraint = payer_fee_account.owner == transfer_record.fee_payer @ UniversalNftError::Unauthorized
+    )]
+    pub payer_fee_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Return the fee a finished transfer no longer needs to its payer: the whole
+/// fee after a revert or failure, the unspent gas budget after delivery.
+/// Anyone may submit it; the refund only goes to the payer.
+pub fn handler(ctx: Context<ClaimFeeRefund>, mint: Pubkey, nonce: u64) -> Result<()> {
+    let amount = ctx.accounts.transfer_record.refundable_fee();
+    require!(amount > 0, UniversalNftError::NoFeeRefund);
+
+    let fee_mint = ctx.accounts.transfer_record.fee_mint;
+    refund_bridge_fee(
+        fee_mint,
+        amount,
+        RefundAccounts {
+            fee_treasury: &ctx.accounts.fee_treasury.to_account_info(),
+            fee_treasury_bump: ctx.bumps.fee_treasury,
+            fee_payer: &ctx.accounts.fee_payer.to_account_info(),
+            treasury_fee_account: ctx.accounts.treasury_fee_account.as_ref(),
+            payer_fee_account: ctx.accounts.payer_fee_account.as_ref(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+        },
+    )?;
+
+    ctx.accounts.transfer_record.fee_refunded = true;
+    FeeReserve::release_if_exists(&ctx.accounts.fee_reserve, fee_mint, amount)?;
+
+    emit!(FeeRefundedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint,
+        nonce,
+        fee_payer: ctx.accounts.fee_payer.key(),
+        fee_mint,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Refunded {} fees ({:?}) for mint: {}, nonce: {}", amount, fee_mint, mint, nonce);
+
+    Ok(())
+}
+
+#[event]
+pub struct FeeRefundedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub nonce: u64,
+    pub fee_payer: Pubkey,
+    pub fee_mint: Option<Pubkey>,
+    pub amount: u64,
+    pub timestamp: i64,
+}
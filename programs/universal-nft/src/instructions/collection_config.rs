@@ -0,0 +1,226 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use anchor_lang::solana_program::program_option::COption;
+use crate::state::{ProgramState, CollectionConfig, CollectionAction, CreatorApproval, NftMetadata, SymbolRegistry, SymbolReservation, VerificationBadge};
+use crate::error::UniversalNftError;
+use crate::constants::{COLLECTION_CONFIG_SEED, CREATOR_SET_SEED, NFT_METADATA_SEED, PROGRAM_STATE_SEED, SYMBOL_REGISTRY_SEED, SYMBOL_RESERVATION_SEED};
+use crate::utils::creators::{authorize_collection_action, load_creator_set};
+
+#[derive(Accounts)]
+pub struct CreateCollectionConfig<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + CollectionConfig::INIT_SPACE,
+        seeds = [COLLECTION_CONFIG_SEED, collection_mint.key().as_ref()],
+        bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    pub collection_mint: Account<'info, Mint>,
+
+    /// CHECK: the `SymbolRegistry`, which may not exist; read in the handler
+    #[account(seeds = [SYMBOL_REGISTRY_SEED], bump)]
+    pub symbol_registry: UncheckedAccount<'info>,
+
+    /// The collection mint's own metadata, whose symbol the collection reserves
+    #[account(
+        seeds = [NFT_METADATA_SEED, collection_mint.key().as_ref()],
+        bump = collection_metadata.bump
+    )]
+    pub collection_metadata: Option<Account<'info, NftMetadata>>,
+
+    /// Reserves the collection's symbol for `creator`; required while the symbol registry is enabled
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + SymbolReservation::INIT_SPACE,
+        seeds = [SYMBOL_RESERVATION_SEED, SymbolReservation::collection_seed(&collection_metadata).as_ref()],
+        bump
+    )]
+    pub symbol_reservation: Option<Account<'info, SymbolReservation>>,
+
+    /// The collection mint authority, or the program authority registering on a creator's behalf
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetCollectionBridging<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_CONFIG_SEED, collection_config.collection_mint.as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    /// CHECK: the collection's `CreatorSet`, which may not exist; read by `load_creator_set`
+    #[account(seeds = [CREATOR_SET_SEED, collection_config.collection_mint.as_ref()], bump)]
+    pub creator_set: UncheckedAccount<'info>,
+
+    /// The creators' approval of this change; required once the creator set is active
+    #[account(mut)]
+    pub creator_approval: Option<Account<'info, CreatorApproval>>,
+
+    /// The collection's creator, a member of its creator set, or the program authority
+    pub signer: Signer<'info>,
+}
+
+/// Register a collection for `creator`. While the symbol registry is on, the
+/// collection mint must carry this program's metadata, and its symbol is
+/// reserved for `creator`.
+pub fn create_handler(ctx: Context<CreateCollectionConfig>, creator: Pubkey) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+    let is_mint_authority = ctx.accounts.collection_mint.mint_authority == COption::Some(signer);
+    require!(
+        (is_mint_authority && creator == signer) || signer == ctx.accounts.program_state.authority,
+        UniversalNftError::Unauthorized
+    );
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let symbol_registry = &ctx.accounts.symbol_registry;
+    let registry_enabled = symbol_registry.owner == &crate::ID
+        && SymbolRegistry::try_deserialize(&mut &symbol_registry.try_borrow_data()?[..])?.enabled;
+    match (&ctx.accounts.collection_metadata, ctx.accounts.symbol_reservation.as_deref_mut()) {
+        (Some(collection_metadata), Some(symbol_reservation)) => {
+            let bump = ctx.bumps.symbol_reservation.ok_or(UniversalNftError::SymbolReservationRequired)?;
+            let collection_mint = ctx.accounts.collection_mint.key();
+            symbol_reservation.reserve(&collection_metadata.symbol, creator, collection_mint, timestamp, bump)?;
+        }
+        (_, Some(_)) => return err!(UniversalNftError::SymbolReservationRequired),
+        (_, None) => require!(!registry_enabled, UniversalNftError::SymbolReservationRequired),
+    }
+
+    let collection_config = &mut ctx.accounts.collection_config;
+    collection_config.collection_mint = ctx.accounts.collection_mint.key();
+    collection_config.creator = creator;
+    collection_config.cross_chain_enabled = true;
+    collection_config.daily_bridge_limit = 0;
+    collection_config.window_start = 0;
+    collection_config.window_count = 0;
+    collection_config.verification = VerificationBadge::default();
+    collection_config.bump = ctx.bumps.collection_config;
+
+    emit!(CollectionConfigCreatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        collection_mint: collection_config.collection_mint,
+        creator,
+        timestamp,
+    });
+
+    msg!("Created collection config for: {}", collection_config.collection_mint);
+
+    Ok(())
+}
+
+pub fn set_bridging_handler(ctx: Context<SetCollectionBridging>, enabled: bool) -> Result<()> {
+    authorize(ctx.accounts, CollectionAction::SetBridging, &enabled.try_to_vec()?)?;
+
+    let collection_config = &mut ctx.accounts.collection_config;
+    collection_config.cross_chain_enabled = enabled;
+
+    emit!(CollectionBridgingUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        collection_mint: collection_config.collection_mint,
+        cross_chain_enabled: enabled,
+        updated_by: ctx.accounts.signer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Collection {} cross-chain enabled: {}", collection_config.collection_mint, enabled);
+
+    Ok(())
+}
+
+/// Cap how many of the collection's items can be bridged out per rolling day;
+/// 0 removes the cap. Items already counted in the current window still count
+/// against a lowered limit.
+pub fn set_velocity_limit_handler(ctx: Context<SetCollectionBridging>, daily_limit: u32) -> Result<()> {
+    authorize(ctx.accounts, CollectionAction::SetVelocityLimit, &daily_limit.try_to_vec()?)?;
+
+    let collection_config = &mut ctx.accounts.collection_config;
+    let old_limit = collection_config.daily_bridge_limit;
+    collection_config.daily_bridge_limit = daily_limit;
+
+    emit!(CollectionVelocityLimitUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        collection_mint: collection_config.collection_mint,
+        old_limit,
+        new_limit: daily_limit,
+        updated_by: ctx.accounts.signer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Collection {} daily bridge limit: {} -> {}",
+        collection_config.collection_mint,
+        old_limit,
+        daily_limit
+    );
+
+    Ok(())
+}
+
+fn authorize(accounts: &mut SetCollectionBridging, action: CollectionAction, args: &[u8]) -> Result<()> {
+    let creator_set = load_creator_set(&accounts.creator_set)?;
+    authorize_collection_action(
+        &accounts.collection_config,
+        creator_set.as_ref(),
+        accounts.creator_approval.as_deref_mut(),
+        &accounts.signer.key(),
+        &accounts.program_state.authority,
+        action,
+        args,
+    )
+}
+
+#[event]
+pub struct CollectionConfigCreatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub collection_mint: Pubkey,
+    pub creator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollectionBridgingUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub collection_mint: Pubkey,
+    pub cross_chain_enabled: bool,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollectionVelocityLimitUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub collection_mint: Pubkey,
+    pub old_limit: u32,
+    pub new_limit: u32,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use crate::state::{ProgramState, CrossChainConfig, NftMetadata, SwapOrder, Relayer};
+use crate::error::UniversalNftError;
+use crate::constants::{CROSS_CHAIN_CONFIG_SEED, NFT_METADATA_SEED, PROGRAM_STATE_SEED, RELAYER_SEED, SWAP_ORDER_SEED};
+use crate::utils::relayers::require_bonded_relayer;
+use crate::utils::security::{verify_tss_signature, signed_message};
+use universal_nft_messages::SwapPayment;
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct CompleteSwap<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
+        bump = cross_chain_config.bump,
+        constraint = !cross_chain_config.is_paused @ UniversalNftError::CrossChainPaused
+    )]
+    pub cross_chain_config: Account<'info, CrossChainConfig>,
+
+    #[account(
+        seeds = [NFT_METADATA_SEED, mint.as_ref()],
+        bump = nft_metadata.bump
+    )]
+    pub nft_metadata: Account<'info, NftMetadata>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [SWAP_ORDER_SEED, mint.as_ref()],
+        bump = swap_order.bump,
+        constraint = swap_order.status == 0 @ UniversalNftError::SwapNotOpen
+    )]
+    pub swap_order: Account<'info, SwapOrder>,
+
+    /// CHECK: Receives the swap order rent; must be the seller
+    #[account(mut, address = swap_order.seller @ UniversalNftError::Unauthorized)]
+    pub seller: UncheckedAccount<'info>,
+
+    /// Required while a relayer requirement is set
+    #[account(
+        mut,
+        seeds = [RELAYER_SEED, relayer.key().as_ref()],
+        bump = relayer_registration.bump
+    )]
+    pub relayer_registration: Option<Account<'info, Relayer>>,
+
+    pub relayer: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<CompleteSwap>,
+    mint: Pubkey,
+    payment_tx_hash: Vec<u8>,
+    tss_signature: Vec<u8>,
+) -> Result<()> {
+    let swap_order = &ctx.accounts.swap_order;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(now <= swap_order.deadline, UniversalNftError::SwapExpired);
+    require!(
+        !payment_tx_hash.is_empty() && payment_tx_hash.len() <= 64,
+        UniversalNftError::InvalidSwapTerms
+    );
+    require!(
+        !tss_signature.is_empty()
+            && ctx.accounts.program_state.validation_limits.fits_signature(&tss_signature),
+        UniversalNftError::InvalidTssSignature
+    );
+    require_bonded_relayer(&ctx.accounts.cross_chain_config, ctx.accounts.relayer_registration.as_mut())?;
+
+    // The TSS attests that the buyer paid the agreed amount on the destination chain
+    let message = signed_message(
+        ctx.accounts.cross_chain_config.chain_id,
+        &SwapPayment {
+            mint: mint.to_bytes(),
+            destination_chain_id: swap_order.destination_chain_id,
+            payment_token: &swap_order.payment_token,
+            payment_amount: swap_order.payment_amount,
+            buyer_address: &swap_order.buyer_address,
+            payment_tx_hash: &payment_tx_hash,
+        },
+    );
+
+    let is_valid = verify_tss_signature(
+        &message,
+        &tss_signature,
+        &ctx.accounts.cross_chain_config.tss_address,
+    )?;
+    require!(is_valid, UniversalNftError::InvalidTssSignature);
+
+    // The NFT stays locked here and is delivered to the buyer on the destination chain
+    let program_state = &mut ctx.accounts.program_state;
+    program_state.cross_chain_transfers = program_state
+        .cross_chain_transfers
+        .checked_add(1)
+        .ok_or(UniversalNftError::ArithmeticOverflow)?;
+
+    emit!(SwapCompletedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint,
+        seller: swap_order.seller,
+        destination_chain_id: swap_order.destination_chain_id,
+        buyer_address: swap_order.buyer_address.clone(),
+        payment_amount: swap_order.payment_amount,
+        payment_tx_hash,
+        metadata_uri: ctx.accounts.nft_metadata.metadata_uri.clone(),
+        timestamp: now,
+    });
+
+    msg!("Swap completed for mint: {}", mint);
+
+    Ok(())
+}
+
+#[event]
+pub struct SwapCompletedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub seller: Pubkey,
+    pub destination_chain_id: u64,
+    pub buyer_address: Vec<u8>,
+    pub payment_amount: u64,
+    pub payment_tx_hash: Vec<u8>,
+    pub metadata_uri: String,
+    pub timestamp: i64,
+}
@@ -0,0 +1,222 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::{ProgramState, CrossChainConfig, NftMetadata, SwapOrder, Blocklist, ChainConfig, CollectionLedger, CollectionConfig, OptOutRegistry};
+use crate::error::UniversalNftError;
+use crate::constants::{BLOCKLIST_SEED, CHAIN_CONFIG_SEED, COLLECTION_CONFIG_SEED, COLLECTION_LEDGER_SEED, CROSS_CHAIN_CONFIG_SEED, NFT_METADATA_SEED, OPT_OUT_REGISTRY_SEED, PROGRAM_STATE_SEED, STAKE_RECORD_SEED, SWAP_ORDER_SEED};
+use crate::utils::staking::checkpoint_stake;
+use crate::utils::validation::{validate_collection_bridging, validate_not_opted_out, validate_nft_mint_supply};
+use universal_nft_messages::chain_id;
+
+#[derive(Accounts)]
+#[instruction(destination_chain_id: u64)]
+pub struct CreateSwap<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = !program_state.is_sunset @ UniversalNftError::ProgramSunset
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
+        bump = cross_chain_config.bump,
+        constraint = !cross_chain_config.is_paused @ UniversalNftError::CrossChainPaused
+    )]
+    pub cross_chain_config: Account<'info, CrossChainConfig>,
+
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    #[account(
+        seeds = [OPT_OUT_REGISTRY_SEED],
+        bump = opt_out_registry.bump
+    )]
+    pub opt_out_registry: Account<'info, OptOutRegistry>,
+
+    #[account(
+        seeds = [CHAIN_CONFIG_SEED, destination_chain_id.to_le_bytes().as_ref()],
+        bump = destination_chain_config.bump,
+        constraint = !destination_chain_config.is_paused @ UniversalNftError::ChainPaused
+    )]
+    pub destination_chain_config: Account<'info, ChainConfig>,
+
+    #[account(
+        mut,
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump,
+        constraint = nft_metadata.cross_chain_enabled @ UniversalNftError::CrossChainNotEnabled,
+        constraint = !nft_metadata.is_locked @ UniversalNftError::NftLocked,
+        constraint = nft_metadata.child_count == 0 @ UniversalNftError::HasAttachedChildren
+    )]
+    pub nft_metadata: Account<'info, NftMetadata>,
+
+    /// Required when the NFT belongs to a collection
+    #[account(
+        seeds = [COLLECTION_CONFIG_SEED, nft_metadata.collection.as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Option<Account<'info, CollectionConfig>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + CollectionLedger::INIT_SPACE,
+        seeds = [
+            COLLECTION_LEDGER_SEED,
+            nft_metadata.origin_chain_id.to_le_bytes().as_ref(),
+            nft_metadata.origin_contract.as_slice()
+        ],
+        bump
+    )]
+    pub collection_ledger: Account<'info, CollectionLedger>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + SwapOrder::INIT_SPACE,
+        seeds = [SWAP_ORDER_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub swap_order: Account<'info, SwapOrder>,
+
+    /// CHECK: Mint account validated by token account constraint
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = token_account.mint == mint.key(),
+        constraint = token_account.owner == seller.key(),
+        constraint = token_account.amount >= 1 @ UniversalNftError::InsufficientTokens
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    pub seller: Signer<'info>,
+
+    /// CHECK: The NFT's stake PDA, checkpointed by the handler if it exists
+    #[account(mut, seeds = [STAKE_RECORD_SEED, mint.key().as_ref()], bump)]
+    pub stake_record: UncheckedAccount<'info>,
+
+    /// Funds the swap order and its escrow
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateSwap>,
+    destination_chain_id: u64,
+    payment_token: Vec<u8>,
+    payment_amount: u64,
+    buyer_address: Vec<u8>,
+    deadline: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    // Validate swap terms
+    require!(
+        !payment_token.is_empty() && payment_token.len() <= 64,
+        UniversalNftError::InvalidSwapTerms
+    );
+    require!(payment_amount > 0, UniversalNftError::InvalidSwapTerms);
+    require!(deadline > now, UniversalNftError::InvalidSwapTerms);
+    require!(
+        !buyer_address.is_empty() && buyer_address.len() <= 64,
+        UniversalNftError::InvalidRecipientAddress
+    );
+    require!(
+        destination_chain_id > 0 && !chain_id::is_solana(destination_chain_id),
+        UniversalNftError::UnsupportedChain
+    );
+
+    // Reject blocklisted sellers and buyers
+    let blocklist = &ctx.accounts.blocklist;
+    require!(
+        !blocklist.is_account_blocked(&ctx.accounts.seller.key()),
+        UniversalNftError::AddressBlocked
+    );
+    require!(
+        !blocklist.is_foreign_address_blocked(&buyer_address),
+        UniversalNftError::AddressBlocked
+    );
+
+    validate_collection_bridging(
+        &ctx.accounts.nft_metadata,
+        ctx.accounts.collection_config.as_deref(),
+    )?;
+    validate_not_opted_out(&ctx.accounts.nft_metadata, &ctx.accounts.opt_out_registry)?;
+    validate_nft_mint_supply(&ctx.accounts.mint)?;
+
+    // Lock the NFT for the duration of the swap
+    let nft_metadata = &mut ctx.accounts.nft_metadata;
+    nft_metadata.is_locked = true;
+    nft_metadata.current_owner = ctx.accounts.seller.key();
+
+    // Update the collection's supply ledger
+    let collection_ledger = &mut ctx.accounts.collection_ledger;
+    collection_ledger.ensure_initialized(
+        nft_metadata.origin_chain_id,
+        &nft_metadata.origin_contract,
+        ctx.bumps.collection_ledger,
+    );
+    collection_ledger.record_outbound()?;
+
+    let swap_order = &mut ctx.accounts.swap_order;
+    swap_order.seller = ctx.accounts.seller.key();
+    swap_order.mint = ctx.accounts.mint.key();
+    swap_order.destination_chain_id = destination_chain_id;
+    swap_order.payment_token = payment_token.clone();
+    swap_order.payment_amount = payment_amount;
+    swap_order.buyer_address = buyer_address.clone();
+    swap_order.deadline = deadline;
+    swap_order.created_at = now;
+    swap_order.status = 0; // Open
+    swap_order.bump = ctx.bumps.swap_order;
+
+    // Suspend the NFT's stake while it is locked; swaps have no transfer nonce
+    let staked_seconds = checkpoint_stake(
+        &ctx.accounts.stake_record,
+        &mut ctx.accounts.program_state,
+        ctx.accounts.mint.key(),
+        ctx.accounts.seller.key(),
+        0,
+        now,
+    )?;
+    ctx.accounts.swap_order.staked_seconds = staked_seconds;
+
+    emit!(SwapCreatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint: ctx.accounts.mint.key(),
+        seller: ctx.accounts.seller.key(),
+        destination_chain_id,
+        payment_token,
+        payment_amount,
+        buyer_address,
+        deadline,
+        timestamp: now,
+    });
+
+    msg!("Swap created for mint: {}", ctx.accounts.mint.key());
+    msg!("Price: {} on chain {}, deadline: {}", payment_amount, destination_chain_id, deadline);
+
+    Ok(())
+}
+
+#[event]
+pub struct SwapCreatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub seller: Pubkey,
+    pub destination_chain_id: u64,
+    pub payment_token: Vec<u8>,
+    pub payment_amount: u64,
+    pub buyer_address: Vec<u8>,
+    pub deadline: i64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,192 @@
+use anchor_lang::prelude::*;
+use crate::state::{ProgramState, CollectionConfig, CollectionAction, CreatorApproval, CreatorSet};
+use crate::error::UniversalNftError;
+use crate::constants::{COLLECTION_CONFIG_SEED, CREATOR_APPROVAL_SEED, CREATOR_SET_SEED, PROGRAM_STATE_SEED};
+use crate::utils::creators::authorize_collection_action;
+
+/// Creates the creator set on first use
+#[derive(Accounts)]
+pub struct SetCreatorSet<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [COLLECTION_CONFIG_SEED, collection_config.collection_mint.as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + CreatorSet::INIT_SPACE,
+        seeds = [CREATOR_SET_SEED, collection_config.collection_mint.as_ref()],
+        bump
+    )]
+    pub creator_set: Account<'info, CreatorSet>,
+
+    /// The current creators' approval of the new set; required once the set is active
+    #[account(mut)]
+    pub creator_approval: Option<Account<'info, CreatorApproval>>,
+
+    /// The collection's creator or the program authority, or once the set is
+    /// active one of its creators; pays for the account
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(action: CollectionAction, args_hash: [u8; 32])]
+pub struct ApproveCollectionAction<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [CREATOR_SET_SEED, creator_set.collection_mint.as_ref()],
+        bump = creator_set.bump,
+        constraint = creator_set.is_active() @ UniversalNftError::InvalidCreatorSet
+    )]
+    pub creator_set: Account<'info, CreatorSet>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + CreatorApproval::INIT_SPACE,
+        seeds = [CREATOR_APPROVAL_SEED, creator_set.collection_mint.as_ref(), &[action as u8], args_hash.as_ref()],
+        bump
+    )]
+    pub creator_approval: Account<'info, CreatorApproval>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Make `creators` jointly manage the collection, `threshold` of them needed
+/// per change; no creators with a threshold of 0 hands control back to the
+/// collection's creator. Changing an active set needs its own approval.
+pub fn set_handler(ctx: Context<SetCreatorSet>, creators: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    CreatorSet::validate(&creators, threshold)?;
+    let signer = ctx.accounts.signer.key();
+    authorize_collection_action(
+        &ctx.accounts.collection_config,
+        Some(&ctx.accounts.creator_set),
+        ctx.accounts.creator_approval.as_deref_mut(),
+        &signer,
+        &ctx.accounts.program_state.authority,
+        CollectionAction::SetCreatorSet,
+        &(&creators, threshold).try_to_vec()?,
+    )?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let creator_set = &mut ctx.accounts.creator_set;
+    creator_set.collection_mint = ctx.accounts.collection_config.collection_mint;
+    creator_set.threshold = threshold;
+    creator_set.version = creator_set.version.wrapping_add(1);
+    creator_set.updated_at = timestamp;
+    creator_set.bump = ctx.bumps.creator_set;
+    creator_set.creators = creators.clone();
+
+    emit!(CreatorSetUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        collection_mint: creator_set.collection_mint,
+        creators,
+        threshold,
+        version: creator_set.version,
+        updated_by: signer,
+        timestamp,
+    });
+
+    msg!(
+        "Collection {} creator set: {} of {}",
+        creator_set.collection_mint,
+        threshold,
+        creator_set.creators.len()
+    );
+
+    Ok(())
+}
+
+/// Approve applying `action` with the arguments hashing to `args_hash`.
+/// Each set of arguments gathers approvals in its own account, so competing
+/// proposals don't discard each other; a change to the creator set does.
+pub fn approve_handler(ctx: Context<ApproveCollectionAction>, action: CollectionAction, args_hash: [u8; 32]) -> Result<()> {
+    let creator_set = &ctx.accounts.creator_set;
+    let creator = ctx.accounts.creator.key();
+    let position = creator_set.position(&creator).ok_or(UniversalNftError::Unauthorized)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let creator_approval = &mut ctx.accounts.creator_approval;
+    if creator_approval.args_hash != args_hash || creator_approval.set_version != creator_set.version {
+        creator_approval.args_hash = args_hash;
+        creator_approval.set_version = creator_set.version;
+        creator_approval.approvals = 0;
+        creator_approval.opened_at = now;
+    }
+    creator_approval.collection_mint = creator_set.collection_mint;
+    creator_approval.action = action;
+    creator_approval.approvals |= 1 << position;
+    creator_approval.bump = ctx.bumps.creator_approval;
+
+    emit!(CollectionActionApprovedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        collection_mint: creator_set.collection_mint,
+        action,
+        args_hash,
+        approver: creator,
+        approvals: creator_approval.approval_count() as u8,
+        threshold: creator_set.threshold,
+        timestamp: now,
+    });
+
+    msg!(
+        "Collection {} {:?} approved by {}: {} of {}",
+        creator_set.collection_mint,
+        action,
+        creator,
+        creator_approval.approval_count(),
+        creator_set.threshold
+    );
+
+    Ok(())
+}
+
+#[event]
+pub struct CreatorSetUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub collection_mint: Pubkey,
+    pub creators: Vec<Pubkey>,
+    pub threshold: u8,
+    pub version: u32,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollectionActionApprovedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub collection_mint: Pubkey,
+    pub action: CollectionAction,
+    pub args_hash: [u8; 32],
+    pub approver: Pubkey,
+    pub approvals: u8,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
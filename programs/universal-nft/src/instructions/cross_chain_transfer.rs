@@ -1,44 +1,114 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount};
-use crate::state::{ProgramState, CrossChainConfig, NftMetadata, CrossChainTransfer};
+use crate::state::{ProgramState, CrossChainConfig, NftMetadata, CrossChainTransfer, FeeReserve, Blocklist, ChainConfig, CollectionLedger, CollectionConfig, OptOutRegistry, OwnerTransferIndex, PendingTransfers, LocalizedMetadata};
 use crate::error::UniversalNftError;
+use crate::constants::{BLOCKLIST_SEED, CHAIN_CONFIG_SEED, COLLECTION_CONFIG_SEED, COLLECTION_LEDGER_SEED, CROSS_CHAIN_CONFIG_SEED, CROSS_CHAIN_TRANSFER_SEED, FEE_RESERVE_SEED, LOCALIZED_METADATA_SEED, NFT_METADATA_SEED, OPT_OUT_REGISTRY_SEED, OWNER_TRANSFER_INDEX_SEED, PENDING_TRANSFERS_SEED, PROGRAM_STATE_SEED, STAKE_RECORD_SEED};
+use crate::utils::validation::{validate_outbound_transfer, validate_nft_mint_supply, validate_payload_size, validate_collection_bridging, validate_not_opted_out, record_outbound_velocity, record_collection_velocity, validate_credential, validate_token_gate};
+use crate::utils::fees::{collect_bridge_fee, FeeAccounts};
+use crate::utils::staking::checkpoint_stake;
+use crate::adapters::{Adapter, AdapterAccounts, BridgeAdapter};
+use crate::instructions::set_localized_uris::LocalizedUrisTransferredEvent;
+use universal_nft_messages::{LocalizedUris, OutboundPayload, OutboundTransfer};
 
 #[derive(Accounts)]
 #[instruction(destination_chain_id: u64, recipient_address: Vec<u8>, nonce: u64)]
 pub struct InitiateCrossChainTransfer<'info> {
     #[account(
         mut,
-        seeds = [b"program_state"],
+        seeds = [PROGRAM_STATE_SEED],
         bump = program_state.bump,
-        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = !program_state.is_sunset @ UniversalNftError::ProgramSunset
     )]
     pub program_state: Account<'info, ProgramState>,
 
     #[account(
-        seeds = [b"cross_chain_config"],
+        mut,
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
         bump = cross_chain_config.bump,
         constraint = !cross_chain_config.is_paused @ UniversalNftError::CrossChainPaused
     )]
     pub cross_chain_config: Account<'info, CrossChainConfig>,
 
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    #[account(
+        seeds = [OPT_OUT_REGISTRY_SEED],
+        bump = opt_out_registry.bump
+    )]
+    pub opt_out_registry: Account<'info, OptOutRegistry>,
+
+    #[account(
+        seeds = [CHAIN_CONFIG_SEED, destination_chain_id.to_le_bytes().as_ref()],
+        bump = destination_chain_config.bump,
+        constraint = !destination_chain_config.is_paused @ UniversalNftError::ChainPaused
+    )]
+    pub destination_chain_config: Account<'info, ChainConfig>,
+
     #[account(
         mut,
-        seeds = [b"nft_metadata", mint.key().as_ref()],
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
         bump = nft_metadata.bump,
         constraint = nft_metadata.cross_chain_enabled @ UniversalNftError::CrossChainNotEnabled,
-        constraint = !nft_metadata.is_locked @ UniversalNftError::NftLocked
+        constraint = !nft_metadata.is_locked @ UniversalNftError::NftLocked,
+        constraint = nft_metadata.child_count == 0 @ UniversalNftError::HasAttachedChildren
     )]
     pub nft_metadata: Account<'info, NftMetadata>,
 
+    /// Required when the NFT belongs to a collection
+    #[account(
+        mut,
+        seeds = [COLLECTION_CONFIG_SEED, nft_metadata.collection.as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Option<Account<'info, CollectionConfig>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + CollectionLedger::INIT_SPACE,
+        seeds = [
+            COLLECTION_LEDGER_SEED,
+            nft_metadata.origin_chain_id.to_le_bytes().as_ref(),
+            nft_metadata.origin_contract.as_slice()
+        ],
+        bump
+    )]
+    pub collection_ledger: Account<'info, CollectionLedger>,
+
     #[account(
         init,
-        payer = owner,
+        payer = payer,
         space = 8 + CrossChainTransfer::INIT_SPACE,
-        seeds = [b"cross_chain_transfer", mint.key().as_ref(), nonce.to_le_bytes().as_ref()],
+        seeds = [CROSS_CHAIN_TRANSFER_SEED, mint.key().as_ref(), nonce.to_le_bytes().as_ref()],
         bump
     )]
     pub transfer_record: Account<'info, CrossChainTransfer>,
 
+    /// Lists the owner's recent transfers
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + OwnerTransferIndex::INIT_SPACE,
+        seeds = [OWNER_TRANSFER_INDEX_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub owner_transfer_index: Box<Account<'info, OwnerTransferIndex>>,
+
+    /// Outbound backlog for monitoring
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PendingTransfers::INIT_SPACE,
+        seeds = [PENDING_TRANSFERS_SEED],
+        bump
+    )]
+    pub pending_transfers: Box<Account<'info, PendingTransfers>>,
+
     /// CHECK: Mint account validated by token account constraint
     pub mint: UncheckedAccount<'info>,
 
@@ -49,9 +119,53 @@ pub struct InitiateCrossChainTransfer<'info> {
     )]
     pub token_account: Account<'info, TokenAccount>,
 
-    #[account(mut)]
+    /// Holder of the NFT; may be a PDA signing through CPI
     pub owner: Signer<'info>,
 
+    /// CHECK: Checked against `CrossChainConfig.credential_requirement`; required only when one is set
+    pub credential: Option<UncheckedAccount<'info>>,
+
+    /// Owner's account of the gate mint; required only when a token gate applies
+    pub gate_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: The NFT's stake PDA, checkpointed by the handler if it exists;
+    /// its unclaimed time travels in the payload
+    #[account(mut, seeds = [STAKE_RECORD_SEED, mint.key().as_ref()], bump)]
+    pub stake_record: UncheckedAccount<'info>,
+
+    /// The NFT's localized metadata URIs, if it has any; they travel in the payload
+    #[account(
+        seeds = [LOCALIZED_METADATA_SEED, mint.key().as_ref()],
+        bump = localized_metadata.bump
+    )]
+    pub localized_metadata: Option<Box<Account<'info, LocalizedMetadata>>>,
+
+    /// CHECK: Fee treasury PDA, checked by the handler; required for lamport fees
+    #[account(mut)]
+    pub fee_treasury: Option<UncheckedAccount<'info>>,
+
+    /// Payer's account of the fee mint; required for SPL fees
+    #[account(mut)]
+    pub payer_fee_account: Option<Account<'info, TokenAccount>>,
+
+    /// The fee treasury's associated token account for the fee mint; required for SPL fees
+    #[account(mut)]
+    pub treasury_fee_account: Option<Account<'info, TokenAccount>>,
+
+    /// Refundable fees the treasury must keep
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + FeeReserve::INIT_SPACE,
+        seeds = [FEE_RESERVE_SEED],
+        bump
+    )]
+    pub fee_reserve: Box<Account<'info, FeeReserve>>,
+
+    /// Funds the transfer record and, on first use, the collection ledger and transfer index
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
@@ -63,32 +177,67 @@ pub fn handler(
     nonce: u64,
 ) -> Result<()> {
     let program_state = &mut ctx.accounts.program_state;
-    let cross_chain_config = &ctx.accounts.cross_chain_config;
+    let cross_chain_config = &mut ctx.accounts.cross_chain_config;
     let nft_metadata = &mut ctx.accounts.nft_metadata;
     let transfer_record = &mut ctx.accounts.transfer_record;
 
-    // Validate nonce
-    require!(
-        nonce > cross_chain_config.nonce_counter,
-        UniversalNftError::InvalidNonce
-    );
-
-    // Validate recipient address length
-    require!(
-        recipient_address.len() <= 64 && !recipient_address.is_empty(),
-        UniversalNftError::InvalidRecipientAddress
-    );
-
-    // Validate destination chain (simplified - in production, maintain a list)
-    require!(
-        destination_chain_id > 0 && destination_chain_id != 7565164, // Not Solana
-        UniversalNftError::UnsupportedChain
-    );
+    validate_outbound_transfer(
+        cross_chain_config,
+        &ctx.accounts.blocklist,
+        &ctx.accounts.owner.key(),
+        destination_chain_id,
+        &recipient_address,
+        nonce,
+    )?;
+    validate_nft_mint_supply(&ctx.accounts.mint)?;
+    cross_chain_config.consume_nonce(nonce);
+    record_outbound_velocity(program_state, cross_chain_config, 1)?;
+    validate_collection_bridging(
+        nft_metadata,
+        ctx.accounts.collection_config.as_deref(),
+    )?;
+    record_collection_velocity(ctx.accounts.collection_config.as_deref_mut(), 1)?;
+    validate_not_opted_out(nft_metadata, &ctx.accounts.opt_out_registry)?;
+    validate_credential(
+        cross_chain_config,
+        ctx.accounts.credential.as_deref(),
+        &ctx.accounts.owner.key(),
+    )?;
+    validate_token_gate(
+        cross_chain_config,
+        &ctx.accounts.destination_chain_config,
+        ctx.accounts.gate_token_account.as_deref(),
+        &ctx.accounts.owner.key(),
+    )?;
+    let fee = ctx.accounts.destination_chain_config.fee;
+    let fee_paid = collect_bridge_fee(
+        &fee,
+        1,
+        FeeAccounts {
+            payer: &ctx.accounts.payer.to_account_info(),
+            fee_treasury: ctx.accounts.fee_treasury.as_deref(),
+            payer_fee_account: ctx.accounts.payer_fee_account.as_ref(),
+            treasury_fee_account: ctx.accounts.treasury_fee_account.as_ref(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+        },
+    )?;
+    // Held back from withdrawals until it is refunded or earned
+    ctx.accounts.fee_reserve.reserve(ctx.bumps.fee_reserve, fee.mint, fee_paid)?;
 
     // Lock the NFT
     nft_metadata.is_locked = true;
     nft_metadata.current_owner = ctx.accounts.owner.key();
 
+    // Update the collection's supply ledger
+    let collection_ledger = &mut ctx.accounts.collection_ledger;
+    collection_ledger.ensure_initialized(
+        nft_metadata.origin_chain_id,
+        &nft_metadata.origin_contract,
+        ctx.bumps.collection_ledger,
+    );
+    collection_ledger.record_outbound()?;
+
     // Create transfer record
     transfer_record.mint = ctx.accounts.mint.key();
     transfer_record.original_owner = ctx.accounts.owner.key();
@@ -97,7 +246,29 @@ pub fn handler(
     transfer_record.nonce = nonce;
     transfer_record.timestamp = Clock::get()?.unix_timestamp;
     transfer_record.status = 0; // Pending
+    transfer_record.value_mint = Pubkey::default();
+    transfer_record.value_amount = 0;
+    transfer_record.destination_tx_hash = [0u8; 32];
+    transfer_record.completed_at = 0;
+    transfer_record.fee_payer = ctx.accounts.payer.key();
+    transfer_record.fee_mint = fee.mint;
+    transfer_record.fee_paid = fee_paid;
+    transfer_record.gas_budget = fee.gas_budget;
+    transfer_record.gas_spent = 0;
+    transfer_record.fee_refunded = false;
     transfer_record.bump = ctx.bumps.transfer_record;
+    ctx.accounts.owner_transfer_index.record(
+        ctx.accounts.owner.key(),
+        ctx.bumps.owner_transfer_index,
+        ctx.accounts.mint.key(),
+        nonce,
+    );
+    ctx.accounts.pending_transfers.record_created(
+        ctx.bumps.pending_transfers,
+        ctx.accounts.mint.key(),
+        nonce,
+        transfer_record.timestamp,
+    );
 
     // Update program statistics
     program_state.cross_chain_transfers = program_state
@@ -105,13 +276,74 @@ pub fn handler(
         .checked_add(1)
         .ok_or(UniversalNftError::ArithmeticOverflow)?;
 
-    // Emit event for ZetaChain gateway to pick up
+    // Suspend the NFT's stake and carry its unclaimed time to the destination
+    let now = Clock::get()?.unix_timestamp;
+    let staked_seconds = checkpoint_stake(
+        &ctx.accounts.stake_record,
+        &mut ctx.accounts.program_state,
+        ctx.accounts.mint.key(),
+        ctx.accounts.owner.key(),
+        nonce,
+        now,
+    )?;
+    ctx.accounts.transfer_record.staked_seconds = staked_seconds;
+
+    let localized_uris = match ctx.accounts.localized_metadata.as_deref() {
+        Some(localized_metadata) if !localized_metadata.entries.is_empty() => {
+            emit!(LocalizedUrisTransferredEvent {
+                schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+                sequence: ctx.accounts.program_state.next_event_sequence(),
+                mint: ctx.accounts.mint.key(),
+                nonce,
+                entries: localized_metadata.entries.clone(),
+                timestamp: now,
+            });
+            LocalizedUris::encode(
+                localized_metadata
+                    .entries
+                    .iter()
+                    .map(|entry| (entry.locale.as_str(), entry.uri.as_str())),
+            )
+        }
+        _ => Vec::new(),
+    };
+
+    // Hand the transfer to the destination chain's transport
+    let adapter = Adapter::for_chain(
+        &ctx.accounts.destination_chain_config,
+        cross_chain_config,
+        AdapterAccounts {
+            endpoint_accounts: ctx.remaining_accounts,
+            ..Default::default()
+        },
+    );
+    let outbound = OutboundTransfer {
+        mint: ctx.accounts.mint.key().to_bytes(),
+        owner: ctx.accounts.owner.key().to_bytes(),
+        destination_chain_id,
+        recipient_address: &recipient_address,
+        nonce,
+        metadata_uri: &nft_metadata.metadata_uri,
+        fallback_uri: nft_metadata.fallback_uri.as_deref().unwrap_or_default(),
+        staked_seconds,
+        localized_uris: LocalizedUris::from_encoded(&localized_uris)
+            .map_err(|_| UniversalNftError::InvalidLocalizedUris)?,
+    };
+    validate_payload_size(&ctx.accounts.destination_chain_config, outbound.encoded_len())?;
+    let message_hash = adapter.outbound_message_hash(&outbound);
+    adapter.dispatch_outbound(&outbound, &message_hash)?;
+
     emit!(CrossChainTransferEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
         mint: ctx.accounts.mint.key(),
         owner: ctx.accounts.owner.key(),
         destination_chain_id,
         recipient_address,
         nonce,
+        message_hash,
+        metadata_uri: nft_metadata.metadata_uri.clone(),
+        fallback_uri: nft_metadata.fallback_uri.clone(),
         timestamp: Clock::get()?.unix_timestamp,
     });
 
@@ -123,10 +355,15 @@ pub fn handler(
 
 #[event]
 pub struct CrossChainTransferEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
     pub mint: Pubkey,
     pub owner: Pubkey,
     pub destination_chain_id: u64,
     pub recipient_address: Vec<u8>,
     pub nonce: u64,
+    pub message_hash: [u8; 32],
+    pub metadata_uri: String,
+    pub fallback_uri: Option<String>,
     pub timestamp: i64,
 }
\ No newline at end of file
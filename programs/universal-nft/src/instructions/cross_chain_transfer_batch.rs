@@ -0,0 +1,391 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::{ProgramState, CrossChainConfig, NftMetadata, BundleTransfer, Blocklist, ChainConfig, CollectionLedger, CollectionConfig, FeeReserve, OptOutRegistry, OwnerTransferIndex, PendingTransfers};
+use crate::error::UniversalNftError;
+use crate::constants::{BLOCKLIST_SEED, BUNDLE_TRANSFER_SEED, CHAIN_CONFIG_SEED, COLLECTION_CONFIG_SEED, COLLECTION_LEDGER_SEED, CROSS_CHAIN_CONFIG_SEED, FEE_RESERVE_SEED, NFT_METADATA_SEED, OPT_OUT_REGISTRY_SEED, OWNER_TRANSFER_INDEX_SEED, PENDING_TRANSFERS_SEED, PROGRAM_STATE_SEED, STAKE_RECORD_SEED};
+use crate::utils::compute::{calculate_bundle_compute_budget, require_compute_budget};
+use crate::utils::validation::{validate_outbound_transfer, validate_nft_mint_supply, validate_payload_size, validate_collection_bridging, validate_not_opted_out, record_outbound_velocity, record_collection_velocity, validate_credential, validate_token_gate};
+use crate::utils::fees::{collect_bridge_fee, FeeAccounts};
+use crate::utils::staking::checkpoint_stake;
+use crate::adapters::{Adapter, AdapterAccounts, BridgeAdapter};
+use universal_nft_messages::{OutboundBundle, OutboundBundleItem, OutboundPayload};
+
+/// Remaining accounts are passed as `(mint, token_account, nft_metadata, stake_record)`
+/// quadruples, one per NFT in the bundle, with `nft_metadata` and `stake_record`
+/// writable; `stake_record` is the NFT's stake PDA whether or not it is staked.
+/// Accounts after the `item_count` quadruples go to the destination's adapter,
+/// such as the LayerZero endpoint's `send` accounts.
+/// Every NFT must belong to the collection tracked by `collection_ledger`, and NFTs
+/// that belong to a collection must all belong to the one passed as `collection_config`.
+#[derive(Accounts)]
+#[instruction(destination_chain_id: u64, recipient_address: Vec<u8>, nonce: u64)]
+pub struct InitiateBundleTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = !program_state.is_sunset @ UniversalNftError::ProgramSunset
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
+        bump = cross_chain_config.bump,
+        constraint = !cross_chain_config.is_paused @ UniversalNftError::CrossChainPaused
+    )]
+    pub cross_chain_config: Account<'info, CrossChainConfig>,
+
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    #[account(
+        seeds = [OPT_OUT_REGISTRY_SEED],
+        bump = opt_out_registry.bump
+    )]
+    pub opt_out_registry: Account<'info, OptOutRegistry>,
+
+    #[account(
+        seeds = [CHAIN_CONFIG_SEED, destination_chain_id.to_le_bytes().as_ref()],
+        bump = destination_chain_config.bump,
+        constraint = !destination_chain_config.is_paused @ UniversalNftError::ChainPaused
+    )]
+    pub destination_chain_config: Account<'info, ChainConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            COLLECTION_LEDGER_SEED,
+            collection_ledger.origin_chain_id.to_le_bytes().as_ref(),
+            collection_ledger.origin_contract.as_slice()
+        ],
+        bump = collection_ledger.bump
+    )]
+    pub collection_ledger: Account<'info, CollectionLedger>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_CONFIG_SEED, collection_config.collection_mint.as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Option<Account<'info, CollectionConfig>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + BundleTransfer::INIT_SPACE,
+        seeds = [BUNDLE_TRANSFER_SEED, owner.key().as_ref(), nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bundle_record: Account<'info, BundleTransfer>,
+
+    pub owner: Signer<'info>,
+
+    /// Lists the owner's recent transfers
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + OwnerTransferIndex::INIT_SPACE,
+        seeds = [OWNER_TRANSFER_INDEX_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub owner_transfer_index: Box<Account<'info, OwnerTransferIndex>>,
+
+    /// Outbound backlog for monitoring
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PendingTransfers::INIT_SPACE,
+        seeds = [PENDING_TRANSFERS_SEED],
+        bump
+    )]
+    pub pending_transfers: Box<Account<'info, PendingTransfers>>,
+
+    /// Refundable fees the treasury must keep
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + FeeReserve::INIT_SPACE,
+        seeds = [FEE_RESERVE_SEED],
+        bump
+    )]
+    pub fee_reserve: Box<Account<'info, FeeReserve>>,
+
+    /// Funds the bundle record and the bridge fee
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Checked against `CrossChainConfig.credential_requirement`; required only when one is set
+    pub credential: Option<UncheckedAccount<'info>>,
+
+    /// Owner's account of the gate mint; required only when a token gate applies
+    pub gate_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Fee treasury PDA, checked by the handler; required for lamport fees
+    #[account(mut)]
+    pub fee_treasury: Option<UncheckedAccount<'info>>,
+
+    /// Payer's account of the fee mint; required for SPL fees
+    #[account(mut)]
+    pub payer_fee_account: Option<Account<'info, TokenAccount>>,
+
+    /// The fee treasury's associated token account for the fee mint; required for SPL fees
+    #[account(mut)]
+    pub treasury_fee_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected for the transaction's compute unit limit
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, InitiateBundleTransfer<'info>>,
+    destination_chain_id: u64,
+    recipient_address: Vec<u8>,
+    nonce: u64,
+    item_count: u8,
+) -> Result<()> {
+    let cross_chain_config = &mut ctx.accounts.cross_chain_config;
+    let owner = ctx.accounts.owner.key();
+
+    validate_outbound_transfer(
+        cross_chain_config,
+        &ctx.accounts.blocklist,
+        &owner,
+        destination_chain_id,
+        &recipient_address,
+        nonce,
+    )?;
+    validate_credential(cross_chain_config, ctx.accounts.credential.as_deref(), &owner)?;
+    validate_token_gate(
+        cross_chain_config,
+        &ctx.accounts.destination_chain_config,
+        ctx.accounts.gate_token_account.as_deref(),
+        &owner,
+    )?;
+    cross_chain_config.consume_nonce(nonce);
+
+    // Validate bundle shape
+    let item_count = usize::from(item_count);
+    let item_accounts_len = item_count * BundleTransfer::ITEM_ACCOUNTS;
+    require!(
+        item_count > 0
+            && item_count <= BundleTransfer::MAX_ITEMS
+            && ctx.remaining_accounts.len() >= item_accounts_len,
+        UniversalNftError::InvalidBundleSize
+    );
+    let (remaining, endpoint_accounts) = ctx.remaining_accounts.split_at(item_accounts_len);
+    record_outbound_velocity(&mut ctx.accounts.program_state, cross_chain_config, item_count as u64)?;
+    require_compute_budget(
+        &ctx.accounts.instructions_sysvar,
+        calculate_bundle_compute_budget(item_count),
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let mut items = Vec::with_capacity(item_count);
+    let mut collection_items = 0u32;
+    for accounts in remaining.chunks_exact(BundleTransfer::ITEM_ACCOUNTS) {
+        let mint_info = &accounts[0];
+        let token_account = Account::<TokenAccount>::try_from(&accounts[1])?;
+        let mut nft_metadata = Account::<NftMetadata>::try_from(&accounts[2])?;
+
+        // The metadata account must be the canonical PDA for this mint
+        let (expected_metadata, _) = Pubkey::find_program_address(
+            &[NFT_METADATA_SEED, mint_info.key.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(
+            accounts[2].key(),
+            expected_metadata,
+            UniversalNftError::InvalidNftMetadata
+        );
+        let (expected_stake_record, _) = Pubkey::find_program_address(
+            &[STAKE_RECORD_SEED, mint_info.key.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(
+            accounts[3].key(),
+            expected_stake_record,
+            UniversalNftError::InvalidStakeRecord
+        );
+        require!(
+            !items.iter().any(|item: &BundleItem| item.mint == *mint_info.key),
+            UniversalNftError::InvalidBundleSize
+        );
+
+        // Same ownership checks as a single transfer
+        require_keys_eq!(token_account.mint, *mint_info.key, UniversalNftError::InvalidMint);
+        validate_nft_mint_supply(mint_info)?;
+        require_keys_eq!(token_account.owner, owner, UniversalNftError::Unauthorized);
+        require!(token_account.amount >= 1, UniversalNftError::InsufficientTokens);
+        require!(nft_metadata.cross_chain_enabled, UniversalNftError::CrossChainNotEnabled);
+        require!(!nft_metadata.is_locked, UniversalNftError::NftLocked);
+        require!(nft_metadata.child_count == 0, UniversalNftError::HasAttachedChildren);
+        validate_collection_bridging(&nft_metadata, ctx.accounts.collection_config.as_deref())?;
+        if nft_metadata.collection != Pubkey::default() {
+            collection_items += 1;
+        }
+        validate_not_opted_out(&nft_metadata, &ctx.accounts.opt_out_registry)?;
+        require!(
+            nft_metadata.origin_chain_id == ctx.accounts.collection_ledger.origin_chain_id
+                && nft_metadata.origin_contract == ctx.accounts.collection_ledger.origin_contract,
+            UniversalNftError::CollectionMismatch
+        );
+
+        // Lock the NFT
+        nft_metadata.is_locked = true;
+        nft_metadata.current_owner = owner;
+        nft_metadata.exit(ctx.program_id)?;
+        ctx.accounts.collection_ledger.record_outbound()?;
+        let staked_seconds = checkpoint_stake(
+            &accounts[3],
+            &mut ctx.accounts.program_state,
+            *mint_info.key,
+            owner,
+            nonce,
+            now,
+        )?;
+
+        items.push(BundleItem {
+            mint: *mint_info.key,
+            metadata_uri: nft_metadata.metadata_uri.clone(),
+            fallback_uri: nft_metadata.fallback_uri.clone(),
+            staked_seconds,
+        });
+    }
+
+    record_collection_velocity(ctx.accounts.collection_config.as_deref_mut(), collection_items)?;
+    let outbound = OutboundBundle {
+        owner: owner.to_bytes(),
+        destination_chain_id,
+        recipient_address: &recipient_address,
+        nonce,
+        items: items
+            .iter()
+            .map(|item| OutboundBundleItem {
+                mint: item.mint.to_bytes(),
+                metadata_uri: &item.metadata_uri,
+                fallback_uri: item.fallback_uri.as_deref().unwrap_or_default(),
+                staked_seconds: item.staked_seconds,
+            })
+            .collect(),
+    };
+    validate_payload_size(&ctx.accounts.destination_chain_config, outbound.encoded_len())?;
+    let fee = ctx.accounts.destination_chain_config.fee;
+    let fee_paid = collect_bridge_fee(
+        &fee,
+        items.len() as u64,
+        FeeAccounts {
+            payer: &ctx.accounts.payer.to_account_info(),
+            fee_treasury: ctx.accounts.fee_treasury.as_deref(),
+            payer_fee_account: ctx.accounts.payer_fee_account.as_ref(),
+            treasury_fee_account: ctx.accounts.treasury_fee_account.as_ref(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+        },
+    )?;
+    // Held back from withdrawals until it is refunded or earned
+    ctx.accounts.fee_reserve.reserve(ctx.bumps.fee_reserve, fee.mint, fee_paid)?;
+
+    // Create bundle record
+    let bundle_record = &mut ctx.accounts.bundle_record;
+    bundle_record.original_owner = owner;
+    bundle_record.destination_chain_id = destination_chain_id;
+    bundle_record.recipient_address = recipient_address.clone();
+    bundle_record.mints = items.iter().map(|item| item.mint).collect();
+    bundle_record.nonce = nonce;
+    bundle_record.timestamp = now;
+    bundle_record.status = 0; // Pending
+    bundle_record.bump = ctx.bumps.bundle_record;
+    bundle_record.fee_payer = ctx.accounts.payer.key();
+    bundle_record.fee_paid = fee_paid;
+    bundle_record.fee_mint = fee.mint;
+    bundle_record.gas_budget = fee
+        .gas_budget
+        .checked_mul(items.len() as u64)
+        .ok_or(UniversalNftError::ArithmeticOverflow)?;
+    bundle_record.gas_spent = 0;
+    bundle_record.fee_refunded = false;
+    bundle_record.destination_tx_hash = [0u8; 32];
+    bundle_record.completed_at = 0;
+    bundle_record.staked_seconds = items.iter().map(|item| item.staked_seconds).collect();
+    ctx.accounts.owner_transfer_index.record(
+        owner,
+        ctx.bumps.owner_transfer_index,
+        Pubkey::default(),
+        nonce,
+    );
+    // Counted once, under the default mint like the owner's index
+    ctx.accounts.pending_transfers.record_created(
+        ctx.bumps.pending_transfers,
+        Pubkey::default(),
+        nonce,
+        now,
+    );
+
+    // Update program statistics
+    let program_state = &mut ctx.accounts.program_state;
+    program_state.cross_chain_transfers = program_state
+        .cross_chain_transfers
+        .checked_add(items.len() as u64)
+        .ok_or(UniversalNftError::ArithmeticOverflow)?;
+
+    // Hand the bundle to the destination chain's transport
+    let adapter = Adapter::for_chain(
+        &ctx.accounts.destination_chain_config,
+        &ctx.accounts.cross_chain_config,
+        AdapterAccounts {
+            endpoint_accounts,
+            ..Default::default()
+        },
+    );
+    let message_hash = adapter.outbound_message_hash(&outbound);
+    adapter.dispatch_outbound(&outbound, &message_hash)?;
+
+    msg!("Bundle transfer initiated with {} NFTs", items.len());
+    msg!("Destination chain: {}, nonce: {}", destination_chain_id, nonce);
+
+    // Emit a single event carrying every item for the gateway to pick up
+    emit!(BundleTransferEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        owner,
+        destination_chain_id,
+        recipient_address,
+        items,
+        nonce,
+        message_hash,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BundleItem {
+    pub mint: Pubkey,
+    pub metadata_uri: String,
+    pub fallback_uri: Option<String>,
+    pub staked_seconds: u64, // Unclaimed staking time checkpointed on the way out
+}
+
+#[event]
+pub struct BundleTransferEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub owner: Pubkey,
+    pub destination_chain_id: u64,
+    pub recipient_address: Vec<u8>,
+    pub items: Vec<BundleItem>,
+    pub nonce: u64,
+    pub message_hash: [u8; 32],
+    pub timestamp: i64,
+}
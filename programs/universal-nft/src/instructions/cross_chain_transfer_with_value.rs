@@ -0,0 +1,410 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Mint};
+use crate::state::{ProgramState, CrossChainConfig, NftMetadata, CrossChainTransfer, FeeReserve, Blocklist, ChainConfig, CollectionLedger, CollectionConfig, OptOutRegistry, OwnerTransferIndex, PendingTransfers, LocalizedMetadata};
+use crate::error::UniversalNftError;
+use crate::constants::{BLOCKLIST_SEED, CHAIN_CONFIG_SEED, COLLECTION_CONFIG_SEED, COLLECTION_LEDGER_SEED, CROSS_CHAIN_CONFIG_SEED, CROSS_CHAIN_TRANSFER_SEED, FEE_RESERVE_SEED, LOCALIZED_METADATA_SEED, NFT_METADATA_SEED, OPT_OUT_REGISTRY_SEED, OWNER_TRANSFER_INDEX_SEED, PENDING_TRANSFERS_SEED, PROGRAM_STATE_SEED, STAKE_RECORD_SEED, VALUE_ESCROW_SEED};
+use crate::utils::validation::{validate_outbound_transfer, validate_nft_mint_supply, validate_payload_size, validate_collection_bridging, validate_not_opted_out, record_outbound_velocity, record_collection_velocity, validate_credential, validate_token_gate};
+use crate::utils::fees::{collect_bridge_fee, FeeAccounts};
+use crate::utils::staking::checkpoint_stake;
+use crate::adapters::{Adapter, AdapterAccounts, BridgeAdapter};
+use crate::instructions::set_localized_uris::LocalizedUrisTransferredEvent;
+use universal_nft_messages::{LocalizedUris, OutboundPayload, OutboundTransfer, OutboundTransferWithValue};
+
+#[derive(Accounts)]
+#[instruction(destination_chain_id: u64, recipient_address: Vec<u8>, nonce: u64)]
+pub struct InitiateCrossChainTransferWithValue<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = !program_state.is_sunset @ UniversalNftError::ProgramSunset
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+
+    #[account(
+        mut,
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
+        bump = cross_chain_config.bump,
+        constraint = !cross_chain_config.is_paused @ UniversalNftError::CrossChainPaused
+    )]
+    pub cross_chain_config: Box<Account<'info, CrossChainConfig>>,
+
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Box<Account<'info, Blocklist>>,
+
+    #[account(
+        seeds = [OPT_OUT_REGISTRY_SEED],
+        bump = opt_out_registry.bump
+    )]
+    pub opt_out_registry: Box<Account<'info, OptOutRegistry>>,
+
+    #[account(
+        seeds = [CHAIN_CONFIG_SEED, destination_chain_id.to_le_bytes().as_ref()],
+        bump = destination_chain_config.bump,
+        constraint = !destination_chain_config.is_paused @ UniversalNftError::ChainPaused
+    )]
+    pub destination_chain_config: Box<Account<'info, ChainConfig>>,
+
+    #[account(
+        mut,
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump,
+        constraint = nft_metadata.cross_chain_enabled @ UniversalNftError::CrossChainNotEnabled,
+        constraint = !nft_metadata.is_locked @ UniversalNftError::NftLocked,
+        constraint = nft_metadata.child_count == 0 @ UniversalNftError::HasAttachedChildren
+    )]
+    pub nft_metadata: Box<Account<'info, NftMetadata>>,
+
+    /// Required when the NFT belongs to a collection
+    #[account(
+        mut,
+        seeds = [COLLECTION_CONFIG_SEED, nft_metadata.collection.as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Option<Box<Account<'info, CollectionConfig>>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + CollectionLedger::INIT_SPACE,
+        seeds = [
+            COLLECTION_LEDGER_SEED,
+            nft_metadata.origin_chain_id.to_le_bytes().as_ref(),
+            nft_metadata.origin_contract.as_slice()
+        ],
+        bump
+    )]
+    pub collection_ledger: Box<Account<'info, CollectionLedger>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + CrossChainTransfer::INIT_SPACE,
+        seeds = [CROSS_CHAIN_TRANSFER_SEED, mint.key().as_ref(), nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub transfer_record: Box<Account<'info, CrossChainTransfer>>,
+
+    /// Lists the owner's recent transfers
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + OwnerTransferIndex::INIT_SPACE,
+        seeds = [OWNER_TRANSFER_INDEX_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub owner_transfer_index: Box<Account<'info, OwnerTransferIndex>>,
+
+    /// Outbound backlog for monitoring
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PendingTransfers::INIT_SPACE,
+        seeds = [PENDING_TRANSFERS_SEED],
+        bump
+    )]
+    pub pending_transfers: Box<Account<'info, PendingTransfers>>,
+
+    /// CHECK: Mint account validated by token account constraint
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = token_account.mint == mint.key(),
+        constraint = token_account.owner == owner.key(),
+        constraint = token_account.amount >= 1 @ UniversalNftError::InsufficientTokens
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// SPL mint of the fungible value sent alongside the NFT
+    pub value_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = owner_value_account.mint == value_mint.key() @ UniversalNftError::InvalidMint,
+        constraint = owner_value_account.owner == owner.key() @ UniversalNftError::Unauthorized
+    )]
+    pub owner_value_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = value_mint,
+        token::authority = value_escrow,
+        seeds = [VALUE_ESCROW_SEED, transfer_record.key().as_ref()],
+        bump
+    )]
+    pub value_escrow: Box<Account<'info, TokenAccount>>,
+
+    pub owner: Signer<'info>,
+
+    /// Funds the transfer record, the value escrow and any bridge fee
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Checked against `CrossChainConfig.credential_requirement`; required only when one is set
+    pub credential: Option<UncheckedAccount<'info>>,
+
+    /// Owner's account of the gate mint; required only when a token gate applies
+    pub gate_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: The NFT's stake PDA, checkpointed by the handler if it exists;
+    /// its unclaimed time travels in the payload
+    #[account(mut, seeds = [STAKE_RECORD_SEED, mint.key().as_ref()], bump)]
+    pub stake_record: UncheckedAccount<'info>,
+
+    /// The NFT's localized metadata URIs, if it has any; they travel in the payload
+    #[account(
+        seeds = [LOCALIZED_METADATA_SEED, mint.key().as_ref()],
+        bump = localized_metadata.bump
+    )]
+    pub localized_metadata: Option<Box<Account<'info, LocalizedMetadata>>>,
+
+    /// CHECK: Fee treasury PDA, checked by the handler; required for lamport fees
+    #[account(mut)]
+    pub fee_treasury: Option<UncheckedAccount<'info>>,
+
+    /// Payer's account of the fee mint; required for SPL fees
+    #[account(mut)]
+    pub payer_fee_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// The fee treasury's associated token account for the fee mint; required for SPL fees
+    #[account(mut)]
+    pub treasury_fee_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Refundable fees the treasury must keep
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + FeeReserve::INIT_SPACE,
+        seeds = [FEE_RESERVE_SEED],
+        bump
+    )]
+    pub fee_reserve: Box<Account<'info, FeeReserve>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler(
+    ctx: Context<InitiateCrossChainTransferWithValue>,
+    destination_chain_id: u64,
+    recipient_address: Vec<u8>,
+    nonce: u64,
+    value_amount: u64,
+) -> Result<()> {
+    validate_outbound_transfer(
+        &ctx.accounts.cross_chain_config,
+        &ctx.accounts.blocklist,
+        &ctx.accounts.owner.key(),
+        destination_chain_id,
+        &recipient_address,
+        nonce,
+    )?;
+    validate_nft_mint_supply(&ctx.accounts.mint)?;
+    ctx.accounts.cross_chain_config.consume_nonce(nonce);
+    record_outbound_velocity(&mut ctx.accounts.program_state, &mut ctx.accounts.cross_chain_config, 1)?;
+    validate_collection_bridging(
+        &ctx.accounts.nft_metadata,
+        ctx.accounts.collection_config.as_deref().map(|config| &**config),
+    )?;
+    record_collection_velocity(
+        ctx.accounts.collection_config.as_deref_mut().map(|config| &mut **config),
+        1,
+    )?;
+    validate_not_opted_out(&ctx.accounts.nft_metadata, &ctx.accounts.opt_out_registry)?;
+    validate_credential(
+        &ctx.accounts.cross_chain_config,
+        ctx.accounts.credential.as_deref(),
+        &ctx.accounts.owner.key(),
+    )?;
+    validate_token_gate(
+        &ctx.accounts.cross_chain_config,
+        &ctx.accounts.destination_chain_config,
+        ctx.accounts.gate_token_account.as_deref().map(|account| &**account),
+        &ctx.accounts.owner.key(),
+    )?;
+    require!(value_amount > 0, UniversalNftError::InsufficientTokens);
+    let fee = ctx.accounts.destination_chain_config.fee;
+    let fee_paid = collect_bridge_fee(
+        &fee,
+        1,
+        FeeAccounts {
+            payer: &ctx.accounts.payer.to_account_info(),
+            fee_treasury: ctx.accounts.fee_treasury.as_deref(),
+            payer_fee_account: ctx.accounts.payer_fee_account.as_deref(),
+            treasury_fee_account: ctx.accounts.treasury_fee_account.as_deref(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+        },
+    )?;
+    // Held back from withdrawals until it is refunded or earned
+    ctx.accounts.fee_reserve.reserve(ctx.bumps.fee_reserve, fee.mint, fee_paid)?;
+
+    // Escrow the fungible value until the transfer completes or reverts
+    let cpi_accounts = token::Transfer {
+        from: ctx.accounts.owner_value_account.to_account_info(),
+        to: ctx.accounts.value_escrow.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, value_amount)?;
+
+    // Lock the NFT
+    let nft_metadata = &mut ctx.accounts.nft_metadata;
+    nft_metadata.is_locked = true;
+    nft_metadata.current_owner = ctx.accounts.owner.key();
+
+    // Update the collection's supply ledger
+    let collection_ledger = &mut ctx.accounts.collection_ledger;
+    collection_ledger.ensure_initialized(
+        nft_metadata.origin_chain_id,
+        &nft_metadata.origin_contract,
+        ctx.bumps.collection_ledger,
+    );
+    collection_ledger.record_outbound()?;
+
+    // Create transfer record
+    let timestamp = Clock::get()?.unix_timestamp;
+    let transfer_record = &mut ctx.accounts.transfer_record;
+    transfer_record.mint = ctx.accounts.mint.key();
+    transfer_record.original_owner = ctx.accounts.owner.key();
+    transfer_record.destination_chain_id = destination_chain_id;
+    transfer_record.recipient_address = recipient_address.clone();
+    transfer_record.nonce = nonce;
+    transfer_record.timestamp = timestamp;
+    transfer_record.status = 0; // Pending
+    transfer_record.value_mint = ctx.accounts.value_mint.key();
+    transfer_record.value_amount = value_amount;
+    transfer_record.destination_tx_hash = [0u8; 32];
+    transfer_record.completed_at = 0;
+    transfer_record.fee_payer = ctx.accounts.payer.key();
+    transfer_record.fee_mint = fee.mint;
+    transfer_record.fee_paid = fee_paid;
+    transfer_record.gas_budget = fee.gas_budget;
+    transfer_record.gas_spent = 0;
+    transfer_record.fee_refunded = false;
+    transfer_record.bump = ctx.bumps.transfer_record;
+    ctx.accounts.owner_transfer_index.record(
+        ctx.accounts.owner.key(),
+        ctx.bumps.owner_transfer_index,
+        ctx.accounts.mint.key(),
+        nonce,
+    );
+    ctx.accounts.pending_transfers.record_created(
+        ctx.bumps.pending_transfers,
+        ctx.accounts.mint.key(),
+        nonce,
+        transfer_record.timestamp,
+    );
+
+    // Update program statistics
+    let program_state = &mut ctx.accounts.program_state;
+    program_state.cross_chain_transfers = program_state
+        .cross_chain_transfers
+        .checked_add(1)
+        .ok_or(UniversalNftError::ArithmeticOverflow)?;
+
+    // Suspend the NFT's stake and carry its unclaimed time to the destination
+    let staked_seconds = checkpoint_stake(
+        &ctx.accounts.stake_record,
+        &mut ctx.accounts.program_state,
+        ctx.accounts.mint.key(),
+        ctx.accounts.owner.key(),
+        nonce,
+        timestamp,
+    )?;
+    ctx.accounts.transfer_record.staked_seconds = staked_seconds;
+
+    let localized_uris = match ctx.accounts.localized_metadata.as_deref() {
+        Some(localized_metadata) if !localized_metadata.entries.is_empty() => {
+            emit!(LocalizedUrisTransferredEvent {
+                schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+                sequence: ctx.accounts.program_state.next_event_sequence(),
+                mint: ctx.accounts.mint.key(),
+                nonce,
+                entries: localized_metadata.entries.clone(),
+                timestamp,
+            });
+            LocalizedUris::encode(
+                localized_metadata
+                    .entries
+                    .iter()
+                    .map(|entry| (entry.locale.as_str(), entry.uri.as_str())),
+            )
+        }
+        _ => Vec::new(),
+    };
+
+    // Hand the transfer to the destination chain's transport
+    let adapter = Adapter::for_chain(
+        &ctx.accounts.destination_chain_config,
+        &ctx.accounts.cross_chain_config,
+        AdapterAccounts {
+            endpoint_accounts: ctx.remaining_accounts,
+            ..Default::default()
+        },
+    );
+    let outbound = OutboundTransferWithValue {
+        transfer: OutboundTransfer {
+            mint: ctx.accounts.mint.key().to_bytes(),
+            owner: ctx.accounts.owner.key().to_bytes(),
+            destination_chain_id,
+            recipient_address: &recipient_address,
+            nonce,
+            metadata_uri: &ctx.accounts.nft_metadata.metadata_uri,
+            fallback_uri: ctx.accounts.nft_metadata.fallback_uri.as_deref().unwrap_or_default(),
+            staked_seconds,
+            localized_uris: LocalizedUris::from_encoded(&localized_uris)
+                .map_err(|_| UniversalNftError::InvalidLocalizedUris)?,
+        },
+        value_mint: ctx.accounts.value_mint.key().to_bytes(),
+        value_amount,
+    };
+    validate_payload_size(&ctx.accounts.destination_chain_config, outbound.encoded_len())?;
+    let message_hash = adapter.outbound_message_hash(&outbound);
+    adapter.dispatch_outbound(&outbound, &message_hash)?;
+
+    // One event carries both the NFT and the attached value
+    emit!(CrossChainValueTransferEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint: ctx.accounts.mint.key(),
+        owner: ctx.accounts.owner.key(),
+        destination_chain_id,
+        recipient_address,
+        nonce,
+        message_hash,
+        metadata_uri: ctx.accounts.nft_metadata.metadata_uri.clone(),
+        fallback_uri: ctx.accounts.nft_metadata.fallback_uri.clone(),
+        value_mint: ctx.accounts.value_mint.key(),
+        value_amount,
+        timestamp,
+    });
+
+    msg!("Cross-chain transfer with value initiated for mint: {}", ctx.accounts.mint.key());
+    msg!("Attached {} of {}", value_amount, ctx.accounts.value_mint.key());
+
+    Ok(())
+}
+
+#[event]
+pub struct CrossChainValueTransferEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub destination_chain_id: u64,
+    pub recipient_address: Vec<u8>,
+    pub nonce: u64,
+    pub message_hash: [u8; 32],
+    pub metadata_uri: String,
+    pub fallback_uri: Option<String>,
+    pub value_mint: Pubkey,
+    pub value_amount: u64,
+    pub timestamp: i64,
+}
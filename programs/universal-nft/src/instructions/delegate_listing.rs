@@ -0,0 +1,355 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Token, TokenAccount, Mint};
+use crate::state::{ProgramState, NftMetadata, DelegateListing};
+use crate::error::UniversalNftError;
+use crate::constants::{DELEGATE_LISTING_SEED, FREEZE_AUTHORITY_SEED, NFT_METADATA_SEED, PROGRAM_STATE_SEED};
+use crate::utils::royalties::{creator_payouts, load_collection_royalty, pay_from_buyer};
+
+/// Lists an NFT without escrow: the listing becomes the delegate of the
+/// seller's token account, which is frozen until the NFT sells or the seller
+/// cancels. Only mints whose freeze authority is the program's qualify.
+#[derive(Accounts)]
+pub struct ListWithDelegate<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = !program_state.is_sunset @ UniversalNftError::ProgramSunset
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump,
+        constraint = !nft_metadata.is_locked @ UniversalNftError::NftLocked
+    )]
+    pub nft_metadata: Box<Account<'info, NftMetadata>>,
+
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + DelegateListing::INIT_SPACE,
+        seeds = [DELEGATE_LISTING_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub delegate_listing: Box<Account<'info, DelegateListing>>,
+
+    #[account(
+        constraint = mint.freeze_authority == COption::Some(freeze_authority.key()) @ UniversalNftError::MintNotFreezable
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: PDA holding the freeze authority of the NFTs the program mints
+    #[account(seeds = [FREEZE_AUTHORITY_SEED], bump)]
+    pub freeze_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = token_account.mint == mint.key() @ UniversalNftError::InvalidMint,
+        constraint = token_account.owner == seller.key() @ UniversalNftError::Unauthorized,
+        constraint = token_account.amount >= 1 @ UniversalNftError::InsufficientTokens
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Holder of the NFT; pays for the listing and gets its rent back
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Buys a delegate-listed NFT straight out of the seller's wallet. Royalty
+/// creators are passed in remaining accounts, in the collection's creator order.
+#[derive(Accounts)]
+pub struct BuyDelegateListing<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump,
+        constraint = !nft_metadata.is_locked @ UniversalNftError::NftLocked
+    )]
+    pub nft_metadata: Box<Account<'info, NftMetadata>>,
+
+    /// CHECK: Must be the royalty PDA of the NFT's collection, created or not;
+    /// checked by `load_collection_royalty`. Omitted for NFTs outside a collection.
+    pub collection_royalty: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [DELEGATE_LISTING_SEED, mint.key().as_ref()],
+        bump = delegate_listing.bump,
+        has_one = seller,
+        has_one = mint,
+        has_one = token_account
+    )]
+    pub delegate_listing: Box<Account<'info, DelegateListing>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: PDA holding the freeze authority of the NFTs the program mints
+    #[account(seeds = [FREEZE_AUTHORITY_SEED], bump)]
+    pub freeze_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+    )]
+    pub buyer_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The listing's seller, checked by `has_one`; receives the proceeds and rent
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Thaws the seller's token account and revokes the listing's delegation
+#[derive(Accounts)]
+pub struct CancelDelegateListing<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [DELEGATE_LISTING_SEED, mint.key().as_ref()],
+        bump = delegate_listing.bump,
+        has_one = seller,
+        has_one = mint,
+        has_one = token_account
+    )]
+    pub delegate_listing: Box<Account<'info, DelegateListing>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: PDA holding the freeze authority of the NFTs the program mints
+    #[account(seeds = [FREEZE_AUTHORITY_SEED], bump)]
+    pub freeze_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// List the NFT for `price` lamports. It stays in the seller's token account,
+/// frozen, with the listing as its delegate.
+pub fn list_handler(ctx: Context<ListWithDelegate>, price: u64) -> Result<()> {
+    require!(price > 0, UniversalNftError::InvalidListingPrice);
+
+    let now = Clock::get()?.unix_timestamp;
+    let delegate_listing = &mut ctx.accounts.delegate_listing;
+    delegate_listing.seller = ctx.accounts.seller.key();
+    delegate_listing.mint = ctx.accounts.mint.key();
+    delegate_listing.token_account = ctx.accounts.token_account.key();
+    delegate_listing.price = price;
+    delegate_listing.created_at = now;
+    delegate_listing.bump = ctx.bumps.delegate_listing;
+
+    let cpi_accounts = token::Approve {
+        to: ctx.accounts.token_account.to_account_info(),
+        delegate: delegate_listing.to_account_info(),
+        authority: ctx.accounts.seller.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::approve(cpi_ctx, 1)?;
+
+    let freeze_bump = ctx.bumps.freeze_authority;
+    let signer_seeds: &[&[&[u8]]] = &[&[FREEZE_AUTHORITY_SEED, &[freeze_bump]]];
+    let cpi_accounts = token::FreezeAccount {
+        account: ctx.accounts.token_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        authority: ctx.accounts.freeze_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::freeze_account(cpi_ctx)?;
+
+    emit!(DelegateListingCreatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint: delegate_listing.mint,
+        seller: delegate_listing.seller,
+        price,
+        timestamp: now,
+    });
+
+    msg!("Listed {} for {} lamports without escrow", delegate_listing.mint, price);
+
+    Ok(())
+}
+
+/// Buy the NFT at the listing's price, if it is at most `max_price`. The
+/// collection's creators get their royalty and the seller the rest.
+pub fn buy_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BuyDelegateListing<'info>>,
+    max_price: u64,
+) -> Result<()> {
+    let price = ctx.accounts.delegate_listing.price;
+    require!(price <= max_price, UniversalNftError::ListingPriceTooHigh);
+
+    let royalty = load_collection_royalty(
+        &ctx.accounts.nft_metadata.collection,
+        ctx.accounts.collection_royalty.as_ref().map(|account| account.as_ref()),
+    )?;
+    let (mut payouts, royalty_paid) = creator_payouts(royalty.as_ref(), price, ctx.remaining_accounts)?;
+    payouts.push((ctx.accounts.seller.to_account_info(), price - royalty_paid));
+    pay_from_buyer(
+        &ctx.accounts.buyer.to_account_info(),
+        payouts,
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    thaw(
+        &ctx.accounts.token_account,
+        &ctx.accounts.mint,
+        &ctx.accounts.freeze_authority,
+        ctx.bumps.freeze_authority,
+        &ctx.accounts.token_program,
+    )?;
+
+    // The listing moves the NFT as the account's delegate
+    let mint = ctx.accounts.mint.key();
+    let listing_bump = ctx.accounts.delegate_listing.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[DELEGATE_LISTING_SEED, mint.as_ref(), &[listing_bump]]];
+    let cpi_accounts = token::Transfer {
+        from: ctx.accounts.token_account.to_account_info(),
+        to: ctx.accounts.buyer_token_account.to_account_info(),
+        authority: ctx.accounts.delegate_listing.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(cpi_ctx, 1)?;
+
+    let nft_metadata = &mut ctx.accounts.nft_metadata;
+    nft_metadata.current_owner = ctx.accounts.buyer.key();
+
+    emit!(DelegateListingSoldEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint,
+        seller: ctx.accounts.seller.key(),
+        buyer: ctx.accounts.buyer.key(),
+        price,
+        royalty_paid,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Delegate listing for {} sold at {} lamports ({} in royalties)", mint, price, royalty_paid);
+
+    Ok(())
+}
+
+pub fn cancel_handler(ctx: Context<CancelDelegateListing>) -> Result<()> {
+    thaw(
+        &ctx.accounts.token_account,
+        &ctx.accounts.mint,
+        &ctx.accounts.freeze_authority,
+        ctx.bumps.freeze_authority,
+        &ctx.accounts.token_program,
+    )?;
+
+    let cpi_accounts = token::Revoke {
+        source: ctx.accounts.token_account.to_account_info(),
+        authority: ctx.accounts.seller.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::revoke(cpi_ctx)?;
+
+    emit!(DelegateListingCancelledEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint: ctx.accounts.mint.key(),
+        seller: ctx.accounts.seller.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Delegate listing for {} cancelled", ctx.accounts.mint.key());
+
+    Ok(())
+}
+
+fn thaw<'info>(
+    token_account: &Account<'info, TokenAccount>,
+    mint: &Account<'info, Mint>,
+    freeze_authority: &AccountInfo<'info>,
+    freeze_bump: u8,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[FREEZE_AUTHORITY_SEED, &[freeze_bump]]];
+    let cpi_accounts = token::ThawAccount {
+        account: token_account.to_account_info(),
+        mint: mint.to_account_info(),
+        authority: freeze_authority.clone(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+    token::thaw_account(cpi_ctx)
+}
+
+#[event]
+pub struct DelegateListingCreatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub seller: Pubkey,
+    pub price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegateListingSoldEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    pub royalty_paid: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegateListingCancelledEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub seller: Pubkey,
+    pub timestamp: i64,
+}
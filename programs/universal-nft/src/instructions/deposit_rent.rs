@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::{ProgramState, RentDeposit};
+use crate::error::UniversalNftError;
+use crate::constants::{PROGRAM_STATE_SEED, RENT_DEPOSIT_SEED};
+
+#[derive(Accounts)]
+pub struct DepositRent<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + RentDeposit::INIT_SPACE,
+        seeds = [RENT_DEPOSIT_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub rent_deposit: Account<'info, RentDeposit>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Open or top up the signer's rent deposit with `amount` lamports
+pub fn handler(ctx: Context<DepositRent>, amount: u64) -> Result<()> {
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.owner.to_account_info(),
+        to: ctx.accounts.rent_deposit.to_account_info(),
+    };
+    system_program::transfer(
+        CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts),
+        amount,
+    )?;
+
+    let rent_deposit = &mut ctx.accounts.rent_deposit;
+    if rent_deposit.owner == Pubkey::default() {
+        rent_deposit.owner = ctx.accounts.owner.key();
+        rent_deposit.bump = ctx.bumps.rent_deposit;
+    }
+    rent_deposit.balance = rent_deposit
+        .balance
+        .checked_add(amount)
+        .ok_or(UniversalNftError::ArithmeticOverflow)?;
+
+    emit!(RentDepositUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        owner: rent_deposit.owner,
+        balance: rent_deposit.balance,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Rent deposit of {} now holds {} lamports", rent_deposit.owner, rent_deposit.balance);
+
+    Ok(())
+}
+
+#[event]
+pub struct RentDepositUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub owner: Pubkey,
+    pub balance: u64,
+    pub timestamp: i64,
+}
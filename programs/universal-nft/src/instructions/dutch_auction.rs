@@ -0,0 +1,373 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Token, TokenAccount, Mint};
+use crate::state::{ProgramState, NftMetadata, DutchAuction};
+use crate::error::UniversalNftError;
+use crate::constants::{AUCTION_ESCROW_SEED, DUTCH_AUCTION_SEED, NFT_METADATA_SEED, PROGRAM_STATE_SEED};
+use crate::utils::royalties::{creator_payouts, load_collection_royalty, pay_from_buyer};
+use crate::utils::validation::validate_nft_mint_supply;
+
+/// Lists an NFT at a falling price, escrowing it until it sells or the seller
+/// cancels. A bridged-out NFT can't be listed.
+#[derive(Accounts)]
+pub struct CreateDutchAuction<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = !program_state.is_sunset @ UniversalNftError::ProgramSunset
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump,
+        constraint = !nft_metadata.is_locked @ UniversalNftError::NftLocked
+    )]
+    pub nft_metadata: Box<Account<'info, NftMetadata>>,
+
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + DutchAuction::INIT_SPACE,
+        seeds = [DUTCH_AUCTION_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub dutch_auction: Box<Account<'info, DutchAuction>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = seller_token_account.mint == mint.key() @ UniversalNftError::InvalidMint,
+        constraint = seller_token_account.owner == seller.key() @ UniversalNftError::Unauthorized,
+        constraint = seller_token_account.amount >= 1 @ UniversalNftError::InsufficientTokens
+    )]
+    pub seller_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Holds the NFT while it is listed; its own authority, like the child escrow
+    #[account(
+        init,
+        payer = seller,
+        token::mint = mint,
+        token::authority = auction_escrow,
+        seeds = [AUCTION_ESCROW_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub auction_escrow: Box<Account<'info, TokenAccount>>,
+
+    /// Holder of the NFT; pays for the auction and escrow, and gets their rent back
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Buys a listed NFT at its current price. Royalty creators are passed in
+/// remaining accounts, in the collection's creator order.
+#[derive(Accounts)]
+pub struct BuyDutchAuction<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump,
+        constraint = !nft_metadata.is_locked @ UniversalNftError::NftLocked
+    )]
+    pub nft_metadata: Box<Account<'info, NftMetadata>>,
+
+    /// CHECK: Must be the royalty PDA of the NFT's collection, created or not;
+    /// checked by `load_collection_royalty`. Omitted for NFTs outside a collection.
+    pub collection_royalty: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [DUTCH_AUCTION_SEED, mint.key().as_ref()],
+        bump = dutch_auction.bump,
+        has_one = seller,
+        has_one = mint
+    )]
+    pub dutch_auction: Box<Account<'info, DutchAuction>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [AUCTION_ESCROW_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub auction_escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+    )]
+    pub buyer_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The auction's seller, checked by `has_one`; receives the proceeds and rent
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Returns an unsold NFT to its seller
+#[derive(Accounts)]
+pub struct CancelDutchAuction<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [DUTCH_AUCTION_SEED, mint.key().as_ref()],
+        bump = dutch_auction.bump,
+        has_one = seller,
+        has_one = mint
+    )]
+    pub dutch_auction: Box<Account<'info, DutchAuction>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [AUCTION_ESCROW_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub auction_escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = mint,
+        associated_token::authority = seller,
+    )]
+    pub seller_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// List the NFT from `start_time` (now, if earlier) at `start_price`, falling
+/// by `decay_per_second` lamports each second until it reaches `floor_price`.
+pub fn create_handler(
+    ctx: Context<CreateDutchAuction>,
+    start_price: u64,
+    floor_price: u64,
+    decay_per_second: u64,
+    start_time: i64,
+) -> Result<()> {
+    require!(
+        floor_price > 0 && start_price >= floor_price,
+        UniversalNftError::InvalidAuctionTerms
+    );
+    validate_nft_mint_supply(&ctx.accounts.mint.to_account_info())?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let dutch_auction = &mut ctx.accounts.dutch_auction;
+    dutch_auction.seller = ctx.accounts.seller.key();
+    dutch_auction.mint = ctx.accounts.mint.key();
+    dutch_auction.start_price = start_price;
+    dutch_auction.floor_price = floor_price;
+    dutch_auction.decay_per_second = decay_per_second;
+    dutch_auction.start_time = start_time.max(now);
+    dutch_auction.created_at = now;
+    dutch_auction.bump = ctx.bumps.dutch_auction;
+
+    let cpi_accounts = token::Transfer {
+        from: ctx.accounts.seller_token_account.to_account_info(),
+        to: ctx.accounts.auction_escrow.to_account_info(),
+        authority: ctx.accounts.seller.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, 1)?;
+
+    emit!(DutchAuctionCreatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint: dutch_auction.mint,
+        seller: dutch_auction.seller,
+        start_price,
+        floor_price,
+        decay_per_second,
+        start_time: dutch_auction.start_time,
+        timestamp: now,
+    });
+
+    msg!(
+        "Dutch auction for {}: {} falling to {} lamports",
+        dutch_auction.mint,
+        start_price,
+        floor_price
+    );
+
+    Ok(())
+}
+
+/// Buy the NFT at the auction's current price, if it is at most `max_price`.
+/// The collection's creators get their royalty and the seller the rest.
+pub fn buy_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BuyDutchAuction<'info>>,
+    max_price: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let dutch_auction = &ctx.accounts.dutch_auction;
+    require!(now >= dutch_auction.start_time, UniversalNftError::AuctionNotStarted);
+    let price = dutch_auction.price_at(now);
+    require!(price <= max_price, UniversalNftError::AuctionPriceTooHigh);
+
+    let royalty = load_collection_royalty(
+        &ctx.accounts.nft_metadata.collection,
+        ctx.accounts.collection_royalty.as_ref().map(|account| account.as_ref()),
+    )?;
+    let (mut payouts, royalty_paid) = creator_payouts(royalty.as_ref(), price, ctx.remaining_accounts)?;
+    payouts.push((ctx.accounts.seller.to_account_info(), price - royalty_paid));
+    pay_from_buyer(
+        &ctx.accounts.buyer.to_account_info(),
+        payouts,
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    release_escrow(
+        &ctx.accounts.mint.key(),
+        ctx.bumps.auction_escrow,
+        &ctx.accounts.auction_escrow,
+        &ctx.accounts.buyer_token_account,
+        &ctx.accounts.seller,
+        &ctx.accounts.token_program,
+    )?;
+
+    let nft_metadata = &mut ctx.accounts.nft_metadata;
+    nft_metadata.current_owner = ctx.accounts.buyer.key();
+
+    emit!(DutchAuctionSettledEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint: ctx.accounts.mint.key(),
+        seller: ctx.accounts.seller.key(),
+        buyer: ctx.accounts.buyer.key(),
+        price,
+        royalty_paid,
+        timestamp: now,
+    });
+
+    msg!(
+        "Dutch auction for {} settled at {} lamports ({} in royalties)",
+        ctx.accounts.mint.key(),
+        price,
+        royalty_paid
+    );
+
+    Ok(())
+}
+
+pub fn cancel_handler(ctx: Context<CancelDutchAuction>) -> Result<()> {
+    release_escrow(
+        &ctx.accounts.mint.key(),
+        ctx.bumps.auction_escrow,
+        &ctx.accounts.auction_escrow,
+        &ctx.accounts.seller_token_account,
+        &ctx.accounts.seller,
+        &ctx.accounts.token_program,
+    )?;
+
+    emit!(DutchAuctionCancelledEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint: ctx.accounts.mint.key(),
+        seller: ctx.accounts.seller.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Dutch auction for {} cancelled", ctx.accounts.mint.key());
+
+    Ok(())
+}
+
+/// Move the escrowed NFT to `to` and close the escrow, refunding its rent to the seller
+fn release_escrow<'info>(
+    mint: &Pubkey,
+    escrow_bump: u8,
+    escrow: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    seller: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[AUCTION_ESCROW_SEED, mint.as_ref(), &[escrow_bump]]];
+
+    let cpi_accounts = token::Transfer {
+        from: escrow.to_account_info(),
+        to: to.to_account_info(),
+        authority: escrow.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+    token::transfer(cpi_ctx, 1)?;
+
+    let cpi_accounts = token::CloseAccount {
+        account: escrow.to_account_info(),
+        destination: seller.clone(),
+        authority: escrow.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+    token::close_account(cpi_ctx)
+}
+
+#[event]
+pub struct DutchAuctionCreatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub seller: Pubkey,
+    pub start_price: u64,
+    pub floor_price: u64,
+    pub decay_per_second: u64,
+    pub start_time: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DutchAuctionSettledEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    pub royalty_paid: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DutchAuctionCancelledEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub seller: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,327 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{Token, TokenAccount, Mint};
+use crate::state::{ProgramState, CrossChainConfig, ChainConfig, NftMetadata, CrossChainReceipt, Blocklist, CollectionLedger, ForeignCollection, StagedInbound, Relayer, FeeReserve};
+use crate::error::UniversalNftError;
+use crate::constants::{BLOCKLIST_SEED, CHAIN_CONFIG_SEED, COLLECTION_LEDGER_SEED, CROSS_CHAIN_CONFIG_SEED, CROSS_CHAIN_RECEIPT_SEED, FEE_RESERVE_SEED, FEE_TREASURY_SEED, FOREIGN_COLLECTION_SEED, FREEZE_AUTHORITY_SEED, INBOUND_MINT_SEED, NFT_METADATA_SEED, PROGRAM_STATE_SEED, RELAYER_SEED, STAGED_INBOUND_SEED};
+use crate::utils::inbound::{deliver_inbound_nft, InboundOrigin};
+use crate::utils::ipfs::ipfs_multihash;
+use crate::utils::relayers::require_bonded_relayer;
+use crate::utils::validation::{validate_recipient_account, validate_recipient_token_account};
+
+#[derive(Accounts)]
+#[instruction(origin_tx_hash: [u8; 32], nonce: u64)]
+pub struct ExecuteInboundMessage<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+
+    #[account(
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
+        bump = cross_chain_config.bump,
+        constraint = !cross_chain_config.is_paused @ UniversalNftError::CrossChainPaused
+    )]
+    pub cross_chain_config: Box<Account<'info, CrossChainConfig>>,
+
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Box<Account<'info, Blocklist>>,
+
+    #[account(
+        seeds = [CHAIN_CONFIG_SEED, staged_inbound.origin_chain_id.to_le_bytes().as_ref()],
+        bump = origin_chain_config.bump
+    )]
+    pub origin_chain_config: Box<Account<'info, ChainConfig>>,
+
+    #[account(
+        mut,
+        close = stager,
+        seeds = [STAGED_INBOUND_SEED, origin_tx_hash.as_ref(), nonce.to_le_bytes().as_ref()],
+        bump = staged_inbound.bump
+    )]
+    pub staged_inbound: Box<Account<'info, StagedInbound>>,
+
+    /// CHECK: Receives the staged message's rent; must be whoever staged it
+    #[account(mut, address = staged_inbound.stager @ UniversalNftError::Unauthorized)]
+    pub stager: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + CrossChainReceipt::INIT_SPACE,
+        seeds = [CROSS_CHAIN_RECEIPT_SEED, origin_tx_hash.as_ref(), nonce.to_le_bytes().as_ref()],
+        bump,
+        constraint = !receipt.is_processed() @ UniversalNftError::AlreadyProcessed
+    )]
+    pub receipt: Box<Account<'info, CrossChainReceipt>>,
+
+    // An NFT returning from a trip out finds its mint, token account and
+    // metadata already in place
+    #[account(
+        init_if_needed,
+        payer = authority,
+        mint::decimals = 0,
+        mint::authority = mint,
+        mint::freeze_authority = freeze_authority,
+        seeds = [
+            INBOUND_MINT_SEED,
+            staged_inbound.origin_chain_id.to_le_bytes().as_ref(),
+            staged_inbound.origin_contract.as_slice(),
+            staged_inbound.origin_token_id.as_ref()
+        ],
+        bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: PDA holding the freeze authority of the NFTs the program mints,
+    /// so inbound NFTs can be listed without escrow
+    #[account(seeds = [FREEZE_AUTHORITY_SEED], bump)]
+    pub freeze_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + NftMetadata::INIT_SPACE,
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub nft_metadata: Box<Account<'info, NftMetadata>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + CollectionLedger::INIT_SPACE,
+        seeds = [
+            COLLECTION_LEDGER_SEED,
+            staged_inbound.origin_chain_id.to_le_bytes().as_ref(),
+            staged_inbound.origin_contract.as_slice()
+        ],
+        bump
+    )]
+    pub collection_ledger: Box<Account<'info, CollectionLedger>>,
+
+    /// Required once the origin collection is registered
+    #[account(
+        seeds = [
+            FOREIGN_COLLECTION_SEED,
+            staged_inbound.origin_chain_id.to_le_bytes().as_ref(),
+            staged_inbound.origin_contract.as_slice()
+        ],
+        bump = foreign_collection.bump
+    )]
+    pub foreign_collection: Option<Box<Account<'info, ForeignCollection>>>,
+
+    /// CHECK: Must be the recipient named in the staged message
+    #[account(address = staged_inbound.recipient @ UniversalNftError::RecipientMismatch)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// CHECK: Fee treasury PDA; pays the execution bounty
+    #[account(
+        mut,
+        seeds = [FEE_TREASURY_SEED],
+        bump
+    )]
+    pub fee_treasury: UncheckedAccount<'info>,
+
+    /// CHECK: `FeeReserve`, read if it exists; the bounty can't spend refundable fees
+    #[account(seeds = [FEE_RESERVE_SEED], bump)]
+    pub fee_reserve: UncheckedAccount<'info>,
+
+    /// Required from bonded relayers executing before `open_at`
+    #[account(
+        mut,
+        seeds = [RELAYER_SEED, authority.key().as_ref()],
+        bump = relayer_registration.bump
+    )]
+    pub relayer_registration: Option<Account<'info, Relayer>>,
+
+    /// Executor; pays for the new accounts and earns the bounty
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+
+/// Mint a staged inbound message to its recipient and pay the executor the
+/// origin chain's bounty, as far as the fee treasury can cover it. Bonded
+/// relayers may execute at once, anyone else from `open_at`.
+pub fn handler(ctx: Context<ExecuteInboundMessage>, origin_tx_hash: [u8; 32], nonce: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    if now < ctx.accounts.staged_inbound.open_at {
+        require_bonded_relayer(&ctx.accounts.cross_chain_config, ctx.accounts.relayer_registration.as_mut())?;
+    }
+
+    let staged = &ctx.accounts.staged_inbound;
+    require!(
+        !ctx.accounts.blocklist.is_account_blocked(&staged.recipient),
+        UniversalNftError::AddressBlocked
+    );
+    validate_recipient_account(&ctx.accounts.recipient)?;
+    validate_recipient_token_account(&ctx.accounts.token_account)?;
+
+    // Mint the NFT to recipient, or unlock it if it is coming back
+    let returning = deliver_inbound_nft(
+        &ctx.accounts.mint,
+        ctx.bumps.mint,
+        &ctx.accounts.token_account,
+        &ctx.accounts.nft_metadata,
+        &InboundOrigin {
+            chain_id: staged.origin_chain_id,
+            contract: &staged.origin_contract,
+            token_id: staged.origin_token_id,
+        },
+        ctx.accounts.token_program.to_account_info(),
+    )?;
+
+    // Group items from a registered collection under its Solana collection mint
+    let registered = ctx.accounts.collection_ledger.foreign_collection;
+    let collection = if registered == Pubkey::default() {
+        Pubkey::default()
+    } else {
+        let foreign_collection = ctx
+            .accounts
+            .foreign_collection
+            .as_ref()
+            .ok_or(UniversalNftError::ForeignCollectionMismatch)?;
+        require_keys_eq!(
+            foreign_collection.key(),
+            registered,
+            UniversalNftError::ForeignCollectionMismatch
+        );
+        require!(
+            foreign_collection.symbol == staged.symbol,
+            UniversalNftError::ForeignCollectionMismatch
+        );
+        foreign_collection.collection_mint
+    };
+
+    // Update the collection's supply ledger
+    let collection_ledger = &mut ctx.accounts.collection_ledger;
+    collection_ledger.ensure_initialized(
+        staged.origin_chain_id,
+        &staged.origin_contract,
+        ctx.bumps.collection_ledger,
+    );
+    collection_ledger.record_inbound()?;
+
+    let nft_metadata = &mut ctx.accounts.nft_metadata;
+    if returning {
+        nft_metadata.is_locked = false;
+        nft_metadata.current_owner = staged.recipient;
+    } else {
+        // Initialize NFT metadata
+        nft_metadata.mint = ctx.accounts.mint.key();
+        nft_metadata.original_owner = staged.recipient; // Recipient becomes owner on Solana
+        nft_metadata.current_owner = staged.recipient;
+        nft_metadata.metadata_uri = staged.metadata_uri.clone();
+        nft_metadata.name = staged.name.clone();
+        nft_metadata.symbol = staged.symbol.clone();
+        nft_metadata.cross_chain_enabled = true; // Cross-chain NFTs are always transferable
+        nft_metadata.is_locked = false;
+        nft_metadata.origin_chain_id = staged.origin_chain_id;
+        nft_metadata.origin_contract = staged.origin_contract.clone();
+        nft_metadata.fallback_uri = None;
+        nft_metadata.metadata_multihash = ipfs_multihash(&nft_metadata.metadata_uri)?;
+        nft_metadata.child_count = 0;
+        nft_metadata.origin_token_id = staged.origin_token_id;
+        nft_metadata.collection = collection;
+        nft_metadata.wrapped_mint = Pubkey::default();
+        nft_metadata.creation_timestamp = now;
+        nft_metadata.bump = ctx.bumps.nft_metadata;
+    }
+
+    // Create receipt
+    let receipt = &mut ctx.accounts.receipt;
+    receipt.version = CrossChainReceipt::VERSION;
+    receipt.origin_chain_id = staged.origin_chain_id;
+    receipt.origin_tx_hash = origin_tx_hash;
+    receipt.mint = ctx.accounts.mint.key();
+    receipt.recipient = staged.recipient;
+    receipt.original_owner = staged.original_owner.clone();
+    receipt.nonce = nonce;
+    receipt.timestamp = now;
+    receipt.tss_signature_hash = staged.tss_signature_hash;
+    receipt.bump = ctx.bumps.receipt;
+
+    // Update program state
+    if !returning {
+        let program_state = &mut ctx.accounts.program_state;
+        program_state.total_nfts_minted = program_state
+            .total_nfts_minted
+            .checked_add(1)
+            .ok_or(UniversalNftError::ArithmeticOverflow)?;
+    }
+
+    // Pay the bounty out of earned fees, leaving the treasury rent-exempt
+    let available = ctx
+        .accounts
+        .fee_treasury
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0))
+        .saturating_sub(FeeReserve::outstanding_if_exists(&ctx.accounts.fee_reserve, None)?);
+    let bounty = ctx.accounts.origin_chain_config.execution_bounty.min(available);
+    if bounty > 0 {
+        let signer_seeds: &[&[&[u8]]] = &[&[FEE_TREASURY_SEED, &[ctx.bumps.fee_treasury]]];
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.fee_treasury.to_account_info(),
+            to: ctx.accounts.authority.to_account_info(),
+        };
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            ),
+            bounty,
+        )?;
+    }
+
+    emit!(InboundMessageExecutedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint: ctx.accounts.mint.key(),
+        recipient: ctx.accounts.staged_inbound.recipient,
+        origin_chain_id: ctx.accounts.staged_inbound.origin_chain_id,
+        origin_tx_hash,
+        nonce,
+        executor: ctx.accounts.authority.key(),
+        bounty,
+        timestamp: now,
+    });
+
+    msg!("Executed inbound message: {}, bounty {} lamports", ctx.accounts.mint.key(), bounty);
+
+    Ok(())
+}
+
+#[event]
+pub struct InboundMessageExecutedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub origin_chain_id: u64,
+    pub origin_tx_hash: [u8; 32],
+    pub nonce: u64,
+    pub executor: Pubkey,
+    pub bounty: u64,
+    pub timestamp: i64,
+}
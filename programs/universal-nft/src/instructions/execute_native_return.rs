@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use crate::state::{ProgramState, CrossChainConfig, NftMetadata, CollectionLedger, PendingReturn};
+use crate::error::UniversalNftError;
+use crate::constants::{COLLECTION_LEDGER_SEED, CROSS_CHAIN_CONFIG_SEED, NFT_METADATA_SEED, PENDING_RETURN_SEED, PROGRAM_STATE_SEED, STAKE_RECORD_SEED};
+use crate::utils::staking::resume_stake;
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct ExecuteNativeReturn<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    // Unlocking moves assets, so a pause also holds staged returns
+    #[account(
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
+        bump = cross_chain_config.bump,
+        constraint = !cross_chain_config.is_paused @ UniversalNftError::CrossChainPaused
+    )]
+    pub cross_chain_config: Account<'info, CrossChainConfig>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [PENDING_RETURN_SEED, mint.as_ref()],
+        bump = pending_return.bump
+    )]
+    pub pending_return: Account<'info, PendingReturn>,
+
+    #[account(
+        mut,
+        seeds = [NFT_METADATA_SEED, mint.as_ref()],
+        bump = nft_metadata.bump,
+        constraint = nft_metadata.is_locked @ UniversalNftError::NativeNftNotLocked
+    )]
+    pub nft_metadata: Account<'info, NftMetadata>,
+
+    #[account(
+        mut,
+        seeds = [
+            COLLECTION_LEDGER_SEED,
+            nft_metadata.origin_chain_id.to_le_bytes().as_ref(),
+            nft_metadata.origin_contract.as_slice()
+        ],
+        bump = collection_ledger.bump
+    )]
+    pub collection_ledger: Account<'info, CollectionLedger>,
+
+    /// CHECK: The NFT's stake PDA, resumed by the handler if it was
+    /// checkpointed on the way out
+    #[account(mut, seeds = [STAKE_RECORD_SEED, mint.as_ref()], bump)]
+    pub stake_record: UncheckedAccount<'info>,
+
+    /// CHECK: Receives the staged return's rent; must be the account that staged it
+    #[account(mut, address = pending_return.payer @ UniversalNftError::Unauthorized)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+/// Unlock a staged native return once its finality delay has passed. Anyone
+/// may submit it.
+pub fn handler(ctx: Context<ExecuteNativeReturn>, mint: Pubkey) -> Result<()> {
+    let pending_return = &ctx.accounts.pending_return;
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= pending_return.executable_at,
+        UniversalNftError::FinalityDelayNotElapsed
+    );
+
+    let nft_metadata = &mut ctx.accounts.nft_metadata;
+    nft_metadata.is_locked = false;
+    nft_metadata.current_owner = pending_return.recipient;
+    ctx.accounts.collection_ledger.record_native_returned()?;
+
+    resume_stake(
+        &ctx.accounts.stake_record,
+        &mut ctx.accounts.program_state,
+        mint,
+        pending_return.recipient,
+        pending_return.staked_seconds,
+        now,
+    )?;
+
+    emit!(NativeReturnExecutedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint,
+        recipient: pending_return.recipient,
+        origin_chain_id: pending_return.origin_chain_id,
+        nonce: pending_return.nonce,
+        timestamp: now,
+    });
+
+    msg!("Native return executed for mint: {}", mint);
+
+    Ok(())
+}
+
+#[event]
+pub struct NativeReturnExecutedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub origin_chain_id: u64,
+    pub nonce: u64,
+    pub timestamp: i64,
+}
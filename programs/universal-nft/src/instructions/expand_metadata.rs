@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::{ProgramState, NftMetadata};
+use crate::error::UniversalNftError;
+use crate::constants::{NFT_METADATA_SEED, PROGRAM_STATE_SEED};
+use crate::utils::ipfs::ipfs_multihash;
+
+#[derive(Accounts)]
+#[instruction(metadata_uri: String)]
+pub struct ExpandMetadata<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Grown to fit `metadata_uri`, never shrunk
+    #[account(
+        mut,
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump,
+        constraint = !nft_metadata.is_locked @ UniversalNftError::NftLocked,
+        realloc = NftMetadata::space_for_uri(metadata_uri.len()).max(nft_metadata.to_account_info().data_len()),
+        realloc::payer = owner,
+        realloc::zero = false
+    )]
+    pub nft_metadata: Account<'info, NftMetadata>,
+
+    /// CHECK: Mint account validated by token account constraint
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = token_account.mint == mint.key(),
+        constraint = token_account.owner == owner.key(),
+        constraint = token_account.amount >= 1 @ UniversalNftError::InsufficientTokens
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    /// Holder of the NFT; pays the rent for any added space
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Replace the metadata URI with one up to `NftMetadata::MAX_EXPANDED_URI_LEN`
+/// bytes, growing the account first when the new URI does not fit
+pub fn handler(ctx: Context<ExpandMetadata>, metadata_uri: String) -> Result<()> {
+    require!(
+        !metadata_uri.is_empty() && metadata_uri.len() <= NftMetadata::MAX_EXPANDED_URI_LEN,
+        UniversalNftError::InvalidMetadataUri
+    );
+
+    let nft_metadata = &mut ctx.accounts.nft_metadata;
+    nft_metadata.metadata_multihash = ipfs_multihash(&metadata_uri)?;
+    nft_metadata.metadata_uri = metadata_uri;
+    let space = nft_metadata.to_account_info().data_len();
+
+    emit!(MetadataExpandedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint: nft_metadata.mint,
+        owner: ctx.accounts.owner.key(),
+        metadata_uri: nft_metadata.metadata_uri.clone(),
+        space: space as u32,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Metadata URI of {} updated; account is {} bytes", nft_metadata.mint, space);
+
+    Ok(())
+}
+
+#[event]
+pub struct MetadataExpandedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub metadata_uri: String,
+    pub space: u32,
+    pub timestamp: i64,
+}
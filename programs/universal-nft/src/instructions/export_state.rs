@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::state::ProgramState;
+use crate::error::UniversalNftError;
+use crate::constants::PROGRAM_STATE_SEED;
+
+/// Attests a page of program accounts for an off-chain state export. The
+/// accounts are passed in `remaining_accounts`; the page comes back as return
+/// data, which caps it at `MAX_EXPORT_PAGE` accounts.
+#[derive(Accounts)]
+pub struct ExportState<'info> {
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+/// Accounts per page; 12 entries keep `ExportPage` under the 1 KiB return data limit
+pub const MAX_EXPORT_PAGE: usize = 12;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ExportedAccount {
+    pub address: Pubkey,
+    pub discriminator: [u8; 8], // Names the account type
+    pub data_hash: [u8; 32],    // sha256 of the full account data
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ExportPage {
+    pub page: u32, // Echoed from the caller, which chooses how accounts are paged
+    pub slot: u64,
+    pub accounts: Vec<ExportedAccount>,
+}
+
+pub fn handler(ctx: Context<ExportState>, page: u32) -> Result<ExportPage> {
+    let remaining = ctx.remaining_accounts;
+    require!(
+        !remaining.is_empty() && remaining.len() <= MAX_EXPORT_PAGE,
+        UniversalNftError::InvalidExportPage
+    );
+
+    let mut accounts = Vec::with_capacity(remaining.len());
+    for info in remaining {
+        require_keys_eq!(*info.owner, crate::ID, UniversalNftError::InvalidExportPage);
+        let data = info.try_borrow_data()?;
+        require!(data.len() >= 8, UniversalNftError::InvalidExportPage);
+
+        let mut discriminator = [0u8; 8];
+        discriminator.copy_from_slice(&data[..8]);
+        accounts.push(ExportedAccount {
+            address: info.key(),
+            discriminator,
+            data_hash: hash(&data).to_bytes(),
+        });
+    }
+
+    Ok(ExportPage {
+        page,
+        slot: Clock::get()?.slot,
+        accounts,
+    })
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::state::CrossChainReceipt;
+use crate::error::UniversalNftError;
+use crate::constants::CROSS_CHAIN_RECEIPT_SEED;
+use crate::instructions::migrate_receipt::normalize_origin_tx_hash;
+
+/// Looks up the receipt of an inbound message by its origin identifiers
+#[derive(Accounts)]
+pub struct FindReceipt<'info> {
+    /// CHECK: Must be the receipt PDA for the arguments, verified in the handler; may not exist yet
+    pub receipt: UncheckedAccount<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ReceiptLookup {
+    pub address: Pubkey,
+    pub processed: bool, // The message arrived and its NFT was delivered
+    pub receipt: Option<CrossChainReceipt>, // None until the message is received or staged
+}
+
+/// `origin_tx_hash` is the identifier as the origin chain reports it; longer
+/// than 32 bytes, it is reduced the same way the receive paths reduce it.
+pub fn handler(ctx: Context<FindReceipt>, origin_tx_hash: Vec<u8>, nonce: u64) -> Result<ReceiptLookup> {
+    let origin_tx_hash = normalize_origin_tx_hash(&origin_tx_hash);
+    let (address, _) = Pubkey::find_program_address(
+        &[CROSS_CHAIN_RECEIPT_SEED, origin_tx_hash.as_ref(), nonce.to_le_bytes().as_ref()],
+        ctx.program_id,
+    );
+    let info = ctx.accounts.receipt.to_account_info();
+    require_keys_eq!(info.key(), address, UniversalNftError::InvalidReceiptAddress);
+
+    if info.owner != ctx.program_id {
+        return Ok(ReceiptLookup {
+            address,
+            processed: false,
+            receipt: None,
+        });
+    }
+
+    // Receipts still in the legacy layout must go through `migrate_receipt` first
+    let receipt = CrossChainReceipt::try_deserialize(&mut &info.try_borrow_data()?[..])
+        .map_err(|_| UniversalNftError::InvalidReceiptLayout)?;
+
+    Ok(ReceiptLookup {
+        address,
+        processed: receipt.is_processed(),
+        receipt: Some(receipt),
+    })
+}
@@ -0,0 +1,299 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Token, TokenAccount, Mint};
+use anchor_spl::token::spl_token::instruction::AuthorityType;
+use crate::state::{ProgramState, NftMetadata, FractionVault};
+use crate::error::UniversalNftError;
+use crate::constants::{FRACTION_ESCROW_SEED, FRACTION_MINT_SEED, FRACTION_VAULT_SEED, NFT_METADATA_SEED, PROGRAM_STATE_SEED};
+use crate::utils::validation::validate_nft_mint_supply;
+
+/// Locks an NFT in a vault and issues its fractions to the depositor. The
+/// escrowed NFT can't be bridged, since only its holder can send it.
+#[derive(Accounts)]
+pub struct FractionalizeNft<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = !program_state.is_sunset @ UniversalNftError::ProgramSunset
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump,
+        constraint = !nft_metadata.is_locked @ UniversalNftError::NftLocked
+    )]
+    pub nft_metadata: Box<Account<'info, NftMetadata>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + FractionVault::INIT_SPACE,
+        seeds = [FRACTION_VAULT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub fraction_vault: Box<Account<'info, FractionVault>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == mint.key() @ UniversalNftError::InvalidMint,
+        constraint = owner_token_account.owner == owner.key() @ UniversalNftError::Unauthorized,
+        constraint = owner_token_account.amount >= 1 @ UniversalNftError::InsufficientTokens
+    )]
+    pub owner_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Holds the NFT while it is fractionalized; its own authority, like the child escrow
+    #[account(
+        init,
+        payer = owner,
+        token::mint = mint,
+        token::authority = vault_escrow,
+        seeds = [FRACTION_ESCROW_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub vault_escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = owner,
+        mint::decimals = 0,
+        mint::authority = fraction_vault,
+        seeds = [FRACTION_MINT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub fraction_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = fraction_mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_fraction_account: Box<Account<'info, TokenAccount>>,
+
+    /// Holder of the NFT; receives every fraction and pays for the vault
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Burns every fraction of a vault and releases its NFT to the redeemer
+#[derive(Accounts)]
+pub struct RedeemFractions<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump
+    )]
+    pub nft_metadata: Box<Account<'info, NftMetadata>>,
+
+    #[account(
+        mut,
+        close = depositor,
+        seeds = [FRACTION_VAULT_SEED, mint.key().as_ref()],
+        bump = fraction_vault.bump,
+        has_one = mint,
+        has_one = depositor,
+        has_one = fraction_mint
+    )]
+    pub fraction_vault: Box<Account<'info, FractionVault>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [FRACTION_ESCROW_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub vault_escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub fraction_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = redeemer_fraction_account.mint == fraction_mint.key() @ UniversalNftError::InvalidMint,
+        constraint = redeemer_fraction_account.owner == redeemer.key() @ UniversalNftError::Unauthorized,
+        constraint = redeemer_fraction_account.amount >= fraction_vault.total_supply @ UniversalNftError::IncompleteFractions
+    )]
+    pub redeemer_fraction_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = redeemer,
+        associated_token::mint = mint,
+        associated_token::authority = redeemer,
+    )]
+    pub redeemer_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The vault's depositor, checked by `has_one`; gets the vault and escrow rent back
+    #[account(mut)]
+    pub depositor: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub redeemer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Escrow the NFT and mint `total_supply` fractions to its holder. The
+/// fraction mint's authority is revoked, so the supply is fixed.
+pub fn fractionalize_handler(ctx: Context<FractionalizeNft>, total_supply: u64) -> Result<()> {
+    require!(total_supply > 0, UniversalNftError::InvalidFractionSupply);
+    validate_nft_mint_supply(&ctx.accounts.mint.to_account_info())?;
+
+    let mint = ctx.accounts.mint.key();
+    let now = Clock::get()?.unix_timestamp;
+    let fraction_vault = &mut ctx.accounts.fraction_vault;
+    fraction_vault.mint = mint;
+    fraction_vault.depositor = ctx.accounts.owner.key();
+    fraction_vault.fraction_mint = ctx.accounts.fraction_mint.key();
+    fraction_vault.total_supply = total_supply;
+    fraction_vault.created_at = now;
+    fraction_vault.bump = ctx.bumps.fraction_vault;
+
+    let cpi_accounts = token::Transfer {
+        from: ctx.accounts.owner_token_account.to_account_info(),
+        to: ctx.accounts.vault_escrow.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, 1)?;
+
+    let vault_bump = fraction_vault.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[FRACTION_VAULT_SEED, mint.as_ref(), &[vault_bump]]];
+    let cpi_accounts = token::MintTo {
+        mint: ctx.accounts.fraction_mint.to_account_info(),
+        to: ctx.accounts.owner_fraction_account.to_account_info(),
+        authority: fraction_vault.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::mint_to(cpi_ctx, total_supply)?;
+
+    let cpi_accounts = token::SetAuthority {
+        current_authority: fraction_vault.to_account_info(),
+        account_or_mint: ctx.accounts.fraction_mint.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::set_authority(cpi_ctx, AuthorityType::MintTokens, None)?;
+
+    emit!(NftFractionalizedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint,
+        depositor: fraction_vault.depositor,
+        fraction_mint: fraction_vault.fraction_mint,
+        total_supply,
+        timestamp: now,
+    });
+
+    msg!("Fractionalized {} into {} fractions of {}", mint, total_supply, fraction_vault.fraction_mint);
+
+    Ok(())
+}
+
+/// Burn every fraction and take the NFT out of the vault
+pub fn redeem_handler(ctx: Context<RedeemFractions>) -> Result<()> {
+    let mint = ctx.accounts.mint.key();
+    let total_supply = ctx.accounts.fraction_vault.total_supply;
+
+    let cpi_accounts = token::Burn {
+        mint: ctx.accounts.fraction_mint.to_account_info(),
+        from: ctx.accounts.redeemer_fraction_account.to_account_info(),
+        authority: ctx.accounts.redeemer.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::burn(cpi_ctx, total_supply)?;
+
+    // Release the NFT and close the escrow
+    let escrow_bump = ctx.bumps.vault_escrow;
+    let signer_seeds: &[&[&[u8]]] = &[&[FRACTION_ESCROW_SEED, mint.as_ref(), &[escrow_bump]]];
+
+    let cpi_accounts = token::Transfer {
+        from: ctx.accounts.vault_escrow.to_account_info(),
+        to: ctx.accounts.redeemer_token_account.to_account_info(),
+        authority: ctx.accounts.vault_escrow.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(cpi_ctx, 1)?;
+
+    let cpi_accounts = token::CloseAccount {
+        account: ctx.accounts.vault_escrow.to_account_info(),
+        destination: ctx.accounts.depositor.to_account_info(),
+        authority: ctx.accounts.vault_escrow.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::close_account(cpi_ctx)?;
+
+    let nft_metadata = &mut ctx.accounts.nft_metadata;
+    nft_metadata.current_owner = ctx.accounts.redeemer.key();
+
+    emit!(FractionsRedeemedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint,
+        redeemer: ctx.accounts.redeemer.key(),
+        fraction_mint: ctx.accounts.fraction_mint.key(),
+        total_supply,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Redeemed {} with all {} fractions", mint, total_supply);
+
+    Ok(())
+}
+
+#[event]
+pub struct NftFractionalizedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub depositor: Pubkey,
+    pub fraction_mint: Pubkey,
+    pub total_supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FractionsRedeemedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub redeemer: Pubkey,
+    pub fraction_mint: Pubkey,
+    pub total_supply: u64,
+    pub timestamp: i64,
+}
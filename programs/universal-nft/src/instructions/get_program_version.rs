@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::ProgramState;
+use crate::constants::PROGRAM_STATE_SEED;
+
+/// Semantic version of the deployed program
+pub const PROGRAM_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Git commit the program was built from, or "unknown"
+pub const GIT_HASH: &str = env!("UNIVERSAL_NFT_GIT_HASH");
+
+#[derive(Accounts)]
+pub struct GetProgramVersion<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProgramVersion {
+    pub version: String,
+    pub git_hash: String,
+}
+
+pub fn handler(_ctx: Context<GetProgramVersion>) -> Result<ProgramVersion> {
+    msg!("Universal NFT {} ({})", PROGRAM_VERSION, GIT_HASH);
+
+    Ok(ProgramVersion {
+        version: PROGRAM_VERSION.to_string(),
+        git_hash: GIT_HASH.to_string(),
+    })
+}
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::state::{ProgramState, CrossChainConfig};
+use crate::state::{ProgramState, CrossChainConfig, Blocklist, OptOutRegistry, AuditLog, CpiAllowlist, ValidationLimits};
 use crate::error::UniversalNftError;
+use crate::constants::{AUDIT_LOG_SEED, BLOCKLIST_SEED, CPI_ALLOWLIST_SEED, CROSS_CHAIN_CONFIG_SEED, OPT_OUT_REGISTRY_SEED, PROGRAM_STATE_SEED};
+use crate::config::{SELF_CHAIN_ID, GATEWAY_PROGRAM_ID};
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
@@ -8,7 +10,7 @@ pub struct Initialize<'info> {
         init,
         payer = authority,
         space = 8 + ProgramState::INIT_SPACE,
-        seeds = [b"program_state"],
+        seeds = [PROGRAM_STATE_SEED],
         bump
     )]
     pub program_state: Account<'info, ProgramState>,
@@ -17,11 +19,47 @@ pub struct Initialize<'info> {
         init,
         payer = authority,
         space = 8 + CrossChainConfig::INIT_SPACE,
-        seeds = [b"cross_chain_config"],
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
         bump
     )]
     pub cross_chain_config: Account<'info, CrossChainConfig>,
 
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Blocklist::INIT_SPACE,
+        seeds = [BLOCKLIST_SEED],
+        bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + OptOutRegistry::INIT_SPACE,
+        seeds = [OPT_OUT_REGISTRY_SEED],
+        bump
+    )]
+    pub opt_out_registry: Account<'info, OptOutRegistry>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditLog::INIT_SPACE,
+        seeds = [AUDIT_LOG_SEED],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + CpiAllowlist::INIT_SPACE,
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -34,14 +72,24 @@ pub fn handler(
     tss_address: Pubkey,
     chain_id: u64,
 ) -> Result<()> {
+    // Messages are signed under this ID, so it must be the cluster the build targets
+    require!(chain_id == SELF_CHAIN_ID, UniversalNftError::UnsupportedChain);
+    if let Some(gateway) = GATEWAY_PROGRAM_ID {
+        require_keys_eq!(gateway_address, gateway, UniversalNftError::InvalidGateway);
+    }
+
     let program_state = &mut ctx.accounts.program_state;
     let cross_chain_config = &mut ctx.accounts.cross_chain_config;
+    let blocklist = &mut ctx.accounts.blocklist;
 
     // Initialize program state
     program_state.authority = ctx.accounts.authority.key();
     program_state.is_initialized = true;
     program_state.total_nfts_minted = 0;
     program_state.cross_chain_transfers = 0;
+    program_state.is_sunset = false;
+    program_state.event_sequence = 0;
+    program_state.validation_limits = ValidationLimits::DEFAULT;
     program_state.bump = ctx.bumps.program_state;
 
     // Initialize cross-chain configuration
@@ -50,10 +98,59 @@ pub fn handler(
     cross_chain_config.chain_id = chain_id;
     cross_chain_config.is_paused = false;
     cross_chain_config.nonce_counter = 0;
+    cross_chain_config.breaker_max_transfers = 0;
+    cross_chain_config.breaker_window_slots = 0;
+    cross_chain_config.breaker_window_start = 0;
+    cross_chain_config.breaker_window_count = 0;
+    cross_chain_config.credential_requirement = None;
+    cross_chain_config.token_gate = None;
+    cross_chain_config.relayer_requirement = None;
     cross_chain_config.bump = ctx.bumps.cross_chain_config;
 
+    // Start with an empty blocklist
+    blocklist.blocked_accounts = Vec::new();
+    blocklist.blocked_foreign_addresses = Vec::new();
+    blocklist.bump = ctx.bumps.blocklist;
+
+    let opt_out_registry = &mut ctx.accounts.opt_out_registry;
+    opt_out_registry.opted_out = Vec::new();
+    opt_out_registry.bump = ctx.bumps.opt_out_registry;
+
+    // Start an empty audit chain
+    let audit_log = &mut ctx.accounts.audit_log;
+    audit_log.entry_count = 0;
+    audit_log.head_hash = [0u8; 32];
+    audit_log.bump = ctx.bumps.audit_log;
+
+    // CPI guard starts disabled; any program may call in until it is enforced
+    let cpi_allowlist = &mut ctx.accounts.cpi_allowlist;
+    cpi_allowlist.enforced = false;
+    cpi_allowlist.programs = Vec::new();
+    cpi_allowlist.bump = ctx.bumps.cpi_allowlist;
+
+    emit!(InitializedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        authority: ctx.accounts.authority.key(),
+        gateway_address,
+        tss_address,
+        chain_id,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     msg!("Universal NFT Program initialized with ZetaChain gateway: {}", gateway_address);
     msg!("TSS address: {}, Chain ID: {}", tss_address, chain_id);
 
     Ok(())
 }
+
+#[event]
+pub struct InitializedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub authority: Pubkey,
+    pub gateway_address: Pubkey,
+    pub tss_address: Pubkey,
+    pub chain_id: u64,
+    pub timestamp: i64,
+}
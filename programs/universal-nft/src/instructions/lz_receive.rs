@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::BridgeAdapterKind;
+use crate::error::UniversalNftError;
+use crate::adapters::LzReceiveParams;
+use crate::utils::validation::validate_origin_chain;
+use super::receive_cross_chain::{self, ReceiveCrossChain};
+
+/// LayerZero executor entrypoint. Shares `ReceiveCrossChain` accounts, so the
+/// arguments match `receive_cross_chain` up to `nonce`, with the executor's
+/// `extra_data` in place of the TSS signature. The LayerZero endpoint
+/// accounts for `clear` follow as remaining accounts.
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ReceiveCrossChain<'info>>,
+    origin_chain_id: u64,
+    origin_tx_hash: [u8; 32],
+    origin_contract: Vec<u8>,
+    origin_token_id: [u8; 32],
+    metadata_uri: String,
+    name: String,
+    symbol: String,
+    original_owner: Vec<u8>,
+    intended_recipient: Pubkey,
+    _extra_data: Vec<u8>,
+    nonce: u64,
+    params: LzReceiveParams,
+) -> Result<()> {
+    let origin_chain_config = validate_origin_chain(
+        &ctx.accounts.cross_chain_config,
+        &ctx.accounts.origin_chain_config,
+        origin_chain_id,
+    )?;
+    require!(
+        matches!(origin_chain_config.adapter, BridgeAdapterKind::LayerZero { .. }),
+        UniversalNftError::AdapterMismatch
+    );
+
+    receive_cross_chain::handler(
+        ctx,
+        origin_chain_id,
+        origin_tx_hash,
+        origin_contract,
+        origin_token_id,
+        metadata_uri,
+        name,
+        symbol,
+        original_owner,
+        intended_recipient,
+        params.try_to_vec()?,
+        nonce,
+    )
+}
@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{hash::hash, program::invoke_signed, system_instruction};
+use anchor_lang::Discriminator;
+use crate::state::{ProgramState, CrossChainReceipt, LegacyCrossChainReceipt};
+use crate::error::UniversalNftError;
+use crate::constants::{CROSS_CHAIN_RECEIPT_SEED, PROGRAM_STATE_SEED};
+
+#[derive(Accounts)]
+pub struct MigrateReceipt<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = program_state.authority == authority.key() @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// CHECK: Owner, discriminator, layout and PDA address are verified in the handler
+    #[account(mut, owner = crate::ID)]
+    pub legacy_receipt: UncheckedAccount<'info>,
+
+    /// CHECK: New receipt PDA, verified in the handler. Omitted when the legacy hash was
+    /// already 32 bytes, because the address is unchanged and the receipt is rewritten in place.
+    #[account(mut)]
+    pub new_receipt: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateReceipt>) -> Result<()> {
+    let legacy_info = ctx.accounts.legacy_receipt.to_account_info();
+    let authority_info = ctx.accounts.authority.to_account_info();
+
+    // Only accounts still in the legacy layout can be migrated
+    let legacy = {
+        let data = legacy_info.try_borrow_data()?;
+        require!(
+            data.len() == 8 + LegacyCrossChainReceipt::INIT_SPACE
+                && data[..8] == CrossChainReceipt::DISCRIMINATOR,
+            UniversalNftError::InvalidReceiptLayout
+        );
+        LegacyCrossChainReceipt::deserialize(&mut &data[8..])
+            .map_err(|_| UniversalNftError::InvalidReceiptLayout)?
+    };
+
+    let (legacy_address, _) = Pubkey::find_program_address(
+        &[CROSS_CHAIN_RECEIPT_SEED, legacy.origin_tx_hash.as_slice(), legacy.nonce.to_le_bytes().as_ref()],
+        ctx.program_id,
+    );
+    require_keys_eq!(legacy_info.key(), legacy_address, UniversalNftError::InvalidReceiptLayout);
+
+    let origin_tx_hash = normalize_origin_tx_hash(&legacy.origin_tx_hash);
+    let (new_address, new_bump) = Pubkey::find_program_address(
+        &[CROSS_CHAIN_RECEIPT_SEED, origin_tx_hash.as_ref(), legacy.nonce.to_le_bytes().as_ref()],
+        ctx.program_id,
+    );
+
+    let receipt = CrossChainReceipt {
+        version: CrossChainReceipt::VERSION,
+        origin_chain_id: legacy.origin_chain_id,
+        origin_tx_hash,
+        mint: legacy.mint,
+        recipient: legacy.recipient,
+        original_owner: legacy.original_owner,
+        nonce: legacy.nonce,
+        timestamp: legacy.timestamp,
+        tss_signature_hash: hash(&legacy.tss_signature).to_bytes(),
+        bump: new_bump,
+    };
+    let new_space = 8 + CrossChainReceipt::INIT_SPACE;
+    let rent_exempt = Rent::get()?.minimum_balance(new_space);
+
+    if new_address == legacy_address {
+        // Same address: shrink in place and hand the freed rent to the authority
+        legacy_info.realloc(new_space, false)?;
+        receipt.try_serialize(&mut &mut legacy_info.try_borrow_mut_data()?[..])?;
+
+        let surplus = legacy_info.lamports().saturating_sub(rent_exempt);
+        **legacy_info.try_borrow_mut_lamports()? -= surplus;
+        **authority_info.try_borrow_mut_lamports()? += surplus;
+    } else {
+        let new_info = ctx
+            .accounts
+            .new_receipt
+            .as_ref()
+            .ok_or(UniversalNftError::InvalidReceiptLayout)?
+            .to_account_info();
+        require_keys_eq!(new_info.key(), new_address, UniversalNftError::InvalidReceiptLayout);
+
+        invoke_signed(
+            &system_instruction::create_account(
+                &authority_info.key(),
+                &new_address,
+                rent_exempt,
+                new_space as u64,
+                ctx.program_id,
+            ),
+            &[
+                authority_info.clone(),
+                new_info.clone(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[&[
+                CROSS_CHAIN_RECEIPT_SEED,
+                origin_tx_hash.as_ref(),
+                legacy.nonce.to_le_bytes().as_ref(),
+                &[new_bump],
+            ]],
+        )?;
+        receipt.try_serialize(&mut &mut new_info.try_borrow_mut_data()?[..])?;
+
+        // Close the legacy account
+        let lamports = legacy_info.lamports();
+        **legacy_info.try_borrow_mut_lamports()? = 0;
+        **authority_info.try_borrow_mut_lamports()? += lamports;
+        legacy_info.realloc(0, false)?;
+        legacy_info.assign(&System::id());
+    }
+
+    emit!(ReceiptMigratedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        legacy_receipt: legacy_address,
+        new_receipt: new_address,
+        origin_tx_hash,
+        nonce: receipt.nonce,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Migrated receipt {} to {}", legacy_address, new_address);
+
+    Ok(())
+}
+
+/// Fixed-size form of an origin transaction identifier: 32-byte hashes are used as-is,
+/// anything else (e.g. 64-byte Solana signatures) is reduced with sha256
+pub fn normalize_origin_tx_hash(identifier: &[u8]) -> [u8; 32] {
+    match <[u8; 32]>::try_from(identifier) {
+        Ok(fixed) => fixed,
+        Err(_) => hash(identifier).to_bytes(),
+    }
+}
+
+#[event]
+pub struct ReceiptMigratedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub legacy_receipt: Pubkey,
+    pub new_receipt: Pubkey,
+    pub origin_tx_hash: [u8; 32],
+    pub nonce: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,217 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Token, TokenAccount, Mint};
+use crate::state::{ProgramState, CrossChainConfig, NftMetadata, Blocklist, TrustedPeer};
+use crate::error::UniversalNftError;
+use crate::constants::{ACHIEVEMENT_MINT_SEED, BLOCKLIST_SEED, CROSS_CHAIN_CONFIG_SEED, NFT_METADATA_SEED, PROGRAM_STATE_SEED, TRUSTED_PEER_SEED};
+use crate::utils::ipfs::ipfs_multihash;
+use crate::utils::validation::{validate_nft_fields, validate_recipient_account, validate_recipient_token_account};
+use crate::utils::security::{verify_tss_signature, signed_message};
+use universal_nft_messages::AchievementMint;
+
+/// Mints an achievement NFT awarded by a trusted game contract on another chain
+#[derive(Accounts)]
+#[instruction(peer_chain_id: u64, peer_contract: Vec<u8>, achievement_id: [u8; 32])]
+pub struct MintAchievement<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = !program_state.is_sunset @ UniversalNftError::ProgramSunset
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+
+    #[account(
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
+        bump = cross_chain_config.bump,
+        constraint = !cross_chain_config.is_paused @ UniversalNftError::CrossChainPaused
+    )]
+    pub cross_chain_config: Box<Account<'info, CrossChainConfig>>,
+
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Box<Account<'info, Blocklist>>,
+
+    #[account(
+        mut,
+        seeds = [TRUSTED_PEER_SEED, peer_chain_id.to_le_bytes().as_ref(), peer_contract.as_slice()],
+        bump = trusted_peer.bump,
+        constraint = trusted_peer.remaining_quota() > 0 @ UniversalNftError::AchievementQuotaExceeded
+    )]
+    pub trusted_peer: Box<Account<'info, TrustedPeer>>,
+
+    /// Derived from the award, so each achievement can be minted only once
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = 0,
+        mint::authority = mint,
+        seeds = [
+            ACHIEVEMENT_MINT_SEED,
+            peer_chain_id.to_le_bytes().as_ref(),
+            peer_contract.as_slice(),
+            achievement_id.as_ref()
+        ],
+        bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + NftMetadata::INIT_SPACE,
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub nft_metadata: Box<Account<'info, NftMetadata>>,
+
+    /// CHECK: Recipient validated by token account and bound to the signed message
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Relayer submitting the attested award; pays for the new accounts
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handler(
+    ctx: Context<MintAchievement>,
+    peer_chain_id: u64,
+    peer_contract: Vec<u8>,
+    achievement_id: [u8; 32],
+    metadata_uri: String,
+    name: String,
+    symbol: String,
+    tss_signature: Vec<u8>,
+) -> Result<()> {
+    let program_state = &ctx.accounts.program_state;
+    let cross_chain_config = &ctx.accounts.cross_chain_config;
+    let recipient = ctx.accounts.recipient.key();
+
+    validate_nft_fields(&program_state.validation_limits, &metadata_uri, &name, &symbol)?;
+    require!(
+        program_state.validation_limits.fits_signature(&tss_signature),
+        UniversalNftError::InvalidTssSignature
+    );
+    require!(
+        !ctx.accounts.blocklist.is_account_blocked(&recipient),
+        UniversalNftError::AddressBlocked
+    );
+    validate_recipient_account(&ctx.accounts.recipient)?;
+    validate_recipient_token_account(&ctx.accounts.token_account)?;
+
+    // The TSS attests the award came from the peer contract on its chain
+    let message = signed_message(
+        cross_chain_config.chain_id,
+        &AchievementMint {
+            peer_chain_id,
+            peer_contract: &peer_contract,
+            achievement_id,
+            recipient: recipient.to_bytes(),
+            metadata_uri: &metadata_uri,
+            name: &name,
+            symbol: &symbol,
+        },
+    );
+    let is_valid = verify_tss_signature(&message, &tss_signature, &cross_chain_config.tss_address)?;
+    require!(is_valid, UniversalNftError::InvalidTssSignature);
+
+    let trusted_peer = &mut ctx.accounts.trusted_peer;
+    trusted_peer.minted = trusted_peer
+        .minted
+        .checked_add(1)
+        .ok_or(UniversalNftError::ArithmeticOverflow)?;
+
+    let mint = ctx.accounts.mint.key();
+    let peer_chain_id_bytes = peer_chain_id.to_le_bytes();
+    let mint_bump = ctx.bumps.mint;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        ACHIEVEMENT_MINT_SEED,
+        peer_chain_id_bytes.as_ref(),
+        peer_contract.as_slice(),
+        achievement_id.as_ref(),
+        &[mint_bump],
+    ]];
+    let cpi_accounts = token::MintTo {
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.token_account.to_account_info(),
+        authority: ctx.accounts.mint.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::mint_to(cpi_ctx, 1)?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let nft_metadata = &mut ctx.accounts.nft_metadata;
+    nft_metadata.mint = mint;
+    nft_metadata.original_owner = recipient;
+    nft_metadata.current_owner = recipient;
+    nft_metadata.metadata_uri = metadata_uri;
+    nft_metadata.name = name;
+    nft_metadata.symbol = symbol;
+    nft_metadata.cross_chain_enabled = true;
+    nft_metadata.is_locked = false;
+    nft_metadata.origin_chain_id = peer_chain_id;
+    nft_metadata.origin_contract = peer_contract.clone();
+    nft_metadata.fallback_uri = None;
+    nft_metadata.metadata_multihash = ipfs_multihash(&nft_metadata.metadata_uri)?;
+    nft_metadata.child_count = 0;
+    nft_metadata.origin_token_id = achievement_id;
+    nft_metadata.collection = Pubkey::default();
+    nft_metadata.wrapped_mint = Pubkey::default();
+    nft_metadata.creation_timestamp = timestamp;
+    nft_metadata.bump = ctx.bumps.nft_metadata;
+
+    let program_state = &mut ctx.accounts.program_state;
+    program_state.total_nfts_minted = program_state
+        .total_nfts_minted
+        .checked_add(1)
+        .ok_or(UniversalNftError::ArithmeticOverflow)?;
+
+    emit!(AchievementMintedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: program_state.next_event_sequence(),
+        peer_chain_id,
+        peer_contract,
+        achievement_id,
+        mint,
+        recipient,
+        minted: trusted_peer.minted,
+        timestamp,
+    });
+
+    msg!("Minted achievement {} to {} ({} of {})", mint, recipient, trusted_peer.minted, trusted_peer.mint_quota);
+
+    Ok(())
+}
+
+#[event]
+pub struct AchievementMintedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub peer_chain_id: u64,
+    pub peer_contract: Vec<u8>,
+    pub achievement_id: [u8; 32],
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub minted: u64, // Achievements the peer has minted, this one included
+    pub timestamp: i64,
+}
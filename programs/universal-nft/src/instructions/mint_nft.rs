@@ -1,30 +1,78 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 use anchor_spl::token::{self, Token, TokenAccount, Mint};
-use crate::state::{ProgramState, NftMetadata};
+use crate::state::{ProgramState, NftMetadata, Blocklist, CollectionConfig, MintGuard, MintLimits, WalletMintCounter};
 use crate::error::UniversalNftError;
+use crate::constants::{BLOCKLIST_SEED, COLLECTION_CONFIG_SEED, FREEZE_AUTHORITY_SEED, MINT_GUARD_SEED, MINT_LIMITS_SEED, NFT_METADATA_SEED, PROGRAM_STATE_SEED, WALLET_MINT_COUNTER_SEED};
+use crate::utils::fees::fee_treasury_address;
+use crate::utils::ipfs::ipfs_multihash;
+use crate::utils::validation::validate_nft_fields;
+use crate::config::SELF_CHAIN_ID;
 
 #[derive(Accounts)]
 #[instruction(metadata_uri: String, name: String, symbol: String)]
 pub struct MintNft<'info> {
     #[account(
         mut,
-        seeds = [b"program_state"],
+        seeds = [PROGRAM_STATE_SEED],
         bump = program_state.bump,
-        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = !program_state.is_sunset @ UniversalNftError::ProgramSunset
     )]
     pub program_state: Account<'info, ProgramState>,
 
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    /// Optional collection to mint into; only its creator (or the program authority) may
+    #[account(
+        seeds = [COLLECTION_CONFIG_SEED, collection_config.collection_mint.as_ref()],
+        bump = collection_config.bump,
+        constraint = collection_config.can_manage(&authority.key(), &program_state.authority) @ UniversalNftError::Unauthorized
+    )]
+    pub collection_config: Option<Account<'info, CollectionConfig>>,
+
+    /// CHECK: PDA holding the freeze authority of minted NFTs; only delegate listings freeze with it
+    #[account(seeds = [FREEZE_AUTHORITY_SEED], bump)]
+    pub freeze_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Mint guard PDA, read by the handler; empty until `set_mint_guard` creates it
+    #[account(seeds = [MINT_GUARD_SEED], bump)]
+    pub mint_guard: UncheckedAccount<'info>,
+
+    /// CHECK: Fee treasury PDA, checked by the handler; required when the mint guard charges a fee
+    #[account(mut)]
+    pub fee_treasury: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Mint limits PDA, read by the handler; empty until `set_mint_limits` creates it
+    #[account(seeds = [MINT_LIMITS_SEED], bump)]
+    pub mint_limits: UncheckedAccount<'info>,
+
+    /// Mints by `authority` so far; created on its first mint
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + WalletMintCounter::INIT_SPACE,
+        seeds = [WALLET_MINT_COUNTER_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub wallet_mint_counter: Box<Account<'info, WalletMintCounter>>,
+
     #[account(
         init,
-        payer = authority,
+        payer = payer,
         mint::decimals = 0,
         mint::authority = authority,
+        mint::freeze_authority = freeze_authority,
     )]
     pub mint: Account<'info, Mint>,
 
     #[account(
         init,
-        payer = authority,
+        payer = payer,
         associated_token::mint = mint,
         associated_token::authority = authority,
     )]
@@ -32,16 +80,19 @@ pub struct MintNft<'info> {
 
     #[account(
         init,
-        payer = authority,
+        payer = payer,
         space = 8 + NftMetadata::INIT_SPACE,
-        seeds = [b"nft_metadata", mint.key().as_ref()],
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
         bump
     )]
     pub nft_metadata: Account<'info, NftMetadata>,
 
-    #[account(mut)]
     pub authority: Signer<'info>,
 
+    /// Funds the mint, its token account and metadata
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
     pub system_program: Program<'info, System>,
@@ -59,9 +110,56 @@ pub fn handler(
     let nft_metadata = &mut ctx.accounts.nft_metadata;
 
     // Validate inputs
-    require!(metadata_uri.len() <= 200, UniversalNftError::InvalidMetadataUri);
-    require!(name.len() <= 32, UniversalNftError::InvalidMetadataUri);
-    require!(symbol.len() <= 10, UniversalNftError::InvalidMetadataUri);
+    validate_nft_fields(&program_state.validation_limits, &metadata_uri, &name, &symbol)?;
+    require!(
+        !ctx.accounts.blocklist.is_account_blocked(&ctx.accounts.authority.key()),
+        UniversalNftError::AddressBlocked
+    );
+
+    // Open mints pay the guard's fee and prove work on the mint address
+    let exempt = ctx.accounts.collection_config.is_some() || ctx.accounts.authority.key() == program_state.authority;
+    let mint_guard_info = ctx.accounts.mint_guard.to_account_info();
+    if !exempt && mint_guard_info.owner == &crate::ID {
+        let mint_guard = MintGuard::try_deserialize(&mut &mint_guard_info.try_borrow_data()?[..])?;
+        require!(
+            MintGuard::pow_bits(&ctx.accounts.authority.key(), &ctx.accounts.mint.key())
+                >= u32::from(mint_guard.pow_difficulty),
+            UniversalNftError::InsufficientProofOfWork
+        );
+        if mint_guard.fee_lamports > 0 {
+            let fee_treasury = ctx.accounts.fee_treasury.as_ref().ok_or(UniversalNftError::MissingFeeAccount)?;
+            require_keys_eq!(fee_treasury.key(), fee_treasury_address().0, UniversalNftError::InvalidFeeAccount);
+            let cpi_accounts = system_program::Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: fee_treasury.to_account_info(),
+            };
+            system_program::transfer(
+                CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts),
+                mint_guard.fee_lamports,
+            )?;
+            msg!("Mint fee: {} lamports", mint_guard.fee_lamports);
+        }
+    }
+
+    // Count the mint against the wallet, capped for open mints
+    let mint_limits_info = ctx.accounts.mint_limits.to_account_info();
+    let mint_limits = if mint_limits_info.owner == &crate::ID {
+        Some(MintLimits::try_deserialize(&mut &mint_limits_info.try_borrow_data()?[..])?)
+    } else {
+        None
+    };
+    let wallet_mint_counter = &mut ctx.accounts.wallet_mint_counter;
+    wallet_mint_counter.wallet = ctx.accounts.authority.key();
+    wallet_mint_counter.bump = ctx.bumps.wallet_mint_counter;
+    wallet_mint_counter.record_mint(mint_limits.as_ref().map_or(0, |limits| limits.phase));
+    if let Some(limits) = mint_limits.as_ref().filter(|_| !exempt) {
+        require!(
+            (limits.max_per_wallet == 0 || wallet_mint_counter.total_minted <= limits.max_per_wallet)
+                && (limits.phase_max_per_wallet == 0
+                    || wallet_mint_counter.phase_minted <= limits.phase_max_per_wallet),
+            UniversalNftError::MintLimitReached
+        );
+    }
 
     // Mint 1 NFT token to the authority
     let cpi_accounts = token::MintTo {
@@ -82,7 +180,18 @@ pub fn handler(
     nft_metadata.symbol = symbol;
     nft_metadata.cross_chain_enabled = cross_chain_enabled;
     nft_metadata.is_locked = false;
-    nft_metadata.origin_chain_id = 7565164; // Solana chain ID
+    nft_metadata.origin_chain_id = SELF_CHAIN_ID;
+    nft_metadata.origin_contract = Vec::new();
+    nft_metadata.fallback_uri = None;
+    nft_metadata.metadata_multihash = ipfs_multihash(&nft_metadata.metadata_uri)?;
+    nft_metadata.child_count = 0;
+    nft_metadata.origin_token_id = [0u8; 32];
+    nft_metadata.collection = ctx
+        .accounts
+        .collection_config
+        .as_ref()
+        .map_or(Pubkey::default(), |config| config.collection_mint);
+    nft_metadata.wrapped_mint = Pubkey::default();
     nft_metadata.creation_timestamp = Clock::get()?.unix_timestamp;
     nft_metadata.bump = ctx.bumps.nft_metadata;
 
@@ -1,11 +1,170 @@
+// Every instruction module exposes a `handler`; Anchor needs the glob
+// re-exports for its generated account structs, so the name clash is expected.
+#![allow(ambiguous_glob_reexports)]
+
 pub mod initialize;
 pub mod mint_nft;
 pub mod cross_chain_transfer;
 pub mod receive_cross_chain;
 pub mod verify_ownership;
+pub mod update_blocklist;
+pub mod register_chain;
+pub mod pause_chain;
+pub mod update_config;
+pub mod cross_chain_transfer_batch;
+pub mod cross_chain_transfer_with_value;
+pub mod cross_chain_transfer_with_permit;
+pub mod revert_cross_chain_transfer;
+pub mod create_swap;
+pub mod complete_swap;
+pub mod revert_swap;
+pub mod migrate_receipt;
+pub mod verify_reserves;
+pub mod collection_config;
+pub mod set_opt_out;
+pub mod wrap_existing_nft;
+pub mod unwrap_nft;
+pub mod release_foreign_nft;
+pub mod register_foreign_collection;
+pub mod get_program_version;
+pub mod acknowledge_delivery;
+pub mod acknowledge_failure;
+pub mod preview_transfer;
+pub mod set_cpi_allowlist;
+pub mod set_chain_adapter;
+pub mod lz_receive;
+pub mod reconcile_nft;
+pub mod set_chain_token_gate;
+pub mod set_chain_fee;
+pub mod withdraw_fees;
+pub mod claim_fee_refund;
+pub mod set_chain_finality_delay;
+pub mod stage_native_return;
+pub mod execute_native_return;
+pub mod cancel_native_return;
+pub mod set_chain_optimistic_window;
+pub mod stage_optimistic_receive;
+pub mod claim_optimistic_receive;
+pub mod cancel_optimistic_receive;
+pub mod register_watcher;
+pub mod submit_challenge;
+pub mod resolve_challenge;
+pub mod register_relayer;
+pub mod withdraw_bond;
+pub mod slash_relayer;
+pub mod set_chain_execution_bounty;
+pub mod stage_inbound_message;
+pub mod execute_inbound_message;
+pub mod deposit_rent;
+pub mod withdraw_rent_deposit;
+pub mod sweep_surplus_lamports;
+pub mod expand_metadata;
+pub mod set_fallback_uri;
+pub mod app_data;
+pub mod nesting;
+pub mod set_trusted_peer;
+pub mod mint_achievement;
+pub mod record_snapshot;
+pub mod export_state;
+pub mod find_receipt;
+pub mod set_chain_max_payload;
+pub mod set_collection_royalty;
+pub mod dutch_auction;
+pub mod offer;
+pub mod delegate_listing;
+pub mod fractionalize;
+pub mod set_reward_config;
+pub mod staking;
+pub mod redeem_nft;
+pub mod ticketing;
+pub mod set_localized_uris;
+pub mod oracle_metadata;
+pub mod set_mint_guard;
+pub mod creator_set;
+pub mod symbol_registry;
+pub mod verify_collection;
+pub mod set_operator;
+pub mod bundle_settlement;
 
 pub use initialize::*;
 pub use mint_nft::*;
 pub use cross_chain_transfer::*;
 pub use receive_cross_chain::*;
 pub use verify_ownership::*;
+pub use update_blocklist::*;
+pub use register_chain::*;
+pub use pause_chain::*;
+pub use update_config::*;
+pub use cross_chain_transfer_batch::*;
+pub use cross_chain_transfer_with_value::*;
+pub use cross_chain_transfer_with_permit::*;
+pub use revert_cross_chain_transfer::*;
+pub use create_swap::*;
+pub use complete_swap::*;
+pub use revert_swap::*;
+pub use migrate_receipt::*;
+pub use verify_reserves::*;
+pub use collection_config::*;
+pub use set_opt_out::*;
+pub use wrap_existing_nft::*;
+pub use unwrap_nft::*;
+pub use release_foreign_nft::*;
+pub use register_foreign_collection::*;
+pub use get_program_version::*;
+pub use acknowledge_delivery::*;
+pub use acknowledge_failure::*;
+pub use preview_transfer::*;
+pub use set_cpi_allowlist::*;
+pub use set_chain_adapter::*;
+pub use reconcile_nft::*;
+pub use set_chain_token_gate::*;
+pub use set_chain_fee::*;
+pub use withdraw_fees::*;
+pub use claim_fee_refund::*;
+pub use set_chain_finality_delay::*;
+pub use stage_native_return::*;
+pub use execute_native_return::*;
+pub use cancel_native_return::*;
+pub use set_chain_optimistic_window::*;
+pub use stage_optimistic_receive::*;
+pub use claim_optimistic_receive::*;
+pub use cancel_optimistic_receive::*;
+pub use register_watcher::*;
+pub use submit_challenge::*;
+pub use resolve_challenge::*;
+pub use register_relayer::*;
+pub use withdraw_bond::*;
+pub use slash_relayer::*;
+pub use set_chain_execution_bounty::*;
+pub use stage_inbound_message::*;
+pub use execute_inbound_message::*;
+pub use deposit_rent::*;
+pub use withdraw_rent_deposit::*;
+pub use sweep_surplus_lamports::*;
+pub use expand_metadata::*;
+pub use set_fallback_uri::*;
+pub use app_data::*;
+pub use nesting::*;
+pub use set_trusted_peer::*;
+pub use mint_achievement::*;
+pub use record_snapshot::*;
+pub use export_state::*;
+pub use find_receipt::*;
+pub use set_chain_max_payload::*;
+pub use set_collection_royalty::*;
+pub use dutch_auction::*;
+pub use offer::*;
+pub use delegate_listing::*;
+pub use fractionalize::*;
+pub use set_reward_config::*;
+pub use staking::*;
+pub use redeem_nft::*;
+pub use ticketing::*;
+pub use set_localized_uris::*;
+pub use oracle_metadata::*;
+pub use set_mint_guard::*;
+pub use creator_set::*;
+pub use symbol_registry::*;
+pub use verify_collection::*;
+pub use set_operator::*;
+pub use bundle_settlement::*;
@@ -0,0 +1,260 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Token, TokenAccount, Mint};
+use crate::state::{ProgramState, NftMetadata, ChildIndex};
+use crate::error::UniversalNftError;
+use crate::constants::{CHILD_ESCROW_SEED, CHILD_INDEX_SEED, NFT_METADATA_SEED, PROGRAM_STATE_SEED};
+
+/// Moves a child NFT into escrow under its parent. Bridging, swapping or
+/// releasing the parent is blocked until every child is unequipped.
+#[derive(Accounts)]
+pub struct EquipChild<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [NFT_METADATA_SEED, parent_mint.key().as_ref()],
+        bump = parent_metadata.bump,
+        constraint = !parent_metadata.is_locked @ UniversalNftError::NftLocked
+    )]
+    pub parent_metadata: Box<Account<'info, NftMetadata>>,
+
+    /// CHECK: Mint account validated by token account constraint
+    pub parent_mint: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = parent_token_account.mint == parent_mint.key(),
+        constraint = parent_token_account.owner == owner.key(),
+        constraint = parent_token_account.amount >= 1 @ UniversalNftError::InsufficientTokens
+    )]
+    pub parent_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + ChildIndex::INIT_SPACE,
+        seeds = [CHILD_INDEX_SEED, parent_mint.key().as_ref()],
+        bump
+    )]
+    pub child_index: Box<Account<'info, ChildIndex>>,
+
+    #[account(
+        seeds = [NFT_METADATA_SEED, child_mint.key().as_ref()],
+        bump = child_metadata.bump,
+        constraint = !child_metadata.is_locked @ UniversalNftError::NftLocked
+    )]
+    pub child_metadata: Box<Account<'info, NftMetadata>>,
+
+    #[account(constraint = child_mint.key() != parent_mint.key() @ UniversalNftError::InvalidChild)]
+    pub child_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = owner_child_account.mint == child_mint.key() @ UniversalNftError::InvalidMint,
+        constraint = owner_child_account.owner == owner.key() @ UniversalNftError::Unauthorized,
+        constraint = owner_child_account.amount >= 1 @ UniversalNftError::InsufficientTokens
+    )]
+    pub owner_child_account: Box<Account<'info, TokenAccount>>,
+
+    /// Holds the child while it is equipped; its own authority, like the wrap escrow
+    #[account(
+        init,
+        payer = owner,
+        token::mint = child_mint,
+        token::authority = child_escrow,
+        seeds = [CHILD_ESCROW_SEED, child_mint.key().as_ref()],
+        bump
+    )]
+    pub child_escrow: Box<Account<'info, TokenAccount>>,
+
+    /// Holder of both NFTs; pays for the child index and escrow
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Returns an equipped child to the parent's holder
+#[derive(Accounts)]
+pub struct UnequipChild<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [NFT_METADATA_SEED, parent_mint.key().as_ref()],
+        bump = parent_metadata.bump,
+        constraint = !parent_metadata.is_locked @ UniversalNftError::NftLocked
+    )]
+    pub parent_metadata: Box<Account<'info, NftMetadata>>,
+
+    /// CHECK: Mint account validated by token account constraint
+    pub parent_mint: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = parent_token_account.mint == parent_mint.key(),
+        constraint = parent_token_account.owner == owner.key(),
+        constraint = parent_token_account.amount >= 1 @ UniversalNftError::InsufficientTokens
+    )]
+    pub parent_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [CHILD_INDEX_SEED, parent_mint.key().as_ref()],
+        bump = child_index.bump
+    )]
+    pub child_index: Box<Account<'info, ChildIndex>>,
+
+    pub child_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [CHILD_ESCROW_SEED, child_mint.key().as_ref()],
+        bump
+    )]
+    pub child_escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = child_mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_child_account: Box<Account<'info, TokenAccount>>,
+
+    /// Holder of the parent; receives the child and the escrow's rent
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn equip_handler(ctx: Context<EquipChild>) -> Result<()> {
+    let parent = ctx.accounts.parent_mint.key();
+    let child = ctx.accounts.child_mint.key();
+
+    let child_index = &mut ctx.accounts.child_index;
+    require!(child_index.children.len() < ChildIndex::MAX_CHILDREN, UniversalNftError::TooManyChildren);
+    child_index.parent = parent;
+    child_index.bump = ctx.bumps.child_index;
+    child_index.children.push(child);
+
+    let parent_metadata = &mut ctx.accounts.parent_metadata;
+    parent_metadata.child_count = child_index.children.len() as u8;
+
+    let cpi_accounts = token::Transfer {
+        from: ctx.accounts.owner_child_account.to_account_info(),
+        to: ctx.accounts.child_escrow.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, 1)?;
+
+    emit!(ChildEquippedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        parent,
+        child,
+        owner: ctx.accounts.owner.key(),
+        child_count: parent_metadata.child_count,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Equipped {} on {}", child, parent);
+
+    Ok(())
+}
+
+pub fn unequip_handler(ctx: Context<UnequipChild>) -> Result<()> {
+    let parent = ctx.accounts.parent_mint.key();
+    let child = ctx.accounts.child_mint.key();
+
+    let child_index = &mut ctx.accounts.child_index;
+    let position = child_index
+        .children
+        .iter()
+        .position(|mint| *mint == child)
+        .ok_or(UniversalNftError::ChildNotAttached)?;
+    child_index.children.remove(position);
+
+    let parent_metadata = &mut ctx.accounts.parent_metadata;
+    parent_metadata.child_count = child_index.children.len() as u8;
+
+    // Return the child and close the escrow
+    let escrow_bump = ctx.bumps.child_escrow;
+    let signer_seeds: &[&[&[u8]]] = &[&[CHILD_ESCROW_SEED, child.as_ref(), &[escrow_bump]]];
+
+    let cpi_accounts = token::Transfer {
+        from: ctx.accounts.child_escrow.to_account_info(),
+        to: ctx.accounts.owner_child_account.to_account_info(),
+        authority: ctx.accounts.child_escrow.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(cpi_ctx, 1)?;
+
+    let cpi_accounts = token::CloseAccount {
+        account: ctx.accounts.child_escrow.to_account_info(),
+        destination: ctx.accounts.owner.to_account_info(),
+        authority: ctx.accounts.child_escrow.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::close_account(cpi_ctx)?;
+
+    emit!(ChildUnequippedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        parent,
+        child,
+        owner: ctx.accounts.owner.key(),
+        child_count: parent_metadata.child_count,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Unequipped {} from {}", child, parent);
+
+    Ok(())
+}
+
+#[event]
+pub struct ChildEquippedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub parent: Pubkey,
+    pub child: Pubkey,
+    pub owner: Pubkey,
+    pub child_count: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ChildUnequippedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub parent: Pubkey,
+    pub child: Pubkey,
+    pub owner: Pubkey,
+    pub child_count: u8,
+    pub timestamp: i64,
+}
@@ -0,0 +1,292 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Token, TokenAccount, Mint};
+use crate::state::{ProgramState, NftMetadata, Offer};
+use crate::error::UniversalNftError;
+use crate::constants::{NFT_METADATA_SEED, OFFER_SEED, PROGRAM_STATE_SEED};
+use crate::utils::royalties::{creator_payouts, load_collection_royalty};
+
+/// Escrows a buyer's offer for any NFT the program manages
+#[derive(Accounts)]
+pub struct MakeOffer<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = !program_state.is_sunset @ UniversalNftError::ProgramSunset
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump
+    )]
+    pub nft_metadata: Box<Account<'info, NftMetadata>>,
+
+    /// CHECK: Tied to the NFT by the metadata seeds
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + Offer::INIT_SPACE,
+        seeds = [OFFER_SEED, mint.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub offer: Box<Account<'info, Offer>>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Sells the NFT to the offer's buyer. Royalty creators are passed in
+/// remaining accounts, in the collection's creator order.
+#[derive(Accounts)]
+pub struct AcceptOffer<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump,
+        constraint = !nft_metadata.is_locked @ UniversalNftError::NftLocked
+    )]
+    pub nft_metadata: Box<Account<'info, NftMetadata>>,
+
+    /// CHECK: Must be the royalty PDA of the NFT's collection, created or not;
+    /// checked by `load_collection_royalty`. Omitted for NFTs outside a collection.
+    pub collection_royalty: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [OFFER_SEED, mint.key().as_ref(), buyer.key().as_ref()],
+        bump = offer.bump,
+        has_one = buyer,
+        has_one = mint
+    )]
+    pub offer: Box<Account<'info, Offer>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == mint.key() @ UniversalNftError::InvalidMint,
+        constraint = owner_token_account.owner == owner.key() @ UniversalNftError::Unauthorized,
+        constraint = owner_token_account.amount >= 1 @ UniversalNftError::InsufficientTokens
+    )]
+    pub owner_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+    )]
+    pub buyer_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The offer's buyer, checked by `has_one`; receives the offer's rent
+    #[account(mut)]
+    pub buyer: UncheckedAccount<'info>,
+
+    /// Holder of the NFT; receives the offer less royalties
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Refunds an offer to its buyer. The buyer may cancel at any time; once it
+/// has expired, anyone may close it.
+#[derive(Accounts)]
+pub struct CancelOffer<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [OFFER_SEED, offer.mint.as_ref(), buyer.key().as_ref()],
+        bump = offer.bump,
+        has_one = buyer
+    )]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// CHECK: The offer's buyer, checked by `has_one`; receives the refund
+    #[account(mut)]
+    pub buyer: UncheckedAccount<'info>,
+
+    pub signer: Signer<'info>,
+}
+
+/// Offer `amount` lamports for the NFT until `expires_at`. The lamports are
+/// escrowed in the offer account.
+pub fn make_handler(ctx: Context<MakeOffer>, amount: u64, expires_at: i64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(amount > 0 && expires_at > now, UniversalNftError::InvalidOfferTerms);
+
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.buyer.to_account_info(),
+        to: ctx.accounts.offer.to_account_info(),
+    };
+    system_program::transfer(
+        CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts),
+        amount,
+    )?;
+
+    let offer = &mut ctx.accounts.offer;
+    offer.buyer = ctx.accounts.buyer.key();
+    offer.mint = ctx.accounts.mint.key();
+    offer.amount = amount;
+    offer.expires_at = expires_at;
+    offer.created_at = now;
+    offer.bump = ctx.bumps.offer;
+
+    emit!(OfferMadeEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint: offer.mint,
+        buyer: offer.buyer,
+        amount,
+        expires_at,
+        timestamp: now,
+    });
+
+    msg!("Offer of {} lamports for {} by {}", amount, offer.mint, offer.buyer);
+
+    Ok(())
+}
+
+/// Sell the NFT for the offer, if it is still open and at least `min_amount`.
+/// The collection's creators get their royalty and the holder the rest.
+pub fn accept_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AcceptOffer<'info>>,
+    min_amount: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let offer = &ctx.accounts.offer;
+    require!(!offer.is_expired(now), UniversalNftError::OfferExpired);
+    require!(offer.amount >= min_amount, UniversalNftError::InvalidOfferTerms);
+    let amount = offer.amount;
+
+    let royalty = load_collection_royalty(
+        &ctx.accounts.nft_metadata.collection,
+        ctx.accounts.collection_royalty.as_ref().map(|account| account.as_ref()),
+    )?;
+    let (payouts, royalty_paid) = creator_payouts(royalty.as_ref(), amount, ctx.remaining_accounts)?;
+
+    // Pay out of the escrowed lamports; closing the offer refunds its rent to the buyer
+    let offer_info = offer.to_account_info();
+    **offer_info.try_borrow_mut_lamports()? -= amount;
+    for (creator, cut) in payouts {
+        **creator.try_borrow_mut_lamports()? += cut;
+    }
+    let owner_info = ctx.accounts.owner.to_account_info();
+    **owner_info.try_borrow_mut_lamports()? += amount - royalty_paid;
+
+    let cpi_accounts = token::Transfer {
+        from: ctx.accounts.owner_token_account.to_account_info(),
+        to: ctx.accounts.buyer_token_account.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, 1)?;
+
+    let nft_metadata = &mut ctx.accounts.nft_metadata;
+    nft_metadata.current_owner = ctx.accounts.buyer.key();
+
+    emit!(OfferAcceptedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint: ctx.accounts.mint.key(),
+        buyer: ctx.accounts.buyer.key(),
+        seller: ctx.accounts.owner.key(),
+        amount,
+        royalty_paid,
+        timestamp: now,
+    });
+
+    msg!(
+        "Offer of {} lamports for {} accepted ({} in royalties)",
+        amount,
+        ctx.accounts.mint.key(),
+        royalty_paid
+    );
+
+    Ok(())
+}
+
+pub fn cancel_handler(ctx: Context<CancelOffer>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let offer = &ctx.accounts.offer;
+    let expired = offer.is_expired(now);
+    require!(
+        expired || ctx.accounts.signer.key() == offer.buyer,
+        UniversalNftError::OfferNotExpired
+    );
+
+    emit!(OfferCancelledEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint: offer.mint,
+        buyer: offer.buyer,
+        amount: offer.amount,
+        expired,
+        timestamp: now,
+    });
+
+    msg!("Offer for {} refunded to {}", offer.mint, offer.buyer);
+
+    Ok(())
+}
+
+#[event]
+pub struct OfferMadeEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OfferAcceptedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    pub amount: u64,
+    pub royalty_paid: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OfferCancelledEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub expired: bool, // Closed after expiry rather than by the buyer
+    pub timestamp: i64,
+}
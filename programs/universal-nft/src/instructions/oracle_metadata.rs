@@ -0,0 +1,379 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::token::TokenAccount;
+use crate::state::{
+    ProgramState, CrossChainConfig, NftMetadata, OracleFeed, DynamicMetadata, MetadataVariant,
+    AuditLog, AuditEntry, AuditAction, CpiAllowlist,
+};
+use crate::error::UniversalNftError;
+use crate::constants::{
+    AUDIT_ENTRY_SEED, AUDIT_LOG_SEED, CPI_ALLOWLIST_SEED, CROSS_CHAIN_CONFIG_SEED, DYNAMIC_METADATA_SEED,
+    NFT_METADATA_SEED, ORACLE_FEED_SEED, PROGRAM_STATE_SEED,
+};
+use crate::utils::ipfs::ipfs_multihash;
+use crate::utils::security::{require_allowed_caller, signed_message};
+use universal_nft_messages::MetadataSync;
+
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct ConfigureOracleFeed<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = program_state.authority == authority.key() @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + OracleFeed::INIT_SPACE,
+        seeds = [ORACLE_FEED_SEED, feed_id.as_ref()],
+        bump
+    )]
+    pub oracle_feed: Account<'info, OracleFeed>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PushOracleValue<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [ORACLE_FEED_SEED, oracle_feed.feed_id.as_ref()],
+        bump = oracle_feed.bump,
+        has_one = updater @ UniversalNftError::Unauthorized
+    )]
+    pub oracle_feed: Account<'info, OracleFeed>,
+
+    pub updater: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMetadataVariants<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump,
+        constraint = !nft_metadata.is_locked @ UniversalNftError::NftLocked
+    )]
+    pub nft_metadata: Account<'info, NftMetadata>,
+
+    /// The feed that will select between the variants
+    #[account(
+        seeds = [ORACLE_FEED_SEED, oracle_feed.feed_id.as_ref()],
+        bump = oracle_feed.bump
+    )]
+    pub oracle_feed: Account<'info, OracleFeed>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + DynamicMetadata::INIT_SPACE,
+        seeds = [DYNAMIC_METADATA_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub dynamic_metadata: Account<'info, DynamicMetadata>,
+
+    /// CHECK: Mint account validated by token account constraint
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = token_account.mint == mint.key(),
+        constraint = token_account.owner == owner.key(),
+        constraint = token_account.amount >= 1 @ UniversalNftError::InsufficientTokens
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    /// Holder of the NFT; pays for the account
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless: anyone may apply the feed's latest value. Works while the
+/// NFT is bridged away, so its remote copy can follow through the sync message.
+#[derive(Accounts)]
+pub struct UpdateMetadataFromOracle<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
+        bump = cross_chain_config.bump
+    )]
+    pub cross_chain_config: Account<'info, CrossChainConfig>,
+
+    #[account(
+        mut,
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump
+    )]
+    pub nft_metadata: Account<'info, NftMetadata>,
+
+    #[account(
+        mut,
+        seeds = [DYNAMIC_METADATA_SEED, mint.key().as_ref()],
+        bump = dynamic_metadata.bump
+    )]
+    pub dynamic_metadata: Account<'info, DynamicMetadata>,
+
+    #[account(address = dynamic_metadata.feed @ UniversalNftError::InvalidOracleConfig)]
+    pub oracle_feed: Account<'info, OracleFeed>,
+
+    /// CHECK: Only used to derive the NFT's accounts
+    pub mint: UncheckedAccount<'info>,
+}
+
+/// Create or update the feed `feed_id`. Reconfiguring keeps the latest value,
+/// so a new updater takes over without a gap.
+pub fn configure_feed_handler(
+    ctx: Context<ConfigureOracleFeed>,
+    feed_id: [u8; 32],
+    updater: Pubkey,
+    max_staleness_seconds: u32,
+) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+    require!(
+        updater != Pubkey::default()
+            && (1..=OracleFeed::MAX_STALENESS_SECONDS).contains(&max_staleness_seconds),
+        UniversalNftError::InvalidOracleConfig
+    );
+
+    let oracle_feed = &mut ctx.accounts.oracle_feed;
+    oracle_feed.feed_id = feed_id;
+    oracle_feed.updater = updater;
+    oracle_feed.max_staleness_seconds = max_staleness_seconds;
+    oracle_feed.bump = ctx.bumps.oracle_feed;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::ConfigureOracleFeed,
+        ctx.accounts.authority.key(),
+        &(feed_id, updater, max_staleness_seconds).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(OracleFeedConfiguredEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        feed: oracle_feed.key(),
+        feed_id,
+        updater,
+        max_staleness_seconds,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Oracle feed {} updated by {}, stale after {}s", oracle_feed.key(), updater, max_staleness_seconds);
+
+    Ok(())
+}
+
+/// Publish the feed's latest value
+pub fn push_value_handler(ctx: Context<PushOracleValue>, value: i64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let oracle_feed = &mut ctx.accounts.oracle_feed;
+    oracle_feed.value = value;
+    oracle_feed.updated_at = now;
+
+    emit!(OracleValuePushedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        feed: oracle_feed.key(),
+        value,
+        timestamp: now,
+    });
+
+    msg!("Oracle feed {} value: {}", oracle_feed.key(), value);
+
+    Ok(())
+}
+
+/// Register the URIs an NFT switches between as `oracle_feed` moves, each
+/// selected once the value reaches its `min_value`. The NFT keeps its current
+/// URI until the next `update_metadata_from_oracle`.
+pub fn set_variants_handler(ctx: Context<SetMetadataVariants>, variants: Vec<MetadataVariant>) -> Result<()> {
+    DynamicMetadata::validate(&variants)?;
+    let max_uri_len = ctx.accounts.program_state.validation_limits.max_uri_len as usize;
+    for variant in &variants {
+        require!(
+            !variant.uri.is_empty() && variant.uri.len() <= max_uri_len,
+            UniversalNftError::InvalidMetadataUri
+        );
+        ipfs_multihash(&variant.uri)?;
+    }
+
+    let dynamic_metadata = &mut ctx.accounts.dynamic_metadata;
+    dynamic_metadata.mint = ctx.accounts.mint.key();
+    dynamic_metadata.feed = ctx.accounts.oracle_feed.key();
+    dynamic_metadata.variants = variants;
+    dynamic_metadata.bump = ctx.bumps.dynamic_metadata;
+
+    emit!(MetadataVariantsSetEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint: dynamic_metadata.mint,
+        feed: dynamic_metadata.feed,
+        owner: ctx.accounts.owner.key(),
+        variants: dynamic_metadata.variants.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("{} metadata variants for {}", dynamic_metadata.variants.len(), dynamic_metadata.mint);
+
+    Ok(())
+}
+
+/// Point the NFT at the variant the feed's latest value selects. When that
+/// changes its URI, emit a `MetadataSync` message for relayers to apply to the
+/// NFT's copy on whichever chain holds it.
+pub fn update_handler(ctx: Context<UpdateMetadataFromOracle>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let oracle_feed = &ctx.accounts.oracle_feed;
+    require!(oracle_feed.is_fresh(now), UniversalNftError::StaleOracleValue);
+
+    let dynamic_metadata = &mut ctx.accounts.dynamic_metadata;
+    let variant_index = dynamic_metadata.select(oracle_feed.value);
+    dynamic_metadata.active_variant = variant_index as u8;
+    dynamic_metadata.last_value = oracle_feed.value;
+    dynamic_metadata.updated_at = now;
+
+    let nft_metadata = &mut ctx.accounts.nft_metadata;
+    let uri = &dynamic_metadata.variants[variant_index].uri;
+    if nft_metadata.metadata_uri == *uri {
+        msg!("Metadata for {} already at variant {}", nft_metadata.mint, variant_index);
+        return Ok(());
+    }
+    nft_metadata.metadata_uri = uri.clone();
+    nft_metadata.metadata_multihash = ipfs_multihash(&nft_metadata.metadata_uri)?;
+
+    let sync = MetadataSync {
+        mint: nft_metadata.mint.to_bytes(),
+        origin_chain_id: nft_metadata.origin_chain_id,
+        origin_token_id: nft_metadata.origin_token_id,
+        variant_index: dynamic_metadata.active_variant,
+        oracle_value: oracle_feed.value,
+        oracle_updated_at: oracle_feed.updated_at,
+        metadata_uri: &nft_metadata.metadata_uri,
+    };
+    let message_hash = hash(&signed_message(ctx.accounts.cross_chain_config.chain_id, &sync)).to_bytes();
+
+    emit!(MetadataSyncEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint: nft_metadata.mint,
+        variant_index: dynamic_metadata.active_variant,
+        oracle_value: oracle_feed.value,
+        oracle_updated_at: oracle_feed.updated_at,
+        metadata_uri: nft_metadata.metadata_uri.clone(),
+        bridged: nft_metadata.is_locked,
+        message_hash,
+        timestamp: now,
+    });
+
+    msg!("Metadata for {} now variant {}: {}", nft_metadata.mint, variant_index, nft_metadata.metadata_uri);
+
+    Ok(())
+}
+
+#[event]
+pub struct OracleFeedConfiguredEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub feed: Pubkey,
+    pub feed_id: [u8; 32],
+    pub updater: Pubkey,
+    pub max_staleness_seconds: u32,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OracleValuePushedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub feed: Pubkey,
+    pub value: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MetadataVariantsSetEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub feed: Pubkey,
+    pub owner: Pubkey,
+    pub variants: Vec<MetadataVariant>,
+    pub timestamp: i64,
+}
+
+/// Relayers deliver the `MetadataSync` message hashed in `message_hash` to the
+/// NFT's copy on other chains
+#[event]
+pub struct MetadataSyncEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub variant_index: u8,
+    pub oracle_value: i64,
+    pub oracle_updated_at: i64,
+    pub metadata_uri: String,
+    pub bridged: bool, // Locked here while the NFT is on another chain
+    pub message_hash: [u8; 32],
+    pub timestamp: i64,
+}
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use crate::state::{ProgramState, ChainConfig, AuditLog, AuditEntry, AuditAction, CpiAllowlist};
+use crate::error::UniversalNftError;
+use crate::constants::{AUDIT_ENTRY_SEED, AUDIT_LOG_SEED, CHAIN_CONFIG_SEED, CPI_ALLOWLIST_SEED, PROGRAM_STATE_SEED};
+use crate::utils::security::require_allowed_caller;
+
+#[derive(Accounts)]
+#[instruction(chain_id: u64)]
+pub struct PauseChain<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = program_state.authority == authority.key() @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [CHAIN_CONFIG_SEED, chain_id.to_le_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Shared by `pause_chain` and `unpause_chain`
+pub fn handler(ctx: Context<PauseChain>, chain_id: u64, paused: bool) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    let chain_config = &mut ctx.accounts.chain_config;
+    chain_config.is_paused = paused;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::SetChainPaused,
+        ctx.accounts.authority.key(),
+        &(chain_id, paused).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(ChainPauseUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        chain_id,
+        paused,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Chain {} paused: {}", chain_id, paused);
+
+    Ok(())
+}
+
+#[event]
+pub struct ChainPauseUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub chain_id: u64,
+    pub paused: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,235 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::{ProgramState, CrossChainConfig, NftMetadata, CrossChainTransfer, Blocklist, ChainConfig, CollectionLedger, CollectionConfig, OptOutRegistry, OwnerTransferIndex, PendingTransfers, LocalizedMetadata};
+use crate::error::UniversalNftError;
+use crate::constants::{BLOCKLIST_SEED, CHAIN_CONFIG_SEED, COLLECTION_CONFIG_SEED, COLLECTION_LEDGER_SEED, CROSS_CHAIN_CONFIG_SEED, CROSS_CHAIN_TRANSFER_SEED, LOCALIZED_METADATA_SEED, NFT_METADATA_SEED, OPT_OUT_REGISTRY_SEED, OWNER_TRANSFER_INDEX_SEED, PENDING_TRANSFERS_SEED, PROGRAM_STATE_SEED};
+use crate::utils::validation::{validate_outbound_transfer, validate_nft_mint_supply, validate_payload_size, validate_collection_bridging, validate_not_opted_out, record_collection_velocity, validate_credential, validate_token_gate};
+use crate::adapters::{Adapter, AdapterAccounts, BridgeAdapter};
+use universal_nft_messages::{LocalizedUris, OutboundPayload, OutboundTransfer};
+
+/// Read-only mirror of `InitiateCrossChainTransfer`. Nothing is written, so
+/// wallets can simulate it without the owner's signature.
+#[derive(Accounts)]
+#[instruction(destination_chain_id: u64, recipient_address: Vec<u8>, nonce: u64)]
+pub struct PreviewTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = !program_state.is_sunset @ UniversalNftError::ProgramSunset
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
+        bump = cross_chain_config.bump,
+        constraint = !cross_chain_config.is_paused @ UniversalNftError::CrossChainPaused
+    )]
+    pub cross_chain_config: Account<'info, CrossChainConfig>,
+
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    #[account(
+        seeds = [OPT_OUT_REGISTRY_SEED],
+        bump = opt_out_registry.bump
+    )]
+    pub opt_out_registry: Account<'info, OptOutRegistry>,
+
+    #[account(
+        seeds = [CHAIN_CONFIG_SEED, destination_chain_id.to_le_bytes().as_ref()],
+        bump = destination_chain_config.bump,
+        constraint = !destination_chain_config.is_paused @ UniversalNftError::ChainPaused
+    )]
+    pub destination_chain_config: Account<'info, ChainConfig>,
+
+    #[account(
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump,
+        constraint = nft_metadata.cross_chain_enabled @ UniversalNftError::CrossChainNotEnabled,
+        constraint = !nft_metadata.is_locked @ UniversalNftError::NftLocked,
+        constraint = nft_metadata.child_count == 0 @ UniversalNftError::HasAttachedChildren
+    )]
+    pub nft_metadata: Account<'info, NftMetadata>,
+
+    /// Required when the NFT belongs to a collection
+    #[account(
+        seeds = [COLLECTION_CONFIG_SEED, nft_metadata.collection.as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Option<Account<'info, CollectionConfig>>,
+
+    /// CHECK: May not exist yet; the transfer creates it on first use
+    #[account(
+        seeds = [
+            COLLECTION_LEDGER_SEED,
+            nft_metadata.origin_chain_id.to_le_bytes().as_ref(),
+            nft_metadata.origin_contract.as_slice()
+        ],
+        bump
+    )]
+    pub collection_ledger: UncheckedAccount<'info>,
+
+    /// CHECK: Must not exist yet, otherwise the transfer would fail
+    #[account(
+        seeds = [CROSS_CHAIN_TRANSFER_SEED, mint.key().as_ref(), nonce.to_le_bytes().as_ref()],
+        bump,
+        constraint = transfer_record.data_is_empty() @ UniversalNftError::InvalidNonce
+    )]
+    pub transfer_record: UncheckedAccount<'info>,
+
+    /// CHECK: May not exist yet; the transfer creates it on the owner's first transfer
+    #[account(
+        seeds = [OWNER_TRANSFER_INDEX_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub owner_transfer_index: UncheckedAccount<'info>,
+
+    /// CHECK: May not exist yet; the first transfer creates it
+    #[account(
+        seeds = [PENDING_TRANSFERS_SEED],
+        bump
+    )]
+    pub pending_transfers: UncheckedAccount<'info>,
+
+    /// CHECK: Mint account validated by token account constraint
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = token_account.mint == mint.key(),
+        constraint = token_account.owner == owner.key(),
+        constraint = token_account.amount >= 1 @ UniversalNftError::InsufficientTokens
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: The owner the real transfer will be signed by
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against `CrossChainConfig.credential_requirement`; required only when one is set
+    pub credential: Option<UncheckedAccount<'info>>,
+
+    /// Owner's account of the gate mint; required only when a token gate applies
+    pub gate_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// The NFT's localized metadata URIs, if the real transfer will carry them
+    #[account(
+        seeds = [LOCALIZED_METADATA_SEED, mint.key().as_ref()],
+        bump = localized_metadata.bump
+    )]
+    pub localized_metadata: Option<Box<Account<'info, LocalizedMetadata>>>,
+}
+
+/// What `cross_chain_transfer` would do with the same arguments
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TransferPreview {
+    /// Matches `message_hash` in the resulting `CrossChainTransferEvent`
+    pub message_hash: [u8; 32],
+    /// Rent the owner pays for accounts the transfer creates
+    pub rent_lamports: u64,
+    pub transfer_record: Pubkey,
+    /// Bridge fee the payer is charged, in lamports or base units of `fee_mint`
+    pub fee_amount: u64,
+    pub fee_mint: Option<Pubkey>,
+    /// Size of the outbound payload, checked against the destination's cap
+    pub payload_bytes: u32,
+}
+
+pub fn handler(
+    ctx: Context<PreviewTransfer>,
+    destination_chain_id: u64,
+    recipient_address: Vec<u8>,
+    nonce: u64,
+) -> Result<TransferPreview> {
+    let cross_chain_config = &ctx.accounts.cross_chain_config;
+    let nft_metadata = &ctx.accounts.nft_metadata;
+    let owner = ctx.accounts.owner.key();
+
+    validate_outbound_transfer(
+        cross_chain_config,
+        &ctx.accounts.blocklist,
+        &owner,
+        destination_chain_id,
+        &recipient_address,
+        nonce,
+    )?;
+    validate_nft_mint_supply(&ctx.accounts.mint)?;
+    validate_collection_bridging(
+        nft_metadata,
+        ctx.accounts.collection_config.as_deref(),
+    )?;
+    // Dry run against a copy; the preview never writes
+    record_collection_velocity(ctx.accounts.collection_config.as_deref().cloned().as_mut(), 1)?;
+    validate_not_opted_out(nft_metadata, &ctx.accounts.opt_out_registry)?;
+    validate_credential(cross_chain_config, ctx.accounts.credential.as_deref(), &owner)?;
+    validate_token_gate(
+        cross_chain_config,
+        &ctx.accounts.destination_chain_config,
+        ctx.accounts.gate_token_account.as_deref(),
+        &owner,
+    )?;
+
+    // Rent for the transfer record, plus the ledger on a collection's first
+    // transfer, the index on the owner's first and the backlog registry on
+    // the very first
+    let rent = Rent::get()?;
+    let mut rent_lamports = rent.minimum_balance(8 + CrossChainTransfer::INIT_SPACE);
+    if ctx.accounts.collection_ledger.data_is_empty() {
+        rent_lamports = rent_lamports
+            .checked_add(rent.minimum_balance(8 + CollectionLedger::INIT_SPACE))
+            .ok_or(UniversalNftError::ArithmeticOverflow)?;
+    }
+    if ctx.accounts.owner_transfer_index.data_is_empty() {
+        rent_lamports = rent_lamports
+            .checked_add(rent.minimum_balance(8 + OwnerTransferIndex::INIT_SPACE))
+            .ok_or(UniversalNftError::ArithmeticOverflow)?;
+    }
+    if ctx.accounts.pending_transfers.data_is_empty() {
+        rent_lamports = rent_lamports
+            .checked_add(rent.minimum_balance(8 + PendingTransfers::INIT_SPACE))
+            .ok_or(UniversalNftError::ArithmeticOverflow)?;
+    }
+
+    let adapter = Adapter::for_chain(&ctx.accounts.destination_chain_config, cross_chain_config, AdapterAccounts::default());
+    require!(adapter.can_send(), UniversalNftError::AdapterCannotSend);
+    let localized_uris = ctx
+        .accounts
+        .localized_metadata
+        .as_deref()
+        .map(|localized_metadata| {
+            LocalizedUris::encode(
+                localized_metadata
+                    .entries
+                    .iter()
+                    .map(|entry| (entry.locale.as_str(), entry.uri.as_str())),
+            )
+        })
+        .unwrap_or_default();
+    let outbound = OutboundTransfer {
+        mint: ctx.accounts.mint.key().to_bytes(),
+        owner: owner.to_bytes(),
+        destination_chain_id,
+        recipient_address: &recipient_address,
+        nonce,
+        metadata_uri: &ctx.accounts.nft_metadata.metadata_uri,
+        fallback_uri: ctx.accounts.nft_metadata.fallback_uri.as_deref().unwrap_or_default(),
+        staked_seconds: 0,
+        localized_uris: LocalizedUris::from_encoded(&localized_uris)
+            .map_err(|_| UniversalNftError::InvalidLocalizedUris)?,
+    };
+    validate_payload_size(&ctx.accounts.destination_chain_config, outbound.encoded_len())?;
+    let message_hash = adapter.outbound_message_hash(&outbound);
+
+    let fee = ctx.accounts.destination_chain_config.fee;
+    Ok(TransferPreview {
+        message_hash,
+        rent_lamports,
+        transfer_record: ctx.accounts.transfer_record.key(),
+        fee_amount: fee.per_item()?,
+        fee_mint: fee.mint,
+        payload_bytes: outbound.encoded_len() as u32,
+    })
+}
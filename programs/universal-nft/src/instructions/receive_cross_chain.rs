@@ -1,37 +1,103 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Mint, MintTo, mint_to};
-use crate::state::{ProgramState, CrossChainConfig, NftMetadata, CrossChainReceipt};
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::token::{Token, TokenAccount, Mint};
+use crate::state::{ProgramState, CrossChainConfig, NftMetadata, CrossChainReceipt, Blocklist, CollectionLedger, ForeignCollection, CpiAllowlist, InboundNonce, Relayer, RentDeposit};
 use crate::error::UniversalNftError;
-use crate::utils::security::verify_tss_signature;
+use crate::constants::{BLOCKLIST_SEED, CHAIN_CONFIG_SEED, COLLECTION_LEDGER_SEED, CPI_ALLOWLIST_SEED, CROSS_CHAIN_CONFIG_SEED, CROSS_CHAIN_RECEIPT_SEED, FOREIGN_COLLECTION_SEED, FREEZE_AUTHORITY_SEED, INBOUND_MINT_SEED, INBOUND_NONCE_SEED, NFT_METADATA_SEED, PROGRAM_STATE_SEED, RELAYER_SEED, RENT_DEPOSIT_SEED};
+use crate::utils::inbound::{deliver_inbound_nft, InboundOrigin};
+use crate::utils::ipfs::ipfs_multihash;
+use crate::utils::relayers::require_bonded_relayer;
+use crate::utils::validation::{validate_inbound_nft, validate_origin_chain, validate_recipient_account, validate_recipient_token_account};
+use crate::utils::security::require_allowed_caller;
+use crate::adapters::{Adapter, AdapterAccounts, BridgeAdapter};
+use universal_nft_messages::ReceiveNft;
+use crate::utils::compute::{calculate_compute_budget, require_compute_budget};
 
 #[derive(Accounts)]
-#[instruction(origin_chain_id: u64, origin_tx_hash: Vec<u8>, metadata_uri: String, name: String, symbol: String, original_owner: Vec<u8>, tss_signature: Vec<u8>, nonce: u64)]
+#[instruction(origin_chain_id: u64, origin_tx_hash: [u8; 32], origin_contract: Vec<u8>, origin_token_id: [u8; 32], metadata_uri: String, name: String, symbol: String, original_owner: Vec<u8>, intended_recipient: Pubkey, tss_signature: Vec<u8>, nonce: u64)]
 pub struct ReceiveCrossChain<'info> {
     #[account(
         mut,
-        seeds = [b"program_state"],
+        seeds = [PROGRAM_STATE_SEED],
         bump = program_state.bump,
         constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
     )]
     pub program_state: Account<'info, ProgramState>,
 
     #[account(
-        seeds = [b"cross_chain_config"],
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
         bump = cross_chain_config.bump,
         constraint = !cross_chain_config.is_paused @ UniversalNftError::CrossChainPaused
     )]
     pub cross_chain_config: Account<'info, CrossChainConfig>,
 
     #[account(
-        init,
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    /// CHECK: Origin chain's registry entry, loaded by `validate_origin_chain`
+    /// so an unregistered chain fails with OriginChainNotRegistered
+    #[account(
+        seeds = [CHAIN_CONFIG_SEED, origin_chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub origin_chain_config: UncheckedAccount<'info>,
+
+    // Declared ahead of the other init accounts so a retried message fails
+    // with AlreadyProcessed rather than an opaque "account already in use"
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + CrossChainReceipt::INIT_SPACE,
+        seeds = [CROSS_CHAIN_RECEIPT_SEED, origin_tx_hash.as_ref(), nonce.to_le_bytes().as_ref()],
+        bump,
+        constraint = !receipt.is_processed() @ UniversalNftError::AlreadyProcessed
+    )]
+    pub receipt: Account<'info, CrossChainReceipt>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + InboundNonce::INIT_SPACE,
+        seeds = [INBOUND_NONCE_SEED, origin_chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub inbound_nonce: Box<Account<'info, InboundNonce>>,
+
+    // An NFT returning from a trip out finds its mint, token account and
+    // metadata already in place
+    #[account(
+        init_if_needed,
         payer = authority,
         mint::decimals = 0,
-        mint::authority = authority,
+        mint::authority = mint,
+        mint::freeze_authority = freeze_authority,
+        seeds = [
+            INBOUND_MINT_SEED,
+            origin_chain_id.to_le_bytes().as_ref(),
+            origin_contract.as_slice(),
+            origin_token_id.as_ref()
+        ],
+        bump
     )]
     pub mint: Account<'info, Mint>,
 
+    /// CHECK: PDA holding the freeze authority of the NFTs the program mints,
+    /// so inbound NFTs can be listed without escrow
+    #[account(seeds = [FREEZE_AUTHORITY_SEED], bump)]
+    pub freeze_authority: UncheckedAccount<'info>,
+
     #[account(
-        init,
+        init_if_needed,
         payer = authority,
         associated_token::mint = mint,
         associated_token::authority = recipient,
@@ -39,26 +105,62 @@ pub struct ReceiveCrossChain<'info> {
     pub token_account: Account<'info, TokenAccount>,
 
     #[account(
-        init,
+        init_if_needed,
         payer = authority,
         space = 8 + NftMetadata::INIT_SPACE,
-        seeds = [b"nft_metadata", mint.key().as_ref()],
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
         bump
     )]
     pub nft_metadata: Account<'info, NftMetadata>,
 
     #[account(
-        init,
+        init_if_needed,
         payer = authority,
-        space = 8 + CrossChainReceipt::INIT_SPACE,
-        seeds = [b"cross_chain_receipt", origin_tx_hash.as_slice(), nonce.to_le_bytes().as_ref()],
+        space = 8 + CollectionLedger::INIT_SPACE,
+        seeds = [
+            COLLECTION_LEDGER_SEED,
+            origin_chain_id.to_le_bytes().as_ref(),
+            origin_contract.as_slice()
+        ],
         bump
     )]
-    pub receipt: Account<'info, CrossChainReceipt>,
+    pub collection_ledger: Box<Account<'info, CollectionLedger>>,
+
+    /// Required once the origin collection is registered
+    #[account(
+        seeds = [
+            FOREIGN_COLLECTION_SEED,
+            origin_chain_id.to_le_bytes().as_ref(),
+            origin_contract.as_slice()
+        ],
+        bump = foreign_collection.bump
+    )]
+    pub foreign_collection: Option<Box<Account<'info, ForeignCollection>>>,
+
+    /// CHECK: Posted VAA, required when the origin chain uses the Wormhole adapter; checked by the adapter
+    pub posted_vaa: Option<UncheckedAccount<'info>>,
 
-    /// CHECK: Recipient validated by token account
+    /// CHECK: Recipient validated by token account and bound to the signed message
+    #[account(address = intended_recipient @ UniversalNftError::RecipientMismatch)]
     pub recipient: UncheckedAccount<'info>,
 
+    /// Recipient's rent deposit; when passed, it repays the authority for the
+    /// mint, token account, metadata and receipt this receive creates
+    #[account(
+        mut,
+        seeds = [RENT_DEPOSIT_SEED, recipient.key().as_ref()],
+        bump = recipient_deposit.bump
+    )]
+    pub recipient_deposit: Option<Account<'info, RentDeposit>>,
+
+    /// Required while a relayer requirement is set
+    #[account(
+        mut,
+        seeds = [RELAYER_SEED, authority.key().as_ref()],
+        bump = relayer_registration.bump
+    )]
+    pub relayer_registration: Option<Account<'info, Relayer>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -66,16 +168,24 @@ pub struct ReceiveCrossChain<'info> {
     pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Instructions sysvar, inspected for the compute unit limit and calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
 }
 
-pub fn handler(
-    ctx: Context<ReceiveCrossChain>,
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ReceiveCrossChain<'info>>,
     origin_chain_id: u64,
-    origin_tx_hash: Vec<u8>,
+    origin_tx_hash: [u8; 32],
+    origin_contract: Vec<u8>,
+    origin_token_id: [u8; 32],
     metadata_uri: String,
     name: String,
     symbol: String,
     original_owner: Vec<u8>,
+    intended_recipient: Pubkey,
     tss_signature: Vec<u8>,
     nonce: u64,
 ) -> Result<()> {
@@ -84,56 +194,126 @@ pub fn handler(
     let nft_metadata = &mut ctx.accounts.nft_metadata;
     let receipt = &mut ctx.accounts.receipt;
 
-    // Validate inputs
-    require!(metadata_uri.len() <= 200, UniversalNftError::InvalidMetadataUri);
-    require!(name.len() <= 32, UniversalNftError::InvalidMetadataUri);
-    require!(symbol.len() <= 10, UniversalNftError::InvalidMetadataUri);
-    require!(!origin_tx_hash.is_empty() && origin_tx_hash.len() <= 64, UniversalNftError::InvalidMetadataUri);
-    require!(!original_owner.is_empty() && original_owner.len() <= 64, UniversalNftError::InvalidMetadataUri);
-    require!(!tss_signature.is_empty() && tss_signature.len() <= 128, UniversalNftError::InvalidTssSignature);
-
-    // Construct message for TSS verification
-    let mut message = Vec::new();
-    message.extend_from_slice(&origin_chain_id.to_le_bytes());
-    message.extend_from_slice(&origin_tx_hash);
-    message.extend_from_slice(metadata_uri.as_bytes());
-    message.extend_from_slice(name.as_bytes());
-    message.extend_from_slice(symbol.as_bytes());
-    message.extend_from_slice(&original_owner);
-    message.extend_from_slice(&nonce.to_le_bytes());
-
-    // Verify TSS signature (simplified for demo - in production use proper crypto)
-    let is_valid = verify_tss_signature(
-        &message,
-        &tss_signature,
-        &cross_chain_config.tss_address,
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+    require_bonded_relayer(cross_chain_config, ctx.accounts.relayer_registration.as_mut())?;
+    require_compute_budget(
+        &ctx.accounts.instructions_sysvar,
+        calculate_compute_budget("receive_cross_chain"),
+    )?;
+
+    let origin_chain_config = validate_origin_chain(
+        cross_chain_config,
+        &ctx.accounts.origin_chain_config,
+        origin_chain_id,
     )?;
-    require!(is_valid, UniversalNftError::InvalidTssSignature);
 
-    // Mint the NFT to recipient
-    let cpi_accounts = MintTo {
-        mint: ctx.accounts.mint.to_account_info(),
-        to: ctx.accounts.token_account.to_account_info(),
-        authority: ctx.accounts.authority.to_account_info(),
+    // Empty for adapters that attest through accounts, such as a posted Wormhole VAA
+    require!(
+        program_state.validation_limits.fits_signature(&tss_signature),
+        UniversalNftError::InvalidTssSignature
+    );
+
+    let payload = ReceiveNft {
+        origin_chain_id,
+        origin_tx_hash,
+        origin_contract: &origin_contract,
+        origin_token_id,
+        metadata_uri: &metadata_uri,
+        name: &name,
+        symbol: &symbol,
+        original_owner: &original_owner,
+        intended_recipient: intended_recipient.to_bytes(),
+        nonce,
     };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    mint_to(cpi_ctx, 1)?;
-
-    // Initialize NFT metadata
-    nft_metadata.mint = ctx.accounts.mint.key();
-    nft_metadata.original_owner = ctx.accounts.recipient.key(); // Recipient becomes owner on Solana
-    nft_metadata.current_owner = ctx.accounts.recipient.key();
-    nft_metadata.metadata_uri = metadata_uri;
-    nft_metadata.name = name;
-    nft_metadata.symbol = symbol;
-    nft_metadata.cross_chain_enabled = true; // Cross-chain NFTs are always transferable
-    nft_metadata.is_locked = false;
-    nft_metadata.origin_chain_id = origin_chain_id;
-    nft_metadata.creation_timestamp = Clock::get()?.unix_timestamp;
-    nft_metadata.bump = ctx.bumps.nft_metadata;
+    validate_inbound_nft(&payload, &ctx.accounts.blocklist, &program_state.validation_limits)?;
+    validate_recipient_account(&ctx.accounts.recipient)?;
+    validate_recipient_token_account(&ctx.accounts.token_account)?;
+
+    // Rebuild the message the origin chain's transport attested
+    let adapter = Adapter::for_chain(
+        &origin_chain_config,
+        cross_chain_config,
+        AdapterAccounts {
+            posted_vaa: ctx.accounts.posted_vaa.as_ref().map(|account| account.as_ref()),
+            endpoint_accounts: ctx.remaining_accounts,
+        },
+    );
+    let message = adapter.inbound_message(&payload);
+    adapter.verify_inbound(&message, &tss_signature)?;
+
+    // Nonces from each origin chain must increase
+    ctx.accounts
+        .inbound_nonce
+        .record(origin_chain_id, nonce, ctx.bumps.inbound_nonce)?;
+
+    // Mint the NFT to recipient, or unlock it if it is coming back
+    let returning = deliver_inbound_nft(
+        &ctx.accounts.mint,
+        ctx.bumps.mint,
+        &ctx.accounts.token_account,
+        nft_metadata,
+        &InboundOrigin {
+            chain_id: origin_chain_id,
+            contract: &origin_contract,
+            token_id: origin_token_id,
+        },
+        ctx.accounts.token_program.to_account_info(),
+    )?;
+
+    // Group items from a registered collection under its Solana collection mint
+    let registered = ctx.accounts.collection_ledger.foreign_collection;
+    let collection = if registered == Pubkey::default() {
+        Pubkey::default()
+    } else {
+        let foreign_collection = ctx
+            .accounts
+            .foreign_collection
+            .as_ref()
+            .ok_or(UniversalNftError::ForeignCollectionMismatch)?;
+        require_keys_eq!(
+            foreign_collection.key(),
+            registered,
+            UniversalNftError::ForeignCollectionMismatch
+        );
+        require!(
+            foreign_collection.symbol == symbol,
+            UniversalNftError::ForeignCollectionMismatch
+        );
+        foreign_collection.collection_mint
+    };
+
+    // Update the collection's supply ledger
+    let collection_ledger = &mut ctx.accounts.collection_ledger;
+    collection_ledger.ensure_initialized(origin_chain_id, &origin_contract, ctx.bumps.collection_ledger);
+    collection_ledger.record_inbound()?;
+
+    if returning {
+        nft_metadata.is_locked = false;
+        nft_metadata.current_owner = ctx.accounts.recipient.key();
+    } else {
+        // Initialize NFT metadata
+        nft_metadata.mint = ctx.accounts.mint.key();
+        nft_metadata.original_owner = ctx.accounts.recipient.key(); // Recipient becomes owner on Solana
+        nft_metadata.current_owner = ctx.accounts.recipient.key();
+        nft_metadata.metadata_uri = metadata_uri;
+        nft_metadata.name = name;
+        nft_metadata.symbol = symbol;
+        nft_metadata.cross_chain_enabled = true; // Cross-chain NFTs are always transferable
+        nft_metadata.is_locked = false;
+        nft_metadata.origin_chain_id = origin_chain_id;
+        nft_metadata.origin_contract = origin_contract;
+        nft_metadata.fallback_uri = None;
+        nft_metadata.metadata_multihash = ipfs_multihash(&nft_metadata.metadata_uri)?;
+        nft_metadata.child_count = 0;
+        nft_metadata.origin_token_id = origin_token_id;
+        nft_metadata.collection = collection;
+        nft_metadata.wrapped_mint = Pubkey::default();
+        nft_metadata.creation_timestamp = Clock::get()?.unix_timestamp;
+        nft_metadata.bump = ctx.bumps.nft_metadata;
+    }
 
     // Create receipt
+    receipt.version = CrossChainReceipt::VERSION;
     receipt.origin_chain_id = origin_chain_id;
     receipt.origin_tx_hash = origin_tx_hash;
     receipt.mint = ctx.accounts.mint.key();
@@ -141,21 +321,55 @@ pub fn handler(
     receipt.original_owner = original_owner;
     receipt.nonce = nonce;
     receipt.timestamp = Clock::get()?.unix_timestamp;
-    receipt.tss_signature = tss_signature;
+    receipt.tss_signature_hash = hash(&tss_signature).to_bytes();
     receipt.bump = ctx.bumps.receipt;
 
+    // A pre-funded recipient covers the rent of what was created for them
+    if let Some(recipient_deposit) = ctx.accounts.recipient_deposit.as_mut() {
+        let rent = &ctx.accounts.rent;
+        let created = if returning {
+            vec![receipt.to_account_info()]
+        } else {
+            vec![
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.token_account.to_account_info(),
+                nft_metadata.to_account_info(),
+                receipt.to_account_info(),
+            ]
+        };
+        let cost = created
+            .iter()
+            .map(|info| rent.minimum_balance(info.data_len()))
+            .sum::<u64>();
+        recipient_deposit.balance = recipient_deposit
+            .balance
+            .checked_sub(cost)
+            .ok_or(UniversalNftError::RentDepositInsufficient)?;
+        recipient_deposit.total_spent = recipient_deposit.total_spent.saturating_add(cost);
+
+        let deposit_info = recipient_deposit.to_account_info();
+        let authority_info = ctx.accounts.authority.to_account_info();
+        **deposit_info.try_borrow_mut_lamports()? -= cost;
+        **authority_info.try_borrow_mut_lamports()? += cost;
+    }
+
     // Update program state
-    program_state.total_nfts_minted = program_state
-        .total_nfts_minted
-        .checked_add(1)
-        .ok_or(UniversalNftError::ArithmeticOverflow)?;
+    if !returning {
+        program_state.total_nfts_minted = program_state
+            .total_nfts_minted
+            .checked_add(1)
+            .ok_or(UniversalNftError::ArithmeticOverflow)?;
+    }
 
     // Emit event
     emit!(CrossChainReceiveEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
         mint: ctx.accounts.mint.key(),
         recipient: ctx.accounts.recipient.key(),
         origin_chain_id,
         nonce,
+        tss_signature,
         timestamp: Clock::get()?.unix_timestamp,
     });
 
@@ -167,9 +381,12 @@ pub fn handler(
 
 #[event]
 pub struct CrossChainReceiveEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
     pub mint: Pubkey,
     pub recipient: Pubkey,
     pub origin_chain_id: u64,
     pub nonce: u64,
+    pub tss_signature: Vec<u8>,
     pub timestamp: i64,
 }
\ No newline at end of file
@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::token::TokenAccount;
+use crate::state::{ProgramState, NftMetadata, CrossChainTransfer, SwapOrder, ReconcileAction, AuditLog, AuditEntry, AuditAction, CpiAllowlist};
+use crate::error::UniversalNftError;
+use crate::constants::{AUDIT_ENTRY_SEED, AUDIT_LOG_SEED, CPI_ALLOWLIST_SEED, CROSS_CHAIN_TRANSFER_SEED, NFT_METADATA_SEED, PROGRAM_STATE_SEED, SWAP_ORDER_SEED, VALUE_ESCROW_SEED};
+use crate::utils::security::require_allowed_caller;
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey, transfer_nonce: u64)]
+pub struct ReconcileNft<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = program_state.authority == authority.key() @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [NFT_METADATA_SEED, mint.as_ref()],
+        bump = nft_metadata.bump
+    )]
+    pub nft_metadata: Account<'info, NftMetadata>,
+
+    /// The NFT's most recent outbound transfer, if it has one
+    #[account(
+        seeds = [CROSS_CHAIN_TRANSFER_SEED, mint.as_ref(), transfer_nonce.to_le_bytes().as_ref()],
+        bump = transfer_record.bump
+    )]
+    pub transfer_record: Option<Account<'info, CrossChainTransfer>>,
+
+    /// Required when a pending transfer carries fungible value; checked by the handler
+    pub value_escrow: Option<Account<'info, TokenAccount>>,
+
+    /// The NFT's swap order, if it has one
+    #[account(
+        seeds = [SWAP_ORDER_SEED, mint.as_ref()],
+        bump = swap_order.bump
+    )]
+    pub swap_order: Option<Account<'info, SwapOrder>>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Bring an NFT's lock back in line with its transfer record and swap order.
+/// An NFT must be locked exactly while it has a Pending transfer or an Open
+/// swap, and a Pending transfer carrying value must have it in escrow. The
+/// caller is trusted to pass the NFT's latest transfer record; leaving it out
+/// asserts that the NFT has no pending transfer.
+pub fn handler(ctx: Context<ReconcileNft>, mint: Pubkey, transfer_nonce: u64) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    let transfer_pending = match &ctx.accounts.transfer_record {
+        Some(record) => {
+            let pending = record.status == 0;
+            if pending && record.value_amount > 0 {
+                let escrow = ctx
+                    .accounts
+                    .value_escrow
+                    .as_ref()
+                    .ok_or(UniversalNftError::MissingValueEscrow)?;
+                let (expected, _) = Pubkey::find_program_address(
+                    &[VALUE_ESCROW_SEED, record.key().as_ref()],
+                    &crate::ID,
+                );
+                require_keys_eq!(escrow.key(), expected, UniversalNftError::MissingValueEscrow);
+                require!(
+                    escrow.amount >= record.value_amount,
+                    UniversalNftError::EscrowUnderfunded
+                );
+            }
+            pending
+        }
+        None => false,
+    };
+    let swap_open = ctx
+        .accounts
+        .swap_order
+        .as_ref()
+        .is_some_and(|order| order.status == 0);
+
+    let nft_metadata = &mut ctx.accounts.nft_metadata;
+    let action = match (nft_metadata.is_locked, transfer_pending || swap_open) {
+        (true, false) => {
+            nft_metadata.is_locked = false;
+            ReconcileAction::Unlocked
+        }
+        (false, true) => {
+            nft_metadata.is_locked = true;
+            ReconcileAction::Locked
+        }
+        _ => ReconcileAction::Consistent,
+    };
+
+    let transfer_nonce = ctx.accounts.transfer_record.as_ref().map(|_| transfer_nonce);
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::ReconcileNft,
+        ctx.accounts.authority.key(),
+        &(mint, transfer_nonce, action).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(NftReconciledEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint,
+        transfer_nonce,
+        action,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("NFT {} reconciled: {:?}", mint, action);
+
+    Ok(())
+}
+
+#[event]
+pub struct NftReconciledEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub transfer_nonce: Option<u64>,
+    pub action: ReconcileAction,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::{ProgramState, NftMetadata, CollectionConfig, HolderSnapshot, SnapshotEntry};
+use crate::error::UniversalNftError;
+use crate::constants::{COLLECTION_CONFIG_SEED, HOLDER_SNAPSHOT_SEED, NFT_METADATA_SEED, PROGRAM_STATE_SEED};
+
+/// Records the holders of up to `HolderSnapshot::MAX_ENTRIES` NFTs of a
+/// collection. Each NFT is passed in `remaining_accounts` as a
+/// (token_account, nft_metadata) pair, where the token account holds it.
+#[derive(Accounts)]
+#[instruction(snapshot_id: u64, chunk_index: u16)]
+pub struct RecordSnapshot<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [COLLECTION_CONFIG_SEED, collection_config.collection_mint.as_ref()],
+        bump = collection_config.bump,
+        constraint = collection_config.can_manage(&authority.key(), &program_state.authority) @ UniversalNftError::Unauthorized
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + HolderSnapshot::INIT_SPACE,
+        seeds = [
+            HOLDER_SNAPSHOT_SEED,
+            collection_config.collection_mint.as_ref(),
+            snapshot_id.to_le_bytes().as_ref(),
+            chunk_index.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub snapshot: Account<'info, HolderSnapshot>,
+
+    /// The collection's creator or the program authority; pays for the chunk
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RecordSnapshot<'info>>,
+    snapshot_id: u64,
+    chunk_index: u16,
+) -> Result<()> {
+    let remaining = ctx.remaining_accounts;
+    require!(
+        !remaining.is_empty()
+            && remaining.chunks_exact(2).remainder().is_empty()
+            && remaining.len() / 2 <= HolderSnapshot::MAX_ENTRIES,
+        UniversalNftError::InvalidSnapshotSize
+    );
+
+    let collection_mint = ctx.accounts.collection_config.collection_mint;
+    let mut holders = Vec::with_capacity(remaining.len() / 2);
+    for accounts in remaining.chunks_exact(2) {
+        let token_account = Account::<TokenAccount>::try_from(&accounts[0])?;
+        let nft_metadata = Account::<NftMetadata>::try_from(&accounts[1])?;
+
+        // The metadata account must be the canonical PDA for the held mint
+        let (expected_metadata, _) = Pubkey::find_program_address(
+            &[NFT_METADATA_SEED, token_account.mint.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(
+            accounts[1].key(),
+            expected_metadata,
+            UniversalNftError::InvalidNftMetadata
+        );
+        require_keys_eq!(nft_metadata.collection, collection_mint, UniversalNftError::NotInCollection);
+        require!(token_account.amount >= 1, UniversalNftError::InsufficientTokens);
+        require!(
+            !holders.iter().any(|entry: &SnapshotEntry| entry.mint == token_account.mint),
+            UniversalNftError::InvalidSnapshotSize
+        );
+
+        holders.push(SnapshotEntry {
+            mint: token_account.mint,
+            owner: token_account.owner,
+        });
+    }
+
+    let taken_at = Clock::get()?.unix_timestamp;
+    let snapshot = &mut ctx.accounts.snapshot;
+    snapshot.collection_mint = collection_mint;
+    snapshot.snapshot_id = snapshot_id;
+    snapshot.chunk_index = chunk_index;
+    snapshot.taken_at = taken_at;
+    snapshot.taken_by = ctx.accounts.authority.key();
+    snapshot.bump = ctx.bumps.snapshot;
+    snapshot.holders = holders;
+
+    emit!(SnapshotRecordedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        collection_mint,
+        snapshot_id,
+        chunk_index,
+        holder_count: snapshot.holders.len() as u8,
+        timestamp: taken_at,
+    });
+
+    msg!(
+        "Snapshot {} chunk {} of {}: {} holders",
+        snapshot_id,
+        chunk_index,
+        collection_mint,
+        snapshot.holders.len()
+    );
+
+    Ok(())
+}
+
+#[event]
+pub struct SnapshotRecordedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub collection_mint: Pubkey,
+    pub snapshot_id: u64,
+    pub chunk_index: u16,
+    pub holder_count: u8,
+    pub timestamp: i64,
+}
@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Mint};
+use crate::state::{ProgramState, NftMetadata, CollectionLedger, Redemption};
+use crate::error::UniversalNftError;
+use crate::constants::{COLLECTION_LEDGER_SEED, NFT_METADATA_SEED, PROGRAM_STATE_SEED, REDEMPTION_SEED};
+
+/// Burns an NFT in exchange for what it stands for, such as a physical item
+/// or an event ticket, and records the claim for a fulfillment service.
+#[derive(Accounts)]
+pub struct RedeemNft<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+
+    #[account(
+        mut,
+        close = redeemer,
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump,
+        constraint = !nft_metadata.is_locked @ UniversalNftError::NftLocked,
+        constraint = !nft_metadata.is_wrapped() @ UniversalNftError::WrappedNotRedeemable,
+        constraint = nft_metadata.child_count == 0 @ UniversalNftError::HasAttachedChildren
+    )]
+    pub nft_metadata: Box<Account<'info, NftMetadata>>,
+
+    /// The origin collection's ledger; required for foreign NFTs, whose
+    /// originals stay escrowed at origin like released ones
+    #[account(
+        mut,
+        seeds = [
+            COLLECTION_LEDGER_SEED,
+            nft_metadata.origin_chain_id.to_le_bytes().as_ref(),
+            nft_metadata.origin_contract.as_slice()
+        ],
+        bump = collection_ledger.bump
+    )]
+    pub collection_ledger: Option<Box<Account<'info, CollectionLedger>>>,
+
+    #[account(
+        init,
+        payer = redeemer,
+        space = 8 + Redemption::INIT_SPACE,
+        seeds = [REDEMPTION_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub redemption: Box<Account<'info, Redemption>>,
+
+    #[account(mut)]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = token_account.mint == mint.key() @ UniversalNftError::InvalidMint,
+        constraint = token_account.owner == redeemer.key() @ UniversalNftError::Unauthorized,
+        constraint = token_account.amount >= 1 @ UniversalNftError::InsufficientTokens
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub redeemer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Burn the NFT and open its redemption. `claim_code_hash` is the sha256 of a
+/// code the redeemer keeps to prove the claim is theirs when collecting.
+pub fn handler(ctx: Context<RedeemNft>, claim_code_hash: [u8; 32], details: String) -> Result<()> {
+    require!(
+        claim_code_hash != [0u8; 32] && details.len() <= Redemption::MAX_DETAILS_LEN,
+        UniversalNftError::InvalidRedemption
+    );
+
+    let cpi_accounts = token::Burn {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.token_account.to_account_info(),
+        authority: ctx.accounts.redeemer.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::burn(cpi_ctx, 1)?;
+
+    let nft_metadata = &ctx.accounts.nft_metadata;
+    if !nft_metadata.is_native() {
+        ctx.accounts
+            .collection_ledger
+            .as_mut()
+            .ok_or(UniversalNftError::MissingCollectionLedger)?
+            .record_released()?;
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let redemption = &mut ctx.accounts.redemption;
+    redemption.mint = ctx.accounts.mint.key();
+    redemption.redeemer = ctx.accounts.redeemer.key();
+    redemption.collection = nft_metadata.collection;
+    redemption.origin_chain_id = nft_metadata.origin_chain_id;
+    redemption.origin_token_id = nft_metadata.origin_token_id;
+    redemption.claim_code_hash = claim_code_hash;
+    redemption.redeemed_at = now;
+    redemption.bump = ctx.bumps.redemption;
+    redemption.metadata_uri = nft_metadata.metadata_uri.clone();
+    redemption.name = nft_metadata.name.clone();
+    redemption.details = details;
+
+    emit!(NftRedeemedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint: redemption.mint,
+        redeemer: redemption.redeemer,
+        collection: redemption.collection,
+        claim_code_hash,
+        metadata_uri: redemption.metadata_uri.clone(),
+        details: redemption.details.clone(),
+        timestamp: now,
+    });
+
+    msg!("Redeemed {} for {}", redemption.mint, redemption.redeemer);
+
+    Ok(())
+}
+
+#[event]
+pub struct NftRedeemedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub redeemer: Pubkey,
+    pub collection: Pubkey,
+    pub claim_code_hash: [u8; 32],
+    pub metadata_uri: String,
+    pub details: String,
+    pub timestamp: i64,
+}
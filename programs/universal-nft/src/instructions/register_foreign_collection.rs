@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+use crate::state::{ProgramState, CrossChainConfig, ForeignCollection, CollectionLedger, SymbolRegistry, SymbolReservation, VerificationBadge};
+use crate::error::UniversalNftError;
+use crate::constants::{COLLECTION_LEDGER_SEED, CROSS_CHAIN_CONFIG_SEED, FOREIGN_COLLECTION_SEED, PROGRAM_STATE_SEED, SYMBOL_REGISTRY_SEED, SYMBOL_RESERVATION_SEED};
+use crate::utils::validation::{validate_display_text, MAX_NAME_CHARS, MAX_SYMBOL_CHARS};
+use crate::utils::security::{verify_tss_signature, signed_message};
+use universal_nft_messages::{chain_id, ForeignCollection as ForeignCollectionPayload};
+
+#[derive(Accounts)]
+#[instruction(origin_chain_id: u64, origin_contract: Vec<u8>, name: String, symbol: String)]
+pub struct RegisterForeignCollection<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = !program_state.is_sunset @ UniversalNftError::ProgramSunset
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+
+    #[account(
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
+        bump = cross_chain_config.bump
+    )]
+    pub cross_chain_config: Box<Account<'info, CrossChainConfig>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ForeignCollection::INIT_SPACE,
+        seeds = [
+            FOREIGN_COLLECTION_SEED,
+            origin_chain_id.to_le_bytes().as_ref(),
+            origin_contract.as_slice()
+        ],
+        bump
+    )]
+    pub foreign_collection: Box<Account<'info, ForeignCollection>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + CollectionLedger::INIT_SPACE,
+        seeds = [
+            COLLECTION_LEDGER_SEED,
+            origin_chain_id.to_le_bytes().as_ref(),
+            origin_contract.as_slice()
+        ],
+        bump
+    )]
+    pub collection_ledger: Box<Account<'info, CollectionLedger>>,
+
+    /// CHECK: the `SymbolRegistry`, which may not exist; read in the handler
+    #[account(seeds = [SYMBOL_REGISTRY_SEED], bump)]
+    pub symbol_registry: UncheckedAccount<'info>,
+
+    /// Reserves `symbol` for the collection mint; required while the symbol registry is enabled
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + SymbolReservation::INIT_SPACE,
+        seeds = [SYMBOL_RESERVATION_SEED, SymbolReservation::seed(&symbol).as_ref()],
+        bump
+    )]
+    pub symbol_reservation: Option<Box<Account<'info, SymbolReservation>>>,
+
+    /// The program authority, or anyone presenting a TSS attestation
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handler(
+    ctx: Context<RegisterForeignCollection>,
+    origin_chain_id: u64,
+    origin_contract: Vec<u8>,
+    name: String,
+    symbol: String,
+    royalty_basis_points: u16,
+    royalty_recipient: Vec<u8>,
+    collection_mint: Pubkey,
+    tss_signature: Option<Vec<u8>>,
+) -> Result<()> {
+    require!(
+        origin_chain_id > 0 && !chain_id::is_solana(origin_chain_id),
+        UniversalNftError::UnsupportedChain
+    );
+    require!(
+        !origin_contract.is_empty() && origin_contract.len() <= 32,
+        UniversalNftError::InvalidOriginContract
+    );
+    validate_display_text(&name, MAX_NAME_CHARS)?;
+    validate_display_text(&symbol, MAX_SYMBOL_CHARS)?;
+    require!(
+        royalty_basis_points <= ForeignCollection::MAX_ROYALTY_BASIS_POINTS
+            && royalty_recipient.len() <= 64
+            && (royalty_basis_points == 0 || !royalty_recipient.is_empty()),
+        UniversalNftError::InvalidRoyalty
+    );
+
+    // Anyone but the program authority needs the collection details attested by TSS
+    let payer = ctx.accounts.payer.key();
+    if payer != ctx.accounts.program_state.authority {
+        let tss_signature = tss_signature.ok_or(UniversalNftError::Unauthorized)?;
+        let cross_chain_config = &ctx.accounts.cross_chain_config;
+
+        let message = signed_message(
+            cross_chain_config.chain_id,
+            &ForeignCollectionPayload {
+                origin_chain_id,
+                origin_contract: &origin_contract,
+                name: &name,
+                symbol: &symbol,
+                royalty_basis_points,
+                royalty_recipient: &royalty_recipient,
+                collection_mint: collection_mint.to_bytes(),
+            },
+        );
+
+        let is_valid = verify_tss_signature(
+            &message,
+            &tss_signature,
+            &cross_chain_config.tss_address,
+        )?;
+        require!(is_valid, UniversalNftError::InvalidTssSignature);
+    }
+
+    // Foreign collections have no Solana creator, so the collection mint they
+    // are grouped under holds the symbol
+    let timestamp = Clock::get()?.unix_timestamp;
+    let symbol_registry = &ctx.accounts.symbol_registry;
+    let registry_enabled = symbol_registry.owner == &crate::ID
+        && SymbolRegistry::try_deserialize(&mut &symbol_registry.try_borrow_data()?[..])?.enabled;
+    match ctx.accounts.symbol_reservation.as_deref_mut() {
+        Some(symbol_reservation) => {
+            let bump = ctx.bumps.symbol_reservation.ok_or(UniversalNftError::SymbolReservationRequired)?;
+            symbol_reservation.reserve(&symbol, collection_mint, collection_mint, timestamp, bump)?;
+        }
+        None => require!(!registry_enabled, UniversalNftError::SymbolReservationRequired),
+    }
+
+    let foreign_collection = &mut ctx.accounts.foreign_collection;
+    foreign_collection.origin_chain_id = origin_chain_id;
+    foreign_collection.origin_contract = origin_contract.clone();
+    foreign_collection.name = name;
+    foreign_collection.symbol = symbol;
+    foreign_collection.royalty_basis_points = royalty_basis_points;
+    foreign_collection.royalty_recipient = royalty_recipient;
+    foreign_collection.collection_mint = collection_mint;
+    foreign_collection.registered_by = payer;
+    foreign_collection.registered_at = timestamp;
+    foreign_collection.verification = VerificationBadge::default();
+    foreign_collection.bump = ctx.bumps.foreign_collection;
+
+    // Link the registration so inbound messages can't skip it
+    let collection_ledger = &mut ctx.accounts.collection_ledger;
+    collection_ledger.ensure_initialized(origin_chain_id, &origin_contract, ctx.bumps.collection_ledger);
+    collection_ledger.foreign_collection = foreign_collection.key();
+
+    emit!(ForeignCollectionRegisteredEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        origin_chain_id,
+        origin_contract,
+        collection_mint,
+        registered_by: payer,
+        timestamp,
+    });
+
+    msg!("Registered foreign collection from chain {} as {}", origin_chain_id, collection_mint);
+
+    Ok(())
+}
+
+#[event]
+pub struct ForeignCollectionRegisteredEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub origin_chain_id: u64,
+    pub origin_contract: Vec<u8>,
+    pub collection_mint: Pubkey,
+    pub registered_by: Pubkey,
+    pub timestamp: i64,
+}
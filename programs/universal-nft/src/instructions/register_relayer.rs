@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Token, TokenAccount};
+use crate::state::{ProgramState, CrossChainConfig, Relayer};
+use crate::error::UniversalNftError;
+use crate::constants::{CROSS_CHAIN_CONFIG_SEED, PROGRAM_STATE_SEED, RELAYER_SEED};
+use crate::utils::relayers::relayer_bond_account_address;
+
+#[derive(Accounts)]
+pub struct RegisterRelayer<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
+        bump = cross_chain_config.bump
+    )]
+    pub cross_chain_config: Account<'info, CrossChainConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + Relayer::INIT_SPACE,
+        seeds = [RELAYER_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub relayer: Account<'info, Relayer>,
+
+    /// Owner's account of the bond mint; required for SPL bonds
+    #[account(
+        mut,
+        constraint = owner_bond_account.owner == owner.key() @ UniversalNftError::InvalidRelayerBond
+    )]
+    pub owner_bond_account: Option<Account<'info, TokenAccount>>,
+
+    /// `relayer_bond_account_address(relayer, mint)`; required for SPL bonds
+    #[account(mut)]
+    pub relayer_bond_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Register as a relayer, or add to an existing bond, by posting `amount`
+/// of the configured bond mint. The total must reach the minimum bond.
+pub fn handler(ctx: Context<RegisterRelayer>, amount: u64) -> Result<()> {
+    let requirement = ctx
+        .accounts
+        .cross_chain_config
+        .relayer_requirement
+        .ok_or(UniversalNftError::RelayerModeDisabled)?;
+
+    // An emptied bond may be refilled in whatever mint is now required
+    let relayer = &ctx.accounts.relayer;
+    require!(
+        relayer.bond == 0 || relayer.bond_mint == requirement.bond_mint,
+        UniversalNftError::InvalidRelayerBond
+    );
+
+    match requirement.bond_mint {
+        None => {
+            let cpi_accounts = system_program::Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.relayer.to_account_info(),
+            };
+            system_program::transfer(
+                CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts),
+                amount,
+            )?;
+        }
+        Some(mint) => {
+            let (Some(owner_bond_account), Some(relayer_bond_account)) =
+                (&ctx.accounts.owner_bond_account, &ctx.accounts.relayer_bond_account)
+            else {
+                return err!(UniversalNftError::InvalidRelayerBond);
+            };
+            require_keys_eq!(owner_bond_account.mint, mint, UniversalNftError::InvalidRelayerBond);
+            require_keys_eq!(
+                relayer_bond_account.key(),
+                relayer_bond_account_address(&ctx.accounts.relayer.key(), &mint),
+                UniversalNftError::InvalidRelayerBond
+            );
+
+            let cpi_accounts = token::Transfer {
+                from: owner_bond_account.to_account_info(),
+                to: relayer_bond_account.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            };
+            token::transfer(
+                CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+                amount,
+            )?;
+        }
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let relayer = &mut ctx.accounts.relayer;
+    if relayer.owner == Pubkey::default() {
+        relayer.owner = ctx.accounts.owner.key();
+        relayer.registered_at = now;
+        relayer.bump = ctx.bumps.relayer;
+    }
+    relayer.bond_mint = requirement.bond_mint;
+    relayer.bond = relayer
+        .bond
+        .checked_add(amount)
+        .ok_or(UniversalNftError::ArithmeticOverflow)?;
+    relayer.last_active_at = now;
+    require!(relayer.bond >= requirement.min_bond, UniversalNftError::RelayerNotBonded);
+
+    emit!(RelayerBondedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        relayer: relayer.owner,
+        bond_mint: relayer.bond_mint,
+        bond: relayer.bond,
+        timestamp: now,
+    });
+
+    msg!("Relayer {} bonded {} ({:?})", relayer.owner, relayer.bond, relayer.bond_mint);
+
+    Ok(())
+}
+
+#[event]
+pub struct RelayerBondedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub relayer: Pubkey,
+    pub bond_mint: Option<Pubkey>,
+    pub bond: u64,
+    pub timestamp: i64,
+}
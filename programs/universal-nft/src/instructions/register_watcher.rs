@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::{ProgramState, Watcher};
+use crate::error::UniversalNftError;
+use crate::constants::{PROGRAM_STATE_SEED, WATCHER_SEED};
+
+#[derive(Accounts)]
+pub struct RegisterWatcher<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + Watcher::INIT_SPACE,
+        seeds = [WATCHER_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub watcher: Account<'info, Watcher>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Register as a watcher, or top up an existing bond, by posting `bond`
+/// lamports. The total must reach `Watcher::MIN_BOND`.
+pub fn handler(ctx: Context<RegisterWatcher>, bond: u64) -> Result<()> {
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.owner.to_account_info(),
+        to: ctx.accounts.watcher.to_account_info(),
+    };
+    system_program::transfer(
+        CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts),
+        bond,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let watcher = &mut ctx.accounts.watcher;
+    if watcher.owner == Pubkey::default() {
+        watcher.owner = ctx.accounts.owner.key();
+        watcher.registered_at = now;
+        watcher.bump = ctx.bumps.watcher;
+    }
+    watcher.bond = watcher
+        .bond
+        .checked_add(bond)
+        .ok_or(UniversalNftError::ArithmeticOverflow)?;
+    require!(watcher.is_bonded(), UniversalNftError::WatcherBondTooLow);
+
+    emit!(WatcherRegisteredEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        watcher: watcher.owner,
+        bond: watcher.bond,
+        timestamp: now,
+    });
+
+    msg!("Watcher {} bonded with {} lamports", watcher.owner, watcher.bond);
+
+    Ok(())
+}
+
+#[event]
+pub struct WatcherRegisteredEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub watcher: Pubkey,
+    pub bond: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,231 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Token, TokenAccount, Mint};
+use mpl_token_metadata::accounts::{MasterEdition, Metadata};
+use mpl_token_metadata::instructions::{
+    CreateMasterEditionV3Cpi, CreateMasterEditionV3CpiAccounts, CreateMasterEditionV3InstructionArgs,
+    CreateMetadataAccountV3Cpi, CreateMetadataAccountV3CpiAccounts, CreateMetadataAccountV3InstructionArgs,
+};
+use mpl_token_metadata::types::DataV2;
+use crate::state::{ProgramState, NftMetadata, CrossChainReceipt, CollectionLedger};
+use crate::error::UniversalNftError;
+use crate::constants::{COLLECTION_LEDGER_SEED, NFT_METADATA_SEED, PROGRAM_STATE_SEED, RELEASED_MINT_SEED};
+
+/// Turns a foreign NFT held on Solana into a standard Metaplex NFT and stops tracking it.
+/// The inbound token is burned; the original stays escrowed on its origin chain.
+#[derive(Accounts)]
+pub struct ReleaseForeignNft<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump,
+        constraint = !nft_metadata.is_native() @ UniversalNftError::NotForeignNft,
+        constraint = !nft_metadata.is_locked @ UniversalNftError::NftLocked,
+        constraint = nft_metadata.child_count == 0 @ UniversalNftError::HasAttachedChildren
+    )]
+    pub nft_metadata: Box<Account<'info, NftMetadata>>,
+
+    #[account(
+        mut,
+        seeds = [
+            COLLECTION_LEDGER_SEED,
+            nft_metadata.origin_chain_id.to_le_bytes().as_ref(),
+            nft_metadata.origin_contract.as_slice()
+        ],
+        bump = collection_ledger.bump
+    )]
+    pub collection_ledger: Box<Account<'info, CollectionLedger>>,
+
+    /// The inbound receipt for this NFT; closed when provided
+    #[account(
+        mut,
+        close = rent_receiver,
+        constraint = receipt.mint == mint.key() @ UniversalNftError::InvalidMint
+    )]
+    pub receipt: Option<Box<Account<'info, CrossChainReceipt>>>,
+
+    /// CHECK: Receipts are paid for by the relayer, so their rent goes back to the program authority
+    #[account(mut, address = program_state.authority @ UniversalNftError::Unauthorized)]
+    pub rent_receiver: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = token_account.mint == mint.key() @ UniversalNftError::InvalidMint,
+        constraint = token_account.owner == owner.key() @ UniversalNftError::Unauthorized,
+        constraint = token_account.amount >= 1 @ UniversalNftError::InsufficientTokens
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = released_mint,
+        mint::freeze_authority = released_mint,
+        seeds = [RELEASED_MINT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub released_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = released_mint,
+        associated_token::authority = owner,
+    )]
+    pub released_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Created by the token metadata program
+    #[account(
+        mut,
+        address = Metadata::find_pda(&released_mint.key()).0 @ UniversalNftError::InvalidExternalMetadata
+    )]
+    pub released_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Created by the token metadata program
+    #[account(
+        mut,
+        address = MasterEdition::find_pda(&released_mint.key()).0 @ UniversalNftError::InvalidExternalMetadata
+    )]
+    pub released_master_edition: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Funds the released mint, its Metaplex accounts and the owner's token account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Token metadata program
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler(ctx: Context<ReleaseForeignNft>) -> Result<()> {
+    // Burn the inbound token so it can no longer be bridged
+    let cpi_accounts = token::Burn {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.token_account.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::burn(cpi_ctx, 1)?;
+
+    let mint = ctx.accounts.mint.key();
+    let released_bump = ctx.bumps.released_mint;
+    let signer_seeds: &[&[&[u8]]] = &[&[RELEASED_MINT_SEED, mint.as_ref(), &[released_bump]]];
+
+    let cpi_accounts = token::MintTo {
+        mint: ctx.accounts.released_mint.to_account_info(),
+        to: ctx.accounts.released_token_account.to_account_info(),
+        authority: ctx.accounts.released_mint.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::mint_to(cpi_ctx, 1)?;
+
+    // Recreate the display metadata as a standard Metaplex NFT
+    let nft_metadata = &ctx.accounts.nft_metadata;
+    let metadata_program = ctx.accounts.token_metadata_program.to_account_info();
+    let released_mint = ctx.accounts.released_mint.to_account_info();
+    let released_metadata = ctx.accounts.released_metadata.to_account_info();
+    let payer = ctx.accounts.payer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let rent = ctx.accounts.rent.to_account_info();
+
+    CreateMetadataAccountV3Cpi::new(
+        &metadata_program,
+        CreateMetadataAccountV3CpiAccounts {
+            metadata: &released_metadata,
+            mint: &released_mint,
+            mint_authority: &released_mint,
+            payer: &payer,
+            update_authority: (&released_mint, true),
+            system_program: &system_program,
+            rent: Some(&rent),
+        },
+        CreateMetadataAccountV3InstructionArgs {
+            data: DataV2 {
+                name: nft_metadata.name.clone(),
+                symbol: nft_metadata.symbol.clone(),
+                uri: nft_metadata.metadata_uri.clone(),
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            },
+            is_mutable: false,
+            collection_details: None,
+        },
+    )
+    .invoke_signed(signer_seeds)?;
+
+    // The master edition takes over the mint authority, fixing the supply at one
+    let token_program = ctx.accounts.token_program.to_account_info();
+    CreateMasterEditionV3Cpi::new(
+        &metadata_program,
+        CreateMasterEditionV3CpiAccounts {
+            edition: &ctx.accounts.released_master_edition.to_account_info(),
+            mint: &released_mint,
+            update_authority: &released_mint,
+            mint_authority: &released_mint,
+            payer: &payer,
+            metadata: &released_metadata,
+            token_program: &token_program,
+            system_program: &system_program,
+            rent: Some(&rent),
+        },
+        CreateMasterEditionV3InstructionArgs { max_supply: Some(0) },
+    )
+    .invoke_signed(signer_seeds)?;
+
+    ctx.accounts.collection_ledger.record_released()?;
+
+    emit!(ForeignNftReleasedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint,
+        released_mint: ctx.accounts.released_mint.key(),
+        owner: ctx.accounts.owner.key(),
+        origin_chain_id: nft_metadata.origin_chain_id,
+        origin_contract: nft_metadata.origin_contract.clone(),
+        origin_token_id: nft_metadata.origin_token_id,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Released foreign NFT {} as {}", mint, ctx.accounts.released_mint.key());
+
+    Ok(())
+}
+
+#[event]
+pub struct ForeignNftReleasedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub released_mint: Pubkey,
+    pub owner: Pubkey,
+    pub origin_chain_id: u64,
+    pub origin_contract: Vec<u8>,
+    pub origin_token_id: [u8; 32],
+    pub timestamp: i64,
+}
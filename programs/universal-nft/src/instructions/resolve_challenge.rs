@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use crate::state::{ProgramState, OptimisticReceive, Watcher, Challenge, AuditLog, AuditEntry, AuditAction, CpiAllowlist};
+use crate::error::UniversalNftError;
+use crate::constants::{AUDIT_ENTRY_SEED, AUDIT_LOG_SEED, CHALLENGE_SEED, CPI_ALLOWLIST_SEED, OPTIMISTIC_RECEIVE_SEED, PROGRAM_STATE_SEED, WATCHER_SEED};
+use crate::utils::security::require_allowed_caller;
+
+#[derive(Accounts)]
+#[instruction(origin_tx_hash: [u8; 32], nonce: u64)]
+pub struct ResolveChallenge<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = program_state.authority == authority.key() @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [OPTIMISTIC_RECEIVE_SEED, origin_tx_hash.as_ref(), nonce.to_le_bytes().as_ref()],
+        bump = optimistic_receive.bump,
+        constraint = optimistic_receive.status == 3 @ UniversalNftError::OptimisticReceiveNotChallenged
+    )]
+    pub optimistic_receive: Box<Account<'info, OptimisticReceive>>,
+
+    #[account(
+        mut,
+        close = challenger,
+        seeds = [CHALLENGE_SEED, optimistic_receive.key().as_ref()],
+        bump = challenge.bump
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [WATCHER_SEED, challenge.challenger.as_ref()],
+        bump = challenger_watcher.bump
+    )]
+    pub challenger_watcher: Account<'info, Watcher>,
+
+    /// CHECK: Receives the challenge's rent and, when upheld, the slashed bond
+    #[account(mut, address = challenge.challenger @ UniversalNftError::Unauthorized)]
+    pub challenger: UncheckedAccount<'info>,
+
+    /// Required when upholding a challenge against a watcher's receive
+    #[account(
+        mut,
+        seeds = [WATCHER_SEED, optimistic_receive.submitter.as_ref()],
+        bump = submitter_watcher.bump
+    )]
+    pub submitter_watcher: Option<Account<'info, Watcher>>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Settle a challenge. Upholding it cancels the receive and pays the
+/// submitter's whole bond to the challenger; dismissing it returns the
+/// receive to pending. The challenge account's rent goes back to the
+/// challenger either way.
+pub fn handler(
+    ctx: Context<ResolveChallenge>,
+    origin_tx_hash: [u8; 32],
+    nonce: u64,
+    upheld: bool,
+) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    let submitter = ctx.accounts.optimistic_receive.submitter;
+    let mut slashed = 0;
+    if upheld {
+        ctx.accounts.optimistic_receive.status = 2; // Cancelled
+        ctx.accounts.challenger_watcher.challenges_won = ctx
+            .accounts
+            .challenger_watcher
+            .challenges_won
+            .saturating_add(1);
+
+        // Receives staged by the program authority carry no bond
+        if submitter != ctx.accounts.program_state.authority {
+            let submitter_watcher = ctx
+                .accounts
+                .submitter_watcher
+                .as_mut()
+                .ok_or(UniversalNftError::SubmitterWatcherRequired)?;
+            slashed = submitter_watcher.bond;
+            submitter_watcher.bond = 0;
+            submitter_watcher.times_slashed = submitter_watcher.times_slashed.saturating_add(1);
+
+            let submitter_info = submitter_watcher.to_account_info();
+            let challenger_info = ctx.accounts.challenger.to_account_info();
+            **submitter_info.try_borrow_mut_lamports()? -= slashed;
+            **challenger_info.try_borrow_mut_lamports()? += slashed;
+        }
+    } else {
+        ctx.accounts.optimistic_receive.status = 0; // Pending
+    }
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::ResolveChallenge,
+        ctx.accounts.authority.key(),
+        &(origin_tx_hash, nonce, upheld).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(ChallengeResolvedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        origin_chain_id: ctx.accounts.optimistic_receive.origin_chain_id,
+        origin_tx_hash,
+        nonce,
+        submitter,
+        challenger: ctx.accounts.challenge.challenger,
+        upheld,
+        slashed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Challenge on nonce {} {}, {} lamports slashed", nonce, if upheld { "upheld" } else { "dismissed" }, slashed);
+
+    Ok(())
+}
+
+#[event]
+pub struct ChallengeResolvedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub origin_chain_id: u64,
+    pub origin_tx_hash: [u8; 32],
+    pub nonce: u64,
+    pub submitter: Pubkey,
+    pub challenger: Pubkey,
+    pub upheld: bool,
+    pub slashed: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,220 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::token::{self, Token, TokenAccount};
+use crate::state::{ProgramState, NftMetadata, CrossChainTransfer, CollectionLedger, PendingTransfers, AuditLog, AuditEntry, AuditAction, CpiAllowlist};
+use crate::error::UniversalNftError;
+use crate::constants::{AUDIT_ENTRY_SEED, AUDIT_LOG_SEED, COLLECTION_LEDGER_SEED, CPI_ALLOWLIST_SEED, CROSS_CHAIN_TRANSFER_SEED, NFT_METADATA_SEED, PENDING_TRANSFERS_SEED, PROGRAM_STATE_SEED, STAKE_RECORD_SEED, VALUE_ESCROW_SEED};
+use crate::utils::security::require_allowed_caller;
+use crate::utils::staking::resume_stake;
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey, nonce: u64)]
+pub struct RevertCrossChainTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = program_state.authority == authority.key() @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [CROSS_CHAIN_TRANSFER_SEED, mint.as_ref(), nonce.to_le_bytes().as_ref()],
+        bump = transfer_record.bump,
+        constraint = transfer_record.status == 0 @ UniversalNftError::TransferNotPending
+    )]
+    pub transfer_record: Account<'info, CrossChainTransfer>,
+
+    #[account(
+        mut,
+        seeds = [NFT_METADATA_SEED, mint.as_ref()],
+        bump = nft_metadata.bump
+    )]
+    pub nft_metadata: Account<'info, NftMetadata>,
+
+    #[account(
+        mut,
+        seeds = [
+            COLLECTION_LEDGER_SEED,
+            nft_metadata.origin_chain_id.to_le_bytes().as_ref(),
+            nft_metadata.origin_contract.as_slice()
+        ],
+        bump = collection_ledger.bump
+    )]
+    pub collection_ledger: Account<'info, CollectionLedger>,
+
+    /// Required when the transfer carried fungible value
+    #[account(
+        mut,
+        seeds = [VALUE_ESCROW_SEED, transfer_record.key().as_ref()],
+        bump
+    )]
+    pub value_escrow: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = owner_value_account.owner == transfer_record.original_owner @ UniversalNftError::Unauthorized,
+        constraint = owner_value_account.mint == transfer_record.value_mint @ UniversalNftError::InvalidMint
+    )]
+    pub owner_value_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Receives the escrow account rent; must be the original owner
+    #[account(mut, address = transfer_record.original_owner @ UniversalNftError::Unauthorized)]
+    pub original_owner: UncheckedAccount<'info>,
+
+    /// CHECK: The NFT's stake PDA, resumed by the handler if the transfer
+    /// checkpointed it
+    #[account(mut, seeds = [STAKE_RECORD_SEED, mint.as_ref()], bump)]
+    pub stake_record: UncheckedAccount<'info>,
+
+    /// CHECK: `PendingTransfers` registry, updated if it exists
+    #[account(mut, seeds = [PENDING_TRANSFERS_SEED], bump)]
+    pub pending_transfers: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<RevertCrossChainTransfer>, mint: Pubkey, nonce: u64) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    let transfer_record = &ctx.accounts.transfer_record;
+
+    // Return any escrowed value together with the NFT
+    return_escrowed_value(
+        transfer_record,
+        ctx.accounts.value_escrow.as_ref(),
+        ctx.accounts.owner_value_account.as_ref(),
+        &ctx.accounts.original_owner,
+        &ctx.accounts.token_program,
+        ctx.bumps.value_escrow,
+    )?;
+
+    // Unlock the NFT and mark the transfer as failed
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.nft_metadata.is_locked = false;
+    ctx.accounts.collection_ledger.record_outbound_reverted()?;
+    resume_stake(
+        &ctx.accounts.stake_record,
+        &mut ctx.accounts.program_state,
+        mint,
+        ctx.accounts.transfer_record.original_owner,
+        ctx.accounts.transfer_record.staked_seconds,
+        now,
+    )?;
+    ctx.accounts.transfer_record.status = 2; // Failed
+    PendingTransfers::record_settled_if_exists(&ctx.accounts.pending_transfers, mint, nonce, 2, now)?;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::RevertCrossChainTransfer,
+        ctx.accounts.authority.key(),
+        &(mint, nonce).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(CrossChainTransferRevertedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint,
+        owner: ctx.accounts.transfer_record.original_owner,
+        nonce,
+        value_mint: ctx.accounts.transfer_record.value_mint,
+        value_amount: ctx.accounts.transfer_record.value_amount,
+        timestamp: now,
+    });
+
+    msg!("Cross-chain transfer reverted for mint: {}, nonce: {}", mint, nonce);
+
+    Ok(())
+}
+
+/// Send a failed transfer's escrowed value back to its owner and close the escrow.
+/// Shared by the admin revert and TSS-attested failure acknowledgments.
+pub(crate) fn return_escrowed_value<'info>(
+    transfer_record: &Account<'info, CrossChainTransfer>,
+    value_escrow: Option<&Account<'info, TokenAccount>>,
+    owner_value_account: Option<&Account<'info, TokenAccount>>,
+    original_owner: &UncheckedAccount<'info>,
+    token_program: &Program<'info, Token>,
+    escrow_bump: Option<u8>,
+) -> Result<()> {
+    if transfer_record.value_amount == 0 {
+        return Ok(());
+    }
+
+    let (Some(value_escrow), Some(owner_value_account)) = (value_escrow, owner_value_account) else {
+        return err!(UniversalNftError::MissingValueEscrow);
+    };
+
+    let transfer_key = transfer_record.key();
+    let escrow_bump = escrow_bump.ok_or(UniversalNftError::MissingValueEscrow)?;
+    let signer_seeds: &[&[&[u8]]] = &[&[VALUE_ESCROW_SEED, transfer_key.as_ref(), &[escrow_bump]]];
+
+    let cpi_accounts = token::Transfer {
+        from: value_escrow.to_account_info(),
+        to: owner_value_account.to_account_info(),
+        authority: value_escrow.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(cpi_ctx, transfer_record.value_amount)?;
+
+    let cpi_accounts = token::CloseAccount {
+        account: value_escrow.to_account_info(),
+        destination: original_owner.to_account_info(),
+        authority: value_escrow.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::close_account(cpi_ctx)
+}
+
+#[event]
+pub struct CrossChainTransferRevertedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub nonce: u64,
+    pub value_mint: Pubkey,
+    pub value_amount: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use crate::state::{ProgramState, NftMetadata, SwapOrder, CollectionLedger};
+use crate::error::UniversalNftError;
+use crate::constants::{COLLECTION_LEDGER_SEED, NFT_METADATA_SEED, PROGRAM_STATE_SEED, STAKE_RECORD_SEED, SWAP_ORDER_SEED};
+use crate::utils::staking::resume_stake;
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct RevertSwap<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [NFT_METADATA_SEED, mint.as_ref()],
+        bump = nft_metadata.bump
+    )]
+    pub nft_metadata: Account<'info, NftMetadata>,
+
+    #[account(
+        mut,
+        seeds = [
+            COLLECTION_LEDGER_SEED,
+            nft_metadata.origin_chain_id.to_le_bytes().as_ref(),
+            nft_metadata.origin_contract.as_slice()
+        ],
+        bump = collection_ledger.bump
+    )]
+    pub collection_ledger: Account<'info, CollectionLedger>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [SWAP_ORDER_SEED, mint.as_ref()],
+        bump = swap_order.bump,
+        constraint = swap_order.status == 0 @ UniversalNftError::SwapNotOpen
+    )]
+    pub swap_order: Account<'info, SwapOrder>,
+
+    /// CHECK: Receives the swap order rent; must be the seller
+    #[account(mut, address = swap_order.seller @ UniversalNftError::Unauthorized)]
+    pub seller: UncheckedAccount<'info>,
+
+    /// CHECK: The NFT's stake PDA, resumed by the handler if the swap
+    /// checkpointed it
+    #[account(mut, seeds = [STAKE_RECORD_SEED, mint.as_ref()], bump)]
+    pub stake_record: UncheckedAccount<'info>,
+
+    /// Anyone may revert an expired swap; the NFT always unlocks for the seller
+    pub caller: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RevertSwap>, mint: Pubkey) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now > ctx.accounts.swap_order.deadline,
+        UniversalNftError::SwapNotExpired
+    );
+
+    ctx.accounts.nft_metadata.is_locked = false;
+    ctx.accounts.collection_ledger.record_outbound_reverted()?;
+    resume_stake(
+        &ctx.accounts.stake_record,
+        &mut ctx.accounts.program_state,
+        mint,
+        ctx.accounts.swap_order.seller,
+        ctx.accounts.swap_order.staked_seconds,
+        now,
+    )?;
+
+    emit!(SwapRevertedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint,
+        seller: ctx.accounts.swap_order.seller,
+        timestamp: now,
+    });
+
+    msg!("Swap reverted for mint: {}", mint);
+
+    Ok(())
+}
+
+#[event]
+pub struct SwapRevertedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub seller: Pubkey,
+    pub timestamp: i64,
+}
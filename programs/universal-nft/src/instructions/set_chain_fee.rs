@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::{self, AssociatedToken};
+use anchor_spl::token::{Mint, Token};
+use crate::state::{ProgramState, ChainConfig, BridgeFee, AuditLog, AuditEntry, AuditAction, CpiAllowlist};
+use crate::error::UniversalNftError;
+use crate::constants::{AUDIT_ENTRY_SEED, AUDIT_LOG_SEED, CHAIN_CONFIG_SEED, CPI_ALLOWLIST_SEED, FEE_TREASURY_SEED, OPERATOR_CONFIG_SEED, PROGRAM_STATE_SEED};
+use crate::utils::security::{can_operate, require_allowed_caller};
+use crate::utils::fees::treasury_fee_account_address;
+
+#[derive(Accounts)]
+#[instruction(chain_id: u64)]
+pub struct SetChainFee<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = can_operate(&program_state.authority, &operator_config, &authority.key()) @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// CHECK: The operator config, which may not exist; read by `can_operate`
+    #[account(
+        seeds = [OPERATOR_CONFIG_SEED],
+        bump
+    )]
+    pub operator_config: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [CHAIN_CONFIG_SEED, chain_id.to_le_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
+
+    /// CHECK: Fee treasury PDA; holds no data
+    #[account(
+        mut,
+        seeds = [FEE_TREASURY_SEED],
+        bump
+    )]
+    pub fee_treasury: UncheckedAccount<'info>,
+
+    /// Required for SPL fees
+    pub fee_mint: Option<Account<'info, Mint>>,
+
+    /// CHECK: The fee treasury's associated token account, created if missing; required for SPL fees
+    #[account(mut)]
+    pub treasury_fee_account: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    /// The program authority or its operator
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Charge `fee` for each NFT bridged to `chain_id`. Lamport fees need the
+/// treasury to be rent exempt, so the authority tops it up; SPL fees need the
+/// treasury's token account, which is created if missing.
+pub fn handler(ctx: Context<SetChainFee>, chain_id: u64, fee: BridgeFee) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    fee.per_item()?;
+    match fee.mint {
+        None => {
+            let minimum = Rent::get()?.minimum_balance(0);
+            let shortfall = minimum.saturating_sub(ctx.accounts.fee_treasury.lamports());
+            if shortfall > 0 {
+                let cpi_accounts = system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.fee_treasury.to_account_info(),
+                };
+                system_program::transfer(
+                    CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts),
+                    shortfall,
+                )?;
+            }
+        }
+        Some(mint) => {
+            let (Some(fee_mint), Some(treasury_fee_account)) =
+                (&ctx.accounts.fee_mint, &ctx.accounts.treasury_fee_account)
+            else {
+                return err!(UniversalNftError::InvalidFeeConfig);
+            };
+            require_keys_eq!(fee_mint.key(), mint, UniversalNftError::InvalidFeeConfig);
+            require_keys_eq!(
+                treasury_fee_account.key(),
+                treasury_fee_account_address(&mint),
+                UniversalNftError::InvalidFeeAccount
+            );
+
+            let cpi_accounts = associated_token::Create {
+                payer: ctx.accounts.authority.to_account_info(),
+                associated_token: treasury_fee_account.to_account_info(),
+                authority: ctx.accounts.fee_treasury.to_account_info(),
+                mint: fee_mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            };
+            associated_token::create_idempotent(CpiContext::new(
+                ctx.accounts.associated_token_program.to_account_info(),
+                cpi_accounts,
+            ))?;
+        }
+    }
+
+    ctx.accounts.chain_config.fee = fee;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::SetChainFee,
+        ctx.accounts.authority.key(),
+        &(chain_id, fee).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(ChainFeeUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        chain_id,
+        fee,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Chain {} fee: {:?}", chain_id, fee);
+
+    Ok(())
+}
+
+#[event]
+pub struct ChainFeeUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub chain_id: u64,
+    pub fee: BridgeFee,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
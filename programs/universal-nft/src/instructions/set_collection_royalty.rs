@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use crate::state::{ProgramState, CollectionConfig, CollectionRoyalty, RoyaltyShare, CollectionAction, CreatorApproval};
+use crate::error::UniversalNftError;
+use crate::constants::{COLLECTION_CONFIG_SEED, COLLECTION_ROYALTY_SEED, CREATOR_SET_SEED, PROGRAM_STATE_SEED};
+use crate::utils::creators::{authorize_collection_action, load_creator_set};
+
+#[derive(Accounts)]
+pub struct SetCollectionRoyalty<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [COLLECTION_CONFIG_SEED, collection_config.collection_mint.as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + CollectionRoyalty::INIT_SPACE,
+        seeds = [COLLECTION_ROYALTY_SEED, collection_config.collection_mint.as_ref()],
+        bump
+    )]
+    pub collection_royalty: Account<'info, CollectionRoyalty>,
+
+    /// CHECK: the collection's `CreatorSet`, which may not exist; read by `load_creator_set`
+    #[account(seeds = [CREATOR_SET_SEED, collection_config.collection_mint.as_ref()], bump)]
+    pub creator_set: UncheckedAccount<'info>,
+
+    /// The creators' approval of this royalty; required once the creator set is active
+    #[account(mut)]
+    pub creator_approval: Option<Account<'info, CreatorApproval>>,
+
+    /// The collection's creator, a member of its creator set, or the program
+    /// authority; pays for the account
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Set the royalty sales of the collection's NFTs pay, split between up to
+/// `CollectionRoyalty::MAX_CREATORS` creators. Replaces any earlier setting;
+/// `basis_points` of 0 with no creators waives royalties.
+pub fn handler(
+    ctx: Context<SetCollectionRoyalty>,
+    basis_points: u16,
+    creators: Vec<RoyaltyShare>,
+) -> Result<()> {
+    CollectionRoyalty::validate(basis_points, &creators)?;
+    let creator_set = load_creator_set(&ctx.accounts.creator_set)?;
+    authorize_collection_action(
+        &ctx.accounts.collection_config,
+        creator_set.as_ref(),
+        ctx.accounts.creator_approval.as_deref_mut(),
+        &ctx.accounts.signer.key(),
+        &ctx.accounts.program_state.authority,
+        CollectionAction::SetRoyalty,
+        &(basis_points, &creators).try_to_vec()?,
+    )?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let collection_royalty = &mut ctx.accounts.collection_royalty;
+    collection_royalty.collection_mint = ctx.accounts.collection_config.collection_mint;
+    collection_royalty.basis_points = basis_points;
+    collection_royalty.creators = creators.clone();
+    collection_royalty.updated_by = ctx.accounts.signer.key();
+    collection_royalty.updated_at = timestamp;
+    collection_royalty.bump = ctx.bumps.collection_royalty;
+
+    emit!(CollectionRoyaltyUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        collection_mint: collection_royalty.collection_mint,
+        basis_points,
+        creators,
+        updated_by: collection_royalty.updated_by,
+        timestamp,
+    });
+
+    msg!(
+        "Collection {} royalty: {} bps to {} creators",
+        collection_royalty.collection_mint,
+        basis_points,
+        collection_royalty.creators.len()
+    );
+
+    Ok(())
+}
+
+#[event]
+pub struct CollectionRoyaltyUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub collection_mint: Pubkey,
+    pub basis_points: u16,
+    pub creators: Vec<RoyaltyShare>,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
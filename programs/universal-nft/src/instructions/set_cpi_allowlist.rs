@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use crate::state::{ProgramState, CpiAllowlist, AuditLog, AuditEntry, AuditAction};
+use crate::error::UniversalNftError;
+use crate::constants::{AUDIT_ENTRY_SEED, AUDIT_LOG_SEED, CPI_ALLOWLIST_SEED, PROGRAM_STATE_SEED};
+use crate::utils::security::require_allowed_caller;
+
+#[derive(Accounts)]
+pub struct SetCpiAllowlist<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = program_state.authority == authority.key() @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Replace the allowlist wholesale; an empty list with `enforced` set
+/// closes sensitive instructions to every CPI caller
+pub fn handler(ctx: Context<SetCpiAllowlist>, enforced: bool, programs: Vec<Pubkey>) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    require!(
+        programs.len() <= CpiAllowlist::MAX_PROGRAMS,
+        UniversalNftError::CpiAllowlistFull
+    );
+
+    let mut deduped: Vec<Pubkey> = Vec::with_capacity(programs.len());
+    for program in &programs {
+        if !deduped.contains(program) {
+            deduped.push(*program);
+        }
+    }
+
+    let cpi_allowlist = &mut ctx.accounts.cpi_allowlist;
+    cpi_allowlist.enforced = enforced;
+    cpi_allowlist.programs = deduped;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::SetCpiAllowlist,
+        ctx.accounts.authority.key(),
+        &(enforced, &cpi_allowlist.programs).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(CpiAllowlistUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        enforced,
+        programs: cpi_allowlist.programs.clone(),
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("CPI allowlist enforced: {}, programs: {}", enforced, cpi_allowlist.programs.len());
+
+    Ok(())
+}
+
+#[event]
+pub struct CpiAllowlistUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub enforced: bool,
+    pub programs: Vec<Pubkey>,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::{ProgramState, NftMetadata};
+use crate::error::UniversalNftError;
+use crate::constants::{NFT_METADATA_SEED, PROGRAM_STATE_SEED};
+use crate::utils::ipfs::ipfs_multihash;
+
+#[derive(Accounts)]
+pub struct SetFallbackUri<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump,
+        constraint = !nft_metadata.is_locked @ UniversalNftError::NftLocked
+    )]
+    pub nft_metadata: Account<'info, NftMetadata>,
+
+    /// CHECK: Mint account validated by token account constraint
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = token_account.mint == mint.key(),
+        constraint = token_account.owner == owner.key(),
+        constraint = token_account.amount >= 1 @ UniversalNftError::InsufficientTokens
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    /// Holder of the NFT
+    pub owner: Signer<'info>,
+}
+
+/// Set or clear the second host wallets and destination chains fall back to
+/// when `metadata_uri` stops resolving
+pub fn handler(ctx: Context<SetFallbackUri>, fallback_uri: Option<String>) -> Result<()> {
+    if let Some(uri) = &fallback_uri {
+        require!(
+            !uri.is_empty()
+                && uri.len() <= ctx.accounts.program_state.validation_limits.max_uri_len as usize,
+            UniversalNftError::InvalidMetadataUri
+        );
+        ipfs_multihash(uri)?;
+    }
+
+    let nft_metadata = &mut ctx.accounts.nft_metadata;
+    nft_metadata.fallback_uri = fallback_uri;
+
+    emit!(FallbackUriUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint: nft_metadata.mint,
+        owner: ctx.accounts.owner.key(),
+        fallback_uri: nft_metadata.fallback_uri.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Fallback URI of {}: {:?}", nft_metadata.mint, nft_metadata.fallback_uri);
+
+    Ok(())
+}
+
+#[event]
+pub struct FallbackUriUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub fallback_uri: Option<String>,
+    pub timestamp: i64,
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::{ProgramState, NftMetadata, LocalizedMetadata, LocalizedUri};
+use crate::error::UniversalNftError;
+use crate::constants::{LOCALIZED_METADATA_SEED, NFT_METADATA_SEED, PROGRAM_STATE_SEED};
+use crate::utils::ipfs::ipfs_multihash;
+
+#[derive(Accounts)]
+pub struct SetLocalizedUris<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump,
+        constraint = !nft_metadata.is_locked @ UniversalNftError::NftLocked
+    )]
+    pub nft_metadata: Account<'info, NftMetadata>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + LocalizedMetadata::INIT_SPACE,
+        seeds = [LOCALIZED_METADATA_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub localized_metadata: Account<'info, LocalizedMetadata>,
+
+    /// CHECK: Mint account validated by token account constraint
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = token_account.mint == mint.key(),
+        constraint = token_account.owner == owner.key(),
+        constraint = token_account.amount >= 1 @ UniversalNftError::InsufficientTokens
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    /// Holder of the NFT; pays for the account
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Replace the NFT's localized metadata URIs; an empty list clears them
+pub fn handler(ctx: Context<SetLocalizedUris>, entries: Vec<LocalizedUri>) -> Result<()> {
+    LocalizedMetadata::validate(&entries)?;
+    let max_uri_len = ctx.accounts.program_state.validation_limits.max_uri_len as usize;
+    for entry in &entries {
+        require!(
+            !entry.uri.is_empty() && entry.uri.len() <= max_uri_len,
+            UniversalNftError::InvalidMetadataUri
+        );
+        ipfs_multihash(&entry.uri)?;
+    }
+
+    let localized_metadata = &mut ctx.accounts.localized_metadata;
+    localized_metadata.mint = ctx.accounts.mint.key();
+    localized_metadata.entries = entries;
+    localized_metadata.updated_at = Clock::get()?.unix_timestamp;
+    localized_metadata.bump = ctx.bumps.localized_metadata;
+
+    emit!(LocalizedUrisUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint: localized_metadata.mint,
+        owner: ctx.accounts.owner.key(),
+        entries: localized_metadata.entries.clone(),
+        timestamp: localized_metadata.updated_at,
+    });
+
+    msg!("{} localized URIs for {}", localized_metadata.entries.len(), localized_metadata.mint);
+
+    Ok(())
+}
+
+#[event]
+pub struct LocalizedUrisUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub entries: Vec<LocalizedUri>,
+    pub timestamp: i64,
+}
+
+/// Localized URIs a `cross_chain_transfer` carried, so relayers can rebuild
+/// the transfer's `message_hash`
+#[event]
+pub struct LocalizedUrisTransferredEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub nonce: u64,
+    pub entries: Vec<LocalizedUri>,
+    pub timestamp: i64,
+}
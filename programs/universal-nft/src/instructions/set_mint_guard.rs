@@ -0,0 +1,238 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use crate::state::{ProgramState, MintGuard, MintLimits, AuditLog, AuditEntry, AuditAction, CpiAllowlist};
+use crate::error::UniversalNftError;
+use crate::constants::{AUDIT_ENTRY_SEED, AUDIT_LOG_SEED, CPI_ALLOWLIST_SEED, MINT_GUARD_SEED, MINT_LIMITS_SEED, OPERATOR_CONFIG_SEED, PROGRAM_STATE_SEED};
+use crate::utils::security::{can_operate, require_allowed_caller};
+
+/// Creates the mint guard on first use
+#[derive(Accounts)]
+pub struct SetMintGuard<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = can_operate(&program_state.authority, &operator_config, &authority.key()) @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// CHECK: The operator config, which may not exist; read by `can_operate`
+    #[account(
+        seeds = [OPERATOR_CONFIG_SEED],
+        bump
+    )]
+    pub operator_config: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + MintGuard::INIT_SPACE,
+        seeds = [MINT_GUARD_SEED],
+        bump
+    )]
+    pub mint_guard: Account<'info, MintGuard>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    /// The program authority or its operator
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Creates the mint limits on first use
+#[derive(Accounts)]
+pub struct SetMintLimits<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = can_operate(&program_state.authority, &operator_config, &authority.key()) @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// CHECK: The operator config, which may not exist; read by `can_operate`
+    #[account(
+        seeds = [OPERATOR_CONFIG_SEED],
+        bump
+    )]
+    pub operator_config: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + MintLimits::INIT_SPACE,
+        seeds = [MINT_LIMITS_SEED],
+        bump
+    )]
+    pub mint_limits: Account<'info, MintLimits>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    /// The program authority or its operator
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Charge open mints `fee_lamports` and require `pow_difficulty` leading zero
+/// bits of proof of work on the mint address. Zero for both turns the guard off.
+pub fn guard_handler(ctx: Context<SetMintGuard>, fee_lamports: u64, pow_difficulty: u8) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+    require!(
+        fee_lamports <= MintGuard::MAX_FEE_LAMPORTS && pow_difficulty <= MintGuard::MAX_POW_DIFFICULTY,
+        UniversalNftError::InvalidMintGuard
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let mint_guard = &mut ctx.accounts.mint_guard;
+    mint_guard.fee_lamports = fee_lamports;
+    mint_guard.pow_difficulty = pow_difficulty;
+    mint_guard.updated_at = now;
+    mint_guard.bump = ctx.bumps.mint_guard;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::SetMintGuard,
+        ctx.accounts.authority.key(),
+        &(fee_lamports, pow_difficulty).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(MintGuardUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        fee_lamports,
+        pow_difficulty,
+        authority: ctx.accounts.authority.key(),
+        timestamp: now,
+    });
+
+    msg!("Mint guard: {} lamport fee, {} bits of proof of work", fee_lamports, pow_difficulty);
+
+    Ok(())
+}
+
+/// Cap open mints at `max_per_wallet` per wallet overall and
+/// `phase_max_per_wallet` within `phase`; 0 leaves a cap off. Changing
+/// `phase` starts every wallet's phase count again.
+pub fn limits_handler(
+    ctx: Context<SetMintLimits>,
+    max_per_wallet: u32,
+    phase: u16,
+    phase_max_per_wallet: u32,
+) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+    require!(
+        max_per_wallet == 0 || phase_max_per_wallet <= max_per_wallet,
+        UniversalNftError::InvalidMintLimits
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let mint_limits = &mut ctx.accounts.mint_limits;
+    mint_limits.max_per_wallet = max_per_wallet;
+    mint_limits.phase = phase;
+    mint_limits.phase_max_per_wallet = phase_max_per_wallet;
+    mint_limits.updated_at = now;
+    mint_limits.bump = ctx.bumps.mint_limits;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::SetMintLimits,
+        ctx.accounts.authority.key(),
+        &(max_per_wallet, phase, phase_max_per_wallet).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(MintLimitsUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        max_per_wallet,
+        phase,
+        phase_max_per_wallet,
+        authority: ctx.accounts.authority.key(),
+        timestamp: now,
+    });
+
+    msg!(
+        "Mint limits: {} per wallet, {} per wallet in phase {}",
+        max_per_wallet,
+        phase_max_per_wallet,
+        phase
+    );
+
+    Ok(())
+}
+
+#[event]
+pub struct MintGuardUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub fee_lamports: u64,
+    pub pow_difficulty: u8,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintLimitsUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub max_per_wallet: u32,
+    pub phase: u16,
+    pub phase_max_per_wallet: u32,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
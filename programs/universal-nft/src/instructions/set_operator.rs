@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use crate::state::{ProgramState, OperatorConfig, AuditLog, AuditEntry, AuditAction, CpiAllowlist};
+use crate::error::UniversalNftError;
+use crate::constants::{AUDIT_ENTRY_SEED, AUDIT_LOG_SEED, CPI_ALLOWLIST_SEED, OPERATOR_CONFIG_SEED, PROGRAM_STATE_SEED};
+use crate::utils::security::require_allowed_caller;
+
+/// Creates the operator config on first use. Only the program authority may
+/// name the operator; the operator cannot replace itself.
+#[derive(Accounts)]
+pub struct SetOperator<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = program_state.authority == authority.key() @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + OperatorConfig::INIT_SPACE,
+        seeds = [OPERATOR_CONFIG_SEED],
+        bump
+    )]
+    pub operator_config: Account<'info, OperatorConfig>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Delegate operational settings (chain fees, execution bounties, payload
+/// caps, mint guard and limits, staking rewards) to `operator`, so the
+/// authority key that controls the TSS, gateway and pause can stay cold. The
+/// default pubkey revokes the operator.
+pub fn handler(ctx: Context<SetOperator>, operator: Pubkey) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+    require_keys_neq!(operator, ctx.accounts.program_state.authority, UniversalNftError::InvalidOperator);
+
+    let now = Clock::get()?.unix_timestamp;
+    let operator_config = &mut ctx.accounts.operator_config;
+    let old_operator = operator_config.operator;
+    operator_config.operator = operator;
+    operator_config.updated_at = now;
+    operator_config.bump = ctx.bumps.operator_config;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::SetOperator,
+        ctx.accounts.authority.key(),
+        &operator.try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(OperatorUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        old_operator,
+        new_operator: operator,
+        authority: ctx.accounts.authority.key(),
+        timestamp: now,
+    });
+
+    msg!("Operator updated from {} to {}", old_operator, operator);
+
+    Ok(())
+}
+
+#[event]
+pub struct OperatorUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub old_operator: Pubkey,
+    pub new_operator: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use crate::state::{ProgramState, CollectionConfig, OptOutRegistry, OptOutSubject};
+use crate::error::UniversalNftError;
+use crate::constants::{COLLECTION_CONFIG_SEED, OPT_OUT_REGISTRY_SEED, PROGRAM_STATE_SEED};
+
+#[derive(Accounts)]
+pub struct SetOptOut<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [OPT_OUT_REGISTRY_SEED],
+        bump = opt_out_registry.bump
+    )]
+    pub opt_out_registry: Account<'info, OptOutRegistry>,
+
+    /// Required for collection opt-outs
+    #[account(
+        seeds = [COLLECTION_CONFIG_SEED, collection_config.collection_mint.as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Option<Account<'info, CollectionConfig>>,
+
+    /// The creator opting out, or the creator of the collection
+    pub signer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetOptOut>, subject: OptOutSubject, opted_out: bool) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+
+    let key = match &subject {
+        OptOutSubject::Creator => signer,
+        OptOutSubject::Collection(collection_mint) => {
+            let collection_config = ctx
+                .accounts
+                .collection_config
+                .as_ref()
+                .ok_or(UniversalNftError::InvalidCollectionConfig)?;
+            require_keys_eq!(
+                collection_config.collection_mint,
+                *collection_mint,
+                UniversalNftError::InvalidCollectionConfig
+            );
+            require!(
+                collection_config.can_manage(&signer, &ctx.accounts.program_state.authority),
+                UniversalNftError::Unauthorized
+            );
+            *collection_mint
+        }
+    };
+
+    let opt_out_registry = &mut ctx.accounts.opt_out_registry;
+    let position = opt_out_registry.opted_out.iter().position(|k| *k == key);
+    match (opted_out, position) {
+        (true, None) => {
+            require!(
+                opt_out_registry.opted_out.len() < OptOutRegistry::MAX_ENTRIES,
+                UniversalNftError::OptOutRegistryFull
+            );
+            opt_out_registry.opted_out.push(key);
+        }
+        (false, Some(index)) => {
+            opt_out_registry.opted_out.swap_remove(index);
+        }
+        // Already in the requested state - nothing to do
+        _ => {}
+    }
+
+    emit!(OptOutUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        subject,
+        key,
+        opted_out,
+        signer,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Wrapping opt-out for {}: {}", key, opted_out);
+
+    Ok(())
+}
+
+#[event]
+pub struct OptOutUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub subject: OptOutSubject,
+    pub key: Pubkey,
+    pub opted_out: bool,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
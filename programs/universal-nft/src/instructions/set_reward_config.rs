@@ -0,0 +1,236 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::token::{Token, Mint};
+use crate::state::{ProgramState, RewardConfig, RewardMultiplier, AuditLog, AuditEntry, AuditAction, CpiAllowlist};
+use crate::error::UniversalNftError;
+use crate::constants::{AUDIT_ENTRY_SEED, AUDIT_LOG_SEED, CPI_ALLOWLIST_SEED, OPERATOR_CONFIG_SEED, PROGRAM_STATE_SEED, REWARD_CONFIG_SEED, REWARD_MINT_SEED, REWARD_MULTIPLIER_SEED};
+use crate::utils::security::{can_operate, require_allowed_caller};
+
+/// Creates the reward config and its mint on first use
+#[derive(Accounts)]
+pub struct SetRewardConfig<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = can_operate(&program_state.authority, &operator_config, &authority.key()) @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// CHECK: The operator config, which may not exist; read by `can_operate`
+    #[account(
+        seeds = [OPERATOR_CONFIG_SEED],
+        bump
+    )]
+    pub operator_config: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + RewardConfig::INIT_SPACE,
+        seeds = [REWARD_CONFIG_SEED],
+        bump
+    )]
+    pub reward_config: Box<Account<'info, RewardConfig>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        mint::decimals = RewardConfig::REWARD_DECIMALS,
+        mint::authority = reward_config,
+        seeds = [REWARD_MINT_SEED],
+        bump
+    )]
+    pub reward_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    /// The program authority or its operator
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(collection_mint: Pubkey)]
+pub struct SetRewardMultiplier<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = can_operate(&program_state.authority, &operator_config, &authority.key()) @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// CHECK: The operator config, which may not exist; read by `can_operate`
+    #[account(
+        seeds = [OPERATOR_CONFIG_SEED],
+        bump
+    )]
+    pub operator_config: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + RewardMultiplier::INIT_SPACE,
+        seeds = [REWARD_MULTIPLIER_SEED, collection_mint.as_ref()],
+        bump
+    )]
+    pub reward_multiplier: Account<'info, RewardMultiplier>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    /// The program authority or its operator
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Emit `emission_per_epoch` reward base units per staked NFT every
+/// `epoch_seconds`. A change applies to every epoch not yet claimed; an
+/// emission of 0 stops rewards.
+pub fn config_handler(ctx: Context<SetRewardConfig>, emission_per_epoch: u64, epoch_seconds: u32) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+    require!(epoch_seconds > 0, UniversalNftError::InvalidRewardConfig);
+
+    let now = Clock::get()?.unix_timestamp;
+    let reward_config = &mut ctx.accounts.reward_config;
+    reward_config.reward_mint = ctx.accounts.reward_mint.key();
+    reward_config.emission_per_epoch = emission_per_epoch;
+    reward_config.epoch_seconds = epoch_seconds;
+    reward_config.updated_at = now;
+    reward_config.bump = ctx.bumps.reward_config;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::SetRewardConfig,
+        ctx.accounts.authority.key(),
+        &(emission_per_epoch, epoch_seconds).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(RewardConfigUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        reward_mint: reward_config.reward_mint,
+        emission_per_epoch,
+        epoch_seconds,
+        authority: ctx.accounts.authority.key(),
+        timestamp: now,
+    });
+
+    msg!("Staking rewards: {} per NFT every {}s", emission_per_epoch, epoch_seconds);
+
+    Ok(())
+}
+
+/// Boost rewards for NFTs of `collection_mint` to `multiplier_bps` of the base
+/// emission. Multipliers only boost, so a staker gains nothing by leaving the
+/// account out of a claim.
+pub fn multiplier_handler(ctx: Context<SetRewardMultiplier>, collection_mint: Pubkey, multiplier_bps: u32) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+    require!(
+        (RewardConfig::BPS_DENOMINATOR..=RewardMultiplier::MAX_MULTIPLIER_BPS).contains(&multiplier_bps),
+        UniversalNftError::InvalidRewardConfig
+    );
+
+    let reward_multiplier = &mut ctx.accounts.reward_multiplier;
+    reward_multiplier.collection_mint = collection_mint;
+    reward_multiplier.multiplier_bps = multiplier_bps;
+    reward_multiplier.bump = ctx.bumps.reward_multiplier;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::SetRewardMultiplier,
+        ctx.accounts.authority.key(),
+        &(collection_mint, multiplier_bps).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(RewardMultiplierUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        collection_mint,
+        multiplier_bps,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Collection {} reward multiplier: {} bps", collection_mint, multiplier_bps);
+
+    Ok(())
+}
+
+#[event]
+pub struct RewardConfigUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub reward_mint: Pubkey,
+    pub emission_per_epoch: u64,
+    pub epoch_seconds: u32,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardMultiplierUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub collection_mint: Pubkey,
+    pub multiplier_bps: u32,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use crate::state::{ProgramState, TrustedPeer, AuditLog, AuditEntry, AuditAction, CpiAllowlist};
+use crate::error::UniversalNftError;
+use crate::constants::{AUDIT_ENTRY_SEED, AUDIT_LOG_SEED, CPI_ALLOWLIST_SEED, PROGRAM_STATE_SEED, TRUSTED_PEER_SEED};
+use crate::utils::security::require_allowed_caller;
+use universal_nft_messages::chain_id;
+
+#[derive(Accounts)]
+#[instruction(peer_chain_id: u64, peer_contract: Vec<u8>)]
+pub struct SetTrustedPeer<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = program_state.authority == authority.key() @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + TrustedPeer::INIT_SPACE,
+        seeds = [TRUSTED_PEER_SEED, peer_chain_id.to_le_bytes().as_ref(), peer_contract.as_slice()],
+        bump
+    )]
+    pub trusted_peer: Account<'info, TrustedPeer>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Trust `peer_contract` on `peer_chain_id` to mint up to `mint_quota`
+/// achievement NFTs in total. Lowering the quota to what it has already
+/// minted, or to 0, revokes it; the count of minted achievements is kept.
+pub fn handler(
+    ctx: Context<SetTrustedPeer>,
+    peer_chain_id: u64,
+    peer_contract: Vec<u8>,
+    mint_quota: u64,
+) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+    require!(
+        peer_chain_id > 0 && !chain_id::is_solana(peer_chain_id),
+        UniversalNftError::UnsupportedChain
+    );
+    require!(
+        !peer_contract.is_empty() && peer_contract.len() <= 32,
+        UniversalNftError::InvalidOriginContract
+    );
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let trusted_peer = &mut ctx.accounts.trusted_peer;
+    if trusted_peer.registered_at == 0 {
+        trusted_peer.chain_id = peer_chain_id;
+        trusted_peer.contract = peer_contract.clone();
+        trusted_peer.registered_at = timestamp;
+        trusted_peer.bump = ctx.bumps.trusted_peer;
+    }
+    trusted_peer.mint_quota = mint_quota;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::SetTrustedPeer,
+        ctx.accounts.authority.key(),
+        &(peer_chain_id, peer_contract.clone(), mint_quota).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(TrustedPeerUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        chain_id: peer_chain_id,
+        contract: peer_contract,
+        mint_quota,
+        minted: trusted_peer.minted,
+        authority: ctx.accounts.authority.key(),
+        timestamp,
+    });
+
+    msg!("Trusted peer on chain {}: quota {}", peer_chain_id, mint_quota);
+
+    Ok(())
+}
+
+#[event]
+pub struct TrustedPeerUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub chain_id: u64,
+    pub contract: Vec<u8>,
+    pub mint_quota: u64,
+    pub minted: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
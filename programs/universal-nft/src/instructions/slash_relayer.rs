@@ -0,0 +1,173 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::token::{self, Token, TokenAccount};
+use crate::state::{ProgramState, Relayer, AuditLog, AuditEntry, AuditAction, CpiAllowlist};
+use crate::error::UniversalNftError;
+use crate::constants::{AUDIT_ENTRY_SEED, AUDIT_LOG_SEED, CPI_ALLOWLIST_SEED, FEE_TREASURY_SEED, PROGRAM_STATE_SEED, RELAYER_SEED};
+use crate::utils::fees::treasury_fee_account_address;
+use crate::utils::relayers::relayer_bond_account_address;
+use crate::utils::security::require_allowed_caller;
+
+#[derive(Accounts)]
+#[instruction(relayer_owner: Pubkey)]
+pub struct SlashRelayer<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = program_state.authority == authority.key() @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [RELAYER_SEED, relayer_owner.as_ref()],
+        bump = relayer.bump
+    )]
+    pub relayer: Account<'info, Relayer>,
+
+    /// CHECK: Fee treasury PDA; receives slashed lamport bonds
+    #[account(
+        mut,
+        seeds = [FEE_TREASURY_SEED],
+        bump
+    )]
+    pub fee_treasury: UncheckedAccount<'info>,
+
+    /// Required for SPL bonds
+    #[account(mut)]
+    pub relayer_bond_account: Option<Account<'info, TokenAccount>>,
+
+    /// `treasury_fee_account_address(bond_mint)`; required for SPL bonds
+    #[account(mut)]
+    pub treasury_bond_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Move up to `amount` of a relayer's bond into the fee treasury for
+/// misbehavior shown by the evidence hashed into `evidence_hash`, such as a
+/// relayed message the TSS never signed
+pub fn handler(
+    ctx: Context<SlashRelayer>,
+    relayer_owner: Pubkey,
+    amount: u64,
+    evidence_hash: [u8; 32],
+) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    let relayer = &ctx.accounts.relayer;
+    let slashed = amount.min(relayer.bond);
+    match relayer.bond_mint {
+        None => {
+            let relayer_info = relayer.to_account_info();
+            let treasury_info = ctx.accounts.fee_treasury.to_account_info();
+            **relayer_info.try_borrow_mut_lamports()? -= slashed;
+            **treasury_info.try_borrow_mut_lamports()? += slashed;
+        }
+        Some(mint) => {
+            let (Some(relayer_bond_account), Some(treasury_bond_account)) =
+                (&ctx.accounts.relayer_bond_account, &ctx.accounts.treasury_bond_account)
+            else {
+                return err!(UniversalNftError::InvalidRelayerBond);
+            };
+            require_keys_eq!(
+                relayer_bond_account.key(),
+                relayer_bond_account_address(&relayer.key(), &mint),
+                UniversalNftError::InvalidRelayerBond
+            );
+            require_keys_eq!(
+                treasury_bond_account.key(),
+                treasury_fee_account_address(&mint),
+                UniversalNftError::InvalidFeeAccount
+            );
+
+            let signer_seeds: &[&[&[u8]]] = &[&[RELAYER_SEED, relayer_owner.as_ref(), &[relayer.bump]]];
+            let cpi_accounts = token::Transfer {
+                from: relayer_bond_account.to_account_info(),
+                to: treasury_bond_account.to_account_info(),
+                authority: relayer.to_account_info(),
+            };
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    cpi_accounts,
+                    signer_seeds,
+                ),
+                slashed,
+            )?;
+        }
+    }
+
+    let relayer = &mut ctx.accounts.relayer;
+    relayer.bond -= slashed;
+    relayer.times_slashed = relayer.times_slashed.saturating_add(1);
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::SlashRelayer,
+        ctx.accounts.authority.key(),
+        &(relayer_owner, amount, evidence_hash).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(RelayerSlashedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        relayer: relayer_owner,
+        bond_mint: relayer.bond_mint,
+        amount: slashed,
+        remaining_bond: relayer.bond,
+        evidence_hash,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Relayer {} slashed {}, {} bond left", relayer_owner, slashed, relayer.bond);
+
+    Ok(())
+}
+
+#[event]
+pub struct RelayerSlashedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub relayer: Pubkey,
+    pub bond_mint: Option<Pubkey>,
+    pub amount: u64,
+    pub remaining_bond: u64,
+    pub evidence_hash: [u8; 32],
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
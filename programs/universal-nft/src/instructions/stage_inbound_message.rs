@@ -0,0 +1,196 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use crate::state::{ProgramState, CrossChainConfig, Blocklist, CpiAllowlist, InboundNonce, StagedInbound};
+use crate::error::UniversalNftError;
+use crate::constants::{BLOCKLIST_SEED, CHAIN_CONFIG_SEED, CPI_ALLOWLIST_SEED, CROSS_CHAIN_CONFIG_SEED, INBOUND_NONCE_SEED, PROGRAM_STATE_SEED, STAGED_INBOUND_SEED};
+use crate::utils::validation::{validate_inbound_nft, validate_origin_chain};
+use crate::utils::security::require_allowed_caller;
+use crate::adapters::{Adapter, AdapterAccounts, BridgeAdapter};
+use universal_nft_messages::ReceiveNft;
+
+#[derive(Accounts)]
+#[instruction(origin_chain_id: u64, origin_tx_hash: [u8; 32], origin_contract: Vec<u8>, origin_token_id: [u8; 32], metadata_uri: String, name: String, symbol: String, original_owner: Vec<u8>, intended_recipient: Pubkey, tss_signature: Vec<u8>, nonce: u64)]
+pub struct StageInboundMessage<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
+        bump = cross_chain_config.bump,
+        constraint = !cross_chain_config.is_paused @ UniversalNftError::CrossChainPaused
+    )]
+    pub cross_chain_config: Account<'info, CrossChainConfig>,
+
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    /// CHECK: Origin chain's registry entry, loaded by `validate_origin_chain`
+    #[account(
+        seeds = [CHAIN_CONFIG_SEED, origin_chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub origin_chain_config: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = stager,
+        space = 8 + StagedInbound::INIT_SPACE,
+        seeds = [STAGED_INBOUND_SEED, origin_tx_hash.as_ref(), nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub staged_inbound: Box<Account<'info, StagedInbound>>,
+
+    #[account(
+        init_if_needed,
+        payer = stager,
+        space = 8 + InboundNonce::INIT_SPACE,
+        seeds = [INBOUND_NONCE_SEED, origin_chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub inbound_nonce: Account<'info, InboundNonce>,
+
+    /// CHECK: Posted VAA, required when the origin chain uses the Wormhole adapter; checked by the adapter
+    pub posted_vaa: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub stager: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Verify an inbound message through the origin chain's adapter and stage
+/// it for `execute_inbound_message`. Anyone holding the attestation may
+/// stage it, so delivery does not depend on one relayer.
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, StageInboundMessage<'info>>,
+    origin_chain_id: u64,
+    origin_tx_hash: [u8; 32],
+    origin_contract: Vec<u8>,
+    origin_token_id: [u8; 32],
+    metadata_uri: String,
+    name: String,
+    symbol: String,
+    original_owner: Vec<u8>,
+    intended_recipient: Pubkey,
+    tss_signature: Vec<u8>,
+    nonce: u64,
+) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    let cross_chain_config = &ctx.accounts.cross_chain_config;
+    let origin_chain_config = validate_origin_chain(
+        cross_chain_config,
+        &ctx.accounts.origin_chain_config,
+        origin_chain_id,
+    )?;
+
+    // Empty for adapters that attest through accounts, such as a posted Wormhole VAA
+    require!(
+        ctx.accounts.program_state.validation_limits.fits_signature(&tss_signature),
+        UniversalNftError::InvalidTssSignature
+    );
+
+    let payload = ReceiveNft {
+        origin_chain_id,
+        origin_tx_hash,
+        origin_contract: &origin_contract,
+        origin_token_id,
+        metadata_uri: &metadata_uri,
+        name: &name,
+        symbol: &symbol,
+        original_owner: &original_owner,
+        intended_recipient: intended_recipient.to_bytes(),
+        nonce,
+    };
+    validate_inbound_nft(&payload, &ctx.accounts.blocklist, &ctx.accounts.program_state.validation_limits)?;
+
+    let adapter = Adapter::for_chain(
+        &origin_chain_config,
+        cross_chain_config,
+        AdapterAccounts {
+            posted_vaa: ctx.accounts.posted_vaa.as_ref().map(|account| account.as_ref()),
+            endpoint_accounts: ctx.remaining_accounts,
+        },
+    );
+    let message = adapter.inbound_message(&payload);
+    adapter.verify_inbound(&message, &tss_signature)?;
+
+    ctx.accounts
+        .inbound_nonce
+        .record(origin_chain_id, nonce, ctx.bumps.inbound_nonce)?;
+
+    // While relayers must be bonded, they get the first chance to execute
+    let now = Clock::get()?.unix_timestamp;
+    let open_at = if cross_chain_config.relayer_requirement.is_some() {
+        now.checked_add(StagedInbound::RELAYER_PRIORITY_SECONDS)
+            .ok_or(UniversalNftError::ArithmeticOverflow)?
+    } else {
+        now
+    };
+
+    let staged_inbound = &mut ctx.accounts.staged_inbound;
+    staged_inbound.origin_chain_id = origin_chain_id;
+    staged_inbound.origin_tx_hash = origin_tx_hash;
+    staged_inbound.origin_contract = origin_contract;
+    staged_inbound.origin_token_id = origin_token_id;
+    staged_inbound.metadata_uri = metadata_uri;
+    staged_inbound.name = name;
+    staged_inbound.symbol = symbol;
+    staged_inbound.original_owner = original_owner;
+    staged_inbound.recipient = intended_recipient;
+    staged_inbound.nonce = nonce;
+    staged_inbound.tss_signature_hash = hash(&tss_signature).to_bytes();
+    staged_inbound.stager = ctx.accounts.stager.key();
+    staged_inbound.staged_at = now;
+    staged_inbound.open_at = open_at;
+    staged_inbound.bump = ctx.bumps.staged_inbound;
+
+    emit!(InboundMessageStagedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        origin_chain_id,
+        origin_tx_hash,
+        nonce,
+        recipient: intended_recipient,
+        stager: staged_inbound.stager,
+        open_at,
+        timestamp: now,
+    });
+
+    msg!("Inbound message staged from chain {}, nonce {}, open at {}", origin_chain_id, nonce, open_at);
+
+    Ok(())
+}
+
+#[event]
+pub struct InboundMessageStagedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub origin_chain_id: u64,
+    pub origin_tx_hash: [u8; 32],
+    pub nonce: u64,
+    pub recipient: Pubkey,
+    pub stager: Pubkey,
+    pub open_at: i64,
+    pub timestamp: i64,
+}
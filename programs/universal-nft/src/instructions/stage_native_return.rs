@@ -0,0 +1,216 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use crate::state::{ProgramState, CrossChainConfig, NftMetadata, CrossChainReceipt, Blocklist, CpiAllowlist, InboundNonce, PendingReturn, Relayer};
+use crate::error::UniversalNftError;
+use crate::constants::{BLOCKLIST_SEED, CHAIN_CONFIG_SEED, CPI_ALLOWLIST_SEED, CROSS_CHAIN_CONFIG_SEED, CROSS_CHAIN_RECEIPT_SEED, INBOUND_NONCE_SEED, NFT_METADATA_SEED, PENDING_RETURN_SEED, PROGRAM_STATE_SEED, RELAYER_SEED};
+use crate::utils::relayers::require_bonded_relayer;
+use crate::utils::validation::validate_origin_chain;
+use crate::utils::security::{require_allowed_caller, verify_tss_signature, signed_message};
+use universal_nft_messages::NativeReturn;
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey, origin_chain_id: u64, origin_tx_hash: [u8; 32], nonce: u64)]
+pub struct StageNativeReturn<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
+        bump = cross_chain_config.bump,
+        constraint = !cross_chain_config.is_paused @ UniversalNftError::CrossChainPaused
+    )]
+    pub cross_chain_config: Account<'info, CrossChainConfig>,
+
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    /// CHECK: Origin chain's registry entry, loaded by `validate_origin_chain`
+    #[account(
+        seeds = [CHAIN_CONFIG_SEED, origin_chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub origin_chain_config: UncheckedAccount<'info>,
+
+    // Shares the receive path's receipts, so one attestation is accepted once
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + CrossChainReceipt::INIT_SPACE,
+        seeds = [CROSS_CHAIN_RECEIPT_SEED, origin_tx_hash.as_ref(), nonce.to_le_bytes().as_ref()],
+        bump,
+        constraint = !receipt.is_processed() @ UniversalNftError::AlreadyProcessed
+    )]
+    pub receipt: Account<'info, CrossChainReceipt>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + InboundNonce::INIT_SPACE,
+        seeds = [INBOUND_NONCE_SEED, origin_chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub inbound_nonce: Account<'info, InboundNonce>,
+
+    #[account(
+        seeds = [NFT_METADATA_SEED, mint.as_ref()],
+        bump = nft_metadata.bump,
+        constraint = nft_metadata.is_native() @ UniversalNftError::NotNativeNft,
+        constraint = nft_metadata.is_locked @ UniversalNftError::NativeNftNotLocked
+    )]
+    pub nft_metadata: Account<'info, NftMetadata>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PendingReturn::INIT_SPACE,
+        seeds = [PENDING_RETURN_SEED, mint.as_ref()],
+        bump
+    )]
+    pub pending_return: Account<'info, PendingReturn>,
+
+    /// Required while a relayer requirement is set
+    #[account(
+        mut,
+        seeds = [RELAYER_SEED, authority.key().as_ref()],
+        bump = relayer_registration.bump
+    )]
+    pub relayer_registration: Option<Account<'info, Relayer>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Stage the unlock of a native NFT the TSS attests came back from
+/// `origin_chain_id`. The NFT stays locked until `execute_native_return`
+/// runs after the origin chain's finality delay. `staked_seconds` is the
+/// staking time the NFT brings back, echoed from its outbound checkpoint.
+#[allow(clippy::too_many_arguments)]
+pub fn handler(
+    ctx: Context<StageNativeReturn>,
+    mint: Pubkey,
+    origin_chain_id: u64,
+    origin_tx_hash: [u8; 32],
+    nonce: u64,
+    recipient: Pubkey,
+    staked_seconds: u64,
+    tss_signature: Vec<u8>,
+) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+    require_bonded_relayer(&ctx.accounts.cross_chain_config, ctx.accounts.relayer_registration.as_mut())?;
+
+    let cross_chain_config = &ctx.accounts.cross_chain_config;
+    let origin_chain_config = validate_origin_chain(
+        cross_chain_config,
+        &ctx.accounts.origin_chain_config,
+        origin_chain_id,
+    )?;
+
+    require!(
+        !tss_signature.is_empty()
+            && ctx.accounts.program_state.validation_limits.fits_signature(&tss_signature),
+        UniversalNftError::InvalidTssSignature
+    );
+    // Locked native NFTs never leave their holder's token account, so only that holder can get one back
+    require_keys_eq!(
+        recipient,
+        ctx.accounts.nft_metadata.current_owner,
+        UniversalNftError::RecipientMismatch
+    );
+    require!(
+        !ctx.accounts.blocklist.is_account_blocked(&recipient),
+        UniversalNftError::AddressBlocked
+    );
+
+    let message = signed_message(
+        cross_chain_config.chain_id,
+        &NativeReturn {
+            mint: mint.to_bytes(),
+            origin_chain_id,
+            origin_tx_hash,
+            recipient: recipient.to_bytes(),
+            nonce,
+            staked_seconds,
+        },
+    );
+    let is_valid = verify_tss_signature(&message, &tss_signature, &cross_chain_config.tss_address)?;
+    require!(is_valid, UniversalNftError::InvalidTssSignature);
+
+    ctx.accounts
+        .inbound_nonce
+        .record(origin_chain_id, nonce, ctx.bumps.inbound_nonce)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let receipt = &mut ctx.accounts.receipt;
+    receipt.version = CrossChainReceipt::VERSION;
+    receipt.origin_chain_id = origin_chain_id;
+    receipt.origin_tx_hash = origin_tx_hash;
+    receipt.mint = mint;
+    receipt.recipient = recipient;
+    receipt.original_owner = Vec::new(); // Not carried by native returns
+    receipt.nonce = nonce;
+    receipt.timestamp = now;
+    receipt.tss_signature_hash = hash(&tss_signature).to_bytes();
+    receipt.bump = ctx.bumps.receipt;
+
+    let executable_at = now
+        .checked_add(i64::from(origin_chain_config.finality_delay_seconds))
+        .ok_or(UniversalNftError::ArithmeticOverflow)?;
+    let pending_return = &mut ctx.accounts.pending_return;
+    pending_return.mint = mint;
+    pending_return.recipient = recipient;
+    pending_return.origin_chain_id = origin_chain_id;
+    pending_return.origin_tx_hash = origin_tx_hash;
+    pending_return.nonce = nonce;
+    pending_return.staked_seconds = staked_seconds;
+    pending_return.staged_at = now;
+    pending_return.executable_at = executable_at;
+    pending_return.payer = ctx.accounts.authority.key();
+    pending_return.bump = ctx.bumps.pending_return;
+
+    emit!(NativeReturnStagedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint,
+        recipient,
+        origin_chain_id,
+        nonce,
+        executable_at,
+        timestamp: now,
+    });
+
+    msg!("Native return staged for mint: {}, executable at {}", mint, executable_at);
+
+    Ok(())
+}
+
+#[event]
+pub struct NativeReturnStagedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub origin_chain_id: u64,
+    pub nonce: u64,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
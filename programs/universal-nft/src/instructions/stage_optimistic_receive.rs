@@ -0,0 +1,195 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use crate::state::{ProgramState, CrossChainConfig, Blocklist, CpiAllowlist, InboundNonce, OptimisticReceive, Watcher};
+use crate::error::UniversalNftError;
+use crate::constants::{BLOCKLIST_SEED, CHAIN_CONFIG_SEED, CPI_ALLOWLIST_SEED, CROSS_CHAIN_CONFIG_SEED, INBOUND_NONCE_SEED, OPTIMISTIC_RECEIVE_SEED, PROGRAM_STATE_SEED, WATCHER_SEED};
+use crate::utils::validation::{validate_inbound_nft, validate_origin_chain};
+use crate::utils::security::{require_allowed_caller, signed_message};
+use universal_nft_messages::ReceiveNft;
+
+#[derive(Accounts)]
+#[instruction(origin_chain_id: u64, origin_tx_hash: [u8; 32], origin_contract: Vec<u8>, origin_token_id: [u8; 32], metadata_uri: String, name: String, symbol: String, original_owner: Vec<u8>, intended_recipient: Pubkey, nonce: u64)]
+pub struct StageOptimisticReceive<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
+        bump = cross_chain_config.bump,
+        constraint = !cross_chain_config.is_paused @ UniversalNftError::CrossChainPaused
+    )]
+    pub cross_chain_config: Account<'info, CrossChainConfig>,
+
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    /// CHECK: Origin chain's registry entry, loaded by `validate_origin_chain`
+    #[account(
+        seeds = [CHAIN_CONFIG_SEED, origin_chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub origin_chain_config: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + OptimisticReceive::INIT_SPACE,
+        seeds = [OPTIMISTIC_RECEIVE_SEED, origin_tx_hash.as_ref(), nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub optimistic_receive: Box<Account<'info, OptimisticReceive>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + InboundNonce::INIT_SPACE,
+        seeds = [INBOUND_NONCE_SEED, origin_chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub inbound_nonce: Account<'info, InboundNonce>,
+
+    /// Required unless the program authority is staging
+    #[account(
+        seeds = [WATCHER_SEED, authority.key().as_ref()],
+        bump = submitter_watcher.bump
+    )]
+    pub submitter_watcher: Option<Account<'info, Watcher>>,
+
+    /// Program authority or a bonded watcher
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Stage an inbound NFT without waiting for the TSS. It becomes claimable
+/// once the origin chain's challenge window closes, unless a fraud proof
+/// cancels it or a watcher challenges it first.
+#[allow(clippy::too_many_arguments)]
+pub fn handler(
+    ctx: Context<StageOptimisticReceive>,
+    origin_chain_id: u64,
+    origin_tx_hash: [u8; 32],
+    origin_contract: Vec<u8>,
+    origin_token_id: [u8; 32],
+    metadata_uri: String,
+    name: String,
+    symbol: String,
+    original_owner: Vec<u8>,
+    intended_recipient: Pubkey,
+    nonce: u64,
+) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    // Without a TSS signature, only the program's relayer or someone with a
+    // bond at stake may stage
+    if ctx.accounts.authority.key() != ctx.accounts.program_state.authority {
+        let watcher = ctx
+            .accounts
+            .submitter_watcher
+            .as_ref()
+            .ok_or(UniversalNftError::Unauthorized)?;
+        require!(watcher.is_bonded(), UniversalNftError::WatcherBondTooLow);
+    }
+
+    let cross_chain_config = &ctx.accounts.cross_chain_config;
+    let origin_chain_config = validate_origin_chain(
+        cross_chain_config,
+        &ctx.accounts.origin_chain_config,
+        origin_chain_id,
+    )?;
+    require!(
+        origin_chain_config.optimistic_window_seconds > 0,
+        UniversalNftError::OptimisticModeDisabled
+    );
+
+    let payload = ReceiveNft {
+        origin_chain_id,
+        origin_tx_hash,
+        origin_contract: &origin_contract,
+        origin_token_id,
+        metadata_uri: &metadata_uri,
+        name: &name,
+        symbol: &symbol,
+        original_owner: &original_owner,
+        intended_recipient: intended_recipient.to_bytes(),
+        nonce,
+    };
+    validate_inbound_nft(&payload, &ctx.accounts.blocklist, &ctx.accounts.program_state.validation_limits)?;
+    let message_hash = hash(&signed_message(cross_chain_config.chain_id, &payload)).to_bytes();
+
+    ctx.accounts
+        .inbound_nonce
+        .record(origin_chain_id, nonce, ctx.bumps.inbound_nonce)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let claimable_at = now
+        .checked_add(i64::from(origin_chain_config.optimistic_window_seconds))
+        .ok_or(UniversalNftError::ArithmeticOverflow)?;
+
+    let optimistic_receive = &mut ctx.accounts.optimistic_receive;
+    optimistic_receive.origin_chain_id = origin_chain_id;
+    optimistic_receive.origin_tx_hash = origin_tx_hash;
+    optimistic_receive.origin_contract = origin_contract;
+    optimistic_receive.origin_token_id = origin_token_id;
+    optimistic_receive.metadata_uri = metadata_uri;
+    optimistic_receive.name = name;
+    optimistic_receive.symbol = symbol;
+    optimistic_receive.original_owner = original_owner;
+    optimistic_receive.recipient = intended_recipient;
+    optimistic_receive.nonce = nonce;
+    optimistic_receive.message_hash = message_hash;
+    optimistic_receive.submitter = ctx.accounts.authority.key();
+    optimistic_receive.staged_at = now;
+    optimistic_receive.claimable_at = claimable_at;
+    optimistic_receive.status = 0; // Pending
+    optimistic_receive.bump = ctx.bumps.optimistic_receive;
+
+    emit!(OptimisticReceiveStagedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        origin_chain_id,
+        origin_tx_hash,
+        nonce,
+        recipient: intended_recipient,
+        message_hash,
+        claimable_at,
+        timestamp: now,
+    });
+
+    msg!("Optimistic receive staged from chain {}, nonce {}, claimable at {}", origin_chain_id, nonce, claimable_at);
+
+    Ok(())
+}
+
+#[event]
+pub struct OptimisticReceiveStagedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub origin_chain_id: u64,
+    pub origin_tx_hash: [u8; 32],
+    pub nonce: u64,
+    pub recipient: Pubkey,
+    pub message_hash: [u8; 32],
+    pub claimable_at: i64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,306 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Token, TokenAccount, Mint};
+use crate::state::{ProgramState, NftMetadata, RewardConfig, RewardMultiplier, StakeRecord};
+use crate::error::UniversalNftError;
+use crate::constants::{NFT_METADATA_SEED, PROGRAM_STATE_SEED, REWARD_CONFIG_SEED, REWARD_MULTIPLIER_SEED, STAKE_RECORD_SEED};
+
+/// Stakes an NFT in place. A stake left behind by a previous holder is taken
+/// over, forfeiting whatever it had not claimed, and a stake still
+/// checkpointed after the NFT came back without its credit restarts.
+#[derive(Accounts)]
+pub struct StakeNft<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = !program_state.is_sunset @ UniversalNftError::ProgramSunset
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump,
+        constraint = !nft_metadata.is_locked @ UniversalNftError::NftLocked
+    )]
+    pub nft_metadata: Box<Account<'info, NftMetadata>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + StakeRecord::INIT_SPACE,
+        seeds = [STAKE_RECORD_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub stake_record: Box<Account<'info, StakeRecord>>,
+
+    /// CHECK: Tied to the NFT by the metadata seeds
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = owner_token_account.mint == mint.key() @ UniversalNftError::InvalidMint,
+        constraint = owner_token_account.owner == owner.key() @ UniversalNftError::Unauthorized,
+        constraint = owner_token_account.amount >= 1 @ UniversalNftError::InsufficientTokens
+    )]
+    pub owner_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Mints a staker the rewards of every whole epoch since their last claim
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [REWARD_CONFIG_SEED],
+        bump = reward_config.bump,
+        has_one = reward_mint
+    )]
+    pub reward_config: Box<Account<'info, RewardConfig>>,
+
+    /// The NFT's collection multiplier, if it has one; claims at 1x without it
+    #[account(
+        seeds = [REWARD_MULTIPLIER_SEED, nft_metadata.collection.as_ref()],
+        bump = reward_multiplier.bump
+    )]
+    pub reward_multiplier: Option<Account<'info, RewardMultiplier>>,
+
+    #[account(
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump,
+        constraint = !nft_metadata.is_locked @ UniversalNftError::NftLocked
+    )]
+    pub nft_metadata: Box<Account<'info, NftMetadata>>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_RECORD_SEED, mint.key().as_ref()],
+        bump = stake_record.bump,
+        has_one = owner,
+        has_one = mint,
+        constraint = stake_record.bridged_at == 0 @ UniversalNftError::StakeBridged
+    )]
+    pub stake_record: Box<Account<'info, StakeRecord>>,
+
+    /// CHECK: Tied to the stake by `has_one`
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = owner_token_account.mint == mint.key() @ UniversalNftError::InvalidMint,
+        constraint = owner_token_account.owner == owner.key() @ UniversalNftError::Unauthorized,
+        constraint = owner_token_account.amount >= 1 @ UniversalNftError::InsufficientTokens
+    )]
+    pub owner_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub reward_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = reward_mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_reward_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Closes a stake. Rewards not yet claimed are forfeited, so claim first.
+#[derive(Accounts)]
+pub struct UnstakeNft<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [STAKE_RECORD_SEED, stake_record.mint.as_ref()],
+        bump = stake_record.bump,
+        has_one = owner
+    )]
+    pub stake_record: Box<Account<'info, StakeRecord>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Start earning rewards for an NFT the signer holds
+pub fn stake_handler(ctx: Context<StakeNft>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let stake_record = &mut ctx.accounts.stake_record;
+    require!(
+        stake_record.owner != ctx.accounts.owner.key() || stake_record.bridged_at != 0,
+        UniversalNftError::NftAlreadyStaked
+    );
+
+    stake_record.owner = ctx.accounts.owner.key();
+    stake_record.mint = ctx.accounts.mint.key();
+    stake_record.collection = ctx.accounts.nft_metadata.collection;
+    stake_record.staked_at = now;
+    stake_record.last_claimed_at = now;
+    stake_record.total_claimed = 0;
+    stake_record.bridged_at = 0;
+    stake_record.bump = ctx.bumps.stake_record;
+
+    emit!(NftStakedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint: stake_record.mint,
+        owner: stake_record.owner,
+        collection: stake_record.collection,
+        timestamp: now,
+    });
+
+    msg!("Staked {} for {}", stake_record.mint, stake_record.owner);
+
+    Ok(())
+}
+
+/// Mint the rewards accrued since the last claim, at the collection's
+/// multiplier. A partial epoch keeps accruing.
+pub fn claim_handler(ctx: Context<ClaimRewards>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let multiplier_bps = ctx
+        .accounts
+        .reward_multiplier
+        .as_ref()
+        .map_or(RewardConfig::BPS_DENOMINATOR, |multiplier| multiplier.multiplier_bps);
+    let (amount, resume_at) = ctx.accounts.reward_config.accrued(
+        ctx.accounts.stake_record.last_claimed_at,
+        now,
+        multiplier_bps,
+    )?;
+    require!(amount > 0, UniversalNftError::NoRewardsAccrued);
+
+    let config_bump = ctx.accounts.reward_config.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[REWARD_CONFIG_SEED, &[config_bump]]];
+    let cpi_accounts = token::MintTo {
+        mint: ctx.accounts.reward_mint.to_account_info(),
+        to: ctx.accounts.owner_reward_account.to_account_info(),
+        authority: ctx.accounts.reward_config.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::mint_to(cpi_ctx, amount)?;
+
+    let stake_record = &mut ctx.accounts.stake_record;
+    stake_record.last_claimed_at = resume_at;
+    stake_record.total_claimed = stake_record
+        .total_claimed
+        .checked_add(amount)
+        .ok_or(UniversalNftError::ArithmeticOverflow)?;
+    let reward_config = &mut ctx.accounts.reward_config;
+    reward_config.total_emitted = reward_config
+        .total_emitted
+        .checked_add(amount)
+        .ok_or(UniversalNftError::ArithmeticOverflow)?;
+
+    emit!(RewardsClaimedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint: stake_record.mint,
+        owner: stake_record.owner,
+        amount,
+        multiplier_bps,
+        timestamp: now,
+    });
+
+    msg!("Claimed {} reward units for {} at {} bps", amount, stake_record.mint, multiplier_bps);
+
+    Ok(())
+}
+
+pub fn unstake_handler(ctx: Context<UnstakeNft>) -> Result<()> {
+    let stake_record = &ctx.accounts.stake_record;
+
+    emit!(NftUnstakedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint: stake_record.mint,
+        owner: stake_record.owner,
+        total_claimed: stake_record.total_claimed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Unstaked {} after {} reward units", stake_record.mint, stake_record.total_claimed);
+
+    Ok(())
+}
+
+#[event]
+pub struct NftStakedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub collection: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardsClaimedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub multiplier_bps: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NftUnstakedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeCheckpointedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub nonce: u64, // Outbound transfer carrying the checkpoint
+    pub staked_seconds: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeResumedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub credited_seconds: u64,
+    pub timestamp: i64,
+}
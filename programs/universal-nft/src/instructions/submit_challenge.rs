@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::state::{ProgramState, OptimisticReceive, Watcher, Challenge};
+use crate::error::UniversalNftError;
+use crate::constants::{CHALLENGE_SEED, OPTIMISTIC_RECEIVE_SEED, PROGRAM_STATE_SEED, WATCHER_SEED};
+
+#[derive(Accounts)]
+#[instruction(origin_tx_hash: [u8; 32], nonce: u64)]
+pub struct SubmitChallenge<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [OPTIMISTIC_RECEIVE_SEED, origin_tx_hash.as_ref(), nonce.to_le_bytes().as_ref()],
+        bump = optimistic_receive.bump,
+        constraint = optimistic_receive.status == 0 @ UniversalNftError::OptimisticReceiveNotPending,
+        constraint = optimistic_receive.submitter != challenger.key() @ UniversalNftError::Unauthorized
+    )]
+    pub optimistic_receive: Box<Account<'info, OptimisticReceive>>,
+
+    #[account(
+        seeds = [WATCHER_SEED, challenger.key().as_ref()],
+        bump = challenger_watcher.bump
+    )]
+    pub challenger_watcher: Account<'info, Watcher>,
+
+    #[account(
+        init,
+        payer = challenger,
+        space = 8 + Challenge::INIT_SPACE,
+        seeds = [CHALLENGE_SEED, optimistic_receive.key().as_ref()],
+        bump
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Dispute a pending optimistic receive before its window closes. This
+/// freezes the receive, with `evidence` attached, until the admin resolves
+/// the challenge.
+pub fn handler(
+    ctx: Context<SubmitChallenge>,
+    origin_tx_hash: [u8; 32],
+    nonce: u64,
+    evidence: Vec<u8>,
+) -> Result<()> {
+    require!(ctx.accounts.challenger_watcher.is_bonded(), UniversalNftError::WatcherBondTooLow);
+    require!(
+        !evidence.is_empty() && evidence.len() <= Challenge::MAX_EVIDENCE_LEN,
+        UniversalNftError::InvalidChallengeEvidence
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let optimistic_receive = &mut ctx.accounts.optimistic_receive;
+    require!(now < optimistic_receive.claimable_at, UniversalNftError::ChallengeWindowClosed);
+    optimistic_receive.status = 3; // Challenged
+
+    let evidence_hash = hash(&evidence).to_bytes();
+    let challenge = &mut ctx.accounts.challenge;
+    challenge.optimistic_receive = optimistic_receive.key();
+    challenge.challenger = ctx.accounts.challenger.key();
+    challenge.evidence = evidence;
+    challenge.submitted_at = now;
+    challenge.bump = ctx.bumps.challenge;
+
+    emit!(ChallengeSubmittedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        origin_chain_id: optimistic_receive.origin_chain_id,
+        origin_tx_hash,
+        nonce,
+        submitter: optimistic_receive.submitter,
+        challenger: challenge.challenger,
+        evidence_hash,
+        timestamp: now,
+    });
+
+    msg!("Optimistic receive challenged, nonce {}", nonce);
+
+    Ok(())
+}
+
+#[event]
+pub struct ChallengeSubmittedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub origin_chain_id: u64,
+    pub origin_tx_hash: [u8; 32],
+    pub nonce: u64,
+    pub submitter: Pubkey,
+    pub challenger: Pubkey,
+    pub evidence_hash: [u8; 32],
+    pub timestamp: i64,
+}
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use crate::state::{ProgramState, AuditLog, AuditEntry, AuditAction, CpiAllowlist};
+use crate::error::UniversalNftError;
+use crate::constants::{AUDIT_ENTRY_SEED, AUDIT_LOG_SEED, BLOCKLIST_SEED, CPI_ALLOWLIST_SEED, CROSS_CHAIN_CONFIG_SEED, OPT_OUT_REGISTRY_SEED, PROGRAM_STATE_SEED};
+use crate::utils::security::require_allowed_caller;
+
+/// Singleton PDAs whose surplus lamports can be swept. Token escrows are
+/// owned by the token program, so their lamports are out of this program's reach.
+/// The fee treasury is left to `withdraw_fees`, which keeps refundable fees in it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SweepTarget {
+    ProgramState,
+    CrossChainConfig,
+    Blocklist,
+    OptOutRegistry,
+    CpiAllowlist,
+    AuditLog,
+}
+
+impl SweepTarget {
+    pub fn seed(&self) -> &'static [u8] {
+        match self {
+            SweepTarget::ProgramState => PROGRAM_STATE_SEED,
+            SweepTarget::CrossChainConfig => CROSS_CHAIN_CONFIG_SEED,
+            SweepTarget::Blocklist => BLOCKLIST_SEED,
+            SweepTarget::OptOutRegistry => OPT_OUT_REGISTRY_SEED,
+            SweepTarget::CpiAllowlist => CPI_ALLOWLIST_SEED,
+            SweepTarget::AuditLog => AUDIT_LOG_SEED,
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct SweepSurplusLamports<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = program_state.authority == authority.key() @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// CHECK: Checked by the handler against the PDA named by `target`
+    #[account(mut)]
+    pub target_account: UncheckedAccount<'info>,
+
+    /// CHECK: Receives the swept lamports
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Send every lamport `target` holds above its rent-exempt minimum to
+/// `recipient`, recovering SOL transferred to it by mistake
+pub fn handler(ctx: Context<SweepSurplusLamports>, target: SweepTarget) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    let (expected, _) = Pubkey::find_program_address(&[target.seed()], &crate::ID);
+    let target_info = ctx.accounts.target_account.to_account_info();
+    require_keys_eq!(target_info.key(), expected, UniversalNftError::InvalidSweepTarget);
+
+    let amount = target_info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(target_info.data_len()));
+    require!(amount > 0, UniversalNftError::NoSurplusLamports);
+
+    let recipient_info = ctx.accounts.recipient.to_account_info();
+    require_keys_eq!(*target_info.owner, crate::ID, UniversalNftError::InvalidSweepTarget);
+    **target_info.try_borrow_mut_lamports()? -= amount;
+    **recipient_info.try_borrow_mut_lamports()? += amount;
+
+    let recipient = ctx.accounts.recipient.key();
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::SweepSurplusLamports,
+        ctx.accounts.authority.key(),
+        &(target, amount, recipient).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(SurplusLamportsSweptEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        target,
+        account: expected,
+        amount,
+        recipient,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Swept {} surplus lamports from {:?} to {}", amount, target, recipient);
+
+    Ok(())
+}
+
+#[event]
+pub struct SurplusLamportsSweptEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub target: SweepTarget,
+    pub account: Pubkey,
+    pub amount: u64,
+    pub recipient: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
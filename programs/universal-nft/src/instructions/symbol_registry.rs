@@ -0,0 +1,286 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use crate::state::{ProgramState, CollectionConfig, NftMetadata, SymbolRegistry, SymbolReservation, AuditLog, AuditEntry, AuditAction, CpiAllowlist};
+use crate::error::UniversalNftError;
+use crate::constants::{AUDIT_ENTRY_SEED, AUDIT_LOG_SEED, COLLECTION_CONFIG_SEED, CPI_ALLOWLIST_SEED, NFT_METADATA_SEED, PROGRAM_STATE_SEED, SYMBOL_REGISTRY_SEED, SYMBOL_RESERVATION_SEED};
+use crate::utils::security::require_allowed_caller;
+
+/// Creates the symbol registry on first use
+#[derive(Accounts)]
+pub struct SetSymbolRegistry<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = program_state.authority == authority.key() @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + SymbolRegistry::INIT_SPACE,
+        seeds = [SYMBOL_REGISTRY_SEED],
+        bump
+    )]
+    pub symbol_registry: Account<'info, SymbolRegistry>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReserveCollectionSymbol<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [COLLECTION_CONFIG_SEED, collection_config.collection_mint.as_ref()],
+        bump = collection_config.bump,
+        constraint = collection_config.can_manage(&signer.key(), &program_state.authority) @ UniversalNftError::Unauthorized
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    /// The collection mint's own metadata, whose symbol is reserved
+    #[account(
+        seeds = [NFT_METADATA_SEED, collection_config.collection_mint.as_ref()],
+        bump = collection_metadata.bump
+    )]
+    pub collection_metadata: Account<'info, NftMetadata>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + SymbolReservation::INIT_SPACE,
+        seeds = [SYMBOL_RESERVATION_SEED, SymbolReservation::seed(&collection_metadata.symbol).as_ref()],
+        bump
+    )]
+    pub symbol_reservation: Account<'info, SymbolReservation>,
+
+    /// The collection's creator or the program authority; pays for the reservation
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct ReassignCollectionSymbol<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = program_state.authority == authority.key() @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [SYMBOL_RESERVATION_SEED, SymbolReservation::seed(&symbol).as_ref()],
+        bump = symbol_reservation.bump
+    )]
+    pub symbol_reservation: Account<'info, SymbolReservation>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Turn the symbol registry on or off. While it is on, registering a
+/// collection reserves its symbol and fails if another collection holds it.
+pub fn set_handler(ctx: Context<SetSymbolRegistry>, enabled: bool) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let symbol_registry = &mut ctx.accounts.symbol_registry;
+    symbol_registry.enabled = enabled;
+    symbol_registry.updated_at = now;
+    symbol_registry.bump = ctx.bumps.symbol_registry;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::SetSymbolRegistry,
+        ctx.accounts.authority.key(),
+        &enabled.try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(SymbolRegistryUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        enabled,
+        authority: ctx.accounts.authority.key(),
+        timestamp: now,
+    });
+
+    msg!("Symbol registry enabled: {}", enabled);
+
+    Ok(())
+}
+
+/// Reserve the symbol in a Solana collection's own metadata for its creator.
+/// A symbol another creator reserved first fails with `SymbolReserved`; the
+/// same creator may reuse it across their collections.
+pub fn reserve_handler(ctx: Context<ReserveCollectionSymbol>) -> Result<()> {
+    let collection_config = &ctx.accounts.collection_config;
+    let symbol = ctx.accounts.collection_metadata.symbol.clone();
+    let now = Clock::get()?.unix_timestamp;
+    let reserved = ctx.accounts.symbol_reservation.reserve(
+        &symbol,
+        collection_config.creator,
+        collection_config.collection_mint,
+        now,
+        ctx.bumps.symbol_reservation,
+    )?;
+    if reserved {
+        emit!(SymbolReservedEvent {
+            schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+            sequence: ctx.accounts.program_state.next_event_sequence(),
+            symbol: symbol.clone(),
+            creator: collection_config.creator,
+            collection_mint: collection_config.collection_mint,
+            timestamp: now,
+        });
+    }
+
+    msg!("Symbol {} reserved for creator {}", symbol, collection_config.creator);
+
+    Ok(())
+}
+
+/// Hand `symbol`'s reservation to `creator` and `collection_mint`, settling a
+/// squatted or disputed symbol. The default `creator` releases it, so the
+/// next collection to register the symbol claims it.
+pub fn reassign_handler(
+    ctx: Context<ReassignCollectionSymbol>,
+    symbol: String,
+    creator: Pubkey,
+    collection_mint: Pubkey,
+) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let symbol_reservation = &mut ctx.accounts.symbol_reservation;
+    let previous_creator = symbol_reservation.creator;
+    symbol_reservation.creator = creator;
+    symbol_reservation.collection_mint = if creator == Pubkey::default() { Pubkey::default() } else { collection_mint };
+    symbol_reservation.reserved_at = now;
+    let collection_mint = symbol_reservation.collection_mint;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::ReassignCollectionSymbol,
+        ctx.accounts.authority.key(),
+        &(&symbol, creator, collection_mint).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(SymbolReassignedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        symbol: symbol.clone(),
+        previous_creator,
+        creator,
+        collection_mint,
+        authority: ctx.accounts.authority.key(),
+        timestamp: now,
+    });
+
+    msg!("Symbol {} reassigned from {} to {}", symbol, previous_creator, creator);
+
+    Ok(())
+}
+
+#[event]
+pub struct SymbolRegistryUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub enabled: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SymbolReservedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub symbol: String,
+    pub creator: Pubkey,
+    pub collection_mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SymbolReassignedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub symbol: String,
+    pub previous_creator: Pubkey,
+    pub creator: Pubkey, // Default when released
+    pub collection_mint: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,245 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{self, Token, TokenAccount, Mint};
+use crate::state::{ProgramState, NftMetadata, CollectionConfig, TicketConfig, TicketCheckIn};
+use crate::error::UniversalNftError;
+use crate::constants::{
+    COLLECTION_CONFIG_SEED, FREEZE_AUTHORITY_SEED, NFT_METADATA_SEED, PROGRAM_STATE_SEED,
+    TICKET_CHECK_IN_SEED, TICKET_CONFIG_SEED,
+};
+
+#[derive(Accounts)]
+#[instruction(collection_mint: Pubkey)]
+pub struct SetTicketConfig<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// The collection's config, letting its creator manage ticketing; foreign
+    /// collections have none and are managed by the program authority
+    #[account(
+        seeds = [COLLECTION_CONFIG_SEED, collection_mint.as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Option<Account<'info, CollectionConfig>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + TicketConfig::INIT_SPACE,
+        seeds = [TICKET_CONFIG_SEED, collection_mint.as_ref()],
+        bump
+    )]
+    pub ticket_config: Account<'info, TicketConfig>,
+
+    /// The collection's creator or the program authority; pays for the account
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Marks a ticket as used. The holder presents it, and a validator co-signs
+/// when the collection lists any.
+#[derive(Accounts)]
+pub struct CheckIn<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+
+    #[account(
+        seeds = [TICKET_CONFIG_SEED, nft_metadata.collection.as_ref()],
+        bump = ticket_config.bump
+    )]
+    pub ticket_config: Box<Account<'info, TicketConfig>>,
+
+    #[account(
+        mut,
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump,
+        constraint = !nft_metadata.is_locked @ UniversalNftError::NftLocked
+    )]
+    pub nft_metadata: Box<Account<'info, NftMetadata>>,
+
+    #[account(
+        init_if_needed,
+        payer = holder,
+        space = 8 + TicketCheckIn::INIT_SPACE,
+        seeds = [TICKET_CHECK_IN_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub ticket_check_in: Box<Account<'info, TicketCheckIn>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// Must not be frozen for a delegate listing; a listed ticket is checked
+    /// in after the listing is cancelled
+    #[account(
+        mut,
+        constraint = holder_token_account.mint == mint.key() @ UniversalNftError::InvalidMint,
+        constraint = holder_token_account.owner == holder.key() @ UniversalNftError::Unauthorized,
+        constraint = holder_token_account.amount >= 1 @ UniversalNftError::InsufficientTokens,
+        constraint = !holder_token_account.is_frozen() @ UniversalNftError::NftLocked
+    )]
+    pub holder_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: PDA holding the freeze authority of the NFTs the program mints
+    #[account(seeds = [FREEZE_AUTHORITY_SEED], bump)]
+    pub freeze_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    /// One of the ticket config's validators; required when it lists any
+    pub validator: Option<Signer<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Make `collection_mint`'s NFTs tickets, or update how they check in.
+/// `lock_after_use` stops a used ticket from moving; `validators` empty lets
+/// holders check in on their own.
+pub fn config_handler(
+    ctx: Context<SetTicketConfig>,
+    collection_mint: Pubkey,
+    lock_after_use: bool,
+    validators: Vec<Pubkey>,
+) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+    let authority = ctx.accounts.program_state.authority;
+    require!(
+        signer == authority
+            || ctx
+                .accounts
+                .collection_config
+                .as_ref()
+                .is_some_and(|config| config.can_manage(&signer, &authority)),
+        UniversalNftError::Unauthorized
+    );
+    TicketConfig::validate(&validators)?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let ticket_config = &mut ctx.accounts.ticket_config;
+    ticket_config.collection_mint = collection_mint;
+    ticket_config.lock_after_use = lock_after_use;
+    ticket_config.validators = validators.clone();
+    ticket_config.updated_by = signer;
+    ticket_config.updated_at = timestamp;
+    ticket_config.bump = ctx.bumps.ticket_config;
+
+    emit!(TicketConfigUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        collection_mint,
+        lock_after_use,
+        validators,
+        updated_by: signer,
+        timestamp,
+    });
+
+    msg!(
+        "Collection {} tickets: lock after use {}, {} validators",
+        collection_mint,
+        lock_after_use,
+        ticket_config.validators.len()
+    );
+
+    Ok(())
+}
+
+/// Check a ticket in. With `lock_after_use` it can no longer be bridged, and
+/// tickets the program minted are frozen in the holder's wallet. Bridged-in
+/// tickets have no freeze authority, so only their bridging is blocked.
+pub fn check_in_handler(ctx: Context<CheckIn>) -> Result<()> {
+    let ticket_check_in = &mut ctx.accounts.ticket_check_in;
+    require!(ticket_check_in.checked_in_at == 0, UniversalNftError::TicketAlreadyUsed);
+
+    let ticket_config = &ctx.accounts.ticket_config;
+    let validator = ctx.accounts.validator.as_ref().map(|validator| validator.key());
+    if !ticket_config.validators.is_empty() {
+        require!(
+            validator.is_some_and(|validator| ticket_config.validators.contains(&validator)),
+            UniversalNftError::ValidatorRequired
+        );
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    ticket_check_in.mint = ctx.accounts.mint.key();
+    ticket_check_in.collection = ticket_config.collection_mint;
+    ticket_check_in.holder = ctx.accounts.holder.key();
+    ticket_check_in.validator = validator.unwrap_or_default();
+    ticket_check_in.checked_in_at = now;
+    ticket_check_in.bump = ctx.bumps.ticket_check_in;
+
+    let mut frozen = false;
+    if ticket_config.lock_after_use {
+        ctx.accounts.nft_metadata.cross_chain_enabled = false;
+
+        let freeze_authority = ctx.accounts.freeze_authority.key();
+        if ctx.accounts.mint.freeze_authority == COption::Some(freeze_authority) {
+            let freeze_bump = ctx.bumps.freeze_authority;
+            let signer_seeds: &[&[&[u8]]] = &[&[FREEZE_AUTHORITY_SEED, &[freeze_bump]]];
+            let cpi_accounts = token::FreezeAccount {
+                account: ctx.accounts.holder_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.freeze_authority.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token::freeze_account(cpi_ctx)?;
+            frozen = true;
+        }
+    }
+
+    emit!(TicketCheckedInEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint: ticket_check_in.mint,
+        collection: ticket_check_in.collection,
+        holder: ticket_check_in.holder,
+        validator: ticket_check_in.validator,
+        locked: ticket_config.lock_after_use,
+        frozen,
+        timestamp: now,
+    });
+
+    msg!("Checked in ticket {} for {}", ticket_check_in.mint, ticket_check_in.holder);
+
+    Ok(())
+}
+
+#[event]
+pub struct TicketConfigUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub collection_mint: Pubkey,
+    pub lock_after_use: bool,
+    pub validators: Vec<Pubkey>,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TicketCheckedInEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub collection: Pubkey,
+    pub holder: Pubkey,
+    pub validator: Pubkey, // Default when the holder checked in alone
+    pub locked: bool, // No longer bridgeable
+    pub frozen: bool, // Frozen in the holder's wallet
+    pub timestamp: i64,
+}
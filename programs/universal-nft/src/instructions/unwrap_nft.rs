@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Token, TokenAccount, Mint};
+use crate::state::{ProgramState, NftMetadata};
+use crate::error::UniversalNftError;
+use crate::constants::{NFT_METADATA_SEED, PROGRAM_STATE_SEED, WRAP_ESCROW_SEED, WRAPPED_MINT_SEED};
+
+/// Burns the universal stand-in and returns the escrowed Metaplex original
+#[derive(Accounts)]
+pub struct UnwrapNft<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump = nft_metadata.bump,
+        constraint = nft_metadata.is_wrapped() @ UniversalNftError::NotWrapped,
+        constraint = !nft_metadata.is_locked @ UniversalNftError::NftLocked,
+        constraint = nft_metadata.child_count == 0 @ UniversalNftError::HasAttachedChildren
+    )]
+    pub nft_metadata: Box<Account<'info, NftMetadata>>,
+
+    #[account(
+        mut,
+        seeds = [WRAPPED_MINT_SEED, original_mint.key().as_ref()],
+        bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = token_account.mint == mint.key() @ UniversalNftError::InvalidMint,
+        constraint = token_account.owner == owner.key() @ UniversalNftError::Unauthorized,
+        constraint = token_account.amount >= 1 @ UniversalNftError::InsufficientTokens
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = nft_metadata.wrapped_mint @ UniversalNftError::InvalidMint)]
+    pub original_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [WRAP_ESCROW_SEED, original_mint.key().as_ref()],
+        bump
+    )]
+    pub wrap_escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = original_mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_original_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Funds the owner's token account if it doesn't exist yet
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<UnwrapNft>) -> Result<()> {
+    // Burn the universal stand-in
+    let cpi_accounts = token::Burn {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.token_account.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::burn(cpi_ctx, 1)?;
+
+    // Release the original and close the escrow
+    let original_mint = ctx.accounts.original_mint.key();
+    let escrow_bump = ctx.bumps.wrap_escrow;
+    let signer_seeds: &[&[&[u8]]] = &[&[WRAP_ESCROW_SEED, original_mint.as_ref(), &[escrow_bump]]];
+
+    let cpi_accounts = token::Transfer {
+        from: ctx.accounts.wrap_escrow.to_account_info(),
+        to: ctx.accounts.owner_original_account.to_account_info(),
+        authority: ctx.accounts.wrap_escrow.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(cpi_ctx, 1)?;
+
+    let cpi_accounts = token::CloseAccount {
+        account: ctx.accounts.wrap_escrow.to_account_info(),
+        destination: ctx.accounts.owner.to_account_info(),
+        authority: ctx.accounts.wrap_escrow.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::close_account(cpi_ctx)?;
+
+    emit!(NftUnwrappedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        original_mint,
+        mint: ctx.accounts.mint.key(),
+        owner: ctx.accounts.owner.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Unwrapped universal NFT {} back to {}", ctx.accounts.mint.key(), original_mint);
+
+    Ok(())
+}
+
+#[event]
+pub struct NftUnwrappedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub original_mint: Pubkey,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
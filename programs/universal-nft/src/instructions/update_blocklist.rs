@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use crate::state::{ProgramState, Blocklist, BlocklistEntry, AuditLog, AuditEntry, AuditAction, CpiAllowlist};
+use crate::error::UniversalNftError;
+use crate::constants::{AUDIT_ENTRY_SEED, AUDIT_LOG_SEED, BLOCKLIST_SEED, CPI_ALLOWLIST_SEED, PROGRAM_STATE_SEED};
+use crate::utils::security::require_allowed_caller;
+
+#[derive(Accounts)]
+pub struct UpdateBlocklist<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = program_state.authority == authority.key() @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateBlocklist>,
+    entry: BlocklistEntry,
+    blocked: bool,
+) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    let blocklist = &mut ctx.accounts.blocklist;
+
+    match &entry {
+        BlocklistEntry::Account(account) => {
+            let position = blocklist.blocked_accounts.iter().position(|a| a == account);
+            match (blocked, position) {
+                (true, None) => {
+                    require!(
+                        blocklist.blocked_accounts.len() < Blocklist::MAX_BLOCKED_ACCOUNTS,
+                        UniversalNftError::BlocklistFull
+                    );
+                    blocklist.blocked_accounts.push(*account);
+                }
+                (false, Some(index)) => {
+                    blocklist.blocked_accounts.swap_remove(index);
+                }
+                (false, None) => return err!(UniversalNftError::BlocklistEntryNotFound),
+                // Already blocked - nothing to do
+                (true, Some(_)) => {}
+            }
+        }
+        BlocklistEntry::ForeignAddress(address) => {
+            require!(
+                !address.is_empty() && address.len() <= Blocklist::MAX_FOREIGN_ADDRESS_LEN,
+                UniversalNftError::InvalidRecipientAddress
+            );
+            let position = blocklist
+                .blocked_foreign_addresses
+                .iter()
+                .position(|a| a == address);
+            match (blocked, position) {
+                (true, None) => {
+                    require!(
+                        blocklist.blocked_foreign_addresses.len()
+                            < Blocklist::MAX_BLOCKED_FOREIGN_ADDRESSES,
+                        UniversalNftError::BlocklistFull
+                    );
+                    blocklist.blocked_foreign_addresses.push(address.clone());
+                }
+                (false, Some(index)) => {
+                    blocklist.blocked_foreign_addresses.swap_remove(index);
+                }
+                (false, None) => return err!(UniversalNftError::BlocklistEntryNotFound),
+                (true, Some(_)) => {}
+            }
+        }
+    }
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::UpdateBlocklist,
+        ctx.accounts.authority.key(),
+        &(&entry, blocked).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(BlocklistUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        entry,
+        blocked,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Blocklist updated, blocked: {}", blocked);
+
+    Ok(())
+}
+
+#[event]
+pub struct BlocklistUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub entry: BlocklistEntry,
+    pub blocked: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
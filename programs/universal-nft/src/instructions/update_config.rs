@@ -0,0 +1,494 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use crate::state::{ProgramState, CrossChainConfig, CredentialRequirement, TokenGate, RelayerRequirement, AuditLog, AuditEntry, AuditAction, CpiAllowlist, ValidationLimits};
+use crate::error::UniversalNftError;
+use crate::constants::{AUDIT_ENTRY_SEED, AUDIT_LOG_SEED, CPI_ALLOWLIST_SEED, CROSS_CHAIN_CONFIG_SEED, PROGRAM_STATE_SEED};
+use crate::utils::security::require_allowed_caller;
+use crate::utils::validation::validate_token_gate_config;
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = program_state.authority == authority.key() @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
+        bump = cross_chain_config.bump
+    )]
+    pub cross_chain_config: Account<'info, CrossChainConfig>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetSunset<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = program_state.authority == authority.key() @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+pub fn update_gateway_handler(ctx: Context<UpdateConfig>, new_gateway: Pubkey) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    require!(new_gateway != Pubkey::default(), UniversalNftError::InvalidGateway);
+
+    let cross_chain_config = &mut ctx.accounts.cross_chain_config;
+    let old_gateway = cross_chain_config.gateway_address;
+    cross_chain_config.gateway_address = new_gateway;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::UpdateGateway,
+        ctx.accounts.authority.key(),
+        &(old_gateway, new_gateway).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(GatewayUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        old_gateway,
+        new_gateway,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Gateway updated from {} to {}", old_gateway, new_gateway);
+
+    Ok(())
+}
+
+pub fn rotate_tss_handler(ctx: Context<UpdateConfig>, new_tss: Pubkey) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    require!(new_tss != Pubkey::default(), UniversalNftError::InvalidTssAuthority);
+
+    let cross_chain_config = &mut ctx.accounts.cross_chain_config;
+    let old_tss = cross_chain_config.tss_address;
+    cross_chain_config.tss_address = new_tss;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::RotateTss,
+        ctx.accounts.authority.key(),
+        &(old_tss, new_tss).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(TssRotatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        old_tss,
+        new_tss,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("TSS address rotated from {} to {}", old_tss, new_tss);
+
+    Ok(())
+}
+
+pub fn set_paused_handler(ctx: Context<UpdateConfig>, paused: bool) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    let cross_chain_config = &mut ctx.accounts.cross_chain_config;
+    let was_paused = cross_chain_config.is_paused;
+    cross_chain_config.is_paused = paused;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::SetPaused,
+        ctx.accounts.authority.key(),
+        &paused.try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(PausedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        was_paused,
+        is_paused: paused,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Cross-chain paused: {} -> {}", was_paused, paused);
+
+    Ok(())
+}
+
+/// Configure the outbound circuit breaker. Reaching `max_transfers` outbound
+/// transfers within `window_slots` slots pauses cross-chain functionality
+/// until an admin unpauses it; `max_transfers` of 0 disables the breaker.
+/// The current window restarts either way.
+pub fn set_circuit_breaker_handler(
+    ctx: Context<UpdateConfig>,
+    max_transfers: u64,
+    window_slots: u64,
+) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    require!(
+        max_transfers == 0 || window_slots > 0,
+        UniversalNftError::InvalidCircuitBreaker
+    );
+
+    let cross_chain_config = &mut ctx.accounts.cross_chain_config;
+    cross_chain_config.breaker_max_transfers = max_transfers;
+    cross_chain_config.breaker_window_slots = window_slots;
+    cross_chain_config.breaker_window_start = Clock::get()?.slot;
+    cross_chain_config.breaker_window_count = 0;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::SetCircuitBreaker,
+        ctx.accounts.authority.key(),
+        &(max_transfers, window_slots).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(CircuitBreakerUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        max_transfers,
+        window_slots,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Circuit breaker: {} transfers per {} slots", max_transfers, window_slots);
+
+    Ok(())
+}
+
+/// Require owners to present a credential to bridge out, or lift the
+/// requirement with `None`
+pub fn set_credential_requirement_handler(
+    ctx: Context<UpdateConfig>,
+    requirement: Option<CredentialRequirement>,
+) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    if let Some(requirement) = &requirement {
+        require!(
+            requirement.issuer != Pubkey::default() && requirement.credential_tag != Pubkey::default(),
+            UniversalNftError::InvalidCredentialRequirement
+        );
+    }
+
+    ctx.accounts.cross_chain_config.credential_requirement = requirement;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::SetCredentialRequirement,
+        ctx.accounts.authority.key(),
+        &requirement.try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(CredentialRequirementUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        requirement,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Credential requirement: {:?}", requirement);
+
+    Ok(())
+}
+
+/// Require owners bridging to any chain without its own gate to hold the
+/// gate token; `None` removes the global gate
+pub fn set_token_gate_handler(ctx: Context<UpdateConfig>, gate: Option<TokenGate>) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    validate_token_gate_config(gate.as_ref())?;
+    ctx.accounts.cross_chain_config.token_gate = gate;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::SetTokenGate,
+        ctx.accounts.authority.key(),
+        &gate.try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(TokenGateUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        gate,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Token gate: {:?}", gate);
+
+    Ok(())
+}
+
+/// Require relayers to hold a bond to call receive and completion
+/// instructions, or open relaying to anyone with `None`
+pub fn set_relayer_requirement_handler(
+    ctx: Context<UpdateConfig>,
+    requirement: Option<RelayerRequirement>,
+) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    if let Some(requirement) = &requirement {
+        require!(requirement.min_bond > 0, UniversalNftError::InvalidRelayerRequirement);
+    }
+
+    ctx.accounts.cross_chain_config.relayer_requirement = requirement;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::SetRelayerRequirement,
+        ctx.accounts.authority.key(),
+        &requirement.try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(RelayerRequirementUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        requirement,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Relayer requirement: {:?}", requirement);
+
+    Ok(())
+}
+
+pub fn set_validation_limits_handler(ctx: Context<UpdateConfig>, limits: ValidationLimits) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+    require!(limits.is_within_ceiling(), UniversalNftError::InvalidValidationLimits);
+
+    let program_state = &mut ctx.accounts.program_state;
+    let old_limits = program_state.validation_limits;
+    program_state.validation_limits = limits;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::SetValidationLimits,
+        ctx.accounts.authority.key(),
+        &(old_limits, limits).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(ValidationLimitsUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        old_limits,
+        new_limits: limits,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Validation limits: {:?}", limits);
+
+    Ok(())
+}
+
+pub fn set_sunset_handler(ctx: Context<SetSunset>, sunset: bool) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    let program_state = &mut ctx.accounts.program_state;
+    let was_sunset = program_state.is_sunset;
+    program_state.is_sunset = sunset;
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::SetSunset,
+        ctx.accounts.authority.key(),
+        &sunset.try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(SunsetEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        was_sunset,
+        is_sunset: sunset,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Sunset mode: {} -> {}", was_sunset, sunset);
+
+    Ok(())
+}
+
+#[event]
+pub struct GatewayUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub old_gateway: Pubkey,
+    pub new_gateway: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TssRotatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub old_tss: Pubkey,
+    pub new_tss: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PausedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub was_paused: bool,
+    pub is_paused: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SunsetEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub was_sunset: bool,
+    pub is_sunset: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CircuitBreakerUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub max_transfers: u64,
+    pub window_slots: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CredentialRequirementUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub requirement: Option<CredentialRequirement>,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenGateUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub gate: Option<TokenGate>,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RelayerRequirementUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub requirement: Option<RelayerRequirement>,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ValidationLimitsUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub old_limits: ValidationLimits,
+    pub new_limits: ValidationLimits,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CircuitBreakerTrippedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub window_start: u64,
+    pub window_count: u64,
+    pub max_transfers: u64,
+    pub slot: u64,
+    pub timestamp: i64,
+}
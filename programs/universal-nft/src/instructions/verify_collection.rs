@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use crate::state::{ProgramState, CollectionConfig, ForeignCollection, VerificationBadge, AuditLog, AuditEntry, AuditAction, CpiAllowlist};
+use crate::error::UniversalNftError;
+use crate::constants::{AUDIT_ENTRY_SEED, AUDIT_LOG_SEED, COLLECTION_CONFIG_SEED, CPI_ALLOWLIST_SEED, PROGRAM_STATE_SEED};
+use crate::utils::security::require_allowed_caller;
+
+/// Takes the collection's config, its foreign collection registration, or both
+#[derive(Accounts)]
+#[instruction(collection_mint: Pubkey)]
+pub struct SetCollectionVerified<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = program_state.authority == authority.key() @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_CONFIG_SEED, collection_mint.as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Option<Box<Account<'info, CollectionConfig>>>,
+
+    #[account(
+        mut,
+        constraint = foreign_collection.collection_mint == collection_mint @ UniversalNftError::CollectionNotRegistered
+    )]
+    pub foreign_collection: Option<Box<Account<'info, ForeignCollection>>>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Read-only; pass whichever of the two accounts the collection has
+#[derive(Accounts)]
+#[instruction(collection_mint: Pubkey)]
+pub struct GetCollectionVerification<'info> {
+    #[account(
+        seeds = [COLLECTION_CONFIG_SEED, collection_mint.as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Option<Box<Account<'info, CollectionConfig>>>,
+
+    #[account(
+        constraint = foreign_collection.collection_mint == collection_mint @ UniversalNftError::CollectionNotRegistered
+    )]
+    pub foreign_collection: Option<Box<Account<'info, ForeignCollection>>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CollectionVerification {
+    pub collection_mint: Pubkey,
+    pub registered: bool, // False when neither account was passed
+    pub badge: VerificationBadge,
+}
+
+/// Grant or revoke `collection_mint`'s verified badge. A foreign collection
+/// registered from several chains carries a badge per registration.
+pub fn set_handler(ctx: Context<SetCollectionVerified>, collection_mint: Pubkey, verified: bool) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+    require!(
+        ctx.accounts.collection_config.is_some() || ctx.accounts.foreign_collection.is_some(),
+        UniversalNftError::CollectionNotRegistered
+    );
+
+    let authority = ctx.accounts.authority.key();
+    let now = Clock::get()?.unix_timestamp;
+    let badge = VerificationBadge {
+        verified,
+        updated_by: authority,
+        updated_at: now,
+    };
+    if let Some(collection_config) = ctx.accounts.collection_config.as_mut() {
+        collection_config.verification = badge;
+    }
+    let origin_chain_id = match ctx.accounts.foreign_collection.as_mut() {
+        Some(foreign_collection) => {
+            foreign_collection.verification = badge;
+            Some(foreign_collection.origin_chain_id)
+        }
+        None => None,
+    };
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::SetCollectionVerified,
+        authority,
+        &(collection_mint, verified).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(CollectionVerificationUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        collection_mint,
+        verified,
+        origin_chain_id,
+        authority,
+        timestamp: now,
+    });
+
+    msg!("Collection {} verified: {}", collection_mint, verified);
+
+    Ok(())
+}
+
+/// The collection's badge, from its config when passed and otherwise from its
+/// foreign collection registration
+pub fn get_handler(ctx: Context<GetCollectionVerification>, collection_mint: Pubkey) -> Result<CollectionVerification> {
+    let badge = ctx
+        .accounts
+        .collection_config
+        .as_ref()
+        .map(|collection_config| collection_config.verification)
+        .or_else(|| {
+            ctx.accounts
+                .foreign_collection
+                .as_ref()
+                .map(|foreign_collection| foreign_collection.verification)
+        });
+
+    Ok(CollectionVerification {
+        collection_mint,
+        registered: badge.is_some(),
+        badge: badge.unwrap_or_default(),
+    })
+}
+
+#[event]
+pub struct CollectionVerificationUpdatedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub collection_mint: Pubkey,
+    pub verified: bool,
+    pub origin_chain_id: Option<u64>, // Set when a foreign collection registration was updated
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
@@ -1,13 +1,21 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount};
-use crate::state::NftMetadata;
+use crate::state::{ProgramState, NftMetadata};
 use crate::error::UniversalNftError;
+use crate::constants::{NFT_METADATA_SEED, PROGRAM_STATE_SEED};
 
 #[derive(Accounts)]
 #[instruction(token_mint: Pubkey)]
 pub struct VerifyOwnership<'info> {
     #[account(
-        seeds = [b"nft_metadata", token_mint.as_ref()],
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [NFT_METADATA_SEED, token_mint.as_ref()],
         bump = nft_metadata.bump
     )]
     pub nft_metadata: Account<'info, NftMetadata>,
@@ -45,6 +53,8 @@ pub fn handler(
 
     // Emit verification event
     emit!(OwnershipVerifiedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
         mint: token_mint,
         owner: ctx.accounts.owner.key(),
         cross_chain_enabled: nft_metadata.cross_chain_enabled,
@@ -57,6 +67,8 @@ pub fn handler(
 
 #[event]
 pub struct OwnershipVerifiedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
     pub mint: Pubkey,
     pub owner: Pubkey,
     pub cross_chain_enabled: bool,
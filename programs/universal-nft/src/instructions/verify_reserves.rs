@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use crate::state::{ProgramState, CrossChainConfig, CollectionLedger};
+use crate::error::UniversalNftError;
+use crate::constants::{COLLECTION_LEDGER_SEED, CROSS_CHAIN_CONFIG_SEED, PROGRAM_STATE_SEED};
+use crate::utils::security::{verify_tss_signature, signed_message};
+use universal_nft_messages::ReserveAttestation;
+
+/// Attestations older than this are rejected so a stale count can't mask drift
+pub const MAX_ATTESTATION_AGE: i64 = 600;
+
+#[derive(Accounts)]
+#[instruction(origin_chain_id: u64, origin_contract: Vec<u8>)]
+pub struct VerifyReserves<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
+        bump = cross_chain_config.bump
+    )]
+    pub cross_chain_config: Account<'info, CrossChainConfig>,
+
+    #[account(
+        seeds = [
+            COLLECTION_LEDGER_SEED,
+            origin_chain_id.to_le_bytes().as_ref(),
+            origin_contract.as_slice()
+        ],
+        bump = collection_ledger.bump
+    )]
+    pub collection_ledger: Account<'info, CollectionLedger>,
+
+    /// Anyone may run the check; the result is only reported through the event
+    pub caller: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<VerifyReserves>,
+    origin_chain_id: u64,
+    origin_contract: Vec<u8>,
+    attested_remote_count: u64,
+    attested_at: i64,
+    tss_signature: Vec<u8>,
+) -> Result<()> {
+    let cross_chain_config = &ctx.accounts.cross_chain_config;
+    let collection_ledger = &ctx.accounts.collection_ledger;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        attested_at <= now && now - attested_at <= MAX_ATTESTATION_AGE,
+        UniversalNftError::StaleAttestation
+    );
+
+    // The remote count is attested by TSS like any other inbound message
+    let message = signed_message(
+        cross_chain_config.chain_id,
+        &ReserveAttestation {
+            origin_chain_id,
+            origin_contract: &origin_contract,
+            attested_remote_count,
+            attested_at,
+        },
+    );
+
+    let is_valid = verify_tss_signature(
+        &message,
+        &tss_signature,
+        &cross_chain_config.tss_address,
+    )?;
+    require!(is_valid, UniversalNftError::InvalidTssSignature);
+
+    // Native collections: locked here == wrapped elsewhere.
+    // Foreign collections: wrapped here == escrowed on the origin chain.
+    let local_count = collection_ledger.local_reserve_count();
+    let healthy = local_count == attested_remote_count;
+
+    // Report rather than fail, so monitoring sees every result
+    emit!(ReserveHealthEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        origin_chain_id,
+        origin_contract,
+        native_locked: collection_ledger.native_locked,
+        wrapped_supply: collection_ledger.wrapped_supply,
+        attested_remote_count,
+        attested_at,
+        healthy,
+        timestamp: now,
+    });
+
+    if healthy {
+        msg!("Reserves healthy for chain {}: {}", origin_chain_id, local_count);
+    } else {
+        msg!(
+            "Reserve mismatch for chain {}: local {}, remote {}",
+            origin_chain_id,
+            local_count,
+            attested_remote_count
+        );
+    }
+
+    Ok(())
+}
+
+#[event]
+pub struct ReserveHealthEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub origin_chain_id: u64,
+    pub origin_contract: Vec<u8>,
+    pub native_locked: u64,
+    pub wrapped_supply: u64,
+    pub attested_remote_count: u64,
+    pub attested_at: i64,
+    pub healthy: bool,
+    pub timestamp: i64,
+}
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+use crate::state::{ProgramState, CrossChainConfig, Relayer};
+use crate::error::UniversalNftError;
+use crate::constants::{CROSS_CHAIN_CONFIG_SEED, PROGRAM_STATE_SEED, RELAYER_SEED};
+use crate::utils::relayers::relayer_bond_account_address;
+
+#[derive(Accounts)]
+pub struct WithdrawBond<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [CROSS_CHAIN_CONFIG_SEED],
+        bump = cross_chain_config.bump
+    )]
+    pub cross_chain_config: Account<'info, CrossChainConfig>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [RELAYER_SEED, owner.key().as_ref()],
+        bump = relayer.bump
+    )]
+    pub relayer: Account<'info, Relayer>,
+
+    /// Required for SPL bonds; closed once emptied
+    #[account(mut)]
+    pub relayer_bond_account: Option<Account<'info, TokenAccount>>,
+
+    /// Owner's account of the bond mint; required for SPL bonds
+    #[account(
+        mut,
+        constraint = owner_bond_account.owner == owner.key() @ UniversalNftError::InvalidRelayerBond
+    )]
+    pub owner_bond_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Return a relayer's whole bond and close its registration once the
+/// withdrawal cooldown has passed since it last relayed
+pub fn handler(ctx: Context<WithdrawBond>) -> Result<()> {
+    let relayer = &ctx.accounts.relayer;
+    let cooldown = ctx
+        .accounts
+        .cross_chain_config
+        .relayer_requirement
+        .map_or(0, |requirement| requirement.withdraw_cooldown_seconds);
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= relayer.last_active_at.saturating_add(i64::from(cooldown)),
+        UniversalNftError::RelayerCooldownActive
+    );
+
+    // Lamport bonds leave with the account's rent when it closes
+    let amount = relayer.bond;
+    if let Some(mint) = relayer.bond_mint {
+        let (Some(relayer_bond_account), Some(owner_bond_account)) =
+            (&ctx.accounts.relayer_bond_account, &ctx.accounts.owner_bond_account)
+        else {
+            return err!(UniversalNftError::InvalidRelayerBond);
+        };
+        require_keys_eq!(
+            relayer_bond_account.key(),
+            relayer_bond_account_address(&relayer.key(), &mint),
+            UniversalNftError::InvalidRelayerBond
+        );
+
+        let owner_key = ctx.accounts.owner.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[RELAYER_SEED, owner_key.as_ref(), &[relayer.bump]]];
+        let cpi_accounts = token::Transfer {
+            from: relayer_bond_account.to_account_info(),
+            to: owner_bond_account.to_account_info(),
+            authority: relayer.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            ),
+            relayer_bond_account.amount,
+        )?;
+
+        let cpi_accounts = token::CloseAccount {
+            account: relayer_bond_account.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: relayer.to_account_info(),
+        };
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        ))?;
+    }
+
+    emit!(RelayerBondWithdrawnEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        relayer: relayer.owner,
+        bond_mint: relayer.bond_mint,
+        amount,
+        timestamp: now,
+    });
+
+    msg!("Relayer {} withdrew its bond of {}", relayer.owner, amount);
+
+    Ok(())
+}
+
+#[event]
+pub struct RelayerBondWithdrawnEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub relayer: Pubkey,
+    pub bond_mint: Option<Pubkey>,
+    pub amount: u64,
+    pub timestamp: i64,
+}
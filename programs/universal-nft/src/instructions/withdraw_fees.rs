@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Token, TokenAccount};
+use crate::state::{ProgramState, AuditLog, AuditEntry, AuditAction, CpiAllowlist, FeeReserve};
+use crate::error::UniversalNftError;
+use crate::constants::{AUDIT_ENTRY_SEED, AUDIT_LOG_SEED, CPI_ALLOWLIST_SEED, FEE_RESERVE_SEED, FEE_TREASURY_SEED, PROGRAM_STATE_SEED};
+use crate::utils::security::require_allowed_caller;
+use crate::utils::fees::treasury_fee_account_address;
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = program_state.authority == authority.key() @ UniversalNftError::Unauthorized
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// CHECK: Fee treasury PDA; holds no data
+    #[account(
+        mut,
+        seeds = [FEE_TREASURY_SEED],
+        bump
+    )]
+    pub fee_treasury: UncheckedAccount<'info>,
+
+    /// CHECK: `FeeReserve`, read if it exists; its refundable fees stay in the treasury
+    #[account(seeds = [FEE_RESERVE_SEED], bump)]
+    pub fee_reserve: UncheckedAccount<'info>,
+
+    /// Required for SPL fees
+    #[account(mut)]
+    pub treasury_fee_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Receives lamport fees
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Receives SPL fees; required for SPL fees
+    #[account(mut)]
+    pub recipient_fee_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [CPI_ALLOWLIST_SEED],
+        bump = cpi_allowlist.bump
+    )]
+    pub cpi_allowlist: Account<'info, CpiAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditEntry::INIT_SPACE,
+        seeds = [AUDIT_ENTRY_SEED, audit_log.entry_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_entry: Account<'info, AuditEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar, inspected to identify a calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Move collected fees out of the fee treasury: lamports when `mint` is
+/// `None`, otherwise that mint's tokens. The treasury keeps enough lamports
+/// to stay rent exempt, and every fee transfers may still claim back.
+pub fn handler(ctx: Context<WithdrawFees>, mint: Option<Pubkey>, amount: u64) -> Result<()> {
+    require_allowed_caller(&ctx.accounts.cpi_allowlist, &ctx.accounts.instructions_sysvar)?;
+
+    let signer_seeds: &[&[&[u8]]] = &[&[FEE_TREASURY_SEED, &[ctx.bumps.fee_treasury]]];
+    let reserved = FeeReserve::outstanding_if_exists(&ctx.accounts.fee_reserve, mint)?;
+    let recipient = match mint {
+        None => {
+            let available = ctx
+                .accounts
+                .fee_treasury
+                .lamports()
+                .saturating_sub(Rent::get()?.minimum_balance(0))
+                .saturating_sub(reserved);
+            require!(amount <= available, UniversalNftError::InsufficientFeeBalance);
+
+            let cpi_accounts = system_program::Transfer {
+                from: ctx.accounts.fee_treasury.to_account_info(),
+                to: ctx.accounts.recipient.to_account_info(),
+            };
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    cpi_accounts,
+                    signer_seeds,
+                ),
+                amount,
+            )?;
+            ctx.accounts.recipient.key()
+        }
+        Some(mint) => {
+            let (Some(treasury_fee_account), Some(recipient_fee_account)) =
+                (&ctx.accounts.treasury_fee_account, &ctx.accounts.recipient_fee_account)
+            else {
+                return err!(UniversalNftError::MissingFeeAccount);
+            };
+            require_keys_eq!(
+                treasury_fee_account.key(),
+                treasury_fee_account_address(&mint),
+                UniversalNftError::InvalidFeeAccount
+            );
+            require!(
+                treasury_fee_account.amount.saturating_sub(reserved) >= amount,
+                UniversalNftError::InsufficientFeeBalance
+            );
+
+            let cpi_accounts = token::Transfer {
+                from: treasury_fee_account.to_account_info(),
+                to: recipient_fee_account.to_account_info(),
+                authority: ctx.accounts.fee_treasury.to_account_info(),
+            };
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    cpi_accounts,
+                    signer_seeds,
+                ),
+                amount,
+            )?;
+            recipient_fee_account.key()
+        }
+    };
+
+    ctx.accounts.audit_log.append(
+        &mut ctx.accounts.audit_entry,
+        AuditAction::WithdrawFees,
+        ctx.accounts.authority.key(),
+        &(mint, amount, recipient).try_to_vec()?,
+        ctx.bumps.audit_entry,
+    )?;
+
+    emit!(FeesWithdrawnEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        mint,
+        amount,
+        recipient,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Withdrew {} fees ({:?}) to {}", amount, mint, recipient);
+
+    Ok(())
+}
+
+#[event]
+pub struct FeesWithdrawnEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    pub recipient: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::{ProgramState, RentDeposit};
+use crate::error::UniversalNftError;
+use crate::constants::{PROGRAM_STATE_SEED, RENT_DEPOSIT_SEED};
+use super::deposit_rent::RentDepositUpdatedEvent;
+
+#[derive(Accounts)]
+pub struct WithdrawRentDeposit<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [RENT_DEPOSIT_SEED, owner.key().as_ref()],
+        bump = rent_deposit.bump
+    )]
+    pub rent_deposit: Account<'info, RentDeposit>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Take `amount` lamports back out of the signer's rent deposit
+pub fn handler(ctx: Context<WithdrawRentDeposit>, amount: u64) -> Result<()> {
+    let rent_deposit = &mut ctx.accounts.rent_deposit;
+    rent_deposit.balance = rent_deposit
+        .balance
+        .checked_sub(amount)
+        .ok_or(UniversalNftError::RentDepositInsufficient)?;
+
+    let deposit_info = rent_deposit.to_account_info();
+    let owner_info = ctx.accounts.owner.to_account_info();
+    **deposit_info.try_borrow_mut_lamports()? -= amount;
+    **owner_info.try_borrow_mut_lamports()? += amount;
+
+    emit!(RentDepositUpdatedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        owner: rent_deposit.owner,
+        balance: rent_deposit.balance,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Withdrew {} lamports from the rent deposit of {}", amount, rent_deposit.owner);
+
+    Ok(())
+}
@@ -0,0 +1,211 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Token, TokenAccount, Mint};
+use mpl_token_metadata::accounts::Metadata;
+use crate::state::{ProgramState, NftMetadata, Blocklist};
+use crate::error::UniversalNftError;
+use crate::constants::{BLOCKLIST_SEED, NFT_METADATA_SEED, PROGRAM_STATE_SEED, WRAP_ESCROW_SEED, WRAPPED_MINT_SEED};
+use crate::utils::ipfs::ipfs_multihash;
+use crate::utils::validation::{validate_display_text, validate_external_nft_mint};
+use crate::config::SELF_CHAIN_ID;
+
+/// Escrows an existing Metaplex NFT and mints a universal NFT that stands in for it.
+/// The wrapper mint is a PDA of the original, so wrapping again after an unwrap reuses it.
+#[derive(Accounts)]
+pub struct WrapExistingNft<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump,
+        constraint = program_state.is_initialized @ UniversalNftError::ProgramNotInitialized,
+        constraint = !program_state.is_sunset @ UniversalNftError::ProgramSunset
+    )]
+    pub program_state: Box<Account<'info, ProgramState>>,
+
+    #[account(
+        seeds = [BLOCKLIST_SEED],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Box<Account<'info, Blocklist>>,
+
+    #[account(
+        constraint = original_mint.decimals == 0 && original_mint.supply == 1 @ UniversalNftError::InvalidMint
+    )]
+    pub original_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Address and owner are checked here, contents are parsed in the handler
+    #[account(
+        address = Metadata::find_pda(&original_mint.key()).0 @ UniversalNftError::InvalidExternalMetadata,
+        owner = mpl_token_metadata::ID @ UniversalNftError::InvalidExternalMetadata
+    )]
+    pub original_metadata: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = owner_original_account.mint == original_mint.key() @ UniversalNftError::InvalidMint,
+        constraint = owner_original_account.owner == owner.key() @ UniversalNftError::Unauthorized,
+        constraint = owner_original_account.amount == 1 @ UniversalNftError::InsufficientTokens
+    )]
+    pub owner_original_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = original_mint,
+        token::authority = wrap_escrow,
+        seeds = [WRAP_ESCROW_SEED, original_mint.key().as_ref()],
+        bump
+    )]
+    pub wrap_escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = mint,
+        seeds = [WRAPPED_MINT_SEED, original_mint.key().as_ref()],
+        bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + NftMetadata::INIT_SPACE,
+        seeds = [NFT_METADATA_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub nft_metadata: Box<Account<'info, NftMetadata>>,
+
+    pub owner: Signer<'info>,
+
+    /// Funds the wrapped mint, its metadata and the escrow
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler(ctx: Context<WrapExistingNft>) -> Result<()> {
+    require!(
+        !ctx.accounts.blocklist.is_account_blocked(&ctx.accounts.owner.key()),
+        UniversalNftError::AddressBlocked
+    );
+    validate_external_nft_mint(&ctx.accounts.original_mint.key(), &ctx.accounts.original_mint)?;
+
+    let metadata = Metadata::try_from(&ctx.accounts.original_metadata.to_account_info())
+        .map_err(|_| UniversalNftError::InvalidExternalMetadata)?;
+    require_keys_eq!(
+        metadata.mint,
+        ctx.accounts.original_mint.key(),
+        UniversalNftError::InvalidExternalMetadata
+    );
+
+    // Metaplex pads strings with NUL bytes
+    let name = metadata.name.trim_end_matches('\0').to_string();
+    let symbol = metadata.symbol.trim_end_matches('\0').to_string();
+    let metadata_uri = metadata.uri.trim_end_matches('\0').to_string();
+    let limits = ctx.accounts.program_state.validation_limits;
+    require!(metadata_uri.len() <= limits.max_uri_len as usize, UniversalNftError::InvalidMetadataUri);
+    validate_display_text(&name, limits.max_name_len as usize)?;
+    // Many Metaplex NFTs have no symbol
+    if !symbol.is_empty() {
+        validate_display_text(&symbol, limits.max_symbol_len as usize)?;
+    }
+
+    // Only a verified collection is trusted for collection-level settings
+    let collection = metadata
+        .collection
+        .as_ref()
+        .filter(|collection| collection.verified)
+        .map_or(Pubkey::default(), |collection| collection.key);
+
+    // The first verified creator stands in as the original creator, falling back to the update authority
+    let creator = metadata
+        .creators
+        .as_ref()
+        .and_then(|creators| creators.iter().find(|creator| creator.verified))
+        .map_or(metadata.update_authority, |creator| creator.address);
+
+    // Escrow the original
+    let cpi_accounts = token::Transfer {
+        from: ctx.accounts.owner_original_account.to_account_info(),
+        to: ctx.accounts.wrap_escrow.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, 1)?;
+
+    // Mint the universal stand-in
+    let original_mint = ctx.accounts.original_mint.key();
+    let mint_bump = ctx.bumps.mint;
+    let signer_seeds: &[&[&[u8]]] = &[&[WRAPPED_MINT_SEED, original_mint.as_ref(), &[mint_bump]]];
+    let cpi_accounts = token::MintTo {
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.token_account.to_account_info(),
+        authority: ctx.accounts.mint.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::mint_to(cpi_ctx, 1)?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let nft_metadata = &mut ctx.accounts.nft_metadata;
+    nft_metadata.mint = ctx.accounts.mint.key();
+    nft_metadata.original_owner = creator;
+    nft_metadata.current_owner = ctx.accounts.owner.key();
+    nft_metadata.metadata_uri = metadata_uri;
+    nft_metadata.name = name;
+    nft_metadata.symbol = symbol;
+    nft_metadata.cross_chain_enabled = true;
+    nft_metadata.is_locked = false;
+    nft_metadata.origin_chain_id = SELF_CHAIN_ID;
+    nft_metadata.origin_contract = Vec::new();
+    nft_metadata.fallback_uri = None;
+    nft_metadata.metadata_multihash = ipfs_multihash(&nft_metadata.metadata_uri)?;
+    nft_metadata.child_count = 0;
+    nft_metadata.origin_token_id = [0u8; 32];
+    nft_metadata.collection = collection;
+    nft_metadata.wrapped_mint = original_mint;
+    nft_metadata.creation_timestamp = timestamp;
+    nft_metadata.bump = ctx.bumps.nft_metadata;
+
+    emit!(NftWrappedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: ctx.accounts.program_state.next_event_sequence(),
+        original_mint,
+        mint: ctx.accounts.mint.key(),
+        owner: ctx.accounts.owner.key(),
+        collection,
+        timestamp,
+    });
+
+    msg!("Wrapped {} as universal NFT {}", original_mint, ctx.accounts.mint.key());
+
+    Ok(())
+}
+
+#[event]
+pub struct NftWrappedEvent {
+    pub schema_version: u8,
+    pub sequence: u64,
+    pub original_mint: Pubkey,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub collection: Pubkey,
+    pub timestamp: i64,
+}
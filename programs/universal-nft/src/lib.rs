@@ -4,10 +4,16 @@ pub mod instructions;
 pub mod state;
 pub mod error;
 pub mod utils;
+pub mod adapters;
+pub mod config;
+pub mod constants;
 
 use instructions::*;
+use state::{BlocklistEntry, OptOutSubject, BridgeAdapterKind, CredentialRequirement, TokenGate, BridgeFee, RelayerRequirement, ValidationLimits, RoyaltyShare, LocalizedUri, MetadataVariant, CollectionAction};
+use adapters::LzReceiveParams;
 
-declare_id!("UnivNFT111111111111111111111111111111111111");
+// `declare_id!` for the cluster being built; see build.rs
+include!(concat!(env!("OUT_DIR"), "/program_id.rs"));
 
 #[program]
 pub mod universal_nft {
@@ -45,14 +51,18 @@ pub mod universal_nft {
     }
 
     /// Receive an NFT from another chain via ZetaChain gateway
-    pub fn receive_cross_chain(
-        ctx: Context<ReceiveCrossChain>,
+    #[allow(clippy::too_many_arguments)]
+    pub fn receive_cross_chain<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReceiveCrossChain<'info>>,
         origin_chain_id: u64,
-        origin_tx_hash: Vec<u8>,
+        origin_tx_hash: [u8; 32],
+        origin_contract: Vec<u8>,
+        origin_token_id: [u8; 32],
         metadata_uri: String,
         name: String,
         symbol: String,
         original_owner: Vec<u8>,
+        intended_recipient: Pubkey,
         tss_signature: Vec<u8>,
         nonce: u64,
     ) -> Result<()> {
@@ -60,10 +70,13 @@ pub mod universal_nft {
             ctx,
             origin_chain_id,
             origin_tx_hash,
+            origin_contract,
+            origin_token_id,
             metadata_uri,
             name,
             symbol,
             original_owner,
+            intended_recipient,
             tss_signature,
             nonce,
         )
@@ -76,4 +89,936 @@ pub mod universal_nft {
     ) -> Result<()> {
         instructions::verify_ownership::handler(ctx, token_mint)
     }
+
+    /// Add or remove an address from the compliance blocklist (admin only)
+    pub fn update_blocklist(
+        ctx: Context<UpdateBlocklist>,
+        entry: BlocklistEntry,
+        blocked: bool,
+    ) -> Result<()> {
+        instructions::update_blocklist::handler(ctx, entry, blocked)
+    }
+
+    /// Register a connected chain in the chain registry (admin only)
+    pub fn register_chain(ctx: Context<RegisterChain>, chain_id: u64) -> Result<()> {
+        instructions::register_chain::handler(ctx, chain_id)
+    }
+
+    /// Halt transfers to and from a single connected chain (admin only)
+    pub fn pause_chain(ctx: Context<PauseChain>, chain_id: u64) -> Result<()> {
+        instructions::pause_chain::handler(ctx, chain_id, true)
+    }
+
+    /// Resume transfers to and from a single connected chain (admin only)
+    pub fn unpause_chain(ctx: Context<PauseChain>, chain_id: u64) -> Result<()> {
+        instructions::pause_chain::handler(ctx, chain_id, false)
+    }
+
+    /// Point the program at a new ZetaChain gateway (admin only)
+    pub fn update_gateway(ctx: Context<UpdateConfig>, new_gateway: Pubkey) -> Result<()> {
+        instructions::update_config::update_gateway_handler(ctx, new_gateway)
+    }
+
+    /// Rotate the TSS address used to verify inbound messages (admin only)
+    pub fn rotate_tss(ctx: Context<UpdateConfig>, new_tss: Pubkey) -> Result<()> {
+        instructions::update_config::rotate_tss_handler(ctx, new_tss)
+    }
+
+    /// Pause or resume all cross-chain functionality (admin only)
+    pub fn set_paused(ctx: Context<UpdateConfig>, paused: bool) -> Result<()> {
+        instructions::update_config::set_paused_handler(ctx, paused)
+    }
+
+    /// Pause cross-chain automatically after `max_transfers` outbound transfers within `window_slots` slots; 0 disables (admin only)
+    pub fn set_circuit_breaker(ctx: Context<UpdateConfig>, max_transfers: u64, window_slots: u64) -> Result<()> {
+        instructions::update_config::set_circuit_breaker_handler(ctx, max_transfers, window_slots)
+    }
+
+    /// Require a credential account from owners bridging out; `None` lifts the requirement (admin only)
+    pub fn set_credential_requirement(
+        ctx: Context<UpdateConfig>,
+        requirement: Option<CredentialRequirement>,
+    ) -> Result<()> {
+        instructions::update_config::set_credential_requirement_handler(ctx, requirement)
+    }
+
+    /// Require a minimum balance of an SPL token to bridge out to chains without their own gate; `None` removes it (admin only)
+    pub fn set_token_gate(ctx: Context<UpdateConfig>, gate: Option<TokenGate>) -> Result<()> {
+        instructions::update_config::set_token_gate_handler(ctx, gate)
+    }
+
+    /// Require a bond from relayers calling receive and completion instructions; `None` lets anyone relay (admin only)
+    pub fn set_relayer_requirement(
+        ctx: Context<UpdateConfig>,
+        requirement: Option<RelayerRequirement>,
+    ) -> Result<()> {
+        instructions::update_config::set_relayer_requirement_handler(ctx, requirement)
+    }
+
+    /// Tune the URI, name, symbol and TSS signature length limits, up to what accounts can store (admin only)
+    pub fn set_validation_limits(ctx: Context<UpdateConfig>, limits: ValidationLimits) -> Result<()> {
+        instructions::update_config::set_validation_limits_handler(ctx, limits)
+    }
+
+    /// Set a token gate for one destination chain, overriding the global gate; `None` falls back to it (admin only)
+    pub fn set_chain_token_gate(
+        ctx: Context<SetChainTokenGate>,
+        chain_id: u64,
+        gate: Option<TokenGate>,
+    ) -> Result<()> {
+        instructions::set_chain_token_gate::handler(ctx, chain_id, gate)
+    }
+
+    /// Set the fee charged per NFT bridged to a chain, in lamports or an SPL mint (authority or operator)
+    pub fn set_chain_fee(ctx: Context<SetChainFee>, chain_id: u64, fee: BridgeFee) -> Result<()> {
+        instructions::set_chain_fee::handler(ctx, chain_id, fee)
+    }
+
+    /// Move collected lamport (`mint` of `None`) or SPL fees out of the fee treasury (admin only)
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, mint: Option<Pubkey>, amount: u64) -> Result<()> {
+        instructions::withdraw_fees::handler(ctx, mint, amount)
+    }
+
+    /// Enter or leave withdraw-only mode: blocks new mints and outbound transfers (admin only)
+    pub fn set_sunset(ctx: Context<SetSunset>, sunset: bool) -> Result<()> {
+        instructions::update_config::set_sunset_handler(ctx, sunset)
+    }
+
+    /// Lock up to ten NFTs and send them to another chain in a single message
+    pub fn cross_chain_transfer_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InitiateBundleTransfer<'info>>,
+        destination_chain_id: u64,
+        recipient_address: Vec<u8>,
+        nonce: u64,
+        item_count: u8,
+    ) -> Result<()> {
+        instructions::cross_chain_transfer_batch::handler(ctx, destination_chain_id, recipient_address, nonce, item_count)
+    }
+
+    /// Transfer an NFT cross-chain together with escrowed SPL tokens in one message
+    pub fn cross_chain_transfer_with_value(
+        ctx: Context<InitiateCrossChainTransferWithValue>,
+        destination_chain_id: u64,
+        recipient_address: Vec<u8>,
+        nonce: u64,
+        value_amount: u64,
+    ) -> Result<()> {
+        instructions::cross_chain_transfer_with_value::handler(
+            ctx,
+            destination_chain_id,
+            recipient_address,
+            nonce,
+            value_amount,
+        )
+    }
+
+    /// Revert a pending transfer, unlocking the NFT and refunding any escrowed value (admin only)
+    pub fn revert_cross_chain_transfer(
+        ctx: Context<RevertCrossChainTransfer>,
+        mint: Pubkey,
+        nonce: u64,
+    ) -> Result<()> {
+        instructions::revert_cross_chain_transfer::handler(ctx, mint, nonce)
+    }
+
+    /// Lock an NFT for sale against a payment on another chain
+    pub fn create_swap(
+        ctx: Context<CreateSwap>,
+        destination_chain_id: u64,
+        payment_token: Vec<u8>,
+        payment_amount: u64,
+        buyer_address: Vec<u8>,
+        deadline: i64,
+    ) -> Result<()> {
+        instructions::create_swap::handler(
+            ctx,
+            destination_chain_id,
+            payment_token,
+            payment_amount,
+            buyer_address,
+            deadline,
+        )
+    }
+
+    /// Complete a swap once the TSS confirms the buyer's payment
+    pub fn complete_swap(
+        ctx: Context<CompleteSwap>,
+        mint: Pubkey,
+        payment_tx_hash: Vec<u8>,
+        tss_signature: Vec<u8>,
+    ) -> Result<()> {
+        instructions::complete_swap::handler(ctx, mint, payment_tx_hash, tss_signature)
+    }
+
+    /// Unlock the NFT of a swap whose deadline passed without payment
+    pub fn revert_swap(ctx: Context<RevertSwap>, mint: Pubkey) -> Result<()> {
+        instructions::revert_swap::handler(ctx, mint)
+    }
+
+    /// Rewrite a pre-versioning receipt into the current receipt layout (admin only)
+    pub fn migrate_receipt(ctx: Context<MigrateReceipt>) -> Result<()> {
+        instructions::migrate_receipt::handler(ctx)
+    }
+
+    /// Compare a collection's reserve counters with a TSS-attested remote count (permissionless)
+    pub fn verify_reserves(
+        ctx: Context<VerifyReserves>,
+        origin_chain_id: u64,
+        origin_contract: Vec<u8>,
+        attested_remote_count: u64,
+        attested_at: i64,
+        tss_signature: Vec<u8>,
+    ) -> Result<()> {
+        instructions::verify_reserves::handler(
+            ctx,
+            origin_chain_id,
+            origin_contract,
+            attested_remote_count,
+            attested_at,
+            tss_signature,
+        )
+    }
+
+    /// Register collection-wide settings (collection mint authority or program authority)
+    pub fn create_collection_config(ctx: Context<CreateCollectionConfig>, creator: Pubkey) -> Result<()> {
+        instructions::collection_config::create_handler(ctx, creator)
+    }
+
+    /// Enable or disable cross-chain transfers for a whole collection (creator or program authority; creator set approval when active)
+    pub fn set_collection_bridging(ctx: Context<SetCollectionBridging>, enabled: bool) -> Result<()> {
+        instructions::collection_config::set_bridging_handler(ctx, enabled)
+    }
+
+    /// Cap how many of a collection's items can be bridged out per day; 0 removes the cap (creator or program authority; creator set approval when active)
+    pub fn set_collection_velocity_limit(ctx: Context<SetCollectionBridging>, daily_limit: u32) -> Result<()> {
+        instructions::collection_config::set_velocity_limit_handler(ctx, daily_limit)
+    }
+
+    /// Opt a creator or collection out of (or back into) cross-chain wrapping
+    pub fn set_opt_out(ctx: Context<SetOptOut>, subject: OptOutSubject, opted_out: bool) -> Result<()> {
+        instructions::set_opt_out::handler(ctx, subject, opted_out)
+    }
+
+    /// Escrow an existing Metaplex NFT and mint a cross-chain capable stand-in for it
+    pub fn wrap_existing_nft(ctx: Context<WrapExistingNft>) -> Result<()> {
+        instructions::wrap_existing_nft::handler(ctx)
+    }
+
+    /// Burn a wrapped NFT's stand-in and return the escrowed original
+    pub fn unwrap_nft(ctx: Context<UnwrapNft>) -> Result<()> {
+        instructions::unwrap_nft::handler(ctx)
+    }
+
+    /// Burn a foreign NFT's inbound token, mint a standard Metaplex NFT in its place and stop tracking it
+    pub fn release_foreign_nft(ctx: Context<ReleaseForeignNft>) -> Result<()> {
+        instructions::release_foreign_nft::handler(ctx)
+    }
+
+    /// Register a collection from another chain (program authority, or anyone with a TSS attestation)
+    #[allow(clippy::too_many_arguments)]
+    pub fn register_foreign_collection(
+        ctx: Context<RegisterForeignCollection>,
+        origin_chain_id: u64,
+        origin_contract: Vec<u8>,
+        name: String,
+        symbol: String,
+        royalty_basis_points: u16,
+        royalty_recipient: Vec<u8>,
+        collection_mint: Pubkey,
+        tss_signature: Option<Vec<u8>>,
+    ) -> Result<()> {
+        instructions::register_foreign_collection::handler(
+            ctx,
+            origin_chain_id,
+            origin_contract,
+            name,
+            symbol,
+            royalty_basis_points,
+            royalty_recipient,
+            collection_mint,
+            tss_signature,
+        )
+    }
+
+    /// Return the program's semantic version and build commit via return data
+    pub fn get_program_version(ctx: Context<GetProgramVersion>) -> Result<ProgramVersion> {
+        instructions::get_program_version::handler(ctx)
+    }
+
+    /// Record the destination chain transaction that delivered an outbound transfer (TSS-attested)
+    pub fn acknowledge_delivery(
+        ctx: Context<AcknowledgeDelivery>,
+        mint: Pubkey,
+        nonce: u64,
+        destination_tx_hash: [u8; 32],
+        gas_spent: u64,
+        tss_signature: Vec<u8>,
+    ) -> Result<()> {
+        instructions::acknowledge_delivery::handler(ctx, mint, nonce, destination_tx_hash, gas_spent, tss_signature)
+    }
+
+    /// Unlock an outbound transfer the TSS attests will not be delivered, returning any escrowed value
+    pub fn acknowledge_failure(
+        ctx: Context<AcknowledgeFailure>,
+        mint: Pubkey,
+        nonce: u64,
+        tss_signature: Vec<u8>,
+    ) -> Result<()> {
+        instructions::acknowledge_failure::handler(ctx, mint, nonce, tss_signature)
+    }
+
+    /// Refund a finished transfer's fee to its payer: all of it after a failure, the unspent gas budget after delivery
+    pub fn claim_fee_refund(ctx: Context<ClaimFeeRefund>, mint: Pubkey, nonce: u64) -> Result<()> {
+        instructions::claim_fee_refund::handler(ctx, mint, nonce)
+    }
+
+    /// Settle a bundle the TSS attests was delivered, leaving any unspent gas budget refundable
+    pub fn acknowledge_bundle_delivery(
+        ctx: Context<AcknowledgeBundleDelivery>,
+        owner: Pubkey,
+        nonce: u64,
+        destination_tx_hash: [u8; 32],
+        gas_spent: u64,
+        tss_signature: Vec<u8>,
+    ) -> Result<()> {
+        instructions::bundle_settlement::acknowledge_delivery_handler(ctx, owner, nonce, destination_tx_hash, gas_spent, tss_signature)
+    }
+
+    /// Unlock every NFT of a bundle the TSS attests will not be delivered
+    pub fn acknowledge_bundle_failure<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AcknowledgeBundleFailure<'info>>,
+        owner: Pubkey,
+        nonce: u64,
+        tss_signature: Vec<u8>,
+    ) -> Result<()> {
+        instructions::bundle_settlement::acknowledge_failure_handler(ctx, owner, nonce, tss_signature)
+    }
+
+    /// Unlock every NFT of a stuck bundle (admin only)
+    pub fn revert_bundle_transfer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RevertBundleTransfer<'info>>,
+        owner: Pubkey,
+        nonce: u64,
+    ) -> Result<()> {
+        instructions::bundle_settlement::revert_handler(ctx, owner, nonce)
+    }
+
+    /// Refund a finished bundle's fee to its payer, as `claim_fee_refund` does for a single transfer
+    pub fn claim_bundle_fee_refund(ctx: Context<ClaimBundleFeeRefund>, owner: Pubkey, nonce: u64) -> Result<()> {
+        instructions::bundle_settlement::claim_fee_refund_handler(ctx, owner, nonce)
+    }
+
+    /// Stage the unlock of a native NFT the TSS attests came back from another chain
+    #[allow(clippy::too_many_arguments)]
+    pub fn stage_native_return(
+        ctx: Context<StageNativeReturn>,
+        mint: Pubkey,
+        origin_chain_id: u64,
+        origin_tx_hash: [u8; 32],
+        nonce: u64,
+        recipient: Pubkey,
+        staked_seconds: u64,
+        tss_signature: Vec<u8>,
+    ) -> Result<()> {
+        instructions::stage_native_return::handler(
+            ctx,
+            mint,
+            origin_chain_id,
+            origin_tx_hash,
+            nonce,
+            recipient,
+            staked_seconds,
+            tss_signature,
+        )
+    }
+
+    /// Unlock a staged native return once the origin chain's finality delay has passed
+    pub fn execute_native_return(ctx: Context<ExecuteNativeReturn>, mint: Pubkey) -> Result<()> {
+        instructions::execute_native_return::handler(ctx, mint)
+    }
+
+    /// Drop a staged native return before it executes, leaving the NFT locked (admin only)
+    pub fn cancel_native_return(ctx: Context<CancelNativeReturn>, mint: Pubkey) -> Result<()> {
+        instructions::cancel_native_return::handler(ctx, mint)
+    }
+
+    /// Set how long native NFTs returning from a chain wait before they unlock (admin only)
+    pub fn set_chain_finality_delay(
+        ctx: Context<SetChainFinalityDelay>,
+        chain_id: u64,
+        delay_seconds: u32,
+    ) -> Result<()> {
+        instructions::set_chain_finality_delay::handler(ctx, chain_id, delay_seconds)
+    }
+
+    /// Stage an inbound NFT without a TSS signature, claimable after the origin chain's challenge window (relayer only)
+    #[allow(clippy::too_many_arguments)]
+    pub fn stage_optimistic_receive(
+        ctx: Context<StageOptimisticReceive>,
+        origin_chain_id: u64,
+        origin_tx_hash: [u8; 32],
+        origin_contract: Vec<u8>,
+        origin_token_id: [u8; 32],
+        metadata_uri: String,
+        name: String,
+        symbol: String,
+        original_owner: Vec<u8>,
+        intended_recipient: Pubkey,
+        nonce: u64,
+    ) -> Result<()> {
+        instructions::stage_optimistic_receive::handler(
+            ctx,
+            origin_chain_id,
+            origin_tx_hash,
+            origin_contract,
+            origin_token_id,
+            metadata_uri,
+            name,
+            symbol,
+            original_owner,
+            intended_recipient,
+            nonce,
+        )
+    }
+
+    /// Mint a staged optimistic receive once its challenge window has closed
+    pub fn claim_optimistic_receive(
+        ctx: Context<ClaimOptimisticReceive>,
+        origin_tx_hash: [u8; 32],
+        nonce: u64,
+    ) -> Result<()> {
+        instructions::claim_optimistic_receive::handler(ctx, origin_tx_hash, nonce)
+    }
+
+    /// Cancel a pending optimistic receive with a TSS-signed fraud proof
+    pub fn cancel_optimistic_receive(
+        ctx: Context<CancelOptimisticReceive>,
+        origin_tx_hash: [u8; 32],
+        nonce: u64,
+        tss_signature: Vec<u8>,
+    ) -> Result<()> {
+        instructions::cancel_optimistic_receive::handler(ctx, origin_tx_hash, nonce, tss_signature)
+    }
+
+    /// Set the challenge window for optimistic receives from a chain; 0 disables them (admin only)
+    pub fn set_chain_optimistic_window(
+        ctx: Context<SetChainOptimisticWindow>,
+        chain_id: u64,
+        window_seconds: u32,
+    ) -> Result<()> {
+        instructions::set_chain_optimistic_window::handler(ctx, chain_id, window_seconds)
+    }
+
+    /// Register as a bonded watcher, or top up an existing bond
+    pub fn register_watcher(ctx: Context<RegisterWatcher>, bond: u64) -> Result<()> {
+        instructions::register_watcher::handler(ctx, bond)
+    }
+
+    /// Challenge a pending optimistic receive with evidence, freezing it until resolved (bonded watchers only)
+    pub fn submit_challenge(
+        ctx: Context<SubmitChallenge>,
+        origin_tx_hash: [u8; 32],
+        nonce: u64,
+        evidence: Vec<u8>,
+    ) -> Result<()> {
+        instructions::submit_challenge::handler(ctx, origin_tx_hash, nonce, evidence)
+    }
+
+    /// Uphold a challenge, slashing the submitter's bond to the challenger, or dismiss it (admin only)
+    pub fn resolve_challenge(
+        ctx: Context<ResolveChallenge>,
+        origin_tx_hash: [u8; 32],
+        nonce: u64,
+        upheld: bool,
+    ) -> Result<()> {
+        instructions::resolve_challenge::handler(ctx, origin_tx_hash, nonce, upheld)
+    }
+
+    /// Register as a relayer, or add to an existing bond, in the configured bond mint
+    pub fn register_relayer(ctx: Context<RegisterRelayer>, amount: u64) -> Result<()> {
+        instructions::register_relayer::handler(ctx, amount)
+    }
+
+    /// Withdraw a relayer's bond once the cooldown since it last relayed has passed
+    pub fn withdraw_bond(ctx: Context<WithdrawBond>) -> Result<()> {
+        instructions::withdraw_bond::handler(ctx)
+    }
+
+    /// Slash up to `amount` of a relayer's bond into the fee treasury for provable misbehavior (admin only)
+    pub fn slash_relayer(
+        ctx: Context<SlashRelayer>,
+        relayer_owner: Pubkey,
+        amount: u64,
+        evidence_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::slash_relayer::handler(ctx, relayer_owner, amount, evidence_hash)
+    }
+
+    /// Set the bounty paid for executing staged inbound messages from a chain (authority or operator)
+    pub fn set_chain_execution_bounty(
+        ctx: Context<SetChainExecutionBounty>,
+        chain_id: u64,
+        bounty: u64,
+    ) -> Result<()> {
+        instructions::set_chain_execution_bounty::handler(ctx, chain_id, bounty)
+    }
+
+    /// Verify an inbound message and stage it for anyone to execute
+    #[allow(clippy::too_many_arguments)]
+    pub fn stage_inbound_message<'info>(
+        ctx: Context<'_, '_, '_, 'info, StageInboundMessage<'info>>,
+        origin_chain_id: u64,
+        origin_tx_hash: [u8; 32],
+        origin_contract: Vec<u8>,
+        origin_token_id: [u8; 32],
+        metadata_uri: String,
+        name: String,
+        symbol: String,
+        original_owner: Vec<u8>,
+        intended_recipient: Pubkey,
+        tss_signature: Vec<u8>,
+        nonce: u64,
+    ) -> Result<()> {
+        instructions::stage_inbound_message::handler(
+            ctx,
+            origin_chain_id,
+            origin_tx_hash,
+            origin_contract,
+            origin_token_id,
+            metadata_uri,
+            name,
+            symbol,
+            original_owner,
+            intended_recipient,
+            tss_signature,
+            nonce,
+        )
+    }
+
+    /// Mint a staged inbound message and earn the origin chain's execution bounty
+    pub fn execute_inbound_message(
+        ctx: Context<ExecuteInboundMessage>,
+        origin_tx_hash: [u8; 32],
+        nonce: u64,
+    ) -> Result<()> {
+        instructions::execute_inbound_message::handler(ctx, origin_tx_hash, nonce)
+    }
+
+    /// Set aside lamports to pay for the accounts inbound transfers create for the signer
+    pub fn deposit_rent(ctx: Context<DepositRent>, amount: u64) -> Result<()> {
+        instructions::deposit_rent::handler(ctx, amount)
+    }
+
+    /// Take unspent lamports back out of the signer's rent deposit
+    pub fn withdraw_rent_deposit(ctx: Context<WithdrawRentDeposit>, amount: u64) -> Result<()> {
+        instructions::withdraw_rent_deposit::handler(ctx, amount)
+    }
+
+    /// Recover lamports sent by mistake to one of the program's PDAs (admin only)
+    pub fn sweep_surplus_lamports(ctx: Context<SweepSurplusLamports>, target: SweepTarget) -> Result<()> {
+        instructions::sweep_surplus_lamports::handler(ctx, target)
+    }
+
+    /// Validate a cross-chain transfer without executing it; returns the message hash and rent cost
+    pub fn preview_transfer(
+        ctx: Context<PreviewTransfer>,
+        destination_chain_id: u64,
+        recipient_address: Vec<u8>,
+        nonce: u64,
+    ) -> Result<TransferPreview> {
+        instructions::preview_transfer::handler(ctx, destination_chain_id, recipient_address, nonce)
+    }
+
+    /// Set the programs allowed to CPI into receive and admin instructions
+    pub fn set_cpi_allowlist(
+        ctx: Context<SetCpiAllowlist>,
+        enforced: bool,
+        programs: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_cpi_allowlist::handler(ctx, enforced, programs)
+    }
+
+    /// Choose the transport that carries messages to and from a registered chain (admin only)
+    pub fn set_chain_adapter(
+        ctx: Context<SetChainAdapter>,
+        chain_id: u64,
+        adapter: BridgeAdapterKind,
+    ) -> Result<()> {
+        instructions::set_chain_adapter::handler(ctx, chain_id, adapter)
+    }
+
+    /// Admin repair of an NFT whose lock disagrees with its transfer record or swap order
+    pub fn reconcile_nft(ctx: Context<ReconcileNft>, mint: Pubkey, transfer_nonce: u64) -> Result<()> {
+        instructions::reconcile_nft::handler(ctx, mint, transfer_nonce)
+    }
+
+    /// Receive an NFT delivered by the LayerZero executor; the origin chain must use the LayerZero adapter
+    #[allow(clippy::too_many_arguments)]
+    pub fn lz_receive<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReceiveCrossChain<'info>>,
+        origin_chain_id: u64,
+        origin_tx_hash: [u8; 32],
+        origin_contract: Vec<u8>,
+        origin_token_id: [u8; 32],
+        metadata_uri: String,
+        name: String,
+        symbol: String,
+        original_owner: Vec<u8>,
+        intended_recipient: Pubkey,
+        extra_data: Vec<u8>,
+        nonce: u64,
+        params: LzReceiveParams,
+    ) -> Result<()> {
+        instructions::lz_receive::handler(
+            ctx,
+            origin_chain_id,
+            origin_tx_hash,
+            origin_contract,
+            origin_token_id,
+            metadata_uri,
+            name,
+            symbol,
+            original_owner,
+            intended_recipient,
+            extra_data,
+            nonce,
+            params,
+        )
+    }
+
+    /// Set a metadata URI longer than the account was created for, paying rent for the added space
+    pub fn expand_metadata(ctx: Context<ExpandMetadata>, metadata_uri: String) -> Result<()> {
+        instructions::expand_metadata::handler(ctx, metadata_uri)
+    }
+
+    /// Set or clear a second metadata URI, carried alongside the primary in outbound transfers
+    pub fn set_fallback_uri(ctx: Context<SetFallbackUri>, fallback_uri: Option<String>) -> Result<()> {
+        instructions::set_fallback_uri::handler(ctx, fallback_uri)
+    }
+
+    /// Transfer cross-chain on the owner's signed permit, with a sponsor paying the fees
+    pub fn cross_chain_transfer_with_permit(
+        ctx: Context<CrossChainTransferWithPermit>,
+        destination_chain_id: u64,
+        recipient_address: Vec<u8>,
+        nonce: u64,
+        deadline: i64,
+    ) -> Result<()> {
+        instructions::cross_chain_transfer_with_permit::handler(ctx, destination_chain_id, recipient_address, nonce, deadline)
+    }
+
+    /// Approve an application program to write the NFT's app data, or revoke it with `None`
+    pub fn set_app_data_program(ctx: Context<SetAppDataProgram>, app_program: Option<Pubkey>) -> Result<()> {
+        instructions::app_data::set_program_handler(ctx, app_program)
+    }
+
+    /// Write part of an NFT's app data; signed by the approved app's PDA through CPI
+    pub fn write_app_data(ctx: Context<WriteAppData>, offset: u16, bytes: Vec<u8>) -> Result<()> {
+        instructions::app_data::write_handler(ctx, offset, bytes)
+    }
+
+    /// Equip a held NFT on a parent NFT, escrowing it until unequipped
+    pub fn equip_child(ctx: Context<EquipChild>) -> Result<()> {
+        instructions::nesting::equip_handler(ctx)
+    }
+
+    /// Return an equipped child NFT to the parent's holder
+    pub fn unequip_child(ctx: Context<UnequipChild>) -> Result<()> {
+        instructions::nesting::unequip_handler(ctx)
+    }
+
+    /// Trust a game contract on another chain to mint achievement NFTs, up to a quota
+    pub fn set_trusted_peer(
+        ctx: Context<SetTrustedPeer>,
+        peer_chain_id: u64,
+        peer_contract: Vec<u8>,
+        mint_quota: u64,
+    ) -> Result<()> {
+        instructions::set_trusted_peer::handler(ctx, peer_chain_id, peer_contract, mint_quota)
+    }
+
+    /// Mint a TSS-attested achievement NFT awarded by a trusted peer to a Solana wallet
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_achievement(
+        ctx: Context<MintAchievement>,
+        peer_chain_id: u64,
+        peer_contract: Vec<u8>,
+        achievement_id: [u8; 32],
+        metadata_uri: String,
+        name: String,
+        symbol: String,
+        tss_signature: Vec<u8>,
+    ) -> Result<()> {
+        instructions::mint_achievement::handler(
+            ctx,
+            peer_chain_id,
+            peer_contract,
+            achievement_id,
+            metadata_uri,
+            name,
+            symbol,
+            tss_signature,
+        )
+    }
+
+    /// Record the holders of a collection's NFTs, passed in remaining accounts, into a snapshot chunk
+    pub fn record_snapshot<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RecordSnapshot<'info>>,
+        snapshot_id: u64,
+        chunk_index: u16,
+    ) -> Result<()> {
+        instructions::record_snapshot::handler(ctx, snapshot_id, chunk_index)
+    }
+
+    /// Return the type and data hash of a page of program accounts, for verifying an off-chain export
+    pub fn export_state(ctx: Context<ExportState>, page: u32) -> Result<ExportPage> {
+        instructions::export_state::handler(ctx, page)
+    }
+
+    /// Return the receipt for an inbound message, if it has arrived, by its origin tx hash and nonce
+    pub fn find_receipt(ctx: Context<FindReceipt>, origin_tx_hash: Vec<u8>, nonce: u64) -> Result<ReceiptLookup> {
+        instructions::find_receipt::handler(ctx, origin_tx_hash, nonce)
+    }
+
+    /// Cap the size of outbound payloads sent to a chain (authority or operator)
+    pub fn set_chain_max_payload(
+        ctx: Context<SetChainMaxPayload>,
+        chain_id: u64,
+        max_payload_bytes: u32,
+    ) -> Result<()> {
+        instructions::set_chain_max_payload::handler(ctx, chain_id, max_payload_bytes)
+    }
+
+    /// Set the royalty sales of a collection's NFTs pay its creators (creator or program authority; creator set approval when active)
+    pub fn set_collection_royalty(
+        ctx: Context<SetCollectionRoyalty>,
+        basis_points: u16,
+        creators: Vec<RoyaltyShare>,
+    ) -> Result<()> {
+        instructions::set_collection_royalty::handler(ctx, basis_points, creators)
+    }
+
+    /// List an NFT at a price that falls over time to a floor, escrowing it until sold or cancelled
+    pub fn create_dutch_auction(
+        ctx: Context<CreateDutchAuction>,
+        start_price: u64,
+        floor_price: u64,
+        decay_per_second: u64,
+        start_time: i64,
+    ) -> Result<()> {
+        instructions::dutch_auction::create_handler(ctx, start_price, floor_price, decay_per_second, start_time)
+    }
+
+    /// Buy a Dutch-auctioned NFT at its current price, paying the collection's royalty
+    pub fn buy_dutch_auction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyDutchAuction<'info>>,
+        max_price: u64,
+    ) -> Result<()> {
+        instructions::dutch_auction::buy_handler(ctx, max_price)
+    }
+
+    /// Return an unsold Dutch-auctioned NFT to its seller
+    pub fn cancel_dutch_auction(ctx: Context<CancelDutchAuction>) -> Result<()> {
+        instructions::dutch_auction::cancel_handler(ctx)
+    }
+
+    /// Escrow an offer of lamports for any NFT, until it is accepted, cancelled or expires
+    pub fn make_offer(ctx: Context<MakeOffer>, amount: u64, expires_at: i64) -> Result<()> {
+        instructions::offer::make_handler(ctx, amount, expires_at)
+    }
+
+    /// Sell an NFT to an open offer, paying the collection's royalty (holder only)
+    pub fn accept_offer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AcceptOffer<'info>>,
+        min_amount: u64,
+    ) -> Result<()> {
+        instructions::offer::accept_handler(ctx, min_amount)
+    }
+
+    /// Refund an offer to its buyer; anyone may close it once it has expired
+    pub fn cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
+        instructions::offer::cancel_handler(ctx)
+    }
+
+    /// List an NFT at a fixed price while it stays, frozen, in the seller's wallet
+    pub fn list_with_delegate(ctx: Context<ListWithDelegate>, price: u64) -> Result<()> {
+        instructions::delegate_listing::list_handler(ctx, price)
+    }
+
+    /// Buy a delegate-listed NFT out of the seller's wallet, paying the collection's royalty
+    pub fn buy_delegate_listing<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyDelegateListing<'info>>,
+        max_price: u64,
+    ) -> Result<()> {
+        instructions::delegate_listing::buy_handler(ctx, max_price)
+    }
+
+    /// Thaw a delegate-listed NFT and revoke the listing
+    pub fn cancel_delegate_listing(ctx: Context<CancelDelegateListing>) -> Result<()> {
+        instructions::delegate_listing::cancel_handler(ctx)
+    }
+
+    /// Lock an NFT in a vault and mint a fixed supply of fraction tokens to its holder
+    pub fn fractionalize_nft(ctx: Context<FractionalizeNft>, total_supply: u64) -> Result<()> {
+        instructions::fractionalize::fractionalize_handler(ctx, total_supply)
+    }
+
+    /// Burn every fraction of a vault to take its NFT out
+    pub fn redeem_fractions(ctx: Context<RedeemFractions>) -> Result<()> {
+        instructions::fractionalize::redeem_handler(ctx)
+    }
+
+    /// Set the staking reward emission per NFT per epoch, creating the reward mint (authority or operator)
+    pub fn set_reward_config(
+        ctx: Context<SetRewardConfig>,
+        emission_per_epoch: u64,
+        epoch_seconds: u32,
+    ) -> Result<()> {
+        instructions::set_reward_config::config_handler(ctx, emission_per_epoch, epoch_seconds)
+    }
+
+    /// Boost staking rewards for one collection's NFTs (authority or operator)
+    pub fn set_reward_multiplier(
+        ctx: Context<SetRewardMultiplier>,
+        collection_mint: Pubkey,
+        multiplier_bps: u32,
+    ) -> Result<()> {
+        instructions::set_reward_config::multiplier_handler(ctx, collection_mint, multiplier_bps)
+    }
+
+    /// Stake an NFT in place to earn reward tokens while holding it
+    pub fn stake_nft(ctx: Context<StakeNft>) -> Result<()> {
+        instructions::staking::stake_handler(ctx)
+    }
+
+    /// Mint the staking rewards an NFT has accrued since its last claim
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        instructions::staking::claim_handler(ctx)
+    }
+
+    /// Close a stake, forfeiting rewards not yet claimed
+    pub fn unstake_nft(ctx: Context<UnstakeNft>) -> Result<()> {
+        instructions::staking::unstake_handler(ctx)
+    }
+
+    /// Burn an NFT to redeem what it stands for, recording a claim for fulfillment
+    pub fn redeem_nft(ctx: Context<RedeemNft>, claim_code_hash: [u8; 32], details: String) -> Result<()> {
+        instructions::redeem_nft::handler(ctx, claim_code_hash, details)
+    }
+
+    /// Make a collection's NFTs single-use tickets (collection creator or program authority)
+    pub fn set_ticket_config(
+        ctx: Context<SetTicketConfig>,
+        collection_mint: Pubkey,
+        lock_after_use: bool,
+        validators: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::ticketing::config_handler(ctx, collection_mint, lock_after_use, validators)
+    }
+
+    /// Mark a ticket as used, optionally co-signed by a validator
+    pub fn check_in(ctx: Context<CheckIn>) -> Result<()> {
+        instructions::ticketing::check_in_handler(ctx)
+    }
+
+    /// Set the metadata URIs an NFT offers for other locales (holder only)
+    pub fn set_localized_uris(ctx: Context<SetLocalizedUris>, entries: Vec<LocalizedUri>) -> Result<()> {
+        instructions::set_localized_uris::handler(ctx, entries)
+    }
+
+    /// Create or update an oracle feed and name its updater (authority only)
+    pub fn configure_oracle_feed(
+        ctx: Context<ConfigureOracleFeed>,
+        feed_id: [u8; 32],
+        updater: Pubkey,
+        max_staleness_seconds: u32,
+    ) -> Result<()> {
+        instructions::oracle_metadata::configure_feed_handler(ctx, feed_id, updater, max_staleness_seconds)
+    }
+
+    /// Publish an oracle feed's latest value (feed updater only)
+    pub fn push_oracle_value(ctx: Context<PushOracleValue>, value: i64) -> Result<()> {
+        instructions::oracle_metadata::push_value_handler(ctx, value)
+    }
+
+    /// Register the URI variants an oracle feed selects between (holder only)
+    pub fn set_metadata_variants(ctx: Context<SetMetadataVariants>, variants: Vec<MetadataVariant>) -> Result<()> {
+        instructions::oracle_metadata::set_variants_handler(ctx, variants)
+    }
+
+    /// Apply the variant the feed's latest value selects, emitting a metadata sync message on change
+    pub fn update_metadata_from_oracle(ctx: Context<UpdateMetadataFromOracle>) -> Result<()> {
+        instructions::oracle_metadata::update_handler(ctx)
+    }
+
+    /// Set the fee and proof of work required of open mints (authority or operator)
+    pub fn set_mint_guard(ctx: Context<SetMintGuard>, fee_lamports: u64, pow_difficulty: u8) -> Result<()> {
+        instructions::set_mint_guard::guard_handler(ctx, fee_lamports, pow_difficulty)
+    }
+
+    /// Cap open mints per wallet, overall and in the current phase (authority or operator)
+    pub fn set_mint_limits(
+        ctx: Context<SetMintLimits>,
+        max_per_wallet: u32,
+        phase: u16,
+        phase_max_per_wallet: u32,
+    ) -> Result<()> {
+        instructions::set_mint_guard::limits_handler(ctx, max_per_wallet, phase, phase_max_per_wallet)
+    }
+
+    /// Have K of N creators jointly manage a collection's settings (creator or program authority; creator set approval when active)
+    pub fn set_creator_set(ctx: Context<SetCreatorSet>, creators: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        instructions::creator_set::set_handler(ctx, creators, threshold)
+    }
+
+    /// Approve a pending collection action by the hash of its arguments (creator set member only)
+    pub fn approve_collection_action(
+        ctx: Context<ApproveCollectionAction>,
+        action: CollectionAction,
+        args_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::creator_set::approve_handler(ctx, action, args_hash)
+    }
+
+    /// Require foreign collections to reserve their symbol when registered (authority only)
+    pub fn set_symbol_registry(ctx: Context<SetSymbolRegistry>, enabled: bool) -> Result<()> {
+        instructions::symbol_registry::set_handler(ctx, enabled)
+    }
+
+    /// Reserve the symbol in a collection's metadata for its creator, rejecting one another creator holds (creator or program authority)
+    pub fn reserve_collection_symbol(ctx: Context<ReserveCollectionSymbol>) -> Result<()> {
+        instructions::symbol_registry::reserve_handler(ctx)
+    }
+
+    /// Hand a symbol's reservation to another collection, or release it with the default creator (authority only)
+    pub fn reassign_collection_symbol(
+        ctx: Context<ReassignCollectionSymbol>,
+        symbol: String,
+        creator: Pubkey,
+        collection_mint: Pubkey,
+    ) -> Result<()> {
+        instructions::symbol_registry::reassign_handler(ctx, symbol, creator, collection_mint)
+    }
+
+    /// Grant or revoke a collection's verified badge (authority only)
+    pub fn set_collection_verified(
+        ctx: Context<SetCollectionVerified>,
+        collection_mint: Pubkey,
+        verified: bool,
+    ) -> Result<()> {
+        instructions::verify_collection::set_handler(ctx, collection_mint, verified)
+    }
+
+    /// Return a collection's verified badge via return data
+    pub fn get_collection_verification(
+        ctx: Context<GetCollectionVerification>,
+        collection_mint: Pubkey,
+    ) -> Result<CollectionVerification> {
+        instructions::verify_collection::get_handler(ctx, collection_mint)
+    }
+
+    /// Delegate fees, limits and rewards to an operator key (authority only)
+    pub fn set_operator(ctx: Context<SetOperator>, operator: Pubkey) -> Result<()> {
+        instructions::set_operator::handler(ctx, operator)
+    }
 }
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+/// A game contract on another chain trusted to mint achievement NFTs
+/// straight to Solana wallets, up to its quota
+#[account]
+#[derive(InitSpace)]
+pub struct TrustedPeer {
+    pub chain_id: u64,
+    #[max_len(32)]
+    pub contract: Vec<u8>,
+    pub mint_quota: u64, // Most achievements the peer may ever mint; 0 revokes it
+    pub minted: u64,
+    pub registered_at: i64,
+    pub bump: u8,
+}
+
+impl TrustedPeer {
+    pub fn remaining_quota(&self) -> u64 {
+        self.mint_quota.saturating_sub(self.minted)
+    }
+}
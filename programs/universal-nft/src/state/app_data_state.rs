@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+/// Bytes an owner-approved application program can write for one NFT, such
+/// as game state or parameters of evolving art. Lives beside `NftMetadata`
+/// so metadata filters and sizes are unaffected.
+#[account]
+#[derive(InitSpace)]
+pub struct NftAppData {
+    pub mint: Pubkey,
+    /// Program the holder approved; default when none is
+    pub app_program: Pubkey,
+    /// The app program's PDA for this mint, which signs its writes
+    pub app_authority: Pubkey,
+    pub updated_at: i64,
+    pub bump: u8,
+    pub data: [u8; 256],
+}
+
+impl NftAppData {
+    pub const DATA_LEN: usize = 256;
+    pub const AUTHORITY_SEED: &'static [u8] = b"universal_nft_app_data";
+
+    /// PDA of `app_program` that must sign writes for `mint`
+    pub fn app_authority(app_program: &Pubkey, mint: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[Self::AUTHORITY_SEED, mint.as_ref()], app_program).0
+    }
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+/// An NFT listed at a price that falls from `start_price` by
+/// `decay_per_second` until it reaches `floor_price`. The NFT sits in the
+/// auction escrow until someone buys it or the seller cancels.
+#[account]
+#[derive(InitSpace)]
+pub struct DutchAuction {
+    pub seller: Pubkey,
+    pub mint: Pubkey,
+    pub start_price: u64, // Lamports
+    pub floor_price: u64, // Lamports
+    pub decay_per_second: u64, // Lamports
+    pub start_time: i64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl DutchAuction {
+    /// The asking price at `now`; the start price before the auction starts
+    pub fn price_at(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.start_time).max(0) as u64;
+        self.start_price
+            .saturating_sub(self.decay_per_second.saturating_mul(elapsed))
+            .max(self.floor_price)
+    }
+}
@@ -0,0 +1,175 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::{hash, hashv};
+use crate::error::UniversalNftError;
+
+/// Head of the hash-chained admin audit log. Entries live in their own
+/// `AuditEntry` PDAs keyed by sequence number, so the log grows without
+/// reallocating this account.
+#[account]
+#[derive(InitSpace)]
+pub struct AuditLog {
+    pub entry_count: u64,
+    pub head_hash: [u8; 32], // entry_hash of the latest entry, zeroed while empty
+    pub bump: u8,
+}
+
+/// One admin action. `entry_hash` commits to the previous entry's hash, so
+/// rewriting any entry breaks every hash after it.
+#[account]
+#[derive(InitSpace)]
+pub struct AuditEntry {
+    pub index: u64,
+    pub action: AuditAction,
+    pub actor: Pubkey,
+    pub details_hash: [u8; 32], // sha256 of the Borsh-encoded action arguments
+    pub prev_hash: [u8; 32],
+    pub entry_hash: [u8; 32],
+    pub timestamp: i64,
+    pub bump: u8,
+}
+
+/// Governance actions recorded in the audit log. The Borsh-encoded arguments
+/// hashed into `details_hash` are listed for each action.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum AuditAction {
+    /// `(old_gateway: Pubkey, new_gateway: Pubkey)`
+    UpdateGateway,
+    /// `(old_tss: Pubkey, new_tss: Pubkey)`
+    RotateTss,
+    /// `(paused: bool)`
+    SetPaused,
+    /// `(sunset: bool)`
+    SetSunset,
+    /// `(chain_id: u64, paused: bool)`
+    SetChainPaused,
+    /// `(chain_id: u64)`
+    RegisterChain,
+    /// `(entry: BlocklistEntry, blocked: bool)`
+    UpdateBlocklist,
+    /// `(enforced: bool, programs: Vec<Pubkey>)`
+    SetCpiAllowlist,
+    /// `(chain_id: u64, adapter: BridgeAdapterKind)`
+    SetChainAdapter,
+    /// `(mint: Pubkey, transfer_nonce: Option<u64>, action: ReconcileAction)`
+    ReconcileNft,
+    /// `(max_transfers: u64, window_slots: u64)`
+    SetCircuitBreaker,
+    /// `requirement: Option<CredentialRequirement>`
+    SetCredentialRequirement,
+    /// `gate: Option<TokenGate>`
+    SetTokenGate,
+    /// `(chain_id: u64, gate: Option<TokenGate>)`
+    SetChainTokenGate,
+    /// `(chain_id: u64, fee: BridgeFee)`
+    SetChainFee,
+    /// `(mint: Option<Pubkey>, amount: u64, recipient: Pubkey)`
+    WithdrawFees,
+    /// `(chain_id: u64, delay_seconds: u32)`
+    SetChainFinalityDelay,
+    /// `(mint: Pubkey, origin_chain_id: u64, nonce: u64)`
+    CancelNativeReturn,
+    /// `(chain_id: u64, window_seconds: u32)`
+    SetChainOptimisticWindow,
+    /// `(origin_tx_hash: [u8; 32], nonce: u64, upheld: bool)`
+    ResolveChallenge,
+    /// `requirement: Option<RelayerRequirement>`
+    SetRelayerRequirement,
+    /// `(relayer: Pubkey, amount: u64, evidence_hash: [u8; 32])`
+    SlashRelayer,
+    /// `(chain_id: u64, bounty: u64)`
+    SetChainExecutionBounty,
+    /// `(target: SweepTarget, amount: u64, recipient: Pubkey)`
+    SweepSurplusLamports,
+    /// `(old_limits: ValidationLimits, new_limits: ValidationLimits)`
+    SetValidationLimits,
+    /// `(chain_id: u64, contract: Vec<u8>, mint_quota: u64)`
+    SetTrustedPeer,
+    /// `(chain_id: u64, max_payload_bytes: u32)`
+    SetChainMaxPayload,
+    /// `(emission_per_epoch: u64, epoch_seconds: u32)`
+    SetRewardConfig,
+    /// `(collection_mint: Pubkey, multiplier_bps: u32)`
+    SetRewardMultiplier,
+    /// `(feed_id: [u8; 32], updater: Pubkey, max_staleness_seconds: u32)`
+    ConfigureOracleFeed,
+    /// `(fee_lamports: u64, pow_difficulty: u8)`
+    SetMintGuard,
+    /// `(max_per_wallet: u32, phase: u16, phase_max_per_wallet: u32)`
+    SetMintLimits,
+    /// `(enabled: bool)`
+    SetSymbolRegistry,
+    /// `(collection_mint: Pubkey, verified: bool)`
+    SetCollectionVerified,
+    /// `(operator: Pubkey)`
+    SetOperator,
+    /// `(symbol: String, creator: Pubkey, collection_mint: Pubkey)`
+    ReassignCollectionSymbol,
+    /// `(mint: Pubkey, nonce: u64)`
+    RevertCrossChainTransfer,
+    /// `(owner: Pubkey, nonce: u64)`
+    RevertBundleTransfer,
+}
+
+impl AuditEntry {
+    /// Hash chaining this entry to `prev_hash`
+    pub fn compute_hash(
+        prev_hash: &[u8; 32],
+        index: u64,
+        action: AuditAction,
+        actor: &Pubkey,
+        details_hash: &[u8; 32],
+        timestamp: i64,
+    ) -> [u8; 32] {
+        hashv(&[
+            prev_hash,
+            &index.to_le_bytes(),
+            &[action as u8],
+            actor.as_ref(),
+            details_hash,
+            &timestamp.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+}
+
+impl AuditLog {
+    /// Fill `entry` as the next link in the chain and advance the head
+    pub fn append(
+        &mut self,
+        entry: &mut AuditEntry,
+        action: AuditAction,
+        actor: Pubkey,
+        details: &[u8],
+        entry_bump: u8,
+    ) -> Result<()> {
+        let timestamp = Clock::get()?.unix_timestamp;
+        let details_hash = hash(details).to_bytes();
+        let entry_hash = AuditEntry::compute_hash(
+            &self.head_hash,
+            self.entry_count,
+            action,
+            &actor,
+            &details_hash,
+            timestamp,
+        );
+
+        entry.index = self.entry_count;
+        entry.action = action;
+        entry.actor = actor;
+        entry.details_hash = details_hash;
+        entry.prev_hash = self.head_hash;
+        entry.entry_hash = entry_hash;
+        entry.timestamp = timestamp;
+        entry.bump = entry_bump;
+
+        self.head_hash = entry_hash;
+        self.entry_count = self
+            .entry_count
+            .checked_add(1)
+            .ok_or(UniversalNftError::ArithmeticOverflow)?;
+
+        msg!("Audit entry {} recorded: {:?}", entry.index, action);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Blocklist {
+    #[max_len(64)]
+    pub blocked_accounts: Vec<Pubkey>,
+    #[max_len(32, 64)]
+    pub blocked_foreign_addresses: Vec<Vec<u8>>,
+    pub bump: u8,
+}
+
+impl Blocklist {
+    pub const MAX_BLOCKED_ACCOUNTS: usize = 64;
+    pub const MAX_BLOCKED_FOREIGN_ADDRESSES: usize = 32;
+    pub const MAX_FOREIGN_ADDRESS_LEN: usize = 64;
+
+    pub fn is_account_blocked(&self, account: &Pubkey) -> bool {
+        self.blocked_accounts.contains(account)
+    }
+
+    pub fn is_foreign_address_blocked(&self, address: &[u8]) -> bool {
+        self.blocked_foreign_addresses
+            .iter()
+            .any(|blocked| blocked.as_slice() == address)
+    }
+}
+
+/// A single blocklist entry: either a Solana account or a raw address on another chain
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum BlocklistEntry {
+    Account(Pubkey),
+    ForeignAddress(Vec<u8>),
+}
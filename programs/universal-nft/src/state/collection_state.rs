@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::error::UniversalNftError;
+
+/// Collection-wide settings, keyed by the collection mint
+#[account]
+#[derive(InitSpace)]
+pub struct CollectionConfig {
+    pub collection_mint: Pubkey,
+    pub creator: Pubkey,
+    /// Applies on top of each NFT's own `cross_chain_enabled` flag
+    pub cross_chain_enabled: bool,
+    /// Items that may be bridged out per rolling day; 0 means no limit
+    pub daily_bridge_limit: u32,
+    pub window_start: i64, // When the current day-long window opened
+    pub window_count: u32,
+    pub verification: VerificationBadge,
+    pub bump: u8,
+}
+
+/// Trust badge the program authority grants a collection, for frontends to
+/// show on its NFTs and their bridged copies
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct VerificationBadge {
+    pub verified: bool,
+    pub updated_by: Pubkey, // Default until the badge is first set
+    pub updated_at: i64,
+}
+
+impl CollectionConfig {
+    pub const VELOCITY_WINDOW_SECONDS: i64 = 86_400;
+
+    /// The creator and the program authority may both manage a collection
+    pub fn can_manage(&self, signer: &Pubkey, program_authority: &Pubkey) -> bool {
+        signer == &self.creator || signer == program_authority
+    }
+
+    /// Count `items` bridged out at `now`, opening a new window once a day has
+    /// passed since the current one opened. Fails if the window's limit would
+    /// be exceeded.
+    pub fn record_bridged(&mut self, items: u32, now: i64) -> Result<()> {
+        if self.daily_bridge_limit == 0 {
+            return Ok(());
+        }
+        if now >= self.window_start.saturating_add(Self::VELOCITY_WINDOW_SECONDS) {
+            self.window_start = now;
+            self.window_count = 0;
+        }
+
+        let window_count = self
+            .window_count
+            .checked_add(items)
+            .ok_or(UniversalNftError::ArithmeticOverflow)?;
+        require!(
+            window_count <= self.daily_bridge_limit,
+            UniversalNftError::CollectionVelocityExceeded
+        );
+        self.window_count = window_count;
+        Ok(())
+    }
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+/// Programs allowed to reach sensitive instructions through CPI. Direct
+/// (top-level) calls are never affected; while `enforced` is false any
+/// program may CPI in.
+#[account]
+#[derive(InitSpace)]
+pub struct CpiAllowlist {
+    pub enforced: bool,
+    #[max_len(16)]
+    pub programs: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl CpiAllowlist {
+    pub const MAX_PROGRAMS: usize = 16;
+
+    pub fn is_allowed(&self, program_id: &Pubkey) -> bool {
+        !self.enforced || self.programs.contains(program_id)
+    }
+}
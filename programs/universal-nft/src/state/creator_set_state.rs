@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use crate::error::UniversalNftError;
+
+/// Collection settings that need the creator set's approval once it is active
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum CollectionAction {
+    /// `set_collection_bridging`, args `enabled: bool`
+    SetBridging,
+    /// `set_collection_velocity_limit`, args `daily_limit: u32`
+    SetVelocityLimit,
+    /// `set_collection_royalty`, args `(basis_points: u16, creators: Vec<RoyaltyShare>)`
+    SetRoyalty,
+    /// `set_creator_set`, args `(creators: Vec<Pubkey>, threshold: u8)`
+    SetCreatorSet,
+}
+
+/// K-of-N creators who jointly manage a collection, keyed by the collection
+/// mint. While `threshold` is non-zero the collection's creator can no longer
+/// change its settings alone; `threshold` creators approve each change first.
+/// The program authority keeps its override.
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorSet {
+    pub collection_mint: Pubkey,
+    pub threshold: u8, // 0 leaves the set inactive
+    pub version: u32, // Bumped on every change, voiding approvals given against older sets
+    pub updated_at: i64,
+    pub bump: u8,
+    #[max_len(5)]
+    pub creators: Vec<Pubkey>,
+}
+
+impl CreatorSet {
+    pub const MAX_CREATORS: usize = 5;
+
+    /// Up to `MAX_CREATORS` distinct creators and a threshold they can reach;
+    /// no creators with a threshold of 0 dissolves the set
+    pub fn validate(creators: &[Pubkey], threshold: u8) -> Result<()> {
+        require!(
+            creators.len() <= Self::MAX_CREATORS
+                && usize::from(threshold) <= creators.len()
+                && (threshold > 0 || creators.is_empty()),
+            UniversalNftError::InvalidCreatorSet
+        );
+        for (index, creator) in creators.iter().enumerate() {
+            require!(
+                *creator != Pubkey::default() && !creators[..index].contains(creator),
+                UniversalNftError::InvalidCreatorSet
+            );
+        }
+        Ok(())
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.threshold > 0
+    }
+
+    pub fn position(&self, creator: &Pubkey) -> Option<usize> {
+        self.creators.iter().position(|member| member == creator)
+    }
+}
+
+/// Approvals gathered for one pending change to a collection, keyed by the
+/// collection mint and the action. Approving different arguments, or approving
+/// after the creator set changed, starts over.
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorApproval {
+    pub collection_mint: Pubkey,
+    pub action: CollectionAction,
+    pub args_hash: [u8; 32], // sha256 of the action's Borsh-encoded arguments
+    pub set_version: u32, // `CreatorSet::version` the approvals were given against
+    pub approvals: u8, // Bit i set once `creators[i]` approved
+    pub opened_at: i64,
+    pub bump: u8,
+}
+
+impl CreatorApproval {
+    pub fn approval_count(&self) -> u32 {
+        self.approvals.count_ones()
+    }
+
+    /// Whether this approval covers `args_hash` under the current `creator_set`
+    pub fn covers(&self, creator_set: &CreatorSet, args_hash: &[u8; 32]) -> bool {
+        self.set_version == creator_set.version
+            && self.args_hash == *args_hash
+            && self.approval_count() >= u32::from(creator_set.threshold)
+    }
+}
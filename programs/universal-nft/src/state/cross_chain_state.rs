@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::error::UniversalNftError;
 
 #[account]
 #[derive(InitSpace)]
@@ -8,26 +9,109 @@ pub struct CrossChainConfig {
     pub chain_id: u64,
     pub is_paused: bool,
     pub nonce_counter: u64,
+    pub breaker_max_transfers: u64, // Outbound transfers per window that trip the breaker; 0 disables it
+    pub breaker_window_slots: u64,
+    pub breaker_window_start: u64, // Slot the current window opened at
+    pub breaker_window_count: u64,
+    pub credential_requirement: Option<CredentialRequirement>, // None lets any owner bridge out
+    pub token_gate: Option<TokenGate>, // Applies to chains without their own gate
+    pub relayer_requirement: Option<RelayerRequirement>, // None lets anyone relay
     pub bump: u8,
 }
 
+impl CrossChainConfig {
+    /// Record an outbound nonce accepted by `validate_outbound_transfer`, so
+    /// it and every lower nonce are rejected from now on
+    pub fn consume_nonce(&mut self, nonce: u64) {
+        self.nonce_counter = nonce;
+    }
+
+    /// Count `transfers` outbound transfers at `slot` against the circuit
+    /// breaker, opening a new window once the current one has elapsed.
+    /// Returns true when this reaches the threshold and pauses the bridge;
+    /// the transfers being counted still go through.
+    pub fn record_outbound_velocity(&mut self, transfers: u64, slot: u64) -> bool {
+        if self.breaker_max_transfers == 0 {
+            return false;
+        }
+        if slot >= self.breaker_window_start.saturating_add(self.breaker_window_slots) {
+            self.breaker_window_start = slot;
+            self.breaker_window_count = 0;
+        }
+        self.breaker_window_count = self.breaker_window_count.saturating_add(transfers);
+
+        let tripped = self.breaker_window_count >= self.breaker_max_transfers;
+        if tripped {
+            self.is_paused = true;
+        }
+        tripped
+    }
+}
+
+/// Fixed-size fields come first so RPC `memcmp` filters can match them at
+/// stable offsets (the SDK's `TRANSFER_RECORD_OFFSETS`): `mint` at 8,
+/// `original_owner` at 40, `status` at 72, `destination_chain_id` at 73.
+/// `fee_mint` and `recipient_address` vary in size and stay last.
 #[account]
 #[derive(InitSpace)]
 pub struct CrossChainTransfer {
     pub mint: Pubkey,
     pub original_owner: Pubkey,
+    pub status: u8, // 0: Pending, 1: Completed, 2: Failed
     pub destination_chain_id: u64,
-    #[max_len(64)]
-    pub recipient_address: Vec<u8>,
     pub nonce: u64,
     pub timestamp: i64,
-    pub status: u8, // 0: Pending, 1: Completed, 2: Failed
+    pub completed_at: i64,
+    pub value_mint: Pubkey, // Default when no fungible value is attached
+    pub value_amount: u64,
+    pub fee_payer: Pubkey,
+    pub fee_paid: u64, // Flat fee plus gas budget collected at initiation
+    pub gas_budget: u64,
+    pub gas_spent: u64, // Attested on delivery, at most `gas_budget`
+    pub fee_refunded: bool,
+    pub destination_tx_hash: [u8; 32], // Zeroed until delivery is acknowledged
+    pub staked_seconds: u64, // Checkpointed from the NFT's stake, credited back if the transfer fails
     pub bump: u8,
+    pub fee_mint: Option<Pubkey>, // None when the fee was paid in lamports
+    #[max_len(64)]
+    pub recipient_address: Vec<u8>,
+}
+
+impl CrossChainTransfer {
+    /// Fee owed back to `fee_payer`: all of it once the transfer has failed,
+    /// the unspent gas budget once it has been delivered
+    pub fn refundable_fee(&self) -> u64 {
+        if self.fee_refunded {
+            return 0;
+        }
+        match self.status {
+            1 => self.gas_budget.saturating_sub(self.gas_spent),
+            2 => self.fee_paid,
+            _ => 0,
+        }
+    }
 }
 
 #[account]
 #[derive(InitSpace)]
 pub struct CrossChainReceipt {
+    pub version: u8,
+    pub origin_chain_id: u64,
+    pub origin_tx_hash: [u8; 32], // sha256 of identifiers longer than 32 bytes
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    #[max_len(64)]
+    pub original_owner: Vec<u8>,
+    pub nonce: u64,
+    pub timestamp: i64,
+    pub tss_signature_hash: [u8; 32], // Full signature is only emitted in CrossChainReceiveEvent
+    pub bump: u8,
+}
+
+/// Receipt layout used before versioning (variable-length tx hash, full signature);
+/// only read by `migrate_receipt`
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacyCrossChainReceipt {
     pub origin_chain_id: u64,
     #[max_len(64)]
     pub origin_tx_hash: Vec<u8>,
@@ -41,3 +125,248 @@ pub struct CrossChainReceipt {
     pub tss_signature: Vec<u8>,
     pub bump: u8,
 }
+
+impl CrossChainReceipt {
+    /// Layout version; the pre-versioned layout is LegacyCrossChainReceipt
+    pub const VERSION: u8 = 1;
+
+    /// A freshly created receipt has no mint recorded yet
+    pub fn is_processed(&self) -> bool {
+        self.mint != Pubkey::default()
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ChainConfig {
+    pub chain_id: u64,
+    pub is_paused: bool,
+    pub adapter: BridgeAdapterKind,
+    pub token_gate: Option<TokenGate>, // Overrides `CrossChainConfig.token_gate` for this chain
+    pub fee: BridgeFee,
+    pub finality_delay_seconds: u32, // Wait before a native NFT returning from this chain unlocks
+    pub optimistic_window_seconds: u32, // Challenge window for optimistic receives; 0 disables them
+    pub execution_bounty: u64, // Lamports paid from the fee treasury for executing a staged inbound message
+    pub max_payload_bytes: u32, // Largest outbound payload the chain can execute; 0 is unlimited
+    pub bump: u8,
+}
+
+/// Credential an owner must present to bridge out: an account owned by
+/// `issuer` holding `credential_tag` at `tag_offset` and the owner's key at
+/// `subject_offset`. A soulbound KYC token account is owned by the token
+/// program, with its mint at 0 and holder at 32; an attestation names its
+/// credential and subject at offsets fixed by the attestation program.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct CredentialRequirement {
+    pub issuer: Pubkey,
+    pub credential_tag: Pubkey,
+    pub tag_offset: u16,
+    pub subject_offset: u16,
+}
+
+/// Fee charged for each NFT bridged to a chain, paid into the fee treasury
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct BridgeFee {
+    pub mint: Option<Pubkey>, // None charges lamports
+    pub flat_fee: u64,
+    pub gas_budget: u64, // Destination gas, collected up front with the flat fee
+}
+
+impl BridgeFee {
+    /// Amount charged per NFT, in lamports or base units of `mint`
+    pub fn per_item(&self) -> Result<u64> {
+        Ok(self
+            .flat_fee
+            .checked_add(self.gas_budget)
+            .ok_or(UniversalNftError::ArithmeticOverflow)?)
+    }
+}
+
+/// Minimum balance of an SPL token an owner must hold to bridge out
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct TokenGate {
+    pub mint: Pubkey,
+    pub min_amount: u64, // Base units
+}
+
+/// Bond a relayer must hold to call receive and completion instructions in
+/// permissioned mode
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct RelayerRequirement {
+    pub bond_mint: Option<Pubkey>, // None for a bond in lamports
+    pub min_bond: u64,
+    pub withdraw_cooldown_seconds: u32, // Measured from the relayer's last relayed message
+}
+
+/// Transport that carries messages to and from a chain; see `crate::adapters`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum BridgeAdapterKind {
+    /// ZetaChain gateway, attested by the ZetaChain TSS
+    ZetaChain,
+    /// Inbound only: VAAs posted to the Wormhole core bridge by `emitter_address` on `emitter_chain`
+    Wormhole {
+        core_bridge: Pubkey,
+        emitter_chain: u16, // Wormhole chain ID, not the chain ID used here
+        emitter_address: [u8; 32],
+    },
+    /// LayerZero endpoint, exchanging messages with the OApp `peer` on endpoint ID `remote_eid`
+    LayerZero {
+        endpoint: Pubkey,
+        remote_eid: u32,
+        peer: [u8; 32],
+    },
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct BundleTransfer {
+    pub original_owner: Pubkey,
+    pub destination_chain_id: u64,
+    #[max_len(64)]
+    pub recipient_address: Vec<u8>,
+    #[max_len(10)]
+    pub mints: Vec<Pubkey>,
+    pub nonce: u64,
+    pub timestamp: i64,
+    pub status: u8, // 0: Pending, 1: Completed, 2: Failed
+    pub bump: u8,
+    pub fee_payer: Pubkey,
+    pub fee_paid: u64, // Flat fee plus gas budget for every item
+    pub fee_mint: Option<Pubkey>, // None when the fee was paid in lamports
+    pub gas_budget: u64, // Gas budget for every item
+    pub gas_spent: u64, // Attested on delivery, at most `gas_budget`
+    pub fee_refunded: bool,
+    pub destination_tx_hash: [u8; 32], // Zeroed until delivery is acknowledged
+    pub completed_at: i64,
+    #[max_len(10)]
+    pub staked_seconds: Vec<u64>, // Checkpointed per item, in the order of `mints`
+}
+
+impl BundleTransfer {
+    pub const MAX_ITEMS: usize = 10;
+    /// Remaining accounts per item: mint, token account, NFT metadata and stake record
+    pub const ITEM_ACCOUNTS: usize = 4;
+    /// Remaining accounts per item when a failed bundle is unlocked: NFT
+    /// metadata and stake record
+    pub const UNLOCK_ACCOUNTS: usize = 2;
+
+    /// Fee owed back to `fee_payer`, as `CrossChainTransfer::refundable_fee`
+    pub fn refundable_fee(&self) -> u64 {
+        if self.fee_refunded {
+            return 0;
+        }
+        match self.status {
+            1 => self.gas_budget.saturating_sub(self.gas_spent),
+            2 => self.fee_paid,
+            _ => 0,
+        }
+    }
+}
+
+/// Unlock of a Solana-native NFT returning from another chain, staged by
+/// `stage_native_return` and executable once `executable_at` has passed
+#[account]
+#[derive(InitSpace)]
+pub struct PendingReturn {
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub origin_chain_id: u64,
+    pub origin_tx_hash: [u8; 32],
+    pub nonce: u64,
+    pub staked_seconds: u64, // TSS-attested staking credit, applied to the stake on unlock
+    pub staged_at: i64,
+    pub executable_at: i64,
+    pub payer: Pubkey, // Paid the rent; receives it back when the unlock executes or is cancelled
+    pub bump: u8,
+}
+
+/// Inbound NFT staged without a TSS signature. It can be claimed, minting
+/// the NFT, once `claimable_at` passes, unless a fraud proof cancels it or a
+/// watcher challenges it first.
+#[account]
+#[derive(InitSpace)]
+pub struct OptimisticReceive {
+    pub origin_chain_id: u64,
+    pub origin_tx_hash: [u8; 32],
+    #[max_len(32)]
+    pub origin_contract: Vec<u8>,
+    pub origin_token_id: [u8; 32],
+    #[max_len(200)]
+    pub metadata_uri: String,
+    #[max_len(32)]
+    pub name: String,
+    #[max_len(10)]
+    pub symbol: String,
+    #[max_len(64)]
+    pub original_owner: Vec<u8>,
+    pub recipient: Pubkey,
+    pub nonce: u64,
+    pub message_hash: [u8; 32], // sha256 of the staged ReceiveNft message, named by fraud proofs
+    pub submitter: Pubkey, // Program authority or a bonded watcher
+    pub staged_at: i64,
+    pub claimable_at: i64,
+    pub status: u8, // 0: Pending, 1: Claimed, 2: Cancelled, 3: Challenged
+    pub bump: u8,
+}
+
+/// TSS-verified inbound NFT waiting to be minted. Bonded relayers may
+/// execute it at once; anyone may from `open_at`, earning the origin chain's
+/// execution bounty.
+#[account]
+#[derive(InitSpace)]
+pub struct StagedInbound {
+    pub origin_chain_id: u64,
+    pub origin_tx_hash: [u8; 32],
+    #[max_len(32)]
+    pub origin_contract: Vec<u8>,
+    pub origin_token_id: [u8; 32],
+    #[max_len(200)]
+    pub metadata_uri: String,
+    #[max_len(32)]
+    pub name: String,
+    #[max_len(10)]
+    pub symbol: String,
+    #[max_len(64)]
+    pub original_owner: Vec<u8>,
+    pub recipient: Pubkey,
+    pub nonce: u64,
+    pub tss_signature_hash: [u8; 32],
+    pub stager: Pubkey, // Paid the rent; receives it back on execution
+    pub staged_at: i64,
+    pub open_at: i64, // Staging time, plus the relayer priority window while relayers must be bonded
+    pub bump: u8,
+}
+
+impl StagedInbound {
+    /// How long bonded relayers alone may execute a staged message
+    pub const RELAYER_PRIORITY_SECONDS: i64 = 600;
+}
+
+/// Inbound nonces seen from one origin chain. Each message must carry a
+/// higher nonce than the last one accepted from that chain.
+#[account]
+#[derive(InitSpace)]
+pub struct InboundNonce {
+    pub origin_chain_id: u64,
+    pub last_nonce: u64,
+    pub received_count: u64,
+    pub bump: u8,
+}
+
+impl InboundNonce {
+    /// Accept `nonce` from `origin_chain_id`, initializing the tracker on first use
+    pub fn record(&mut self, origin_chain_id: u64, nonce: u64, bump: u8) -> Result<()> {
+        if self.origin_chain_id == 0 {
+            self.origin_chain_id = origin_chain_id;
+            self.bump = bump;
+        }
+        require!(nonce > self.last_nonce, crate::error::UniversalNftError::InvalidInboundNonce);
+
+        self.last_nonce = nonce;
+        self.received_count = self
+            .received_count
+            .checked_add(1)
+            .ok_or(crate::error::UniversalNftError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::error::UniversalNftError;
+
+/// Collected bridge fees that may still be refunded, per fee denomination.
+/// The fee treasury holds them alongside earned fees, and `withdraw_fees`
+/// can't take it below them. Created with the first transfer; deployments
+/// upgraded with transfers in flight reserve only fees collected afterwards.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeReserve {
+    #[max_len(8)]
+    pub entries: Vec<FeeReserveEntry>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct FeeReserveEntry {
+    pub mint: Option<Pubkey>, // None for lamports
+    pub outstanding: u64,     // Lamports or base units of `mint`
+}
+
+impl FeeReserve {
+    pub const MAX_ENTRIES: usize = 8;
+
+    /// Fees in `mint` that transfers may still claim back
+    pub fn outstanding(&self, mint: Option<Pubkey>) -> u64 {
+        self.entries
+            .iter()
+            .find(|entry| entry.mint == mint)
+            .map_or(0, |entry| entry.outstanding)
+    }
+
+    /// Reserve a fee a transfer just paid, until it is refunded or earned
+    pub fn reserve(&mut self, bump: u8, mint: Option<Pubkey>, amount: u64) -> Result<()> {
+        self.bump = bump;
+        if amount == 0 {
+            return Ok(());
+        }
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.mint == mint) {
+            entry.outstanding = entry
+                .outstanding
+                .checked_add(amount)
+                .ok_or(UniversalNftError::ArithmeticOverflow)?;
+            return Ok(());
+        }
+        require!(self.entries.len() < Self::MAX_ENTRIES, UniversalNftError::FeeReserveFull);
+        self.entries.push(FeeReserveEntry { mint, outstanding: amount });
+        Ok(())
+    }
+
+    /// Release `amount` a transfer can no longer claim, dropping denominations
+    /// with nothing left outstanding
+    pub fn release(&mut self, mint: Option<Pubkey>, amount: u64) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.mint == mint) {
+            entry.outstanding = entry.outstanding.saturating_sub(amount);
+        }
+        self.entries.retain(|entry| entry.outstanding > 0);
+    }
+
+    /// `release` for settle and refund paths, which don't create the reserve
+    pub fn release_if_exists(info: &AccountInfo, mint: Option<Pubkey>, amount: u64) -> Result<()> {
+        if info.owner != &crate::ID || amount == 0 {
+            return Ok(());
+        }
+        let mut reserve = FeeReserve::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        reserve.release(mint, amount);
+        reserve.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])
+    }
+
+    /// `outstanding` for `withdraw_fees`, which runs whether or not any
+    /// transfer has created the reserve yet
+    pub fn outstanding_if_exists(info: &AccountInfo, mint: Option<Pubkey>) -> Result<u64> {
+        if info.owner != &crate::ID {
+            return Ok(0);
+        }
+        let reserve = FeeReserve::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        Ok(reserve.outstanding(mint))
+    }
+}
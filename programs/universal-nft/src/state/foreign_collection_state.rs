@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::state::VerificationBadge;
+
+/// A collection on another chain whose NFTs are grouped under one Solana collection mint
+#[account]
+#[derive(InitSpace)]
+pub struct ForeignCollection {
+    pub origin_chain_id: u64,
+    #[max_len(32)]
+    pub origin_contract: Vec<u8>,
+    #[max_len(32)]
+    pub name: String,
+    #[max_len(10)]
+    pub symbol: String,
+    pub royalty_basis_points: u16,
+    #[max_len(64)]
+    pub royalty_recipient: Vec<u8>, // Address on the origin chain
+    pub collection_mint: Pubkey,
+    pub registered_by: Pubkey,
+    pub registered_at: i64,
+    pub verification: VerificationBadge,
+    pub bump: u8,
+}
+
+impl ForeignCollection {
+    pub const MAX_ROYALTY_BASIS_POINTS: u16 = 10_000;
+}
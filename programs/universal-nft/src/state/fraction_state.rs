@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// An NFT split into a fixed supply of SPL fraction tokens. The NFT sits in
+/// the vault escrow, where it can't be sold or bridged, until one holder
+/// collects every fraction and redeems it.
+#[account]
+#[derive(InitSpace)]
+pub struct FractionVault {
+    pub mint: Pubkey,
+    pub depositor: Pubkey,
+    pub fraction_mint: Pubkey,
+    pub total_supply: u64, // Fractions minted; the mint authority is revoked after
+    pub created_at: i64,
+    pub bump: u8,
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use crate::error::UniversalNftError;
+use universal_nft_messages::chain_id;
+
+/// Supply counters for one origin collection, keyed by (origin_chain_id, origin_contract).
+/// Solana-native NFTs share the (Solana chain id, empty contract) ledger.
+#[account]
+#[derive(InitSpace)]
+pub struct CollectionLedger {
+    pub origin_chain_id: u64,
+    #[max_len(32)]
+    pub origin_contract: Vec<u8>,
+    /// Solana-native NFTs currently locked here while represented on another chain
+    pub native_locked: u64,
+    /// Foreign NFTs currently held as wrapped items on Solana
+    pub wrapped_supply: u64,
+    /// Lifetime count of items sent out of Solana, net of reverts
+    pub total_outbound: u64,
+    /// Lifetime count of items received on Solana
+    pub total_inbound: u64,
+    /// Foreign items released as plain Solana NFTs; their originals stay escrowed at origin
+    pub total_released: u64,
+    pub last_updated: i64,
+    /// Registered ForeignCollection for this origin collection, default if none
+    pub foreign_collection: Pubkey,
+    pub bump: u8,
+}
+
+impl CollectionLedger {
+    /// Fill in the key fields the first time an `init_if_needed` ledger is used
+    pub fn ensure_initialized(&mut self, origin_chain_id: u64, origin_contract: &[u8], bump: u8) {
+        if self.origin_chain_id == 0 {
+            self.origin_chain_id = origin_chain_id;
+            self.origin_contract = origin_contract.to_vec();
+            self.bump = bump;
+        }
+    }
+
+    pub fn is_native(&self) -> bool {
+        chain_id::is_solana(self.origin_chain_id)
+    }
+
+    /// The figure that must match the attested count on the other side of the bridge
+    pub fn local_reserve_count(&self) -> u64 {
+        if self.is_native() {
+            self.native_locked
+        } else {
+            self.wrapped_supply.saturating_add(self.total_released)
+        }
+    }
+
+    /// An item of this collection left Solana: native items are locked,
+    /// wrapped items stop being outstanding here
+    pub fn record_outbound(&mut self) -> Result<()> {
+        if self.is_native() {
+            self.native_locked = checked_step(self.native_locked, true)?;
+        } else {
+            self.wrapped_supply = checked_step(self.wrapped_supply, false)?;
+        }
+        self.total_outbound = checked_step(self.total_outbound, true)?;
+        self.touch()
+    }
+
+    /// Undo `record_outbound` for a transfer or swap that never completed
+    pub fn record_outbound_reverted(&mut self) -> Result<()> {
+        if self.is_native() {
+            self.native_locked = checked_step(self.native_locked, false)?;
+        } else {
+            self.wrapped_supply = checked_step(self.wrapped_supply, true)?;
+        }
+        self.total_outbound = checked_step(self.total_outbound, false)?;
+        self.touch()
+    }
+
+    /// A foreign item was minted on Solana
+    pub fn record_inbound(&mut self) -> Result<()> {
+        self.wrapped_supply = checked_step(self.wrapped_supply, true)?;
+        self.total_inbound = checked_step(self.total_inbound, true)?;
+        self.touch()
+    }
+
+    /// A native item that was locked here came back from another chain
+    pub fn record_native_returned(&mut self) -> Result<()> {
+        self.native_locked = checked_step(self.native_locked, false)?;
+        self.total_inbound = checked_step(self.total_inbound, true)?;
+        self.touch()
+    }
+
+    /// A wrapped foreign item left the bridge for good as a plain Solana NFT
+    pub fn record_released(&mut self) -> Result<()> {
+        self.wrapped_supply = checked_step(self.wrapped_supply, false)?;
+        self.total_released = checked_step(self.total_released, true)?;
+        self.touch()
+    }
+
+    fn touch(&mut self) -> Result<()> {
+        self.last_updated = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+}
+
+fn checked_step(value: u64, increment: bool) -> Result<u64> {
+    let next = if increment { value.checked_add(1) } else { value.checked_sub(1) };
+    Ok(next.ok_or(UniversalNftError::ArithmeticOverflow)?)
+}
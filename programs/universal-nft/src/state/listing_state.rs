@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// An NFT listed at a fixed price without leaving its holder's wallet. The
+/// listing is the token account's delegate, and the account stays frozen
+/// until the NFT sells or the seller cancels.
+#[account]
+#[derive(InitSpace)]
+pub struct DelegateListing {
+    pub seller: Pubkey,
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub price: u64, // Lamports
+    pub created_at: i64,
+    pub bump: u8,
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::error::UniversalNftError;
+
+/// Metadata URIs for other locales, kept beside an NFT's `NftMetadata` so
+/// wallets can render the one that matches their region. Travels with the
+/// NFT in the outbound payload.
+#[account]
+#[derive(InitSpace)]
+pub struct LocalizedMetadata {
+    pub mint: Pubkey,
+    pub updated_at: i64,
+    pub bump: u8,
+    #[max_len(4)]
+    pub entries: Vec<LocalizedUri>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct LocalizedUri {
+    #[max_len(16)]
+    pub locale: String, // BCP 47 tag, e.g. "pt-BR"
+    #[max_len(200)]
+    pub uri: String,
+}
+
+impl LocalizedMetadata {
+    pub const MAX_ENTRIES: usize = 4;
+    pub const MAX_LOCALE_LEN: usize = 16;
+
+    /// Check the locales; URIs are checked against the program's limits by the caller
+    pub fn validate(entries: &[LocalizedUri]) -> Result<()> {
+        require!(entries.len() <= Self::MAX_ENTRIES, UniversalNftError::InvalidLocalizedUris);
+        for (index, entry) in entries.iter().enumerate() {
+            require!(
+                !entry.locale.is_empty()
+                    && entry.locale.len() <= Self::MAX_LOCALE_LEN
+                    && entry.locale.bytes().all(|byte| byte.is_ascii_alphanumeric() || byte == b'-')
+                    && !entries[..index].iter().any(|earlier| earlier.locale == entry.locale),
+                UniversalNftError::InvalidLocalizedUris
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+/// Anti-spam checks on open mints: a lamport fee into the fee treasury and a
+/// hashcash-style proof of work on the mint address. Mints into a collection
+/// and mints by the program authority are exempt. No account means no guard.
+#[account]
+#[derive(InitSpace)]
+pub struct MintGuard {
+    pub fee_lamports: u64, // 0 charges nothing
+    pub pow_difficulty: u8, // Leading zero bits the mint's proof hash needs; 0 disables
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl MintGuard {
+    pub const MAX_FEE_LAMPORTS: u64 = 100_000_000; // 0.1 SOL
+    pub const MAX_POW_DIFFICULTY: u8 = 28;
+    pub const POW_TAG: &'static [u8] = b"UNIVERSAL_NFT_MINT_POW";
+
+    /// Leading zero bits of sha256(tag || program id || authority || mint).
+    /// Minters grind mint keypairs until it reaches the difficulty, so the
+    /// work is bound to one mint and can't be reused.
+    pub fn pow_bits(authority: &Pubkey, mint: &Pubkey) -> u32 {
+        let digest = hashv(&[Self::POW_TAG, crate::ID.as_ref(), authority.as_ref(), mint.as_ref()]).to_bytes();
+        let mut bits = 0;
+        for byte in digest {
+            bits += byte.leading_zeros();
+            if byte != 0 {
+                break;
+            }
+        }
+        bits
+    }
+}
+
+/// Per-wallet caps on open mints for fair launches: one over the deployment's
+/// lifetime and one for the current mint phase. 0 leaves a cap off. Starting a
+/// new phase resets every wallet's phase count.
+#[account]
+#[derive(InitSpace)]
+pub struct MintLimits {
+    pub max_per_wallet: u32,
+    pub phase: u16,
+    pub phase_max_per_wallet: u32,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+/// How many NFTs a wallet has minted through `mint_nft`, counted from its
+/// first mint whether or not limits were set
+#[account]
+#[derive(InitSpace)]
+pub struct WalletMintCounter {
+    pub wallet: Pubkey,
+    pub total_minted: u32,
+    pub phase: u16, // Phase `phase_minted` counts for
+    pub phase_minted: u32,
+    pub bump: u8,
+}
+
+impl WalletMintCounter {
+    /// Count one more mint in `phase`, resetting the phase count when it has moved on
+    pub fn record_mint(&mut self, phase: u16) {
+        if self.phase != phase {
+            self.phase = phase;
+            self.phase_minted = 0;
+        }
+        self.total_minted = self.total_minted.saturating_add(1);
+        self.phase_minted = self.phase_minted.saturating_add(1);
+    }
+}
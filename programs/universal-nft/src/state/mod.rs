@@ -1,5 +1,69 @@
 pub mod nft_state;
 pub mod cross_chain_state;
+pub mod blocklist_state;
+pub mod swap_state;
+pub mod ledger_state;
+pub mod collection_state;
+pub mod opt_out_state;
+pub mod foreign_collection_state;
+pub mod audit_state;
+pub mod cpi_allowlist_state;
+pub mod watcher_state;
+pub mod relayer_state;
+pub mod rent_deposit_state;
+pub mod transfer_index_state;
+pub mod app_data_state;
+pub mod nesting_state;
+pub mod achievement_state;
+pub mod snapshot_state;
+pub mod pending_transfers_state;
+pub mod royalty_state;
+pub mod auction_state;
+pub mod offer_state;
+pub mod listing_state;
+pub mod fraction_state;
+pub mod staking_state;
+pub mod redemption_state;
+pub mod ticket_state;
+pub mod localized_metadata_state;
+pub mod oracle_state;
+pub mod mint_guard_state;
+pub mod creator_set_state;
+pub mod symbol_registry_state;
+pub mod operator_state;
+pub mod fee_reserve_state;
 
 pub use nft_state::*;
 pub use cross_chain_state::*;
+pub use blocklist_state::*;
+pub use swap_state::*;
+pub use ledger_state::*;
+pub use collection_state::*;
+pub use opt_out_state::*;
+pub use foreign_collection_state::*;
+pub use audit_state::*;
+pub use cpi_allowlist_state::*;
+pub use watcher_state::*;
+pub use relayer_state::*;
+pub use rent_deposit_state::*;
+pub use transfer_index_state::*;
+pub use app_data_state::*;
+pub use nesting_state::*;
+pub use achievement_state::*;
+pub use snapshot_state::*;
+pub use pending_transfers_state::*;
+pub use royalty_state::*;
+pub use auction_state::*;
+pub use offer_state::*;
+pub use listing_state::*;
+pub use fraction_state::*;
+pub use staking_state::*;
+pub use redemption_state::*;
+pub use ticket_state::*;
+pub use localized_metadata_state::*;
+pub use oracle_state::*;
+pub use mint_guard_state::*;
+pub use creator_set_state::*;
+pub use symbol_registry_state::*;
+pub use operator_state::*;
+pub use fee_reserve_state::*;
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+/// NFTs equipped on a parent NFT. Children sit in escrow, so whoever holds
+/// the parent controls them, and the parent can't leave Solana or be burned
+/// until they are unequipped.
+#[account]
+#[derive(InitSpace)]
+pub struct ChildIndex {
+    pub parent: Pubkey,
+    pub bump: u8,
+    #[max_len(8)]
+    pub children: Vec<Pubkey>, // Child mints, in the order they were equipped
+}
+
+impl ChildIndex {
+    pub const MAX_CHILDREN: usize = 8;
+}
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use universal_nft_messages::chain_id;
+use crate::utils::validation::{MAX_NAME_CHARS, MAX_SYMBOL_CHARS};
 
 #[account]
 #[derive(InitSpace)]
@@ -7,24 +9,127 @@ pub struct ProgramState {
     pub is_initialized: bool,
     pub total_nfts_minted: u64,
     pub cross_chain_transfers: u64,
+    /// Withdraw-only mode: no new mints or outbound transfers, everything else still settles
+    pub is_sunset: bool,
+    pub event_sequence: u64, // Sequence number of the last event emitted
+    pub validation_limits: ValidationLimits,
     pub bump: u8,
 }
 
+/// Input limits tuned per deployment with `set_validation_limits`. Each one
+/// is bounded by `ValidationLimits::CEILING`, what the accounts that store
+/// the value have room for.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct ValidationLimits {
+    pub max_uri_len: u16, // Bytes; longer URIs go through `expand_metadata`
+    pub max_name_len: u8, // Bytes, and characters once decoded
+    pub max_symbol_len: u8,
+    pub max_signature_len: u16, // Bytes of a TSS signature
+}
+
+/// Fixed-size fields come first so RPC `memcmp` filters can match them at
+/// stable offsets (the SDK's `NFT_METADATA_OFFSETS`): `mint` at 8,
+/// `current_owner` at 40, `collection` at 104, `is_locked` at 217. New
+/// fields go after `bump`, ahead of the strings, or at the very end.
 #[account]
 #[derive(InitSpace)]
 pub struct NftMetadata {
     pub mint: Pubkey,
-    pub original_owner: Pubkey,
     pub current_owner: Pubkey,
+    pub original_owner: Pubkey,
+    pub collection: Pubkey, // Default when the NFT is not part of a collection
+    pub wrapped_mint: Pubkey, // Escrowed Metaplex original for wrapped NFTs, default otherwise
+    pub origin_chain_id: u64,
+    pub origin_token_id: [u8; 32],
+    pub creation_timestamp: i64,
+    pub cross_chain_enabled: bool,
+    pub is_locked: bool,
+    pub bump: u8,
     #[max_len(200)]
     pub metadata_uri: String,
     #[max_len(32)]
     pub name: String,
     #[max_len(10)]
     pub symbol: String,
-    pub cross_chain_enabled: bool,
-    pub is_locked: bool,
-    pub origin_chain_id: u64,
-    pub creation_timestamp: i64,
-    pub bump: u8,
+    #[max_len(32)]
+    pub origin_contract: Vec<u8>, // Empty for Solana-native NFTs
+    /// Second host for the metadata JSON, for when `metadata_uri` stops resolving
+    #[max_len(200)]
+    pub fallback_uri: Option<String>,
+    /// Decoded from the CID when `metadata_uri` is `ipfs://`, empty otherwise
+    #[max_len(68)]
+    pub metadata_multihash: Vec<u8>,
+    pub child_count: u8, // Children equipped through `ChildIndex`
+}
+
+/// Repair made by `reconcile_nft`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum ReconcileAction {
+    /// Lock and pending state already agreed
+    Consistent,
+    /// Locked again because a transfer or swap was still pending
+    Locked,
+    /// Unlocked because nothing was pending
+    Unlocked,
+}
+
+impl ProgramState {
+    /// Layout version stamped on every event; bump when any event's fields change
+    pub const EVENT_SCHEMA_VERSION: u8 = 2;
+
+    /// Claim the sequence number for the next event. Sequences start at 1 and
+    /// have no gaps, so indexers can tell a missed event from a replayed one.
+    pub fn next_event_sequence(&mut self) -> u64 {
+        self.event_sequence += 1;
+        self.event_sequence
+    }
+}
+
+impl ValidationLimits {
+    pub const DEFAULT: Self = Self {
+        max_uri_len: 200,
+        max_name_len: 32,
+        max_symbol_len: 10,
+        max_signature_len: 128,
+    };
+
+    /// Signatures are checked and hashed, never stored, so only the
+    /// transaction size bounds them
+    pub const CEILING: Self = Self {
+        max_uri_len: NftMetadata::DEFAULT_URI_LEN as u16,
+        max_name_len: MAX_NAME_CHARS as u8,
+        max_symbol_len: MAX_SYMBOL_CHARS as u8,
+        max_signature_len: 512,
+    };
+
+    pub fn is_within_ceiling(&self) -> bool {
+        (1..=Self::CEILING.max_uri_len).contains(&self.max_uri_len)
+            && (1..=Self::CEILING.max_name_len).contains(&self.max_name_len)
+            && (1..=Self::CEILING.max_symbol_len).contains(&self.max_symbol_len)
+            && (1..=Self::CEILING.max_signature_len).contains(&self.max_signature_len)
+    }
+
+    pub fn fits_signature(&self, signature: &[u8]) -> bool {
+        signature.len() <= self.max_signature_len as usize
+    }
+}
+
+impl NftMetadata {
+    /// URI capacity of a newly created account
+    pub const DEFAULT_URI_LEN: usize = 200;
+    /// Longest URI `expand_metadata` will grow an account to hold
+    pub const MAX_EXPANDED_URI_LEN: usize = 500;
+
+    /// Account size, discriminator included, with room for a `uri_len`-byte URI
+    pub fn space_for_uri(uri_len: usize) -> usize {
+        8 + Self::INIT_SPACE + uri_len.saturating_sub(Self::DEFAULT_URI_LEN)
+    }
+
+    pub fn is_wrapped(&self) -> bool {
+        self.wrapped_mint != Pubkey::default()
+    }
+
+    pub fn is_native(&self) -> bool {
+        chain_id::is_solana(self.origin_chain_id)
+    }
 }
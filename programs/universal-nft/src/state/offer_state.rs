@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+/// A buyer's standing offer for an NFT, whether or not it is listed. The
+/// offered lamports sit in this account on top of its rent until the holder
+/// accepts, or the buyer cancels or lets it expire.
+#[account]
+#[derive(InitSpace)]
+pub struct Offer {
+    pub buyer: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64, // Lamports
+    pub expires_at: i64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Offer {
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+}
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+/// Hot key for operational settings such as fees and limits, delegated by the
+/// program authority. Security settings (TSS, gateway, pause, allowlists)
+/// stay with the authority alone. No account means no operator.
+#[account]
+#[derive(InitSpace)]
+pub struct OperatorConfig {
+    pub operator: Pubkey, // Default pubkey when revoked
+    pub updated_at: i64,
+    pub bump: u8,
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+/// Creators and collections that refuse to have their Solana-native NFTs
+/// bridged to other chains
+#[account]
+#[derive(InitSpace)]
+pub struct OptOutRegistry {
+    #[max_len(128)]
+    pub opted_out: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl OptOutRegistry {
+    pub const MAX_ENTRIES: usize = 128;
+
+    pub fn is_opted_out(&self, subject: &Pubkey) -> bool {
+        self.opted_out.contains(subject)
+    }
+}
+
+/// What an opt-out applies to
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum OptOutSubject {
+    /// Every NFT originally minted by the signing creator
+    Creator,
+    /// Every NFT in a collection managed by the signer
+    Collection(Pubkey),
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::error::UniversalNftError;
+
+/// A value published on-chain by an off-chain oracle, such as a price, a
+/// temperature or a match score. Only `updater` may push new values.
+#[account]
+#[derive(InitSpace)]
+pub struct OracleFeed {
+    pub feed_id: [u8; 32], // Chosen by the authority, e.g. the sha256 of "SOL/USD"
+    pub updater: Pubkey,
+    pub value: i64,
+    pub updated_at: i64, // 0 until the first value is pushed
+    pub max_staleness_seconds: u32,
+    pub bump: u8,
+}
+
+impl OracleFeed {
+    pub const MAX_STALENESS_SECONDS: u32 = 86_400;
+
+    /// Whether the latest value is recent enough to select metadata at `now`
+    pub fn is_fresh(&self, now: i64) -> bool {
+        self.updated_at > 0 && now.saturating_sub(self.updated_at) <= i64::from(self.max_staleness_seconds)
+    }
+}
+
+/// URI variants an NFT switches between as its oracle feed moves. Keyed by
+/// mint, so it stays in place while the NFT is bridged away and applies again
+/// when it returns.
+#[account]
+#[derive(InitSpace)]
+pub struct DynamicMetadata {
+    pub mint: Pubkey,
+    pub feed: Pubkey, // The `OracleFeed` account
+    pub active_variant: u8,
+    pub last_value: i64, // Feed value that selected `active_variant`
+    pub updated_at: i64, // 0 until the first update from the oracle
+    pub bump: u8,
+    #[max_len(4)]
+    pub variants: Vec<MetadataVariant>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct MetadataVariant {
+    pub min_value: i64, // Selected once the feed reaches this value
+    #[max_len(200)]
+    pub uri: String,
+}
+
+impl DynamicMetadata {
+    pub const MAX_VARIANTS: usize = 4;
+
+    /// Check the thresholds; URIs are checked against the program's limits by the caller
+    pub fn validate(variants: &[MetadataVariant]) -> Result<()> {
+        require!(
+            (1..=Self::MAX_VARIANTS).contains(&variants.len())
+                && variants.windows(2).all(|pair| pair[0].min_value < pair[1].min_value),
+            UniversalNftError::InvalidMetadataVariants
+        );
+        Ok(())
+    }
+
+    /// Index of the variant for `value`: the last one whose threshold it
+    /// reaches, or the first when it is below them all
+    pub fn select(&self, value: i64) -> usize {
+        self.variants
+            .iter()
+            .rposition(|variant| value >= variant.min_value)
+            .unwrap_or(0)
+    }
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+/// Backlog of outbound transfers, so monitoring can read one account to see
+/// whether transfers are settling instead of scanning every transfer record.
+/// Created with the first transfer; deployments upgraded with transfers in
+/// flight count only those started afterwards.
+#[account]
+#[derive(InitSpace)]
+pub struct PendingTransfers {
+    pub pending: u64, // Created and neither completed nor reverted
+    pub total_created: u64,
+    pub total_completed: u64,
+    pub total_reverted: u64,
+    pub last_updated: i64,
+    pub bump: u8,
+    #[max_len(16)]
+    pub recent: Vec<PendingTransferChange>, // Oldest first
+}
+
+/// One status change of a transfer record
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct PendingTransferChange {
+    pub mint: Pubkey,
+    pub nonce: u64,
+    pub status: u8, // The record's new status: 0 pending, 1 completed, 2 failed
+    pub timestamp: i64,
+}
+
+impl PendingTransfers {
+    pub const MAX_RECENT: usize = 16;
+
+    pub fn record_created(&mut self, bump: u8, mint: Pubkey, nonce: u64, timestamp: i64) {
+        self.bump = bump;
+        self.pending = self.pending.saturating_add(1);
+        self.total_created = self.total_created.saturating_add(1);
+        self.push(mint, nonce, 0, timestamp);
+    }
+
+    /// Count a transfer leaving pending with `status` 1 (completed) or 2 (failed)
+    pub fn record_settled(&mut self, mint: Pubkey, nonce: u64, status: u8, timestamp: i64) {
+        self.pending = self.pending.saturating_sub(1);
+        if status == 1 {
+            self.total_completed = self.total_completed.saturating_add(1);
+        } else {
+            self.total_reverted = self.total_reverted.saturating_add(1);
+        }
+        self.push(mint, nonce, status, timestamp);
+    }
+
+    /// `record_settled` for settle paths, which don't create the registry: a
+    /// transfer started before it existed was never counted.
+    pub fn record_settled_if_exists(
+        info: &AccountInfo,
+        mint: Pubkey,
+        nonce: u64,
+        status: u8,
+        timestamp: i64,
+    ) -> Result<()> {
+        if info.owner != &crate::ID {
+            return Ok(());
+        }
+        let mut registry = PendingTransfers::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        registry.record_settled(mint, nonce, status, timestamp);
+        registry.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])
+    }
+
+    fn push(&mut self, mint: Pubkey, nonce: u64, status: u8, timestamp: i64) {
+        if self.recent.len() >= Self::MAX_RECENT {
+            self.recent.remove(0);
+        }
+        self.recent.push(PendingTransferChange { mint, nonce, status, timestamp });
+        self.last_updated = timestamp;
+    }
+}
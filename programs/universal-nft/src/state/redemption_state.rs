@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+/// Proof that an NFT was burned to redeem whatever it stood for. It keeps the
+/// NFT's display data after the metadata account is closed, so a fulfillment
+/// service can honour the claim from this account alone.
+#[account]
+#[derive(InitSpace)]
+pub struct Redemption {
+    pub mint: Pubkey,
+    pub redeemer: Pubkey,
+    pub collection: Pubkey, // Default when the NFT was not part of a collection
+    pub origin_chain_id: u64,
+    pub origin_token_id: [u8; 32],
+    pub claim_code_hash: [u8; 32], // sha256 of the code the redeemer presents to collect
+    pub redeemed_at: i64,
+    pub bump: u8,
+    #[max_len(200)]
+    pub metadata_uri: String,
+    #[max_len(32)]
+    pub name: String,
+    #[max_len(200)]
+    pub details: String, // Redeemer-supplied, such as a variant or a delivery reference
+}
+
+impl Redemption {
+    pub const MAX_DETAILS_LEN: usize = 200;
+}
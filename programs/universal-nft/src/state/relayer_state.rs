@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// A relayer's bond. Lamport bonds are held by this account on top of its
+/// rent, SPL bonds by its associated token account for the bond mint.
+#[account]
+#[derive(InitSpace)]
+pub struct Relayer {
+    pub owner: Pubkey,
+    pub bond_mint: Option<Pubkey>,
+    pub bond: u64,
+    pub registered_at: i64,
+    pub last_active_at: i64, // Last bond or relayed message; starts the withdrawal cooldown
+    pub times_slashed: u32,
+    pub bump: u8,
+}
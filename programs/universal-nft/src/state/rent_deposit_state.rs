@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+/// Lamports a recipient sets aside to pay for the accounts an inbound
+/// transfer creates for them, so relayers don't have to
+#[account]
+#[derive(InitSpace)]
+pub struct RentDeposit {
+    pub owner: Pubkey,
+    pub balance: u64, // Lamports held in this account on top of its rent
+    pub total_spent: u64,
+    pub bump: u8,
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::error::UniversalNftError;
+
+/// One creator's cut of a collection's royalty
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct RoyaltyShare {
+    pub address: Pubkey,
+    pub share: u8, // Percent of the royalty; a collection's shares add up to 100
+}
+
+/// Royalty every sale of a collection's NFTs pays its creators, keyed by the
+/// collection mint
+#[account]
+#[derive(InitSpace)]
+pub struct CollectionRoyalty {
+    pub collection_mint: Pubkey,
+    pub basis_points: u16, // Of the sale price; 0 waives royalties
+    #[max_len(5)]
+    pub creators: Vec<RoyaltyShare>,
+    pub updated_by: Pubkey,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl CollectionRoyalty {
+    pub const MAX_CREATORS: usize = 5;
+    pub const MAX_BASIS_POINTS: u16 = 10_000;
+
+    /// A royalty needs distinct creators whose non-zero shares add up to 100.
+    /// A waived royalty may list no creators.
+    pub fn validate(basis_points: u16, creators: &[RoyaltyShare]) -> Result<()> {
+        require!(
+            basis_points <= Self::MAX_BASIS_POINTS && creators.len() <= Self::MAX_CREATORS,
+            UniversalNftError::InvalidRoyalty
+        );
+        if basis_points == 0 && creators.is_empty() {
+            return Ok(());
+        }
+        let total: u32 = creators.iter().map(|creator| u32::from(creator.share)).sum();
+        require!(
+            total == 100 && creators.iter().all(|creator| creator.share > 0),
+            UniversalNftError::InvalidRoyalty
+        );
+        for (i, creator) in creators.iter().enumerate() {
+            require!(
+                creator.address != Pubkey::default()
+                    && !creators[..i].iter().any(|other| other.address == creator.address),
+                UniversalNftError::InvalidRoyalty
+            );
+        }
+        Ok(())
+    }
+
+    /// Each creator's cut of a sale at `price`, in creator order. Cuts round
+    /// down; the remainder stays with the seller.
+    pub fn split(&self, price: u64) -> Result<Vec<(Pubkey, u64)>> {
+        let royalty = u128::from(price)
+            .checked_mul(u128::from(self.basis_points))
+            .ok_or(UniversalNftError::ArithmeticOverflow)?
+            / u128::from(Self::MAX_BASIS_POINTS);
+        Ok(self
+            .creators
+            .iter()
+            .map(|creator| {
+                // royalty <= price, so each cut fits in a u64
+                (creator.address, (royalty * u128::from(creator.share) / 100) as u64)
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+/// One chunk of a collection's holder snapshot: who held each NFT when it
+/// was taken. Chunks stay on chain after the NFTs move or bridge away, so
+/// rewards can be distributed against them later.
+#[account]
+#[derive(InitSpace)]
+pub struct HolderSnapshot {
+    pub collection_mint: Pubkey,
+    pub snapshot_id: u64,
+    pub chunk_index: u16,
+    pub taken_at: i64,
+    pub taken_by: Pubkey,
+    pub bump: u8,
+    #[max_len(32)]
+    pub holders: Vec<SnapshotEntry>,
+}
+
+impl HolderSnapshot {
+    pub const MAX_ENTRIES: usize = 32;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct SnapshotEntry {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use crate::error::UniversalNftError;
+
+/// Emission schedule for staking rewards. The reward mint is a program PDA
+/// and its own mint authority, so only `claim_rewards` and `unstake_nft` can
+/// issue it.
+#[account]
+#[derive(InitSpace)]
+pub struct RewardConfig {
+    pub reward_mint: Pubkey,
+    pub emission_per_epoch: u64, // Reward base units per staked NFT per epoch
+    pub epoch_seconds: u32,
+    pub total_emitted: u64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl RewardConfig {
+    pub const REWARD_DECIMALS: u8 = 9;
+    pub const BPS_DENOMINATOR: u32 = 10_000;
+
+    /// Rewards for the whole epochs between `last_claimed_at` and `now` at
+    /// `multiplier_bps`, and the timestamp accrual resumes from. A partial
+    /// epoch carries over to the next claim.
+    pub fn accrued(&self, last_claimed_at: i64, now: i64, multiplier_bps: u32) -> Result<(u64, i64)> {
+        let epoch_seconds = i64::from(self.epoch_seconds.max(1));
+        let epochs = (now - last_claimed_at).max(0) / epoch_seconds;
+        let amount = u128::from(epochs as u64)
+            .checked_mul(u128::from(self.emission_per_epoch))
+            .and_then(|amount| amount.checked_mul(u128::from(multiplier_bps)))
+            .map(|amount| amount / u128::from(Self::BPS_DENOMINATOR))
+            .and_then(|amount| u64::try_from(amount).ok())
+            .ok_or(UniversalNftError::ArithmeticOverflow)?;
+        Ok((amount, last_claimed_at + epochs * epoch_seconds))
+    }
+}
+
+/// Boost to the emission rate for NFTs of one collection, keyed by the
+/// collection mint. Collections without one earn at 1x.
+#[account]
+#[derive(InitSpace)]
+pub struct RewardMultiplier {
+    pub collection_mint: Pubkey,
+    pub multiplier_bps: u32, // 10_000 is 1x
+    pub bump: u8,
+}
+
+impl RewardMultiplier {
+    pub const MAX_MULTIPLIER_BPS: u32 = 100_000;
+}
+
+/// A staked NFT, keyed by its mint. Staking is non-custodial: the NFT stays
+/// in the staker's wallet, and rewards are only paid while they still hold it
+/// and it isn't locked for a transfer.
+#[account]
+#[derive(InitSpace)]
+pub struct StakeRecord {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub collection: Pubkey, // Default when the NFT is outside a collection
+    pub staked_at: i64,
+    pub last_claimed_at: i64, // Start of the epoch accrual resumes from
+    pub total_claimed: u64,
+    pub bridged_at: i64, // When an outbound transfer checkpointed the stake; 0 while on Solana
+    pub bump: u8,
+}
+
+impl StakeRecord {
+    /// Suspend the stake for a transfer out, returning the unclaimed staking
+    /// time the NFT carries in its payload
+    pub fn checkpoint(&mut self, now: i64) -> u64 {
+        self.bridged_at = now;
+        (now - self.last_claimed_at).max(0) as u64
+    }
+
+    /// Resume a checkpointed stake, crediting `staked_seconds` of accrual
+    pub fn resume(&mut self, now: i64, staked_seconds: u64) {
+        let credit = i64::try_from(staked_seconds).unwrap_or(i64::MAX).min(now);
+        self.last_claimed_at = now - credit;
+        self.bridged_at = 0;
+    }
+}
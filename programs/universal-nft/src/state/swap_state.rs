@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct SwapOrder {
+    pub seller: Pubkey,
+    pub mint: Pubkey,
+    pub destination_chain_id: u64,
+    #[max_len(64)]
+    pub payment_token: Vec<u8>, // Token contract on the destination chain
+    pub payment_amount: u64,
+    #[max_len(64)]
+    pub buyer_address: Vec<u8>, // Payer on the destination chain, receives the NFT
+    pub deadline: i64,
+    pub created_at: i64,
+    pub staked_seconds: u64, // Checkpointed from the NFT's stake, credited back if the swap is reverted
+    pub status: u8, // 0: Open, 1: Completed, 2: Reverted
+    pub bump: u8,
+}
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::error::UniversalNftError;
+use crate::state::NftMetadata;
+
+/// Whether collections must reserve their symbol when registered. No account
+/// means the registry is off; collections may reserve either way.
+#[account]
+#[derive(InitSpace)]
+pub struct SymbolRegistry {
+    pub enabled: bool,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+/// A collection symbol claimed by its first registration, keyed by the hash of
+/// its skeleton so "bayc", "BAYC" and "BAYС" (Cyrillic С) share one reservation
+#[account]
+#[derive(InitSpace)]
+pub struct SymbolReservation {
+    #[max_len(10)]
+    pub symbol: String, // As first registered
+    /// Creator of a Solana collection, or the collection mint a foreign
+    /// collection is grouped under; only it may reuse the symbol. Default once
+    /// the program authority released it.
+    pub creator: Pubkey,
+    pub collection_mint: Pubkey, // Collection that registered the symbol, or the one it was reassigned to
+    pub reserved_at: i64,
+    pub bump: u8,
+}
+
+impl SymbolReservation {
+    /// Reservation seed for `symbol`: sha256 of its skeleton
+    pub fn seed(symbol: &str) -> [u8; 32] {
+        hash(Self::skeleton(symbol).as_bytes()).to_bytes()
+    }
+
+    /// `symbol` trimmed, upper-cased and with lookalike characters folded
+    /// together: full-width forms to ASCII, `0` to `O`, `1`, `L` and `|` to
+    /// `I`, `5` to `S`, and Cyrillic and Greek capitals to the Latin letters
+    /// they resemble
+    pub fn skeleton(symbol: &str) -> String {
+        symbol
+            .trim()
+            .chars()
+            .map(|c| match c {
+                '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+                _ => c,
+            })
+            .flat_map(char::to_uppercase)
+            .map(|c| match c {
+                '0' | 'О' | 'Ο' => 'O',
+                '1' | 'L' | '|' | 'І' | 'Ι' | 'Ӏ' => 'I',
+                '5' | 'Ѕ' => 'S',
+                'А' | 'Α' => 'A',
+                'В' | 'Β' => 'B',
+                'С' | 'Ϲ' => 'C',
+                'Е' | 'Ε' => 'E',
+                'Н' | 'Η' => 'H',
+                'Ј' => 'J',
+                'К' | 'Κ' => 'K',
+                'М' | 'Μ' => 'M',
+                'Ν' => 'N',
+                'Р' | 'Ρ' => 'P',
+                'Т' | 'Τ' => 'T',
+                'Х' | 'Χ' => 'X',
+                'У' | 'Υ' => 'Y',
+                'Ζ' => 'Z',
+                _ => c,
+            })
+            .collect()
+    }
+
+    /// Seed of the reservation a collection minted by this program holds
+    /// through its own metadata, or zeroes for one without
+    pub fn collection_seed(collection_metadata: &Option<Account<NftMetadata>>) -> [u8; 32] {
+        collection_metadata
+            .as_ref()
+            .map_or([0; 32], |collection_metadata| Self::seed(&collection_metadata.symbol))
+    }
+
+    /// Claim the symbol on first use; afterwards only its creator may reuse it.
+    /// Returns whether this call made the reservation.
+    pub fn reserve(&mut self, symbol: &str, creator: Pubkey, collection_mint: Pubkey, now: i64, bump: u8) -> Result<bool> {
+        if self.creator != Pubkey::default() {
+            require_keys_eq!(self.creator, creator, UniversalNftError::SymbolReserved);
+            return Ok(false);
+        }
+        self.symbol = symbol.to_string();
+        self.creator = creator;
+        self.collection_mint = collection_mint;
+        self.reserved_at = now;
+        self.bump = bump;
+        Ok(true)
+    }
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::error::UniversalNftError;
+
+/// Turns a collection's NFTs into single-use tickets, keyed by the collection
+/// mint. Foreign NFTs bridged in from a registered collection carry its
+/// collection mint, so tickets issued by an EVM contract check in here too.
+#[account]
+#[derive(InitSpace)]
+pub struct TicketConfig {
+    pub collection_mint: Pubkey,
+    pub lock_after_use: bool, // Stop a ticket from moving once it is used
+    pub updated_by: Pubkey,
+    pub updated_at: i64,
+    pub bump: u8,
+    #[max_len(8)]
+    pub validators: Vec<Pubkey>, // Gate staff who must co-sign check-ins; empty lets holders check in alone
+}
+
+impl TicketConfig {
+    pub const MAX_VALIDATORS: usize = 8;
+
+    pub fn validate(validators: &[Pubkey]) -> Result<()> {
+        require!(validators.len() <= Self::MAX_VALIDATORS, UniversalNftError::InvalidTicketConfig);
+        for (index, validator) in validators.iter().enumerate() {
+            require!(
+                *validator != Pubkey::default() && !validators[..index].contains(validator),
+                UniversalNftError::InvalidTicketConfig
+            );
+        }
+        Ok(())
+    }
+}
+
+/// The used flag of a ticket, keyed by its mint. It outlives any later change
+/// of holder, so a ticket can only be checked in once.
+#[account]
+#[derive(InitSpace)]
+pub struct TicketCheckIn {
+    pub mint: Pubkey,
+    pub collection: Pubkey,
+    pub holder: Pubkey,
+    pub validator: Pubkey, // Default when the holder checked in alone
+    pub checked_in_at: i64,
+    pub bump: u8,
+}
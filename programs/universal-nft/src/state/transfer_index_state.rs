@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+/// An owner's most recent outbound transfers, oldest first, so wallets can
+/// list bridge history without an indexer. Older records are still reachable
+/// with a `getProgramAccounts` filter on `CrossChainTransfer.original_owner`.
+#[account]
+#[derive(InitSpace)]
+pub struct OwnerTransferIndex {
+    pub owner: Pubkey,
+    pub total_transfers: u64, // Every transfer ever recorded, including evicted ones
+    pub bump: u8,
+    #[max_len(32)]
+    pub entries: Vec<TransferIndexEntry>,
+}
+
+/// Locates one transfer record
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct TransferIndexEntry {
+    /// Default for bundle transfers, whose record is keyed by owner and nonce
+    pub mint: Pubkey,
+    pub nonce: u64,
+}
+
+impl OwnerTransferIndex {
+    pub const MAX_ENTRIES: usize = 32;
+
+    /// Append a transfer, evicting the oldest once the index is full
+    pub fn record(&mut self, owner: Pubkey, bump: u8, mint: Pubkey, nonce: u64) {
+        if self.owner == Pubkey::default() {
+            self.owner = owner;
+            self.bump = bump;
+        }
+        if self.entries.len() >= Self::MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+        self.entries.push(TransferIndexEntry { mint, nonce });
+        self.total_transfers = self.total_transfers.saturating_add(1);
+    }
+}
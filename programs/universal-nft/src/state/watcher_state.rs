@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+/// A bonded participant in optimistic mode. Watchers may stage optimistic
+/// receives and challenge them; a submitter whose receive is shown to be
+/// fraudulent loses its bond to the challenger.
+#[account]
+#[derive(InitSpace)]
+pub struct Watcher {
+    pub owner: Pubkey,
+    pub bond: u64, // Lamports held in this account on top of its rent
+    pub registered_at: i64,
+    pub challenges_won: u32,
+    pub times_slashed: u32,
+    pub bump: u8,
+}
+
+impl Watcher {
+    pub const MIN_BOND: u64 = 1_000_000_000; // 1 SOL
+
+    pub fn is_bonded(&self) -> bool {
+        self.bond >= Self::MIN_BOND
+    }
+}
+
+/// An open dispute over one optimistic receive, which cannot be claimed
+/// until the admin resolves it
+#[account]
+#[derive(InitSpace)]
+pub struct Challenge {
+    pub optimistic_receive: Pubkey,
+    pub challenger: Pubkey,
+    #[max_len(256)]
+    pub evidence: Vec<u8>, // E.g. the origin chain's receipt or the correct message, reviewed off-chain
+    pub submitted_at: i64,
+    pub bump: u8,
+}
+
+impl Challenge {
+    pub const MAX_EVIDENCE_LEN: usize = 256;
+}
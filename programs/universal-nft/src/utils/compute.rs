@@ -1,6 +1,17 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey;
+use anchor_lang::solana_program::sysvar::instructions::load_instruction_at_checked;
 use crate::error::UniversalNftError;
 
+/// Compute Budget program; its ID is not exported by solana-program
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey = pubkey!("ComputeBudget111111111111111111111111111111");
+
+/// `ComputeBudgetInstruction::SetComputeUnitLimit` discriminant
+const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+
+/// Additional compute per NFT in a bundle transfer
+pub const BUNDLE_ITEM_COMPUTE_UNITS: u32 = 25_000;
+
 /// Increase compute budget for complex operations
 pub fn increase_compute_budget(units: u32) -> Result<()> {
     msg!("Requesting {} compute units", units);
@@ -21,6 +32,13 @@ pub fn calculate_compute_budget(operation: &str) -> u32 {
     }
 }
 
+/// Compute budget for a bundle transfer of `items` NFTs
+pub fn calculate_bundle_compute_budget(items: usize) -> u32 {
+    let items = u32::try_from(items).unwrap_or(u32::MAX);
+    calculate_compute_budget("cross_chain_transfer")
+        .saturating_add(BUNDLE_ITEM_COMPUTE_UNITS.saturating_mul(items))
+}
+
 /// Check if compute budget is sufficient for operation
 pub fn check_compute_budget(required: u32, available: u32) -> Result<()> {
     if available < required {
@@ -28,3 +46,36 @@ pub fn check_compute_budget(required: u32, available: u32) -> Result<()> {
     }
     Ok(())
 }
+
+/// Compute unit limit requested by the current transaction, read from the
+/// Instructions sysvar. Returns `None` when no SetComputeUnitLimit is present.
+pub fn requested_compute_unit_limit(instructions_sysvar: &AccountInfo) -> Result<Option<u32>> {
+    let mut limit = None;
+    let mut index = 0;
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions_sysvar) {
+        if instruction.program_id == COMPUTE_BUDGET_PROGRAM_ID
+            && instruction.data.first() == Some(&SET_COMPUTE_UNIT_LIMIT)
+        {
+            let units = instruction
+                .data
+                .get(1..5)
+                .and_then(|bytes| bytes.try_into().ok())
+                .map(u32::from_le_bytes)
+                .ok_or(UniversalNftError::InsufficientComputeBudget)?;
+            limit = Some(units);
+        }
+        index += 1;
+    }
+    Ok(limit)
+}
+
+/// Fail up front when the transaction did not request enough compute units,
+/// instead of running out partway through a CPI
+pub fn require_compute_budget(instructions_sysvar: &AccountInfo, required: u32) -> Result<()> {
+    let requested = requested_compute_unit_limit(instructions_sysvar)?.unwrap_or(0);
+    if requested < required {
+        msg!("Compute unit limit {} is below the required {}", requested, required);
+        return err!(UniversalNftError::InsufficientComputeBudget);
+    }
+    Ok(())
+}
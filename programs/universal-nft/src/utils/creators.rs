@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::error::UniversalNftError;
+use crate::state::{CollectionAction, CollectionConfig, CreatorApproval, CreatorSet};
+
+/// The collection's creator set, if one was ever created. Collection
+/// instructions take its PDA by seeds whether or not it exists, so the
+/// collection's creator can't skip the approval by leaving it out.
+pub fn load_creator_set(creator_set_info: &AccountInfo) -> Result<Option<CreatorSet>> {
+    if creator_set_info.owner != &crate::ID {
+        return Ok(None);
+    }
+    let creator_set = CreatorSet::try_deserialize(&mut &creator_set_info.try_borrow_data()?[..])?;
+    Ok(Some(creator_set))
+}
+
+/// Allow `signer` to apply `action` with Borsh-encoded `args` to a collection.
+/// Without an active creator set the collection's creator or the program
+/// authority may; with one, the program authority or any of its creators
+/// holding a met approval for exactly these arguments, which is used up.
+pub fn authorize_collection_action(
+    collection_config: &CollectionConfig,
+    creator_set: Option<&CreatorSet>,
+    creator_approval: Option<&mut CreatorApproval>,
+    signer: &Pubkey,
+    program_authority: &Pubkey,
+    action: CollectionAction,
+    args: &[u8],
+) -> Result<()> {
+    let Some(creator_set) = creator_set.filter(|creator_set| creator_set.is_active()) else {
+        require!(
+            collection_config.can_manage(signer, program_authority),
+            UniversalNftError::Unauthorized
+        );
+        return Ok(());
+    };
+    if signer == program_authority {
+        return Ok(());
+    }
+    require!(creator_set.position(signer).is_some(), UniversalNftError::Unauthorized);
+
+    let creator_approval = creator_approval.ok_or(UniversalNftError::CreatorApprovalRequired)?;
+    require!(
+        creator_approval.collection_mint == collection_config.collection_mint
+            && creator_approval.action == action
+            && creator_approval.covers(creator_set, &hash(args).to_bytes()),
+        UniversalNftError::CreatorApprovalRequired
+    );
+    creator_approval.approvals = 0;
+    creator_approval.args_hash = [0; 32];
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{self, TokenAccount};
+use crate::state::BridgeFee;
+use crate::error::UniversalNftError;
+use crate::constants::FEE_TREASURY_SEED;
+
+pub fn fee_treasury_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FEE_TREASURY_SEED], &crate::ID)
+}
+
+/// The fee treasury's account for `mint`
+pub fn treasury_fee_account_address(mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(&fee_treasury_address().0, mint)
+}
+
+/// Accounts an outbound transfer passes to pay its destination chain's fee
+pub struct FeeAccounts<'a, 'info> {
+    /// Signer the fee is charged to
+    pub payer: &'a AccountInfo<'info>,
+    /// Required for lamport fees
+    pub fee_treasury: Option<&'a AccountInfo<'info>>,
+    /// Required for SPL fees: the payer's account of the fee mint
+    pub payer_fee_account: Option<&'a Account<'info, TokenAccount>>,
+    /// Required for SPL fees: `treasury_fee_account_address(mint)`
+    pub treasury_fee_account: Option<&'a Account<'info, TokenAccount>>,
+    pub token_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+/// Charge `items` times the fee into the fee treasury. Returns the amount
+/// collected, in lamports or base units of the fee mint.
+pub fn collect_bridge_fee(fee: &BridgeFee, items: u64, accounts: FeeAccounts) -> Result<u64> {
+    let amount = fee
+        .per_item()?
+        .checked_mul(items)
+        .ok_or(UniversalNftError::ArithmeticOverflow)?;
+    if amount == 0 {
+        return Ok(0);
+    }
+
+    match fee.mint {
+        None => {
+            let fee_treasury = accounts.fee_treasury.ok_or(UniversalNftError::MissingFeeAccount)?;
+            require_keys_eq!(
+                fee_treasury.key(),
+                fee_treasury_address().0,
+                UniversalNftError::InvalidFeeAccount
+            );
+
+            let cpi_accounts = system_program::Transfer {
+                from: accounts.payer.clone(),
+                to: fee_treasury.clone(),
+            };
+            system_program::transfer(
+                CpiContext::new(accounts.system_program.clone(), cpi_accounts),
+                amount,
+            )?;
+        }
+        Some(mint) => {
+            let (Some(payer_fee_account), Some(treasury_fee_account)) =
+                (accounts.payer_fee_account, accounts.treasury_fee_account)
+            else {
+                return err!(UniversalNftError::MissingFeeAccount);
+            };
+            require_keys_eq!(payer_fee_account.mint, mint, UniversalNftError::InvalidFeeAccount);
+            require_keys_eq!(
+                treasury_fee_account.key(),
+                treasury_fee_account_address(&mint),
+                UniversalNftError::InvalidFeeAccount
+            );
+
+            let cpi_accounts = token::Transfer {
+                from: payer_fee_account.to_account_info(),
+                to: treasury_fee_account.to_account_info(),
+                authority: accounts.payer.clone(),
+            };
+            token::transfer(
+                CpiContext::new(accounts.token_program.clone(), cpi_accounts),
+                amount,
+            )?;
+        }
+    }
+
+    Ok(amount)
+}
+
+/// Accounts a fee refund is paid out of the fee treasury with
+pub struct RefundAccounts<'a, 'info> {
+    /// The fee treasury PDA, which signs the payout
+    pub fee_treasury: &'a AccountInfo<'info>,
+    pub fee_treasury_bump: u8,
+    /// Receives lamport refunds
+    pub fee_payer: &'a AccountInfo<'info>,
+    /// Required for SPL fees: `treasury_fee_account_address(mint)`
+    pub treasury_fee_account: Option<&'a Account<'info, TokenAccount>>,
+    /// Required for SPL fees: the fee payer's account of the fee mint
+    pub payer_fee_account: Option<&'a Account<'info, TokenAccount>>,
+    pub token_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+/// Pay `amount` of a fee in `fee_mint` back out of the fee treasury
+pub fn refund_bridge_fee(fee_mint: Option<Pubkey>, amount: u64, accounts: RefundAccounts) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[FEE_TREASURY_SEED, &[accounts.fee_treasury_bump]]];
+    match fee_mint {
+        None => {
+            let available = accounts
+                .fee_treasury
+                .lamports()
+                .saturating_sub(Rent::get()?.minimum_balance(0));
+            require!(amount <= available, UniversalNftError::InsufficientFeeBalance);
+
+            let cpi_accounts = system_program::Transfer {
+                from: accounts.fee_treasury.clone(),
+                to: accounts.fee_payer.clone(),
+            };
+            system_program::transfer(
+                CpiContext::new_with_signer(accounts.system_program.clone(), cpi_accounts, signer_seeds),
+                amount,
+            )
+        }
+        Some(fee_mint) => {
+            let (Some(treasury_fee_account), Some(payer_fee_account)) =
+                (accounts.treasury_fee_account, accounts.payer_fee_account)
+            else {
+                return err!(UniversalNftError::MissingFeeAccount);
+            };
+            require_keys_eq!(
+                treasury_fee_account.key(),
+                treasury_fee_account_address(&fee_mint),
+                UniversalNftError::InvalidFeeAccount
+            );
+            require!(treasury_fee_account.amount >= amount, UniversalNftError::InsufficientFeeBalance);
+
+            let cpi_accounts = token::Transfer {
+                from: treasury_fee_account.to_account_info(),
+                to: payer_fee_account.to_account_info(),
+                authority: accounts.fee_treasury.clone(),
+            };
+            token::transfer(
+                CpiContext::new_with_signer(accounts.token_program.clone(), cpi_accounts, signer_seeds),
+                amount,
+            )
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{mint_to, Mint, MintTo, TokenAccount};
+use crate::constants::INBOUND_MINT_SEED;
+use crate::error::UniversalNftError;
+use crate::state::NftMetadata;
+
+/// Where a foreign NFT comes from; after `INBOUND_MINT_SEED`, also the seeds
+/// of its inbound mint
+pub struct InboundOrigin<'a> {
+    pub chain_id: u64,
+    pub contract: &'a [u8],
+    pub token_id: [u8; 32],
+}
+
+/// Deliver an inbound NFT to the recipient's `token_account`, returning
+/// whether it is coming back. Inbound mints and their metadata live at fixed
+/// PDAs, so an NFT that was bridged here before and left again finds them in
+/// place, still locked from its trip out. The token is minted by the mint's
+/// own PDA when none is outstanding; otherwise it never left its holder's
+/// account, which must be the recipient's.
+pub fn deliver_inbound_nft<'info>(
+    mint: &Account<'info, Mint>,
+    mint_bump: u8,
+    token_account: &Account<'info, TokenAccount>,
+    nft_metadata: &NftMetadata,
+    origin: &InboundOrigin,
+    token_program: AccountInfo<'info>,
+) -> Result<bool> {
+    let returning = nft_metadata.mint != Pubkey::default();
+    if returning {
+        require!(
+            nft_metadata.is_locked
+                && nft_metadata.origin_chain_id == origin.chain_id
+                && nft_metadata.origin_contract == origin.contract
+                && nft_metadata.origin_token_id == origin.token_id,
+            UniversalNftError::InboundNftNotReturnable
+        );
+    }
+
+    if mint.supply == 0 {
+        let chain_id = origin.chain_id.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            INBOUND_MINT_SEED,
+            &chain_id,
+            origin.contract,
+            &origin.token_id,
+            &[mint_bump],
+        ]];
+        let cpi_accounts = MintTo {
+            mint: mint.to_account_info(),
+            to: token_account.to_account_info(),
+            authority: mint.to_account_info(),
+        };
+        mint_to(CpiContext::new_with_signer(token_program, cpi_accounts, signer_seeds), 1)?;
+    } else {
+        require!(returning, UniversalNftError::InboundNftNotReturnable);
+        require!(token_account.amount == 1, UniversalNftError::RecipientMismatch);
+    }
+    Ok(returning)
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use crate::error::UniversalNftError;
+
+pub const IPFS_SCHEME: &str = "ipfs://";
+
+/// Longest multihash kept on `NftMetadata`: a two-byte varint hash code and
+/// length, then a digest of up to 64 bytes
+pub const MAX_MULTIHASH_LEN: usize = 68;
+
+/// sha2-256 multihash code; the only hash a CIDv0 can use
+const SHA2_256: u64 = 0x12;
+
+/// The multihash named by an `ipfs://` URI, or an empty vec for any other
+/// scheme. Fails with `InvalidIpfsCid` when the CID is malformed, so the
+/// content a URI points at is pinned down before it is stored or bridged.
+///
+/// Accepts CIDv0 (`Qm...`, base58btc) and CIDv1 in base32 (`b...`) or
+/// base58btc (`z...`), optionally followed by a path.
+pub fn ipfs_multihash(uri: &str) -> Result<Vec<u8>> {
+    let Some(rest) = uri.strip_prefix(IPFS_SCHEME) else {
+        return Ok(Vec::new());
+    };
+    let cid = rest.split(['/', '?', '#']).next().unwrap_or_default();
+
+    let multihash = if cid.len() == 46 && cid.starts_with("Qm") {
+        // CIDv0 is a bare sha2-256 multihash
+        let bytes = bs58::decode(cid).into_vec().map_err(|_| UniversalNftError::InvalidIpfsCid)?;
+        require!(bytes.len() == 34 && bytes[..2] == [0x12, 0x20], UniversalNftError::InvalidIpfsCid);
+        bytes
+    } else {
+        let bytes = match cid.split_at_checked(1) {
+            Some(("b", encoded)) => decode_base32(encoded)?,
+            Some(("z", encoded)) => bs58::decode(encoded).into_vec().map_err(|_| UniversalNftError::InvalidIpfsCid)?,
+            _ => return err!(UniversalNftError::InvalidIpfsCid),
+        };
+        let mut reader = bytes.as_slice();
+        require!(read_varint(&mut reader)? == 1, UniversalNftError::InvalidIpfsCid);
+        read_varint(&mut reader)?; // Content codec
+        reader.to_vec()
+    };
+
+    validate_multihash(&multihash)?;
+    Ok(multihash)
+}
+
+/// A multihash is a varint hash code, a varint digest length and exactly
+/// that many digest bytes
+fn validate_multihash(multihash: &[u8]) -> Result<()> {
+    require!(multihash.len() <= MAX_MULTIHASH_LEN, UniversalNftError::InvalidIpfsCid);
+    let mut reader = multihash;
+    let code = read_varint(&mut reader)?;
+    let digest_len = read_varint(&mut reader)?;
+    require!(
+        digest_len > 0 && digest_len == reader.len() as u64,
+        UniversalNftError::InvalidIpfsCid
+    );
+    if code == SHA2_256 {
+        require!(digest_len == 32, UniversalNftError::InvalidIpfsCid);
+    }
+    Ok(())
+}
+
+/// Unsigned LEB128, at most 9 bytes as multiformats allow
+fn read_varint(reader: &mut &[u8]) -> Result<u64> {
+    let mut value = 0u64;
+    for (i, &byte) in reader.iter().enumerate().take(9) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            *reader = &reader[i + 1..];
+            return Ok(value);
+        }
+    }
+    err!(UniversalNftError::InvalidIpfsCid)
+}
+
+/// RFC 4648 base32, lowercase and unpadded, as CIDv1 strings use
+fn decode_base32(encoded: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(encoded.len() * 5 / 8);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in encoded.bytes() {
+        let value = match c {
+            b'a'..=b'z' => c - b'a',
+            b'2'..=b'7' => c - b'2' + 26,
+            _ => return err!(UniversalNftError::InvalidIpfsCid),
+        };
+        buffer = (buffer << 5) | u32::from(value);
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    // Leftover bits are padding and must be zero
+    require!(bits < 5 && buffer == 0, UniversalNftError::InvalidIpfsCid);
+    Ok(bytes)
+}
@@ -1,5 +1,21 @@
 pub mod compute;
+pub mod creators;
+pub mod fees;
+pub mod inbound;
+pub mod ipfs;
+pub mod relayers;
+pub mod royalties;
 pub mod security;
+pub mod staking;
+pub mod validation;
 
 pub use compute::*;
+pub use creators::*;
+pub use fees::*;
+pub use inbound::*;
+pub use ipfs::*;
+pub use relayers::*;
+pub use royalties::*;
 pub use security::*;
+pub use staking::*;
+pub use validation::*;
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use crate::state::{CrossChainConfig, Relayer};
+use crate::error::UniversalNftError;
+use crate::constants::RELAYER_SEED;
+
+pub fn relayer_address(owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RELAYER_SEED, owner.as_ref()], &crate::ID)
+}
+
+/// The relayer account's token account holding an SPL bond in `mint`
+pub fn relayer_bond_account_address(relayer: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(relayer, mint)
+}
+
+/// With a relayer requirement set, the signer must pass its `Relayer`
+/// account holding at least the minimum bond in the bond mint. Relaying
+/// restarts its withdrawal cooldown.
+pub fn require_bonded_relayer(
+    cross_chain_config: &CrossChainConfig,
+    registration: Option<&mut Account<Relayer>>,
+) -> Result<()> {
+    let requirement = match cross_chain_config.relayer_requirement {
+        Some(requirement) => requirement,
+        None => return Ok(()),
+    };
+    let registration = registration.ok_or(UniversalNftError::RelayerNotBonded)?;
+    require!(
+        registration.bond_mint == requirement.bond_mint && registration.bond >= requirement.min_bond,
+        UniversalNftError::RelayerNotBonded
+    );
+
+    registration.last_active_at = Clock::get()?.unix_timestamp;
+
+    Ok(())
+}
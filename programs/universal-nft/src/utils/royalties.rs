@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::constants::COLLECTION_ROYALTY_SEED;
+use crate::error::UniversalNftError;
+use crate::state::CollectionRoyalty;
+
+/// The royalty a sale of an NFT in `collection` owes. Sale instructions pass
+/// the collection's royalty PDA whether or not it was ever created, so a
+/// seller can't dodge the royalty by leaving it out. NFTs outside a collection
+/// and collections that never set a royalty owe none.
+pub fn load_collection_royalty(
+    collection: &Pubkey,
+    royalty_info: Option<&AccountInfo>,
+) -> Result<Option<CollectionRoyalty>> {
+    if *collection == Pubkey::default() {
+        return Ok(None);
+    }
+    let royalty_info = royalty_info.ok_or(UniversalNftError::InvalidRoyalty)?;
+    let (expected, _) = Pubkey::find_program_address(
+        &[COLLECTION_ROYALTY_SEED, collection.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(royalty_info.key(), expected, UniversalNftError::InvalidRoyalty);
+
+    if royalty_info.owner != &crate::ID {
+        return Ok(None);
+    }
+    let royalty = CollectionRoyalty::try_deserialize(&mut &royalty_info.try_borrow_data()?[..])?;
+    Ok(Some(royalty))
+}
+
+/// Pair each creator's cut of a sale at `price` with its account, passed by
+/// the caller in creator order. Returns the payouts and their total.
+pub fn creator_payouts<'info>(
+    royalty: Option<&CollectionRoyalty>,
+    price: u64,
+    creator_accounts: &[AccountInfo<'info>],
+) -> Result<(Vec<(AccountInfo<'info>, u64)>, u64)> {
+    let Some(royalty) = royalty else {
+        return Ok((Vec::new(), 0));
+    };
+    let mut payouts = Vec::with_capacity(royalty.creators.len());
+    let mut total: u64 = 0;
+    for (i, (creator, cut)) in royalty.split(price)?.into_iter().enumerate() {
+        let account = creator_accounts.get(i).ok_or(UniversalNftError::InvalidRoyalty)?;
+        require_keys_eq!(account.key(), creator, UniversalNftError::InvalidRoyalty);
+        total = total.checked_add(cut).ok_or(UniversalNftError::ArithmeticOverflow)?;
+        payouts.push((account.clone(), cut));
+    }
+    Ok((payouts, total))
+}
+
+/// Pay a sale out of a signing buyer's wallet, skipping zero amounts
+pub fn pay_from_buyer<'info>(
+    buyer: &AccountInfo<'info>,
+    payouts: Vec<(AccountInfo<'info>, u64)>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    for (to, amount) in payouts.into_iter().filter(|(_, amount)| *amount > 0) {
+        let cpi_accounts = system_program::Transfer { from: buyer.clone(), to };
+        system_program::transfer(CpiContext::new(system_program.clone(), cpi_accounts), amount)?;
+    }
+    Ok(())
+}
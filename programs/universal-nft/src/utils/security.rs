@@ -1,12 +1,105 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use anchor_lang::solana_program::sysvar::instructions::get_instruction_relative;
+use crate::state::{CpiAllowlist, OperatorConfig};
+use crate::error::UniversalNftError;
+
+pub use universal_nft_messages::{
+    SignedMessageType, SignedPayload, SigningDomain, SIGNING_DOMAIN_TAG, SIGNING_DOMAIN_VERSION,
+};
+
+/// Domain binding signed messages to this program on `chain_id`
+pub fn signing_domain(chain_id: u64) -> SigningDomain {
+    SigningDomain {
+        program_id: crate::ID.to_bytes(),
+        chain_id,
+    }
+}
+
+/// Message the TSS signs for `payload`: the domain prefix followed by the
+/// payload body, as laid out in `universal-nft-messages`
+pub fn signed_message<P: SignedPayload>(chain_id: u64, payload: &P) -> Vec<u8> {
+    signing_domain(chain_id).message(payload)
+}
+
+/// Reject a CPI from a program that is not on the allowlist. The caller is
+/// identified as the program of the enclosing top-level instruction, so for
+/// nested CPIs it is the outermost program that must be allowed.
+pub fn require_allowed_caller(
+    allowlist: &CpiAllowlist,
+    instructions_sysvar: &AccountInfo,
+) -> Result<()> {
+    if !allowlist.enforced || get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT {
+        return Ok(());
+    }
+    let caller = get_instruction_relative(0, instructions_sysvar)?.program_id;
+    if !allowlist.is_allowed(&caller) {
+        msg!("CPI from {} is not allowed", caller);
+        return err!(crate::error::UniversalNftError::CallerNotAllowed);
+    }
+    Ok(())
+}
+
+/// Whether `signer` may change operational settings such as fees and limits:
+/// the program authority, or the operator it named in `operator_config`. A
+/// missing or revoked operator config leaves only the authority.
+pub fn can_operate(program_authority: &Pubkey, operator_config: &AccountInfo, signer: &Pubkey) -> bool {
+    if signer == program_authority {
+        return true;
+    }
+    if operator_config.owner != &crate::ID || *signer == Pubkey::default() {
+        return false;
+    }
+    let Ok(data) = operator_config.try_borrow_data() else {
+        return false;
+    };
+    OperatorConfig::try_deserialize(&mut &data[..]).is_ok_and(|config| config.operator == *signer)
+}
+
+/// Require that the instruction just before this one is an Ed25519 program
+/// instruction verifying `signer`'s signature over exactly `message`. The
+/// runtime fails the transaction if that signature is invalid, so all that is
+/// left is checking what was verified. Offsets must point into the Ed25519
+/// instruction itself, not at data elsewhere in the transaction.
+pub fn require_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let instruction = get_instruction_relative(-1, instructions_sysvar)
+        .map_err(|_| UniversalNftError::InvalidOwnerSignature)?;
+    require_keys_eq!(instruction.program_id, ed25519_program::ID, UniversalNftError::InvalidOwnerSignature);
+
+    // One signature: count, padding, then seven u16 offsets
+    let data = &instruction.data;
+    require!(data.len() >= 16 && data[0] == 1, UniversalNftError::InvalidOwnerSignature);
+    let [signature_offset, signature_ix, public_key_offset, public_key_ix, message_offset, message_len, message_ix]: [u16; 7] =
+        core::array::from_fn(|i| u16::from_le_bytes([data[2 + 2 * i], data[3 + 2 * i]]));
+    require!(
+        [signature_ix, public_key_ix, message_ix].iter().all(|&ix| ix == u16::MAX),
+        UniversalNftError::InvalidOwnerSignature
+    );
+    require!(data.len() >= signature_offset as usize + 64, UniversalNftError::InvalidOwnerSignature);
+
+    let field = |start: u16, len: usize| data.get(start as usize..start as usize + len);
+    require!(
+        field(public_key_offset, 32) == Some(signer.as_ref())
+            && field(message_offset, message_len as usize) == Some(message),
+        UniversalNftError::InvalidOwnerSignature
+    );
+    Ok(())
+}
 
 /// Simplified TSS signature verification for demo purposes
-/// In production, this would use proper cryptographic verification
+/// In production, this would use proper cryptographic verification.
+/// Builds without `config::DEMO_MODE` refuse every signature instead.
 pub fn verify_tss_signature(
     message: &[u8],
     signature: &[u8],
     tss_address: &Pubkey,
 ) -> Result<bool> {
+    require!(crate::config::DEMO_MODE, crate::error::UniversalNftError::TssVerifierUnavailable);
     // Demo implementation - always returns true if signature is not empty
     // In production, implement proper TSS signature verification
     require!(!signature.is_empty(), crate::error::UniversalNftError::InvalidTssSignature);
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use crate::state::{ProgramState, StakeRecord};
+use crate::instructions::staking::{StakeCheckpointedEvent, StakeResumedEvent};
+
+/// Suspend the NFT's stake, if it has one, as the NFT leaves `owner`'s
+/// wallet, returning the unclaimed staking time its payload carries.
+/// Outbound transfers take the stake PDA by seeds whether or not it exists,
+/// so a holder can't keep rewards accruing while the NFT is away by leaving
+/// it out. A stake left behind by a previous holder is suspended too, but its
+/// time is forfeit, as when `stake_nft` takes it over.
+pub fn checkpoint_stake(
+    stake_record_info: &AccountInfo,
+    program_state: &mut ProgramState,
+    mint: Pubkey,
+    owner: Pubkey,
+    nonce: u64,
+    now: i64,
+) -> Result<u64> {
+    if stake_record_info.owner != &crate::ID {
+        return Ok(0);
+    }
+    let mut stake_record = StakeRecord::try_deserialize(&mut &stake_record_info.try_borrow_data()?[..])?;
+    if stake_record.bridged_at != 0 {
+        return Ok(0);
+    }
+    let checkpointed_seconds = stake_record.checkpoint(now);
+    stake_record.try_serialize(&mut &mut stake_record_info.try_borrow_mut_data()?[..])?;
+    let staked_seconds = if stake_record.owner == owner { checkpointed_seconds } else { 0 };
+
+    emit!(StakeCheckpointedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: program_state.next_event_sequence(),
+        mint,
+        owner: stake_record.owner,
+        nonce,
+        staked_seconds,
+        timestamp: now,
+    });
+    Ok(staked_seconds)
+}
+
+/// Resume the NFT's stake, if a transfer out checkpointed it, as the NFT
+/// returns to `recipient`. The attested `staked_seconds` are only credited
+/// when the stake is still theirs; a stake that returns to someone else
+/// restarts with nothing carried over.
+pub fn resume_stake(
+    stake_record_info: &AccountInfo,
+    program_state: &mut ProgramState,
+    mint: Pubkey,
+    recipient: Pubkey,
+    staked_seconds: u64,
+    now: i64,
+) -> Result<()> {
+    if stake_record_info.owner != &crate::ID {
+        return Ok(());
+    }
+    let mut stake_record = StakeRecord::try_deserialize(&mut &stake_record_info.try_borrow_data()?[..])?;
+    if stake_record.bridged_at == 0 {
+        return Ok(());
+    }
+    let credited_seconds = if stake_record.owner == recipient { staked_seconds } else { 0 };
+    stake_record.resume(now, credited_seconds);
+    stake_record.try_serialize(&mut &mut stake_record_info.try_borrow_mut_data()?[..])?;
+
+    emit!(StakeResumedEvent {
+        schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+        sequence: program_state.next_event_sequence(),
+        mint,
+        owner: stake_record.owner,
+        credited_seconds,
+        timestamp: now,
+    });
+    Ok(())
+}
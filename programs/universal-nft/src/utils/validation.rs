@@ -0,0 +1,352 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{self, Mint, TokenAccount};
+use anchor_spl::token_2022;
+use mpl_token_metadata::accounts::MasterEdition;
+use crate::state::{ProgramState, CrossChainConfig, TokenGate, Blocklist, NftMetadata, CollectionConfig, OptOutRegistry, ChainConfig, ValidationLimits};
+use crate::error::UniversalNftError;
+use crate::instructions::CircuitBreakerTrippedEvent;
+use crate::utils::ipfs::ipfs_multihash;
+use universal_nft_messages::{chain_id, ReceiveNft};
+
+/// Checks shared by every outbound transfer path
+pub fn validate_outbound_transfer(
+    cross_chain_config: &CrossChainConfig,
+    blocklist: &Blocklist,
+    owner: &Pubkey,
+    destination_chain_id: u64,
+    recipient_address: &[u8],
+    nonce: u64,
+) -> Result<()> {
+    // Validate nonce
+    require!(
+        nonce > cross_chain_config.nonce_counter,
+        UniversalNftError::InvalidNonce
+    );
+
+    // Validate recipient address length
+    require!(
+        recipient_address.len() <= 64 && !recipient_address.is_empty(),
+        UniversalNftError::InvalidRecipientAddress
+    );
+
+    // Reject blocklisted senders and recipients
+    require!(
+        !blocklist.is_account_blocked(owner),
+        UniversalNftError::AddressBlocked
+    );
+    require!(
+        !blocklist.is_foreign_address_blocked(recipient_address),
+        UniversalNftError::AddressBlocked
+    );
+
+    // Validate destination chain (simplified - in production, maintain a list)
+    require!(
+        destination_chain_id > 0 && !chain_id::is_solana(destination_chain_id),
+        UniversalNftError::UnsupportedChain
+    );
+
+    Ok(())
+}
+
+/// Count accepted outbound transfers against the circuit breaker. The
+/// transaction that trips it still succeeds, so the pause it sets is kept.
+pub fn record_outbound_velocity(
+    program_state: &mut ProgramState,
+    cross_chain_config: &mut CrossChainConfig,
+    transfers: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    if cross_chain_config.record_outbound_velocity(transfers, clock.slot) {
+        emit!(CircuitBreakerTrippedEvent {
+            schema_version: ProgramState::EVENT_SCHEMA_VERSION,
+            sequence: program_state.next_event_sequence(),
+            window_start: cross_chain_config.breaker_window_start,
+            window_count: cross_chain_config.breaker_window_count,
+            max_transfers: cross_chain_config.breaker_max_transfers,
+            slot: clock.slot,
+            timestamp: clock.unix_timestamp,
+        });
+        msg!(
+            "Circuit breaker tripped: {} transfers since slot {}; cross-chain paused",
+            cross_chain_config.breaker_window_count,
+            cross_chain_config.breaker_window_start
+        );
+    }
+    Ok(())
+}
+
+/// URI, name and symbol checks against the deployment's limits, plus CID
+/// checks for `ipfs://` URIs, shared by minting and every inbound receive path
+pub fn validate_nft_fields(
+    limits: &ValidationLimits,
+    metadata_uri: &str,
+    name: &str,
+    symbol: &str,
+) -> Result<()> {
+    require!(metadata_uri.len() <= limits.max_uri_len as usize, UniversalNftError::InvalidMetadataUri);
+    require!(name.len() <= limits.max_name_len as usize, UniversalNftError::InvalidMetadataUri);
+    require!(symbol.len() <= limits.max_symbol_len as usize, UniversalNftError::InvalidMetadataUri);
+    ipfs_multihash(metadata_uri)?;
+    validate_display_text(name, limits.max_name_len as usize)?;
+    validate_display_text(symbol, limits.max_symbol_len as usize)?;
+    Ok(())
+}
+
+/// Field and blocklist checks shared by every inbound receive path
+pub fn validate_inbound_nft(nft: &ReceiveNft, blocklist: &Blocklist, limits: &ValidationLimits) -> Result<()> {
+    validate_nft_fields(limits, nft.metadata_uri, nft.name, nft.symbol)?;
+    require!(
+        !nft.original_owner.is_empty() && nft.original_owner.len() <= 64,
+        UniversalNftError::InvalidMetadataUri
+    );
+    require!(
+        !nft.origin_contract.is_empty() && nft.origin_contract.len() <= 32,
+        UniversalNftError::InvalidOriginContract
+    );
+
+    // Reject blocklisted senders and recipients
+    require!(
+        !blocklist.is_account_blocked(&Pubkey::new_from_array(nft.intended_recipient)),
+        UniversalNftError::AddressBlocked
+    );
+    require!(
+        !blocklist.is_foreign_address_blocked(nft.original_owner),
+        UniversalNftError::AddressBlocked
+    );
+
+    Ok(())
+}
+
+/// An outbound NFT must be a single indivisible token. Token accounts tie the
+/// mint to the token program's account, so only its layout is checked here.
+pub fn validate_nft_mint_supply(mint: &AccountInfo) -> Result<()> {
+    require_keys_eq!(*mint.owner, token::ID, UniversalNftError::InvalidMint);
+    let data = mint.try_borrow_data()?;
+    let mint = Mint::try_deserialize(&mut &data[..]).map_err(|_| UniversalNftError::InvalidMint)?;
+    require!(mint.decimals == 0 && mint.supply == 1, UniversalNftError::InflatableMint);
+    Ok(())
+}
+
+/// A mint created outside this program can be wrapped only if nobody can
+/// inflate its supply or freeze it: its mint and freeze authorities must be
+/// revoked or held by its Metaplex master edition.
+pub fn validate_external_nft_mint(mint_key: &Pubkey, mint: &Mint) -> Result<()> {
+    let master_edition = MasterEdition::find_pda(mint_key).0;
+    let fixed = |authority: &COption<Pubkey>| match authority {
+        COption::None => true,
+        COption::Some(key) => *key == master_edition,
+    };
+    require!(fixed(&mint.mint_authority), UniversalNftError::InflatableMint);
+    require!(fixed(&mint.freeze_authority), UniversalNftError::UnexpectedFreezeAuthority);
+    Ok(())
+}
+
+/// The account an inbound NFT is minted to. Wallets, and PDAs without data,
+/// belong to the system program. Accounts of other programs are accepted as
+/// PDA recipients unless they belong to this program or SPL Token, so a token
+/// account, mint or program ID signed as the recipient by mistake is rejected.
+pub fn validate_recipient_account(recipient: &AccountInfo) -> Result<()> {
+    if recipient.owner == &system_program::ID {
+        return Ok(());
+    }
+    require!(
+        !recipient.executable
+            && recipient.owner != &crate::ID
+            && recipient.owner != &token::ID
+            && recipient.owner != &token_2022::ID,
+        UniversalNftError::InvalidRecipientAccount
+    );
+    Ok(())
+}
+
+/// A token account receiving an NFT must not let anyone but its owner move
+/// or close it
+pub fn validate_recipient_token_account(token_account: &TokenAccount) -> Result<()> {
+    require!(
+        token_account.delegate.is_none() && token_account.close_authority.is_none(),
+        UniversalNftError::RecipientTokenAccountEncumbered
+    );
+    Ok(())
+}
+
+/// An inbound message must come from another chain that is registered and
+/// not paused. Returns the origin chain's registry entry.
+pub fn validate_origin_chain(
+    cross_chain_config: &CrossChainConfig,
+    origin_chain_config: &AccountInfo,
+    origin_chain_id: u64,
+) -> Result<ChainConfig> {
+    require!(
+        origin_chain_id != cross_chain_config.chain_id,
+        UniversalNftError::OriginIsLocalChain
+    );
+    require!(
+        !origin_chain_config.data_is_empty(),
+        UniversalNftError::OriginChainNotRegistered
+    );
+
+    require_keys_eq!(*origin_chain_config.owner, crate::ID, UniversalNftError::OriginChainNotRegistered);
+    let origin_chain_config = ChainConfig::try_deserialize(&mut &origin_chain_config.try_borrow_data()?[..])?;
+    require!(!origin_chain_config.is_paused, UniversalNftError::ChainPaused);
+
+    Ok(origin_chain_config)
+}
+
+/// Outbound payloads must fit what the destination chain can execute
+pub fn validate_payload_size(chain_config: &ChainConfig, payload_len: usize) -> Result<()> {
+    require!(
+        chain_config.max_payload_bytes == 0 || payload_len <= chain_config.max_payload_bytes as usize,
+        UniversalNftError::PayloadTooLarge
+    );
+    Ok(())
+}
+
+/// Collection-level bridging switch; NFTs outside any collection are unaffected
+pub fn validate_collection_bridging(
+    nft_metadata: &NftMetadata,
+    collection_config: Option<&CollectionConfig>,
+) -> Result<()> {
+    if nft_metadata.collection == Pubkey::default() {
+        return Ok(());
+    }
+
+    let collection_config = collection_config.ok_or(UniversalNftError::InvalidCollectionConfig)?;
+    require_keys_eq!(
+        collection_config.collection_mint,
+        nft_metadata.collection,
+        UniversalNftError::InvalidCollectionConfig
+    );
+    require!(
+        collection_config.cross_chain_enabled,
+        UniversalNftError::CollectionBridgingDisabled
+    );
+
+    Ok(())
+}
+
+/// With a credential requirement set, the owner must present a credential
+/// account from the configured issuer that names them as its subject
+pub fn validate_credential(
+    cross_chain_config: &CrossChainConfig,
+    credential: Option<&AccountInfo>,
+    owner: &Pubkey,
+) -> Result<()> {
+    let requirement = match cross_chain_config.credential_requirement {
+        Some(requirement) => requirement,
+        None => return Ok(()),
+    };
+    let credential = credential.ok_or(UniversalNftError::MissingCredential)?;
+    require_keys_eq!(*credential.owner, requirement.issuer, UniversalNftError::InvalidCredential);
+
+    let data = credential.try_borrow_data()?;
+    let key_at = |offset: u16| data.get(offset as usize..offset as usize + 32);
+    require!(
+        key_at(requirement.tag_offset) == Some(requirement.credential_tag.as_ref())
+            && key_at(requirement.subject_offset) == Some(owner.as_ref()),
+        UniversalNftError::InvalidCredential
+    );
+
+    Ok(())
+}
+
+/// The destination chain's token gate, or the global one if it has none,
+/// requires the owner to hold at least `min_amount` of the gate mint
+pub fn validate_token_gate(
+    cross_chain_config: &CrossChainConfig,
+    destination_chain_config: &ChainConfig,
+    gate_token_account: Option<&TokenAccount>,
+    owner: &Pubkey,
+) -> Result<()> {
+    let gate = match destination_chain_config.token_gate.or(cross_chain_config.token_gate) {
+        Some(gate) => gate,
+        None => return Ok(()),
+    };
+    let gate_token_account = gate_token_account.ok_or(UniversalNftError::TokenGateNotMet)?;
+    require!(
+        gate_token_account.mint == gate.mint
+            && gate_token_account.owner == *owner
+            && gate_token_account.amount >= gate.min_amount,
+        UniversalNftError::TokenGateNotMet
+    );
+
+    Ok(())
+}
+
+/// Token gates must name a mint and a minimum; `None` removes the gate
+pub fn validate_token_gate_config(gate: Option<&TokenGate>) -> Result<()> {
+    if let Some(gate) = gate {
+        require!(
+            gate.mint != Pubkey::default() && gate.min_amount > 0,
+            UniversalNftError::InvalidTokenGate
+        );
+    }
+    Ok(())
+}
+
+/// Count bridged items against the collection's daily velocity limit.
+/// NFTs outside any collection pass `None` and are unaffected.
+pub fn record_collection_velocity(
+    collection_config: Option<&mut CollectionConfig>,
+    items: u32,
+) -> Result<()> {
+    match collection_config {
+        Some(collection_config) => collection_config.record_bridged(items, Clock::get()?.unix_timestamp),
+        None => Ok(()),
+    }
+}
+
+/// Solana-native NFTs can't leave if their creator or collection opted out.
+/// Foreign NFTs returning home are always allowed.
+pub fn validate_not_opted_out(
+    nft_metadata: &NftMetadata,
+    opt_out_registry: &OptOutRegistry,
+) -> Result<()> {
+    if !nft_metadata.is_native() {
+        return Ok(());
+    }
+
+    require!(
+        !opt_out_registry.is_opted_out(&nft_metadata.original_owner),
+        UniversalNftError::WrappingOptedOut
+    );
+    require!(
+        nft_metadata.collection == Pubkey::default()
+            || !opt_out_registry.is_opted_out(&nft_metadata.collection),
+        UniversalNftError::WrappingOptedOut
+    );
+
+    Ok(())
+}
+
+/// Capacity of the name and symbol fields in accounts; `ValidationLimits`
+/// can tighten but not exceed them
+pub const MAX_NAME_CHARS: usize = 32;
+pub const MAX_SYMBOL_CHARS: usize = 10;
+
+/// Rejects characters that let a name or symbol impersonate another in wallets:
+/// control characters, zero-width characters and bidirectional overrides.
+/// Borsh already guarantees the string is valid UTF-8.
+pub fn validate_display_text(text: &str, max_chars: usize) -> Result<()> {
+    require!(!text.trim().is_empty(), UniversalNftError::InvalidDisplayText);
+    require!(text.chars().count() <= max_chars, UniversalNftError::InvalidDisplayText);
+    require!(
+        text.chars().all(is_allowed_display_char),
+        UniversalNftError::InvalidDisplayText
+    );
+    Ok(())
+}
+
+fn is_allowed_display_char(c: char) -> bool {
+    if c.is_control() {
+        return false;
+    }
+    !matches!(
+        c,
+        // Zero-width and invisible formatting characters
+        '\u{200B}'..='\u{200D}' | '\u{2060}'..='\u{2064}' | '\u{FEFF}' | '\u{00AD}'
+        // Bidirectional marks, embeddings, overrides and isolates
+        | '\u{061C}' | '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}'
+    )
+}
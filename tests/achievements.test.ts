@@ -0,0 +1,150 @@
+import * as anchor from '@coral-xyz/anchor';
+import { PublicKey, Keypair, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY } from '@solana/web3.js';
+
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddress,
+  getAccount,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import * as crypto from 'crypto';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('cross-chain achievements', () => {
+  const { provider, program, authority } = setupProgram();
+
+  const peerChainId = 137; // Polygon
+  const gameContract = crypto.randomBytes(20);
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+  const chainIdBytes = new anchor.BN(peerChainId).toArrayLike(Buffer, "le", 8);
+  const trustedPeerPda = pda([Buffer.from("trusted_peer"), chainIdBytes, gameContract]);
+
+  const setTrustedPeer = async (mintQuota: number) => {
+    const auditLog = await program.account.auditLog.fetch(auditLogPda);
+    return program.methods
+      .setTrustedPeer(new anchor.BN(peerChainId), gameContract, new anchor.BN(mintQuota))
+      .accounts({
+        programState: programStatePda,
+        trustedPeer: trustedPeerPda,
+        cpiAllowlist: pda([Buffer.from("cpi_allowlist")]),
+        auditLog: auditLogPda,
+        auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  };
+
+  const mintAchievement = async (achievementId: Buffer, recipient: PublicKey) => {
+    const mint = pda([Buffer.from("achievement_mint"), chainIdBytes, gameContract, achievementId]);
+    await program.methods
+      .mintAchievement(
+        new anchor.BN(peerChainId),
+        gameContract,
+        Array.from(achievementId),
+        "https://game.example.com/achievements/first-win.json",
+        "First Win",
+        "WIN",
+        crypto.randomBytes(64)
+      )
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: pda([Buffer.from("cross_chain_config")]),
+        blocklist: pda([Buffer.from("blocklist")]),
+        trustedPeer: trustedPeerPda,
+        mint,
+        tokenAccount: await getAssociatedTokenAddress(mint, recipient),
+        nftMetadata: pda([Buffer.from("nft_metadata"), mint.toBytes()]),
+        recipient,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    return mint;
+  };
+
+  before(async () => {
+    await ensureInitialized();
+  });
+
+  it('Mints an awarded achievement to the player once, within the peer quota', async () => {
+    await setTrustedPeer(1);
+    const player = Keypair.generate().publicKey;
+    const achievementId = crypto.randomBytes(32);
+
+    const mint = await mintAchievement(achievementId, player);
+    const tokenAccount = await getAccount(provider.connection, await getAssociatedTokenAddress(mint, player));
+    expect(tokenAccount.amount).to.equal(BigInt(1));
+
+    const metadata = await program.account.nftMetadata.fetch(pda([Buffer.from("nft_metadata"), mint.toBytes()]));
+    expect(metadata.currentOwner.toString()).to.equal(player.toString());
+    expect(metadata.originChainId.toNumber()).to.equal(peerChainId);
+    expect(Buffer.from(metadata.originContract).equals(gameContract)).to.be.true;
+    expect((await program.account.trustedPeer.fetch(trustedPeerPda)).minted.toNumber()).to.equal(1);
+
+    try {
+      await mintAchievement(crypto.randomBytes(32), player);
+      expect.fail("Should have rejected a mint past the quota");
+    } catch (error) {
+      expect(error.message).to.include("AchievementQuotaExceeded");
+    }
+
+    // Raising the quota doesn't let the same award mint twice
+    await setTrustedPeer(5);
+    try {
+      await mintAchievement(achievementId, player);
+      expect.fail("Should have rejected a replayed award");
+    } catch (error) {
+      expect(error.message).to.include("already in use");
+    }
+
+    const auditLog = await program.account.auditLog.fetch(auditLogPda);
+    const entry = await program.account.auditEntry.fetch(
+      pda([Buffer.from("audit_entry"), auditLog.entryCount.subn(1).toArrayLike(Buffer, "le", 8)])
+    );
+    expect(entry.action).to.deep.equal({ setTrustedPeer: {} });
+  });
+
+  it('Rejects awards from a contract that was never trusted', async () => {
+    const untrusted = crypto.randomBytes(20);
+    const achievementId = crypto.randomBytes(32);
+    const mint = pda([Buffer.from("achievement_mint"), chainIdBytes, untrusted, achievementId]);
+    try {
+      await program.methods
+        .mintAchievement(
+          new anchor.BN(peerChainId),
+          untrusted,
+          Array.from(achievementId),
+          "https://game.example.com/achievements/fake.json",
+          "Fake",
+          "FAKE",
+          crypto.randomBytes(64)
+        )
+        .accounts({
+          programState: programStatePda,
+          crossChainConfig: pda([Buffer.from("cross_chain_config")]),
+          blocklist: pda([Buffer.from("blocklist")]),
+          trustedPeer: pda([Buffer.from("trusted_peer"), chainIdBytes, untrusted]),
+          mint,
+          tokenAccount: await getAssociatedTokenAddress(mint, authority.publicKey),
+          nftMetadata: pda([Buffer.from("nft_metadata"), mint.toBytes()]),
+          recipient: authority.publicKey,
+          authority: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      expect.fail("Should have rejected an untrusted peer");
+    } catch (error) {
+      expect(error.message).to.include("AccountNotInitialized");
+    }
+  });
+});
@@ -0,0 +1,121 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+// @ts-ignore - Types will be available after build
+import { UniversalNft } from '../target/types/universal_nft';
+// @ts-ignore - Types will be available after build
+import { CpiCaller } from '../target/types/cpi_caller';
+import { PublicKey, Keypair, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/web3.js';
+
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from '@solana/spl-token';
+
+import { expect } from 'chai';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('NFT app data', () => {
+  const { provider, program, authority } = setupProgram();
+  const caller = anchor.workspace.CpiCaller as Program<CpiCaller>;
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const appAuthority = (mint: PublicKey) => pda([Buffer.from("universal_nft_app_data"), mint.toBytes()], caller.programId);
+
+  const setAppProgram = async (mint: PublicKey, appProgram: PublicKey | null) =>
+    program.methods
+      .setAppDataProgram(appProgram)
+      .accounts({
+        programState: programStatePda,
+        nftMetadata: pda([Buffer.from("nft_metadata"), mint.toBytes()]),
+        appData: pda([Buffer.from("app_data"), mint.toBytes()]),
+        mint,
+        tokenAccount: await getAssociatedTokenAddress(mint, authority.publicKey),
+        owner: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+  // Through the example app, which signs as its app authority PDA
+  const writeThroughApp = (mint: PublicKey, offset: number, bytes: Buffer) =>
+    caller.methods
+      .writeAppData(offset, bytes)
+      .accounts({
+        appAuthority: appAuthority(mint),
+        mint,
+        programState: programStatePda,
+        nftMetadata: pda([Buffer.from("nft_metadata"), mint.toBytes()]),
+        appData: pda([Buffer.from("app_data"), mint.toBytes()]),
+        universalNftProgram: program.programId,
+      })
+      .rpc();
+
+  const mintNft = async () => {
+    const mint = Keypair.generate();
+    await program.methods
+      .mintNft("https://example.com/game-item.json", "Game Item", "ITEM", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        collectionConfig: null,
+        mint: mint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, authority.publicKey),
+        nftMetadata: pda([Buffer.from("nft_metadata"), mint.publicKey.toBytes()]),
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+    return mint.publicKey;
+  };
+
+  before(async () => {
+    await ensureInitialized();
+  });
+
+  it('Lets the approved app write, within bounds', async () => {
+    const mint = await mintNft();
+    await setAppProgram(mint, caller.programId);
+
+    await writeThroughApp(mint, 10, Buffer.from([1, 2, 3]));
+    const appData = await program.account.nftAppData.fetch(pda([Buffer.from("app_data"), mint.toBytes()]));
+    expect(appData.appProgram.toBase58()).to.equal(caller.programId.toBase58());
+    expect(Buffer.from(appData.data).subarray(8, 15)).to.deep.equal(Buffer.from([0, 0, 1, 2, 3, 0, 0]));
+
+    try {
+      await writeThroughApp(mint, 250, Buffer.alloc(7));
+      expect.fail("Should have rejected a write past the end");
+    } catch (error) {
+      expect(error.message).to.include("AppDataOutOfBounds");
+    }
+  });
+
+  it('Rejects writes from anyone but the approved app', async () => {
+    const mint = await mintNft();
+    await setAppProgram(mint, caller.programId);
+
+    try {
+      await program.methods
+        .writeAppData(0, Buffer.from([9]))
+        .accounts({
+          programState: programStatePda,
+          nftMetadata: pda([Buffer.from("nft_metadata"), mint.toBytes()]),
+          appData: pda([Buffer.from("app_data"), mint.toBytes()]),
+          appAuthority: authority.publicKey,
+        })
+        .rpc();
+      expect.fail("Should have rejected the holder writing directly");
+    } catch (error) {
+      expect(error.message).to.include("AppNotApproved");
+    }
+
+    // Revoking keeps the account but shuts the app out
+    await setAppProgram(mint, null);
+    try {
+      await writeThroughApp(mint, 0, Buffer.from([9]));
+      expect.fail("Should have rejected the revoked app");
+    } catch (error) {
+      expect(error.message).to.include("AppNotApproved");
+    }
+  });
+});
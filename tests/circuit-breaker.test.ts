@@ -0,0 +1,156 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  Keypair,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from '@solana/web3.js';
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddress,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import * as crypto from 'crypto';
+import { setupProgram, ensureInitialized, pda, crossChainTransferAccounts } from './helpers';
+
+describe('outbound circuit breaker', () => {
+  const { provider, program, authority } = setupProgram();
+
+  const destinationChainId = 1; // Ethereum
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const crossChainConfigPda = pda([Buffer.from("cross_chain_config")]);
+  const blocklistPda = pda([Buffer.from("blocklist")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+  const cpiAllowlistPda = pda([Buffer.from("cpi_allowlist")]);
+  const destinationChainConfigPda = pda([
+    Buffer.from("chain_config"),
+    new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8),
+  ]);
+
+  before(async () => {
+    await ensureInitialized();
+
+    // Register the destination chain if an earlier test hasn't already
+    try {
+      await program.account.chainConfig.fetch(destinationChainConfigPda);
+    } catch (error) {
+      const auditLog = await program.account.auditLog.fetch(auditLogPda);
+      await program.methods
+        .registerChain(new anchor.BN(destinationChainId))
+        .accounts({
+          programState: programStatePda,
+          chainConfig: destinationChainConfigPda,
+          cpiAllowlist: cpiAllowlistPda,
+          auditLog: auditLogPda,
+          auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .rpc();
+    }
+  });
+
+  const mintNft = async () => {
+    const mint = Keypair.generate();
+    const tokenAccount = await getAssociatedTokenAddress(mint.publicKey, authority.publicKey);
+    const nftMetadataPda = pda([Buffer.from("nft_metadata"), mint.publicKey.toBytes()]);
+
+    await program.methods
+      .mintNft("https://example.com/breaker.json", "Breaker NFT", "BRKR", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: blocklistPda,
+        collectionConfig: null,
+        mint: mint.publicKey,
+        tokenAccount: tokenAccount,
+        nftMetadata: nftMetadataPda,
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+
+    return { mint: mint.publicKey, tokenAccount, nftMetadataPda };
+  };
+
+  const transferOut = async (nft: Awaited<ReturnType<typeof mintNft>>, nonce: anchor.BN) => {
+    return program.methods
+      .crossChainTransfer(new anchor.BN(destinationChainId), crypto.randomBytes(20), nonce)
+      .accounts(
+        crossChainTransferAccounts(
+          { mint: nft.mint, tokenAccount: nft.tokenAccount, owner: authority.publicKey, destinationChainId, nonce }
+        )
+      )
+      .rpc();
+  };
+
+  const nextNonce = async () => {
+    const config = await program.account.crossChainConfig.fetch(crossChainConfigPda);
+    return anchor.BN.max(config.nonceCounter.addn(1), new anchor.BN(Date.now()));
+  };
+
+  const adminAccounts = async () => {
+    const auditLog = await program.account.auditLog.fetch(auditLogPda);
+    return {
+      programState: programStatePda,
+      crossChainConfig: crossChainConfigPda,
+      cpiAllowlist: cpiAllowlistPda,
+      auditLog: auditLogPda,
+      auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+      authority: authority.publicKey,
+      systemProgram: SystemProgram.programId,
+      instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+    };
+  };
+
+  after(async () => {
+    await program.methods
+      .setCircuitBreaker(new anchor.BN(0), new anchor.BN(0))
+      .accounts(await adminAccounts())
+      .rpc();
+    await program.methods.setPaused(false).accounts(await adminAccounts()).rpc();
+  });
+
+  it('Rejects an enabled breaker with an empty window', async () => {
+    try {
+      await program.methods
+        .setCircuitBreaker(new anchor.BN(2), new anchor.BN(0))
+        .accounts(await adminAccounts())
+        .rpc();
+      expect.fail("Should have rejected a zero-slot window");
+    } catch (error) {
+      expect(error.message).to.include("InvalidCircuitBreaker");
+    }
+  });
+
+  it('Pauses the bridge once the threshold is reached', async () => {
+    await program.methods
+      .setCircuitBreaker(new anchor.BN(2), new anchor.BN(1_000_000))
+      .accounts(await adminAccounts())
+      .rpc();
+
+    // The first transfer stays under the threshold; the second trips it but still succeeds
+    await transferOut(await mintNft(), await nextNonce());
+    expect((await program.account.crossChainConfig.fetch(crossChainConfigPda)).isPaused).to.be.false;
+    await transferOut(await mintNft(), await nextNonce());
+
+    const config = await program.account.crossChainConfig.fetch(crossChainConfigPda);
+    expect(config.isPaused).to.be.true;
+    expect(config.breakerWindowCount.toNumber()).to.equal(2);
+
+    try {
+      await transferOut(await mintNft(), await nextNonce());
+      expect.fail("Should have been paused by the circuit breaker");
+    } catch (error) {
+      expect(error.message).to.include("CrossChainPaused");
+    }
+  });
+});
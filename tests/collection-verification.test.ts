@@ -0,0 +1,123 @@
+import * as anchor from '@coral-xyz/anchor';
+import { PublicKey, Keypair, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY } from '@solana/web3.js';
+
+import { createMint } from '@solana/spl-token';
+
+import { expect } from 'chai';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('collection verification', () => {
+  const { provider, program, authority, client } = setupProgram();
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+
+  let collectionMint: PublicKey;
+
+  before(async () => {
+    await ensureInitialized();
+
+    collectionMint = await createMint(provider.connection, authority.payer, authority.publicKey, null, 0);
+    await program.methods
+      .createCollectionConfig(authority.publicKey)
+      .accounts({
+        programState: programStatePda,
+        collectionConfig: client.findCollectionConfigAddress(collectionMint),
+        collectionMint,
+        symbolRegistry: pda([Buffer.from("symbol_registry")]),
+        collectionMetadata: null,
+        symbolReservation: null,
+        signer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  });
+
+  it('Grants and revokes the badge of a Solana collection', async () => {
+    expect(await client.getCollectionVerification(collectionMint)).to.include({ registered: true, verified: false });
+
+    await client.setCollectionVerified(collectionMint, true);
+    const verification = await client.getCollectionVerification(collectionMint);
+    expect(verification).to.include({ verified: true, updatedBy: authority.publicKey.toString() });
+    expect((await program.account.collectionConfig.fetch(client.findCollectionConfigAddress(collectionMint)))
+      .verification.verified).to.be.true;
+
+    await client.setCollectionVerified(collectionMint, false);
+    expect(await client.getCollectionVerification(collectionMint)).to.include({ verified: false });
+  });
+
+  it('Badges a foreign collection registration', async () => {
+    const originChainId = 1;
+    const originContract = Keypair.generate().publicKey.toBuffer().subarray(0, 20);
+    const foreignMint = Keypair.generate().publicKey;
+    const chainSeed = new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8);
+    await program.methods
+      .registerForeignCollection(
+        new anchor.BN(originChainId),
+        originContract,
+        "Bridged Punks",
+        "BPUNK",
+        0,
+        Buffer.alloc(0),
+        foreignMint,
+        null
+      )
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: pda([Buffer.from("cross_chain_config")]),
+        foreignCollection: client.findForeignCollectionAddress(originChainId, originContract),
+        collectionLedger: pda([Buffer.from("collection_ledger"), chainSeed, originContract]),
+        symbolReservation: null,
+        payer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const foreign = { originChainId, originContract };
+    await client.setCollectionVerified(foreignMint, true, foreign);
+    expect(await client.getCollectionVerification(foreignMint, foreign)).to.include({ registered: true, verified: true });
+
+    try {
+      await client.setCollectionVerified(Keypair.generate().publicKey, true, foreign);
+      expect.fail("Should have rejected a registration for another collection");
+    } catch (error) {
+      expect(error.message).to.include("CollectionNotRegistered");
+    }
+  });
+
+  it('Reports unknown collections as unregistered and only lets the authority badge', async () => {
+    const unknown = Keypair.generate().publicKey;
+    const verification = await program.methods
+      .getCollectionVerification(unknown)
+      .accounts({ collectionConfig: null, foreignCollection: null })
+      .view();
+    expect(verification.registered).to.be.false;
+    expect(verification.badge.verified).to.be.false;
+
+    const stranger = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(stranger.publicKey, 1_000_000_000),
+      'confirmed'
+    );
+    const auditLog = await program.account.auditLog.fetch(pda([Buffer.from("audit_log")]));
+    try {
+      await program.methods
+        .setCollectionVerified(collectionMint, true)
+        .accounts({
+          programState: programStatePda,
+          collectionConfig: client.findCollectionConfigAddress(collectionMint),
+          foreignCollection: null,
+          cpiAllowlist: pda([Buffer.from("cpi_allowlist")]),
+          auditLog: pda([Buffer.from("audit_log")]),
+          auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+          authority: stranger.publicKey,
+          systemProgram: SystemProgram.programId,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .signers([stranger])
+        .rpc();
+      expect.fail("Should have rejected a stranger");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+});
@@ -0,0 +1,220 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+// @ts-ignore - Types will be available after build
+import { UniversalNft } from '../target/types/universal_nft';
+// @ts-ignore - Types will be available after build
+import { CpiCaller } from '../target/types/cpi_caller';
+import {
+  Keypair,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from '@solana/web3.js';
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddress,
+  getOrCreateAssociatedTokenAccount,
+  transfer,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import * as crypto from 'crypto';
+import { setupProgram, ensureInitialized, pda, SOLANA_CHAIN_ID } from './helpers';
+
+describe('cross-chain transfer via CPI', () => {
+  const { provider, program, authority } = setupProgram();
+  const caller = anchor.workspace.CpiCaller as Program<CpiCaller>;
+
+  const destinationChainId = 1; // Ethereum
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const crossChainConfigPda = pda([Buffer.from("cross_chain_config")]);
+  const blocklistPda = pda([Buffer.from("blocklist")]);
+  const optOutRegistryPda = pda([Buffer.from("opt_out_registry")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+  const cpiAllowlistPda = pda([Buffer.from("cpi_allowlist")]);
+  const destinationChainConfigPda = pda([
+    Buffer.from("chain_config"),
+    new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8),
+  ]);
+  const vaultPda = pda([Buffer.from("vault")], caller.programId);
+
+  before(async () => {
+    await ensureInitialized();
+
+    // Register the destination chain if an earlier test hasn't already
+    try {
+      await program.account.chainConfig.fetch(destinationChainConfigPda);
+    } catch (error) {
+      const auditLog = await program.account.auditLog.fetch(auditLogPda);
+      await program.methods
+        .registerChain(new anchor.BN(destinationChainId))
+        .accounts({
+          programState: programStatePda,
+          chainConfig: destinationChainConfigPda,
+          cpiAllowlist: cpiAllowlistPda,
+          auditLog: auditLogPda,
+          auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .rpc();
+    }
+
+    try {
+      await caller.account.vault.fetch(vaultPda);
+    } catch (error) {
+      await caller.methods
+        .initializeVault()
+        .accounts({
+          vault: vaultPda,
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+  });
+
+  it('Bridges an NFT owned by a calling program PDA', async () => {
+    const mint = Keypair.generate();
+    const authorityTokenAccount = await getAssociatedTokenAddress(mint.publicKey, authority.publicKey);
+    const nftMetadataPda = pda([Buffer.from("nft_metadata"), mint.publicKey.toBytes()]);
+
+    await program.methods
+      .mintNft("https://example.com/vaulted.json", "Vaulted NFT", "VAULT", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: blocklistPda,
+        collectionConfig: null,
+        mint: mint.publicKey,
+        tokenAccount: authorityTokenAccount,
+        nftMetadata: nftMetadataPda,
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+
+    // Move the NFT into the calling program's vault, a PDA that holds data
+    const vaultTokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      authority.payer,
+      mint.publicKey,
+      vaultPda,
+      true
+    );
+    await transfer(
+      provider.connection,
+      authority.payer,
+      authorityTokenAccount,
+      vaultTokenAccount.address,
+      authority.publicKey,
+      1
+    );
+
+    const nonce = Date.now();
+    const transferRecordPda = pda([
+      Buffer.from("cross_chain_transfer"),
+      mint.publicKey.toBytes(),
+      new anchor.BN(nonce).toArrayLike(Buffer, "le", 8),
+    ]);
+    const recipient = crypto.randomBytes(20);
+
+    await caller.methods
+      .bridgeFromVault(new anchor.BN(destinationChainId), recipient, new anchor.BN(nonce))
+      .accounts({
+        vault: vaultPda,
+        authority: authority.publicKey,
+        programState: programStatePda,
+        crossChainConfig: crossChainConfigPda,
+        blocklist: blocklistPda,
+        optOutRegistry: optOutRegistryPda,
+        destinationChainConfig: destinationChainConfigPda,
+        nftMetadata: nftMetadataPda,
+        collectionConfig: null,
+        collectionLedger: pda([
+          Buffer.from("collection_ledger"),
+          new anchor.BN(SOLANA_CHAIN_ID).toArrayLike(Buffer, "le", 8),
+        ]),
+        transferRecord: transferRecordPda,
+        ownerTransferIndex: pda([Buffer.from("owner_transfer_index"), vaultPda.toBytes()]),
+        pendingTransfers: pda([Buffer.from("pending_transfers")]),
+        mint: mint.publicKey,
+        tokenAccount: vaultTokenAccount.address,
+        credential: null,
+        gateTokenAccount: null,
+        stakeRecord: pda([Buffer.from("stake_record"), mint.publicKey.toBytes()]),
+        feeTreasury: null,
+        payerFeeAccount: null,
+        treasuryFeeAccount: null,
+        feeReserve: pda([Buffer.from("fee_reserve")]),
+        universalNftProgram: program.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const transferRecord = await program.account.crossChainTransfer.fetch(transferRecordPda);
+    expect(transferRecord.originalOwner.toString()).to.equal(vaultPda.toString());
+    expect(Buffer.from(transferRecord.recipientAddress).equals(recipient)).to.be.true;
+    expect(transferRecord.status).to.equal(0);
+
+    const index = await program.account.ownerTransferIndex.fetch(
+      pda([Buffer.from("owner_transfer_index"), vaultPda.toBytes()])
+    );
+    expect(index.entries[index.entries.length - 1].mint.toString()).to.equal(mint.publicKey.toString());
+    expect(index.entries[index.entries.length - 1].nonce.toNumber()).to.equal(nonce);
+
+    const nftMetadata = await program.account.nftMetadata.fetch(nftMetadataPda);
+    expect(nftMetadata.isLocked).to.be.true;
+    expect(nftMetadata.currentOwner.toString()).to.equal(vaultPda.toString());
+  });
+
+  it('Rejects a vault transfer from anyone but the vault authority', async () => {
+    const stranger = Keypair.generate();
+
+    try {
+      await caller.methods
+        .bridgeFromVault(new anchor.BN(destinationChainId), crypto.randomBytes(20), new anchor.BN(Date.now()))
+        .accounts({
+          vault: vaultPda,
+          authority: stranger.publicKey,
+          programState: programStatePda,
+          crossChainConfig: crossChainConfigPda,
+          blocklist: blocklistPda,
+          optOutRegistry: optOutRegistryPda,
+          destinationChainConfig: destinationChainConfigPda,
+          nftMetadata: Keypair.generate().publicKey,
+          collectionConfig: null,
+          collectionLedger: Keypair.generate().publicKey,
+          transferRecord: Keypair.generate().publicKey,
+          ownerTransferIndex: Keypair.generate().publicKey,
+          pendingTransfers: pda([Buffer.from("pending_transfers")]),
+          mint: Keypair.generate().publicKey,
+          tokenAccount: Keypair.generate().publicKey,
+          credential: null,
+          gateTokenAccount: null,
+          stakeRecord: Keypair.generate().publicKey,
+          feeTreasury: null,
+          payerFeeAccount: null,
+          treasuryFeeAccount: null,
+          feeReserve: pda([Buffer.from("fee_reserve")]),
+          universalNftProgram: program.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([stranger])
+        .rpc();
+
+      expect.fail("Should have rejected a signer that is not the vault authority");
+    } catch (error) {
+      expect(error.message).to.include("has one constraint");
+    }
+  });
+});
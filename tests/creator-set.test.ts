@@ -0,0 +1,177 @@
+import * as anchor from '@coral-xyz/anchor';
+import { PublicKey, Keypair, SystemProgram } from '@solana/web3.js';
+
+import { createMint } from '@solana/spl-token';
+
+import { expect } from 'chai';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('creator sets', () => {
+  const { provider, program, authority, client } = setupProgram();
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const creators = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+  const [alice, bob, carol] = creators;
+
+  let collectionMint: PublicKey;
+  const collectionConfigPda = () => pda([Buffer.from("collection_config"), collectionMint.toBytes()]);
+
+  const setBridging = (enabled: boolean, signer: Keypair) =>
+    program.methods
+      .setCollectionBridging(enabled)
+      .accounts({
+        programState: programStatePda,
+        collectionConfig: collectionConfigPda(),
+        creatorSet: client.findCreatorSetAddress(collectionMint),
+        creatorApproval: client.findCreatorApprovalAddress(collectionMint, { action: 'setBridging', enabled }),
+        signer: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+
+  before(async () => {
+    await ensureInitialized();
+    for (const creator of creators) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(creator.publicKey, 1_000_000_000),
+        'confirmed'
+      );
+    }
+
+    collectionMint = await createMint(provider.connection, authority.payer, authority.publicKey, null, 0);
+    await program.methods
+      .createCollectionConfig(alice.publicKey)
+      .accounts({
+        programState: programStatePda,
+        collectionConfig: collectionConfigPda(),
+        collectionMint,
+        symbolRegistry: pda([Buffer.from("symbol_registry")]),
+        collectionMetadata: null,
+        symbolReservation: null,
+        signer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    await client.setCreatorSet(collectionMint, creators.map((creator) => creator.publicKey), 2);
+  });
+
+  it('Needs two of three creators to change bridging', async () => {
+    try {
+      await setBridging(false, alice);
+      expect.fail("Should have required approvals");
+    } catch (error) {
+      expect(error.message).to.include("CreatorApprovalRequired");
+    }
+
+    const disable = { action: 'setBridging' as const, enabled: false };
+    await client.approveCollectionAction(collectionMint, disable, alice);
+    try {
+      await setBridging(false, alice);
+      expect.fail("One approval should not be enough");
+    } catch (error) {
+      expect(error.message).to.include("CreatorApprovalRequired");
+    }
+
+    await client.approveCollectionAction(collectionMint, disable, bob);
+    await setBridging(false, carol);
+    expect((await program.account.collectionConfig.fetch(collectionConfigPda())).crossChainEnabled).to.be.false;
+
+    try {
+      await setBridging(false, carol);
+      expect.fail("Approvals should be used up");
+    } catch (error) {
+      expect(error.message).to.include("CreatorApprovalRequired");
+    }
+  });
+
+  it('Only accepts approvals for the exact arguments', async () => {
+    await client.approveCollectionAction(collectionMint, { action: 'setBridging', enabled: true }, alice);
+    await client.approveCollectionAction(collectionMint, { action: 'setBridging', enabled: true }, bob);
+    try {
+      await setBridging(false, alice);
+      expect.fail("Should have rejected arguments nobody approved");
+    } catch (error) {
+      expect(error.message).to.include("CreatorApprovalRequired");
+    }
+    await setBridging(true, alice);
+
+    try {
+      await client.approveCollectionAction(collectionMint, { action: 'setBridging', enabled: false }, Keypair.generate());
+      expect.fail("Should have rejected an outsider's approval");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+
+  it('Keeps competing proposals apart', async () => {
+    const disable = { action: 'setBridging' as const, enabled: false };
+    const enable = { action: 'setBridging' as const, enabled: true };
+    await client.approveCollectionAction(collectionMint, disable, alice);
+    await client.approveCollectionAction(collectionMint, enable, bob);
+    await client.approveCollectionAction(collectionMint, disable, carol);
+
+    const approvals = async (args: typeof disable) =>
+      (await program.account.creatorApproval.fetch(client.findCreatorApprovalAddress(collectionMint, args))).approvals;
+    expect(await approvals(disable)).to.equal(0b101);
+    expect(await approvals(enable)).to.equal(0b010);
+
+    await setBridging(false, bob);
+    expect((await program.account.collectionConfig.fetch(collectionConfigPda())).crossChainEnabled).to.be.false;
+    try {
+      await setBridging(true, bob);
+      expect.fail("One approval should not be enough");
+    } catch (error) {
+      expect(error.message).to.include("CreatorApprovalRequired");
+    }
+
+    await client.approveCollectionAction(collectionMint, enable, alice);
+    await setBridging(true, carol);
+    expect((await program.account.collectionConfig.fetch(collectionConfigPda())).crossChainEnabled).to.be.true;
+  });
+
+  it('Gates changes to the set itself and keeps the program authority override', async () => {
+    const smaller = { action: 'setCreatorSet' as const, creators: [alice.publicKey, bob.publicKey], threshold: 2 };
+    await client.approveCollectionAction(collectionMint, smaller, carol);
+    await client.approveCollectionAction(collectionMint, smaller, bob);
+    await program.methods
+      .setCreatorSet(smaller.creators, smaller.threshold)
+      .accounts({
+        programState: programStatePda,
+        collectionConfig: collectionConfigPda(),
+        creatorSet: client.findCreatorSetAddress(collectionMint),
+        creatorApproval: client.findCreatorApprovalAddress(collectionMint, smaller),
+        signer: bob.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([bob])
+      .rpc();
+    const creatorSet = (await client.getCreatorSet(collectionMint))!;
+    expect(creatorSet.creators).to.deep.equal(smaller.creators.map((creator) => creator.toString()));
+    expect(creatorSet.version).to.equal(2);
+
+    await client.setCollectionBridging(collectionMint, false);
+    expect((await program.account.collectionConfig.fetch(collectionConfigPda())).crossChainEnabled).to.be.false;
+    await client.setCreatorSet(collectionMint, [], 0);
+    await setBridging(true, alice);
+  });
+
+  it('Rejects thresholds the creators cannot reach', async () => {
+    const creator = () => Keypair.generate().publicKey;
+    const duplicate = creator();
+    const invalid: [PublicKey[], number][] = [
+      [[creator(), creator()], 3],
+      [[creator()], 0],
+      [[], 1],
+      [[duplicate, duplicate], 1],
+      [[creator(), creator(), creator(), creator(), creator(), creator()], 2],
+    ];
+    for (const [members, threshold] of invalid) {
+      try {
+        await client.setCreatorSet(collectionMint, members, threshold);
+        expect.fail(`Should have rejected ${threshold} of ${members.length}`);
+      } catch (error) {
+        expect(error.message).to.include("InvalidCreatorSet");
+      }
+    }
+  });
+});
@@ -0,0 +1,158 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from '@solana/web3.js';
+
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddress,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import * as crypto from 'crypto';
+import { setupProgram, ensureInitialized, pda, crossChainTransferAccounts } from './helpers';
+
+describe('credential-gated bridging', () => {
+  const { provider, program, authority } = setupProgram();
+
+  const destinationChainId = 1; // Ethereum
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const crossChainConfigPda = pda([Buffer.from("cross_chain_config")]);
+  const blocklistPda = pda([Buffer.from("blocklist")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+  const cpiAllowlistPda = pda([Buffer.from("cpi_allowlist")]);
+  const destinationChainConfigPda = pda([
+    Buffer.from("chain_config"),
+    new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8),
+  ]);
+
+  before(async () => {
+    await ensureInitialized();
+
+    // Register the destination chain if an earlier test hasn't already
+    try {
+      await program.account.chainConfig.fetch(destinationChainConfigPda);
+    } catch (error) {
+      const auditLog = await program.account.auditLog.fetch(auditLogPda);
+      await program.methods
+        .registerChain(new anchor.BN(destinationChainId))
+        .accounts({
+          programState: programStatePda,
+          chainConfig: destinationChainConfigPda,
+          cpiAllowlist: cpiAllowlistPda,
+          auditLog: auditLogPda,
+          auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .rpc();
+    }
+  });
+
+  const mintNft = async () => {
+    const mint = Keypair.generate();
+    const tokenAccount = await getAssociatedTokenAddress(mint.publicKey, authority.publicKey);
+    const nftMetadataPda = pda([Buffer.from("nft_metadata"), mint.publicKey.toBytes()]);
+
+    await program.methods
+      .mintNft("https://example.com/gated.json", "Gated NFT", "GATE", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: blocklistPda,
+        collectionConfig: null,
+        mint: mint.publicKey,
+        tokenAccount: tokenAccount,
+        nftMetadata: nftMetadataPda,
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+
+    return { mint: mint.publicKey, tokenAccount, nftMetadataPda };
+  };
+
+  const transferOut = async (
+    nft: Awaited<ReturnType<typeof mintNft>>,
+    nonce: anchor.BN,
+    credential: PublicKey | null = null
+  ) => {
+    return program.methods
+      .crossChainTransfer(new anchor.BN(destinationChainId), crypto.randomBytes(20), nonce)
+      .accounts(
+        crossChainTransferAccounts(
+          { mint: nft.mint, tokenAccount: nft.tokenAccount, owner: authority.publicKey, destinationChainId, nonce },
+          { credential }
+        )
+      )
+      .rpc();
+  };
+
+  const nextNonce = async () => {
+    const config = await program.account.crossChainConfig.fetch(crossChainConfigPda);
+    return anchor.BN.max(config.nonceCounter.addn(1), new anchor.BN(Date.now()));
+  };
+
+  const setRequirement = async (requirement: object | null) => {
+    const auditLog = await program.account.auditLog.fetch(auditLogPda);
+    await program.methods
+      .setCredentialRequirement(requirement)
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: crossChainConfigPda,
+        cpiAllowlist: cpiAllowlistPda,
+        auditLog: auditLogPda,
+        auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  };
+
+  after(async () => {
+    await setRequirement(null);
+  });
+
+  it('Requires a token account of the credential mint held by the owner', async () => {
+    // Any NFT mint stands in for a soulbound KYC mint: its token account holds the mint at 0 and the owner at 32
+    const credential = await mintNft();
+    await setRequirement({
+      issuer: TOKEN_PROGRAM_ID,
+      credentialTag: credential.mint,
+      tagOffset: 0,
+      subjectOffset: 32,
+    });
+
+    try {
+      await transferOut(await mintNft(), await nextNonce());
+      expect.fail("Should have required a credential");
+    } catch (error) {
+      expect(error.message).to.include("MissingCredential");
+    }
+
+    // The NFT's own token account is owned by the token program but holds the wrong mint
+    const nft = await mintNft();
+    try {
+      await transferOut(nft, await nextNonce(), nft.tokenAccount);
+      expect.fail("Should have rejected a credential for another mint");
+    } catch (error) {
+      expect(error.message).to.include("InvalidCredential");
+    }
+
+    await transferOut(nft, await nextNonce(), credential.tokenAccount);
+    const metadata = await program.account.nftMetadata.fetch(nft.nftMetadataPda);
+    expect(metadata.isLocked).to.be.true;
+  });
+});
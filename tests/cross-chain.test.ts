@@ -6,12 +6,16 @@ import {
   PublicKey, 
   Keypair, 
   SystemProgram,
-  SYSVAR_RENT_PUBKEY 
+  ComputeBudgetProgram,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
 } from '@solana/web3.js';
 import { 
   TOKEN_PROGRAM_ID, 
   ASSOCIATED_TOKEN_PROGRAM_ID,
   getAssociatedTokenAddress,
+  createMint,
+  createAssociatedTokenAccount,
 } from '@solana/spl-token';
 import { expect } from 'chai';
 import * as crypto from 'crypto';
@@ -168,7 +172,9 @@ describe('cross-chain functionality', () => {
     expect(receipt.mint.toString()).to.equal(mintKeypair.publicKey.toString());
     expect(receipt.recipient.toString()).to.equal(recipient.publicKey.toString());
     expect(receipt.nonce.toString()).to.equal(nonce.toString());
-    expect(Buffer.from(receipt.tssSignature)).to.deep.equal(Buffer.from(tssSignature));
+    expect(Buffer.from(receipt.tssSignatureHash)).to.deep.equal(
+      crypto.createHash('sha256').update(tssSignature).digest()
+    );
 
     // Verify program state updated
     const programState = await program.account.programState.fetch(programStatePda);
@@ -348,6 +354,26 @@ describe('cross-chain functionality', () => {
           mint: mintKeypair.publicKey,
           tokenAccount: tokenAccount,
           owner: owner.publicKey,
+          ownerTransferIndex: PublicKey.findProgramAddressSync(
+            [Buffer.from("owner_transfer_index"), owner.publicKey.toBytes()],
+            program.programId
+          )[0],
+          pendingTransfers: PublicKey.findProgramAddressSync(
+            [Buffer.from("pending_transfers")],
+            program.programId
+          )[0],
+          credential: null,
+          gateTokenAccount: null,
+          stakeRecord: PublicKey.findProgramAddressSync(
+            [Buffer.from("stake_record"), mintKeypair.publicKey.toBytes()],
+            program.programId
+          )[0],
+          localizedMetadata: null,
+          feeTreasury: null,
+          payerFeeAccount: null,
+          treasuryFeeAccount: null,
+          feeReserve: PublicKey.findProgramAddressSync([Buffer.from("fee_reserve")], program.programId)[0],
+          payer: owner.publicKey,
           gatewayProgram: gatewayAddress,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
@@ -360,4 +386,410 @@ describe('cross-chain functionality', () => {
       expect(error.message).to.include("Invalid recipient address");
     }
   });
+
+  it('Rejects an already processed inbound message with AlreadyProcessed', async () => {
+    const recipient = Keypair.generate();
+    const originChainId = 1; // Ethereum
+    const originTxHash = crypto.randomBytes(32);
+    const originContract = crypto.randomBytes(20);
+    const originTokenId = crypto.randomBytes(32);
+    const nonce = Date.now();
+
+    const [blocklistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blocklist")],
+      program.programId
+    );
+
+    const [cpiAllowlistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cpi_allowlist")],
+      program.programId
+    );
+
+    const [originChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("chain_config"), new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    // Register the origin chain if an earlier test hasn't already
+    try {
+      const [auditLogPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("audit_log")],
+        program.programId
+      );
+      const auditLog = await program.account.auditLog.fetch(auditLogPda);
+      const [auditEntryPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      await program.methods
+        .registerChain(new anchor.BN(originChainId))
+        .accounts({
+          programState: programStatePda,
+          chainConfig: originChainConfigPda,
+          cpiAllowlist: cpiAllowlistPda,
+          auditLog: auditLogPda,
+          auditEntry: auditEntryPda,
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .rpc();
+    } catch (error) {
+      // Already registered
+    }
+
+    const [receiptPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("cross_chain_receipt"),
+        originTxHash,
+        new anchor.BN(nonce).toArrayLike(Buffer, "le", 8)
+      ],
+      program.programId
+    );
+
+    const [inboundNoncePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("inbound_nonce"), new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    const [mint] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("inbound_mint"),
+        new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8),
+        originContract,
+        originTokenId,
+      ],
+      program.programId
+    );
+
+    const tokenAccount = await getAssociatedTokenAddress(mint, recipient.publicKey);
+
+    const [nftMetadataPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nft_metadata"), mint.toBytes()],
+      program.programId
+    );
+
+    const [collectionLedgerPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("collection_ledger"),
+        new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8),
+        originContract,
+      ],
+      program.programId
+    );
+
+    const receive = async () => {
+      return program.methods
+        .receiveCrossChain(
+          new anchor.BN(originChainId),
+          Array.from(originTxHash),
+          originContract,
+          Array.from(originTokenId),
+          "https://ethereum.example.com/metadata.json",
+          "Retried NFT",
+          "RETRY",
+          Array.from(crypto.randomBytes(20)),
+          recipient.publicKey,
+          Array.from(crypto.randomBytes(64)),
+          new anchor.BN(nonce)
+        )
+        .accounts({
+          programState: programStatePda,
+          crossChainConfig: crossChainConfigPda,
+          blocklist: blocklistPda,
+          cpiAllowlist: cpiAllowlistPda,
+          originChainConfig: originChainConfigPda,
+          receipt: receiptPda,
+          inboundNonce: inboundNoncePda,
+          mint: mint,
+          freezeAuthority: PublicKey.findProgramAddressSync([Buffer.from("freeze_authority")], program.programId)[0],
+          tokenAccount: tokenAccount,
+          nftMetadata: nftMetadataPda,
+          collectionLedger: collectionLedgerPda,
+          recipient: recipient.publicKey,
+          authority: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 })])
+        .rpc();
+    };
+
+    // First delivery succeeds
+    await receive();
+
+    // A relayer retry of the same message is reported as a duplicate
+    try {
+      await receive();
+      expect.fail("Should have rejected the duplicate message");
+    } catch (error) {
+      expect(error.message).to.include("already been processed");
+    }
+  });
+
+  it('Rejects inbound messages from unregistered chains and from this chain', async () => {
+    const config = await program.account.crossChainConfig.fetch(crossChainConfigPda);
+
+    const [blocklistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blocklist")],
+      program.programId
+    );
+
+    const [cpiAllowlistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cpi_allowlist")],
+      program.programId
+    );
+
+    const receiveFrom = async (originChainId: anchor.BN) => {
+      const recipient = Keypair.generate();
+      const originTxHash = crypto.randomBytes(32);
+      const originContract = crypto.randomBytes(20);
+      const originTokenId = crypto.randomBytes(32);
+      const nonce = new anchor.BN(Date.now());
+      const chainIdBytes = originChainId.toArrayLike(Buffer, "le", 8);
+
+      const [originChainConfigPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("chain_config"), chainIdBytes],
+        program.programId
+      );
+      const [receiptPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("cross_chain_receipt"), originTxHash, nonce.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [inboundNoncePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("inbound_nonce"), chainIdBytes],
+        program.programId
+      );
+      const [mint] = PublicKey.findProgramAddressSync(
+        [Buffer.from("inbound_mint"), chainIdBytes, originContract, originTokenId],
+        program.programId
+      );
+      const [nftMetadataPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("nft_metadata"), mint.toBytes()],
+        program.programId
+      );
+      const [collectionLedgerPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("collection_ledger"), chainIdBytes, originContract],
+        program.programId
+      );
+
+      return program.methods
+        .receiveCrossChain(
+          originChainId,
+          Array.from(originTxHash),
+          originContract,
+          Array.from(originTokenId),
+          "https://example.com/metadata.json",
+          "Stray NFT",
+          "STRAY",
+          Array.from(crypto.randomBytes(20)),
+          recipient.publicKey,
+          Array.from(crypto.randomBytes(64)),
+          nonce
+        )
+        .accounts({
+          programState: programStatePda,
+          crossChainConfig: crossChainConfigPda,
+          blocklist: blocklistPda,
+          cpiAllowlist: cpiAllowlistPda,
+          originChainConfig: originChainConfigPda,
+          receipt: receiptPda,
+          inboundNonce: inboundNoncePda,
+          mint: mint,
+          freezeAuthority: PublicKey.findProgramAddressSync([Buffer.from("freeze_authority")], program.programId)[0],
+          tokenAccount: await getAssociatedTokenAddress(mint, recipient.publicKey),
+          nftMetadata: nftMetadataPda,
+          collectionLedger: collectionLedgerPda,
+          recipient: recipient.publicKey,
+          authority: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 })])
+        .rpc();
+    };
+
+    try {
+      await receiveFrom(new anchor.BN(987654321));
+      expect.fail("Should have rejected an unregistered origin chain");
+    } catch (error) {
+      expect(error.message).to.include("OriginChainNotRegistered");
+    }
+
+    try {
+      await receiveFrom(config.chainId);
+      expect.fail("Should have rejected a message claiming to come from this chain");
+    } catch (error) {
+      expect(error.message).to.include("OriginIsLocalChain");
+    }
+  });
+
+  it('Rejects an inbound nonce that is not higher than the last accepted', async () => {
+    // Registered by the AlreadyProcessed test above
+    const originChainId = new anchor.BN(1);
+    const chainIdBytes = originChainId.toArrayLike(Buffer, "le", 8);
+    const nonce = new anchor.BN(Date.now());
+
+    const [blocklistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blocklist")],
+      program.programId
+    );
+    const [cpiAllowlistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cpi_allowlist")],
+      program.programId
+    );
+    const [originChainConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("chain_config"), chainIdBytes],
+      program.programId
+    );
+    const [inboundNoncePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("inbound_nonce"), chainIdBytes],
+      program.programId
+    );
+
+    // Each delivery is a distinct message; only the nonce is shared
+    const deliver = async () => {
+      const recipient = Keypair.generate();
+      const originTxHash = crypto.randomBytes(32);
+      const originContract = crypto.randomBytes(20);
+      const originTokenId = crypto.randomBytes(32);
+
+      const [receiptPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("cross_chain_receipt"), originTxHash, nonce.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [mint] = PublicKey.findProgramAddressSync(
+        [Buffer.from("inbound_mint"), chainIdBytes, originContract, originTokenId],
+        program.programId
+      );
+      const [nftMetadataPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("nft_metadata"), mint.toBytes()],
+        program.programId
+      );
+      const [collectionLedgerPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("collection_ledger"), chainIdBytes, originContract],
+        program.programId
+      );
+
+      return program.methods
+        .receiveCrossChain(
+          originChainId,
+          Array.from(originTxHash),
+          originContract,
+          Array.from(originTokenId),
+          "https://ethereum.example.com/metadata.json",
+          "Nonce NFT",
+          "NONCE",
+          Array.from(crypto.randomBytes(20)),
+          recipient.publicKey,
+          Array.from(crypto.randomBytes(64)),
+          nonce
+        )
+        .accounts({
+          programState: programStatePda,
+          crossChainConfig: crossChainConfigPda,
+          blocklist: blocklistPda,
+          cpiAllowlist: cpiAllowlistPda,
+          originChainConfig: originChainConfigPda,
+          receipt: receiptPda,
+          inboundNonce: inboundNoncePda,
+          mint: mint,
+          freezeAuthority: PublicKey.findProgramAddressSync([Buffer.from("freeze_authority")], program.programId)[0],
+          tokenAccount: await getAssociatedTokenAddress(mint, recipient.publicKey),
+          nftMetadata: nftMetadataPda,
+          collectionLedger: collectionLedgerPda,
+          recipient: recipient.publicKey,
+          authority: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 })])
+        .rpc();
+    };
+
+    await deliver();
+    const tracker = await program.account.inboundNonce.fetch(inboundNoncePda);
+    expect(tracker.lastNonce.toString()).to.equal(nonce.toString());
+
+    try {
+      await deliver();
+      expect.fail("Should have rejected a reused inbound nonce");
+    } catch (error) {
+      expect(error.message).to.include("InvalidInboundNonce");
+    }
+  });
+
+  it('Rejects a token account signed as the recipient', async () => {
+    const originChainId = new anchor.BN(1);
+    const chainIdBytes = originChainId.toArrayLike(Buffer, "le", 8);
+    const nonce = new anchor.BN(Date.now());
+    const originTxHash = crypto.randomBytes(32);
+    const originContract = crypto.randomBytes(20);
+    const originTokenId = crypto.randomBytes(32);
+
+    // An address the sender copied from a wallet's token list instead of the wallet
+    const someMint = await createMint(provider.connection, authority.payer, authority.publicKey, null, 0);
+    const tokenAccountRecipient = await createAssociatedTokenAccount(
+      provider.connection,
+      authority.payer,
+      someMint,
+      authority.publicKey
+    );
+
+    const pda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+    const mint = pda([Buffer.from("inbound_mint"), chainIdBytes, originContract, originTokenId]);
+
+    try {
+      await program.methods
+        .receiveCrossChain(
+          originChainId,
+          Array.from(originTxHash),
+          originContract,
+          Array.from(originTokenId),
+          "https://ethereum.example.com/metadata.json",
+          "Misaddressed NFT",
+          "MISS",
+          Array.from(crypto.randomBytes(20)),
+          tokenAccountRecipient,
+          Array.from(crypto.randomBytes(64)),
+          nonce
+        )
+        .accounts({
+          programState: programStatePda,
+          crossChainConfig: crossChainConfigPda,
+          blocklist: pda([Buffer.from("blocklist")]),
+          cpiAllowlist: pda([Buffer.from("cpi_allowlist")]),
+          originChainConfig: pda([Buffer.from("chain_config"), chainIdBytes]),
+          receipt: pda([Buffer.from("cross_chain_receipt"), originTxHash, nonce.toArrayLike(Buffer, "le", 8)]),
+          inboundNonce: pda([Buffer.from("inbound_nonce"), chainIdBytes]),
+          mint,
+          freezeAuthority: pda([Buffer.from("freeze_authority")]),
+          tokenAccount: await getAssociatedTokenAddress(mint, tokenAccountRecipient, true),
+          nftMetadata: pda([Buffer.from("nft_metadata"), mint.toBytes()]),
+          collectionLedger: pda([Buffer.from("collection_ledger"), chainIdBytes, originContract]),
+          recipient: tokenAccountRecipient,
+          authority: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 })])
+        .rpc();
+      expect.fail("Should have rejected a token account as the recipient");
+    } catch (error) {
+      expect(error.message).to.include("InvalidRecipientAccount");
+    }
+  });
 });
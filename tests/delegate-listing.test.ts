@@ -0,0 +1,226 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  LAMPORTS_PER_SOL,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from '@solana/web3.js';
+
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createMint,
+  getAssociatedTokenAddress,
+  getAccount,
+  createAssociatedTokenAccount,
+  transfer,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import * as crypto from 'crypto';
+import { setupProgram, ensureInitialized, pda, crossChainTransferAccounts } from './helpers';
+
+describe('delegate listings', () => {
+  const { provider, program, authority, client } = setupProgram();
+
+  const destinationChainId = 1; // Ethereum
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+  const cpiAllowlistPda = pda([Buffer.from("cpi_allowlist")]);
+  const destinationChainConfigPda = pda([
+    Buffer.from("chain_config"),
+    new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8),
+  ]);
+  const metadataPda = (mint: PublicKey) => pda([Buffer.from("nft_metadata"), mint.toBytes()]);
+  const collectionConfigPda = (collectionMint: PublicKey) =>
+    pda([Buffer.from("collection_config"), collectionMint.toBytes()]);
+
+  const price = new anchor.BN(LAMPORTS_PER_SOL);
+  let buyer: Keypair;
+
+  const createCollection = async () => {
+    const collectionMint = await createMint(provider.connection, authority.payer, authority.publicKey, null, 0);
+    await program.methods
+      .createCollectionConfig(authority.publicKey)
+      .accounts({
+        programState: programStatePda,
+        collectionConfig: collectionConfigPda(collectionMint),
+        collectionMint,
+        symbolRegistry: pda([Buffer.from("symbol_registry")]),
+        collectionMetadata: null,
+        symbolReservation: null,
+        signer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    return collectionMint;
+  };
+
+  const mintNft = async (collectionMint: PublicKey | null = null) => {
+    const mint = Keypair.generate();
+    await program.methods
+      .mintNft("https://example.com/listing.json", "Listed NFT", "LIST", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        collectionConfig: collectionMint ? collectionConfigPda(collectionMint) : null,
+        freezeAuthority: pda([Buffer.from("freeze_authority")]),
+        mint: mint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, authority.publicKey),
+        nftMetadata: metadataPda(mint.publicKey),
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+    return mint.publicKey;
+  };
+
+  const transferOut = async (mint: PublicKey) => {
+    const nonce = new anchor.BN(Date.now());
+    return program.methods
+      .crossChainTransfer(new anchor.BN(destinationChainId), crypto.randomBytes(20), nonce)
+      .accounts(
+        crossChainTransferAccounts(
+          { mint, tokenAccount: await getAssociatedTokenAddress(mint, authority.publicKey), owner: authority.publicKey, destinationChainId, nonce }
+        )
+      )
+      .rpc();
+  };
+
+  const balance = (address: PublicKey) => provider.connection.getBalance(address, 'confirmed');
+
+  before(async () => {
+    await ensureInitialized();
+
+    try {
+      await program.account.chainConfig.fetch(destinationChainConfigPda);
+    } catch (error) {
+      const auditLog = await program.account.auditLog.fetch(auditLogPda);
+      await program.methods
+        .registerChain(new anchor.BN(destinationChainId))
+        .accounts({
+          programState: programStatePda,
+          chainConfig: destinationChainConfigPda,
+          cpiAllowlist: cpiAllowlistPda,
+          auditLog: auditLogPda,
+          auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .rpc();
+    }
+
+    buyer = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(buyer.publicKey, 20 * LAMPORTS_PER_SOL),
+      'confirmed'
+    );
+  });
+
+  const createAssociatedTokenAccountFor = (mint: PublicKey, owner: Keypair) =>
+    createAssociatedTokenAccount(provider.connection, owner, mint, owner.publicKey);
+
+  const sellerAccount = async (mint: PublicKey) =>
+    getAccount(provider.connection, await getAssociatedTokenAddress(mint, authority.publicKey));
+
+  it('Keeps a listed NFT frozen in the wallet until it sells', async () => {
+    const creators = [Keypair.generate().publicKey, Keypair.generate().publicKey];
+    const collectionMint = await createCollection();
+    await client.setCollectionRoyalty(collectionMint, 250, [
+      { address: creators[0], share: 80 },
+      { address: creators[1], share: 20 },
+    ]);
+    const mint = await mintNft(collectionMint);
+    await client.listWithDelegate(mint, authority.payer, price);
+
+    const listing = client.findDelegateListingAddress(mint);
+    let account = await sellerAccount(mint);
+    expect(account.amount).to.equal(BigInt(1));
+    expect(account.isFrozen).to.be.true;
+    expect(account.delegate!.toString()).to.equal(listing.toString());
+
+    try {
+      await transfer(
+        provider.connection,
+        authority.payer,
+        account.address,
+        await createAssociatedTokenAccountFor(mint, buyer),
+        authority.payer,
+        1
+      );
+      expect.fail("Should have kept the listed NFT frozen");
+    } catch (error) {
+      expect(error.message).to.include("0x11"); // AccountFrozen
+    }
+
+    const rent = (await provider.connection.getAccountInfo(listing))!.lamports;
+    const before = await Promise.all([authority.publicKey, ...creators].map(balance));
+    await client.buyDelegateListing(mint, buyer, price);
+    const after = await Promise.all([authority.publicKey, ...creators].map(balance));
+
+    expect(after.map((lamports, i) => lamports - before[i])).to.deep.equal([
+      975_000_000 + rent,
+      20_000_000,
+      5_000_000,
+    ]);
+    account = await sellerAccount(mint);
+    expect(account.amount).to.equal(BigInt(0));
+    expect(account.isFrozen).to.be.false;
+    expect(account.delegate).to.be.null;
+    const buyerAccount = await getAccount(provider.connection, await getAssociatedTokenAddress(mint, buyer.publicKey));
+    expect(buyerAccount.amount).to.equal(BigInt(1));
+    expect(buyerAccount.isFrozen).to.be.false;
+    expect((await program.account.nftMetadata.fetch(metadataPda(mint))).currentOwner.toString())
+      .to.equal(buyer.publicKey.toString());
+    expect(await provider.connection.getAccountInfo(listing)).to.be.null;
+  });
+
+  it('Thaws and revokes on cancel, and honors the buyer\'s maximum', async () => {
+    const mint = await mintNft();
+    try {
+      await client.listWithDelegate(mint, authority.payer, new anchor.BN(0));
+      expect.fail("Should have rejected a free listing");
+    } catch (error) {
+      expect(error.message).to.include("InvalidListingPrice");
+    }
+
+    await client.listWithDelegate(mint, authority.payer, price);
+    try {
+      await client.buyDelegateListing(mint, buyer, price.subn(1));
+      expect.fail("Should have rejected a price above the maximum");
+    } catch (error) {
+      expect(error.message).to.include("ListingPriceTooHigh");
+    }
+
+    await client.cancelDelegateListing(mint, authority.payer);
+    const account = await sellerAccount(mint);
+    expect(account.amount).to.equal(BigInt(1));
+    expect(account.isFrozen).to.be.false;
+    expect(account.delegate).to.be.null;
+    expect(await provider.connection.getAccountInfo(client.findDelegateListingAddress(mint))).to.be.null;
+  });
+
+  it('Rejects selling a listed NFT once it is bridged out', async () => {
+    const mint = await mintNft();
+    await client.listWithDelegate(mint, authority.payer, price);
+    await transferOut(mint);
+
+    try {
+      await client.buyDelegateListing(mint, buyer, price);
+      expect.fail("Should have rejected selling a bridged-out NFT");
+    } catch (error) {
+      expect(error.message).to.include("NftLocked");
+    }
+    await client.cancelDelegateListing(mint, authority.payer);
+    expect((await sellerAccount(mint)).isFrozen).to.be.false;
+  });
+});
@@ -0,0 +1,291 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  LAMPORTS_PER_SOL,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from '@solana/web3.js';
+
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createMint,
+  getAssociatedTokenAddress,
+  getAccount,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import * as crypto from 'crypto';
+import { setupProgram, ensureInitialized, pda, crossChainTransferAccounts } from './helpers';
+
+describe('Dutch auctions', () => {
+  const { provider, program, authority, client } = setupProgram();
+
+  const destinationChainId = 1; // Ethereum
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+  const cpiAllowlistPda = pda([Buffer.from("cpi_allowlist")]);
+  const destinationChainConfigPda = pda([
+    Buffer.from("chain_config"),
+    new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8),
+  ]);
+  const metadataPda = (mint: PublicKey) => pda([Buffer.from("nft_metadata"), mint.toBytes()]);
+  const collectionConfigPda = (collectionMint: PublicKey) =>
+    pda([Buffer.from("collection_config"), collectionMint.toBytes()]);
+
+  const price = new anchor.BN(LAMPORTS_PER_SOL);
+  const fixedPrice = { startPrice: price, floorPrice: price, decayPerSecond: new anchor.BN(0) };
+  let buyer: Keypair;
+
+  const createCollection = async () => {
+    const collectionMint = await createMint(provider.connection, authority.payer, authority.publicKey, null, 0);
+    await program.methods
+      .createCollectionConfig(authority.publicKey)
+      .accounts({
+        programState: programStatePda,
+        collectionConfig: collectionConfigPda(collectionMint),
+        collectionMint,
+        symbolRegistry: pda([Buffer.from("symbol_registry")]),
+        collectionMetadata: null,
+        symbolReservation: null,
+        signer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    return collectionMint;
+  };
+
+  const mintNft = async (collectionMint: PublicKey | null = null) => {
+    const mint = Keypair.generate();
+    await program.methods
+      .mintNft("https://example.com/auction.json", "Auction NFT", "DUTCH", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        collectionConfig: collectionMint ? collectionConfigPda(collectionMint) : null,
+        mint: mint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, authority.publicKey),
+        nftMetadata: metadataPda(mint.publicKey),
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+    return mint.publicKey;
+  };
+
+  const transferOut = async (mint: PublicKey) => {
+    const nonce = new anchor.BN(Date.now());
+    return program.methods
+      .crossChainTransfer(new anchor.BN(destinationChainId), crypto.randomBytes(20), nonce)
+      .accounts(
+        crossChainTransferAccounts(
+          { mint, tokenAccount: await getAssociatedTokenAddress(mint, authority.publicKey), owner: authority.publicKey, destinationChainId, nonce }
+        )
+      )
+      .rpc();
+  };
+
+  const balance = (address: PublicKey) => provider.connection.getBalance(address, 'confirmed');
+
+  // What the seller gets back besides the proceeds: the auction and escrow rent
+  const listingRent = async (mint: PublicKey) => {
+    const accounts = await provider.connection.getMultipleAccountsInfo([
+      client.findDutchAuctionAddress(mint),
+      client.findAuctionEscrowAddress(mint),
+    ]);
+    return accounts.reduce((total, account) => total + account!.lamports, 0);
+  };
+
+  before(async () => {
+    await ensureInitialized();
+
+    try {
+      await program.account.chainConfig.fetch(destinationChainConfigPda);
+    } catch (error) {
+      const auditLog = await program.account.auditLog.fetch(auditLogPda);
+      await program.methods
+        .registerChain(new anchor.BN(destinationChainId))
+        .accounts({
+          programState: programStatePda,
+          chainConfig: destinationChainConfigPda,
+          cpiAllowlist: cpiAllowlistPda,
+          auditLog: auditLogPda,
+          auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .rpc();
+    }
+
+    buyer = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(buyer.publicKey, 20 * LAMPORTS_PER_SOL),
+      'confirmed'
+    );
+  });
+
+  it('Splits the sale between the royalty creators and the seller', async () => {
+    const creators = [Keypair.generate().publicKey, Keypair.generate().publicKey];
+    const cases: { name: string; collection: () => Promise<PublicKey | null>; payouts: number[] }[] = [
+      {
+        name: "two creators",
+        collection: async () => {
+          const collectionMint = await createCollection();
+          await client.setCollectionRoyalty(collectionMint, 500, [
+            { address: creators[0], share: 70 },
+            { address: creators[1], share: 30 },
+          ]);
+          return collectionMint;
+        },
+        payouts: [35_000_000, 15_000_000],
+      },
+      {
+        name: "waived royalty",
+        collection: async () => {
+          const collectionMint = await createCollection();
+          await client.setCollectionRoyalty(collectionMint, 0, []);
+          return collectionMint;
+        },
+        payouts: [0, 0],
+      },
+      { name: "royalty never set", collection: createCollection, payouts: [0, 0] },
+      { name: "no collection", collection: async () => null, payouts: [0, 0] },
+    ];
+
+    for (const { name, collection, payouts } of cases) {
+      const mint = await mintNft(await collection());
+      await client.createDutchAuction(mint, authority.payer, fixedPrice);
+      expect((await getAccount(provider.connection, client.findAuctionEscrowAddress(mint))).amount).to.equal(BigInt(1));
+
+      const rent = await listingRent(mint);
+      const before = await Promise.all([authority.publicKey, ...creators].map(balance));
+      await client.buyDutchAuction(mint, buyer, price);
+      const after = await Promise.all([authority.publicKey, ...creators].map(balance));
+
+      const royalty = payouts.reduce((total, cut) => total + cut, 0);
+      expect(after.map((lamports, i) => lamports - before[i]), name).to.deep.equal([
+        LAMPORTS_PER_SOL - royalty + rent,
+        ...payouts,
+      ]);
+      const buyerAccount = await getAccount(
+        provider.connection,
+        await getAssociatedTokenAddress(mint, buyer.publicKey)
+      );
+      expect(buyerAccount.amount, name).to.equal(BigInt(1));
+      expect((await program.account.nftMetadata.fetch(metadataPda(mint))).currentOwner.toString(), name)
+        .to.equal(buyer.publicKey.toString());
+      expect(await provider.connection.getAccountInfo(client.findDutchAuctionAddress(mint)), name).to.be.null;
+      expect(await provider.connection.getAccountInfo(client.findAuctionEscrowAddress(mint)), name).to.be.null;
+    }
+  });
+
+  it('Rejects a sale that leaves out the royalty creators', async () => {
+    const collectionMint = await createCollection();
+    await client.setCollectionRoyalty(collectionMint, 1_000, [{ address: Keypair.generate().publicKey, share: 100 }]);
+    const mint = await mintNft(collectionMint);
+    await client.createDutchAuction(mint, authority.payer, fixedPrice);
+
+    try {
+      await program.methods
+        .buyDutchAuction(price)
+        .accounts({
+          programState: programStatePda,
+          nftMetadata: metadataPda(mint),
+          collectionRoyalty: client.findCollectionRoyaltyAddress(collectionMint),
+          dutchAuction: client.findDutchAuctionAddress(mint),
+          mint,
+          auctionEscrow: client.findAuctionEscrowAddress(mint),
+          buyerTokenAccount: await getAssociatedTokenAddress(mint, buyer.publicKey),
+          seller: authority.publicKey,
+          buyer: buyer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer])
+        .rpc();
+      expect.fail("Should have required the creator's account");
+    } catch (error) {
+      expect(error.message).to.include("InvalidRoyalty");
+    }
+
+    await client.cancelDutchAuction(mint, authority.payer);
+    const sellerAccount = await getAccount(
+      provider.connection,
+      await getAssociatedTokenAddress(mint, authority.publicKey)
+    );
+    expect(sellerAccount.amount).to.equal(BigInt(1));
+    expect(await client.getDutchAuction(mint)).to.be.null;
+  });
+
+  it('Charges the decayed price, down to the floor, within the buyer\'s maximum', async () => {
+    const mint = await mintNft();
+    const startPrice = new anchor.BN(2 * LAMPORTS_PER_SOL);
+    await client.createDutchAuction(mint, authority.payer, {
+      startPrice,
+      floorPrice: price,
+      decayPerSecond: new anchor.BN(LAMPORTS_PER_SOL / 1_000),
+    });
+
+    try {
+      await client.buyDutchAuction(mint, buyer, price.subn(1));
+      expect.fail("Should have rejected a maximum below the floor");
+    } catch (error) {
+      expect(error.message).to.include("AuctionPriceTooHigh");
+    }
+
+    await new Promise((resolve) => setTimeout(resolve, 2_000));
+    const auction = await client.getDutchAuction(mint);
+    expect(BigInt(auction!.currentPrice) < BigInt(startPrice.toString())).to.be.true;
+
+    const rent = await listingRent(mint);
+    const before = await balance(authority.publicKey);
+    await client.buyDutchAuction(mint, buyer, startPrice);
+    const paid = (await balance(authority.publicKey)) - before - rent;
+    expect(paid).to.be.below(startPrice.toNumber());
+    expect(paid).to.be.at.least(price.toNumber());
+  });
+
+  it('Rejects bad terms, early buyers and bridged-out NFTs', async () => {
+    const mint = await mintNft();
+    try {
+      await client.createDutchAuction(mint, authority.payer, {
+        startPrice: price,
+        floorPrice: price.muln(2),
+        decayPerSecond: new anchor.BN(1),
+      });
+      expect.fail("Should have rejected a floor above the start price");
+    } catch (error) {
+      expect(error.message).to.include("InvalidAuctionTerms");
+    }
+
+    await client.createDutchAuction(mint, authority.payer, {
+      ...fixedPrice,
+      startTime: Math.floor(Date.now() / 1000) + 3_600,
+    });
+    try {
+      await client.buyDutchAuction(mint, buyer, price);
+      expect.fail("Should have rejected a buyer before the start");
+    } catch (error) {
+      expect(error.message).to.include("AuctionNotStarted");
+    }
+    await client.cancelDutchAuction(mint, authority.payer);
+
+    await transferOut(mint);
+    try {
+      await client.createDutchAuction(mint, authority.payer, fixedPrice);
+      expect.fail("Should have rejected listing a bridged-out NFT");
+    } catch (error) {
+      expect(error.message).to.include("NftLocked");
+    }
+  });
+});
@@ -0,0 +1,46 @@
+import * as anchor from '@coral-xyz/anchor';
+import { expect } from 'chai';
+import { ReplayedEvent, eventsToCsv } from '../client/src/event-replay';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('event replay', () => {
+  const { provider, program, authority, client } = setupProgram();
+
+  before(async () => {
+    await ensureInitialized();
+  });
+
+  it('Replays events emitted since a signature, oldest first', async () => {
+    const [latest] = await provider.connection.getSignaturesForAddress(program.programId, { limit: 1 }, 'confirmed');
+
+    const first = await client.setPaused(false);
+    const second = await client.setPaused(false);
+
+    const events: ReplayedEvent[] = [];
+    for await (const event of client.replayEvents({ until: latest.signature, batchSize: 1 })) {
+      events.push(event);
+    }
+
+    expect(events.map((event) => [event.signature, event.name])).to.deep.equal([
+      [first, 'PausedEvent'],
+      [second, 'PausedEvent'],
+    ]);
+    expect(BigInt(events[1].sequence!)).to.equal(BigInt(events[0].sequence!) + BigInt(1));
+    expect((events[0].data as { authority: string }).authority).to.equal(authority.publicKey.toBase58());
+  });
+
+  it('Quotes CSV fields that need it', () => {
+    const csv = eventsToCsv([{
+      signature: 'sig',
+      slot: 7,
+      blockTime: null,
+      name: 'PausedEvent',
+      sequence: '3',
+      data: { isPaused: false, note: 'a "quoted", value' },
+    }]);
+    expect(csv).to.equal(
+      'signature,slot,block_time,name,sequence,data\n'
+      + 'sig,7,,PausedEvent,3,"{""isPaused"":false,""note"":""a \\""quoted\\"", value""}"\n'
+    );
+  });
+});
@@ -0,0 +1,81 @@
+import * as anchor from '@coral-xyz/anchor';
+import { PublicKey, Keypair, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/web3.js';
+
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from '@solana/spl-token';
+
+import { expect } from 'chai';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('metadata expansion', () => {
+  const { provider, program, authority } = setupProgram();
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+
+  const longUri = (length: number) => {
+    const prefix = "https://gateway.example.com/ipfs/bafy?token=";
+    return prefix + "a".repeat(length - prefix.length);
+  };
+
+  const expand = (mint: PublicKey, tokenAccount: PublicKey, metadataUri: string) =>
+    program.methods
+      .expandMetadata(metadataUri)
+      .accounts({
+        programState: programStatePda,
+        nftMetadata: pda([Buffer.from("nft_metadata"), mint.toBytes()]),
+        mint,
+        tokenAccount,
+        owner: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+  before(async () => {
+    await ensureInitialized();
+  });
+
+  it('Grows the account to fit a long URI and never shrinks it', async () => {
+    const mint = Keypair.generate();
+    const tokenAccount = await getAssociatedTokenAddress(mint.publicKey, authority.publicKey);
+    const nftMetadataPda = pda([Buffer.from("nft_metadata"), mint.publicKey.toBytes()]);
+
+    await program.methods
+      .mintNft("https://example.com/short.json", "Expandable NFT", "EXP", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        collectionConfig: null,
+        mint: mint.publicKey,
+        tokenAccount,
+        nftMetadata: nftMetadataPda,
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+    const initialSize = (await provider.connection.getAccountInfo(nftMetadataPda)).data.length;
+
+    await expand(mint.publicKey, tokenAccount, longUri(450));
+    const expanded = await provider.connection.getAccountInfo(nftMetadataPda);
+    expect(expanded.data.length).to.equal(initialSize + 250);
+    expect(expanded.lamports).to.equal(
+      await provider.connection.getMinimumBalanceForRentExemption(expanded.data.length)
+    );
+    expect((await program.account.nftMetadata.fetch(nftMetadataPda)).metadataUri).to.equal(longUri(450));
+
+    await expand(mint.publicKey, tokenAccount, "https://example.com/short-again.json");
+    expect((await provider.connection.getAccountInfo(nftMetadataPda)).data.length).to.equal(initialSize + 250);
+    expect((await program.account.nftMetadata.fetch(nftMetadataPda)).metadataUri)
+      .to.equal("https://example.com/short-again.json");
+
+    try {
+      await expand(mint.publicKey, tokenAccount, longUri(501));
+      expect.fail("Should have rejected a URI over 500 bytes");
+    } catch (error) {
+      expect(error.message).to.include("InvalidMetadataUri");
+    }
+  });
+});
@@ -0,0 +1,71 @@
+import * as anchor from '@coral-xyz/anchor';
+import { PublicKey, Keypair, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/web3.js';
+
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from '@solana/spl-token';
+
+import { expect } from 'chai';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('fallback metadata URI', () => {
+  const { provider, program, authority } = setupProgram();
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+
+  const setFallback = (mint: PublicKey, tokenAccount: PublicKey, fallbackUri: string | null) =>
+    program.methods
+      .setFallbackUri(fallbackUri)
+      .accounts({
+        programState: programStatePda,
+        nftMetadata: pda([Buffer.from("nft_metadata"), mint.toBytes()]),
+        mint,
+        tokenAccount,
+        owner: authority.publicKey,
+      })
+      .rpc();
+
+  before(async () => {
+    await ensureInitialized();
+  });
+
+  it('Sets, clears and bounds the fallback URI', async () => {
+    const mint = Keypair.generate();
+    const tokenAccount = await getAssociatedTokenAddress(mint.publicKey, authority.publicKey);
+    const nftMetadataPda = pda([Buffer.from("nft_metadata"), mint.publicKey.toBytes()]);
+
+    await program.methods
+      .mintNft("https://example.com/primary.json", "Mirrored NFT", "MIR", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        collectionConfig: null,
+        mint: mint.publicKey,
+        tokenAccount,
+        nftMetadata: nftMetadataPda,
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+    expect((await program.account.nftMetadata.fetch(nftMetadataPda)).fallbackUri).to.be.null;
+
+    await setFallback(mint.publicKey, tokenAccount, "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi");
+    expect((await program.account.nftMetadata.fetch(nftMetadataPda)).fallbackUri)
+      .to.equal("ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi");
+
+    for (const fallbackUri of ["", "ipfs://" + "a".repeat(200)]) {
+      try {
+        await setFallback(mint.publicKey, tokenAccount, fallbackUri);
+        expect.fail("Should have rejected the fallback URI");
+      } catch (error) {
+        expect(error.message).to.include("InvalidMetadataUri");
+      }
+    }
+
+    await setFallback(mint.publicKey, tokenAccount, null);
+    expect((await program.account.nftMetadata.fetch(nftMetadataPda)).fallbackUri).to.be.null;
+  });
+});
@@ -0,0 +1,299 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  Transaction,
+} from '@solana/web3.js';
+
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddress,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import * as crypto from 'crypto';
+import { setupProgram, ensureInitialized, pda, crossChainTransferAccounts, SOLANA_CHAIN_ID } from './helpers';
+
+describe('bridge fees', () => {
+  const { provider, program, authority } = setupProgram();
+
+  const destinationChainId = 1; // Ethereum
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const crossChainConfigPda = pda([Buffer.from("cross_chain_config")]);
+  const blocklistPda = pda([Buffer.from("blocklist")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+  const cpiAllowlistPda = pda([Buffer.from("cpi_allowlist")]);
+  const destinationChainConfigPda = pda([
+    Buffer.from("chain_config"),
+    new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8),
+  ]);
+
+  before(async () => {
+    await ensureInitialized();
+
+    // Register the destination chain if an earlier test hasn't already
+    try {
+      await program.account.chainConfig.fetch(destinationChainConfigPda);
+    } catch (error) {
+      const auditLog = await program.account.auditLog.fetch(auditLogPda);
+      await program.methods
+        .registerChain(new anchor.BN(destinationChainId))
+        .accounts({
+          programState: programStatePda,
+          chainConfig: destinationChainConfigPda,
+          cpiAllowlist: cpiAllowlistPda,
+          auditLog: auditLogPda,
+          auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .rpc();
+    }
+  });
+
+  const mintNft = async () => {
+    const mint = Keypair.generate();
+    const tokenAccount = await getAssociatedTokenAddress(mint.publicKey, authority.publicKey);
+    const nftMetadataPda = pda([Buffer.from("nft_metadata"), mint.publicKey.toBytes()]);
+
+    await program.methods
+      .mintNft("https://example.com/fee.json", "Fee NFT", "FEE", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: blocklistPda,
+        collectionConfig: null,
+        mint: mint.publicKey,
+        tokenAccount: tokenAccount,
+        nftMetadata: nftMetadataPda,
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+
+    return { mint: mint.publicKey, tokenAccount, nftMetadataPda };
+  };
+
+  const transferOut = async (
+    nft: Awaited<ReturnType<typeof mintNft>>,
+    nonce: anchor.BN,
+    feeTreasury: PublicKey | null = null
+  ) => {
+    return program.methods
+      .crossChainTransfer(new anchor.BN(destinationChainId), crypto.randomBytes(20), nonce)
+      .accounts(
+        crossChainTransferAccounts(
+          { mint: nft.mint, tokenAccount: nft.tokenAccount, owner: authority.publicKey, destinationChainId, nonce },
+          { feeTreasury }
+        )
+      )
+      .rpc();
+  };
+
+  const feeTreasuryPda = pda([Buffer.from("fee_treasury")]);
+  const feeReservePda = pda([Buffer.from("fee_reserve")]);
+
+  // Lamport fees transfers may still claim back
+  const reservedLamports = async () => {
+    const feeReserve = await program.account.feeReserve.fetchNullable(feeReservePda);
+    const entry = feeReserve?.entries.find((entry) => entry.mint === null);
+    return entry ? entry.outstanding.toNumber() : 0;
+  };
+
+  const nextNonce = async () => {
+    const config = await program.account.crossChainConfig.fetch(crossChainConfigPda);
+    return anchor.BN.max(config.nonceCounter.addn(1), new anchor.BN(Date.now()));
+  };
+
+  const auditAccounts = async () => {
+    const auditLog = await program.account.auditLog.fetch(auditLogPda);
+    return {
+      programState: programStatePda,
+      cpiAllowlist: cpiAllowlistPda,
+      auditLog: auditLogPda,
+      auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+      authority: authority.publicKey,
+      systemProgram: SystemProgram.programId,
+      instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+    };
+  };
+
+  const setLamportFee = async (flatFee: number, gasBudget: number) => {
+    await program.methods
+      .setChainFee(new anchor.BN(destinationChainId), {
+        mint: null,
+        flatFee: new anchor.BN(flatFee),
+        gasBudget: new anchor.BN(gasBudget),
+      })
+      .accounts({
+        ...(await auditAccounts()),
+        chainConfig: destinationChainConfigPda,
+        feeTreasury: feeTreasuryPda,
+        feeMint: null,
+        treasuryFeeAccount: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+  };
+
+  after(async () => {
+    await setLamportFee(0, 0);
+  });
+
+  it('Charges the lamport fee into the fee treasury', async () => {
+    await setLamportFee(10_000, 40_000);
+
+    try {
+      await transferOut(await mintNft(), await nextNonce());
+      expect.fail("Should have required the fee treasury");
+    } catch (error) {
+      expect(error.message).to.include("MissingFeeAccount");
+    }
+
+    const balance = await provider.connection.getBalance(feeTreasuryPda);
+    await transferOut(await mintNft(), await nextNonce(), feeTreasuryPda);
+    expect(await provider.connection.getBalance(feeTreasuryPda)).to.equal(balance + 50_000);
+  });
+
+  it('Refunds the whole fee once a transfer is reverted', async () => {
+    const nft = await mintNft();
+    const nonce = await nextNonce();
+    const transferRecord = pda([
+      Buffer.from("cross_chain_transfer"),
+      nft.mint.toBytes(),
+      nonce.toArrayLike(Buffer, "le", 8),
+    ]);
+    const reservedBefore = await reservedLamports();
+    await transferOut(nft, nonce, feeTreasuryPda);
+    expect(await reservedLamports()).to.equal(reservedBefore + 50_000);
+
+    const claim = async () =>
+      program.methods
+        .claimFeeRefund(nft.mint, nonce)
+        .accounts({
+          programState: programStatePda,
+          transferRecord,
+          feeTreasury: feeTreasuryPda,
+          feeReserve: feeReservePda,
+          feePayer: authority.publicKey,
+          treasuryFeeAccount: null,
+          payerFeeAccount: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    try {
+      await claim();
+      expect.fail("Should not refund a pending transfer");
+    } catch (error) {
+      expect(error.message).to.include("NoFeeRefund");
+    }
+
+    const registryPda = pda([Buffer.from("pending_transfers")]);
+    const before = await program.account.pendingTransfers.fetch(registryPda);
+    const auditLog = await program.account.auditLog.fetch(auditLogPda);
+    await program.methods
+      .revertCrossChainTransfer(nft.mint, nonce)
+      .accounts({
+        programState: programStatePda,
+        transferRecord,
+        nftMetadata: nft.nftMetadataPda,
+        collectionLedger: pda([
+          Buffer.from("collection_ledger"),
+          new anchor.BN(SOLANA_CHAIN_ID).toArrayLike(Buffer, "le", 8),
+        ]),
+        valueEscrow: null,
+        ownerValueAccount: null,
+        originalOwner: authority.publicKey,
+        stakeRecord: pda([Buffer.from("stake_record"), nft.mint.toBytes()]),
+        pendingTransfers: pda([Buffer.from("pending_transfers")]),
+        cpiAllowlist: cpiAllowlistPda,
+        auditLog: auditLogPda,
+        auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+
+    // The revert is recorded in the audit log
+    const entry = await program.account.auditEntry.fetch(
+      pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)])
+    );
+    expect(entry.action).to.deep.equal({ revertCrossChainTransfer: {} });
+
+    // The revert settles the transfer in the backlog registry
+    const after = await program.account.pendingTransfers.fetch(registryPda);
+    expect(after.pending.toNumber()).to.equal(before.pending.toNumber() - 1);
+    expect(after.totalReverted.toNumber()).to.equal(before.totalReverted.toNumber() + 1);
+    const latest = after.recent[after.recent.length - 1];
+    expect(latest.mint.toString()).to.equal(nft.mint.toString());
+    expect(latest.status).to.equal(2);
+
+    const balance = await provider.connection.getBalance(feeTreasuryPda);
+    await claim();
+    expect(await provider.connection.getBalance(feeTreasuryPda)).to.equal(balance - 50_000);
+    expect((await program.account.crossChainTransfer.fetch(transferRecord)).feeRefunded).to.be.true;
+    expect(await reservedLamports()).to.equal(reservedBefore);
+
+    try {
+      await claim();
+      expect.fail("Should refund only once");
+    } catch (error) {
+      expect(error.message).to.include("NoFeeRefund");
+    }
+  });
+
+  it('Withdraws collected lamports but keeps the treasury rent exempt and refundable fees in it', async () => {
+    // A pending transfer's fee can still be refunded
+    await transferOut(await mintNft(), await nextNonce(), feeTreasuryPda);
+    const refundable = await reservedLamports();
+    expect(refundable).to.be.at.least(50_000);
+
+    const recipient = Keypair.generate().publicKey;
+    const balance = await provider.connection.getBalance(feeTreasuryPda);
+    const reserve = await provider.connection.getMinimumBalanceForRentExemption(0);
+
+    // Fund the recipient so a small withdrawal doesn't leave it below rent exemption
+    await provider.sendAndConfirm(new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: authority.publicKey, toPubkey: recipient, lamports: reserve })
+    ));
+    const withdraw = async (amount: number) =>
+      program.methods
+        .withdrawFees(null, new anchor.BN(amount))
+        .accounts({
+          ...(await auditAccounts()),
+          feeTreasury: feeTreasuryPda,
+          feeReserve: feeReservePda,
+          treasuryFeeAccount: null,
+          recipient,
+          recipientFeeAccount: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+    try {
+      await withdraw(balance - reserve - refundable + 1);
+      expect.fail("Should have kept the rent-exempt reserve and refundable fees");
+    } catch (error) {
+      expect(error.message).to.include("InsufficientFeeBalance");
+    }
+
+    await withdraw(balance - reserve - refundable);
+    expect(await provider.connection.getBalance(recipient)).to.equal(balance - refundable);
+    expect(await provider.connection.getBalance(feeTreasuryPda)).to.equal(reserve + refundable);
+  });
+});
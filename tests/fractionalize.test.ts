@@ -0,0 +1,180 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  LAMPORTS_PER_SOL,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from '@solana/web3.js';
+
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddress,
+  getAccount,
+  getMint,
+  getOrCreateAssociatedTokenAccount,
+  transfer,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import * as crypto from 'crypto';
+import { setupProgram, ensureInitialized, pda, crossChainTransferAccounts } from './helpers';
+
+describe('fractionalization', () => {
+  const { provider, program, authority, client } = setupProgram();
+
+  const destinationChainId = 1; // Ethereum
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+  const cpiAllowlistPda = pda([Buffer.from("cpi_allowlist")]);
+  const destinationChainConfigPda = pda([
+    Buffer.from("chain_config"),
+    new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8),
+  ]);
+  const metadataPda = (mint: PublicKey) => pda([Buffer.from("nft_metadata"), mint.toBytes()]);
+
+  let buyer: Keypair;
+
+  const mintNft = async () => {
+    const mint = Keypair.generate();
+    await program.methods
+      .mintNft("https://example.com/vault.json", "Vaulted NFT", "FRAC", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        collectionConfig: null,
+        mint: mint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, authority.publicKey),
+        nftMetadata: metadataPda(mint.publicKey),
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+    return mint.publicKey;
+  };
+
+  const transferOut = async (mint: PublicKey) => {
+    const nonce = new anchor.BN(Date.now());
+    return program.methods
+      .crossChainTransfer(new anchor.BN(destinationChainId), crypto.randomBytes(20), nonce)
+      .accounts(
+        crossChainTransferAccounts(
+          { mint, tokenAccount: await getAssociatedTokenAddress(mint, authority.publicKey), owner: authority.publicKey, destinationChainId, nonce }
+        )
+      )
+      .rpc();
+  };
+
+  const sendFractions = async (fractionMint: PublicKey, to: PublicKey, amount: number) => {
+    const destination = await getOrCreateAssociatedTokenAccount(provider.connection, authority.payer, fractionMint, to);
+    await transfer(
+      provider.connection,
+      authority.payer,
+      await getAssociatedTokenAddress(fractionMint, authority.publicKey),
+      destination.address,
+      authority.payer,
+      amount
+    );
+  };
+
+  before(async () => {
+    await ensureInitialized();
+
+    try {
+      await program.account.chainConfig.fetch(destinationChainConfigPda);
+    } catch (error) {
+      const auditLog = await program.account.auditLog.fetch(auditLogPda);
+      await program.methods
+        .registerChain(new anchor.BN(destinationChainId))
+        .accounts({
+          programState: programStatePda,
+          chainConfig: destinationChainConfigPda,
+          cpiAllowlist: cpiAllowlistPda,
+          auditLog: auditLogPda,
+          auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .rpc();
+    }
+
+    buyer = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(buyer.publicKey, LAMPORTS_PER_SOL),
+      'confirmed'
+    );
+  });
+
+  it('Issues a fixed supply of fractions and blocks bridging the vaulted NFT', async () => {
+    const mint = await mintNft();
+    await client.fractionalizeNft(mint, authority.payer, new anchor.BN(1_000));
+
+    const fractionMint = client.findFractionMintAddress(mint);
+    const fractions = await getMint(provider.connection, fractionMint);
+    expect(fractions.supply).to.equal(BigInt(1_000));
+    expect(fractions.decimals).to.equal(0);
+    expect(fractions.mintAuthority).to.be.null;
+    const holding = await getAccount(provider.connection, await getAssociatedTokenAddress(fractionMint, authority.publicKey));
+    expect(holding.amount).to.equal(BigInt(1_000));
+    expect((await getAccount(provider.connection, client.findFractionEscrowAddress(mint))).amount).to.equal(BigInt(1));
+
+    const vault = await client.getFractionVault(mint);
+    expect(vault!.depositor).to.equal(authority.publicKey.toString());
+    expect(vault!.totalSupply).to.equal('1000');
+
+    try {
+      await transferOut(mint);
+      expect.fail("Should have blocked bridging a fractionalized NFT");
+    } catch (error) {
+      expect(error.message).to.include("InsufficientTokens");
+    }
+  });
+
+  it('Releases the NFT only to a holder of every fraction', async () => {
+    const mint = await mintNft();
+    await client.fractionalizeNft(mint, authority.payer, new anchor.BN(100));
+    const fractionMint = client.findFractionMintAddress(mint);
+
+    await sendFractions(fractionMint, buyer.publicKey, 40);
+    try {
+      await client.redeemFractions(mint, authority.payer);
+      expect.fail("Should have required every fraction");
+    } catch (error) {
+      expect(error.message).to.include("IncompleteFractions");
+    }
+
+    // The buyer buys out the rest and redeems
+    await sendFractions(fractionMint, buyer.publicKey, 60);
+    const depositorBefore = await provider.connection.getBalance(authority.publicKey, 'confirmed');
+    await client.redeemFractions(mint, buyer);
+
+    const nft = await getAccount(provider.connection, await getAssociatedTokenAddress(mint, buyer.publicKey));
+    expect(nft.amount).to.equal(BigInt(1));
+    expect((await getMint(provider.connection, fractionMint)).supply).to.equal(BigInt(0));
+    expect((await program.account.nftMetadata.fetch(metadataPda(mint))).currentOwner.toString())
+      .to.equal(buyer.publicKey.toString());
+    expect(await client.getFractionVault(mint)).to.be.null;
+    expect(await provider.connection.getAccountInfo(client.findFractionEscrowAddress(mint))).to.be.null;
+    // The vault and escrow rent go back to the depositor
+    expect(await provider.connection.getBalance(authority.publicKey, 'confirmed')).to.be.above(depositorBefore);
+  });
+
+  it('Rejects an empty fraction supply', async () => {
+    const mint = await mintNft();
+    try {
+      await client.fractionalizeNft(mint, authority.payer, new anchor.BN(0));
+      expect.fail("Should have rejected zero fractions");
+    } catch (error) {
+      expect(error.message).to.include("InvalidFractionSupply");
+    }
+  });
+});
@@ -0,0 +1,107 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+// @ts-ignore - Type will be available after build
+import { UniversalNft } from '../target/types/universal_nft';
+import { PublicKey, Keypair, SystemProgram } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID } from '@solana/spl-token';
+import { UniversalNftClient } from '../client/src/client';
+
+/** Chain ID `ensureInitialized` configures the localnet deployment with */
+export const SOLANA_CHAIN_ID = 902;
+
+/** Provider, program, authority wallet and SDK client every test file starts from */
+export function setupProgram() {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.UniversalNft as Program<UniversalNft>;
+  const authority = provider.wallet as anchor.Wallet;
+  const client = new UniversalNftClient(provider.connection, authority, program.programId.toBase58());
+  return { provider, program, authority, client };
+}
+
+/** Derive a PDA of the Universal NFT program, or of `programId` */
+export function pda(seeds: (Buffer | Uint8Array)[], programId?: PublicKey): PublicKey {
+  const owner = programId ?? (anchor.workspace.UniversalNft as Program<UniversalNft>).programId;
+  return PublicKey.findProgramAddressSync(seeds, owner)[0];
+}
+
+/**
+ * Initialize the program unless an earlier test file already has. All files
+ * share one validator, so the first to run creates the singleton accounts
+ * with throwaway gateway and TSS keys.
+ */
+export async function ensureInitialized(): Promise<void> {
+  const { program, authority } = setupProgram();
+  const programState = pda([Buffer.from("program_state")]);
+  if (await program.account.programState.fetchNullable(programState)) {
+    return;
+  }
+
+  await program.methods
+    .initialize(Keypair.generate().publicKey, Keypair.generate().publicKey, new anchor.BN(SOLANA_CHAIN_ID))
+    .accounts({
+      programState,
+      crossChainConfig: pda([Buffer.from("cross_chain_config")]),
+      blocklist: pda([Buffer.from("blocklist")]),
+      optOutRegistry: pda([Buffer.from("opt_out_registry")]),
+      auditLog: pda([Buffer.from("audit_log")]),
+      cpiAllowlist: pda([Buffer.from("cpi_allowlist")]),
+      authority: authority.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+}
+
+/**
+ * Accounts for `cross_chain_transfer` of a Solana-native NFT outside any
+ * collection, with every optional account left out. `overrides` replaces
+ * any of them, e.g. `{ feeTreasury }` to pay a lamport fee.
+ */
+export function crossChainTransferAccounts(
+  params: {
+    mint: PublicKey;
+    tokenAccount: PublicKey;
+    owner: PublicKey;
+    destinationChainId: number;
+    nonce: anchor.BN;
+    payer?: PublicKey;
+  },
+  overrides: Record<string, PublicKey | null> = {}
+) {
+  const { mint, tokenAccount, owner, destinationChainId, nonce } = params;
+  return {
+    programState: pda([Buffer.from("program_state")]),
+    crossChainConfig: pda([Buffer.from("cross_chain_config")]),
+    blocklist: pda([Buffer.from("blocklist")]),
+    optOutRegistry: pda([Buffer.from("opt_out_registry")]),
+    destinationChainConfig: pda([
+      Buffer.from("chain_config"),
+      new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8),
+    ]),
+    nftMetadata: pda([Buffer.from("nft_metadata"), mint.toBytes()]),
+    collectionConfig: null,
+    collectionLedger: pda([
+      Buffer.from("collection_ledger"),
+      new anchor.BN(SOLANA_CHAIN_ID).toArrayLike(Buffer, "le", 8),
+    ]),
+    transferRecord: pda([Buffer.from("cross_chain_transfer"), mint.toBytes(), nonce.toArrayLike(Buffer, "le", 8)]),
+    mint,
+    tokenAccount,
+    owner,
+    ownerTransferIndex: pda([Buffer.from("owner_transfer_index"), owner.toBytes()]),
+    pendingTransfers: pda([Buffer.from("pending_transfers")]),
+    credential: null,
+    gateTokenAccount: null,
+    stakeRecord: pda([Buffer.from("stake_record"), mint.toBytes()]),
+    localizedMetadata: null,
+    feeTreasury: null,
+    payerFeeAccount: null,
+    treasuryFeeAccount: null,
+    feeReserve: pda([Buffer.from("fee_reserve")]),
+    payer: params.payer ?? owner,
+    tokenProgram: TOKEN_PROGRAM_ID,
+    systemProgram: SystemProgram.programId,
+    ...overrides,
+  };
+}
@@ -0,0 +1,130 @@
+import * as anchor from '@coral-xyz/anchor';
+import { PublicKey, Keypair, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/web3.js';
+
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddress,
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  transfer,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('holder snapshots', () => {
+  const { provider, program, authority } = setupProgram();
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const metadataPda = (mint: PublicKey) => pda([Buffer.from("nft_metadata"), mint.toBytes()]);
+  const snapshotPda = (collectionMint: PublicKey, snapshotId: number, chunkIndex: number) =>
+    pda([
+      Buffer.from("holder_snapshot"),
+      collectionMint.toBytes(),
+      new anchor.BN(snapshotId).toArrayLike(Buffer, "le", 8),
+      new anchor.BN(chunkIndex).toArrayLike(Buffer, "le", 2),
+    ]);
+
+  let collectionMint: PublicKey;
+
+  const mintNft = async (collectionConfig: PublicKey | null) => {
+    const mint = Keypair.generate();
+    await program.methods
+      .mintNft("https://example.com/snapshot.json", "Snapshot NFT", "SNAP", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        collectionConfig,
+        mint: mint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, authority.publicKey),
+        nftMetadata: metadataPda(mint.publicKey),
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+    return mint.publicKey;
+  };
+
+  const recordSnapshot = (snapshotId: number, chunkIndex: number, holdings: [PublicKey, PublicKey][]) =>
+    program.methods
+      .recordSnapshot(new anchor.BN(snapshotId), chunkIndex)
+      .accounts({
+        programState: programStatePda,
+        collectionConfig: pda([Buffer.from("collection_config"), collectionMint.toBytes()]),
+        snapshot: snapshotPda(collectionMint, snapshotId, chunkIndex),
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(
+        holdings.flatMap(([mint, tokenAccount]) => [
+          { pubkey: tokenAccount, isWritable: false, isSigner: false },
+          { pubkey: metadataPda(mint), isWritable: false, isSigner: false },
+        ])
+      )
+      .rpc();
+
+  before(async () => {
+    await ensureInitialized();
+
+    collectionMint = await createMint(provider.connection, authority.payer, authority.publicKey, null, 0);
+    await program.methods
+      .createCollectionConfig(authority.publicKey)
+      .accounts({
+        programState: programStatePda,
+        collectionConfig: pda([Buffer.from("collection_config"), collectionMint.toBytes()]),
+        collectionMint,
+        symbolRegistry: pda([Buffer.from("symbol_registry")]),
+        collectionMetadata: null,
+        symbolReservation: null,
+        signer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  });
+
+  it('Records who holds each NFT of the collection', async () => {
+    const collectionConfig = pda([Buffer.from("collection_config"), collectionMint.toBytes()]);
+    const kept = await mintNft(collectionConfig);
+    const sold = await mintNft(collectionConfig);
+
+    // Move one NFT to another wallet with a plain token transfer
+    const buyer = Keypair.generate();
+    const buyerAccount = await getOrCreateAssociatedTokenAccount(provider.connection, authority.payer, sold, buyer.publicKey);
+    await transfer(
+      provider.connection,
+      authority.payer,
+      await getAssociatedTokenAddress(sold, authority.publicKey),
+      buyerAccount.address,
+      authority.publicKey,
+      1
+    );
+
+    await recordSnapshot(1, 0, [
+      [kept, await getAssociatedTokenAddress(kept, authority.publicKey)],
+      [sold, buyerAccount.address],
+    ]);
+
+    const snapshot = await program.account.holderSnapshot.fetch(snapshotPda(collectionMint, 1, 0));
+    expect(snapshot.collectionMint.toString()).to.equal(collectionMint.toString());
+    expect(snapshot.holders.map((entry) => [entry.mint.toString(), entry.owner.toString()])).to.deep.equal([
+      [kept.toString(), authority.publicKey.toString()],
+      [sold.toString(), buyer.publicKey.toString()],
+    ]);
+  });
+
+  it('Rejects NFTs outside the collection', async () => {
+    const outsider = await mintNft(null);
+    try {
+      await recordSnapshot(2, 0, [[outsider, await getAssociatedTokenAddress(outsider, authority.publicKey)]]);
+      expect.fail("Should have rejected an NFT from outside the collection");
+    } catch (error) {
+      expect(error.message).to.include("NotInCollection");
+    }
+  });
+});
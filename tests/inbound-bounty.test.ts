@@ -0,0 +1,210 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  Keypair,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from '@solana/web3.js';
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddress,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import * as crypto from 'crypto';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('staged inbound messages', () => {
+  const { provider, program, authority } = setupProgram();
+
+  const originChainId = 1; // Ethereum
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const crossChainConfigPda = pda([Buffer.from("cross_chain_config")]);
+  const blocklistPda = pda([Buffer.from("blocklist")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+  const cpiAllowlistPda = pda([Buffer.from("cpi_allowlist")]);
+  const originChainConfigPda = pda([
+    Buffer.from("chain_config"),
+    new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8),
+  ]);
+  const inboundNoncePda = pda([
+    Buffer.from("inbound_nonce"),
+    new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8),
+  ]);
+  const feeTreasuryPda = pda([Buffer.from("fee_treasury")]);
+  const originContract = crypto.randomBytes(20);
+
+  before(async () => {
+    await ensureInitialized();
+
+    // Register the origin chain if an earlier test hasn't already
+    try {
+      await program.account.chainConfig.fetch(originChainConfigPda);
+    } catch (error) {
+      await program.methods
+        .registerChain(new anchor.BN(originChainId))
+        .accounts({ ...(await auditAccounts()), chainConfig: originChainConfigPda })
+        .rpc();
+    }
+  });
+
+  const auditAccounts = async () => {
+    const auditLog = await program.account.auditLog.fetch(auditLogPda);
+    return {
+      programState: programStatePda,
+      cpiAllowlist: cpiAllowlistPda,
+      auditLog: auditLogPda,
+      auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+      authority: authority.publicKey,
+      systemProgram: SystemProgram.programId,
+      instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+    };
+  };
+
+  const setExecutionBounty = async (bounty: number) => {
+    await program.methods
+      .setChainExecutionBounty(new anchor.BN(originChainId), new anchor.BN(bounty))
+      .accounts({ ...(await auditAccounts()), chainConfig: originChainConfigPda })
+      .rpc();
+  };
+
+  const nextInboundNonce = async () => {
+    try {
+      const tracker = await program.account.inboundNonce.fetch(inboundNoncePda);
+      return anchor.BN.max(tracker.lastNonce.addn(1), new anchor.BN(Date.now()));
+    } catch (error) {
+      return new anchor.BN(Date.now());
+    }
+  };
+
+  const stagedInboundPda = (originTxHash: Buffer, nonce: anchor.BN) =>
+    pda([Buffer.from("staged_inbound"), originTxHash, nonce.toArrayLike(Buffer, "le", 8)]);
+
+  const stage = async (stager: Keypair) => {
+    const originTxHash = crypto.randomBytes(32);
+    const originTokenId = crypto.randomBytes(32);
+    const nonce = await nextInboundNonce();
+    await program.methods
+      .stageInboundMessage(
+        new anchor.BN(originChainId),
+        Array.from(originTxHash),
+        originContract,
+        Array.from(originTokenId),
+        "https://example.com/staged.json",
+        "Staged NFT",
+        "STG",
+        crypto.randomBytes(20),
+        authority.publicKey,
+        crypto.randomBytes(64),
+        nonce
+      )
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: crossChainConfigPda,
+        blocklist: blocklistPda,
+        cpiAllowlist: cpiAllowlistPda,
+        originChainConfig: originChainConfigPda,
+        stagedInbound: stagedInboundPda(originTxHash, nonce),
+        inboundNonce: inboundNoncePda,
+        postedVaa: null,
+        stager: stager.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .signers([stager])
+      .rpc();
+    return { originTxHash, originTokenId, nonce, stager };
+  };
+
+  const execute = async (staged: Awaited<ReturnType<typeof stage>>, executor: Keypair) => {
+    const chainIdBytes = new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8);
+    const mint = pda([Buffer.from("inbound_mint"), chainIdBytes, originContract, staged.originTokenId]);
+    await program.methods
+      .executeInboundMessage(Array.from(staged.originTxHash), staged.nonce)
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: crossChainConfigPda,
+        blocklist: blocklistPda,
+        originChainConfig: originChainConfigPda,
+        stagedInbound: stagedInboundPda(staged.originTxHash, staged.nonce),
+        stager: staged.stager.publicKey,
+        receipt: pda([Buffer.from("cross_chain_receipt"), staged.originTxHash, staged.nonce.toArrayLike(Buffer, "le", 8)]),
+        mint,
+        freezeAuthority: pda([Buffer.from("freeze_authority")]),
+        tokenAccount: await getAssociatedTokenAddress(mint, authority.publicKey),
+        nftMetadata: pda([Buffer.from("nft_metadata"), mint.toBytes()]),
+        collectionLedger: pda([Buffer.from("collection_ledger"), chainIdBytes, originContract]),
+        foreignCollection: null,
+        recipient: authority.publicKey,
+        feeTreasury: feeTreasuryPda,
+        feeReserve: pda([Buffer.from("fee_reserve")]),
+        relayerRegistration: null,
+        authority: executor.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([executor])
+      .rpc();
+    return mint;
+  };
+
+  const funded = async () => {
+    const keypair = Keypair.generate();
+    const fundTx = await provider.connection.requestAirdrop(
+      keypair.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(fundTx);
+    return keypair;
+  };
+
+  after(async () => {
+    await setExecutionBounty(0);
+  });
+
+  it('Pays the execution bounty to whoever executes a staged message', async () => {
+    const bounty = 5_000_000;
+    await setExecutionBounty(bounty);
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: authority.publicKey,
+          toPubkey: feeTreasuryPda,
+          lamports: anchor.web3.LAMPORTS_PER_SOL,
+        })
+      )
+    );
+
+    const stager = await funded();
+    const executor = await funded();
+    const staged = await stage(stager);
+
+    const stagedAccount = await program.account.stagedInbound.fetch(stagedInboundPda(staged.originTxHash, staged.nonce));
+    expect(stagedAccount.stager.toString()).to.equal(stager.publicKey.toString());
+
+    const treasuryBalance = await provider.connection.getBalance(feeTreasuryPda);
+    const mint = await execute(staged, executor);
+
+    const metadata = await program.account.nftMetadata.fetch(pda([Buffer.from("nft_metadata"), mint.toBytes()]));
+    expect(metadata.currentOwner.toString()).to.equal(authority.publicKey.toString());
+    expect(await provider.connection.getBalance(feeTreasuryPda)).to.equal(treasuryBalance - bounty);
+    expect(await provider.connection.getAccountInfo(stagedInboundPda(staged.originTxHash, staged.nonce))).to.be.null;
+  });
+
+  it('Refuses to execute the same message twice', async () => {
+    const stager = await funded();
+    const staged = await stage(stager);
+    await execute(staged, stager);
+
+    try {
+      await execute(staged, stager);
+      expect.fail("Should have refused a second execution");
+    } catch (error) {
+      expect(error.message).to.include("AccountNotInitialized");
+    }
+  });
+});
@@ -0,0 +1,71 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from '@solana/spl-token';
+
+import { expect } from 'chai';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('IPFS CID validation', () => {
+  const { provider, program, authority } = setupProgram();
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+
+  const mintNft = async (metadataUri: string) => {
+    const mint = Keypair.generate();
+    const nftMetadata = pda([Buffer.from("nft_metadata"), mint.publicKey.toBytes()]);
+    await program.methods
+      .mintNft(metadataUri, "IPFS NFT", "IPFS", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        collectionConfig: null,
+        mint: mint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, authority.publicKey),
+        nftMetadata,
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+    return program.account.nftMetadata.fetch(nftMetadata);
+  };
+
+  before(async () => {
+    await ensureInitialized();
+  });
+
+  it('Stores the multihash of CIDv0 and CIDv1 URIs', async () => {
+    const v0 = await mintNft("ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG/1.json");
+    expect(Buffer.from(v0.metadataMultihash).toString('hex')).to.equal(
+      "12209d6c2be50f706953479ab9df2ce3edca90b68053c00b3004b7f0accbe1e8eedf"
+    );
+
+    const v1 = await mintNft("ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi");
+    expect(Buffer.from(v1.metadataMultihash).subarray(0, 2).toString('hex')).to.equal("1220");
+    expect(v1.metadataMultihash.length).to.equal(34);
+
+    const https = await mintNft("https://example.com/metadata.json");
+    expect(https.metadataMultihash.length).to.equal(0);
+  });
+
+  it('Rejects malformed CIDs', async () => {
+    const malformed = [
+      "ipfs://",
+      "ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbd0", // 0 is not base58
+      "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzd", // Truncated digest
+      "ipfs://BAFYBEIGDYRZT5SFP7UDM7HU76UH7Y26NF3EFUYLQABF3OCLGTQY55FBZDI", // Unsupported multibase
+    ];
+    for (const metadataUri of malformed) {
+      try {
+        await mintNft(metadataUri);
+        expect.fail(`Should have rejected ${metadataUri}`);
+      } catch (error) {
+        expect(error.message).to.include("InvalidIpfsCid");
+      }
+    }
+  });
+});
@@ -0,0 +1,73 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from '@solana/spl-token';
+
+import { expect } from 'chai';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('localized metadata', () => {
+  const { provider, program, authority, client } = setupProgram();
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+
+  before(async () => {
+    await ensureInitialized();
+  });
+
+  it('Sets, clears and bounds the localized URIs', async () => {
+    const mint = Keypair.generate();
+    await program.methods
+      .mintNft("https://example.com/en.json", "Localized NFT", "LOC", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        collectionConfig: null,
+        mint: mint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, authority.publicKey),
+        nftMetadata: pda([Buffer.from("nft_metadata"), mint.publicKey.toBytes()]),
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+    expect(await client.getLocalizedUris(mint.publicKey)).to.deep.equal([]);
+
+    const entries = [
+      { locale: "ja-JP", uri: "https://example.com/ja.json" },
+      { locale: "pt-BR", uri: "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi" },
+    ];
+    await client.setLocalizedUris(mint.publicKey, authority.payer, entries);
+    expect(await client.getLocalizedUris(mint.publicKey)).to.deep.equal(entries);
+    expect((await client.findLocalizedMetadataToCarry(mint.publicKey))!.toString())
+      .to.equal(client.findLocalizedMetadataAddress(mint.publicKey).toString());
+
+    const uri = "https://example.com/x.json";
+    for (const invalid of [
+      [{ locale: "ja-JP", uri }, { locale: "ja-JP", uri }],
+      [{ locale: "", uri }],
+      [{ locale: "en_US", uri }],
+      ["de", "fr", "es", "it", "nl"].map((locale) => ({ locale, uri })),
+    ]) {
+      try {
+        await client.setLocalizedUris(mint.publicKey, authority.payer, invalid);
+        expect.fail("Should have rejected the locales");
+      } catch (error) {
+        expect(error.message).to.include("InvalidLocalizedUris");
+      }
+    }
+    try {
+      await client.setLocalizedUris(mint.publicKey, authority.payer, [{ locale: "fr", uri: "" }]);
+      expect.fail("Should have rejected the URI");
+    } catch (error) {
+      expect(error.message).to.include("InvalidMetadataUri");
+    }
+
+    await client.setLocalizedUris(mint.publicKey, authority.payer, []);
+    expect(await client.getLocalizedUris(mint.publicKey)).to.deep.equal([]);
+    expect(await client.findLocalizedMetadataToCarry(mint.publicKey)).to.be.null;
+  });
+});
@@ -0,0 +1,112 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from '@solana/spl-token';
+
+import { expect } from 'chai';
+import { UniversalNftClient, grindMintKeypair, mintPowBits } from '../client/src/client';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('mint guard', () => {
+  const { provider, program, authority, client } = setupProgram();
+
+  const fee = 1_000_000;
+  const difficulty = 8;
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const feeTreasury = pda([Buffer.from("fee_treasury")]);
+  const minter = Keypair.generate();
+
+  const openMint = async (mint: Keypair, withTreasury: boolean) =>
+    program.methods
+      .mintNft("https://example.com/open.json", "Open Mint", "OPEN", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        collectionConfig: null,
+        mintGuard: pda([Buffer.from("mint_guard")]),
+        feeTreasury: withTreasury ? feeTreasury : null,
+        mint: mint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, minter.publicKey),
+        nftMetadata: pda([Buffer.from("nft_metadata"), mint.publicKey.toBytes()]),
+        authority: minter.publicKey,
+        payer: minter.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint, minter])
+      .rpc();
+
+  before(async () => {
+    await ensureInitialized();
+    const fundTx = await provider.connection.requestAirdrop(minter.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(fundTx);
+    await client.setMintGuard(fee, difficulty);
+  });
+
+  after(async () => {
+    await client.setMintGuard(0, 0);
+  });
+
+  it('Requires proof of work and the fee on open mints', async () => {
+    let unworked = Keypair.generate();
+    while (mintPowBits(program.programId, minter.publicKey, unworked.publicKey) >= difficulty) {
+      unworked = Keypair.generate();
+    }
+    try {
+      await openMint(unworked, true);
+      expect.fail("Should have required proof of work");
+    } catch (error) {
+      expect(error.message).to.include("InsufficientProofOfWork");
+    }
+
+    const mint = grindMintKeypair(program.programId, minter.publicKey, difficulty);
+    try {
+      await openMint(mint, false);
+      expect.fail("Should have required the fee treasury");
+    } catch (error) {
+      expect(error.message).to.include("MissingFeeAccount");
+    }
+
+    const before = await provider.connection.getBalance(feeTreasury);
+    await openMint(mint, true);
+    expect(await provider.connection.getBalance(feeTreasury)).to.equal(before + fee);
+    expect(await client.getMintGuard()).to.include({ feeLamports: fee, powDifficulty: difficulty });
+  });
+
+  it('Exempts the program authority', async () => {
+    const mint = Keypair.generate();
+    const before = await provider.connection.getBalance(feeTreasury);
+    await program.methods
+      .mintNft("https://example.com/admin.json", "Admin Mint", "ADMIN", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        collectionConfig: null,
+        mint: mint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, authority.publicKey),
+        nftMetadata: pda([Buffer.from("nft_metadata"), mint.publicKey.toBytes()]),
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+    expect(await provider.connection.getBalance(feeTreasury)).to.equal(before);
+  });
+
+  it('Caps the fee and the difficulty', async () => {
+    for (const [feeLamports, powDifficulty] of [[100_000_001, 0], [0, 29]]) {
+      try {
+        await client.setMintGuard(feeLamports, powDifficulty);
+        expect.fail("Should have rejected the guard");
+      } catch (error) {
+        expect(error.message).to.include("InvalidMintGuard");
+      }
+    }
+  });
+});
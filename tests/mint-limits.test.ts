@@ -0,0 +1,80 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from '@solana/spl-token';
+
+import { expect } from 'chai';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('mint limits', () => {
+  const { provider, program, authority, client } = setupProgram();
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const minter = Keypair.generate();
+
+  const openMint = async () => {
+    const mint = Keypair.generate();
+    await program.methods
+      .mintNft("https://example.com/open.json", "Open Mint", "OPEN", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        collectionConfig: null,
+        mintLimits: pda([Buffer.from("mint_limits")]),
+        walletMintCounter: pda([Buffer.from("wallet_mint_counter"), minter.publicKey.toBytes()]),
+        mint: mint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, minter.publicKey),
+        nftMetadata: pda([Buffer.from("nft_metadata"), mint.publicKey.toBytes()]),
+        authority: minter.publicKey,
+        payer: minter.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint, minter])
+      .rpc();
+  };
+
+  before(async () => {
+    await ensureInitialized();
+    const fundTx = await provider.connection.requestAirdrop(minter.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(fundTx);
+  });
+
+  after(async () => {
+    await client.setMintLimits(0, 0, 0);
+  });
+
+  it('Caps each wallet per phase and overall', async () => {
+    await client.setMintLimits(3, 1, 2);
+    await openMint();
+    await openMint();
+    expect(await client.getWalletMintCount(minter.publicKey)).to.deep.equal({ totalMinted: 2, phase: 1, phaseMinted: 2 });
+    try {
+      await openMint();
+      expect.fail("Should have capped the phase");
+    } catch (error) {
+      expect(error.message).to.include("MintLimitReached");
+    }
+
+    await client.setMintLimits(3, 2, 2);
+    expect((await client.getWalletMintCount(minter.publicKey)).phaseMinted).to.equal(0);
+    await openMint();
+    try {
+      await openMint();
+      expect.fail("Should have capped the wallet overall");
+    } catch (error) {
+      expect(error.message).to.include("MintLimitReached");
+    }
+    expect(await client.getWalletMintCount(minter.publicKey)).to.deep.equal({ totalMinted: 3, phase: 2, phaseMinted: 1 });
+  });
+
+  it('Rejects a phase cap above the overall cap', async () => {
+    try {
+      await client.setMintLimits(2, 1, 3);
+      expect.fail("Should have rejected the limits");
+    } catch (error) {
+      expect(error.message).to.include("InvalidMintLimits");
+    }
+  });
+});
@@ -0,0 +1,110 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  Keypair,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from '@solana/web3.js';
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddress,
+  mintTo,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import * as crypto from 'crypto';
+import { setupProgram, ensureInitialized, pda, crossChainTransferAccounts } from './helpers';
+
+describe('inflatable mints', () => {
+  const { provider, program, authority } = setupProgram();
+
+  const destinationChainId = 1; // Ethereum
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const crossChainConfigPda = pda([Buffer.from("cross_chain_config")]);
+  const blocklistPda = pda([Buffer.from("blocklist")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+  const cpiAllowlistPda = pda([Buffer.from("cpi_allowlist")]);
+  const destinationChainConfigPda = pda([
+    Buffer.from("chain_config"),
+    new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8),
+  ]);
+
+  before(async () => {
+    await ensureInitialized();
+
+    // Register the destination chain if an earlier test hasn't already
+    try {
+      await program.account.chainConfig.fetch(destinationChainConfigPda);
+    } catch (error) {
+      const auditLog = await program.account.auditLog.fetch(auditLogPda);
+      await program.methods
+        .registerChain(new anchor.BN(destinationChainId))
+        .accounts({
+          programState: programStatePda,
+          chainConfig: destinationChainConfigPda,
+          cpiAllowlist: cpiAllowlistPda,
+          auditLog: auditLogPda,
+          auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .rpc();
+    }
+  });
+
+  const mintNft = async () => {
+    const mint = Keypair.generate();
+    const tokenAccount = await getAssociatedTokenAddress(mint.publicKey, authority.publicKey);
+    const nftMetadataPda = pda([Buffer.from("nft_metadata"), mint.publicKey.toBytes()]);
+
+    await program.methods
+      .mintNft("https://example.com/supply.json", "Supply NFT", "SUPPLY", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: blocklistPda,
+        collectionConfig: null,
+        mint: mint.publicKey,
+        tokenAccount: tokenAccount,
+        nftMetadata: nftMetadataPda,
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+
+    return { mint: mint.publicKey, tokenAccount, nftMetadataPda };
+  };
+
+  const transferOut = async (nft: Awaited<ReturnType<typeof mintNft>>, nonce: anchor.BN) => {
+    return program.methods
+      .crossChainTransfer(new anchor.BN(destinationChainId), crypto.randomBytes(20), nonce)
+      .accounts(
+        crossChainTransferAccounts(
+          { mint: nft.mint, tokenAccount: nft.tokenAccount, owner: authority.publicKey, destinationChainId, nonce }
+        )
+      )
+      .rpc();
+  };
+
+  it('Refuses to bridge an NFT whose supply was inflated', async () => {
+    const nft = await mintNft();
+    // mint_nft leaves the minter as mint authority, so a second token can be issued
+    await mintTo(provider.connection, authority.payer, nft.mint, nft.tokenAccount, authority.publicKey, 1);
+
+    const config = await program.account.crossChainConfig.fetch(crossChainConfigPda);
+    const nonce = anchor.BN.max(config.nonceCounter.addn(1), new anchor.BN(Date.now()));
+    try {
+      await transferOut(nft, nonce);
+      expect.fail("Should have rejected a mint with a supply above one");
+    } catch (error) {
+      expect(error.message).to.include("InflatableMint");
+    }
+  });
+});
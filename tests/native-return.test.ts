@@ -0,0 +1,257 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  Keypair,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from '@solana/web3.js';
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddress,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import * as crypto from 'crypto';
+import { setupProgram, ensureInitialized, pda, crossChainTransferAccounts, SOLANA_CHAIN_ID } from './helpers';
+
+describe('native returns', () => {
+  const { provider, program, authority } = setupProgram();
+
+  const destinationChainId = 1; // Ethereum
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const crossChainConfigPda = pda([Buffer.from("cross_chain_config")]);
+  const blocklistPda = pda([Buffer.from("blocklist")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+  const cpiAllowlistPda = pda([Buffer.from("cpi_allowlist")]);
+  const destinationChainConfigPda = pda([
+    Buffer.from("chain_config"),
+    new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8),
+  ]);
+
+  before(async () => {
+    await ensureInitialized();
+
+    // Register the destination chain if an earlier test hasn't already
+    try {
+      await program.account.chainConfig.fetch(destinationChainConfigPda);
+    } catch (error) {
+      const auditLog = await program.account.auditLog.fetch(auditLogPda);
+      await program.methods
+        .registerChain(new anchor.BN(destinationChainId))
+        .accounts({
+          programState: programStatePda,
+          chainConfig: destinationChainConfigPda,
+          cpiAllowlist: cpiAllowlistPda,
+          auditLog: auditLogPda,
+          auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .rpc();
+    }
+  });
+
+  const mintNft = async () => {
+    const mint = Keypair.generate();
+    const tokenAccount = await getAssociatedTokenAddress(mint.publicKey, authority.publicKey);
+    const nftMetadataPda = pda([Buffer.from("nft_metadata"), mint.publicKey.toBytes()]);
+
+    await program.methods
+      .mintNft("https://example.com/return.json", "Return NFT", "RET", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: blocklistPda,
+        collectionConfig: null,
+        mint: mint.publicKey,
+        tokenAccount: tokenAccount,
+        nftMetadata: nftMetadataPda,
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+
+    return { mint: mint.publicKey, tokenAccount, nftMetadataPda };
+  };
+
+  const transferOut = async (
+    nft: Awaited<ReturnType<typeof mintNft>>,
+    nonce: anchor.BN
+  ) => {
+    return program.methods
+      .crossChainTransfer(new anchor.BN(destinationChainId), crypto.randomBytes(20), nonce)
+      .accounts(
+        crossChainTransferAccounts(
+          { mint: nft.mint, tokenAccount: nft.tokenAccount, owner: authority.publicKey, destinationChainId, nonce }
+        )
+      )
+      .rpc();
+  };
+
+  const nextNonce = async () => {
+    const config = await program.account.crossChainConfig.fetch(crossChainConfigPda);
+    return anchor.BN.max(config.nonceCounter.addn(1), new anchor.BN(Date.now()));
+  };
+
+  const inboundNoncePda = pda([
+    Buffer.from("inbound_nonce"),
+    new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8),
+  ]);
+
+  const nextInboundNonce = async () => {
+    try {
+      const tracker = await program.account.inboundNonce.fetch(inboundNoncePda);
+      return anchor.BN.max(tracker.lastNonce.addn(1), new anchor.BN(Date.now()));
+    } catch (error) {
+      return new anchor.BN(Date.now());
+    }
+  };
+
+  const auditAccounts = async () => {
+    const auditLog = await program.account.auditLog.fetch(auditLogPda);
+    return {
+      programState: programStatePda,
+      cpiAllowlist: cpiAllowlistPda,
+      auditLog: auditLogPda,
+      auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+      authority: authority.publicKey,
+      systemProgram: SystemProgram.programId,
+      instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+    };
+  };
+
+  const setFinalityDelay = async (delaySeconds: number) => {
+    await program.methods
+      .setChainFinalityDelay(new anchor.BN(destinationChainId), delaySeconds)
+      .accounts({ ...(await auditAccounts()), chainConfig: destinationChainConfigPda })
+      .rpc();
+  };
+
+  const lockedNft = async () => {
+    const nft = await mintNft();
+    await transferOut(nft, await nextNonce());
+    return nft;
+  };
+
+  const stageReturn = async (nft: Awaited<ReturnType<typeof mintNft>>) => {
+    const originTxHash = crypto.randomBytes(32);
+    const nonce = await nextInboundNonce();
+    await program.methods
+      .stageNativeReturn(
+        nft.mint,
+        new anchor.BN(destinationChainId),
+        Array.from(originTxHash),
+        nonce,
+        authority.publicKey,
+        new anchor.BN(0),
+        crypto.randomBytes(64)
+      )
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: crossChainConfigPda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        cpiAllowlist: cpiAllowlistPda,
+        originChainConfig: destinationChainConfigPda,
+        receipt: pda([Buffer.from("cross_chain_receipt"), originTxHash, nonce.toArrayLike(Buffer, "le", 8)]),
+        inboundNonce: inboundNoncePda,
+        nftMetadata: nft.nftMetadataPda,
+        pendingReturn: pda([Buffer.from("pending_return"), nft.mint.toBytes()]),
+        relayerRegistration: null,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  };
+
+  const executeReturn = async (nft: Awaited<ReturnType<typeof mintNft>>) => {
+    await program.methods
+      .executeNativeReturn(nft.mint)
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: crossChainConfigPda,
+        pendingReturn: pda([Buffer.from("pending_return"), nft.mint.toBytes()]),
+        nftMetadata: nft.nftMetadataPda,
+        stakeRecord: pda([Buffer.from("stake_record"), nft.mint.toBytes()]),
+        collectionLedger: pda([
+          Buffer.from("collection_ledger"),
+          new anchor.BN(SOLANA_CHAIN_ID).toArrayLike(Buffer, "le", 8),
+        ]),
+        payer: authority.publicKey,
+      })
+      .rpc();
+  };
+
+  after(async () => {
+    await setFinalityDelay(0);
+  });
+
+  it('Holds a staged return until the finality delay passes, and lets the admin cancel it', async () => {
+    await setFinalityDelay(3600);
+    const nft = await lockedNft();
+    await stageReturn(nft);
+
+    try {
+      await executeReturn(nft);
+      expect.fail("Should have waited for the finality delay");
+    } catch (error) {
+      expect(error.message).to.include("FinalityDelayNotElapsed");
+    }
+
+    const pendingReturn = pda([Buffer.from("pending_return"), nft.mint.toBytes()]);
+    await program.methods
+      .cancelNativeReturn(nft.mint)
+      .accounts({ ...(await auditAccounts()), pendingReturn, payer: authority.publicKey })
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(pendingReturn)).to.be.null;
+    expect((await program.account.nftMetadata.fetch(nft.nftMetadataPda)).isLocked).to.be.true;
+  });
+
+  it('Unlocks a returning NFT once the delay has passed', async () => {
+    await setFinalityDelay(0);
+    const nft = await lockedNft();
+    await stageReturn(nft);
+    await executeReturn(nft);
+
+    const metadata = await program.account.nftMetadata.fetch(nft.nftMetadataPda);
+    expect(metadata.isLocked).to.be.false;
+    expect(metadata.currentOwner.toString()).to.equal(authority.publicKey.toString());
+  });
+
+  it('Rejects returns for NFTs that are not locked', async () => {
+    try {
+      await stageReturn(await mintNft());
+      expect.fail("Should have required a locked NFT");
+    } catch (error) {
+      expect(error.message).to.include("NativeNftNotLocked");
+    }
+  });
+
+  it('Requires a bonded relayer to stage while a relayer requirement is set', async () => {
+    await setFinalityDelay(0);
+    const nft = await lockedNft();
+    const setRequirement = async (requirement: object | null) =>
+      program.methods
+        .setRelayerRequirement(requirement)
+        .accounts({ ...(await auditAccounts()), crossChainConfig: crossChainConfigPda })
+        .rpc();
+
+    await setRequirement({ bondMint: null, minBond: new anchor.BN(1_000_000), withdrawCooldownSeconds: 3600 });
+    try {
+      await stageReturn(nft);
+      expect.fail("Should have required a bonded relayer");
+    } catch (error) {
+      expect(error.message).to.include("RelayerNotBonded");
+    } finally {
+      await setRequirement(null);
+    }
+  });
+});
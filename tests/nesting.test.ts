@@ -0,0 +1,184 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from '@solana/web3.js';
+
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddress,
+  getAccount,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import * as crypto from 'crypto';
+import { setupProgram, ensureInitialized, pda, crossChainTransferAccounts } from './helpers';
+
+describe('NFT nesting', () => {
+  const { provider, program, authority } = setupProgram();
+
+  const destinationChainId = 1; // Ethereum
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+  const cpiAllowlistPda = pda([Buffer.from("cpi_allowlist")]);
+  const destinationChainConfigPda = pda([
+    Buffer.from("chain_config"),
+    new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8),
+  ]);
+  const metadataPda = (mint: PublicKey) => pda([Buffer.from("nft_metadata"), mint.toBytes()]);
+  const childIndexPda = (parent: PublicKey) => pda([Buffer.from("child_index"), parent.toBytes()]);
+  const childEscrowPda = (child: PublicKey) => pda([Buffer.from("child_escrow"), child.toBytes()]);
+
+  const mintNft = async (name: string) => {
+    const mint = Keypair.generate();
+    await program.methods
+      .mintNft("https://example.com/nesting.json", name, "NEST", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        collectionConfig: null,
+        mint: mint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, authority.publicKey),
+        nftMetadata: metadataPda(mint.publicKey),
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+    return mint.publicKey;
+  };
+
+  const equip = async (parent: PublicKey, child: PublicKey) =>
+    program.methods
+      .equipChild()
+      .accounts({
+        programState: programStatePda,
+        parentMetadata: metadataPda(parent),
+        parentMint: parent,
+        parentTokenAccount: await getAssociatedTokenAddress(parent, authority.publicKey),
+        childIndex: childIndexPda(parent),
+        childMetadata: metadataPda(child),
+        childMint: child,
+        ownerChildAccount: await getAssociatedTokenAddress(child, authority.publicKey),
+        childEscrow: childEscrowPda(child),
+        owner: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+  const unequip = async (parent: PublicKey, child: PublicKey) =>
+    program.methods
+      .unequipChild()
+      .accounts({
+        programState: programStatePda,
+        parentMetadata: metadataPda(parent),
+        parentMint: parent,
+        parentTokenAccount: await getAssociatedTokenAddress(parent, authority.publicKey),
+        childIndex: childIndexPda(parent),
+        childMint: child,
+        childEscrow: childEscrowPda(child),
+        ownerChildAccount: await getAssociatedTokenAddress(child, authority.publicKey),
+        owner: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+  const transferOut = async (mint: PublicKey) => {
+    const nonce = new anchor.BN(Date.now());
+    return program.methods
+      .crossChainTransfer(new anchor.BN(destinationChainId), crypto.randomBytes(20), nonce)
+      .accounts(
+        crossChainTransferAccounts(
+          { mint, tokenAccount: await getAssociatedTokenAddress(mint, authority.publicKey), owner: authority.publicKey, destinationChainId, nonce }
+        )
+      )
+      .rpc();
+  };
+
+  before(async () => {
+    await ensureInitialized();
+
+    try {
+      await program.account.chainConfig.fetch(destinationChainConfigPda);
+    } catch (error) {
+      const auditLog = await program.account.auditLog.fetch(auditLogPda);
+      await program.methods
+        .registerChain(new anchor.BN(destinationChainId))
+        .accounts({
+          programState: programStatePda,
+          chainConfig: destinationChainConfigPda,
+          cpiAllowlist: cpiAllowlistPda,
+          auditLog: auditLogPda,
+          auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .rpc();
+    }
+  });
+
+  it('Escrows an equipped child and blocks bridging the parent until it is unequipped', async () => {
+    const parent = await mintNft("Knight");
+    const child = await mintNft("Sword");
+
+    await equip(parent, child);
+    expect((await program.account.childIndex.fetch(childIndexPda(parent))).children.map((m) => m.toString()))
+      .to.deep.equal([child.toString()]);
+    expect((await program.account.nftMetadata.fetch(metadataPda(parent))).childCount).to.equal(1);
+    expect((await getAccount(provider.connection, childEscrowPda(child))).amount).to.equal(BigInt(1));
+
+    try {
+      await transferOut(parent);
+      expect.fail("Should have blocked bridging a parent with children");
+    } catch (error) {
+      expect(error.message).to.include("HasAttachedChildren");
+    }
+
+    await unequip(parent, child);
+    expect((await program.account.childIndex.fetch(childIndexPda(parent))).children).to.be.empty;
+    expect((await program.account.nftMetadata.fetch(metadataPda(parent))).childCount).to.equal(0);
+    const childAccount = await getAccount(
+      provider.connection,
+      await getAssociatedTokenAddress(child, authority.publicKey)
+    );
+    expect(childAccount.amount).to.equal(BigInt(1));
+    expect(await provider.connection.getAccountInfo(childEscrowPda(child))).to.be.null;
+
+    await transferOut(parent);
+  });
+
+  it('Rejects equipping an NFT on itself and unequipping a child it does not have', async () => {
+    const parent = await mintNft("Shield");
+    try {
+      await equip(parent, parent);
+      expect.fail("Should have rejected equipping an NFT on itself");
+    } catch (error) {
+      expect(error.message).to.include("InvalidChild");
+    }
+
+    await equip(parent, await mintNft("Gem"));
+    // Equipped, but on a different parent
+    const other = await mintNft("Ring");
+    const otherChild = await mintNft("Band");
+    await equip(other, otherChild);
+    try {
+      await unequip(parent, otherChild);
+      expect.fail("Should have rejected a child that isn't attached");
+    } catch (error) {
+      expect(error.message).to.include("ChildNotAttached");
+    }
+  });
+});
@@ -0,0 +1,226 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  LAMPORTS_PER_SOL,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from '@solana/web3.js';
+
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createMint,
+  getAssociatedTokenAddress,
+  getAccount,
+  createAssociatedTokenAccount,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import * as crypto from 'crypto';
+import { setupProgram, ensureInitialized, pda, crossChainTransferAccounts } from './helpers';
+
+describe('offers', () => {
+  const { provider, program, authority, client } = setupProgram();
+
+  const destinationChainId = 1; // Ethereum
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+  const cpiAllowlistPda = pda([Buffer.from("cpi_allowlist")]);
+  const destinationChainConfigPda = pda([
+    Buffer.from("chain_config"),
+    new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8),
+  ]);
+  const metadataPda = (mint: PublicKey) => pda([Buffer.from("nft_metadata"), mint.toBytes()]);
+  const collectionConfigPda = (collectionMint: PublicKey) =>
+    pda([Buffer.from("collection_config"), collectionMint.toBytes()]);
+
+  const amount = new anchor.BN(LAMPORTS_PER_SOL);
+  const signatureFee = 5_000;
+  let buyer: Keypair;
+
+  const createCollection = async () => {
+    const collectionMint = await createMint(provider.connection, authority.payer, authority.publicKey, null, 0);
+    await program.methods
+      .createCollectionConfig(authority.publicKey)
+      .accounts({
+        programState: programStatePda,
+        collectionConfig: collectionConfigPda(collectionMint),
+        collectionMint,
+        symbolRegistry: pda([Buffer.from("symbol_registry")]),
+        collectionMetadata: null,
+        symbolReservation: null,
+        signer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    return collectionMint;
+  };
+
+  const mintNft = async (collectionMint: PublicKey | null = null) => {
+    const mint = Keypair.generate();
+    await program.methods
+      .mintNft("https://example.com/offer.json", "Offer NFT", "OFFER", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        collectionConfig: collectionMint ? collectionConfigPda(collectionMint) : null,
+        mint: mint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, authority.publicKey),
+        nftMetadata: metadataPda(mint.publicKey),
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+    return mint.publicKey;
+  };
+
+  const transferOut = async (mint: PublicKey) => {
+    const nonce = new anchor.BN(Date.now());
+    return program.methods
+      .crossChainTransfer(new anchor.BN(destinationChainId), crypto.randomBytes(20), nonce)
+      .accounts(
+        crossChainTransferAccounts(
+          { mint, tokenAccount: await getAssociatedTokenAddress(mint, authority.publicKey), owner: authority.publicKey, destinationChainId, nonce }
+        )
+      )
+      .rpc();
+  };
+
+  const balance = (address: PublicKey) => provider.connection.getBalance(address, 'confirmed');
+  const inOneHour = () => Math.floor(Date.now() / 1000) + 3_600;
+
+  before(async () => {
+    await ensureInitialized();
+
+    try {
+      await program.account.chainConfig.fetch(destinationChainConfigPda);
+    } catch (error) {
+      const auditLog = await program.account.auditLog.fetch(auditLogPda);
+      await program.methods
+        .registerChain(new anchor.BN(destinationChainId))
+        .accounts({
+          programState: programStatePda,
+          chainConfig: destinationChainConfigPda,
+          cpiAllowlist: cpiAllowlistPda,
+          auditLog: auditLogPda,
+          auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .rpc();
+    }
+
+    buyer = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(buyer.publicKey, 20 * LAMPORTS_PER_SOL),
+      'confirmed'
+    );
+  });
+
+  it('Pays the royalty creators and the holder when an offer is accepted', async () => {
+    const creators = [Keypair.generate().publicKey, Keypair.generate().publicKey];
+    const collectionMint = await createCollection();
+    await client.setCollectionRoyalty(collectionMint, 1_000, [
+      { address: creators[0], share: 50 },
+      { address: creators[1], share: 50 },
+    ]);
+    const mint = await mintNft(collectionMint);
+
+    await client.makeOffer(mint, buyer, amount, inOneHour());
+    const offer = client.findOfferAddress(mint, buyer.publicKey);
+    const offerRent = (await provider.connection.getAccountInfo(offer))!.lamports - amount.toNumber();
+    expect((await client.getOffers(mint)).map((open) => [open.buyer, open.amount, open.expired])).to.deep.equal([
+      [buyer.publicKey.toString(), amount.toString(), false],
+    ]);
+
+    try {
+      await client.acceptOffer(mint, buyer.publicKey, authority.payer, amount.addn(1));
+      expect.fail("Should have rejected an offer below the holder's minimum");
+    } catch (error) {
+      expect(error.message).to.include("InvalidOfferTerms");
+    }
+
+    // The buyer's token account exists already, so the holder pays only the signature fee
+    await createAssociatedTokenAccount(provider.connection, buyer, mint, buyer.publicKey);
+    const before = await Promise.all([authority.publicKey, buyer.publicKey, ...creators].map(balance));
+    await client.acceptOffer(mint, buyer.publicKey, authority.payer, amount);
+    const after = await Promise.all([authority.publicKey, buyer.publicKey, ...creators].map(balance));
+
+    expect(after.map((lamports, i) => lamports - before[i])).to.deep.equal([
+      900_000_000 - signatureFee,
+      offerRent,
+      50_000_000,
+      50_000_000,
+    ]);
+    const buyerAccount = await getAccount(provider.connection, await getAssociatedTokenAddress(mint, buyer.publicKey));
+    expect(buyerAccount.amount).to.equal(BigInt(1));
+    expect((await program.account.nftMetadata.fetch(metadataPda(mint))).currentOwner.toString())
+      .to.equal(buyer.publicKey.toString());
+    expect(await provider.connection.getAccountInfo(offer)).to.be.null;
+  });
+
+  it('Refunds offers the buyer cancels, and expired offers to anyone', async () => {
+    const mint = await mintNft();
+    await client.makeOffer(mint, buyer, amount, inOneHour());
+    const stranger = Keypair.generate();
+    try {
+      await client.cancelOffer(mint, buyer.publicKey, stranger);
+      expect.fail("Should have let only the buyer cancel an open offer");
+    } catch (error) {
+      expect(error.message).to.include("OfferNotExpired");
+    }
+
+    let before = await balance(buyer.publicKey);
+    await client.cancelOffer(mint, buyer.publicKey, buyer);
+    expect((await balance(buyer.publicKey)) - before).to.be.above(amount.toNumber());
+    expect(await client.getOffers(mint)).to.be.empty;
+
+    await client.makeOffer(mint, buyer, amount, Math.floor(Date.now() / 1000) + 3);
+    await new Promise((resolve) => setTimeout(resolve, 6_000));
+    try {
+      await client.acceptOffer(mint, buyer.publicKey, authority.payer, amount);
+      expect.fail("Should have rejected an expired offer");
+    } catch (error) {
+      expect(error.message).to.include("OfferExpired");
+    }
+
+    before = await balance(buyer.publicKey);
+    await client.cancelOffer(mint, buyer.publicKey, stranger);
+    expect((await balance(buyer.publicKey)) - before).to.be.above(amount.toNumber());
+  });
+
+  it('Rejects accepting an offer on a bridged-out NFT', async () => {
+    const mint = await mintNft();
+    await client.makeOffer(mint, buyer, amount, inOneHour());
+    await transferOut(mint);
+
+    try {
+      await client.acceptOffer(mint, buyer.publicKey, authority.payer, amount);
+      expect.fail("Should have rejected selling a bridged-out NFT");
+    } catch (error) {
+      expect(error.message).to.include("NftLocked");
+    }
+    await client.cancelOffer(mint, buyer.publicKey, buyer);
+  });
+
+  it('Rejects offers of nothing or already expired', async () => {
+    const mint = await mintNft();
+    for (const [offered, expiresAt] of [[new anchor.BN(0), inOneHour()], [amount, 1]] as [anchor.BN, number][]) {
+      try {
+        await client.makeOffer(mint, buyer, offered, expiresAt);
+        expect.fail("Should have rejected the offer terms");
+      } catch (error) {
+        expect(error.message).to.include("InvalidOfferTerms");
+      }
+    }
+  });
+});
@@ -0,0 +1,129 @@
+import * as anchor from '@coral-xyz/anchor';
+import { PublicKey, Keypair, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY } from '@solana/web3.js';
+
+import { expect } from 'chai';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('operator config', () => {
+  const { provider, program, authority, client } = setupProgram();
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+
+  const operator = Keypair.generate();
+  const stranger = Keypair.generate();
+
+  const setMintGuardAs = async (signer: Keypair, feeLamports: number) => {
+    const auditLog = await program.account.auditLog.fetch(auditLogPda);
+    return program.methods
+      .setMintGuard(new anchor.BN(feeLamports), 0)
+      .accounts({
+        programState: programStatePda,
+        operatorConfig: client.findOperatorConfigAddress(),
+        mintGuard: pda([Buffer.from("mint_guard")]),
+        cpiAllowlist: pda([Buffer.from("cpi_allowlist")]),
+        auditLog: auditLogPda,
+        auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+        authority: signer.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .signers([signer])
+      .rpc();
+  };
+
+  before(async () => {
+    await ensureInitialized();
+
+    for (const wallet of [operator, stranger]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(wallet.publicKey, 1_000_000_000),
+        'confirmed'
+      );
+    }
+  });
+
+  after(async () => {
+    await client.setMintGuard(0, 0);
+    await client.setOperator(PublicKey.default);
+  });
+
+  it('Lets the operator change operational settings', async () => {
+    await client.setOperator(operator.publicKey);
+    expect(await client.getOperator()).to.equal(operator.publicKey.toString());
+
+    await setMintGuardAs(operator, 5000);
+    const mintGuard = await program.account.mintGuard.fetch(pda([Buffer.from("mint_guard")]));
+    expect(mintGuard.feeLamports.toNumber()).to.equal(5000);
+
+    try {
+      await setMintGuardAs(stranger, 0);
+      expect.fail("Should have rejected a stranger");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+
+  it('Keeps security settings and the operator itself with the authority', async () => {
+    const auditLog = await program.account.auditLog.fetch(auditLogPda);
+    const auditEntry = pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]);
+    try {
+      await program.methods
+        .rotateTss(Keypair.generate().publicKey)
+        .accounts({
+          programState: programStatePda,
+          crossChainConfig: pda([Buffer.from("cross_chain_config")]),
+          cpiAllowlist: pda([Buffer.from("cpi_allowlist")]),
+          auditLog: auditLogPda,
+          auditEntry,
+          authority: operator.publicKey,
+          systemProgram: SystemProgram.programId,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .signers([operator])
+        .rpc();
+      expect.fail("Should have rejected the operator");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+
+    try {
+      await program.methods
+        .setOperator(stranger.publicKey)
+        .accounts({
+          programState: programStatePda,
+          operatorConfig: client.findOperatorConfigAddress(),
+          cpiAllowlist: pda([Buffer.from("cpi_allowlist")]),
+          auditLog: auditLogPda,
+          auditEntry,
+          authority: operator.publicKey,
+          systemProgram: SystemProgram.programId,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .signers([operator])
+        .rpc();
+      expect.fail("Should have rejected the operator");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+
+    try {
+      await client.setOperator(authority.publicKey);
+      expect.fail("Should have rejected the authority as operator");
+    } catch (error) {
+      expect(error.message).to.include("InvalidOperator");
+    }
+  });
+
+  it('Revokes the operator', async () => {
+    await client.setOperator(PublicKey.default);
+    expect(await client.getOperator()).to.be.null;
+
+    try {
+      await setMintGuardAs(operator, 0);
+      expect.fail("Should have rejected the revoked operator");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+});
@@ -0,0 +1,281 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from '@solana/web3.js';
+
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddress,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import * as crypto from 'crypto';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('optimistic receives', () => {
+  const { provider, program, authority } = setupProgram();
+
+  const originChainId = 1; // Ethereum
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const crossChainConfigPda = pda([Buffer.from("cross_chain_config")]);
+  const blocklistPda = pda([Buffer.from("blocklist")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+  const cpiAllowlistPda = pda([Buffer.from("cpi_allowlist")]);
+  const originChainConfigPda = pda([
+    Buffer.from("chain_config"),
+    new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8),
+  ]);
+  const inboundNoncePda = pda([
+    Buffer.from("inbound_nonce"),
+    new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8),
+  ]);
+  const originContract = crypto.randomBytes(20);
+
+  before(async () => {
+    await ensureInitialized();
+
+    // Register the origin chain if an earlier test hasn't already
+    try {
+      await program.account.chainConfig.fetch(originChainConfigPda);
+    } catch (error) {
+      await program.methods
+        .registerChain(new anchor.BN(originChainId))
+        .accounts({ ...(await auditAccounts()), chainConfig: originChainConfigPda })
+        .rpc();
+    }
+  });
+
+  const auditAccounts = async () => {
+    const auditLog = await program.account.auditLog.fetch(auditLogPda);
+    return {
+      programState: programStatePda,
+      cpiAllowlist: cpiAllowlistPda,
+      auditLog: auditLogPda,
+      auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+      authority: authority.publicKey,
+      systemProgram: SystemProgram.programId,
+      instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+    };
+  };
+
+  const setOptimisticWindow = async (windowSeconds: number) => {
+    await program.methods
+      .setChainOptimisticWindow(new anchor.BN(originChainId), windowSeconds)
+      .accounts({ ...(await auditAccounts()), chainConfig: originChainConfigPda })
+      .rpc();
+  };
+
+  const nextInboundNonce = async () => {
+    try {
+      const tracker = await program.account.inboundNonce.fetch(inboundNoncePda);
+      return anchor.BN.max(tracker.lastNonce.addn(1), new anchor.BN(Date.now()));
+    } catch (error) {
+      return new anchor.BN(Date.now());
+    }
+  };
+
+  const optimisticReceivePda = (originTxHash: Buffer, nonce: anchor.BN) =>
+    pda([Buffer.from("optimistic_receive"), originTxHash, nonce.toArrayLike(Buffer, "le", 8)]);
+
+  const watcherPda = (owner: PublicKey) => pda([Buffer.from("watcher"), owner.toBytes()]);
+
+  const stage = async (submitter?: Keypair) => {
+    const originTxHash = crypto.randomBytes(32);
+    const originTokenId = crypto.randomBytes(32);
+    const nonce = await nextInboundNonce();
+    await program.methods
+      .stageOptimisticReceive(
+        new anchor.BN(originChainId),
+        Array.from(originTxHash),
+        originContract,
+        Array.from(originTokenId),
+        "https://example.com/optimistic.json",
+        "Optimistic NFT",
+        "OPT",
+        crypto.randomBytes(20),
+        authority.publicKey,
+        nonce
+      )
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: crossChainConfigPda,
+        blocklist: blocklistPda,
+        cpiAllowlist: cpiAllowlistPda,
+        originChainConfig: originChainConfigPda,
+        optimisticReceive: optimisticReceivePda(originTxHash, nonce),
+        inboundNonce: inboundNoncePda,
+        submitterWatcher: submitter ? watcherPda(submitter.publicKey) : null,
+        authority: submitter ? submitter.publicKey : authority.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .signers(submitter ? [submitter] : [])
+      .rpc();
+    return { originTxHash, originTokenId, nonce };
+  };
+
+  const claim = async (staged: Awaited<ReturnType<typeof stage>>) => {
+    const chainIdBytes = new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8);
+    const mint = pda([Buffer.from("inbound_mint"), chainIdBytes, originContract, staged.originTokenId]);
+    await program.methods
+      .claimOptimisticReceive(Array.from(staged.originTxHash), staged.nonce)
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: crossChainConfigPda,
+        blocklist: blocklistPda,
+        optimisticReceive: optimisticReceivePda(staged.originTxHash, staged.nonce),
+        receipt: pda([Buffer.from("cross_chain_receipt"), staged.originTxHash, staged.nonce.toArrayLike(Buffer, "le", 8)]),
+        mint,
+        freezeAuthority: pda([Buffer.from("freeze_authority")]),
+        tokenAccount: await getAssociatedTokenAddress(mint, authority.publicKey),
+        nftMetadata: pda([Buffer.from("nft_metadata"), mint.toBytes()]),
+        collectionLedger: pda([Buffer.from("collection_ledger"), chainIdBytes, originContract]),
+        foreignCollection: null,
+        recipient: authority.publicKey,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+    return mint;
+  };
+
+  const fundedWatcher = async () => {
+    const watcher = Keypair.generate();
+    const fundTx = await provider.connection.requestAirdrop(
+      watcher.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(fundTx);
+    await program.methods
+      .registerWatcher(new anchor.BN(anchor.web3.LAMPORTS_PER_SOL))
+      .accounts({
+        programState: programStatePda,
+        watcher: watcherPda(watcher.publicKey),
+        owner: watcher.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([watcher])
+      .rpc();
+    return watcher;
+  };
+
+  after(async () => {
+    await setOptimisticWindow(0);
+  });
+
+  it('Holds a staged receive for the challenge window and cancels it on a fraud proof', async () => {
+    await setOptimisticWindow(3600);
+    const staged = await stage();
+
+    try {
+      await claim(staged);
+      expect.fail("Should have waited for the challenge window");
+    } catch (error) {
+      expect(error.message).to.include("ChallengeWindowOpen");
+    }
+
+    const optimisticReceive = optimisticReceivePda(staged.originTxHash, staged.nonce);
+    await program.methods
+      .cancelOptimisticReceive(Array.from(staged.originTxHash), staged.nonce, crypto.randomBytes(64))
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: crossChainConfigPda,
+        optimisticReceive,
+        challenger: authority.publicKey,
+      })
+      .rpc();
+
+    expect((await program.account.optimisticReceive.fetch(optimisticReceive)).status).to.equal(2);
+
+    try {
+      await claim(staged);
+      expect.fail("Should have refused a cancelled receive");
+    } catch (error) {
+      expect(error.message).to.include("OptimisticReceiveNotPending");
+    }
+  });
+
+  it('Mints a staged receive once the window has closed', async () => {
+    await setOptimisticWindow(1);
+    const staged = await stage();
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const mint = await claim(staged);
+
+    const metadata = await program.account.nftMetadata.fetch(pda([Buffer.from("nft_metadata"), mint.toBytes()]));
+    expect(metadata.currentOwner.toString()).to.equal(authority.publicKey.toString());
+    const optimisticReceive = optimisticReceivePda(staged.originTxHash, staged.nonce);
+    expect((await program.account.optimisticReceive.fetch(optimisticReceive)).status).to.equal(1);
+  });
+
+  it('Rejects staging while the window is 0', async () => {
+    await setOptimisticWindow(0);
+    try {
+      await stage();
+      expect.fail("Should have required optimistic mode");
+    } catch (error) {
+      expect(error.message).to.include("OptimisticModeDisabled");
+    }
+  });
+
+  it('Slashes a fraudulent submitter\'s bond to the watcher who challenged it', async () => {
+    await setOptimisticWindow(3600);
+    const submitter = await fundedWatcher();
+    const challenger = await fundedWatcher();
+    const staged = await stage(submitter);
+
+    const optimisticReceive = optimisticReceivePda(staged.originTxHash, staged.nonce);
+    const challenge = pda([Buffer.from("challenge"), optimisticReceive.toBytes()]);
+    await program.methods
+      .submitChallenge(Array.from(staged.originTxHash), staged.nonce, Buffer.from("origin tx emitted a different token id"))
+      .accounts({
+        programState: programStatePda,
+        optimisticReceive,
+        challengerWatcher: watcherPda(challenger.publicKey),
+        challenge,
+        challenger: challenger.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([challenger])
+      .rpc();
+
+    expect((await program.account.optimisticReceive.fetch(optimisticReceive)).status).to.equal(3);
+
+    const challengerBalance = await provider.connection.getBalance(challenger.publicKey);
+    await program.methods
+      .resolveChallenge(Array.from(staged.originTxHash), staged.nonce, true)
+      .accounts({
+        ...(await auditAccounts()),
+        optimisticReceive,
+        challenge,
+        challengerWatcher: watcherPda(challenger.publicKey),
+        challenger: challenger.publicKey,
+        submitterWatcher: watcherPda(submitter.publicKey),
+      })
+      .rpc();
+
+    expect((await program.account.optimisticReceive.fetch(optimisticReceive)).status).to.equal(2);
+    const slashed = await program.account.watcher.fetch(watcherPda(submitter.publicKey));
+    expect(slashed.bond.toNumber()).to.equal(0);
+    expect(slashed.timesSlashed).to.equal(1);
+    expect((await program.account.watcher.fetch(watcherPda(challenger.publicKey))).challengesWon).to.equal(1);
+    expect(await provider.connection.getBalance(challenger.publicKey))
+      .to.be.greaterThan(challengerBalance + anchor.web3.LAMPORTS_PER_SOL);
+
+    try {
+      await stage(submitter);
+      expect.fail("Should have required a bond");
+    } catch (error) {
+      expect(error.message).to.include("WatcherBondTooLow");
+    }
+  });
+});
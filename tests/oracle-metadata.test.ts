@@ -0,0 +1,125 @@
+import * as anchor from '@coral-xyz/anchor';
+import { PublicKey, Keypair, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/web3.js';
+
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from '@solana/spl-token';
+
+import { expect } from 'chai';
+import { UniversalNftClient, oracleFeedId } from '../client/src/client';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('oracle-driven metadata', () => {
+  const { provider, program, authority, client } = setupProgram();
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const metadataPda = (mint: PublicKey) => pda([Buffer.from("nft_metadata"), mint.toBytes()]);
+
+  const variants = [
+    { minValue: -1000, uri: "https://example.com/cold.json" },
+    { minValue: 20, uri: "https://example.com/mild.json" },
+    { minValue: 30, uri: "https://example.com/hot.json" },
+  ];
+
+  const mintNft = async () => {
+    const mint = Keypair.generate();
+    await program.methods
+      .mintNft("https://example.com/static.json", "Weather NFT", "WTHR", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        collectionConfig: null,
+        mint: mint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, authority.publicKey),
+        nftMetadata: metadataPda(mint.publicKey),
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+    return mint.publicKey;
+  };
+
+  before(async () => {
+    await ensureInitialized();
+  });
+
+  it('Switches the metadata URI as the feed crosses thresholds', async () => {
+    const feedId = oracleFeedId(`temperature/${Keypair.generate().publicKey.toBase58()}`);
+    const updater = Keypair.generate();
+    await client.configureOracleFeed(feedId, updater.publicKey, 600);
+
+    const mint = await mintNft();
+    await client.setMetadataVariants(mint, authority.payer, feedId, variants);
+    expect((await client.getDynamicMetadata(mint))!.variants).to.deep.equal(variants);
+
+    try {
+      await client.updateMetadataFromOracle(mint);
+      expect.fail("Should have required a value");
+    } catch (error) {
+      expect(error.message).to.include("StaleOracleValue");
+    }
+
+    for (const [value, variant] of [[25, 1], [31, 2], [-5000, 0], [20, 1]]) {
+      await client.pushOracleValue(feedId, value, updater);
+      await client.updateMetadataFromOracle(mint);
+      expect((await program.account.nftMetadata.fetch(metadataPda(mint))).metadataUri)
+        .to.equal(variants[variant].uri);
+      const dynamicMetadata = (await client.getDynamicMetadata(mint))!;
+      expect(dynamicMetadata.activeVariant).to.equal(variant);
+      expect(dynamicMetadata.lastValue).to.equal(value);
+    }
+  });
+
+  it('Only lets the updater push and rejects stale values', async () => {
+    const feedId = oracleFeedId(`score/${Keypair.generate().publicKey.toBase58()}`);
+    const updater = Keypair.generate();
+    await client.configureOracleFeed(feedId, updater.publicKey, 1);
+
+    try {
+      await client.pushOracleValue(feedId, 3, Keypair.generate());
+      expect.fail("Should have rejected the push");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+
+    const mint = await mintNft();
+    await client.setMetadataVariants(mint, authority.payer, feedId, variants);
+    await client.pushOracleValue(feedId, 40, updater);
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+    try {
+      await client.updateMetadataFromOracle(mint);
+      expect.fail("Should have rejected the stale value");
+    } catch (error) {
+      expect(error.message).to.include("StaleOracleValue");
+    }
+    expect((await program.account.nftMetadata.fetch(metadataPda(mint))).metadataUri)
+      .to.equal("https://example.com/static.json");
+  });
+
+  it('Rejects unordered variants and bad feed configs', async () => {
+    const feedId = oracleFeedId(`price/${Keypair.generate().publicKey.toBase58()}`);
+    for (const staleness of [0, 86_401]) {
+      try {
+        await client.configureOracleFeed(feedId, Keypair.generate().publicKey, staleness);
+        expect.fail("Should have rejected the feed");
+      } catch (error) {
+        expect(error.message).to.include("InvalidOracleConfig");
+      }
+    }
+    await client.configureOracleFeed(feedId, Keypair.generate().publicKey, 60);
+
+    const mint = await mintNft();
+    for (const invalid of [[], [variants[1], variants[0]], [variants[0], variants[0]]]) {
+      try {
+        await client.setMetadataVariants(mint, authority.payer, feedId, invalid);
+        expect.fail("Should have rejected the variants");
+      } catch (error) {
+        expect(error.message).to.include("InvalidMetadataVariants");
+      }
+    }
+    expect(await client.getDynamicMetadata(mint)).to.be.null;
+  });
+});
@@ -0,0 +1,141 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  Keypair,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from '@solana/web3.js';
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddress,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import * as crypto from 'crypto';
+import { setupProgram, ensureInitialized, pda, crossChainTransferAccounts } from './helpers';
+
+describe('per-chain payload limits', () => {
+  const { provider, program, authority } = setupProgram();
+
+  const destinationChainId = 56; // BNB Smart Chain
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const crossChainConfigPda = pda([Buffer.from("cross_chain_config")]);
+  const blocklistPda = pda([Buffer.from("blocklist")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+  const cpiAllowlistPda = pda([Buffer.from("cpi_allowlist")]);
+  const destinationChainConfigPda = pda([
+    Buffer.from("chain_config"),
+    new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8),
+  ]);
+
+  before(async () => {
+    await ensureInitialized();
+
+    // Register the destination chain if an earlier test hasn't already
+    try {
+      await program.account.chainConfig.fetch(destinationChainConfigPda);
+    } catch (error) {
+      const auditLog = await program.account.auditLog.fetch(auditLogPda);
+      await program.methods
+        .registerChain(new anchor.BN(destinationChainId))
+        .accounts({
+          programState: programStatePda,
+          chainConfig: destinationChainConfigPda,
+          cpiAllowlist: cpiAllowlistPda,
+          auditLog: auditLogPda,
+          auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .rpc();
+    }
+  });
+
+  const mintNft = async () => {
+    const mint = Keypair.generate();
+    const tokenAccount = await getAssociatedTokenAddress(mint.publicKey, authority.publicKey);
+    const nftMetadataPda = pda([Buffer.from("nft_metadata"), mint.publicKey.toBytes()]);
+
+    await program.methods
+      .mintNft("https://example.com/payload.json", "Payload NFT", "LOAD", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: blocklistPda,
+        collectionConfig: null,
+        mint: mint.publicKey,
+        tokenAccount: tokenAccount,
+        nftMetadata: nftMetadataPda,
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+
+    return { mint: mint.publicKey, tokenAccount, nftMetadataPda };
+  };
+
+  const transferOut = async (nft: Awaited<ReturnType<typeof mintNft>>, nonce: anchor.BN) => {
+    return program.methods
+      .crossChainTransfer(new anchor.BN(destinationChainId), crypto.randomBytes(20), nonce)
+      .accounts(
+        crossChainTransferAccounts(
+          { mint: nft.mint, tokenAccount: nft.tokenAccount, owner: authority.publicKey, destinationChainId, nonce }
+        )
+      )
+      .rpc();
+  };
+
+  const setMaxPayload = async (maxPayloadBytes: number) => {
+    const auditLog = await program.account.auditLog.fetch(auditLogPda);
+    return program.methods
+      .setChainMaxPayload(new anchor.BN(destinationChainId), maxPayloadBytes)
+      .accounts({
+        programState: programStatePda,
+        chainConfig: destinationChainConfigPda,
+        cpiAllowlist: cpiAllowlistPda,
+        auditLog: auditLogPda,
+        auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  };
+
+  const nextNonce = async () => {
+    const config = await program.account.crossChainConfig.fetch(crossChainConfigPda);
+    return anchor.BN.max(config.nonceCounter.addn(1), new anchor.BN(Date.now()));
+  };
+
+  it('Refuses payloads over the destination cap until it is lifted', async () => {
+    const nft = await mintNft();
+    // 92 fixed bytes, a 20-byte recipient and the 32-byte URI make 144
+    await setMaxPayload(143);
+    expect((await program.account.chainConfig.fetch(destinationChainConfigPda)).maxPayloadBytes).to.equal(143);
+
+    try {
+      await transferOut(nft, await nextNonce());
+      expect.fail("Should have rejected a payload over the cap");
+    } catch (error) {
+      expect(error.message).to.include("PayloadTooLarge");
+    }
+
+    await setMaxPayload(144);
+    await transferOut(nft, await nextNonce());
+    expect((await program.account.nftMetadata.fetch(nft.nftMetadataPda)).isLocked).to.be.true;
+
+    await setMaxPayload(0);
+    const auditLog = await program.account.auditLog.fetch(auditLogPda);
+    const entry = await program.account.auditEntry.fetch(
+      pda([Buffer.from("audit_entry"), auditLog.entryCount.subn(1).toArrayLike(Buffer, "le", 8)])
+    );
+    expect(entry.action).to.deep.equal({ setChainMaxPayload: {} });
+  });
+});
@@ -0,0 +1,75 @@
+import { Keypair, PublicKey, ComputeBudgetProgram, Transaction, SystemProgram } from '@solana/web3.js';
+import { expect } from 'chai';
+import {
+  DEFAULT_COMPUTE_UNITS,
+  computeBudgetInstructions,
+  estimatePriorityFee,
+  recommendedComputeUnits,
+  sendJitoBundle,
+} from '../client/src/priority-fees';
+
+describe('priority fees', () => {
+  // Stands in for the RPC; records the accounts it was asked about
+  const mockConnection = (fees: number[]) => {
+    const calls: (PublicKey[] | undefined)[] = [];
+    return {
+      calls,
+      getRecentPrioritizationFees: async (config?: { lockedWritableAccounts?: PublicKey[] }) => {
+        calls.push(config?.lockedWritableAccounts);
+        return fees.map((prioritizationFee, slot) => ({ slot, prioritizationFee }));
+      },
+    };
+  };
+
+  it('Recommends the compute limits the program expects', () => {
+    expect(recommendedComputeUnits('receive_cross_chain')).to.equal(400_000);
+    expect(recommendedComputeUnits('cross_chain_transfer', 3)).to.equal(375_000);
+    expect(recommendedComputeUnits('verify_ownership')).to.equal(50_000);
+    expect(recommendedComputeUnits('burn')).to.equal(DEFAULT_COMPUTE_UNITS);
+  });
+
+  it('Estimates the fee as a percentile of recent blocks', async () => {
+    const connection = mockConnection([500, 0, 100, 10_000, 200]);
+    const writable = [Keypair.generate().publicKey];
+
+    expect(await estimatePriorityFee(connection, { writableAccounts: writable })).to.equal(500);
+    expect(connection.calls[0]).to.deep.equal(writable);
+    expect(await estimatePriorityFee(connection, { percentile: 50 })).to.equal(200);
+    expect(await estimatePriorityFee(connection, { percentile: 100 })).to.equal(10_000);
+    expect(await estimatePriorityFee(connection, { percentile: 0 })).to.equal(0);
+    expect(await estimatePriorityFee(connection, { percentile: 100, maxMicroLamports: 1_000 })).to.equal(1_000);
+    expect(await estimatePriorityFee(mockConnection([]))).to.equal(0);
+
+    try {
+      await estimatePriorityFee(connection, { percentile: 101 });
+      expect.fail("Should have rejected a percentile above 100");
+    } catch (error) {
+      expect(error.message).to.include("between 0 and 100");
+    }
+  });
+
+  it('Only sets a unit price when there is a fee to pay', () => {
+    const free = computeBudgetInstructions(200_000);
+    expect(free).to.have.length(1);
+    expect(free[0].programId.equals(ComputeBudgetProgram.programId)).to.be.true;
+
+    const paid = computeBudgetInstructions(200_000, 1_500);
+    expect(paid).to.have.length(2);
+    expect(paid[1].data.equals(ComputeBudgetProgram.setComputeUnitPrice({ microLamports: 1_500 }).data)).to.be.true;
+  });
+
+  it('Refuses bundles the block engine would reject', async () => {
+    const payer = Keypair.generate().publicKey;
+    const tx = () => new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: payer, toPubkey: payer, lamports: 1 })
+    );
+    for (const bundle of [[], [tx(), tx(), tx(), tx(), tx(), tx()]]) {
+      try {
+        await sendJitoBundle(bundle, 'http://127.0.0.1:1');
+        expect.fail(`Should have rejected a bundle of ${bundle.length}`);
+      } catch (error) {
+        expect(error.message).to.include("1 to 5 transactions");
+      }
+    }
+  });
+});
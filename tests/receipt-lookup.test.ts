@@ -0,0 +1,54 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+// @ts-ignore - Type will be available after build
+import { UniversalNft } from '../target/types/universal_nft';
+import { PublicKey, Keypair } from '@solana/web3.js';
+import { expect } from 'chai';
+import * as crypto from 'crypto';
+
+describe('receipt lookup', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.UniversalNft as Program<UniversalNft>;
+
+  const receiptPda = (originTxHash: Buffer, nonce: number) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("cross_chain_receipt"), originTxHash, new anchor.BN(nonce).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+
+  it('Reports a message that has not arrived', async () => {
+    const originTxHash = crypto.randomBytes(32);
+    const lookup = await program.methods
+      .findReceipt(originTxHash, new anchor.BN(7))
+      .accounts({ receipt: receiptPda(originTxHash, 7) })
+      .view();
+
+    expect(lookup.address.toString()).to.equal(receiptPda(originTxHash, 7).toString());
+    expect(lookup.processed).to.be.false;
+    expect(lookup.receipt).to.be.null;
+  });
+
+  it('Derives the address from a long identifier the way receives do', async () => {
+    const signature = crypto.randomBytes(64);
+    const normalized = crypto.createHash('sha256').update(signature).digest();
+    const lookup = await program.methods
+      .findReceipt(signature, new anchor.BN(1))
+      .accounts({ receipt: receiptPda(normalized, 1) })
+      .view();
+    expect(lookup.address.toString()).to.equal(receiptPda(normalized, 1).toString());
+  });
+
+  it('Rejects an account that is not the receipt PDA', async () => {
+    try {
+      await program.methods
+        .findReceipt(crypto.randomBytes(32), new anchor.BN(1))
+        .accounts({ receipt: Keypair.generate().publicKey })
+        .view();
+      expect.fail("Should have rejected the wrong receipt account");
+    } catch (error) {
+      expect(error.message).to.include("InvalidReceiptAddress");
+    }
+  });
+});
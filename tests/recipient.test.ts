@@ -0,0 +1,84 @@
+import { Connection, Keypair } from '@solana/web3.js';
+import { Wallet } from 'ethers';
+import { expect } from 'chai';
+import {
+  EnsAttestation,
+  ensAttestationMessage,
+  resolveRecipient,
+  validateRecipientAddress,
+} from '../client/src/recipient';
+
+describe('recipient resolution', () => {
+  // Nothing here reaches the RPC except .sol lookups, which these tests avoid
+  const connection = new Connection('http://127.0.0.1:8899', 'confirmed');
+  const ethereum = 1;
+
+  const attest = async (signer: Pick<Wallet, 'signMessage'>, overrides: Partial<EnsAttestation> = {}) => {
+    const attestation = {
+      name: 'alice.eth',
+      address: Wallet.createRandom().address,
+      chainId: ethereum,
+      expiresAt: Math.floor(Date.now() / 1000) + 600,
+      ...overrides,
+    };
+    return { ...attestation, signature: await signer.signMessage(ensAttestationMessage(attestation)) };
+  };
+
+  it('Accepts hex addresses that fit the destination', async () => {
+    const address = Wallet.createRandom().address;
+    const resolved = await resolveRecipient(address, ethereum, connection);
+    expect(resolved.toString('hex')).to.equal(address.slice(2).toLowerCase());
+    expect((await resolveRecipient(address.slice(2), ethereum, connection)).equals(resolved)).to.be.true;
+  });
+
+  it('Rejects addresses in the wrong format for the destination', async () => {
+    for (const recipient of [Keypair.generate().publicKey.toBase58(), '0x1234', 'alice.eth']) {
+      try {
+        await resolveRecipient(recipient, ethereum, connection);
+        expect.fail(`Should have rejected ${recipient}`);
+      } catch (error) {
+        expect(error.message).to.not.include('Should have rejected');
+      }
+    }
+    expect(() => validateRecipientAddress(ethereum, Buffer.alloc(20))).to.throw('zero address');
+  });
+
+  it('Rejects a mistyped EIP-55 checksum', async () => {
+    let address = Wallet.createRandom().address;
+    while (!/[a-f]/.test(address.slice(2)) || !/[A-F]/.test(address)) {
+      address = Wallet.createRandom().address;
+    }
+    const letter = address.slice(2).search(/[a-f]/) + 2;
+    const mistyped = address.slice(0, letter) + address[letter].toUpperCase() + address.slice(letter + 1);
+
+    try {
+      await resolveRecipient(mistyped, ethereum, connection);
+      expect.fail('Should have rejected the bad checksum');
+    } catch (error) {
+      expect(error.message).to.not.include('Should have rejected');
+    }
+  });
+
+  it('Resolves ENS names only through a trusted, current attestation', async () => {
+    const attester = Wallet.createRandom();
+    const attestation = await attest(attester);
+
+    const resolved = await resolveRecipient(attestation, ethereum, connection, [attester.address]);
+    expect(resolved.toString('hex')).to.equal(attestation.address.slice(2).toLowerCase());
+
+    const rejected = [
+      resolveRecipient(attestation, ethereum, connection, [Wallet.createRandom().address]),
+      resolveRecipient(attestation, 137, connection, [attester.address]),
+      resolveRecipient(await attest(attester, { expiresAt: 1 }), ethereum, connection, [attester.address]),
+      resolveRecipient({ ...attestation, address: Wallet.createRandom().address }, ethereum, connection, [attester.address]),
+    ];
+    for (const attempt of rejected) {
+      try {
+        await attempt;
+        expect.fail('Should have rejected the attestation');
+      } catch (error) {
+        expect(error.message).to.not.include('Should have rejected');
+      }
+    }
+  });
+});
@@ -0,0 +1,97 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+} from '@solana/web3.js';
+
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddress,
+  getMint,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import { UniversalNftClient, hashClaimCode } from '../client/src/client';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('burn-to-redeem', () => {
+  const { provider, program, authority, client } = setupProgram();
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const metadataPda = (mint: PublicKey) => pda([Buffer.from("nft_metadata"), mint.toBytes()]);
+
+  const mintNft = async () => {
+    const mint = Keypair.generate();
+    await program.methods
+      .mintNft("https://example.com/ticket.json", "Concert Ticket", "TIX", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        collectionConfig: null,
+        mint: mint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, authority.publicKey),
+        nftMetadata: metadataPda(mint.publicKey),
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+    return mint.publicKey;
+  };
+
+  before(async () => {
+    await ensureInitialized();
+  });
+
+  it('Burns the NFT and records a claim the fulfillment side can check', async () => {
+    const mint = await mintNft();
+    await client.redeemNft(mint, authority.payer, "SECRET-4921", "size: M");
+
+    expect((await getMint(provider.connection, mint)).supply).to.equal(BigInt(0));
+    expect(await provider.connection.getAccountInfo(metadataPda(mint))).to.be.null;
+
+    const redemption = (await client.getRedemption(mint))!;
+    expect(redemption.redeemer).to.equal(authority.publicKey.toString());
+    expect(redemption.claimCodeHash).to.equal(hashClaimCode("SECRET-4921").toString('hex'));
+    expect(redemption.name).to.equal("Concert Ticket");
+    expect(redemption.metadataUri).to.equal("https://example.com/ticket.json");
+    expect(redemption.details).to.equal("size: M");
+    expect(await client.verifyClaimCode(mint, "SECRET-4921")).to.be.true;
+    expect(await client.verifyClaimCode(mint, "SECRET-0000")).to.be.false;
+  });
+
+  it('Rejects an empty claim code hash or oversized details', async () => {
+    const mint = await mintNft();
+    const accounts = {
+      programState: programStatePda,
+      nftMetadata: metadataPda(mint),
+      collectionLedger: null,
+      redemption: client.findRedemptionAddress(mint),
+      mint,
+      tokenAccount: await getAssociatedTokenAddress(mint, authority.publicKey),
+      redeemer: authority.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    };
+
+    for (const [claimCodeHash, details] of [
+      [new Array(32).fill(0), ""],
+      [Array.from(hashClaimCode("code")), "x".repeat(201)],
+    ] as [number[], string][]) {
+      try {
+        await program.methods.redeemNft(claimCodeHash, details).accounts(accounts).rpc();
+        expect.fail("Should have rejected the redemption");
+      } catch (error) {
+        expect(error.message).to.include("InvalidRedemption");
+      }
+    }
+    expect(await client.getRedemption(mint)).to.be.null;
+  });
+});
@@ -0,0 +1,209 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from '@solana/web3.js';
+
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddress,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import * as crypto from 'crypto';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('relayer bonds', () => {
+  const { provider, program, authority } = setupProgram();
+
+  const originChainId = 1; // Ethereum
+  const minBond = anchor.web3.LAMPORTS_PER_SOL / 2;
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const crossChainConfigPda = pda([Buffer.from("cross_chain_config")]);
+  const blocklistPda = pda([Buffer.from("blocklist")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+  const cpiAllowlistPda = pda([Buffer.from("cpi_allowlist")]);
+  const feeTreasuryPda = pda([Buffer.from("fee_treasury")]);
+  const originChainConfigPda = pda([
+    Buffer.from("chain_config"),
+    new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8),
+  ]);
+
+  const auditAccounts = async () => {
+    const auditLog = await program.account.auditLog.fetch(auditLogPda);
+    return {
+      programState: programStatePda,
+      cpiAllowlist: cpiAllowlistPda,
+      auditLog: auditLogPda,
+      auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+      authority: authority.publicKey,
+      systemProgram: SystemProgram.programId,
+      instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+    };
+  };
+
+  before(async () => {
+    await ensureInitialized();
+
+    // Register the origin chain if an earlier test hasn't already
+    try {
+      await program.account.chainConfig.fetch(originChainConfigPda);
+    } catch (error) {
+      await program.methods
+        .registerChain(new anchor.BN(originChainId))
+        .accounts({ ...(await auditAccounts()), chainConfig: originChainConfigPda })
+        .rpc();
+    }
+  });
+
+  const setRequirement = async (withdrawCooldownSeconds: number | null) => {
+    const requirement = withdrawCooldownSeconds === null
+      ? null
+      : { bondMint: null, minBond: new anchor.BN(minBond), withdrawCooldownSeconds };
+    await program.methods
+      .setRelayerRequirement(requirement)
+      .accounts({ ...(await auditAccounts()), crossChainConfig: crossChainConfigPda })
+      .rpc();
+  };
+
+  const relayerPda = (owner: PublicKey) => pda([Buffer.from("relayer"), owner.toBytes()]);
+
+  const withdrawBond = (relayer: Keypair) =>
+    program.methods
+      .withdrawBond()
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: crossChainConfigPda,
+        relayer: relayerPda(relayer.publicKey),
+        relayerBondAccount: null,
+        ownerBondAccount: null,
+        owner: relayer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([relayer])
+      .rpc();
+
+  after(async () => {
+    await setRequirement(null);
+  });
+
+  it('Rejects receives from relayers without a bond', async () => {
+    await setRequirement(3600);
+
+    const originTxHash = crypto.randomBytes(32);
+    const originContract = crypto.randomBytes(20);
+    const originTokenId = crypto.randomBytes(32);
+    const nonce = new anchor.BN(Date.now());
+    const chainIdBytes = new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8);
+    const mint = pda([Buffer.from("inbound_mint"), chainIdBytes, originContract, originTokenId]);
+
+    try {
+      await program.methods
+        .receiveCrossChain(
+          new anchor.BN(originChainId),
+          Array.from(originTxHash),
+          originContract,
+          Array.from(originTokenId),
+          "https://example.com/relayed.json",
+          "Relayed NFT",
+          "RLY",
+          crypto.randomBytes(20),
+          authority.publicKey,
+          crypto.randomBytes(64),
+          nonce
+        )
+        .accounts({
+          programState: programStatePda,
+          crossChainConfig: crossChainConfigPda,
+          blocklist: blocklistPda,
+          cpiAllowlist: cpiAllowlistPda,
+          originChainConfig: originChainConfigPda,
+          receipt: pda([Buffer.from("cross_chain_receipt"), originTxHash, nonce.toArrayLike(Buffer, "le", 8)]),
+          inboundNonce: pda([Buffer.from("inbound_nonce"), chainIdBytes]),
+          mint,
+          freezeAuthority: pda([Buffer.from("freeze_authority")]),
+          tokenAccount: await getAssociatedTokenAddress(mint, authority.publicKey),
+          nftMetadata: pda([Buffer.from("nft_metadata"), mint.toBytes()]),
+          collectionLedger: pda([Buffer.from("collection_ledger"), chainIdBytes, originContract]),
+          foreignCollection: null,
+          postedVaa: null,
+          recipient: authority.publicKey,
+          recipientDeposit: null,
+          relayerRegistration: null,
+          authority: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .rpc();
+      expect.fail("Should have required a bonded relayer");
+    } catch (error) {
+      expect(error.message).to.include("RelayerNotBonded");
+    }
+  });
+
+  it('Bonds a relayer, slashes it, and releases the rest after the cooldown', async () => {
+    await setRequirement(3600);
+
+    const relayer = Keypair.generate();
+    const fundTx = await provider.connection.requestAirdrop(
+      relayer.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(fundTx);
+
+    await program.methods
+      .registerRelayer(new anchor.BN(minBond))
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: crossChainConfigPda,
+        relayer: relayerPda(relayer.publicKey),
+        ownerBondAccount: null,
+        relayerBondAccount: null,
+        owner: relayer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([relayer])
+      .rpc();
+
+    expect((await program.account.relayer.fetch(relayerPda(relayer.publicKey))).bond.toNumber()).to.equal(minBond);
+
+    try {
+      await withdrawBond(relayer);
+      expect.fail("Should have waited for the cooldown");
+    } catch (error) {
+      expect(error.message).to.include("RelayerCooldownActive");
+    }
+
+    const slashAmount = minBond / 5;
+    const treasuryBalance = await provider.connection.getBalance(feeTreasuryPda);
+    await program.methods
+      .slashRelayer(relayer.publicKey, new anchor.BN(slashAmount), Array.from(crypto.randomBytes(32)))
+      .accounts({
+        ...(await auditAccounts()),
+        relayer: relayerPda(relayer.publicKey),
+        feeTreasury: feeTreasuryPda,
+        relayerBondAccount: null,
+        treasuryBondAccount: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const slashed = await program.account.relayer.fetch(relayerPda(relayer.publicKey));
+    expect(slashed.bond.toNumber()).to.equal(minBond - slashAmount);
+    expect(slashed.timesSlashed).to.equal(1);
+    expect(await provider.connection.getBalance(feeTreasuryPda)).to.equal(treasuryBalance + slashAmount);
+
+    await setRequirement(0);
+    await withdrawBond(relayer);
+    expect(await provider.connection.getAccountInfo(relayerPda(relayer.publicKey))).to.be.null;
+  });
+});
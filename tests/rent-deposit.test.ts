@@ -0,0 +1,71 @@
+import * as anchor from '@coral-xyz/anchor';
+import { PublicKey, Keypair, SystemProgram } from '@solana/web3.js';
+
+import { expect } from 'chai';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('rent deposits', () => {
+  const { provider, program, authority } = setupProgram();
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const rentDepositPda = (owner: PublicKey) => pda([Buffer.from("rent_deposit"), owner.toBytes()]);
+
+  const deposit = (owner: Keypair, amount: number) =>
+    program.methods
+      .depositRent(new anchor.BN(amount))
+      .accounts({
+        programState: programStatePda,
+        rentDeposit: rentDepositPda(owner.publicKey),
+        owner: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+
+  const withdraw = (owner: Keypair, amount: number) =>
+    program.methods
+      .withdrawRentDeposit(new anchor.BN(amount))
+      .accounts({
+        programState: programStatePda,
+        rentDeposit: rentDepositPda(owner.publicKey),
+        owner: owner.publicKey,
+      })
+      .signers([owner])
+      .rpc();
+
+  before(async () => {
+    await ensureInitialized();
+  });
+
+  it('Tops up a deposit and withdraws only what it holds', async () => {
+    const recipient = Keypair.generate();
+    const fundTx = await provider.connection.requestAirdrop(
+      recipient.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(fundTx);
+
+    const amount = 10_000_000;
+    await deposit(recipient, amount);
+    await deposit(recipient, amount);
+
+    const funded = await program.account.rentDeposit.fetch(rentDepositPda(recipient.publicKey));
+    expect(funded.owner.toString()).to.equal(recipient.publicKey.toString());
+    expect(funded.balance.toNumber()).to.equal(2 * amount);
+    expect(funded.totalSpent.toNumber()).to.equal(0);
+
+    try {
+      await withdraw(recipient, 2 * amount + 1);
+      expect.fail("Should not withdraw more than the deposit holds");
+    } catch (error) {
+      expect(error.message).to.include("RentDepositInsufficient");
+    }
+
+    const accountBalance = await provider.connection.getBalance(rentDepositPda(recipient.publicKey));
+    await withdraw(recipient, amount);
+    expect((await program.account.rentDeposit.fetch(rentDepositPda(recipient.publicKey))).balance.toNumber())
+      .to.equal(amount);
+    expect(await provider.connection.getBalance(rentDepositPda(recipient.publicKey)))
+      .to.equal(accountBalance - amount);
+  });
+});
@@ -0,0 +1,63 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  Keypair,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+} from '@solana/web3.js';
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddress,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('rent payers', () => {
+  const { provider, program, authority } = setupProgram();
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const blocklistPda = pda([Buffer.from("blocklist")]);
+
+  before(async () => {
+    await ensureInitialized();
+  });
+
+  it('Lets a sponsor fund the rent for an NFT it does not own', async () => {
+    // The creator holds no lamports at all, so every account must be funded by the sponsor
+    const creator = Keypair.generate();
+    const sponsor = Keypair.generate();
+    const fundTx = await provider.connection.requestAirdrop(
+      sponsor.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(fundTx);
+
+    const mint = Keypair.generate();
+    const nftMetadataPda = pda([Buffer.from("nft_metadata"), mint.publicKey.toBytes()]);
+    await program.methods
+      .mintNft("https://example.com/sponsored.json", "Sponsored NFT", "SPON", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: blocklistPda,
+        collectionConfig: null,
+        mint: mint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, creator.publicKey),
+        nftMetadata: nftMetadataPda,
+        authority: creator.publicKey,
+        payer: sponsor.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint, creator, sponsor])
+      .rpc();
+
+    const metadata = await program.account.nftMetadata.fetch(nftMetadataPda);
+    expect(metadata.currentOwner.toString()).to.equal(creator.publicKey.toString());
+    expect(await provider.connection.getBalance(creator.publicKey)).to.equal(0);
+    expect(await provider.connection.getBalance(sponsor.publicKey))
+      .to.be.lessThan(2 * anchor.web3.LAMPORTS_PER_SOL);
+  });
+});
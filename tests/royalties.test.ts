@@ -0,0 +1,103 @@
+import * as anchor from '@coral-xyz/anchor';
+import { PublicKey, Keypair, SystemProgram } from '@solana/web3.js';
+
+import { createMint } from '@solana/spl-token';
+
+import { expect } from 'chai';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('collection royalties', () => {
+  const { provider, program, authority } = setupProgram();
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const royaltyPda = (collectionMint: PublicKey) => pda([Buffer.from("collection_royalty"), collectionMint.toBytes()]);
+
+  let collectionMint: PublicKey;
+
+  const setRoyalty = (basisPoints: number, creators: { address: PublicKey; share: number }[], signer = authority.payer) =>
+    program.methods
+      .setCollectionRoyalty(basisPoints, creators)
+      .accounts({
+        programState: programStatePda,
+        collectionConfig: pda([Buffer.from("collection_config"), collectionMint.toBytes()]),
+        collectionRoyalty: royaltyPda(collectionMint),
+        signer: signer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+
+  before(async () => {
+    await ensureInitialized();
+
+    collectionMint = await createMint(provider.connection, authority.payer, authority.publicKey, null, 0);
+    await program.methods
+      .createCollectionConfig(authority.publicKey)
+      .accounts({
+        programState: programStatePda,
+        collectionConfig: pda([Buffer.from("collection_config"), collectionMint.toBytes()]),
+        collectionMint,
+        symbolRegistry: pda([Buffer.from("symbol_registry")]),
+        collectionMetadata: null,
+        symbolReservation: null,
+        signer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  });
+
+  it('Stores a royalty split between creators, and waives it', async () => {
+    const creators = [
+      { address: Keypair.generate().publicKey, share: 70 },
+      { address: Keypair.generate().publicKey, share: 30 },
+    ];
+    await setRoyalty(500, creators);
+
+    const royalty = await program.account.collectionRoyalty.fetch(royaltyPda(collectionMint));
+    expect(royalty.collectionMint.toString()).to.equal(collectionMint.toString());
+    expect(royalty.basisPoints).to.equal(500);
+    expect(royalty.creators.map((creator) => [creator.address.toString(), creator.share])).to.deep.equal(
+      creators.map((creator) => [creator.address.toString(), creator.share])
+    );
+
+    await setRoyalty(0, []);
+    const waived = await program.account.collectionRoyalty.fetch(royaltyPda(collectionMint));
+    expect(waived.basisPoints).to.equal(0);
+    expect(waived.creators).to.be.empty;
+  });
+
+  it('Rejects splits that do not add up', async () => {
+    const creator = () => Keypair.generate().publicKey;
+    const duplicate = creator();
+    const invalid: [number, { address: PublicKey; share: number }[]][] = [
+      [500, [{ address: creator(), share: 60 }, { address: creator(), share: 30 }]], // Shares sum to 90
+      [500, []], // A royalty with nobody to pay
+      [10_001, [{ address: creator(), share: 100 }]], // More than the sale price
+      [500, [{ address: duplicate, share: 50 }, { address: duplicate, share: 50 }]],
+      [500, [{ address: creator(), share: 100 }, { address: creator(), share: 0 }]],
+      [500, [50, 10, 10, 10, 10, 10].map((share) => ({ address: creator(), share }))], // Six creators
+    ];
+    for (const [basisPoints, creators] of invalid) {
+      try {
+        await setRoyalty(basisPoints, creators);
+        expect.fail(`Should have rejected ${basisPoints} bps over ${creators.length} creators`);
+      } catch (error) {
+        expect(error.message).to.include("InvalidRoyalty");
+      }
+    }
+  });
+
+  it('Only lets the creator or the program authority set it', async () => {
+    const stranger = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(stranger.publicKey, 1_000_000_000),
+      'confirmed'
+    );
+    try {
+      await setRoyalty(500, [{ address: stranger.publicKey, share: 100 }], stranger);
+      expect.fail("Should have rejected a signer that can't manage the collection");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+});
@@ -0,0 +1,88 @@
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionMessage,
+  VersionedTransaction,
+} from '@solana/web3.js';
+import { expect } from 'chai';
+import * as crypto from 'crypto';
+import { LedgerSolanaApp, LedgerWallet } from '../client/src/signers';
+
+describe('hardware wallet signing', () => {
+  // Stands in for a Ledger running the Solana app, holding `keypair`
+  const fakeLedger = (keypair: Keypair) => {
+    const pkcs8Prefix = Buffer.from('302e020100300506032b657004220420', 'hex');
+    const privateKey = crypto.createPrivateKey({
+      key: Buffer.concat([pkcs8Prefix, Buffer.from(keypair.secretKey.subarray(0, 32))]),
+      format: 'der',
+      type: 'pkcs8',
+    });
+    const prompts: string[] = [];
+    const app: LedgerSolanaApp = {
+      getAddress: async () => ({ address: keypair.publicKey.toBuffer() }),
+      signTransaction: async (path, message) => {
+        prompts.push(path);
+        return { signature: crypto.sign(null, message, privateKey) };
+      },
+    };
+    return { app, prompts };
+  };
+
+  const transfer = (from: PublicKey) =>
+    SystemProgram.transfer({ fromPubkey: from, toPubkey: Keypair.generate().publicKey, lamports: 1 });
+  const blockhash = Keypair.generate().publicKey.toBase58();
+
+  it('Signs legacy and versioned transactions on the device', async () => {
+    const keypair = Keypair.generate();
+    const { app, prompts } = fakeLedger(keypair);
+    const wallet = await LedgerWallet.fromApp(app, "44'/501'/1'");
+    expect(wallet.publicKey.equals(keypair.publicKey)).to.be.true;
+
+    const legacy = new Transaction().add(transfer(wallet.publicKey));
+    legacy.feePayer = wallet.publicKey;
+    legacy.recentBlockhash = blockhash;
+
+    const versioned = new VersionedTransaction(
+      new TransactionMessage({
+        payerKey: wallet.publicKey,
+        recentBlockhash: blockhash,
+        instructions: [transfer(wallet.publicKey)],
+      }).compileToV0Message()
+    );
+
+    await wallet.signAllTransactions([legacy, versioned]);
+    expect(legacy.verifySignatures()).to.be.true;
+    expect(
+      crypto.verify(
+        null,
+        Buffer.from(versioned.message.serialize()),
+        crypto.createPublicKey({
+          key: Buffer.concat([Buffer.from('302a300506032b6570032100', 'hex'), keypair.publicKey.toBuffer()]),
+          format: 'der',
+          type: 'spki',
+        }),
+        Buffer.from(versioned.signatures[0])
+      )
+    ).to.be.true;
+    // One confirmation per transaction, on the requested account
+    expect(prompts).to.deep.equal(["44'/501'/1'", "44'/501'/1'"]);
+  });
+
+  it('Refuses to sign for a key the device does not hold', async () => {
+    const { app } = fakeLedger(Keypair.generate());
+    const wallet = await LedgerWallet.fromApp(app);
+    const other = Keypair.generate().publicKey;
+
+    const tx = new Transaction().add(transfer(other));
+    tx.feePayer = other;
+    tx.recentBlockhash = blockhash;
+    try {
+      await wallet.signTransaction(tx);
+      expect.fail("Should have rejected a transaction the Ledger key doesn't sign");
+    } catch (error) {
+      expect(error.message).to.include('unknown signer');
+    }
+  });
+});
@@ -0,0 +1,476 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  ComputeBudgetProgram,
+  LAMPORTS_PER_SOL,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from '@solana/web3.js';
+
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createMint,
+  getAssociatedTokenAddress,
+  getAccount,
+  getMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  transfer,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import * as crypto from 'crypto';
+import { signTransferPermit } from '../client/src/permit';
+import { setupProgram, ensureInitialized, pda, crossChainTransferAccounts, SOLANA_CHAIN_ID } from './helpers';
+
+describe('staking rewards', () => {
+  const { provider, program, authority, client } = setupProgram();
+
+  const destinationChainId = 1; // Ethereum
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+  const cpiAllowlistPda = pda([Buffer.from("cpi_allowlist")]);
+  const destinationChainConfigPda = pda([
+    Buffer.from("chain_config"),
+    new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8),
+  ]);
+  const metadataPda = (mint: PublicKey) => pda([Buffer.from("nft_metadata"), mint.toBytes()]);
+  const collectionConfigPda = (collectionMint: PublicKey) =>
+    pda([Buffer.from("collection_config"), collectionMint.toBytes()]);
+
+  const emission = 1_000;
+  let holder: Keypair;
+
+  const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+  const rewardBalance = async (owner: PublicKey) =>
+    (await getAccount(provider.connection, await getAssociatedTokenAddress(client.findRewardMintAddress(), owner))).amount;
+
+  const createCollection = async () => {
+    const collectionMint = await createMint(provider.connection, authority.payer, authority.publicKey, null, 0);
+    await program.methods
+      .createCollectionConfig(authority.publicKey)
+      .accounts({
+        programState: programStatePda,
+        collectionConfig: collectionConfigPda(collectionMint),
+        collectionMint,
+        symbolRegistry: pda([Buffer.from("symbol_registry")]),
+        collectionMetadata: null,
+        symbolReservation: null,
+        signer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    return collectionMint;
+  };
+
+  const mintNft = async (collectionMint: PublicKey | null = null) => {
+    const mint = Keypair.generate();
+    await program.methods
+      .mintNft("https://example.com/staked.json", "Staked NFT", "STAKE", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        collectionConfig: collectionMint ? collectionConfigPda(collectionMint) : null,
+        mint: mint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, authority.publicKey),
+        nftMetadata: metadataPda(mint.publicKey),
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+    return mint.publicKey;
+  };
+
+  const nextNonce = async () => {
+    const config = await program.account.crossChainConfig.fetch(pda([Buffer.from("cross_chain_config")]));
+    return Math.max(config.nonceCounter.toNumber() + 1, Date.now());
+  };
+
+  const transferOut = async (mint: PublicKey, overrides: Record<string, PublicKey | null> = {}) => {
+    const nonce = new anchor.BN(await nextNonce());
+    return program.methods
+      .crossChainTransfer(new anchor.BN(destinationChainId), crypto.randomBytes(20), nonce)
+      .accounts(
+        crossChainTransferAccounts(
+          { mint, tokenAccount: await getAssociatedTokenAddress(mint, authority.publicKey), owner: authority.publicKey, destinationChainId, nonce },
+          overrides
+        )
+      )
+      .rpc();
+  };
+
+  const transferOutWithPermit = async (mint: PublicKey) => {
+    const permit = {
+      mint,
+      owner: authority.publicKey,
+      destinationChainId,
+      recipientAddress: crypto.randomBytes(20),
+      nonce: await nextNonce(),
+      deadline: Math.floor(Date.now() / 1000) + 600,
+    };
+    const signature = signTransferPermit(authority.payer, await client.buildTransferPermit(permit));
+    return client.crossChainTransferWithPermit(permit, signature, authority.payer);
+  };
+
+  const transferOutWithValue = async (mint: PublicKey) => {
+    const valueMint = await createMint(provider.connection, authority.payer, authority.publicKey, null, 0);
+    const ownerValueAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      authority.payer,
+      valueMint,
+      authority.publicKey
+    );
+    await mintTo(provider.connection, authority.payer, valueMint, ownerValueAccount.address, authority.payer, 10);
+
+    const nonce = new anchor.BN(await nextNonce());
+    // Same accounts as a plain transfer
+    const accounts = crossChainTransferAccounts({
+      mint,
+      tokenAccount: await getAssociatedTokenAddress(mint, authority.publicKey),
+      owner: authority.publicKey,
+      destinationChainId,
+      nonce,
+    });
+    return program.methods
+      .crossChainTransferWithValue(new anchor.BN(destinationChainId), crypto.randomBytes(20), nonce, new anchor.BN(10))
+      .accounts({
+        ...accounts,
+        valueMint,
+        ownerValueAccount: ownerValueAccount.address,
+        valueEscrow: pda([Buffer.from("value_escrow"), accounts.transferRecord!.toBytes()]),
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+  };
+
+  const transferOutBatch = async (mints: PublicKey[]) => {
+    const nonce = new anchor.BN(await nextNonce());
+    const remainingAccounts = [];
+    for (const mint of mints) {
+      remainingAccounts.push(
+        { pubkey: mint, isSigner: false, isWritable: false },
+        { pubkey: await getAssociatedTokenAddress(mint, authority.publicKey), isSigner: false, isWritable: false },
+        { pubkey: metadataPda(mint), isSigner: false, isWritable: true },
+        { pubkey: client.findStakeRecordAddress(mint), isSigner: false, isWritable: true }
+      );
+    }
+    return program.methods
+      .crossChainTransferBatch(new anchor.BN(destinationChainId), crypto.randomBytes(20), nonce, mints.length)
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: pda([Buffer.from("cross_chain_config")]),
+        blocklist: pda([Buffer.from("blocklist")]),
+        optOutRegistry: pda([Buffer.from("opt_out_registry")]),
+        destinationChainConfig: destinationChainConfigPda,
+        collectionLedger: pda([
+          Buffer.from("collection_ledger"),
+          new anchor.BN(SOLANA_CHAIN_ID).toArrayLike(Buffer, "le", 8),
+        ]),
+        collectionConfig: null,
+        bundleRecord: pda([
+          Buffer.from("bundle_transfer"),
+          authority.publicKey.toBytes(),
+          nonce.toArrayLike(Buffer, "le", 8),
+        ]),
+        owner: authority.publicKey,
+        ownerTransferIndex: pda([Buffer.from("owner_transfer_index"), authority.publicKey.toBytes()]),
+        pendingTransfers: pda([Buffer.from("pending_transfers")]),
+        feeReserve: pda([Buffer.from("fee_reserve")]),
+        payer: authority.publicKey,
+        credential: null,
+        gateTokenAccount: null,
+        feeTreasury: null,
+        payerFeeAccount: null,
+        treasuryFeeAccount: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .remainingAccounts(remainingAccounts)
+      .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 })])
+      .rpc();
+    return nonce.toNumber();
+  };
+
+  before(async () => {
+    await ensureInitialized();
+
+    try {
+      await program.account.chainConfig.fetch(destinationChainConfigPda);
+    } catch (error) {
+      const auditLog = await program.account.auditLog.fetch(auditLogPda);
+      await program.methods
+        .registerChain(new anchor.BN(destinationChainId))
+        .accounts({
+          programState: programStatePda,
+          chainConfig: destinationChainConfigPda,
+          cpiAllowlist: cpiAllowlistPda,
+          auditLog: auditLogPda,
+          auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .rpc();
+    }
+
+    holder = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(holder.publicKey, LAMPORTS_PER_SOL),
+      'confirmed'
+    );
+  });
+
+  it('Creates the reward mint under the reward config and validates the schedule', async () => {
+    await client.setRewardConfig(new anchor.BN(emission), 1);
+
+    const rewardMint = await getMint(provider.connection, client.findRewardMintAddress());
+    expect(rewardMint.mintAuthority!.toString()).to.equal(client.findRewardConfigAddress().toString());
+    expect(rewardMint.decimals).to.equal(9);
+    const config = await program.account.rewardConfig.fetch(client.findRewardConfigAddress());
+    expect(config.emissionPerEpoch.toNumber()).to.equal(emission);
+    expect(config.epochSeconds).to.equal(1);
+
+    try {
+      await client.setRewardConfig(new anchor.BN(emission), 0);
+      expect.fail("Should have rejected a zero-length epoch");
+    } catch (error) {
+      expect(error.message).to.include("InvalidRewardConfig");
+    }
+    for (const multiplierBps of [9_999, 100_001]) {
+      try {
+        await client.setRewardMultiplier(Keypair.generate().publicKey, multiplierBps);
+        expect.fail("Should have rejected an out-of-range multiplier");
+      } catch (error) {
+        expect(error.message).to.include("InvalidRewardConfig");
+      }
+    }
+  });
+
+  it('Pays whole epochs only, and boosts collections with a multiplier', async () => {
+    await client.setRewardConfig(new anchor.BN(emission), 1);
+    const collectionMint = await createCollection();
+    await client.setRewardMultiplier(collectionMint, 30_000);
+
+    const plain = await mintNft();
+    const boosted = await mintNft(collectionMint);
+    await client.stakeNft(plain, authority.payer);
+    await client.stakeNft(boosted, authority.payer);
+    expect((await client.getStakeRecord(boosted))!.multiplierBps).to.equal(30_000);
+    await sleep(3_000);
+
+    const before = await rewardBalance(authority.publicKey).catch(() => BigInt(0));
+    await client.claimRewards(plain, authority.payer);
+    const plainReward = (await rewardBalance(authority.publicKey)) - before;
+    await client.claimRewards(boosted, authority.payer);
+    const boostedReward = (await rewardBalance(authority.publicKey)) - before - plainReward;
+
+    expect(plainReward > BigInt(0)).to.be.true;
+    expect(plainReward % BigInt(emission)).to.equal(BigInt(0));
+    expect(boostedReward % BigInt(3 * emission)).to.equal(BigInt(0));
+    expect(boostedReward >= BigInt(3) * plainReward).to.be.true;
+    expect((await client.getStakeRecord(plain))!.totalClaimed).to.equal(plainReward.toString());
+
+    // A partial epoch isn't paid out
+    await client.setRewardConfig(new anchor.BN(emission), 3_600);
+    try {
+      await client.claimRewards(plain, authority.payer);
+      expect.fail("Should have had no whole epoch to pay");
+    } catch (error) {
+      expect(error.message).to.include("NoRewardsAccrued");
+    }
+  });
+
+  it('Stops paying once the NFT leaves the wallet or is bridged', async () => {
+    await client.setRewardConfig(new anchor.BN(emission), 1);
+    const mint = await mintNft();
+    await client.stakeNft(mint, authority.payer);
+    try {
+      await client.stakeNft(mint, authority.payer);
+      expect.fail("Should have rejected staking twice");
+    } catch (error) {
+      expect(error.message).to.include("NftAlreadyStaked");
+    }
+
+    // Hand the NFT over; the new holder takes over the stake
+    const destination = await getOrCreateAssociatedTokenAccount(provider.connection, authority.payer, mint, holder.publicKey);
+    await transfer(
+      provider.connection,
+      authority.payer,
+      await getAssociatedTokenAddress(mint, authority.publicKey),
+      destination.address,
+      authority.payer,
+      1
+    );
+    await sleep(2_000);
+    try {
+      await client.claimRewards(mint, authority.payer);
+      expect.fail("Should have required holding the NFT");
+    } catch (error) {
+      expect(error.message).to.include("InsufficientTokens");
+    }
+    await client.stakeNft(mint, holder);
+    expect((await client.getStakeRecord(mint))!.owner).to.equal(holder.publicKey.toString());
+
+    // A bridged NFT can't claim while it is away
+    const bridged = await mintNft();
+    await client.stakeNft(bridged, authority.payer);
+    await transferOut(bridged);
+    await sleep(2_000);
+    try {
+      await client.claimRewards(bridged, authority.payer);
+      expect.fail("Should have refused a claim for a locked NFT");
+    } catch (error) {
+      expect(error.message).to.include("NftLocked");
+    }
+
+    await client.unstakeNft(bridged, authority.payer);
+    expect(await client.getStakeRecord(bridged)).to.be.null;
+  });
+
+  it('Checkpoints the stake on every outbound path', async () => {
+    await client.setRewardConfig(new anchor.BN(emission), 1);
+    const stakedNft = async () => {
+      const mint = await mintNft();
+      await client.stakeNft(mint, authority.payer);
+      return mint;
+    };
+
+    const viaTransfer = await stakedNft();
+    const viaPermit = await stakedNft();
+    const viaValue = await stakedNft();
+    const viaBatch = [await stakedNft(), await stakedNft()];
+    await sleep(2_000);
+
+    await transferOut(viaTransfer);
+    await transferOutWithPermit(viaPermit);
+    await transferOutWithValue(viaValue);
+    await transferOutBatch(viaBatch);
+
+    for (const mint of [viaTransfer, viaPermit, viaValue, ...viaBatch]) {
+      const stake = (await client.getStakeRecord(mint))!;
+      expect(stake.bridgedAt).to.not.equal("0");
+      expect(stake.pendingRewards).to.equal("0");
+    }
+
+    // The stake PDA can't be swapped out to keep a stake accruing
+    const dodging = await stakedNft();
+    try {
+      await transferOut(dodging, { stakeRecord: Keypair.generate().publicKey });
+      expect.fail("Should have required the NFT's stake PDA");
+    } catch (error) {
+      expect(error.message).to.include("ConstraintSeeds");
+    }
+    expect((await client.getStakeRecord(dodging))!.bridgedAt).to.equal("0");
+  });
+
+  it('Carries staking time across a bridge round trip', async () => {
+    await client.setRewardConfig(new anchor.BN(emission), 1);
+    const mint = await mintNft();
+    await client.stakeNft(mint, authority.payer);
+    await sleep(2_000);
+
+    // Bridging out with the stake checkpoints it instead of forfeiting it
+    await transferOut(mint);
+    const checkpointed = (await client.getStakeRecord(mint))!;
+    expect(checkpointed.bridgedAt).to.not.equal("0");
+    expect(checkpointed.pendingRewards).to.equal("0");
+
+    // The return credits back what the stake earned on the other chain
+    const credit = 3_600;
+    await client.stageNativeReturn(
+      mint,
+      destinationChainId,
+      crypto.randomBytes(32),
+      Date.now(),
+      authority.publicKey,
+      crypto.randomBytes(64),
+      credit
+    );
+    await client.executeNativeReturn(mint);
+
+    const resumed = (await client.getStakeRecord(mint))!;
+    expect(resumed.bridgedAt).to.equal("0");
+    const now = Math.floor(Date.now() / 1000);
+    expect(now - Number(resumed.lastClaimedAt)).to.be.at.least(credit);
+    expect(Number(resumed.pendingRewards)).to.be.at.least(credit * emission);
+
+    await client.claimRewards(mint, authority.payer);
+    expect(Number((await client.getStakeRecord(mint))!.totalClaimed)).to.be.at.least(credit * emission);
+  });
+
+  it('Gives the stake back when a bridge fails', async () => {
+    await client.setRewardConfig(new anchor.BN(emission), 1);
+    const mint = await mintNft();
+    await client.stakeNft(mint, authority.payer);
+    await sleep(2_000);
+
+    const nonce = new anchor.BN(await nextNonce());
+    await program.methods
+      .crossChainTransfer(new anchor.BN(destinationChainId), crypto.randomBytes(20), nonce)
+      .accounts(
+        crossChainTransferAccounts({
+          mint,
+          tokenAccount: await getAssociatedTokenAddress(mint, authority.publicKey),
+          owner: authority.publicKey,
+          destinationChainId,
+          nonce,
+        })
+      )
+      .rpc();
+    const transferRecord = client.findTransferRecordAddress(mint, nonce.toNumber());
+    const staked = (await program.account.crossChainTransfer.fetch(transferRecord)).stakedSeconds.toNumber();
+    expect(staked).to.be.at.least(2);
+
+    // The TSS attests the transfer will never arrive; the time staked before it left is credited back
+    await client.acknowledgeFailure(mint, nonce.toNumber(), crypto.randomBytes(64));
+    const resumed = (await client.getStakeRecord(mint))!;
+    expect(resumed.bridgedAt).to.equal("0");
+    expect(Math.floor(Date.now() / 1000) - Number(resumed.lastClaimedAt)).to.be.at.least(staked);
+
+    await client.claimRewards(mint, authority.payer);
+    expect(Number((await client.getStakeRecord(mint))!.totalClaimed)).to.be.at.least(staked * emission);
+  });
+
+  it('Unlocks a whole bundle and gives its stakes back when it is reverted', async () => {
+    await client.setRewardConfig(new anchor.BN(emission), 1);
+    const mints = [await mintNft(), await mintNft()];
+    for (const mint of mints) {
+      await client.stakeNft(mint, authority.payer);
+    }
+    await sleep(2_000);
+
+    const nonce = await transferOutBatch(mints);
+    const bundleRecord = client.findBundleRecordAddress(authority.publicKey, nonce);
+    const bundle = await program.account.bundleTransfer.fetch(bundleRecord);
+    expect(bundle.stakedSeconds.map((seconds) => seconds.toNumber())).to.have.lengthOf(mints.length);
+    for (const mint of mints) {
+      expect((await program.account.nftMetadata.fetch(metadataPda(mint))).isLocked).to.be.true;
+    }
+
+    await client.revertBundleTransfer(authority.publicKey, nonce);
+
+    expect((await program.account.bundleTransfer.fetch(bundleRecord)).status).to.equal(2);
+    for (const mint of mints) {
+      expect((await program.account.nftMetadata.fetch(metadataPda(mint))).isLocked).to.be.false;
+      expect((await client.getStakeRecord(mint))!.bridgedAt).to.equal("0");
+    }
+
+    // A settled bundle can't be reverted twice
+    try {
+      await client.revertBundleTransfer(authority.publicKey, nonce);
+      expect.fail("Should have rejected a settled bundle");
+    } catch (error) {
+      expect(error.message).to.include("TransferNotPending");
+    }
+  });
+});
@@ -0,0 +1,46 @@
+import * as anchor from '@coral-xyz/anchor';
+import { PublicKey } from '@solana/web3.js';
+import { expect } from 'chai';
+import * as crypto from 'crypto';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('state export', () => {
+  const { provider, program, authority } = setupProgram();
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+
+  const exportPage = (page: number, accounts: PublicKey[]) =>
+    program.methods
+      .exportState(page)
+      .accounts({ programState: programStatePda })
+      .remainingAccounts(accounts.map((pubkey) => ({ pubkey, isWritable: false, isSigner: false })))
+      .view();
+
+  before(async () => {
+    await ensureInitialized();
+  });
+
+  it('Attests the type and data hash of each account in a page', async () => {
+    const accounts = [programStatePda, pda([Buffer.from("cross_chain_config")])];
+    const page = await exportPage(3, accounts);
+
+    expect(page.page).to.equal(3);
+    expect(page.accounts.map((entry) => entry.address.toString())).to.deep.equal(accounts.map(String));
+
+    for (const entry of page.accounts) {
+      const info = await provider.connection.getAccountInfo(entry.address);
+      const dataHash = crypto.createHash('sha256').update(info!.data).digest();
+      expect(Buffer.from(entry.dataHash).equals(dataHash)).to.be.true;
+      expect(Buffer.from(entry.discriminator).equals(info!.data.subarray(0, 8))).to.be.true;
+    }
+  });
+
+  it('Rejects accounts the program does not own', async () => {
+    try {
+      await exportPage(0, [authority.publicKey]);
+      expect.fail("Should have rejected a wallet account");
+    } catch (error) {
+      expect(error.message).to.include("InvalidExportPage");
+    }
+  });
+});
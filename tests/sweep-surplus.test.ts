@@ -0,0 +1,109 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  Transaction,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from '@solana/web3.js';
+
+import { expect } from 'chai';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('surplus lamport sweeps', () => {
+  const { provider, program, authority } = setupProgram();
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const crossChainConfigPda = pda([Buffer.from("cross_chain_config")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+  const cpiAllowlistPda = pda([Buffer.from("cpi_allowlist")]);
+  const feeTreasuryPda = pda([Buffer.from("fee_treasury")]);
+
+  const sweep = async (target: object, targetAccount: PublicKey, recipient: PublicKey) => {
+    const auditLog = await program.account.auditLog.fetch(auditLogPda);
+    return program.methods
+      .sweepSurplusLamports(target as any)
+      .accounts({
+        programState: programStatePda,
+        targetAccount,
+        recipient,
+        cpiAllowlist: cpiAllowlistPda,
+        auditLog: auditLogPda,
+        auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  };
+
+  before(async () => {
+    await ensureInitialized();
+  });
+
+  it('Sweeps lamports sent to the cross-chain config by mistake', async () => {
+    const stray = 5_000_000;
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: authority.publicKey,
+          toPubkey: crossChainConfigPda,
+          lamports: stray,
+        })
+      )
+    );
+
+    const info = await provider.connection.getAccountInfo(crossChainConfigPda);
+    const minimum = await provider.connection.getMinimumBalanceForRentExemption(info.data.length);
+    const recipient = Keypair.generate().publicKey;
+    await sweep({ crossChainConfig: {} }, crossChainConfigPda, recipient);
+
+    expect(await provider.connection.getBalance(recipient)).to.equal(info.lamports - minimum);
+    expect(await provider.connection.getBalance(crossChainConfigPda)).to.equal(minimum);
+
+    const auditLog = await program.account.auditLog.fetch(auditLogPda);
+    const entry = await program.account.auditEntry.fetch(
+      pda([Buffer.from("audit_entry"), auditLog.entryCount.subn(1).toArrayLike(Buffer, "le", 8)])
+    );
+    expect(entry.action).to.deep.equal({ sweepSurplusLamports: {} });
+
+    try {
+      await sweep({ crossChainConfig: {} }, crossChainConfigPda, recipient);
+      expect.fail("Should have found nothing left to sweep");
+    } catch (error) {
+      expect(error.message).to.include("NoSurplusLamports");
+    }
+  });
+
+  it('Rejects an account that is not the named PDA', async () => {
+    try {
+      await sweep({ programState: {} }, crossChainConfigPda, authority.publicKey);
+      expect.fail("Should have rejected the mismatched target");
+    } catch (error) {
+      expect(error.message).to.include("InvalidSweepTarget");
+    }
+  });
+
+  it('Leaves the fee treasury to withdraw_fees', async () => {
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: authority.publicKey,
+          toPubkey: feeTreasuryPda,
+          lamports: 5_000_000,
+        })
+      )
+    );
+    const balance = await provider.connection.getBalance(feeTreasuryPda);
+
+    for (const target of [{ programState: {} }, { auditLog: {} }]) {
+      try {
+        await sweep(target, feeTreasuryPda, authority.publicKey);
+        expect.fail("Should have refused to sweep the fee treasury");
+      } catch (error) {
+        expect(error.message).to.include("InvalidSweepTarget");
+      }
+    }
+    expect(await provider.connection.getBalance(feeTreasuryPda)).to.equal(balance);
+  });
+});
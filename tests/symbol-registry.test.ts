@@ -0,0 +1,233 @@
+import * as anchor from '@coral-xyz/anchor';
+import { PublicKey, Keypair, SystemProgram, SYSVAR_RENT_PUBKEY, SYSVAR_INSTRUCTIONS_PUBKEY } from '@solana/web3.js';
+
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from '@solana/spl-token';
+
+import { expect } from 'chai';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+import { symbolSkeleton } from '../client/src/client';
+
+describe('symbol registry', () => {
+  const { provider, program, authority, client } = setupProgram();
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const randomSymbol = () => `S${Math.floor(Math.random() * 1_000_000)}`;
+
+  // A collection mint carrying its symbol in this program's metadata
+  const createCollection = async (creator: PublicKey, symbol: string, reserve = false) => {
+    const collectionMint = Keypair.generate();
+    const collectionMetadata = pda([Buffer.from("nft_metadata"), collectionMint.publicKey.toBytes()]);
+    await program.methods
+      .mintNft("https://example.com/collection.json", "Collection", symbol, true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        collectionConfig: null,
+        mint: collectionMint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(collectionMint.publicKey, authority.publicKey),
+        nftMetadata: collectionMetadata,
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([collectionMint])
+      .rpc();
+
+    await program.methods
+      .createCollectionConfig(creator)
+      .accounts({
+        programState: programStatePda,
+        collectionConfig: client.findCollectionConfigAddress(collectionMint.publicKey),
+        collectionMint: collectionMint.publicKey,
+        symbolRegistry: client.findSymbolRegistryAddress(),
+        collectionMetadata,
+        symbolReservation: reserve ? client.findSymbolReservationAddress(symbol) : null,
+        signer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    return collectionMint.publicKey;
+  };
+
+  const registerForeign = (originChainId: number, symbol: string, collectionMint: PublicKey, reserve = true) => {
+    const originContract = Keypair.generate().publicKey.toBuffer().subarray(0, 20);
+    const chainSeed = new anchor.BN(originChainId).toArrayLike(Buffer, "le", 8);
+    return program.methods
+      .registerForeignCollection(
+        new anchor.BN(originChainId),
+        originContract,
+        "Bridged Apes",
+        symbol,
+        0,
+        Buffer.alloc(0),
+        collectionMint,
+        null
+      )
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: pda([Buffer.from("cross_chain_config")]),
+        foreignCollection: pda([Buffer.from("foreign_collection"), chainSeed, originContract]),
+        collectionLedger: pda([Buffer.from("collection_ledger"), chainSeed, originContract]),
+        symbolRegistry: client.findSymbolRegistryAddress(),
+        symbolReservation: reserve ? client.findSymbolReservationAddress(symbol) : null,
+        payer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  };
+
+  before(async () => {
+    await ensureInitialized();
+  });
+
+  after(async () => {
+    await client.setSymbolRegistry(false);
+  });
+
+  it('Reserves a collection\'s own symbol for the first creator only', async () => {
+    const alice = Keypair.generate().publicKey;
+    const symbol = randomSymbol();
+    const first = await createCollection(alice, symbol);
+    const second = await createCollection(alice, symbol);
+    const impostor = await createCollection(Keypair.generate().publicKey, ` ${symbol.toLowerCase()}`);
+
+    await client.reserveCollectionSymbol(first);
+    try {
+      await client.reserveCollectionSymbol(impostor);
+      expect.fail("Should have rejected another creator's collection");
+    } catch (error) {
+      expect(error.message).to.include("SymbolReserved");
+    }
+    await client.reserveCollectionSymbol(second);
+
+    const reservation = (await client.getSymbolReservation(symbol.toLowerCase()))!;
+    expect(reservation).to.include({ symbol, creator: alice.toString(), collectionMint: first.toString() });
+  });
+
+  it('Folds lookalike characters together', async () => {
+    expect(symbolSkeleton(' bayc ')).to.equal('BAYC');
+    expect(symbolSkeleton('BAYС')).to.equal('BAYC'); // Cyrillic С
+    expect(symbolSkeleton('ＢＡＹＣ')).to.equal('BAYC'); // Full-width
+    expect(symbolSkeleton('C00L')).to.equal(symbolSkeleton('cOoI'));
+    expect(symbolSkeleton('5ol')).to.equal(symbolSkeleton('SOI'));
+
+    const symbol = randomSymbol();
+    await createCollection(Keypair.generate().publicKey, symbol, true);
+    const lookalike = symbol.replace('S', '5');
+    try {
+      await createCollection(Keypair.generate().publicKey, lookalike, true);
+      expect.fail("Should have rejected a lookalike symbol");
+    } catch (error) {
+      expect(error.message).to.include("SymbolReserved");
+    }
+  });
+
+  it('Makes native collections reserve their symbol while enabled', async () => {
+    await client.setSymbolRegistry(true);
+    const alice = Keypair.generate().publicKey;
+    const symbol = randomSymbol();
+
+    try {
+      await createCollection(alice, symbol);
+      expect.fail("Should have required the reservation");
+    } catch (error) {
+      expect(error.message).to.include("SymbolReservationRequired");
+    }
+
+    const collectionMint = await createCollection(alice, symbol, true);
+    await createCollection(alice, symbol, true); // The same creator's next collection
+    try {
+      await createCollection(Keypair.generate().publicKey, symbol.toLowerCase(), true);
+      expect.fail("Should have rejected another creator's collection");
+    } catch (error) {
+      expect(error.message).to.include("SymbolReserved");
+    }
+    expect(await client.getSymbolReservation(symbol)).to.include({
+      creator: alice.toString(),
+      collectionMint: collectionMint.toString(),
+    });
+
+    await client.setSymbolRegistry(false);
+  });
+
+  it('Makes foreign collections reserve their symbol while enabled', async () => {
+    await client.setSymbolRegistry(true);
+    const symbol = randomSymbol();
+    const collectionMint = Keypair.generate().publicKey;
+
+    try {
+      await registerForeign(1, symbol, collectionMint, false);
+      expect.fail("Should have required the reservation");
+    } catch (error) {
+      expect(error.message).to.include("SymbolReservationRequired");
+    }
+
+    await registerForeign(1, symbol, collectionMint);
+    await registerForeign(8453, symbol, collectionMint); // The same collection from another chain
+    try {
+      await registerForeign(137, symbol, Keypair.generate().publicKey);
+      expect.fail("Should have rejected a lookalike collection");
+    } catch (error) {
+      expect(error.message).to.include("SymbolReserved");
+    }
+    expect(await client.getSymbolReservation(symbol)).to.include({ creator: collectionMint.toString() });
+
+    await client.setSymbolRegistry(false);
+    await registerForeign(137, randomSymbol(), Keypair.generate().publicKey, false);
+  });
+
+  it('Lets the authority reassign or release a symbol', async () => {
+    const squatter = Keypair.generate().publicKey;
+    const owner = Keypair.generate().publicKey;
+    const symbol = randomSymbol();
+    await createCollection(squatter, symbol, true);
+    const collectionMint = await createCollection(owner, symbol);
+
+    const outsider = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(outsider.publicKey, 1_000_000_000),
+      'confirmed'
+    );
+    try {
+      const auditLog = await program.account.auditLog.fetch(client.findAuditLogAddress());
+      await program.methods
+        .reassignCollectionSymbol(symbol, owner, collectionMint)
+        .accounts({
+          programState: programStatePda,
+          symbolReservation: client.findSymbolReservationAddress(symbol),
+          cpiAllowlist: client.findCpiAllowlistAddress(),
+          auditLog: client.findAuditLogAddress(),
+          auditEntry: client.findAuditEntryAddress(auditLog.entryCount.toNumber()),
+          authority: outsider.publicKey,
+          systemProgram: SystemProgram.programId,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .signers([outsider])
+        .rpc();
+      expect.fail("Only the program authority may reassign a symbol");
+    } catch (error) {
+      expect(error.message).to.include("Unauthorized");
+    }
+
+    await client.reassignCollectionSymbol(symbol, owner, collectionMint);
+    expect(await client.getSymbolReservation(symbol)).to.include({
+      creator: owner.toString(),
+      collectionMint: collectionMint.toString(),
+    });
+    await client.reserveCollectionSymbol(collectionMint);
+    try {
+      await createCollection(squatter, symbol, true);
+      expect.fail("The squatter should have lost the symbol");
+    } catch (error) {
+      expect(error.message).to.include("SymbolReserved");
+    }
+
+    await client.reassignCollectionSymbol(symbol, PublicKey.default, PublicKey.default);
+    const newcomer = Keypair.generate().publicKey;
+    await createCollection(newcomer, symbol, true);
+    expect(await client.getSymbolReservation(symbol)).to.include({ creator: newcomer.toString() });
+  });
+});
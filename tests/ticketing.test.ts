@@ -0,0 +1,140 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+} from '@solana/web3.js';
+
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddress,
+  getOrCreateAssociatedTokenAccount,
+  transfer,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('ticketing', () => {
+  const { provider, program, authority, client } = setupProgram();
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const metadataPda = (mint: PublicKey) => pda([Buffer.from("nft_metadata"), mint.toBytes()]);
+  const collectionConfigPda = (collectionMint: PublicKey) =>
+    pda([Buffer.from("collection_config"), collectionMint.toBytes()]);
+
+  const createCollection = async () => {
+    const collectionMint = await createMint(provider.connection, authority.payer, authority.publicKey, null, 0);
+    await program.methods
+      .createCollectionConfig(authority.publicKey)
+      .accounts({
+        programState: programStatePda,
+        collectionConfig: collectionConfigPda(collectionMint),
+        collectionMint,
+        symbolRegistry: pda([Buffer.from("symbol_registry")]),
+        collectionMetadata: null,
+        symbolReservation: null,
+        signer: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    return collectionMint;
+  };
+
+  const mintTicket = async (collectionMint: PublicKey) => {
+    const mint = Keypair.generate();
+    await program.methods
+      .mintNft("https://example.com/ticket.json", "Gate A Ticket", "TIX", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        collectionConfig: collectionConfigPda(collectionMint),
+        mint: mint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, authority.publicKey),
+        nftMetadata: metadataPda(mint.publicKey),
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+    return mint.publicKey;
+  };
+
+  before(async () => {
+    await ensureInitialized();
+  });
+
+  it('Checks a ticket in once and locks it in place', async () => {
+    const collectionMint = await createCollection();
+    await client.setTicketConfig(collectionMint, true);
+    const mint = await mintTicket(collectionMint);
+
+    await client.checkIn(mint, authority.payer);
+    const checkIn = (await client.getTicketCheckIn(mint))!;
+    expect(checkIn.holder).to.equal(authority.publicKey.toString());
+    expect(checkIn.validator).to.be.null;
+    expect((await program.account.nftMetadata.fetch(metadataPda(mint))).crossChainEnabled).to.be.false;
+
+    const tokenAccount = await getAssociatedTokenAddress(mint, authority.publicKey);
+    expect((await getAccount(provider.connection, tokenAccount)).isFrozen).to.be.true;
+    const destination = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      authority.payer,
+      mint,
+      Keypair.generate().publicKey
+    );
+    try {
+      await transfer(provider.connection, authority.payer, tokenAccount, destination.address, authority.payer, 1);
+      expect.fail("Should have kept a used ticket in place");
+    } catch (error) {
+      expect(error.message).to.include("custom program error: 0x11"); // spl-token AccountFrozen
+    }
+  });
+
+  it('Requires a listed validator when the collection has any', async () => {
+    const collectionMint = await createCollection();
+    const validator = Keypair.generate();
+    await client.setTicketConfig(collectionMint, false, [validator.publicKey]);
+    const mint = await mintTicket(collectionMint);
+
+    for (const signer of [null, Keypair.generate()]) {
+      try {
+        await client.checkIn(mint, authority.payer, signer);
+        expect.fail("Should have required a validator");
+      } catch (error) {
+        expect(error.message).to.include("ValidatorRequired");
+      }
+    }
+
+    await client.checkIn(mint, authority.payer, validator);
+    expect((await client.getTicketCheckIn(mint))!.validator).to.equal(validator.publicKey.toString());
+    const tokenAccount = await getAssociatedTokenAddress(mint, authority.publicKey);
+    expect((await getAccount(provider.connection, tokenAccount)).isFrozen).to.be.false;
+
+    try {
+      await client.checkIn(mint, authority.payer, validator);
+      expect.fail("Should have rejected a second check-in");
+    } catch (error) {
+      expect(error.message).to.include("TicketAlreadyUsed");
+    }
+  });
+
+  it('Rejects duplicate validators', async () => {
+    const collectionMint = await createCollection();
+    const validator = Keypair.generate().publicKey;
+    try {
+      await client.setTicketConfig(collectionMint, false, [validator, validator]);
+      expect.fail("Should have rejected the duplicate");
+    } catch (error) {
+      expect(error.message).to.include("InvalidTicketConfig");
+    }
+  });
+});
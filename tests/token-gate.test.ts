@@ -0,0 +1,185 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from '@solana/web3.js';
+
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddress,
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import * as crypto from 'crypto';
+import { setupProgram, ensureInitialized, pda, crossChainTransferAccounts } from './helpers';
+
+describe('token-gated bridging', () => {
+  const { provider, program, authority } = setupProgram();
+
+  const destinationChainId = 1; // Ethereum
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const crossChainConfigPda = pda([Buffer.from("cross_chain_config")]);
+  const blocklistPda = pda([Buffer.from("blocklist")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+  const cpiAllowlistPda = pda([Buffer.from("cpi_allowlist")]);
+  const destinationChainConfigPda = pda([
+    Buffer.from("chain_config"),
+    new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8),
+  ]);
+
+  before(async () => {
+    await ensureInitialized();
+
+    // Register the destination chain if an earlier test hasn't already
+    try {
+      await program.account.chainConfig.fetch(destinationChainConfigPda);
+    } catch (error) {
+      const auditLog = await program.account.auditLog.fetch(auditLogPda);
+      await program.methods
+        .registerChain(new anchor.BN(destinationChainId))
+        .accounts({
+          programState: programStatePda,
+          chainConfig: destinationChainConfigPda,
+          cpiAllowlist: cpiAllowlistPda,
+          auditLog: auditLogPda,
+          auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .rpc();
+    }
+  });
+
+  const mintNft = async () => {
+    const mint = Keypair.generate();
+    const tokenAccount = await getAssociatedTokenAddress(mint.publicKey, authority.publicKey);
+    const nftMetadataPda = pda([Buffer.from("nft_metadata"), mint.publicKey.toBytes()]);
+
+    await program.methods
+      .mintNft("https://example.com/token-gated.json", "Token Gated NFT", "TGATE", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: blocklistPda,
+        collectionConfig: null,
+        mint: mint.publicKey,
+        tokenAccount: tokenAccount,
+        nftMetadata: nftMetadataPda,
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+
+    return { mint: mint.publicKey, tokenAccount, nftMetadataPda };
+  };
+
+  const transferOut = async (
+    nft: Awaited<ReturnType<typeof mintNft>>,
+    nonce: anchor.BN,
+    gateTokenAccount: PublicKey | null = null
+  ) => {
+    return program.methods
+      .crossChainTransfer(new anchor.BN(destinationChainId), crypto.randomBytes(20), nonce)
+      .accounts(
+        crossChainTransferAccounts(
+          { mint: nft.mint, tokenAccount: nft.tokenAccount, owner: authority.publicKey, destinationChainId, nonce },
+          { gateTokenAccount }
+        )
+      )
+      .rpc();
+  };
+
+  const nextNonce = async () => {
+    const config = await program.account.crossChainConfig.fetch(crossChainConfigPda);
+    return anchor.BN.max(config.nonceCounter.addn(1), new anchor.BN(Date.now()));
+  };
+
+  const auditAccounts = async () => {
+    const auditLog = await program.account.auditLog.fetch(auditLogPda);
+    return {
+      programState: programStatePda,
+      cpiAllowlist: cpiAllowlistPda,
+      auditLog: auditLogPda,
+      auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+      authority: authority.publicKey,
+      systemProgram: SystemProgram.programId,
+      instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+    };
+  };
+
+  const setGlobalGate = async (gate: object | null) => {
+    await program.methods
+      .setTokenGate(gate)
+      .accounts({ ...(await auditAccounts()), crossChainConfig: crossChainConfigPda })
+      .rpc();
+  };
+
+  const setChainGate = async (gate: object | null) => {
+    await program.methods
+      .setChainTokenGate(new anchor.BN(destinationChainId), gate)
+      .accounts({ ...(await auditAccounts()), chainConfig: destinationChainConfigPda })
+      .rpc();
+  };
+
+  const createGateToken = async (balance: number) => {
+    const mint = await createMint(provider.connection, authority.payer, authority.publicKey, null, 0);
+    const account = await getOrCreateAssociatedTokenAccount(provider.connection, authority.payer, mint, authority.publicKey);
+    if (balance > 0) {
+      await mintTo(provider.connection, authority.payer, mint, account.address, authority.publicKey, balance);
+    }
+    return { mint, account: account.address };
+  };
+
+  after(async () => {
+    await setChainGate(null);
+    await setGlobalGate(null);
+  });
+
+  it('Requires the global minimum balance of the gate token', async () => {
+    const gate = await createGateToken(4);
+    await setGlobalGate({ mint: gate.mint, minAmount: new anchor.BN(5) });
+
+    for (const account of [null, gate.account]) {
+      try {
+        await transferOut(await mintNft(), await nextNonce(), account);
+        expect.fail("Should have required five gate tokens");
+      } catch (error) {
+        expect(error.message).to.include("TokenGateNotMet");
+      }
+    }
+
+    await mintTo(provider.connection, authority.payer, gate.mint, gate.account, authority.publicKey, 1);
+    await transferOut(await mintNft(), await nextNonce(), gate.account);
+  });
+
+  it('Lets a chain gate override the global gate', async () => {
+    const globalGate = await createGateToken(0);
+    const chainGate = await createGateToken(1);
+    await setGlobalGate({ mint: globalGate.mint, minAmount: new anchor.BN(1) });
+    await setChainGate({ mint: chainGate.mint, minAmount: new anchor.BN(1) });
+
+    // Only the chain's gate applies to transfers to that chain
+    await transferOut(await mintNft(), await nextNonce(), chainGate.account);
+
+    await setChainGate(null);
+    try {
+      await transferOut(await mintNft(), await nextNonce(), chainGate.account);
+      expect.fail("Should have fallen back to the global gate");
+    } catch (error) {
+      expect(error.message).to.include("TokenGateNotMet");
+    }
+  });
+});
@@ -0,0 +1,116 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  Keypair,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from '@solana/web3.js';
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddress,
+} from '@solana/spl-token';
+
+import { expect } from 'chai';
+import { ChainId } from '../client/src/types';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('transfer builder', () => {
+  const { provider, program, authority, client } = setupProgram();
+
+  const destinationChainId = ChainId.Polygon;
+  const recipient = '0x742d35Cc6634C0532925a3b844Bc454e4438f44e';
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+  const destinationChainConfigPda = pda([
+    Buffer.from("chain_config"),
+    new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8),
+  ]);
+
+  before(async () => {
+    await ensureInitialized();
+
+    // Register the destination chain if an earlier test hasn't already
+    try {
+      await program.account.chainConfig.fetch(destinationChainConfigPda);
+    } catch (error) {
+      const auditLog = await program.account.auditLog.fetch(auditLogPda);
+      await program.methods
+        .registerChain(new anchor.BN(destinationChainId))
+        .accounts({
+          programState: programStatePda,
+          chainConfig: destinationChainConfigPda,
+          cpiAllowlist: pda([Buffer.from("cpi_allowlist")]),
+          auditLog: auditLogPda,
+          auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .rpc();
+    }
+  });
+
+  const mintNft = async () => {
+    const mint = Keypair.generate();
+    await program.methods
+      .mintNft("https://example.com/builder.json", "Builder NFT", "BLD", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        collectionConfig: null,
+        mint: mint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, authority.publicKey),
+        nftMetadata: pda([Buffer.from("nft_metadata"), mint.publicKey.toBytes()]),
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+    return mint.publicKey;
+  };
+
+  it('Builds a transfer from the mint, destination and recipient alone', async () => {
+    const mint = await mintNft();
+    const { transaction, transferRecord, nonce } = await client.transfer(mint)
+      .toChain(ChainId.Polygon)
+      .recipient(recipient)
+      .build();
+
+    expect(transaction.feePayer!.equals(authority.publicKey)).to.be.true;
+    const config = await program.account.crossChainConfig.fetch(pda([Buffer.from("cross_chain_config")]));
+    expect(nonce).to.be.greaterThan(config.nonceCounter.toNumber());
+
+    await provider.sendAndConfirm(transaction);
+
+    const record = await program.account.crossChainTransfer.fetch(transferRecord);
+    expect(record.destinationChainId.toNumber()).to.equal(destinationChainId);
+    expect(Buffer.from(record.recipientAddress).toString('hex')).to.equal(recipient.slice(2).toLowerCase());
+    expect((await program.account.nftMetadata.fetch(pda([Buffer.from("nft_metadata"), mint.toBytes()]))).isLocked)
+      .to.be.true;
+  });
+
+  it('Refuses to build without a destination or recipient', async () => {
+    const mint = await mintNft();
+    for (const builder of [client.transfer(mint).recipient(recipient), client.transfer(mint).toChain(ChainId.Polygon)]) {
+      try {
+        await builder.build();
+        expect.fail("Should have required a destination and recipient");
+      } catch (error) {
+        expect(error.message).to.include("destination chain and a recipient");
+      }
+    }
+
+    try {
+      await client.transfer(Keypair.generate().publicKey).toChain(ChainId.Polygon).recipient(recipient).build();
+      expect.fail("Should have rejected a mint the program doesn't know");
+    } catch (error) {
+      expect(error.message).to.include("is not a Universal NFT");
+    }
+  });
+});
@@ -0,0 +1,192 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  Keypair,
+  SystemProgram,
+  Ed25519Program,
+  LAMPORTS_PER_SOL,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from '@solana/spl-token';
+
+import { expect } from 'chai';
+import * as crypto from 'crypto';
+import { TransferPermit, signTransferPermit, transferPermitMessage } from '../client/src/permit';
+import { setupProgram, ensureInitialized, pda, SOLANA_CHAIN_ID } from './helpers';
+
+describe('transfers with permit', () => {
+  const { provider, program, authority } = setupProgram();
+  const sponsor = Keypair.generate();
+
+  const destinationChainId = 1; // Ethereum
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const crossChainConfigPda = pda([Buffer.from("cross_chain_config")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+  const cpiAllowlistPda = pda([Buffer.from("cpi_allowlist")]);
+  const destinationChainConfigPda = pda([
+    Buffer.from("chain_config"),
+    new anchor.BN(destinationChainId).toArrayLike(Buffer, "le", 8),
+  ]);
+
+  before(async () => {
+    await ensureInitialized();
+
+    try {
+      await program.account.chainConfig.fetch(destinationChainConfigPda);
+    } catch (error) {
+      const auditLog = await program.account.auditLog.fetch(auditLogPda);
+      await program.methods
+        .registerChain(new anchor.BN(destinationChainId))
+        .accounts({
+          programState: programStatePda,
+          chainConfig: destinationChainConfigPda,
+          cpiAllowlist: cpiAllowlistPda,
+          auditLog: auditLogPda,
+          auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .rpc();
+    }
+
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(sponsor.publicKey, LAMPORTS_PER_SOL)
+    );
+  });
+
+  const mintPermit = async (deadlineOffset = 600): Promise<TransferPermit> => {
+    const mint = Keypair.generate();
+    await program.methods
+      .mintNft("https://example.com/permit.json", "Permit NFT", "PRMT", true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        collectionConfig: null,
+        mint: mint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, authority.publicKey),
+        nftMetadata: pda([Buffer.from("nft_metadata"), mint.publicKey.toBytes()]),
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+
+    const config = await program.account.crossChainConfig.fetch(crossChainConfigPda);
+    return {
+      mint: mint.publicKey,
+      owner: authority.publicKey,
+      destinationChainId,
+      recipientAddress: crypto.randomBytes(20),
+      nonce: Math.max(config.nonceCounter.toNumber() + 1, Date.now()),
+      deadline: Math.floor(Date.now() / 1000) + deadlineOffset,
+    };
+  };
+
+  // The Ed25519 instruction a sponsor builds from the owner's signature over `signed`
+  const ownerSignature = (signed: TransferPermit) => {
+    const message = transferPermitMessage(program.programId, SOLANA_CHAIN_ID, signed);
+    return Ed25519Program.createInstructionWithPublicKey({
+      publicKey: signed.owner.toBytes(),
+      message,
+      signature: signTransferPermit(authority.payer, message),
+    });
+  };
+
+  // Submitted and signed by the sponsor alone
+  const submit = async (permit: TransferPermit, ed25519Instruction = ownerSignature(permit)) => {
+    const nonce = new anchor.BN(permit.nonce);
+    return program.methods
+      .crossChainTransferWithPermit(
+        new anchor.BN(destinationChainId),
+        permit.recipientAddress,
+        nonce,
+        new anchor.BN(permit.deadline)
+      )
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: crossChainConfigPda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        optOutRegistry: pda([Buffer.from("opt_out_registry")]),
+        destinationChainConfig: destinationChainConfigPda,
+        nftMetadata: pda([Buffer.from("nft_metadata"), permit.mint.toBytes()]),
+        collectionConfig: null,
+        collectionLedger: pda([
+          Buffer.from("collection_ledger"),
+          new anchor.BN(SOLANA_CHAIN_ID).toArrayLike(Buffer, "le", 8),
+        ]),
+        transferRecord: pda([Buffer.from("cross_chain_transfer"), permit.mint.toBytes(), nonce.toArrayLike(Buffer, "le", 8)]),
+        ownerTransferIndex: pda([Buffer.from("owner_transfer_index"), permit.owner.toBytes()]),
+        pendingTransfers: pda([Buffer.from("pending_transfers")]),
+        mint: permit.mint,
+        tokenAccount: await getAssociatedTokenAddress(permit.mint, permit.owner),
+        owner: permit.owner,
+        credential: null,
+        gateTokenAccount: null,
+        stakeRecord: pda([Buffer.from("stake_record"), permit.mint.toBytes()]),
+        feeTreasury: null,
+        payerFeeAccount: null,
+        treasuryFeeAccount: null,
+        feeReserve: pda([Buffer.from("fee_reserve")]),
+        payer: sponsor.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([ed25519Instruction])
+      .signers([sponsor])
+      .rpc();
+  };
+
+  it('Transfers on the owner signature with the sponsor paying', async () => {
+    const permit = await mintPermit();
+    await submit(permit);
+
+    const record = await program.account.crossChainTransfer.fetch(
+      pda([Buffer.from("cross_chain_transfer"), permit.mint.toBytes(), new anchor.BN(permit.nonce).toArrayLike(Buffer, "le", 8)])
+    );
+    expect(record.originalOwner.toBase58()).to.equal(authority.publicKey.toBase58());
+    expect(record.feePayer.toBase58()).to.equal(sponsor.publicKey.toBase58());
+    expect((await program.account.nftMetadata.fetch(pda([Buffer.from("nft_metadata"), permit.mint.toBytes()]))).isLocked)
+      .to.be.true;
+  });
+
+  it('Rejects an expired permit', async () => {
+    const permit = await mintPermit(-60);
+    try {
+      await submit(permit);
+      expect.fail("Should have rejected the expired permit");
+    } catch (error) {
+      expect(error.message).to.include("PermitExpired");
+    }
+  });
+
+  it('Rejects a permit not signed by the owner or for other arguments', async () => {
+    const permit = await mintPermit();
+    const attempts = [
+      // A valid signature, but by someone other than the owner
+      () => submit(permit, Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: Keypair.generate().secretKey,
+        message: transferPermitMessage(program.programId, SOLANA_CHAIN_ID, permit),
+      })),
+      // The owner signed one recipient; the sponsor submits another
+      () => submit({ ...permit, recipientAddress: crypto.randomBytes(20) }, ownerSignature(permit)),
+    ];
+    for (const attempt of attempts) {
+      try {
+        await attempt();
+        expect.fail("Should have rejected the permit");
+      } catch (error) {
+        expect(error.message).to.include("InvalidOwnerSignature");
+      }
+    }
+
+    // The untouched permit still goes through
+    await submit(permit);
+  });
+});
@@ -201,6 +201,7 @@ describe('universal-nft', () => {
       .rpc();
 
     // Now verify ownership
+    const sequenceBefore = (await program.account.programState.fetch(programStatePda)).eventSequence;
     const verifyTx = await program.methods
       .verifyOwnership(mintKeypair.publicKey)
       .accounts({
@@ -216,6 +217,16 @@ describe('universal-nft', () => {
 
     // The transaction completing successfully means ownership was verified
     expect(verifyTx).to.be.a('string');
+
+    // The owner's NFTs can be found by filtering on the fixed current_owner offset
+    const owned = await program.account.nftMetadata.all([
+      { memcmp: { offset: 40, bytes: owner.publicKey.toBase58() } },
+    ]);
+    expect(owned.map(({ account }) => account.mint.toString())).to.include(mintKeypair.publicKey.toString());
+
+    // Its event took the next sequence number
+    const programState = await program.account.programState.fetch(programStatePda);
+    expect(programState.eventSequence.toNumber()).to.equal(sequenceBefore.toNumber() + 1);
   });
 
   it('Handles cross-chain transfer initiation', async () => {
@@ -307,6 +318,26 @@ describe('universal-nft', () => {
         mint: mintKeypair.publicKey,
         tokenAccount: tokenAccount,
         owner: owner.publicKey,
+        ownerTransferIndex: PublicKey.findProgramAddressSync(
+            [Buffer.from("owner_transfer_index"), owner.publicKey.toBytes()],
+            program.programId
+          )[0],
+        pendingTransfers: PublicKey.findProgramAddressSync(
+            [Buffer.from("pending_transfers")],
+            program.programId
+          )[0],
+        credential: null,
+        gateTokenAccount: null,
+        stakeRecord: PublicKey.findProgramAddressSync(
+          [Buffer.from("stake_record"), mintKeypair.publicKey.toBytes()],
+          program.programId
+        )[0],
+        localizedMetadata: null,
+        feeTreasury: null,
+        payerFeeAccount: null,
+        treasuryFeeAccount: null,
+        feeReserve: PublicKey.findProgramAddressSync([Buffer.from("fee_reserve")], program.programId)[0],
+          payer: owner.publicKey,
         gatewayProgram: gatewayAddress,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
@@ -0,0 +1,97 @@
+import * as anchor from '@coral-xyz/anchor';
+import {
+  Keypair,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from '@solana/spl-token';
+
+import { expect } from 'chai';
+import { setupProgram, ensureInitialized, pda } from './helpers';
+
+describe('validation limits', () => {
+  const { provider, program, authority } = setupProgram();
+
+  const defaults = { maxUriLen: 200, maxNameLen: 32, maxSymbolLen: 10, maxSignatureLen: 128 };
+
+  const programStatePda = pda([Buffer.from("program_state")]);
+  const auditLogPda = pda([Buffer.from("audit_log")]);
+
+  const setLimits = async (limits: typeof defaults) => {
+    const auditLog = await program.account.auditLog.fetch(auditLogPda);
+    return program.methods
+      .setValidationLimits(limits)
+      .accounts({
+        programState: programStatePda,
+        crossChainConfig: pda([Buffer.from("cross_chain_config")]),
+        cpiAllowlist: pda([Buffer.from("cpi_allowlist")]),
+        auditLog: auditLogPda,
+        auditEntry: pda([Buffer.from("audit_entry"), auditLog.entryCount.toArrayLike(Buffer, "le", 8)]),
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .rpc();
+  };
+
+  const mintNft = async (symbol: string) => {
+    const mint = Keypair.generate();
+    return program.methods
+      .mintNft("https://example.com/limits.json", "Limits NFT", symbol, true)
+      .accounts({
+        programState: programStatePda,
+        blocklist: pda([Buffer.from("blocklist")]),
+        collectionConfig: null,
+        mint: mint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, authority.publicKey),
+        nftMetadata: pda([Buffer.from("nft_metadata"), mint.publicKey.toBytes()]),
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+  };
+
+  before(async () => {
+    await ensureInitialized();
+  });
+
+  after(async () => {
+    await setLimits(defaults);
+  });
+
+  it('Enforces tightened limits on mint', async () => {
+    await setLimits({ ...defaults, maxSymbolLen: 4 });
+    expect((await program.account.programState.fetch(programStatePda)).validationLimits.maxSymbolLen).to.equal(4);
+
+    await mintNft("LIMT");
+    try {
+      await mintNft("LIMIT");
+      expect.fail("Should have rejected a symbol over the limit");
+    } catch (error) {
+      expect(error.message).to.include("InvalidMetadataUri");
+    }
+
+    const auditLog = await program.account.auditLog.fetch(auditLogPda);
+    const entry = await program.account.auditEntry.fetch(
+      pda([Buffer.from("audit_entry"), auditLog.entryCount.subn(1).toArrayLike(Buffer, "le", 8)])
+    );
+    expect(entry.action).to.deep.equal({ setValidationLimits: {} });
+  });
+
+  it('Rejects limits of zero or past what accounts can store', async () => {
+    for (const limits of [{ ...defaults, maxNameLen: 0 }, { ...defaults, maxUriLen: 201 }]) {
+      try {
+        await setLimits(limits);
+        expect.fail("Should have rejected the limits");
+      } catch (error) {
+        expect(error.message).to.include("InvalidValidationLimits");
+      }
+    }
+  });
+});